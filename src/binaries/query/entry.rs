@@ -41,6 +41,7 @@ use databend_query::servers::MySQLTlsConfig;
 use databend_query::servers::Server;
 use databend_query::servers::ShutdownHandle;
 use databend_query::GlobalServices;
+use databend_storages_common_table_meta::meta::FORMAT_VERSIONS;
 use log::info;
 
 pub async fn run_cmd(conf: &InnerConfig) -> Result<bool> {
@@ -50,6 +51,16 @@ pub async fn run_cmd(conf: &InnerConfig) -> Result<bool> {
             println!("version: {}", *QUERY_SEMVER);
             println!("min-compatible-metasrv-version: {}", MIN_METASRV_SEMVER);
         }
+        Some(Commands::FormatVersions) => {
+            for versions in FORMAT_VERSIONS {
+                println!(
+                    "{}: supported {}, latest {}",
+                    versions.kind,
+                    versions.supported_list(),
+                    versions.latest()
+                );
+            }
+        }
         Some(Commands::Local {
             query,
             output_format,
@@ -44,6 +44,10 @@ pub enum ProfileStatisticsName {
     SpillReadBytes,
     SpillReadTime,
     RuntimeFilterPruneParts,
+    ScanFilterDecodedBytes,
+    ScanRemainDecodedBytes,
+    FragmentCacheHits,
+    FragmentCacheMisses,
     MemoryUsage,
 }
 
@@ -236,6 +240,34 @@ pub fn get_statistics_desc() -> Arc<BTreeMap<ProfileStatisticsName, ProfileDesc>
                 unit: StatisticsUnit::Count,
                 plain_statistics: true,
             }),
+            (ProfileStatisticsName::ScanFilterDecodedBytes, ProfileDesc {
+                display_name: "bytes decoded for filter columns",
+                desc: "The bytes of the filter columns decoded before the other columns are materialized",
+                index: ProfileStatisticsName::ScanFilterDecodedBytes as usize,
+                unit: StatisticsUnit::Bytes,
+                plain_statistics: true,
+            }),
+            (ProfileStatisticsName::ScanRemainDecodedBytes, ProfileDesc {
+                display_name: "bytes decoded for remaining columns",
+                desc: "The bytes of the remaining columns decoded for the blocks with rows passing the filter",
+                index: ProfileStatisticsName::ScanRemainDecodedBytes as usize,
+                unit: StatisticsUnit::Bytes,
+                plain_statistics: true,
+            }),
+            (ProfileStatisticsName::FragmentCacheHits, ProfileDesc {
+                display_name: "fragment result cache hits",
+                desc: "The number of query fragments served from the fragment result cache",
+                index: ProfileStatisticsName::FragmentCacheHits as usize,
+                unit: StatisticsUnit::Count,
+                plain_statistics: true,
+            }),
+            (ProfileStatisticsName::FragmentCacheMisses, ProfileDesc {
+                display_name: "fragment result cache misses",
+                desc: "The number of cacheable query fragments executed because their result is not cached",
+                index: ProfileStatisticsName::FragmentCacheMisses as usize,
+                unit: StatisticsUnit::Count,
+                plain_statistics: true,
+            }),
             (ProfileStatisticsName::MemoryUsage, ProfileDesc {
                 display_name: "memory usage",
                 desc: "The real time memory usage",
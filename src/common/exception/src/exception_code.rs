@@ -127,6 +127,22 @@ build_exceptions! {
     InvalidTimestamp(1080),
     InvalidClusterKeys(1081),
     UnknownFragmentExchange(1082),
+    // The same fragment of a query is prepared more than once on a node.
+    FragmentAlreadyExists(1083),
+    // The consumer of an exchange stream falls behind the sender beyond the lag limits.
+    ExchangeConsumerTooSlow(1084),
+    // A fragment stream is requested again after it was handed out to a consumer.
+    StreamAlreadyConsumed(1085),
+    // The node is shutting down and does not accept new queries or streams.
+    ServerShuttingDown(1086),
+    // The node runs and queues as many partial queries as its limits allow.
+    TooManyStages(1087),
+    // The packets of an exchange stream cannot be spilled to or read back from the local disk.
+    ExchangeSpillFailed(1088),
+    // The consumer of a full exchange stream stops polling it beyond the idle timeout.
+    ExchangeConsumerTimeout(1089),
+    // A query is started with the id of a query which is still running.
+    QueryWithSameIdIsAlreadyRunning(1090),
     TenantIsEmpty(1101),
     IndexOutOfBounds(1102),
     LayoutError(1103),
@@ -172,6 +188,14 @@ build_exceptions! {
     ///
     /// For example: try to with 3 columns into a table with 4 columns.
     TableSchemaMismatch(1303),
+    /// CheckConstraintViolation is used when ingested rows violate a CHECK constraint.
+    ///
+    /// For example: insert `-1` into a table with `CHECK (amount > 0)`.
+    CheckConstraintViolation(1304),
+    /// ColumnReferencedByCheckConstraint is used when an ALTER would break a CHECK constraint.
+    ///
+    /// For example: drop the column `amount` of a table with `CHECK (amount > 0)`.
+    ColumnReferencedByCheckConstraint(1305),
 
     // License related errors starts here
 
@@ -271,6 +295,7 @@ build_exceptions! {
     // Cluster error codes.
     ClusterUnknownNode(2401),
     ClusterNodeAlreadyExists(2402),
+    ClusterNodeDraining(2403),
 
     // Stage error codes.
     UnknownStage(2501),
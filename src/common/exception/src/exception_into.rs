@@ -19,6 +19,7 @@ use std::fmt::Formatter;
 use std::sync::Arc;
 
 use geozero::error::GeozeroError;
+use tonic::metadata::MetadataValue;
 
 use crate::exception::ErrorCodeBacktrace;
 use crate::exception_backtrace::capture;
@@ -295,6 +296,9 @@ impl From<tonic::Status> for ErrorCode {
             tonic::Code::Unknown => {
                 let details = status.details();
                 if details.is_empty() {
+                    if let Some(error_code) = error_code_from_metadata(&status) {
+                        return error_code;
+                    }
                     return ErrorCode::UnknownException(format!(
                         "{}, source: {:?}",
                         status.message(),
@@ -302,7 +306,10 @@ impl From<tonic::Status> for ErrorCode {
                     ));
                 }
                 match serde_json::from_slice::<SerializedError>(details) {
-                    Err(error) => ErrorCode::from(error),
+                    Err(error) => match error_code_from_metadata(&status) {
+                        Some(error_code) => error_code,
+                        None => ErrorCode::from(error),
+                    },
                     Ok(serialized_error) => match serialized_error.backtrace.len() {
                         0 => ErrorCode::create(
                             serialized_error.code,
@@ -327,26 +334,64 @@ impl From<tonic::Status> for ErrorCode {
                     },
                 }
             }
-            _ => ErrorCode::Unimplemented(status.to_string()),
+            _ => match error_code_from_metadata(&status) {
+                Some(error_code) => error_code,
+                None => ErrorCode::Internal(status.to_string()),
+            },
         }
     }
 }
 
+// The error code is also kept in the metadata of the status, in case the details
+// are dropped or replaced on the way, e.g. by a proxy between the nodes.
+const ERROR_CODE_METADATA_KEY: &str = "x-databend-error-code";
+const ERROR_NAME_METADATA_KEY: &str = "x-databend-error-name";
+const ERROR_BACKTRACE_METADATA_KEY: &str = "x-databend-error-backtrace-bin";
+
+fn error_code_from_metadata(status: &tonic::Status) -> Option<ErrorCode> {
+    let metadata = status.metadata();
+    let code = metadata
+        .get(ERROR_CODE_METADATA_KEY)?
+        .to_str()
+        .ok()?
+        .parse::<u16>()
+        .ok()?;
+    let name = metadata
+        .get(ERROR_NAME_METADATA_KEY)
+        .and_then(|name| name.to_str().ok())
+        .unwrap_or("Internal")
+        .to_string();
+    let backtrace = metadata
+        .get_bin(ERROR_BACKTRACE_METADATA_KEY)
+        .and_then(|backtrace| backtrace.to_bytes().ok())
+        .map(|backtrace| String::from_utf8_lossy(&backtrace).to_string())
+        .filter(|backtrace| !backtrace.is_empty())
+        .map(|backtrace| ErrorCodeBacktrace::Serialized(Arc::new(backtrace)));
+
+    Some(ErrorCode::create(
+        code,
+        name,
+        status.message().to_string(),
+        String::new(),
+        None,
+        backtrace,
+    ))
+}
+
 impl From<ErrorCode> for tonic::Status {
     fn from(err: ErrorCode) -> Self {
+        let mut backtrace = err.backtrace_str();
+        backtrace.truncate(2 * 1024);
+
         let error_json = serde_json::to_vec::<SerializedError>(&SerializedError {
             code: err.code(),
             name: err.name(),
             message: err.message(),
             span: err.span(),
-            backtrace: {
-                let mut str = err.backtrace_str();
-                str.truncate(2 * 1024);
-                str
-            },
+            backtrace: backtrace.clone(),
         });
 
-        match error_json {
+        let mut status = match error_json {
             Ok(serialized_error_json) => {
                 // Code::Internal will be used by h2, if something goes wrong internally.
                 // To distinguish from that, we use Code::Unknown here
@@ -357,6 +402,19 @@ impl From<ErrorCode> for tonic::Status {
                 )
             }
             Err(error) => tonic::Status::unknown(error.to_string()),
+        };
+
+        let metadata = status.metadata_mut();
+        metadata.insert(ERROR_CODE_METADATA_KEY, MetadataValue::from(err.code()));
+        if let Ok(name) = MetadataValue::try_from(err.name().as_str()) {
+            metadata.insert(ERROR_NAME_METADATA_KEY, name);
+        }
+        if !backtrace.is_empty() {
+            metadata.insert_bin(
+                ERROR_BACKTRACE_METADATA_KEY,
+                MetadataValue::from_bytes(backtrace.as_bytes()),
+            );
         }
+        status
     }
 }
@@ -136,3 +136,35 @@ fn test_from_and_to_status() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_from_status_metadata() -> anyhow::Result<()> {
+    let e = ErrorCode::BadArguments("divided by zero");
+    let status: Status = e.into();
+    assert_eq!(
+        Some("1006"),
+        status
+            .metadata()
+            .get("x-databend-error-code")
+            .and_then(|code| code.to_str().ok())
+    );
+
+    {
+        // The details are dropped, the error code is recovered from the metadata.
+        let status =
+            Status::with_metadata(Code::Internal, status.message(), status.metadata().clone());
+        let e: ErrorCode = status.into();
+        assert_eq!(ErrorCode::BAD_ARGUMENTS, e.code());
+        assert_eq!("BadArguments", e.name());
+        assert_eq!("divided by zero", e.message());
+    }
+
+    {
+        // The status which is not from an ErrorCode.
+        let e: ErrorCode = Status::unavailable("connection refused").into();
+        assert_eq!(ErrorCode::INTERNAL, e.code());
+        assert!(e.message().contains("connection refused"));
+    }
+
+    Ok(())
+}
@@ -210,7 +210,10 @@ impl ConnectionFactory {
     }
 
     fn client_tls_config(conf: &RpcClientTlsConfig) -> Result<ClientTlsConfig> {
-        let server_root_ca_cert = std::fs::read(conf.rpc_tls_server_root_ca_cert.as_str())?;
+        let path = conf.rpc_tls_server_root_ca_cert.as_str();
+        let server_root_ca_cert = std::fs::read(path).map_err(|e| {
+            ErrorCode::TLSConfigurationFailure(format!("cannot read root ca cert \"{path}\": {e}"))
+        })?;
         let server_root_ca_cert = Certificate::from_pem(server_root_ca_cert);
 
         let tls = ClientTlsConfig::new()
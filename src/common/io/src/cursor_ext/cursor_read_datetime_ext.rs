@@ -18,10 +18,9 @@ use std::io::Read;
 use chrono::DateTime;
 use chrono::Datelike;
 use chrono::Duration;
-use chrono::FixedOffset;
 use chrono::LocalResult;
 use chrono::NaiveDate;
-use chrono::Offset;
+use chrono::NaiveDateTime;
 use chrono::TimeZone;
 use chrono_tz::Tz;
 use databend_common_exception::ErrorCode;
@@ -42,9 +41,8 @@ pub trait BufferReadDateTimeExt {
         &mut self,
         tz: &Tz,
         buf: &mut Vec<u8>,
-        dt: &DateTime<Tz>,
+        dt: &NaiveDateTime,
         west_tz: bool,
-        calc_offset: impl Fn(i64, i64, &DateTime<Tz>) -> Result<DateTime<Tz>>,
     ) -> Result<DateTime<Tz>>;
 }
 
@@ -140,10 +138,8 @@ where T: AsRef<[u8]>
                 return Ok(DateTimeResType::Datetime(less_1000(dt)));
             }
 
-            let dt = unwrap_local_time(tz, &d, &mut times)?;
-
             // ms .microseconds
-            let dt = if self.ignore_byte(b'.') {
+            let nanos = if self.ignore_byte(b'.') {
                 buf.clear();
                 let size = self.keep_read(&mut buf, |f| f.is_ascii_digit());
                 if size == 0 {
@@ -158,28 +154,17 @@ where T: AsRef<[u8]>
                 } else {
                     scales /= (size as i64 - 9) * 10
                 }
-                dt.checked_add_signed(Duration::nanoseconds(scales))
-                    .unwrap()
+                scales
             } else {
-                dt
+                0
             };
 
             // Timezone 2022-02-02T03:00:03.123[z/Z[+/-08:00]]
+            // The time with a timezone is converted from that timezone, it may not exist or be
+            // ambiguous in `tz` because of DST.
             buf.clear();
-            let calc_offset = |current_tz_sec: i64, val_tz_sec: i64, dt: &DateTime<Tz>| {
-                let offset = (current_tz_sec - val_tz_sec) * 1000 * 1000;
-                let mut ts = dt.timestamp_micros();
-                ts += offset;
-                // TODO: need support timestamp_micros in chrono-0.4.22/src/offset/mod.rs
-                // use like tz.timestamp_nanos()
-                let (mut secs, mut micros) = (ts / 1_000_000, ts % 1_000_000);
-                if ts < 0 {
-                    secs -= 1;
-                    micros += 1_000_000;
-                }
-                Ok(tz.timestamp_opt(secs, (micros as u32) * 1000).unwrap())
-            };
             if self.ignore(|b| b == b'z' || b == b'Z') {
+                let dt = naive_time(&d, &times)? + Duration::nanoseconds(nanos);
                 // ISO 8601 The Z on the end means UTC (that is, an offset-from-UTC of zero hours-minutes-seconds).
                 if dt.year() < 1000 {
                     Ok(DateTimeResType::Datetime(
@@ -191,31 +176,22 @@ where T: AsRef<[u8]>
                         ),
                     ))
                 } else {
-                    let current_tz = dt.offset().fix().local_minus_utc();
-                    Ok(DateTimeResType::Datetime(calc_offset(
-                        current_tz.into(),
-                        0,
-                        &dt,
-                    )?))
+                    Ok(DateTimeResType::Datetime(tz.from_utc_datetime(&dt)))
                 }
             } else if self.ignore_byte(b'+') {
-                Ok(DateTimeResType::Datetime(self.parse_time_offset(
-                    tz,
-                    &mut buf,
-                    &dt,
-                    false,
-                    calc_offset,
-                )?))
+                let dt = naive_time(&d, &times)? + Duration::nanoseconds(nanos);
+                Ok(DateTimeResType::Datetime(
+                    self.parse_time_offset(tz, &mut buf, &dt, false)?,
+                ))
             } else if self.ignore_byte(b'-') {
-                Ok(DateTimeResType::Datetime(self.parse_time_offset(
-                    tz,
-                    &mut buf,
-                    &dt,
-                    true,
-                    calc_offset,
-                )?))
+                let dt = naive_time(&d, &times)? + Duration::nanoseconds(nanos);
+                Ok(DateTimeResType::Datetime(
+                    self.parse_time_offset(tz, &mut buf, &dt, true)?,
+                ))
             } else {
                 // only datetime part
+                let dt = unwrap_local_time(tz, &d, &mut times)?;
+                let dt = dt.checked_add_signed(Duration::nanoseconds(nanos)).unwrap();
                 Ok(DateTimeResType::Datetime(less_1000(dt)))
             }
         } else {
@@ -258,15 +234,13 @@ where T: AsRef<[u8]>
         &mut self,
         tz: &Tz,
         buf: &mut Vec<u8>,
-        dt: &DateTime<Tz>,
+        dt: &NaiveDateTime,
         west_tz: bool,
-        calc_offset: impl Fn(i64, i64, &DateTime<Tz>) -> Result<DateTime<Tz>>,
     ) -> Result<DateTime<Tz>> {
         fn get_hour_minute_offset(
             tz: &Tz,
-            dt: &DateTime<Tz>,
+            dt: &NaiveDateTime,
             west_tz: bool,
-            calc_offset: &impl Fn(i64, i64, &DateTime<Tz>) -> Result<DateTime<Tz>>,
             hour_offset: i32,
             minute_offset: i32,
         ) -> Result<DateTime<Tz>, ErrorCode> {
@@ -281,15 +255,12 @@ where T: AsRef<[u8]>
                             .unwrap(),
                     ))
                 } else {
-                    let current_tz_sec = dt.offset().fix().local_minus_utc();
-                    let mut val_tz_sec =
-                        FixedOffset::east_opt(hour_offset * 3600 + minute_offset * 60)
-                            .unwrap()
-                            .local_minus_utc();
+                    let mut val_tz_sec = hour_offset * 3600 + minute_offset * 60;
                     if west_tz {
                         val_tz_sec = -val_tz_sec;
                     }
-                    calc_offset(current_tz_sec.into(), val_tz_sec.into(), dt)
+                    let utc = *dt - Duration::seconds(val_tz_sec.into());
+                    Ok(tz.from_utc_datetime(&utc))
                 }
             } else {
                 Err(ErrorCode::BadBytes(format!(
@@ -315,16 +286,9 @@ where T: AsRef<[u8]>
                         let minute_offset: i32 =
                             lexical_core::FromLexical::from_lexical(buf.as_slice()).unwrap();
                         // max utc: 14:00, min utc: 00:00
-                        get_hour_minute_offset(
-                            tz,
-                            dt,
-                            west_tz,
-                            &calc_offset,
-                            hour_offset,
-                            minute_offset,
-                        )
+                        get_hour_minute_offset(tz, dt, west_tz, hour_offset, minute_offset)
                     } else {
-                        get_hour_minute_offset(tz, dt, west_tz, &calc_offset, hour_offset, 0)
+                        get_hour_minute_offset(tz, dt, west_tz, hour_offset, 0)
                     }
                 } else {
                     Err(ErrorCode::BadBytes(format!(
@@ -343,14 +307,7 @@ where T: AsRef<[u8]>
                 buf.clear();
                 // max utc: 14:00, min utc: 00:00
                 if (0..15).contains(&hour_offset) {
-                    get_hour_minute_offset(
-                        tz,
-                        dt,
-                        west_tz,
-                        &calc_offset,
-                        hour_offset,
-                        minute_offset,
-                    )
+                    get_hour_minute_offset(tz, dt, west_tz, hour_offset, minute_offset)
                 } else {
                     Err(ErrorCode::BadBytes(format!(
                         "Invalid Timezone Offset: The hour offset '{}' is outside the valid range. Expected range is [00-14] within a timezone gap of [-14:00, +14:00].",
@@ -365,6 +322,11 @@ where T: AsRef<[u8]>
     }
 }
 
+fn naive_time(d: &NaiveDate, times: &[u32]) -> Result<NaiveDateTime> {
+    d.and_hms_opt(times[0], times[1], times[2])
+        .ok_or_else(|| ErrorCode::BadBytes(format!("Invalid time provided in times: {:?}", times)))
+}
+
 // Can not directly unwrap, because of DST.
 // e.g.
 // set timezone='Europe/London';
@@ -67,6 +67,26 @@ fn test_read_timestamp_text() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_read_timestamp_text_with_offset() -> Result<()> {
+    // 2024-03-10 02:30:00 doesn't exist and 2023-11-05 01:30:00 is ambiguous in New York, the
+    // times with an offset are not converted from the local time.
+    let mut reader = Cursor::new(
+        "2024-03-10 02:30:00Z,2023-11-05 01:30:00.5+00:00,2024-03-10 07:30:00+05:00".as_bytes(),
+    );
+    let tz = Tz::America__New_York;
+    let expected = vec![1710037800000000, 1699147800500000, 1710037800000000];
+    let mut res = vec![];
+    for _ in 0..expected.len() {
+        if let DateTimeResType::Datetime(time) = reader.read_timestamp_text(&tz, false)? {
+            res.push(time.timestamp_micros());
+        }
+        reader.ignore_byte(b',');
+    }
+    assert_eq!(res, expected);
+    Ok(())
+}
+
 #[test]
 fn test_read_date_text() -> Result<()> {
     let mut reader = Cursor::new("2009-01-01,1000-01-01,2023-03-03,2022-02-02,2009-01-01 3:2:1.123,2009-01-01 0:00:00,2009-01-01 00:00:00.123,2009-01-01 00:00:00.123456,0002-03-03T00:01:02,2022-03-04T00:01:02+08:00,2022-03-04T00:01:02-08:00,0000-00-00,0000-00-00 00:00:00,0001-01-01 00:00:00,2020-01-01T11:11:11Z,2009-01-03 00:00:00,2020-01-01T11:11:11.123Z,2055-02-03 10:00:20.234+08:00,2055-02-03 10:00:20.234-08:00,1022-05-16T03:25:02.000000+08:00,2055-01-01".as_bytes());
@@ -14,6 +14,7 @@
 
 use std::sync::LazyLock;
 
+use crate::register_counter;
 use crate::register_counter_family;
 use crate::register_histogram_family_in_milliseconds;
 use crate::Counter;
@@ -39,6 +40,7 @@ const METRIC_QUERY_SCAN_PARTITIONS: &str = "query_scan_partitions";
 const METRIC_QUERY_TOTAL_PARTITIONS: &str = "query_total_partitions";
 const METRIC_QUERY_RESULT_ROWS: &str = "query_result_rows";
 const METRIC_QUERY_RESULT_BYTES: &str = "query_result_bytes";
+const METRIC_QUERY_PROFILE_EVICTIONS: &str = "query_profile_evictions";
 
 pub static QUERY_START: LazyLock<Family<VecLabels, Counter>> =
     LazyLock::new(|| register_counter_family(METRIC_QUERY_START));
@@ -76,3 +78,5 @@ pub static QUERY_RESULT_ROWS: LazyLock<Family<VecLabels, Counter>> =
     LazyLock::new(|| register_counter_family(METRIC_QUERY_RESULT_ROWS));
 pub static QUERY_RESULT_BYTES: LazyLock<Family<VecLabels, Counter>> =
     LazyLock::new(|| register_counter_family(METRIC_QUERY_RESULT_BYTES));
+pub static QUERY_PROFILE_EVICTIONS: LazyLock<Counter> =
+    LazyLock::new(|| register_counter(METRIC_QUERY_PROFILE_EVICTIONS));
@@ -13,12 +13,16 @@
 // limitations under the License.
 
 use std::sync::LazyLock;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 use crate::register_counter;
 use crate::register_counter_family;
+use crate::register_gauge_family;
 use crate::register_histogram_family_in_milliseconds;
 use crate::Counter;
 use crate::Family;
+use crate::Gauge;
 use crate::Histogram;
 use crate::VecLabels;
 
@@ -60,6 +64,14 @@ pub static EXCHANGE_READ_COUNT: LazyLock<Counter> =
     LazyLock::new(|| register_counter("transform_exchange_read_count"));
 pub static EXCHANGE_READ_BYTES: LazyLock<Counter> =
     LazyLock::new(|| register_counter("transform_exchange_read_bytes"));
+pub static EXCHANGE_STREAM_SENT_ROWS: LazyLock<Family<VecLabels, Counter>> =
+    LazyLock::new(|| register_counter_family("transform_exchange_stream_sent_rows"));
+pub static EXCHANGE_STREAM_SENT_BYTES: LazyLock<Family<VecLabels, Counter>> =
+    LazyLock::new(|| register_counter_family("transform_exchange_stream_sent_bytes"));
+pub static EXCHANGE_STREAM_QUEUED_BLOCKS: LazyLock<Family<VecLabels, Gauge>> =
+    LazyLock::new(|| register_gauge_family("transform_exchange_stream_queued_blocks"));
+pub static EXCHANGE_STREAM_LAST_SEND_SECONDS: LazyLock<Family<VecLabels, Gauge>> =
+    LazyLock::new(|| register_gauge_family("transform_exchange_stream_last_send_seconds"));
 
 pub fn metrics_inc_aggregate_partial_spill_count() {
     let labels = &vec![("spill", "aggregate_partial_spill".to_string())];
@@ -156,6 +168,54 @@ pub fn metrics_inc_exchange_read_bytes(c: usize) {
     EXCHANGE_READ_BYTES.inc_by(c as u64);
 }
 
+/// The metrics of an exchange stream sent to another node, labeled by the query, the fragment
+/// and the target node.
+///
+/// The labels are removed from the registry when it's dropped, so the finished or canceled
+/// queries don't leave their streams in the metrics.
+pub struct ExchangeStreamMetrics {
+    labels: VecLabels,
+}
+
+impl ExchangeStreamMetrics {
+    pub fn create(query_id: &str, fragment: usize, target: &str) -> ExchangeStreamMetrics {
+        ExchangeStreamMetrics {
+            labels: vec![
+                ("query_id", query_id.to_string()),
+                ("fragment", fragment.to_string()),
+                ("target", target.to_string()),
+            ],
+        }
+    }
+
+    pub fn record_send(&self, rows: usize, bytes: usize, queued_blocks: usize) {
+        EXCHANGE_STREAM_SENT_ROWS
+            .get_or_create(&self.labels)
+            .inc_by(rows as u64);
+        EXCHANGE_STREAM_SENT_BYTES
+            .get_or_create(&self.labels)
+            .inc_by(bytes as u64);
+        EXCHANGE_STREAM_QUEUED_BLOCKS
+            .get_or_create(&self.labels)
+            .set(queued_blocks as i64);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        EXCHANGE_STREAM_LAST_SEND_SECONDS
+            .get_or_create(&self.labels)
+            .set(now.as_secs() as i64);
+    }
+}
+
+impl Drop for ExchangeStreamMetrics {
+    fn drop(&mut self) {
+        EXCHANGE_STREAM_SENT_ROWS.remove(&self.labels);
+        EXCHANGE_STREAM_SENT_BYTES.remove(&self.labels);
+        EXCHANGE_STREAM_QUEUED_BLOCKS.remove(&self.labels);
+        EXCHANGE_STREAM_LAST_SEND_SECONDS.remove(&self.labels);
+    }
+}
+
 // Sort spill metrics
 pub fn metrics_inc_sort_spill_count() {
     let labels = &vec![("spill", "sort_spill".to_string())];
@@ -70,6 +70,28 @@ impl fmt::Display for Node {
     }
 }
 
+/// The lifecycle of a query node in the cluster.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Default)]
+pub enum NodeStatus {
+    /// New queries and stages can be placed on the node.
+    #[default]
+    Running,
+    /// The node refuses new work and waits for the running queries to finish.
+    Draining,
+    /// Nothing is running on the node anymore, it's safe to shut it down.
+    Drained,
+}
+
+impl fmt::Display for NodeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NodeStatus::Running => write!(f, "running"),
+            NodeStatus::Draining => write!(f, "draining"),
+            NodeStatus::Drained => write!(f, "drained"),
+        }
+    }
+}
+
 /// Query node
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Default)]
 #[serde(default)]
@@ -79,6 +101,7 @@ pub struct NodeInfo {
     pub version: u32,
     pub flight_address: String,
     pub binary_version: String,
+    pub status: NodeStatus,
 }
 
 impl NodeInfo {
@@ -94,9 +117,15 @@ impl NodeInfo {
             version: 0,
             flight_address,
             binary_version,
+            status: NodeStatus::Running,
         }
     }
 
+    /// Whether new stages can be placed on this node.
+    pub fn is_schedulable(&self) -> bool {
+        self.status == NodeStatus::Running
+    }
+
     pub fn ip_port(&self) -> Result<(String, u16), AddrParseError> {
         let addr = SocketAddr::from_str(&self.flight_address)?;
 
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use databend_common_meta_types::NodeInfo;
+use databend_common_meta_types::NodeStatus;
 
 #[test]
 fn test_node_info_ip_port() -> anyhow::Result<()> {
@@ -22,6 +23,7 @@ fn test_node_info_ip_port() -> anyhow::Result<()> {
         version: 1,
         flight_address: "1.2.3.4:123".to_string(),
         binary_version: "v0.8-binary-version".to_string(),
+        status: NodeStatus::Running,
     };
 
     let (ip, port) = n.ip_port()?;
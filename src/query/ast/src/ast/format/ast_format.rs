@@ -722,6 +722,7 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                 ExplainKind::Plan => "Plan",
                 ExplainKind::Memo(_) => "Memo",
                 ExplainKind::Join => "Join",
+                ExplainKind::Lineage => "Lineage",
                 ExplainKind::AnalyzePlan => "Analyze",
             },
             if options.is_empty() {
@@ -736,6 +737,7 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                                 ExplainOption::Verbose(true) => Some("Verbose"),
                                 ExplainOption::Logical(true) => Some("Logical"),
                                 ExplainOption::Optimized(true) => Some("Optimized"),
+                                ExplainOption::Format(ExplainFormat::Json) => Some("Json"),
                                 _ => None,
                             }
                         })
@@ -1028,6 +1030,13 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_system(&mut self, stmt: &'ast SystemStmt) {
+        let name = format!("System {}", stmt.action);
+        let format_ctx = AstFormatContext::new(name);
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
     fn visit_set_variable(
         &mut self,
         is_global: bool,
@@ -1409,12 +1418,19 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
 
     fn visit_create_table_source(&mut self, source: &'ast CreateTableSource) {
         match source {
-            CreateTableSource::Columns(columns) => {
-                let mut children = Vec::with_capacity(columns.len());
+            CreateTableSource::Columns(columns, constraints) => {
+                let mut children = Vec::with_capacity(columns.len() + constraints.len());
                 for column in columns.iter() {
                     self.visit_column_definition(column);
                     children.push(self.children.pop().unwrap());
                 }
+                for constraint in constraints.iter() {
+                    self.visit_expr(&constraint.expr);
+                    let expr_child = self.children.pop().unwrap();
+                    let name = format!("CheckConstraint {}", constraint.name);
+                    let format_ctx = AstFormatContext::with_children(name, 1);
+                    children.push(FormatTreeNode::with_children(format_ctx, vec![expr_child]));
+                }
                 let name = "ColumnsDefinition".to_string();
                 let format_ctx = AstFormatContext::with_children(name, children.len());
                 let node = FormatTreeNode::with_children(format_ctx, children);
@@ -1532,6 +1548,18 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                 let action_format_ctx = AstFormatContext::new(action_name);
                 FormatTreeNode::new(action_format_ctx)
             }
+            AlterTableAction::AddConstraint { constraint } => {
+                self.visit_expr(&constraint.expr);
+                let expr_child = self.children.pop().unwrap();
+                let action_name = format!("Action Add constraint {}", constraint.name);
+                let action_format_ctx = AstFormatContext::with_children(action_name, 1);
+                FormatTreeNode::with_children(action_format_ctx, vec![expr_child])
+            }
+            AlterTableAction::DropConstraint { name } => {
+                let action_name = format!("Action Drop constraint {}", name);
+                let action_format_ctx = AstFormatContext::new(action_name);
+                FormatTreeNode::new(action_format_ctx)
+            }
             AlterTableAction::AlterTableClusterKey { cluster_by } => {
                 let mut cluster_by_children = Vec::with_capacity(cluster_by.len());
                 for cluster_by_expr in cluster_by.iter() {
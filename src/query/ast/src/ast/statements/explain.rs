@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt::Display;
+use std::fmt::Formatter;
+
 use derive_visitor::Drive;
 use derive_visitor::DriveMut;
 
@@ -35,6 +38,9 @@ pub enum ExplainKind {
 
     Join,
 
+    // Explain the column lineage of the statement
+    Lineage,
+
     // Explain analyze plan
     AnalyzePlan,
 }
@@ -44,4 +50,33 @@ pub enum ExplainOption {
     Verbose(#[drive(skip)] bool),
     Logical(#[drive(skip)] bool),
     Optimized(#[drive(skip)] bool),
+    Format(#[drive(skip)] ExplainFormat),
+}
+
+/// Output format of the EXPLAIN statements.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExplainFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl Display for ExplainFormat {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ExplainFormat::Text => write!(f, "TEXT"),
+            ExplainFormat::Json => write!(f, "JSON"),
+        }
+    }
+}
+
+impl Display for ExplainOption {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ExplainOption::Verbose(v) => write!(f, "VERBOSE = {v}"),
+            ExplainOption::Logical(v) => write!(f, "LOGICAL = {v}"),
+            ExplainOption::Optimized(v) => write!(f, "OPTIMIZED = {v}"),
+            ExplainOption::Format(format) => write!(f, "FORMAT = {format}"),
+        }
+    }
 }
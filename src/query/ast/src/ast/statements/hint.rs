@@ -24,6 +24,7 @@ use crate::ast::Identifier;
 #[derive(Debug, Clone, PartialEq, Drive, DriveMut)]
 pub struct Hint {
     pub hints_list: Vec<HintItem>,
+    pub optimizer_hints: Vec<OptimizerHint>,
 }
 
 #[derive(Debug, Clone, PartialEq, Drive, DriveMut)]
@@ -32,6 +33,32 @@ pub struct HintItem {
     pub expr: Expr,
 }
 
+/// A hint other than `SET_VAR`, like `BROADCAST_JOIN(t)` or `NO_PUSHDOWN`.
+///
+/// The hints are validated by the binder, unknown hints are ignored with a warning.
+#[derive(Debug, Clone, PartialEq, Drive, DriveMut)]
+pub struct OptimizerHint {
+    pub name: Identifier,
+    pub args: Option<Vec<Identifier>>,
+}
+
+impl Display for OptimizerHint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(args) = &self.args {
+            write!(f, "(")?;
+            for (i, arg) in args.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", arg)?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
 impl Display for Hint {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "/*+ ")?;
@@ -42,6 +69,9 @@ impl Display for Hint {
             write!(f, "{}", hint.expr)?;
             write!(f, ") ")?;
         }
+        for hint in &self.optimizer_hints {
+            write!(f, "{} ", hint)?;
+        }
         write!(f, "*/")
     }
 }
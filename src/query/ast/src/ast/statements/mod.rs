@@ -37,6 +37,7 @@ mod show;
 mod stage;
 mod statement;
 mod stream;
+mod system;
 mod table;
 mod task;
 mod udf;
@@ -71,6 +72,7 @@ pub use show::*;
 pub use stage::*;
 pub use statement::*;
 pub use stream::*;
+pub use system::*;
 pub use table::*;
 pub use task::*;
 pub use udf::*;
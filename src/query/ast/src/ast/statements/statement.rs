@@ -43,6 +43,7 @@ pub enum Statement {
         query: Box<Statement>,
     },
     ExplainAnalyze {
+        options: Vec<ExplainOption>,
         query: Box<Statement>,
     },
 
@@ -82,6 +83,7 @@ pub enum Statement {
         #[drive(skip)]
         object_id: String,
     },
+    System(SystemStmt),
 
     SetVariable {
         #[drive(skip)]
@@ -370,26 +372,7 @@ impl Display for Statement {
             } => {
                 write!(f, "EXPLAIN")?;
                 if !options.is_empty() {
-                    write!(
-                        f,
-                        "({})",
-                        options
-                            .iter()
-                            .map(|opt| {
-                                match opt {
-                                    ExplainOption::Verbose(v) => {
-                                        format!("VERBOSE = {}", v)
-                                    }
-                                    ExplainOption::Logical(v) => {
-                                        format!("LOGICAL = {}", v)
-                                    }
-                                    ExplainOption::Optimized(v) => {
-                                        format!("OPTIMIZED = {}", v)
-                                    }
-                                }
-                            })
-                            .join(", ")
-                    )?;
+                    write!(f, "({})", options.iter().join(", "))?;
                 }
                 match *kind {
                     ExplainKind::Ast(_) => write!(f, " AST")?,
@@ -403,11 +386,16 @@ impl Display for Statement {
                     ExplainKind::AnalyzePlan => write!(f, " ANALYZE")?,
                     ExplainKind::Join => write!(f, " JOIN")?,
                     ExplainKind::Memo(_) => write!(f, " MEMO")?,
+                    ExplainKind::Lineage => write!(f, " LINEAGE")?,
                 }
                 write!(f, " {query}")?;
             }
-            Statement::ExplainAnalyze { query } => {
-                write!(f, "EXPLAIN ANALYZE {query}")?;
+            Statement::ExplainAnalyze { options, query } => {
+                write!(f, "EXPLAIN")?;
+                if !options.is_empty() {
+                    write!(f, "({})", options.iter().join(", "))?;
+                }
+                write!(f, " ANALYZE {query}")?;
             }
             Statement::Query(query) => write!(f, "{query}")?,
             Statement::Insert(insert) => write!(f, "{insert}")?,
@@ -477,6 +465,7 @@ impl Display for Statement {
                 }
                 write!(f, " '{object_id}'")?;
             }
+            Statement::System(stmt) => write!(f, "{stmt}")?,
             Statement::SetVariable {
                 is_global,
                 variable,
@@ -230,7 +230,7 @@ impl Display for AttachTableStmt {
 
 #[derive(Debug, Clone, PartialEq, Drive, DriveMut)]
 pub enum CreateTableSource {
-    Columns(Vec<ColumnDefinition>),
+    Columns(Vec<ColumnDefinition>, Vec<CheckConstraintDefinition>),
     Like {
         catalog: Option<Identifier>,
         database: Option<Identifier>,
@@ -241,9 +241,13 @@ pub enum CreateTableSource {
 impl Display for CreateTableSource {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
-            CreateTableSource::Columns(columns) => {
+            CreateTableSource::Columns(columns, constraints) => {
                 write!(f, "(")?;
                 write_comma_separated_list(f, columns)?;
+                if !constraints.is_empty() {
+                    write!(f, ", ")?;
+                    write_comma_separated_list(f, constraints)?;
+                }
                 write!(f, ")")
             }
             CreateTableSource::Like {
@@ -367,6 +371,12 @@ pub enum AlterTableAction {
     DropColumn {
         column: Identifier,
     },
+    AddConstraint {
+        constraint: CheckConstraintDefinition,
+    },
+    DropConstraint {
+        name: Identifier,
+    },
     AlterTableClusterKey {
         cluster_by: Vec<Expr>,
     },
@@ -413,6 +423,12 @@ impl Display for AlterTableAction {
             AlterTableAction::DropColumn { column } => {
                 write!(f, "DROP COLUMN {column}")?;
             }
+            AlterTableAction::AddConstraint { constraint } => {
+                write!(f, "ADD {constraint}")?;
+            }
+            AlterTableAction::DropConstraint { name } => {
+                write!(f, "DROP CONSTRAINT {name}")?;
+            }
             AlterTableAction::AlterTableClusterKey { cluster_by } => {
                 write!(f, "CLUSTER BY ")?;
                 write_comma_separated_list(f, cluster_by)?;
@@ -815,6 +831,19 @@ impl Display for ColumnDefinition {
     }
 }
 
+/// A table level `CONSTRAINT <name> CHECK (<expr>)` definition.
+#[derive(Debug, Clone, PartialEq, Drive, DriveMut)]
+pub struct CheckConstraintDefinition {
+    pub name: Identifier,
+    pub expr: Box<Expr>,
+}
+
+impl Display for CheckConstraintDefinition {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "CONSTRAINT {} CHECK ({})", self.name, self.expr)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Drive, DriveMut)]
 pub enum ModifyColumnAction {
     // (column name id, masking policy name)
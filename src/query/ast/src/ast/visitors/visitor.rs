@@ -427,6 +427,8 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_kill(&mut self, _kill_target: &'ast KillTarget, _object_id: &'ast str) {}
 
+    fn visit_system(&mut self, _stmt: &'ast SystemStmt) {}
+
     fn visit_set_variable(
         &mut self,
         _is_global: bool,
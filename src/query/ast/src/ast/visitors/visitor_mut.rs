@@ -438,6 +438,8 @@ pub trait VisitorMut: Sized {
 
     fn visit_kill(&mut self, _kill_target: &mut KillTarget, _object_id: &mut String) {}
 
+    fn visit_system(&mut self, _stmt: &mut SystemStmt) {}
+
     fn visit_set_variable(
         &mut self,
         _is_global: bool,
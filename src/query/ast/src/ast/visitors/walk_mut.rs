@@ -373,7 +373,7 @@ pub fn walk_statement_mut<V: VisitorMut>(visitor: &mut V, statement: &mut Statem
             options,
             query,
         } => visitor.visit_explain(kind, options, &mut *query),
-        Statement::ExplainAnalyze { query } => visitor.visit_statement(&mut *query),
+        Statement::ExplainAnalyze { query, .. } => visitor.visit_statement(&mut *query),
         Statement::Query(query) => visitor.visit_query(&mut *query),
         Statement::Insert(insert) => visitor.visit_insert(insert),
         Statement::Replace(replace) => visitor.visit_replace(replace),
@@ -401,6 +401,7 @@ pub fn walk_statement_mut<V: VisitorMut>(visitor: &mut V, statement: &mut Statem
             kill_target,
             object_id,
         } => visitor.visit_kill(kill_target, object_id),
+        Statement::System(stmt) => visitor.visit_system(stmt),
         Statement::SetVariable {
             is_global,
             variable,
@@ -368,6 +368,8 @@ impl<'a, I: Iterator<Item = WithSpan<'a, ExprElement>>> PrattParser<I> for ExprP
                 BinaryOperator::Lte => Affix::Infix(Precedence(20), Associativity::Left),
                 BinaryOperator::Like => Affix::Infix(Precedence(20), Associativity::Left),
                 BinaryOperator::NotLike => Affix::Infix(Precedence(20), Associativity::Left),
+                BinaryOperator::ILike => Affix::Infix(Precedence(20), Associativity::Left),
+                BinaryOperator::NotILike => Affix::Infix(Precedence(20), Associativity::Left),
                 BinaryOperator::Regexp => Affix::Infix(Precedence(20), Associativity::Left),
                 BinaryOperator::NotRegexp => Affix::Infix(Precedence(20), Associativity::Left),
                 BinaryOperator::RLike => Affix::Infix(Precedence(20), Associativity::Left),
@@ -1259,6 +1261,8 @@ pub fn binary_op(i: Input) -> IResult<BinaryOperator> {
             value(BinaryOperator::Xor, rule! { XOR }),
             value(BinaryOperator::Like, rule! { LIKE }),
             value(BinaryOperator::NotLike, rule! { NOT ~ LIKE }),
+            value(BinaryOperator::ILike, rule! { ILIKE }),
+            value(BinaryOperator::NotILike, rule! { NOT ~ ILIKE }),
             value(BinaryOperator::Regexp, rule! { REGEXP }),
             value(BinaryOperator::NotRegexp, rule! { NOT ~ REGEXP }),
             value(BinaryOperator::RLike, rule! { RLIKE }),
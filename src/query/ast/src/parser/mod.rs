@@ -40,6 +40,7 @@ pub use parser::parse_expr;
 pub use parser::parse_sql;
 pub use parser::parser_values_with_placeholder;
 pub use parser::run_parser;
+pub use parser::split_sql;
 pub use parser::tokenize_sql;
 
 pub mod token;
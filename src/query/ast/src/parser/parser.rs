@@ -38,6 +38,31 @@ pub fn tokenize_sql(sql: &str) -> Result<Vec<Token>> {
     Tokenizer::new(sql).collect::<Result<Vec<_>>>()
 }
 
+/// Split a SQL string into the text of its statements.
+///
+/// The statements are separated by semicolons, those in string literals, quoted
+/// identifiers and comments are not separators. Empty statements are skipped.
+pub fn split_sql(sql: &str) -> Result<Vec<&str>> {
+    let mut statements = vec![];
+    let mut start = None;
+    let mut end = 0;
+    for token in Tokenizer::new(sql) {
+        let token = token?;
+        match token.kind {
+            TokenKind::SemiColon | TokenKind::EOI => {
+                if let Some(start) = start.take() {
+                    statements.push(&sql[start..end]);
+                }
+            }
+            _ => {
+                start.get_or_insert(token.span.start());
+                end = token.span.end();
+            }
+        }
+    }
+    Ok(statements)
+}
+
 /// Parse a SQL string into `Statement`s.
 #[minitrace::trace]
 pub fn parse_sql(sql_tokens: &[Token], dialect: Dialect) -> Result<(Statement, Option<String>)> {
@@ -63,7 +63,7 @@ pub enum CreateDatabaseOption {
 pub fn statement(i: Input) -> IResult<StatementWithFormat> {
     let explain = map_res(
         rule! {
-            EXPLAIN ~ ( "(" ~ #comma_separated_list1(explain_option) ~ ")" )? ~ ( AST | SYNTAX | PIPELINE | JOIN | GRAPH | FRAGMENTS | RAW | OPTIMIZED | MEMO )? ~ #statement
+            EXPLAIN ~ ( "(" ~ #comma_separated_list1(explain_option) ~ ")" )? ~ ( AST | SYNTAX | PIPELINE | JOIN | GRAPH | FRAGMENTS | RAW | OPTIMIZED | MEMO | LINEAGE )? ~ #statement
         },
         |(_, options, opt_kind, statement)| {
             Ok(Statement::Explain {
@@ -89,6 +89,7 @@ pub fn statement(i: Input) -> IResult<StatementWithFormat> {
                     Some(TokenKind::RAW) => ExplainKind::Raw,
                     Some(TokenKind::OPTIMIZED) => ExplainKind::Optimized,
                     Some(TokenKind::MEMO) => ExplainKind::Memo("".to_string()),
+                    Some(TokenKind::LINEAGE) => ExplainKind::Lineage,
                     None => ExplainKind::Plan,
                     _ => unreachable!(),
                 },
@@ -99,10 +100,17 @@ pub fn statement(i: Input) -> IResult<StatementWithFormat> {
     );
     let explain_analyze = map(
         rule! {
-            EXPLAIN ~ ANALYZE ~ #statement
+            EXPLAIN ~ ( "(" ~ #comma_separated_list1(explain_option) ~ ")" )? ~ ANALYZE ~ VERBOSE? ~ #statement
         },
-        |(_, _, statement)| Statement::ExplainAnalyze {
-            query: Box::new(statement.stmt),
+        |(_, options, _, opt_verbose, statement)| {
+            let mut options = options.map_or(vec![], |(_, opts, _)| opts);
+            if opt_verbose.is_some() {
+                options.push(ExplainOption::Verbose(true));
+            }
+            Statement::ExplainAnalyze {
+                options,
+                query: Box::new(statement.stmt),
+            }
         },
     );
 
@@ -397,6 +405,27 @@ pub fn statement(i: Input) -> IResult<StatementWithFormat> {
         },
     );
 
+    // system drain node 'node1';
+    // system reset query profile;
+    let system_action = alt((
+        map(
+            rule! {
+                DRAIN ~ NODE ~ #literal_string
+            },
+            |(_, _, node)| SystemAction::DrainNode(node),
+        ),
+        value(
+            SystemAction::ResetQueryProfile,
+            rule! { RESET ~ QUERY ~ PROFILE },
+        ),
+    ));
+    let system_stmt = map(
+        rule! {
+            SYSTEM ~ #system_action
+        },
+        |(_, action)| Statement::System(SystemStmt { action }),
+    );
+
     let set_variable = map(
         rule! {
             SET ~ GLOBAL? ~ #ident ~ "=" ~ #subexpr(0)
@@ -1904,7 +1933,7 @@ pub fn statement(i: Input) -> IResult<StatementWithFormat> {
         rule!(
         #map(query, |query| Statement::Query(Box::new(query)))
             | #explain : "`EXPLAIN [PIPELINE | GRAPH] <statement>`"
-            | #explain_analyze : "`EXPLAIN ANALYZE <statement>`"
+            | #explain_analyze : "`EXPLAIN [(<options>)] ANALYZE [VERBOSE] <statement>`"
             | #show_settings : "`SHOW SETTINGS [<show_limit>]`"
             | #show_stages : "`SHOW STAGES`"
             | #show_engines : "`SHOW ENGINES`"
@@ -1914,6 +1943,7 @@ pub fn statement(i: Input) -> IResult<StatementWithFormat> {
             | #show_indexes : "`SHOW INDEXES`"
             | #show_locks : "`SHOW LOCKS [IN ACCOUNT] [WHERE ...]`"
             | #kill_stmt : "`KILL (QUERY | CONNECTION) <object_id>`"
+            | #system_stmt : "`SYSTEM (DRAIN NODE '<node_id>' | RESET QUERY PROFILE)`"
             | #vacuum_temp_files : "VACUUM TEMPORARY FILES [RETAIN number SECONDS|DAYS] [LIMIT number]"
         ),
         // database
@@ -2224,24 +2254,66 @@ pub fn unset_source(i: Input) -> IResult<UnSetSource> {
 pub fn set_var_hints(i: Input) -> IResult<HintItem> {
     map(
         rule! {
-            SET_VAR ~ ^"(" ~ ^#ident ~ ^"=" ~ #subexpr(0) ~ ^")"
+            ( SET_VAR | SET ) ~ ^"(" ~ ^#ident ~ ^"=" ~ #subexpr(0) ~ ^")"
         },
         |(_, _, name, _, expr, _)| HintItem { name, expr },
     )(i)
 }
 
+pub fn optimizer_hint(i: Input) -> IResult<OptimizerHint> {
+    // `LEADING` is a reserved keyword.
+    let leading = map(rule! { LEADING }, |token| Identifier {
+        span: transform_span(&[token.clone()]),
+        name: token.text().to_string(),
+        quote: None,
+    });
+    map(
+        rule! {
+            ( #ident | #leading ) ~ ( "(" ~ ( #ident ~ ","? )* ~ ^")" )?
+        },
+        |(name, args)| OptimizerHint {
+            name,
+            args: args.map(|(_, args, _)| args.into_iter().map(|(arg, _)| arg).collect()),
+        },
+    )(i)
+}
+
 pub fn hint(i: Input) -> IResult<Hint> {
+    enum HintEntry {
+        SetVar(HintItem),
+        Optimizer(OptimizerHint),
+    }
+
+    let hint_entry = alt((
+        map(set_var_hints, HintEntry::SetVar),
+        map(optimizer_hint, HintEntry::Optimizer),
+    ));
     let hint = map(
         rule! {
-            "/*+" ~ #set_var_hints+ ~ "*/"
+            "/*+" ~ #hint_entry+ ~ "*/"
+        },
+        |(_, entries, _)| {
+            let mut hint = Hint {
+                hints_list: vec![],
+                optimizer_hints: vec![],
+            };
+            for entry in entries {
+                match entry {
+                    HintEntry::SetVar(item) => hint.hints_list.push(item),
+                    HintEntry::Optimizer(item) => hint.optimizer_hints.push(item),
+                }
+            }
+            hint
         },
-        |(_, hints_list, _)| Hint { hints_list },
     );
     let invalid_hint = map(
         rule! {
             "/*+" ~ (!"*/" ~ #any_token)* ~ "*/"
         },
-        |_| Hint { hints_list: vec![] },
+        |_| Hint {
+            hints_list: vec![],
+            optimizer_hints: vec![],
+        },
     );
     rule!(#hint|#invalid_hint)(i)
 }
@@ -2661,12 +2733,56 @@ pub fn grant_option(i: Input) -> IResult<PrincipalIdentity> {
     )(i)
 }
 
+pub fn check_constraint_def(i: Input) -> IResult<CheckConstraintDefinition> {
+    map(
+        rule! {
+            CONSTRAINT ~ #ident ~ CHECK ~ ^"(" ~ ^#expr ~ ^")"
+        },
+        |(_, name, _, _, expr, _)| CheckConstraintDefinition {
+            name,
+            expr: Box::new(expr),
+        },
+    )(i)
+}
+
 pub fn create_table_source(i: Input) -> IResult<CreateTableSource> {
-    let columns = map(
+    #[derive(Clone)]
+    enum CreateDefinition {
+        Column(ColumnDefinition),
+        CheckConstraint(CheckConstraintDefinition),
+    }
+
+    let create_def = alt((
+        map(check_constraint_def, CreateDefinition::CheckConstraint),
+        map(column_def, CreateDefinition::Column),
+    ));
+    let columns = map_res(
         rule! {
-            "(" ~ ^#comma_separated_list1(column_def) ~ ^")"
+            "(" ~ ^#comma_separated_list1(create_def) ~ ^")"
+        },
+        |(_, defs, _)| {
+            let mut columns = Vec::with_capacity(defs.len());
+            let mut constraints = vec![];
+            for def in defs {
+                match def {
+                    CreateDefinition::Column(column) => {
+                        if !constraints.is_empty() {
+                            return Err(nom::Err::Failure(ErrorKind::Other(
+                                "column definitions must precede CHECK constraints",
+                            )));
+                        }
+                        columns.push(column);
+                    }
+                    CreateDefinition::CheckConstraint(constraint) => constraints.push(constraint),
+                }
+            }
+            if columns.is_empty() {
+                return Err(nom::Err::Failure(ErrorKind::Other(
+                    "expecting at least one column definition",
+                )));
+            }
+            Ok(CreateTableSource::Columns(columns, constraints))
         },
-        |(_, columns, _)| CreateTableSource::Columns(columns),
     );
     let like = map(
         rule! {
@@ -2848,6 +2964,18 @@ pub fn alter_table_action(i: Input) -> IResult<AlterTableAction> {
         },
         |(_, _, column)| AlterTableAction::DropColumn { column },
     );
+    let add_constraint = map(
+        rule! {
+            ADD ~ #check_constraint_def
+        },
+        |(_, constraint)| AlterTableAction::AddConstraint { constraint },
+    );
+    let drop_constraint = map(
+        rule! {
+            DROP ~ CONSTRAINT ~ #ident
+        },
+        |(_, _, name)| AlterTableAction::DropConstraint { name },
+    );
     let alter_table_cluster_key = map(
         rule! {
             CLUSTER ~ ^BY ~ ^"(" ~ ^#comma_separated_list1(expr) ~ ^")"
@@ -2892,7 +3020,9 @@ pub fn alter_table_action(i: Input) -> IResult<AlterTableAction> {
         | #drop_table_cluster_key
         | #rename_table
         | #rename_column
+        | #add_constraint
         | #add_column
+        | #drop_constraint
         | #drop_column
         | #modify_column
         | #recluster_table
@@ -3702,7 +3832,7 @@ pub fn alter_password_action(i: Input) -> IResult<AlterPasswordAction> {
 }
 
 pub fn explain_option(i: Input) -> IResult<ExplainOption> {
-    map(
+    let flag = map(
         rule! {
             VERBOSE | LOGICAL | OPTIMIZED
         },
@@ -3712,5 +3842,20 @@ pub fn explain_option(i: Input) -> IResult<ExplainOption> {
             OPTIMIZED => ExplainOption::Optimized(true),
             _ => unreachable!(),
         },
+    );
+    let format = map(
+        rule! {
+            FORMAT ~ "="? ~ ( TEXT | JSON )
+        },
+        |(_, _, format)| match &format.kind {
+            TEXT => ExplainOption::Format(ExplainFormat::Text),
+            JSON => ExplainOption::Format(ExplainFormat::Json),
+            _ => unreachable!(),
+        },
+    );
+
+    rule!(
+        #flag
+        | #format
     )(i)
 }
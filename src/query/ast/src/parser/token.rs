@@ -420,6 +420,8 @@ pub enum TokenKind {
     CONNECTION,
     #[token("CONNECTIONS", ignore(ascii_case))]
     CONNECTIONS,
+    #[token("CONSTRAINT", ignore(ascii_case))]
+    CONSTRAINT,
     #[token("CONTENT_TYPE", ignore(ascii_case))]
     CONTENT_TYPE,
     #[token("CHAR", ignore(ascii_case))]
@@ -430,6 +432,8 @@ pub enum TokenKind {
     COLUMNS,
     #[token("CHARACTER", ignore(ascii_case))]
     CHARACTER,
+    #[token("CHECK", ignore(ascii_case))]
+    CHECK,
     #[token("CONFLICT", ignore(ascii_case))]
     CONFLICT,
     #[token("COMPRESSION", ignore(ascii_case))]
@@ -508,6 +512,8 @@ pub enum TokenKind {
     DOY,
     #[token("DOWNLOAD", ignore(ascii_case))]
     DOWNLOAD,
+    #[token("DRAIN", ignore(ascii_case))]
+    DRAIN,
     #[token("DROP", ignore(ascii_case))]
     DROP,
     #[token("DRY", ignore(ascii_case))]
@@ -628,6 +634,8 @@ pub enum TokenKind {
     IDENTIFIED,
     #[token("IF", ignore(ascii_case))]
     IF,
+    #[token("ILIKE", ignore(ascii_case))]
+    ILIKE,
     #[token("IN", ignore(ascii_case))]
     IN,
     #[token("INDEX", ignore(ascii_case))]
@@ -695,6 +703,8 @@ pub enum TokenKind {
     LIKE,
     #[token("LIMIT", ignore(ascii_case))]
     LIMIT,
+    #[token("LINEAGE", ignore(ascii_case))]
+    LINEAGE,
     #[token("LIST", ignore(ascii_case))]
     LIST,
     #[token("LZO", ignore(ascii_case))]
@@ -735,6 +745,8 @@ pub enum TokenKind {
     NETWORK,
     #[token("NDJSON", ignore(ascii_case))]
     NDJSON,
+    #[token("NODE", ignore(ascii_case))]
+    NODE,
     #[token("NO_PASSWORD", ignore(ascii_case))]
     NO_PASSWORD,
     #[token("NONE", ignore(ascii_case))]
@@ -815,6 +827,8 @@ pub enum TokenKind {
     POSITION,
     #[token("PROCESSLIST", ignore(ascii_case))]
     PROCESSLIST,
+    #[token("PROFILE", ignore(ascii_case))]
+    PROFILE,
     #[token("PURGE", ignore(ascii_case))]
     PURGE,
     #[token("QUARTER", ignore(ascii_case))]
@@ -843,6 +857,8 @@ pub enum TokenKind {
     RENAME,
     #[token("REPLACE", ignore(ascii_case))]
     REPLACE,
+    #[token("RESET", ignore(ascii_case))]
+    RESET,
     #[token("RETURN_FAILED_ONLY", ignore(ascii_case))]
     RETURN_FAILED_ONLY,
     #[token("MERGE", ignore(ascii_case))]
@@ -983,6 +999,8 @@ pub enum TokenKind {
     SOUNDS,
     #[token("SYNC", ignore(ascii_case))]
     SYNC,
+    #[token("SYSTEM", ignore(ascii_case))]
+    SYSTEM,
     #[token("STORAGE_TYPE", ignore(ascii_case))]
     STORAGE_TYPE,
     #[token("TABLE", ignore(ascii_case))]
@@ -22,6 +22,7 @@ use databend_common_ast::parser::parse_sql;
 use databend_common_ast::parser::query::*;
 use databend_common_ast::parser::quote::quote_ident;
 use databend_common_ast::parser::quote::unquote_ident;
+use databend_common_ast::parser::split_sql;
 use databend_common_ast::parser::token::*;
 use databend_common_ast::parser::tokenize_sql;
 use databend_common_ast::parser::Backtrace;
@@ -98,11 +99,14 @@ fn test_statement() {
         r#"explain pipeline select a from b;"#,
         r#"explain pipeline select a from t1 ignore_result;"#,
         r#"explain(verbose, logical, optimized) select * from t where a = 1"#,
+        r#"explain(format json) select a from b;"#,
+        r#"explain analyze verbose select a from b;"#,
         r#"describe a;"#,
         r#"describe a format TabSeparatedWithNamesAndTypes;"#,
         r#"CREATE AGGREGATING INDEX idx1 AS SELECT SUM(a), b FROM t1 WHERE b > 3 GROUP BY b;"#,
         r#"CREATE OR REPLACE AGGREGATING INDEX idx1 AS SELECT SUM(a), b FROM t1 WHERE b > 3 GROUP BY b;"#,
         r#"create table a (c decimal(38, 0))"#,
+        r#"create table a (c int, constraint c_positive check (c > 0))"#,
         r#"create table a (c decimal(38))"#,
         r#"create or replace table a (c decimal(38))"#,
         r#"create or replace table a (c int(10) unsigned)"#,
@@ -226,6 +230,8 @@ fn test_statement() {
         r#"ALTER TABLE t RENAME COLUMN a TO b;"#,
         r#"ALTER TABLE t DROP COLUMN b;"#,
         r#"ALTER TABLE t DROP b;"#,
+        r#"ALTER TABLE t ADD CONSTRAINT c_positive CHECK (b > 0);"#,
+        r#"ALTER TABLE t DROP CONSTRAINT c_positive;"#,
         r#"ALTER TABLE t MODIFY COLUMN b SET MASKING POLICY mask;"#,
         r#"ALTER TABLE t MODIFY COLUMN b UNSET MASKING POLICY;"#,
         r#"ALTER TABLE t MODIFY COLUMN a int DEFAULT 1, COLUMN b float;"#,
@@ -553,6 +559,8 @@ fn test_statement() {
         r#"DESC CONNECTION my_conn;"#,
         r#"SHOW CONNECTIONS;"#,
         r#"SHOW LOCKS IN ACCOUNT"#,
+        r#"SYSTEM DRAIN NODE 'node1'"#,
+        r#"SYSTEM RESET QUERY PROFILE"#,
         // pipes
         r#"CREATE PIPE IF NOT EXISTS MyPipe1 AUTO_INGEST = TRUE COMMENT = 'This is test pipe 1' AS COPY INTO MyTable1 FROM '@~/MyStage1' FILE_FORMAT = (TYPE = 'CSV')"#,
         r#"CREATE PIPE pipe1 AS COPY INTO db1.MyTable1 FROM @~/mybucket/data.csv"#,
@@ -943,3 +951,34 @@ fn test_quote() {
         assert_eq!(unquoted, *input, "unquote({}) got {}", quoted, unquoted);
     }
 }
+
+#[test]
+fn test_split_sql() {
+    let cases: &[(&str, &[&str])] = &[
+        ("", &[]),
+        (" ; ;", &[]),
+        ("select 1", &["select 1"]),
+        ("select 1;select 2;", &["select 1", "select 2"]),
+        (
+            "create table t(a string);\n insert into t values ('a;b') ; select count(*) from t",
+            &[
+                "create table t(a string)",
+                "insert into t values ('a;b')",
+                "select count(*) from t",
+            ],
+        ),
+        ("select \"a;b\" from t; select 2", &[
+            "select \"a;b\" from t",
+            "select 2",
+        ]),
+        (
+            "select 1 -- first; not a statement\n; /* second; */ select 2",
+            &["select 1", "select 2"],
+        ),
+    ];
+    for (sql, want) in cases {
+        assert_eq!(split_sql(sql).unwrap(), *want, "sql: {sql}");
+    }
+
+    assert!(split_sql("select 'unterminated; select 1").is_err());
+}
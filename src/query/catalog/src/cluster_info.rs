@@ -18,7 +18,10 @@ use databend_common_meta_types::NodeInfo;
 
 pub struct Cluster {
     pub local_id: String,
+    /// The nodes which new stages can be placed on.
     pub nodes: Vec<Arc<NodeInfo>>,
+    /// The nodes which are draining or already drained, they are not used to plan new queries.
+    pub draining_nodes: Vec<Arc<NodeInfo>>,
 }
 
 impl Cluster {
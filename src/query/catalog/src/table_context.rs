@@ -19,6 +19,7 @@ use std::collections::HashSet;
 use std::fmt::Display;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use std::time::SystemTime;
 
 use dashmap::DashMap;
@@ -101,6 +102,45 @@ impl Display for ProcessInfoState {
     }
 }
 
+/// The summary of a partial query of a distributed query executed by this node, kept after
+/// it is finished, see `system.flight_stages`.
+#[derive(Debug, Clone)]
+pub struct FinishedStageInfo {
+    pub query_id: String,
+    pub stage_id: usize,
+    pub status: FinishedStageStatus,
+    /// The wall time from the start of the execution to the end of it, zero if the stage
+    /// was never started.
+    pub duration: Duration,
+    pub finished_time: SystemTime,
+    pub streams: Vec<FinishedStreamInfo>,
+}
+
+/// The data sent by a stage to another node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinishedStreamInfo {
+    pub target: String,
+    pub sent_rows: usize,
+    pub sent_bytes: usize,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FinishedStageStatus {
+    Finished,
+    Cancelled,
+    Failed,
+}
+
+impl Display for FinishedStageStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FinishedStageStatus::Finished => write!(f, "Finished"),
+            FinishedStageStatus::Cancelled => write!(f, "Cancelled"),
+            FinishedStageStatus::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StageAttachment {
     pub location: String,
@@ -152,6 +192,8 @@ pub trait TableContext: Send + Sync {
 
     fn attach_query_str(&self, kind: QueryKind, query: String);
     fn get_query_str(&self) -> String;
+    /// Attach the column lineage of the write statement, recorded in the query log.
+    fn attach_query_lineage(&self, lineage: serde_json::Value);
 
     fn get_fragment_id(&self) -> usize;
     async fn get_catalog(&self, catalog_name: &str) -> Result<Arc<dyn Catalog>>;
@@ -182,6 +224,7 @@ pub trait TableContext: Send + Sync {
     fn get_cluster(&self) -> Arc<Cluster>;
     fn get_processes_info(&self) -> Vec<ProcessInfo>;
     fn get_queries_profile(&self) -> HashMap<String, Vec<Arc<Profile>>>;
+    fn get_finished_stages(&self) -> Vec<FinishedStageInfo>;
     fn get_stage_attachment(&self) -> Option<StageAttachment>;
     fn get_last_query_id(&self, index: i32) -> String;
     fn get_query_id_history(&self) -> HashSet<String>;
@@ -31,6 +31,7 @@ pub fn codegen_register() {
             #![allow(unused_parens)]
             #![allow(unused_variables)]
             #![allow(clippy::redundant_closure)]
+            use databend_common_arrow::arrow::bitmap::Bitmap;
             use crate::FunctionEval;
             use crate::Function;
             use crate::EvalContext;
@@ -624,6 +625,9 @@ pub fn codegen_register() {
                         let and_validity = {and_validity};
                         let validity = ctx.validity.as_ref().map(|valid| valid & (&and_validity)).unwrap_or(and_validity);
                         ctx.validity = Some(validity.clone());
+                        if validity.unset_bits() == validity.len() {{
+                            return all_null_column(validity, ctx);
+                        }}
                         let column = func({func_arg} ctx).into_column().unwrap();
                         Value::Column(NullableColumn {{ column, validity }})
                     }}"
@@ -651,6 +655,24 @@ pub fn codegen_register() {
         .unwrap();
     }
 
+    writeln!(
+        source,
+        "
+            // All the rows are NULL, the function is not evaluated.
+            fn all_null_column<O: ArgType>(validity: Bitmap, ctx: &EvalContext) -> Value<NullableType<O>> {{
+                let mut builder = O::create_builder(validity.len(), ctx.generics);
+                for _ in 0..validity.len() {{
+                    O::push_default(&mut builder);
+                }}
+                Value::Column(NullableColumn {{
+                    column: O::build_column(builder),
+                    validity,
+                }})
+            }}
+        "
+    )
+    .unwrap();
+
     // Write `combine_nullable_x_arg`.
     for n_args in 1..=MAX_ARGS {
         let arg_generics_bound = (0..n_args)
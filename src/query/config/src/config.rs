@@ -152,6 +152,8 @@ pub struct Config {
 pub enum Commands {
     #[default]
     Ver,
+    /// Print the versions of the persisted table meta this binary reads and writes.
+    FormatVersions,
     Local {
         #[clap(long, short = 'q', default_value_t)]
         query: String,
@@ -1399,6 +1401,26 @@ pub struct QueryConfig {
     #[clap(long, value_name = "VALUE", default_value = "256")]
     pub max_active_sessions: u64,
 
+    /// The max number of partial queries of distributed queries executed by this node
+    /// at the same time, 0 means unlimited.
+    #[clap(long, value_name = "VALUE", default_value = "0")]
+    pub max_concurrent_stages: u64,
+
+    /// The max number of partial queries waiting for `max_concurrent_stages`, the
+    /// others are rejected. 0 means they are rejected without waiting.
+    #[clap(long, value_name = "VALUE", default_value = "64")]
+    pub max_queued_stages: u64,
+
+    /// The max seconds a partial query waits in the queue of `max_concurrent_stages`, the
+    /// query is aborted after that. 0 means it waits until a slot is free.
+    #[clap(long, value_name = "VALUE", default_value = "60")]
+    pub max_stage_queued_secs: u64,
+
+    /// The max number of the finished partial queries whose summaries are kept by this
+    /// node for `system.flight_stages`, the oldest ones are forgotten first.
+    #[clap(long, value_name = "VALUE", default_value = "1024")]
+    pub max_finished_stages: u64,
+
     /// The max total memory in bytes that can be used by this process.
     #[clap(long, value_name = "VALUE", default_value = "0")]
     pub max_server_memory_usage: u64,
@@ -1486,6 +1508,12 @@ pub struct QueryConfig {
     #[clap(long, value_name = "VALUE", default_value = "0")]
     pub rpc_client_timeout_secs: u64,
 
+    /// The directory of the exchange streams spilled to the local disk, empty means
+    /// `databend_exchange_spill` in the temporary directory of the system. Each query spills
+    /// to a directory of its own in it, the ones left behind are removed at startup.
+    #[clap(long, value_name = "VALUE", default_value_t)]
+    pub exchange_spill_dir: String,
+
     /// Table engine memory enabled
     #[clap(long,  value_name = "VALUE",value_parser = clap::value_parser!(bool), default_value = "true")]
     pub table_engine_memory_enabled: bool,
@@ -1679,6 +1707,10 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             mysql_tls_server_cert: self.mysql_tls_server_cert,
             mysql_tls_server_key: self.mysql_tls_server_key,
             max_active_sessions: self.max_active_sessions,
+            max_concurrent_stages: self.max_concurrent_stages,
+            max_queued_stages: self.max_queued_stages,
+            max_stage_queued_secs: self.max_stage_queued_secs,
+            max_finished_stages: self.max_finished_stages,
             max_server_memory_usage: self.max_server_memory_usage,
             max_memory_limit_enabled: self.max_memory_limit_enabled,
             clickhouse_http_handler_host: self.clickhouse_http_handler_host,
@@ -1704,6 +1736,7 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             rpc_tls_query_server_root_ca_cert: self.rpc_tls_query_server_root_ca_cert,
             rpc_tls_query_service_domain_name: self.rpc_tls_query_service_domain_name,
             rpc_client_timeout_secs: self.rpc_client_timeout_secs,
+            exchange_spill_dir: self.exchange_spill_dir,
             table_engine_memory_enabled: self.table_engine_memory_enabled,
             shutdown_wait_timeout_ms: self.shutdown_wait_timeout_ms,
             max_query_log_size: self.max_query_log_size,
@@ -1758,6 +1791,10 @@ impl From<InnerQueryConfig> for QueryConfig {
             mysql_tls_server_cert: inner.mysql_tls_server_cert,
             mysql_tls_server_key: inner.mysql_tls_server_key,
             max_active_sessions: inner.max_active_sessions,
+            max_concurrent_stages: inner.max_concurrent_stages,
+            max_queued_stages: inner.max_queued_stages,
+            max_stage_queued_secs: inner.max_stage_queued_secs,
+            max_finished_stages: inner.max_finished_stages,
             max_server_memory_usage: inner.max_server_memory_usage,
             max_memory_limit_enabled: inner.max_memory_limit_enabled,
 
@@ -1788,6 +1825,7 @@ impl From<InnerQueryConfig> for QueryConfig {
             rpc_tls_query_server_root_ca_cert: inner.rpc_tls_query_server_root_ca_cert,
             rpc_tls_query_service_domain_name: inner.rpc_tls_query_service_domain_name,
             rpc_client_timeout_secs: inner.rpc_client_timeout_secs,
+            exchange_spill_dir: inner.exchange_spill_dir,
             table_engine_memory_enabled: inner.table_engine_memory_enabled,
             shutdown_wait_timeout_ms: inner.shutdown_wait_timeout_ms,
             max_query_log_size: inner.max_query_log_size,
@@ -161,6 +161,10 @@ pub struct QueryConfig {
     pub mysql_tls_server_cert: String,
     pub mysql_tls_server_key: String,
     pub max_active_sessions: u64,
+    pub max_concurrent_stages: u64,
+    pub max_queued_stages: u64,
+    pub max_stage_queued_secs: u64,
+    pub max_finished_stages: u64,
     pub max_server_memory_usage: u64,
     pub max_memory_limit_enabled: bool,
     pub clickhouse_http_handler_host: String,
@@ -189,6 +193,7 @@ pub struct QueryConfig {
     pub rpc_tls_query_server_root_ca_cert: String,
     pub rpc_tls_query_service_domain_name: String,
     pub rpc_client_timeout_secs: u64,
+    pub exchange_spill_dir: String,
     /// Table engine memory enabled
     pub table_engine_memory_enabled: bool,
     /// Graceful shutdown timeout
@@ -246,6 +251,10 @@ impl Default for QueryConfig {
             mysql_tls_server_cert: "".to_string(),
             mysql_tls_server_key: "".to_string(),
             max_active_sessions: 256,
+            max_concurrent_stages: 0,
+            max_queued_stages: 64,
+            max_stage_queued_secs: 60,
+            max_finished_stages: 1024,
             max_server_memory_usage: 0,
             max_memory_limit_enabled: false,
             clickhouse_http_handler_host: "127.0.0.1".to_string(),
@@ -270,6 +279,7 @@ impl Default for QueryConfig {
             rpc_tls_query_server_root_ca_cert: "".to_string(),
             rpc_tls_query_service_domain_name: "localhost".to_string(),
             rpc_client_timeout_secs: 0,
+            exchange_spill_dir: "".to_string(),
             table_engine_memory_enabled: true,
             shutdown_wait_timeout_ms: 5000,
             max_query_log_size: 10_000,
@@ -0,0 +1,175 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[macro_use]
+extern crate criterion;
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use criterion::Criterion;
+use databend_common_expression::group_hash_columns;
+use databend_common_expression::types::number::NumberScalar;
+use databend_common_expression::types::AnyType;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::Float64Type;
+use databend_common_expression::types::Int64Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::Column;
+use databend_common_expression::ColumnBuilder;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_expression::Scalar;
+use databend_common_expression::Value;
+
+fn bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bench_scatter");
+    group.sample_size(10);
+
+    let num_rows = 1_000_000;
+    let block = build_block(num_rows, 10);
+    for scatter_size in [2u32, 16] {
+        let indices = (0..num_rows as u32)
+            .map(|i| i.wrapping_mul(2654435761) % scatter_size)
+            .collect::<Vec<_>>();
+
+        group.bench_function(format!("scatter/{scatter_size}"), |b| {
+            b.iter(|| block.scatter(&indices, scatter_size as usize).unwrap())
+        });
+        group.bench_function(format!("row_by_row/{scatter_size}"), |b| {
+            b.iter(|| scatter_row_by_row(&block, &indices, scatter_size as usize))
+        });
+    }
+}
+
+fn bench_group_hash(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bench_group_hash");
+    group.sample_size(10);
+
+    let num_rows = 1_000_000;
+    let block = build_block(num_rows, 2);
+    let mut columns = block
+        .columns()
+        .iter()
+        .map(|entry| entry.value.as_column().unwrap().clone())
+        .collect::<Vec<_>>();
+    columns.push(Float64Type::from_data(
+        (0..num_rows).map(|i| i as f64 / 3.0).collect::<Vec<_>>(),
+    ));
+
+    let mut values = vec![0; num_rows];
+    group.bench_function("group_hash_columns", |b| {
+        b.iter(|| group_hash_columns(&columns, &mut values))
+    });
+    group.bench_function("row_by_row", |b| {
+        b.iter(|| group_hash_row_by_row(&columns, num_rows))
+    });
+}
+
+fn bench_constant_column(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bench_constant_column");
+    group.sample_size(10);
+
+    let num_rows = 65536;
+    for (name, scalar, data_type) in [
+        (
+            "int64",
+            Scalar::Number(NumberScalar::Int64(1)),
+            DataType::Number(NumberDataType::Int64),
+        ),
+        (
+            "string",
+            Scalar::String("constant".to_string()),
+            DataType::String,
+        ),
+    ] {
+        let value = Value::<AnyType>::Scalar(scalar.clone());
+        group.bench_function(format!("convert_to_full_column/{name}"), |b| {
+            b.iter(|| value.convert_to_full_column(&data_type, num_rows))
+        });
+        group.bench_function(format!("repeat/{name}"), |b| {
+            b.iter(|| ColumnBuilder::repeat(&scalar.as_ref(), num_rows, &data_type).build())
+        });
+    }
+}
+
+// Half of the columns are integers and the others are strings.
+fn build_block(num_rows: usize, num_columns: usize) -> DataBlock {
+    let strings = (0..num_rows)
+        .map(|i| format!("value_{i}"))
+        .collect::<Vec<_>>();
+    let columns = (0..num_columns)
+        .map(|i| {
+            if i % 2 == 0 {
+                Int64Type::from_data((0..num_rows as i64).collect::<Vec<_>>())
+            } else {
+                StringType::from_data(strings.iter().map(|s| s.as_str()).collect::<Vec<_>>())
+            }
+        })
+        .collect();
+    DataBlock::new_from_columns(columns)
+}
+
+// The baseline which appends the scattered rows value by value.
+fn scatter_row_by_row(block: &DataBlock, indices: &[u32], scatter_size: usize) -> Vec<DataBlock> {
+    let columns = block
+        .columns()
+        .iter()
+        .map(|entry| entry.value.as_column().unwrap())
+        .collect::<Vec<_>>();
+    let mut builders = (0..scatter_size)
+        .map(|_| {
+            block
+                .columns()
+                .iter()
+                .map(|entry| ColumnBuilder::with_capacity(&entry.data_type, 0))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    for (row, index) in indices.iter().enumerate() {
+        for (builder, column) in builders[*index as usize].iter_mut().zip(&columns) {
+            builder.push(column.index(row).unwrap());
+        }
+    }
+
+    builders
+        .into_iter()
+        .map(|builders| {
+            let columns = builders
+                .into_iter()
+                .map(ColumnBuilder::build)
+                .collect::<Vec<Column>>();
+            DataBlock::new_from_columns(columns)
+        })
+        .collect()
+}
+
+// The baseline which hashes the rows value by value with the default hasher.
+fn group_hash_row_by_row(columns: &[Column], num_rows: usize) -> Vec<u64> {
+    (0..num_rows)
+        .map(|row| {
+            let mut hasher = DefaultHasher::new();
+            for column in columns {
+                column.index(row).unwrap().to_string().hash(&mut hasher);
+            }
+            hasher.finish()
+        })
+        .collect()
+}
+
+criterion_group!(benches, bench, bench_group_hash, bench_constant_column);
+criterion_main!(benches);
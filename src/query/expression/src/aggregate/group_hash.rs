@@ -15,6 +15,7 @@
 use ethnum::i256;
 use ordered_float::OrderedFloat;
 
+use crate::types::decimal::DecimalScalar;
 use crate::types::decimal::DecimalType;
 use crate::types::geometry::GeometryType;
 use crate::types::AnyType;
@@ -26,6 +27,7 @@ use crate::types::DataType;
 use crate::types::DateType;
 use crate::types::DecimalDataType;
 use crate::types::NumberDataType;
+use crate::types::NumberScalar;
 use crate::types::NumberType;
 use crate::types::StringType;
 use crate::types::TimestampType;
@@ -37,6 +39,11 @@ use crate::ScalarRef;
 
 const NULL_HASH_VAL: u64 = 0xd1cefa08eb382d69;
 
+/// Hashes the rows of the columns into `values`.
+///
+/// The hash of a row only depends on the values of the row, so the equal keys from different
+/// blocks have the same hash. A NULL is hashed to `NULL_HASH_VAL`, `-0.0` and `+0.0` have the
+/// same hash, and so do all the NaNs.
 pub fn group_hash_columns(cols: &[Column], values: &mut [u64]) {
     debug_assert!(!cols.is_empty());
     combine_group_hash_column::<true>(&cols[0], values);
@@ -207,6 +214,7 @@ impl_agg_hash_for_primitive_types!(u32);
 impl_agg_hash_for_primitive_types!(i32);
 impl_agg_hash_for_primitive_types!(u64);
 impl_agg_hash_for_primitive_types!(i64);
+impl_agg_hash_for_primitive_types!(usize);
 
 impl AggHash for bool {
     fn agg_hash(&self) -> u64 {
@@ -231,6 +239,9 @@ impl AggHash for OrderedFloat<f32> {
     fn agg_hash(&self) -> u64 {
         if self.is_nan() {
             f32::NAN.to_bits().agg_hash()
+        } else if self.0 == 0.0 {
+            // -0.0 and +0.0 are equal.
+            0.0f32.to_bits().agg_hash()
         } else {
             self.to_bits().agg_hash()
         }
@@ -242,15 +253,44 @@ impl AggHash for OrderedFloat<f64> {
     fn agg_hash(&self) -> u64 {
         if self.is_nan() {
             f64::NAN.to_bits().agg_hash()
+        } else if self.0 == 0.0 {
+            // -0.0 and +0.0 are equal.
+            0.0f64.to_bits().agg_hash()
         } else {
             self.to_bits().agg_hash()
         }
     }
 }
 
+// The nested values are hashed one by one and combined in order, like the columns of a row.
 impl AggHash for ScalarRef<'_> {
-    #[inline(always)]
     fn agg_hash(&self) -> u64 {
-        self.to_string().as_bytes().agg_hash()
+        match self {
+            ScalarRef::Null => NULL_HASH_VAL,
+            ScalarRef::EmptyArray | ScalarRef::EmptyMap => 0,
+            ScalarRef::Number(v) => with_number_mapped_type!(|NUM_TYPE| match v {
+                NumberScalar::NUM_TYPE(v) => v.agg_hash(),
+            }),
+            ScalarRef::Decimal(DecimalScalar::Decimal128(v, _)) => v.agg_hash(),
+            ScalarRef::Decimal(DecimalScalar::Decimal256(v, _)) => v.agg_hash(),
+            ScalarRef::Boolean(v) => v.agg_hash(),
+            ScalarRef::Timestamp(v) => v.agg_hash(),
+            ScalarRef::Date(v) => v.agg_hash(),
+            ScalarRef::String(v) => v.as_bytes().agg_hash(),
+            ScalarRef::Binary(v)
+            | ScalarRef::Bitmap(v)
+            | ScalarRef::Variant(v)
+            | ScalarRef::Geometry(v) => v.agg_hash(),
+            ScalarRef::Array(col) | ScalarRef::Map(col) => {
+                combine_agg_hash(col.len().agg_hash(), col.iter().map(|x| x.agg_hash()))
+            }
+            ScalarRef::Tuple(fields) => {
+                combine_agg_hash(fields.len().agg_hash(), fields.iter().map(|x| x.agg_hash()))
+            }
+        }
     }
 }
+
+fn combine_agg_hash(seed: u64, hashes: impl Iterator<Item = u64>) -> u64 {
+    hashes.fold(seed, |h, x| h.wrapping_mul(NULL_HASH_VAL) ^ x)
+}
@@ -23,6 +23,7 @@ use databend_common_arrow::ArrayRef;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
 
+use crate::aggregate::AggHash;
 use crate::schema::DataSchema;
 use crate::types::AnyType;
 use crate::types::DataType;
@@ -44,6 +45,8 @@ pub struct DataBlock {
     columns: Vec<BlockEntry>,
     num_rows: usize,
     meta: Option<BlockMetaInfoPtr>,
+    /// Stable position of the rows in the query, see [`DataBlock::with_origin`].
+    origin: Option<u64>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -120,6 +123,7 @@ impl DataBlock {
             columns,
             num_rows,
             meta,
+            origin: None,
         }
     }
 
@@ -143,6 +147,42 @@ impl DataBlock {
         Self::check_columns_valid(&self.columns, self.num_rows)
     }
 
+    /// Checks the block is valid and its columns agree with the fields of `schema`, the
+    /// nested types included. The error names the first column that does not.
+    pub fn check_schema(&self, schema: &DataSchema) -> Result<()> {
+        self.check_valid()?;
+
+        if self.num_columns() != schema.num_fields() {
+            return Err(ErrorCode::Internal(format!(
+                "DataBlock corrupted, it has {} columns, but the schema has {} fields",
+                self.num_columns(),
+                schema.num_fields()
+            )));
+        }
+
+        for (index, (entry, field)) in self.columns.iter().zip(schema.fields()).enumerate() {
+            if &entry.data_type != field.data_type() {
+                return Err(ErrorCode::Internal(format!(
+                    "DataBlock corrupted, column {} '{}' is {}, but the schema type is {}",
+                    index,
+                    field.name(),
+                    entry.data_type,
+                    field.data_type()
+                )));
+            }
+
+            if !is_value_of_type(&entry.data_type, &entry.value) {
+                return Err(ErrorCode::Internal(format!(
+                    "DataBlock corrupted, the value of column {} '{}' is not of type {}",
+                    index,
+                    field.name(),
+                    entry.data_type
+                )));
+            }
+        }
+        Ok(())
+    }
+
     #[inline]
     pub fn new_from_columns(columns: Vec<Column>) -> Self {
         assert!(!columns.is_empty());
@@ -255,6 +295,7 @@ impl DataBlock {
             columns,
             num_rows: self.num_rows,
             meta: self.meta.clone(),
+            origin: self.origin,
         }
     }
 
@@ -262,20 +303,20 @@ impl DataBlock {
         let columns = self
             .columns()
             .iter()
-            .map(|entry| match &entry.value {
-                Value::Scalar(s) => {
-                    BlockEntry::new(entry.data_type.clone(), Value::Scalar(s.clone()))
-                }
-                Value::Column(c) => BlockEntry::new(
+            .map(|entry| {
+                BlockEntry::new(
                     entry.data_type.clone(),
-                    Value::Column(c.slice(range.clone())),
-                ),
+                    entry.value.slice(range.clone(), self.num_rows),
+                )
             })
             .collect();
         Self {
             columns,
             num_rows: range.end - range.start,
             meta: self.meta.clone(),
+            origin: self
+                .origin
+                .map(|origin| mix_block_origin(origin, range.start as u64)),
         }
     }
 
@@ -382,6 +423,7 @@ impl DataBlock {
             columns,
             num_rows: self.num_rows,
             meta: self.meta,
+            origin: self.origin,
         })
     }
 
@@ -397,6 +439,7 @@ impl DataBlock {
             columns: self.columns,
             num_rows: self.num_rows,
             meta,
+            origin: self.origin,
         })
     }
 
@@ -415,6 +458,30 @@ impl DataBlock {
         self.meta
     }
 
+    /// Sets the stable position of the rows of the block in the query, see [`block_origin`].
+    ///
+    /// The origin is the same on every thread and node that produces the block, the seeded
+    /// `rand()` derives its values from it. It's kept by the kernels that build a block from
+    /// the rows of one block, e.g. filter and take, and is None for the blocks built from
+    /// scratch, e.g. the results of aggregations.
+    #[inline]
+    pub fn with_origin(mut self, origin: u64) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    #[inline]
+    pub fn origin(&self) -> Option<u64> {
+        self.origin
+    }
+
+    /// Keeps the origin of the block whose rows the block is built from.
+    #[inline]
+    pub fn with_optional_origin(mut self, origin: Option<u64>) -> Self {
+        self.origin = origin;
+        self
+    }
+
     pub fn from_arrow_chunk<A: AsRef<dyn Array>>(
         arrow_chunk: &ArrowChunk<A>,
         schema: &DataSchema,
@@ -551,6 +618,7 @@ impl DataBlock {
             columns.push(column);
         }
         DataBlock::new_with_meta(columns, self.num_rows, self.meta)
+            .with_optional_origin(self.origin)
     }
 
     #[inline]
@@ -561,6 +629,21 @@ impl DataBlock {
     }
 }
 
+/// Returns the origin of the block at `offset` of a source, e.g. the block of a part or the
+/// packet of an exchange. `source_key` identifies the source in the query, e.g. the location
+/// of the part.
+pub fn block_origin(source_key: &[u8], offset: u64) -> u64 {
+    mix_block_origin(source_key.agg_hash(), offset)
+}
+
+/// Mixes `value` into `origin` with the finalizer of SplitMix64.
+pub(crate) fn mix_block_origin(origin: u64, value: u64) -> u64 {
+    let mut z = origin ^ value.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
 impl TryFrom<DataBlock> for ArrowChunk<ArrayRef> {
     type Error = ErrorCode;
 
@@ -582,7 +665,8 @@ impl TryFrom<DataBlock> for ArrowChunk<ArrayRef> {
 impl BlockEntry {
     pub fn memory_size(&self) -> usize {
         match &self.value {
-            Value::Scalar(s) => std::mem::size_of_val(s),
+            // A scalar is shared by all the rows, so it is only counted once.
+            Value::Scalar(s) => s.as_ref().memory_size(),
             Value::Column(c) => c.memory_size(),
         }
     }
@@ -609,21 +693,23 @@ impl Clone for Box<dyn BlockMetaInfo> {
 }
 
 fn check_type(data_type: &DataType, value: &Value<AnyType>) {
+    assert!(
+        is_value_of_type(data_type, value),
+        "{value:?} is not of type {data_type}"
+    );
+}
+
+fn is_value_of_type(data_type: &DataType, value: &Value<AnyType>) -> bool {
     match value {
-        Value::Scalar(Scalar::Null) => {
-            assert!(data_type.is_nullable_or_null());
-        }
-        Value::Scalar(Scalar::Tuple(fields)) => {
-            // Check if data_type is Tuple type.
-            let data_type = data_type.remove_nullable();
-            assert!(matches!(data_type, DataType::Tuple(_)));
-            if let DataType::Tuple(dts) = data_type {
-                for (s, dt) in fields.iter().zip(dts.iter()) {
-                    check_type(dt, &Value::Scalar(s.clone()));
-                }
-            }
-        }
-        Value::Scalar(s) => assert_eq!(s.as_ref().infer_data_type(), data_type.remove_nullable()),
-        Value::Column(c) => assert_eq!(&c.data_type(), data_type),
+        Value::Scalar(Scalar::Null) => data_type.is_nullable_or_null(),
+        Value::Scalar(Scalar::Tuple(fields)) => match data_type.remove_nullable() {
+            DataType::Tuple(dts) => fields
+                .iter()
+                .zip(dts.iter())
+                .all(|(s, dt)| is_value_of_type(dt, &Value::Scalar(s.clone()))),
+            _ => false,
+        },
+        Value::Scalar(s) => s.as_ref().infer_data_type() == data_type.remove_nullable(),
+        Value::Column(c) => &c.data_type() == data_type,
     }
 }
@@ -14,6 +14,8 @@
 
 use databend_common_arrow::arrow::bitmap::Bitmap;
 use databend_common_arrow::arrow::buffer::Buffer;
+use databend_common_arrow::arrow::compute::cast::cast;
+use databend_common_arrow::arrow::compute::cast::CastOptions;
 use databend_common_arrow::arrow::datatypes::DataType as ArrowDataType;
 use databend_common_arrow::arrow::datatypes::Field as ArrowField;
 use databend_common_arrow::arrow::datatypes::Schema as ArrowSchema;
@@ -42,6 +44,7 @@ use crate::with_number_type;
 use crate::Column;
 use crate::DataField;
 use crate::DataSchema;
+use crate::Scalar;
 use crate::TableDataType;
 use crate::TableField;
 use crate::TableSchema;
@@ -138,6 +141,9 @@ fn arrow_type_to_table_type(ty: &ArrowDataType, is_nullable: bool) -> Result<Tab
                 fields_type,
             }
         }
+        ArrowDataType::Dictionary(_, values_type, _) => {
+            return arrow_type_to_table_type(values_type, is_nullable);
+        }
         ArrowDataType::Extension(custom_name, data_type, _) => match custom_name.as_str() {
             ARROW_EXT_TYPE_EMPTY_ARRAY => TableDataType::EmptyArray,
             ARROW_EXT_TYPE_EMPTY_MAP => TableDataType::EmptyMap,
@@ -489,28 +495,30 @@ impl Column {
                         ))
                     }
                 }
-                (DataType::Timestamp, ArrowDataType::Timestamp(uint, _)) => {
+                (DataType::Timestamp, ArrowDataType::Timestamp(unit, _)) => {
                     let values = arrow_col
                         .as_any()
                         .downcast_ref::<databend_common_arrow::arrow::array::Int64Array>()
                         .expect("fail to read `Timestamp` from arrow: array should be `Int64Array`")
                         .values();
-                    let convert = match uint {
-                        TimeUnit::Second => (1_000_000, 1),
-                        TimeUnit::Millisecond => (1_000, 1),
-                        TimeUnit::Microsecond => (1, 1),
-                        TimeUnit::Nanosecond => (1, 1_000),
-                    };
-                    let values = if convert.0 == 1 && convert.1 == 1 {
-                        values.clone()
-                    } else {
-                        let values = values
-                            .iter()
-                            .map(|x| x * convert.0 / convert.1)
-                            .collect::<Vec<_>>();
-                        values.into()
-                    };
-                    Column::Timestamp(values)
+                    Column::Timestamp(timestamp_to_micros(values, arrow_col.validity(), unit)?)
+                }
+                (
+                    DataType::Timestamp | DataType::Date,
+                    ArrowDataType::Utf8 | ArrowDataType::LargeUtf8,
+                ) => strings_to_temporal(arrow_col, data_type)?,
+                (DataType::Timestamp, ArrowDataType::Date32) => {
+                    let values = arrow_col
+                        .as_any()
+                        .downcast_ref::<databend_common_arrow::arrow::array::Int32Array>()
+                        .expect("fail to read `Timestamp` from arrow: array should be `Int32Array`")
+                        .values();
+                    // Dates are converted to the midnight of the day in UTC.
+                    let values = values
+                        .iter()
+                        .map(|x| *x as i64 * MICROS_PER_DAY)
+                        .collect::<Vec<_>>();
+                    Column::Timestamp(values.into())
                 }
                 (DataType::Date, ArrowDataType::Date32) => Column::Date(
                     arrow_col
@@ -520,6 +528,30 @@ impl Column {
                         .values()
                         .clone(),
                 ),
+                (DataType::Date, ArrowDataType::Date64) => {
+                    let values = arrow_col
+                        .as_any()
+                        .downcast_ref::<databend_common_arrow::arrow::array::Int64Array>()
+                        .expect("fail to read `Date` from arrow: array should be `Int64Array`")
+                        .values();
+                    let validity = arrow_col.validity();
+                    Column::Date(micros_to_days(
+                        &timestamp_to_micros(values, validity, &TimeUnit::Millisecond)?,
+                        validity,
+                    )?)
+                }
+                (DataType::Date, ArrowDataType::Timestamp(unit, _)) => {
+                    let values = arrow_col
+                        .as_any()
+                        .downcast_ref::<databend_common_arrow::arrow::array::Int64Array>()
+                        .expect("fail to read `Date` from arrow: array should be `Int64Array`")
+                        .values();
+                    let validity = arrow_col.validity();
+                    Column::Date(micros_to_days(
+                        &timestamp_to_micros(values, validity, unit)?,
+                        validity,
+                    )?)
+                }
                 (
                     DataType::Variant,
                     ArrowDataType::Extension(name, box ArrowDataType::Binary, None),
@@ -779,6 +811,11 @@ impl Column {
                 (data_type, ArrowDataType::Extension(_, arrow_type, _)) => {
                     from_arrow_with_arrow_type(arrow_col, arrow_type, data_type)?
                 }
+                (data_type, ArrowDataType::Dictionary(_, values_type, _)) => {
+                    // Decode the dictionary into a plain array of its values type.
+                    let decoded = cast(arrow_col, values_type, CastOptions::default())?;
+                    Column::from_arrow(decoded.as_ref(), data_type)?
+                }
                 (DataType::Nullable(ty), _) => {
                     let column = Column::from_arrow(arrow_col, ty)?;
                     let validity = arrow_col
@@ -799,3 +836,105 @@ impl Column {
         from_arrow_with_arrow_type(arrow_col, arrow_col.data_type(), data_type)
     }
 }
+
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+/// Converts arrow timestamps of any unit to microseconds.
+///
+/// Coarser units are scaled up with overflow checks, nanoseconds are rounded
+/// towards negative infinity so that timestamps before 1970 keep their second.
+/// The values behind the null slots are not checked and become zero.
+fn timestamp_to_micros(
+    values: &Buffer<i64>,
+    validity: Option<&Bitmap>,
+    unit: &TimeUnit,
+) -> Result<Buffer<i64>> {
+    let factor = match unit {
+        TimeUnit::Second => 1_000_000,
+        TimeUnit::Millisecond => 1_000,
+        TimeUnit::Microsecond => return Ok(values.clone()),
+        TimeUnit::Nanosecond => {
+            return Ok(values
+                .iter()
+                .map(|x| x.div_euclid(1_000))
+                .collect::<Vec<_>>()
+                .into());
+        }
+    };
+    let values = values
+        .iter()
+        .enumerate()
+        .map(|(row, x)| match is_valid(validity, row) {
+            true => x.checked_mul(factor).ok_or_else(|| {
+                ErrorCode::Overflow(format!(
+                    "timestamp {x} in {unit:?} is out of range of microseconds"
+                ))
+            }),
+            false => Ok(0),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(values.into())
+}
+
+/// Truncates timestamps in microseconds to the days since the epoch, the null slots become zero.
+fn micros_to_days(values: &Buffer<i64>, validity: Option<&Bitmap>) -> Result<Buffer<i32>> {
+    let values = values
+        .iter()
+        .enumerate()
+        .map(|(row, x)| match is_valid(validity, row) {
+            true => i32::try_from(x.div_euclid(MICROS_PER_DAY))
+                .map_err(|_| ErrorCode::Overflow(format!("timestamp {x} is out of range of date"))),
+            false => Ok(0),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(values.into())
+}
+
+fn is_valid(validity: Option<&Bitmap>, row: usize) -> bool {
+    validity.map_or(true, |validity| validity.get_bit(row))
+}
+
+/// Parses arrow strings like `YYYY-MM-DD[ HH:MM:SS[.fff]]` in UTC to dates or timestamps,
+/// the null slots become zero.
+fn strings_to_temporal(
+    arrow_col: &dyn databend_common_arrow::arrow::array::Array,
+    data_type: &DataType,
+) -> Result<Column> {
+    let strings: Box<dyn Iterator<Item = Option<&str>> + '_> = match arrow_col.data_type() {
+        ArrowDataType::Utf8 => Box::new(
+            arrow_col
+                .as_any()
+                .downcast_ref::<databend_common_arrow::arrow::array::Utf8Array<i32>>()
+                .expect("fail to read from arrow: array should be `Utf8Array<i32>`")
+                .iter(),
+        ),
+        _ => Box::new(
+            arrow_col
+                .as_any()
+                .downcast_ref::<databend_common_arrow::arrow::array::Utf8Array<i64>>()
+                .expect("fail to read from arrow: array should be `Utf8Array<i64>`")
+                .iter(),
+        ),
+    };
+
+    match data_type {
+        DataType::Date => {
+            let values = strings
+                .map(|s| match s {
+                    Some(s) => Ok(*Scalar::date_from_str(s)?.as_date().unwrap()),
+                    None => Ok(0),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Column::Date(values.into()))
+        }
+        _ => {
+            let values = strings
+                .map(|s| match s {
+                    Some(s) => Ok(*Scalar::timestamp_from_str(s)?.as_timestamp().unwrap()),
+                    None => Ok(0),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Column::Timestamp(values.into()))
+        }
+    }
+}
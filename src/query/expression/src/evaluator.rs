@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cell::OnceCell;
 use std::collections::HashMap;
 use std::ops::Not;
 
@@ -24,6 +25,8 @@ use databend_common_exception::Span;
 use itertools::Itertools;
 use log::error;
 
+use crate::aggregate::group_hash_columns;
+use crate::aggregate::AggHash;
 use crate::block::DataBlock;
 use crate::expression::Expr;
 use crate::function::EvalContext;
@@ -55,6 +58,7 @@ pub struct Evaluator<'a> {
     data_block: &'a DataBlock,
     func_ctx: &'a FunctionContext,
     fn_registry: &'a FunctionRegistry,
+    random_key: OnceCell<u64>,
 }
 
 impl<'a> Evaluator<'a> {
@@ -67,6 +71,7 @@ impl<'a> Evaluator<'a> {
             data_block,
             func_ctx,
             fn_registry,
+            random_key: OnceCell::new(),
         }
     }
 
@@ -78,6 +83,19 @@ impl<'a> Evaluator<'a> {
         self.func_ctx
     }
 
+    /// See `EvalContext::random_key`, the block is only hashed if the seed is set and the
+    /// block has no origin.
+    pub fn random_key(&self) -> u64 {
+        if self.func_ctx.random_seed.is_none() {
+            return 0;
+        }
+        *self.random_key.get_or_init(|| {
+            self.data_block
+                .origin()
+                .unwrap_or_else(|| block_fingerprint(self.data_block))
+        })
+    }
+
     #[cfg(debug_assertions)]
     fn check_expr(&self, expr: &Expr) {
         let column_refs = expr.column_refs();
@@ -181,6 +199,7 @@ impl<'a> Evaluator<'a> {
                     validity,
                     errors: None,
                     func_ctx: self.func_ctx,
+                    random_key: self.random_key(),
                 };
                 let (_, eval) = function.eval.as_scalar().unwrap();
                 let result = (eval)(cols_ref.as_slice(), &mut ctx);
@@ -846,14 +865,12 @@ impl<'a> Evaluator<'a> {
         // Evaluate the condition first and then partially evaluate the result branches.
         let mut validity = validity.unwrap_or_else(|| Bitmap::new_constant(true, num_rows));
         let mut conds = Vec::new();
-        let mut flags = Vec::new();
         let mut results = Vec::new();
         for cond_idx in (0..args.len() - 1).step_by(2) {
             let cond = self.partial_run(&args[cond_idx], Some(validity.clone()), selection)?;
             match cond.try_downcast::<NullableType<BooleanType>>().unwrap() {
                 Value::Scalar(None | Some(false)) => {
                     results.push(Value::Scalar(Scalar::default_value(&generics[0])));
-                    flags.push(Bitmap::new_constant(false, len.unwrap_or(1)));
                 }
                 Value::Scalar(Some(true)) => {
                     results.push(self.partial_run(
@@ -862,7 +879,7 @@ impl<'a> Evaluator<'a> {
                         selection,
                     )?);
                     validity = Bitmap::new_constant(false, num_rows);
-                    flags.push(Bitmap::new_constant(true, len.unwrap_or(1)));
+                    conds.push(cond);
                     break;
                 }
                 Value::Column(cond) => {
@@ -873,7 +890,6 @@ impl<'a> Evaluator<'a> {
                         selection,
                     )?);
                     validity = (&validity) & (&flag.not());
-                    flags.push(flag);
                 }
             };
             conds.push(cond);
@@ -894,21 +910,13 @@ impl<'a> Evaluator<'a> {
         );
 
         // Pick the results from the result branches depending on the condition.
-        let mut output_builder = ColumnBuilder::with_capacity(&generics[0], len.unwrap_or(1));
-        for row_idx in 0..(len.unwrap_or(1)) {
-            unsafe {
-                let result = flags
-                    .iter()
-                    .position(|flag| flag.get_bit(row_idx))
-                    .map(|idx| results[idx].index_unchecked(row_idx))
-                    .unwrap_or(else_result.index_unchecked(row_idx));
-                output_builder.push(result);
-            }
-        }
-        match len {
-            Some(_) => Ok(Value::Column(output_builder.build())),
-            None => Ok(Value::Scalar(output_builder.build_scalar())),
-        }
+        Value::multi_if(
+            &conds,
+            &results,
+            &else_result,
+            &generics[0],
+            len.unwrap_or(1),
+        )
     }
 
     // `and_filters` is a special builtin function similar to `if` that conditionally evaluate its arguments.
@@ -987,6 +995,7 @@ impl<'a> Evaluator<'a> {
                     validity: None,
                     errors: None,
                     func_ctx: self.func_ctx,
+                    random_key: self.random_key(),
                 };
                 let result = (eval)(&cols_ref, &mut ctx, max_nums_per_row);
                 ctx.render_error(*span, id.params(), &args, &function.signature.name, None)?;
@@ -1272,6 +1281,7 @@ impl<'a> Evaluator<'a> {
                     validity: None,
                     errors: None,
                     func_ctx: self.func_ctx,
+                    random_key: self.random_key(),
                 };
                 let (_, eval) = function.eval.as_scalar().unwrap();
                 let result = (eval)(cols_ref.as_slice(), &mut ctx);
@@ -1942,3 +1952,29 @@ impl<'a, Index: ColumnIndex> ConstantFolder<'a, Index> {
         Some(output_domain)
     }
 }
+
+/// Hashes the values of all the rows of the block. The hash only depends on the data, so a
+/// block has the same fingerprint on every thread and node. It's only used for the blocks
+/// without origin, e.g. the results of aggregations.
+fn block_fingerprint(block: &DataBlock) -> u64 {
+    const MULTIPLIER: u64 = 0x9E37_79B9_7F4A_7C15;
+
+    let mut key = block.num_rows() as u64;
+    let mut columns = Vec::with_capacity(block.num_columns());
+    for entry in block.columns() {
+        match &entry.value {
+            Value::Scalar(scalar) => {
+                key = key.wrapping_mul(MULTIPLIER) ^ scalar.as_ref().agg_hash();
+            }
+            Value::Column(column) => columns.push(column.clone()),
+        }
+    }
+    if !columns.is_empty() {
+        let mut hashes = vec![0; block.num_rows()];
+        group_hash_columns(&columns, &mut hashes);
+        key = hashes
+            .into_iter()
+            .fold(key, |key, hash| key.wrapping_mul(MULTIPLIER) ^ hash);
+    }
+    key
+}
@@ -385,6 +385,7 @@ impl<'a> Selector<'a> {
                     validity: None,
                     errors: None,
                     func_ctx: self.evaluator.func_ctx(),
+                    random_key: self.evaluator.random_key(),
                 };
                 let (_, eval) = function.eval.as_scalar().unwrap();
                 let result = (eval)(cols_ref.as_slice(), &mut ctx);
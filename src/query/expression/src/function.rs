@@ -17,6 +17,7 @@ use std::collections::HashMap;
 use std::ops::BitAnd;
 use std::ops::BitOr;
 use std::ops::Not;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 
 use databend_common_arrow::arrow::bitmap::Bitmap;
@@ -106,6 +107,11 @@ pub struct FunctionContext {
 
     pub external_server_connect_timeout_secs: u64,
     pub external_server_request_timeout_secs: u64,
+
+    /// Seed of `rand()`, set by the `query_random_seed` setting.
+    pub random_seed: Option<u64>,
+    /// Number of strings in arithmetic which are not numbers, see `loose_to_float64`.
+    pub loose_coercion_failures: Arc<AtomicU64>,
 }
 
 #[derive(Clone)]
@@ -119,6 +125,11 @@ pub struct EvalContext<'a> {
     /// default value in nullable's inner column.
     pub validity: Option<Bitmap>,
     pub errors: Option<(MutableBitmap, String)>,
+    /// Origin of the evaluated block, or the fingerprint of its data if it has no origin, 0 if
+    /// `func_ctx.random_seed` is not set. The seeded `rand()` derives the value of a row from
+    /// the seed, this key and the row, so the values don't depend on which thread or node
+    /// evaluates the block.
+    pub random_key: u64,
 }
 
 /// `FunctionID` is a unique identifier for a function in the registry. It's used to
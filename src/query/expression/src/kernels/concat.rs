@@ -21,6 +21,7 @@ use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
 use itertools::Itertools;
 
+use crate::block::mix_block_origin;
 use crate::kernels::take::BIT_MASK;
 use crate::kernels::utils::copy_advance_aligned;
 use crate::kernels::utils::set_vec_len_by_ptr;
@@ -91,8 +92,13 @@ impl DataBlock {
             .collect::<Result<Vec<_>>>()?;
 
         let num_rows = blocks.iter().map(|c| c.num_rows()).sum();
+        let origin = blocks
+            .iter()
+            .map(DataBlock::origin)
+            .reduce(|acc, origin| Some(mix_block_origin(acc?, origin?)))
+            .flatten();
 
-        Ok(DataBlock::new(concat_columns, num_rows))
+        Ok(DataBlock::new(concat_columns, num_rows).with_optional_origin(origin))
     }
 }
 
@@ -18,6 +18,7 @@ use databend_common_arrow::arrow::bitmap::utils::BitChunkIterExact;
 use databend_common_arrow::arrow::bitmap::utils::BitChunksExact;
 use databend_common_arrow::arrow::bitmap::Bitmap;
 use databend_common_arrow::arrow::buffer::Buffer;
+use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
 
 use crate::kernels::take::BIT_MASK;
@@ -44,9 +45,39 @@ use crate::BlockEntry;
 use crate::Column;
 use crate::ColumnBuilder;
 use crate::DataBlock;
+use crate::Scalar;
 use crate::Value;
 
 impl DataBlock {
+    /// Filters the rows by the `predicate` of type `Boolean` or `Nullable(Boolean)`, the rows
+    /// whose predicate is NULL are filtered out as SQL does.
+    pub fn filter_block(self, predicate: &Value<AnyType>) -> Result<DataBlock> {
+        let bitmap = match predicate {
+            Value::Scalar(Scalar::Boolean(true)) => return Ok(self),
+            Value::Scalar(Scalar::Boolean(false) | Scalar::Null) => return Ok(self.slice(0..0)),
+            Value::Column(column) if column.len() != self.num_rows() => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "The filter predicate has {} rows, but the block has {} rows",
+                    column.len(),
+                    self.num_rows()
+                )));
+            }
+            Value::Column(Column::Boolean(bitmap)) => bitmap.clone(),
+            Value::Column(Column::Nullable(box NullableColumn {
+                column: Column::Boolean(bitmap),
+                validity,
+            })) => bitmap & validity,
+            Value::Column(Column::Null { .. }) => return Ok(self.slice(0..0)),
+            _ => {
+                return Err(ErrorCode::BadDataValueType(
+                    "The filter predicate must be a boolean",
+                ));
+            }
+        };
+
+        self.filter_with_bitmap(&bitmap)
+    }
+
     pub fn filter_with_bitmap(self, bitmap: &Bitmap) -> Result<DataBlock> {
         if self.num_rows() == 0 {
             return Ok(self);
@@ -70,7 +101,8 @@ impl DataBlock {
                         _ => entry.clone(),
                     })
                     .collect();
-                Ok(DataBlock::new(after_columns, self.num_rows() - count_zeros))
+                Ok(DataBlock::new(after_columns, self.num_rows() - count_zeros)
+                    .with_optional_origin(self.origin()))
             }
         }
     }
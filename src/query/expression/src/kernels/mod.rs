@@ -16,8 +16,10 @@ mod concat;
 mod filter;
 mod group_by;
 mod group_by_hash;
+mod multi_if;
 mod scatter;
 mod sort;
+mod statistics;
 mod take;
 mod take_chunks;
 mod take_compact;
@@ -27,6 +29,7 @@ mod utils;
 
 pub use group_by_hash::*;
 pub use sort::*;
+pub use statistics::*;
 pub use take_chunks::*;
 pub use topk::*;
 pub use utils::*;
@@ -0,0 +1,192 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_arrow::arrow::bitmap::Bitmap;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+
+use crate::kernels::BlockRowIndex;
+use crate::types::nullable::NullableColumn;
+use crate::types::AnyType;
+use crate::types::DataType;
+use crate::Column;
+use crate::ColumnBuilder;
+use crate::Scalar;
+use crate::Value;
+
+// The rows a condition holds for.
+enum CondRows {
+    All,
+    None,
+    Some(Bitmap),
+}
+
+impl Value<AnyType> {
+    /// Takes each row from `then` if `cond` is true, otherwise from `otherwise`. The rows
+    /// whose condition is NULL take `otherwise`, see [`Value::multi_if`].
+    pub fn if_then_else(
+        cond: &Value<AnyType>,
+        then: &Value<AnyType>,
+        otherwise: &Value<AnyType>,
+        data_type: &DataType,
+        num_rows: usize,
+    ) -> Result<Value<AnyType>> {
+        Value::multi_if(
+            std::slice::from_ref(cond),
+            std::slice::from_ref(then),
+            otherwise,
+            data_type,
+            num_rows,
+        )
+    }
+
+    /// Takes each row from the result of the first condition of `conds` which is true for
+    /// it, or from `otherwise` if none of them is, as `CASE WHEN` does. The conditions are of
+    /// type `Boolean` or `Nullable(Boolean)`, and a NULL condition is not true. The results
+    /// are all of `data_type`, the binder casts them to their common super type.
+    ///
+    /// A constant condition picks or skips its result as a whole, the result taken by all
+    /// the rows is returned as is.
+    pub fn multi_if(
+        conds: &[Value<AnyType>],
+        results: &[Value<AnyType>],
+        otherwise: &Value<AnyType>,
+        data_type: &DataType,
+        num_rows: usize,
+    ) -> Result<Value<AnyType>> {
+        if conds.len() != results.len() {
+            return Err(ErrorCode::BadArguments(format!(
+                "multi_if expects a result for each of the {} conditions, but got {} results",
+                conds.len(),
+                results.len()
+            )));
+        }
+
+        for result in results.iter().chain([otherwise]) {
+            if let Value::Column(column) = result {
+                check_len(column, num_rows)?;
+                if column.data_type() != *data_type {
+                    return Err(ErrorCode::BadDataValueType(format!(
+                        "The results of multi_if must be of type {}, but got {}",
+                        data_type,
+                        column.data_type()
+                    )));
+                }
+            }
+        }
+
+        // The results picked by some of the rows, and the one picked by the rest of them.
+        let mut branches = Vec::with_capacity(results.len());
+        let mut rest = otherwise;
+        for (cond, result) in conds.iter().zip(results) {
+            match cond_rows(cond, num_rows)? {
+                CondRows::None => continue,
+                CondRows::All => {
+                    rest = result;
+                    break;
+                }
+                CondRows::Some(rows) => branches.push((rows, result)),
+            }
+        }
+
+        if branches.is_empty() {
+            return Ok(rest.clone());
+        }
+
+        // The branch of each row, the first one whose condition is true for it.
+        let mut picks = vec![branches.len() as u32; num_rows];
+        for (branch, (rows, _)) in branches.iter().enumerate().rev() {
+            for (row, picked) in rows.iter().enumerate() {
+                if picked {
+                    picks[row] = branch as u32;
+                }
+            }
+        }
+
+        let values = branches
+            .iter()
+            .map(|(_, result)| *result)
+            .chain([rest])
+            .collect::<Vec<_>>();
+        let columns = values
+            .iter()
+            .map(|value| match value {
+                Value::Scalar(scalar) => {
+                    ColumnBuilder::repeat(&scalar.as_ref(), 1, data_type).build()
+                }
+                Value::Column(column) => column.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        // The runs of rows picked from the same constant result are taken at once.
+        let mut indices: Vec<BlockRowIndex> = Vec::with_capacity(num_rows);
+        for (row, branch) in picks.into_iter().enumerate() {
+            match values[branch as usize] {
+                Value::Scalar(_) => match indices.last_mut() {
+                    Some((last, 0, times)) if *last == branch => *times += 1,
+                    _ => indices.push((branch, 0, 1)),
+                },
+                Value::Column(_) => indices.push((branch, row as u32, 1)),
+            }
+        }
+
+        Ok(Value::Column(Column::take_column_indices(
+            &columns,
+            data_type.clone(),
+            &indices,
+            num_rows,
+        )))
+    }
+}
+
+fn cond_rows(cond: &Value<AnyType>, num_rows: usize) -> Result<CondRows> {
+    let rows = match cond {
+        Value::Scalar(Scalar::Boolean(true)) => return Ok(CondRows::All),
+        Value::Scalar(Scalar::Boolean(false) | Scalar::Null) => return Ok(CondRows::None),
+        Value::Column(column) => {
+            check_len(column, num_rows)?;
+            match column {
+                Column::Boolean(bitmap) => bitmap.clone(),
+                Column::Nullable(box NullableColumn {
+                    column: Column::Boolean(bitmap),
+                    validity,
+                }) => bitmap & validity,
+                Column::Null { .. } => return Ok(CondRows::None),
+                _ => return Err(not_boolean()),
+            }
+        }
+        _ => return Err(not_boolean()),
+    };
+
+    Ok(match rows.unset_bits() {
+        0 => CondRows::All,
+        n if n == rows.len() => CondRows::None,
+        _ => CondRows::Some(rows),
+    })
+}
+
+fn check_len(column: &Column, num_rows: usize) -> Result<()> {
+    match column.len() == num_rows {
+        true => Ok(()),
+        false => Err(ErrorCode::BadArguments(format!(
+            "The arguments of multi_if have {} rows, but expected {} rows",
+            column.len(),
+            num_rows
+        ))),
+    }
+}
+
+fn not_boolean() -> ErrorCode {
+    ErrorCode::BadDataValueType("The conditions of multi_if must be booleans")
+}
@@ -0,0 +1,154 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+
+use crate::types::AnyType;
+use crate::types::DataType;
+use crate::Column;
+use crate::DataBlock;
+use crate::Scalar;
+use crate::ScalarRef;
+use crate::Value;
+
+/// The statistics of the values of a column, cheap enough to compute for every block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueStatistics {
+    /// The min of the non-null values, None if all the values are null or the type is not
+    /// ordered, like `Variant` or `Array`.
+    pub min: Option<Scalar>,
+    /// The max of the non-null values, None if `min` is None.
+    pub max: Option<Scalar>,
+    pub null_count: usize,
+    /// The number of distinct non-null values, None if it is not computed.
+    pub distinct_count: Option<usize>,
+}
+
+impl ValueStatistics {
+    /// The statistics of the values of both `self` and `other`. The distinct values are
+    /// not merged, since they may overlap.
+    pub fn merge(&self, other: &ValueStatistics) -> ValueStatistics {
+        ValueStatistics {
+            min: merge_bound(&self.min, &other.min, std::cmp::min),
+            max: merge_bound(&self.max, &other.max, std::cmp::max),
+            null_count: self.null_count + other.null_count,
+            distinct_count: None,
+        }
+    }
+}
+
+fn merge_bound(
+    a: &Option<Scalar>,
+    b: &Option<Scalar>,
+    pick: fn(Scalar, Scalar) -> Scalar,
+) -> Option<Scalar> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(pick(a.clone(), b.clone())),
+        (Some(v), None) | (None, Some(v)) => Some(v.clone()),
+        (None, None) => None,
+    }
+}
+
+// The min and max are only computed for the types whose order is the order of the values.
+fn has_min_max(data_type: &DataType) -> bool {
+    matches!(
+        data_type.remove_nullable(),
+        DataType::Number(_)
+            | DataType::Decimal(_)
+            | DataType::Boolean
+            | DataType::String
+            | DataType::Date
+            | DataType::Timestamp
+    )
+}
+
+impl Value<AnyType> {
+    /// The statistics of the `num_rows` values of type `data_type`.
+    pub fn statistics(&self, data_type: &DataType, num_rows: usize) -> ValueStatistics {
+        match self {
+            Value::Scalar(Scalar::Null) => ValueStatistics {
+                min: None,
+                max: None,
+                null_count: num_rows,
+                distinct_count: Some(0),
+            },
+            Value::Scalar(scalar) => {
+                let bound = match has_min_max(data_type) && num_rows > 0 {
+                    true => Some(scalar.clone()),
+                    false => None,
+                };
+                ValueStatistics {
+                    min: bound.clone(),
+                    max: bound,
+                    null_count: 0,
+                    distinct_count: Some(num_rows.min(1)),
+                }
+            }
+            Value::Column(column) => column.statistics(),
+        }
+    }
+}
+
+impl Column {
+    pub fn statistics(&self) -> ValueStatistics {
+        let null_count = match self.validity() {
+            (true, _) => self.len(),
+            (false, Some(validity)) => validity.unset_bits(),
+            (false, None) => 0,
+        };
+
+        let (min, max) = match has_min_max(&self.data_type()) && null_count < self.len() {
+            true => self
+                .iter()
+                .filter(|value| !matches!(value, ScalarRef::Null))
+                .minmax()
+                .into_option()
+                .map(|(min, max)| (Some(min.to_owned()), Some(max.to_owned())))
+                .unwrap_or_default(),
+            false => (None, None),
+        };
+
+        ValueStatistics {
+            min,
+            max,
+            null_count,
+            distinct_count: None,
+        }
+    }
+}
+
+impl DataBlock {
+    /// The statistics of each column of the block.
+    pub fn statistics(&self) -> Vec<ValueStatistics> {
+        self.columns()
+            .iter()
+            .map(|entry| entry.value.statistics(&entry.data_type, self.num_rows()))
+            .collect()
+    }
+
+    /// The statistics of each column of the blocks, which have the same schema. None if
+    /// there is no block.
+    pub fn merge_statistics(blocks: &[DataBlock]) -> Option<Vec<ValueStatistics>> {
+        blocks
+            .iter()
+            .map(DataBlock::statistics)
+            .reduce(|merged, block| {
+                merged
+                    .iter()
+                    .zip(block.iter())
+                    .map(|(a, b)| a.merge(b))
+                    .collect()
+            })
+    }
+}
@@ -77,11 +77,10 @@ impl DataBlock {
             })
             .collect();
 
-        Ok(DataBlock::new_with_meta(
-            after_columns,
-            indices.len(),
-            self.get_meta().cloned(),
-        ))
+        Ok(
+            DataBlock::new_with_meta(after_columns, indices.len(), self.get_meta().cloned())
+                .with_optional_origin(self.origin()),
+        )
     }
 }
 
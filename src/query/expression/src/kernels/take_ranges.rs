@@ -65,7 +65,7 @@ impl DataBlock {
                 _ => entry.clone(),
             })
             .collect();
-        Ok(DataBlock::new(columns, num_rows))
+        Ok(DataBlock::new(columns, num_rows).with_optional_origin(self.origin()))
     }
 }
 
@@ -17,6 +17,8 @@
 #![allow(unused_parens)]
 #![allow(unused_variables)]
 #![allow(clippy::redundant_closure)]
+use databend_common_arrow::arrow::bitmap::Bitmap;
+
 use crate::property::Domain;
 use crate::types::nullable::NullableColumn;
 use crate::types::nullable::NullableDomain;
@@ -3463,6 +3465,9 @@ pub fn passthrough_nullable_1_arg<I1: ArgType, O: ArgType>(
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(ValueRef::Column(arg1.column), ctx)
                 .into_column()
                 .unwrap();
@@ -3499,6 +3504,9 @@ pub fn passthrough_nullable_2_arg<I1: ArgType, I2: ArgType, O: ArgType>(
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(ValueRef::Column(arg1.column), ValueRef::Scalar(arg2), ctx)
                 .into_column()
                 .unwrap();
@@ -3512,6 +3520,9 @@ pub fn passthrough_nullable_2_arg<I1: ArgType, I2: ArgType, O: ArgType>(
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(ValueRef::Scalar(arg1), ValueRef::Column(arg2.column), ctx)
                 .into_column()
                 .unwrap();
@@ -3526,6 +3537,9 @@ pub fn passthrough_nullable_2_arg<I1: ArgType, I2: ArgType, O: ArgType>(
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -3583,6 +3597,9 @@ pub fn passthrough_nullable_3_arg<I1: ArgType, I2: ArgType, I3: ArgType, O: ArgT
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Scalar(arg2),
@@ -3601,6 +3618,9 @@ pub fn passthrough_nullable_3_arg<I1: ArgType, I2: ArgType, I3: ArgType, O: ArgT
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Column(arg2.column),
@@ -3620,6 +3640,9 @@ pub fn passthrough_nullable_3_arg<I1: ArgType, I2: ArgType, I3: ArgType, O: ArgT
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -3638,6 +3661,9 @@ pub fn passthrough_nullable_3_arg<I1: ArgType, I2: ArgType, I3: ArgType, O: ArgT
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Scalar(arg2),
@@ -3657,6 +3683,9 @@ pub fn passthrough_nullable_3_arg<I1: ArgType, I2: ArgType, I3: ArgType, O: ArgT
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Scalar(arg2),
@@ -3676,6 +3705,9 @@ pub fn passthrough_nullable_3_arg<I1: ArgType, I2: ArgType, I3: ArgType, O: ArgT
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Column(arg2.column),
@@ -3697,6 +3729,9 @@ pub fn passthrough_nullable_3_arg<I1: ArgType, I2: ArgType, I3: ArgType, O: ArgT
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -3771,6 +3806,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Scalar(arg2),
@@ -3795,6 +3833,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Column(arg2.column),
@@ -3820,6 +3861,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -3844,6 +3888,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Scalar(arg2),
@@ -3869,6 +3916,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Scalar(arg2),
@@ -3894,6 +3944,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Column(arg2.column),
@@ -3921,6 +3974,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -3945,6 +4001,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Scalar(arg2),
@@ -3970,6 +4029,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Scalar(arg2),
@@ -3995,6 +4057,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Column(arg2.column),
@@ -4022,6 +4087,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -4047,6 +4115,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Scalar(arg2),
@@ -4074,6 +4145,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Scalar(arg2),
@@ -4101,6 +4175,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Column(arg2.column),
@@ -4131,6 +4208,9 @@ pub fn passthrough_nullable_4_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -4213,6 +4293,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Scalar(arg2),
@@ -4239,6 +4322,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Column(arg2.column),
@@ -4266,6 +4352,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -4292,6 +4381,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Scalar(arg2),
@@ -4319,6 +4411,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Scalar(arg2),
@@ -4346,6 +4441,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Column(arg2.column),
@@ -4375,6 +4473,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -4401,6 +4502,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Scalar(arg2),
@@ -4428,6 +4532,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Scalar(arg2),
@@ -4455,6 +4562,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Column(arg2.column),
@@ -4484,6 +4594,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -4511,6 +4624,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Scalar(arg2),
@@ -4540,6 +4656,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Scalar(arg2),
@@ -4569,6 +4688,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Column(arg2.column),
@@ -4601,6 +4723,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -4627,6 +4752,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Scalar(arg2),
@@ -4654,6 +4782,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Scalar(arg2),
@@ -4681,6 +4812,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Column(arg2.column),
@@ -4710,6 +4844,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -4737,6 +4874,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Scalar(arg2),
@@ -4766,6 +4906,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Scalar(arg2),
@@ -4795,6 +4938,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Column(arg2.column),
@@ -4827,6 +4973,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -4854,6 +5003,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Scalar(arg2),
@@ -4883,6 +5035,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Scalar(arg2),
@@ -4912,6 +5067,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Column(arg2.column),
@@ -4944,6 +5102,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -4973,6 +5134,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Scalar(arg2),
@@ -5005,6 +5169,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Scalar(arg2),
@@ -5037,6 +5204,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Scalar(arg1),
                 ValueRef::Column(arg2.column),
@@ -5072,6 +5242,9 @@ pub fn passthrough_nullable_5_arg<
                 .map(|valid| valid & (&and_validity))
                 .unwrap_or(and_validity);
             ctx.validity = Some(validity.clone());
+            if validity.unset_bits() == validity.len() {
+                return all_null_column(validity, ctx);
+            }
             let column = func(
                 ValueRef::Column(arg1.column),
                 ValueRef::Column(arg2.column),
@@ -5087,6 +5260,18 @@ pub fn passthrough_nullable_5_arg<
     }
 }
 
+// All the rows are NULL, the function is not evaluated.
+fn all_null_column<O: ArgType>(validity: Bitmap, ctx: &EvalContext) -> Value<NullableType<O>> {
+    let mut builder = O::create_builder(validity.len(), ctx.generics);
+    for _ in 0..validity.len() {
+        O::push_default(&mut builder);
+    }
+    Value::Column(NullableColumn {
+        column: O::build_column(builder),
+        validity,
+    })
+}
+
 pub fn combine_nullable_1_arg<I1: ArgType, O: ArgType>(
     func: impl for<'a> Fn(ValueRef<'a, I1>, &mut EvalContext) -> Value<NullableType<O>>
     + Copy
@@ -53,6 +53,28 @@ pub fn check_date(days: i64) -> Result<i32, String> {
     }
 }
 
+/// Maximum date of the ClickHouse `Date` type, which is the day offset from 1970-01-01 in a
+/// `UInt16`, that is 2149-06-06.
+pub const DATE16_MAX: i32 = u16::MAX as i32;
+
+/// Converts a date to the day offset of the ClickHouse `Date` type, which only holds the
+/// dates from 1970-01-01 to 2149-06-06.
+#[inline]
+pub fn date_to_days_u16(days: i32) -> Result<u16, String> {
+    u16::try_from(days).map_err(|_| {
+        format!(
+            "date {} is out of the range of ClickHouse Date [1970-01-01, 2149-06-06]",
+            date_to_string(days, Tz::UTC)
+        )
+    })
+}
+
+/// Converts the day offset of the ClickHouse `Date` type to a date, it is always in range.
+#[inline]
+pub fn days_u16_to_date(days: u16) -> i32 {
+    days as i32
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DateType;
 
@@ -36,15 +36,9 @@ pub fn assert_blocks_eq(expect: Vec<&str>, blocks: &[DataBlock]) {
 }
 
 pub fn assert_blocks_eq_with_name(test_name: &str, expect: Vec<&str>, blocks: &[DataBlock]) {
-    let expected_lines: Vec<String> = expect.iter().map(|&s| s.into()).collect();
-    let formatted = pretty_format_blocks(blocks).unwrap();
+    let formatted = format_blocks_for_assert(test_name, blocks);
     let actual_lines: Vec<&str> = formatted.trim().lines().collect();
-
-    assert_eq!(
-        expected_lines, actual_lines,
-        "{:#?}\n\nexpected:\n\n{:#?}\nactual:\n\n{:#?}\n\n",
-        test_name, expected_lines, actual_lines
-    );
+    assert_table_lines_eq(test_name, &expect, &actual_lines);
 }
 
 /// Sorted assert.
@@ -70,39 +64,33 @@ pub fn assert_block_value_sort_eq(a: &DataBlock, b: &DataBlock) {
     let a: Vec<&str> = get_lines(&a);
     let b: Vec<&str> = get_lines(&b);
 
-    assert_eq!(a, b);
+    assert_table_lines_eq("", &a, &b);
 }
 
 /// Assert with order insensitive.
 /// ['a', 'b'] equals ['b', 'a']
 pub fn assert_blocks_sorted_eq_with_name(test_name: &str, expect: Vec<&str>, blocks: &[DataBlock]) {
-    let mut expected_lines: Vec<String> = expect.iter().map(|&s| s.into()).collect();
-
-    // sort except for header + footer
-    let num_lines = expected_lines.len();
-    if num_lines > 3 {
-        expected_lines.as_mut_slice()[2..num_lines - 1].sort_unstable()
-    }
+    let mut expected_lines = expect;
+    sort_table_rows(&mut expected_lines);
 
-    let formatted = pretty_format_blocks(blocks).unwrap();
+    let formatted = format_blocks_for_assert(test_name, blocks);
     let actual_lines: Vec<&str> = get_lines(&formatted);
 
-    assert_eq!(
-        expected_lines, actual_lines,
-        "{:#?}\n\nexpected:\n\n{:#?}\nactual:\n\n{:#?}\n\n",
-        test_name, expected_lines, actual_lines
-    );
+    assert_table_lines_eq(test_name, &expected_lines, &actual_lines);
 }
 
 fn get_lines(formatted: &str) -> Vec<&str> {
     let mut actual_lines: Vec<&str> = formatted.trim().lines().collect();
+    sort_table_rows(&mut actual_lines);
+    actual_lines
+}
 
-    // sort except for header + footer
-    let num_lines = actual_lines.len();
+// Sort the lines except for the header and the footer.
+fn sort_table_rows(lines: &mut [&str]) {
+    let num_lines = lines.len();
     if num_lines > 3 {
-        actual_lines.as_mut_slice()[2..num_lines - 1].sort_unstable()
+        lines[2..num_lines - 1].sort_unstable()
     }
-    actual_lines
 }
 
 /// Assert with order insensitive.
@@ -112,29 +100,148 @@ pub fn assert_two_blocks_sorted_eq_with_name(
     expect: &[DataBlock],
     blocks: &[DataBlock],
 ) {
-    let expected = pretty_format_blocks(expect).unwrap();
-    let mut expected_lines: Vec<&str> = expected.trim().lines().collect();
+    let expected = format_blocks_for_assert(test_name, expect);
+    let expected_lines: Vec<&str> = get_lines(&expected);
 
-    // sort except for header + footer
-    let num_lines = expected_lines.len();
-    if num_lines > 3 {
-        expected_lines.as_mut_slice()[2..num_lines - 1].sort_unstable()
+    let formatted = format_blocks_for_assert(test_name, blocks);
+    let actual_lines: Vec<&str> = get_lines(&formatted);
+
+    assert_table_lines_eq(test_name, &expected_lines, &actual_lines);
+}
+
+// The blocks of different column types can't be concatenated into one table.
+fn format_blocks_for_assert(test_name: &str, blocks: &[DataBlock]) -> String {
+    let column_types = |block: &DataBlock| {
+        block
+            .columns()
+            .iter()
+            .map(|entry| entry.data_type.to_string())
+            .collect::<Vec<_>>()
+    };
+    if let Some(first) = blocks.first() {
+        let first_types = column_types(first);
+        for (i, block) in blocks.iter().enumerate().skip(1) {
+            let types = column_types(block);
+            assert!(
+                types == first_types,
+                "{:#?}\n\nschema mismatch: block 0 has columns {:?}, but block {} has columns {:?}",
+                test_name,
+                first_types,
+                i,
+                types
+            );
+        }
     }
+    pretty_format_blocks(blocks).unwrap()
+}
 
-    let formatted = pretty_format_blocks(blocks).unwrap();
-    let mut actual_lines: Vec<&str> = formatted.trim().lines().collect();
+fn assert_table_lines_eq(test_name: &str, expected: &[&str], actual: &[&str]) {
+    if let Some(diff) = diff_table_lines(expected, actual) {
+        panic!("{:#?}\n\n{}", test_name, diff);
+    }
+}
 
-    // sort except for header + footer
-    let num_lines = actual_lines.len();
-    if num_lines > 3 {
-        actual_lines.as_mut_slice()[2..num_lines - 1].sort_unstable()
+/// Describes the difference between the expected and the actual lines of a pretty formatted
+/// table, returns `None` if they are equal.
+///
+/// The header is compared first and reported as a schema mismatch. Otherwise the message has
+/// the row counts, the first mismatching line and a unified diff of the lines.
+pub fn diff_table_lines(expected: &[&str], actual: &[&str]) -> Option<String> {
+    if expected == actual {
+        return None;
     }
 
-    assert_eq!(
-        expected_lines, actual_lines,
-        "{:#?}\n\nexpected:\n\n{:#?}\nactual:\n\n{:#?}\n\n",
-        test_name, expected_lines, actual_lines
+    // The header is the second line, the rows are between the third and the last line.
+    if expected.get(1) != actual.get(1) {
+        return Some(format!(
+            "schema mismatch:\nexpected: {}\nactual:   {}",
+            expected.get(1).unwrap_or(&""),
+            actual.get(1).unwrap_or(&"")
+        ));
+    }
+
+    let num_rows = |lines: &[&str]| lines.len().saturating_sub(4);
+    let mut message = format!(
+        "expected {} rows, actual {} rows\n",
+        num_rows(expected),
+        num_rows(actual)
     );
+
+    let first_mismatch = (0..expected.len().max(actual.len()))
+        .find(|i| expected.get(*i) != actual.get(*i))
+        .unwrap();
+    message.push_str(&format!(
+        "first mismatch at line {}:\nexpected: {}\nactual:   {}\n\n",
+        first_mismatch + 1,
+        expected.get(first_mismatch).unwrap_or(&"<none>"),
+        actual.get(first_mismatch).unwrap_or(&"<none>")
+    ));
+
+    message.push_str("--- expected\n+++ actual\n");
+    message.push_str(&unified_diff(expected, actual, 3));
+    Some(message)
+}
+
+// A line diff based on the longest common subsequence, the equal lines which are further
+// than `context` lines from a change are omitted.
+fn unified_diff(expected: &[&str], actual: &[&str], context: usize) -> String {
+    let (n, m) = (expected.len(), actual.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && expected[i] == actual[j] {
+            lines.push((' ', expected[i]));
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            lines.push(('+', actual[j]));
+            j += 1;
+        } else {
+            lines.push(('-', expected[i]));
+            i += 1;
+        }
+    }
+
+    let changed = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (tag, _))| *tag != ' ')
+        .map(|(pos, _)| pos)
+        .collect::<Vec<_>>();
+    let near_change = |pos: usize| {
+        changed
+            .iter()
+            .any(|changed| pos + context >= *changed && pos <= changed + context)
+    };
+
+    let mut diff = String::new();
+    let mut skipped = false;
+    for (pos, (tag, line)) in lines.iter().enumerate() {
+        if near_change(pos) {
+            if skipped {
+                diff.push_str("...\n");
+                skipped = false;
+            }
+            diff.push_str(&format!("{tag}{line}\n"));
+        } else {
+            skipped = true;
+        }
+    }
+    if skipped {
+        diff.push_str("...\n");
+    }
+    diff
 }
 
 pub fn box_render(
@@ -19,11 +19,15 @@ use std::fmt::Formatter;
 use chrono_tz::Tz;
 use comfy_table::Cell;
 use comfy_table::Table;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
 use databend_common_io::display_decimal_128;
 use databend_common_io::display_decimal_256;
+use databend_common_io::escape_string_with_quote;
 use geozero::wkb::Ewkb;
 use geozero::ToWkt;
 use itertools::Itertools;
+use num_traits::Float;
 use num_traits::FromPrimitive;
 use roaring::RoaringTreemap;
 use rust_decimal::Decimal;
@@ -267,6 +271,72 @@ impl Display for Scalar {
     }
 }
 
+impl<'a> ScalarRef<'a> {
+    /// Render the scalar as a SQL literal that parses back into the same value.
+    ///
+    /// Timestamps are in UTC with the `Z` suffix, so they don't depend on the session timezone.
+    /// Floats are casts from their shortest round-trip text, a bare `1.0` would be parsed
+    /// as a decimal and NaN and the infinities have no literal syntax.
+    ///
+    /// Bitmap, tuple, variant and geometry values have no literal syntax and return an error.
+    pub fn to_sql_literal(&self) -> Result<String> {
+        match self {
+            ScalarRef::Null => Ok("NULL".to_string()),
+            ScalarRef::EmptyArray => Ok("[]".to_string()),
+            ScalarRef::EmptyMap => Ok("{}".to_string()),
+            ScalarRef::Number(NumberScalar::Float32(val)) => Ok(format!("'{:?}'::FLOAT32", val.0)),
+            ScalarRef::Number(NumberScalar::Float64(val)) => Ok(format!("'{:?}'::FLOAT64", val.0)),
+            ScalarRef::Number(val) => Ok(val.to_string()),
+            ScalarRef::Decimal(val) => Ok(val.to_string()),
+            ScalarRef::Boolean(val) => Ok(val.to_string()),
+            ScalarRef::Binary(s) => Ok(format!(
+                "X'{}'",
+                s.iter().map(|c| format!("{c:02X}")).join("")
+            )),
+            ScalarRef::String(s) => Ok(format!("'{}'", escape_string_with_quote(s, Some('\'')))),
+            ScalarRef::Timestamp(t) => {
+                Ok(format!("TIMESTAMP '{}Z'", timestamp_to_string(*t, Tz::UTC)))
+            }
+            ScalarRef::Date(d) => Ok(format!("DATE '{}'", date_to_string(*d as i64, Tz::UTC))),
+            ScalarRef::Array(col) => {
+                let items = col
+                    .iter()
+                    .map(|item| item.to_sql_literal())
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(format!("[{}]", items.join(", ")))
+            }
+            ScalarRef::Map(col) => {
+                let kv_col = KvPair::<AnyType, AnyType>::try_downcast_column(col).unwrap();
+                let items = kv_col
+                    .iter()
+                    .map(|(key, value)| -> Result<String> {
+                        Ok(format!(
+                            "{}:{}",
+                            key.to_sql_literal()?,
+                            value.to_sql_literal()?
+                        ))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(format!("{{{}}}", items.join(", ")))
+            }
+            ScalarRef::Bitmap(_)
+            | ScalarRef::Tuple(_)
+            | ScalarRef::Variant(_)
+            | ScalarRef::Geometry(_) => Err(ErrorCode::BadArguments(format!(
+                "{} value has no SQL literal syntax",
+                self.infer_data_type()
+            ))),
+        }
+    }
+}
+
+impl Scalar {
+    /// Render the scalar as a SQL literal, see [`ScalarRef::to_sql_literal`].
+    pub fn to_sql_literal(&self) -> Result<String> {
+        self.as_ref().to_sql_literal()
+    }
+}
+
 impl Debug for NumberScalar {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -1035,26 +1105,44 @@ impl Display for Domain {
     }
 }
 
-/// Display a float as with fixed number of fractional digits to avoid test failures due to
-/// rounding differences between MacOS and Linus.
-fn display_f32(num: f32) -> String {
-    match Decimal::from_f32(num) {
-        Some(d) => d
-            .round_dp_with_strategy(FLOAT_NUM_FRAC_DIGITS, RoundingStrategy::ToZero)
-            .normalize()
-            .to_string(),
-        None => num.to_string(),
-    }
+/// Display a f32 with at most 10 fractional digits, see [`display_float`].
+pub fn display_f32(num: f32) -> String {
+    display_float(num, Decimal::from_f32, FLOAT_NUM_FRAC_DIGITS)
 }
 
-/// Display a float as with fixed number of fractional digits to avoid test failures due to
-/// rounding differences between MacOS and Linus.
-fn display_f64(num: f64) -> String {
-    match Decimal::from_f64(num) {
+/// Display a f64 with at most 10 fractional digits, see [`display_float`].
+pub fn display_f64(num: f64) -> String {
+    display_float(num, Decimal::from_f64, FLOAT_NUM_FRAC_DIGITS)
+}
+
+/// Display a float with at most `frac_digits` fractional digits, the scalars, the columns and
+/// the domains are all displayed with it.
+///
+/// The float is never displayed in the scientific notation, and the digits after `frac_digits`
+/// are truncated to avoid test failures due to rounding differences between MacOS and Linux.
+/// NaN and the infinities are displayed as `nan`, `inf` and `-inf` like ClickHouse, and the
+/// negative zero is displayed as `-0`.
+pub fn display_float<T: Float + Display>(
+    num: T,
+    to_decimal: impl Fn(T) -> Option<Decimal>,
+    frac_digits: u32,
+) -> String {
+    if num.is_nan() {
+        return "nan".to_string();
+    }
+    match (num.is_infinite(), num.is_zero(), num.is_sign_negative()) {
+        (true, _, false) => return "inf".to_string(),
+        (true, _, true) => return "-inf".to_string(),
+        (_, true, true) => return "-0".to_string(),
+        _ => {}
+    }
+
+    match to_decimal(num) {
         Some(d) => d
-            .round_dp_with_strategy(FLOAT_NUM_FRAC_DIGITS, RoundingStrategy::ToZero)
+            .round_dp_with_strategy(frac_digits, RoundingStrategy::ToZero)
             .normalize()
             .to_string(),
+        // Out of the range of decimal, the display of a finite float is not scientific.
         None => num.to_string(),
     }
 }
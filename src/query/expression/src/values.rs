@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::hash::Hash;
 use std::io::Read;
@@ -22,6 +23,7 @@ use base64::engine::general_purpose;
 use base64::prelude::*;
 use borsh::BorshDeserialize;
 use borsh::BorshSerialize;
+use chrono_tz::Tz;
 use databend_common_arrow::arrow::bitmap::Bitmap;
 use databend_common_arrow::arrow::bitmap::MutableBitmap;
 use databend_common_arrow::arrow::buffer::Buffer;
@@ -51,6 +53,8 @@ use crate::types::binary::BinaryColumn;
 use crate::types::binary::BinaryColumnBuilder;
 use crate::types::bitmap::BitmapType;
 use crate::types::boolean::BooleanDomain;
+use crate::types::date::check_date;
+use crate::types::date::string_to_date;
 use crate::types::date::DATE_MAX;
 use crate::types::date::DATE_MIN;
 use crate::types::decimal::Decimal;
@@ -76,6 +80,9 @@ use crate::types::string::StringColumn;
 use crate::types::string::StringColumnBuilder;
 use crate::types::string::StringDomain;
 use crate::types::timestamp::check_timestamp;
+use crate::types::timestamp::string_to_timestamp;
+use crate::types::timestamp::MICROS_IN_A_MILLI;
+use crate::types::timestamp::MICROS_IN_A_SEC;
 use crate::types::timestamp::TIMESTAMP_MAX;
 use crate::types::timestamp::TIMESTAMP_MIN;
 use crate::types::variant::JSONB_NULL;
@@ -85,6 +92,7 @@ use crate::utils::arrow::bitmap_into_mut;
 use crate::utils::arrow::buffer_into_mut;
 use crate::utils::arrow::deserialize_column;
 use crate::utils::arrow::serialize_column;
+use crate::utils::serialize::uniform_date;
 use crate::utils::FromData;
 use crate::values::decimal::DecimalColumnVec;
 use crate::values::map::KvPair;
@@ -306,9 +314,73 @@ impl<T: Decimal> Value<DecimalType<T>> {
     }
 }
 
+// The cached column lives as long as the thread and is not counted by the memory tracker,
+// so a larger constant column, e.g. a long string of every row, is never kept.
+const MAX_CACHED_CONSTANT_COLUMN_BYTES: usize = 512 * 1024;
+
+thread_local! {
+    static LAST_CONSTANT_COLUMN: RefCell<Option<(Scalar, DataType, Column)>> =
+        const { RefCell::new(None) };
+}
+
+// The equality of the floats and the variants is not exact, e.g. `-0.0` equals `0.0`,
+// so their constant columns are not reused.
+fn is_exactly_comparable(scalar: &Scalar) -> bool {
+    match scalar {
+        Scalar::Null
+        | Scalar::Boolean(_)
+        | Scalar::Date(_)
+        | Scalar::Timestamp(_)
+        | Scalar::Decimal(_)
+        | Scalar::String(_)
+        | Scalar::Binary(_) => true,
+        Scalar::Number(n) => n.is_integer(),
+        _ => false,
+    }
+}
+
 impl Value<AnyType> {
+    /// Slice the value without copying the underlying data, `num_rows` is the number of
+    /// rows the value represents. A scalar stays a scalar since every row shares it.
+    ///
+    /// The caller must keep `range` within `0..num_rows`. It is only checked in debug builds
+    /// for a scalar, slicing a column out of its range still panics.
+    pub fn slice(&self, range: Range<usize>, num_rows: usize) -> Self {
+        debug_assert!(
+            range.end <= num_rows,
+            "range {:?} out of len {}",
+            range,
+            num_rows
+        );
+        match self {
+            Value::Scalar(s) => Value::Scalar(s.clone()),
+            Value::Column(c) => Value::Column(c.slice(range)),
+        }
+    }
+
+    /// Materializes the value into a column of `num_rows` rows.
+    ///
+    /// The same constant is usually materialized for every block, the last constant column
+    /// built on the thread is reused if the constant and the number of rows are not changed.
+    /// The columns are immutable and share their buffers, so the reused column is a cheap clone.
+    /// Only the columns of at most `MAX_CACHED_CONSTANT_COLUMN_BYTES` bytes are kept.
     pub fn convert_to_full_column(&self, ty: &DataType, num_rows: usize) -> Column {
         match self {
+            Value::Scalar(s) if is_exactly_comparable(s) => LAST_CONSTANT_COLUMN.with(|last| {
+                let mut last = last.borrow_mut();
+                if let Some((scalar, data_type, column)) = last.as_ref() {
+                    if column.len() == num_rows && data_type == ty && scalar == s {
+                        return column.clone();
+                    }
+                }
+                let column = ColumnBuilder::repeat(&s.as_ref(), num_rows, ty).build();
+                *last = if column.memory_size() <= MAX_CACHED_CONSTANT_COLUMN_BYTES {
+                    Some((s.clone(), ty.clone(), column.clone()))
+                } else {
+                    None
+                };
+                column
+            }),
             Value::Scalar(s) => {
                 let builder = ColumnBuilder::repeat(&s.as_ref(), num_rows, ty);
                 builder.build()
@@ -462,6 +534,160 @@ impl Scalar {
     }
 }
 
+macro_rules! impl_try_get_integer {
+    ($($name:ident => $t:ty),* $(,)?) => {
+        $(
+            #[doc = concat!("Returns the value as `", stringify!($t), "`.")]
+            ///
+            /// The integers, and the floats and decimals without fractional part, are accepted
+            /// if they are in the range, the other values and NULL are `BadDataValueType` errors.
+            pub fn $name(&self) -> Result<$t> {
+                self.as_lossless_i128()
+                    .and_then(|v| <$t>::try_from(v).ok())
+                    .ok_or_else(|| self.bad_data_value_type(stringify!($t)))
+            }
+        )*
+    };
+}
+
+impl Scalar {
+    impl_try_get_integer! {
+        try_get_i64 => i64,
+        try_get_u64 => u64,
+    }
+
+    /// Returns the string value, the numbers and booleans are formatted to strings.
+    pub fn try_get_string(&self) -> Result<String> {
+        match self {
+            Scalar::String(s) => Ok(s.clone()),
+            Scalar::Number(_) | Scalar::Decimal(_) | Scalar::Boolean(_) => Ok(self.to_string()),
+            _ => Err(self.bad_data_value_type("String")),
+        }
+    }
+
+    /// Returns the boolean value, the non-zero numbers are true like MySQL.
+    pub fn try_get_bool(&self) -> Result<bool> {
+        match self {
+            Scalar::Boolean(b) => Ok(*b),
+            Scalar::Number(NumberScalar::Float32(v)) => Ok(v.0 != 0.0),
+            Scalar::Number(NumberScalar::Float64(v)) => Ok(v.0 != 0.0),
+            Scalar::Number(n) => Ok(number_as_i128(n) != Some(0)),
+            Scalar::Decimal(d) => Ok(decimal_as_i256(d).0 != i256::ZERO),
+            _ => Err(self.bad_data_value_type("Boolean")),
+        }
+    }
+
+    /// Parses a date like `2021-04-01` to a `Date` scalar.
+    pub fn date_from_str(s: &str) -> Result<Scalar> {
+        string_to_date(s, Tz::UTC)
+            .and_then(|date| check_date(uniform_date(date) as i64).ok())
+            .map(Scalar::Date)
+            .ok_or_else(|| ErrorCode::BadArguments(format!("Invalid date '{}'", s)))
+    }
+
+    /// Parses a timestamp like `2021-04-01 12:30:00.5` in UTC to a `Timestamp` scalar.
+    pub fn timestamp_from_str(s: &str) -> Result<Scalar> {
+        string_to_timestamp(s, Tz::UTC)
+            .and_then(|ts| check_timestamp(ts.timestamp_micros()).ok())
+            .map(Scalar::Timestamp)
+            .ok_or_else(|| ErrorCode::BadArguments(format!("Invalid timestamp '{}'", s)))
+    }
+
+    /// Creates a `Timestamp` scalar from the seconds since `1970-01-01 00:00:00` UTC.
+    pub fn timestamp_from_secs(secs: i64) -> Result<Scalar> {
+        Self::timestamp_from_micros(secs.saturating_mul(MICROS_IN_A_SEC))
+    }
+
+    /// Creates a `Timestamp` scalar from the milliseconds since `1970-01-01 00:00:00` UTC.
+    pub fn timestamp_from_millis(millis: i64) -> Result<Scalar> {
+        Self::timestamp_from_micros(millis.saturating_mul(MICROS_IN_A_MILLI))
+    }
+
+    /// Creates a `Timestamp` scalar from the microseconds since `1970-01-01 00:00:00` UTC.
+    pub fn timestamp_from_micros(micros: i64) -> Result<Scalar> {
+        check_timestamp(micros)
+            .map(Scalar::Timestamp)
+            .map_err(ErrorCode::BadArguments)
+    }
+
+    /// Creates an `Array` scalar from the values, the element type is `T::data_type()`.
+    ///
+    /// Use `NullableType<T>` to build an array with NULL elements.
+    pub fn array_from_iter<T: ArgType>(iter: impl IntoIterator<Item = T::Scalar>) -> Scalar {
+        Scalar::Array(T::upcast_column(T::column_from_iter(iter.into_iter(), &[])))
+    }
+
+    /// Returns the element type of an `Array` scalar, it is also known for the empty arrays
+    /// built from a typed column. `EmptyArray` has no element type.
+    pub fn array_data_type(&self) -> Option<DataType> {
+        match self {
+            Scalar::Array(col) => Some(col.data_type()),
+            _ => None,
+        }
+    }
+
+    /// Extracts the elements of an `Array` scalar as values of `T`, NULL elements are `None`.
+    ///
+    /// An element that is not of type `T` is a `BadDataValueType` error with its index.
+    pub fn try_into_vec<T: ArgType>(&self) -> Result<Vec<Option<T::Scalar>>> {
+        let col = match self {
+            Scalar::EmptyArray => return Ok(vec![]),
+            Scalar::Array(col) => col,
+            _ => return Err(self.bad_data_value_type("Array")),
+        };
+        col.iter()
+            .enumerate()
+            .map(|(index, item)| match item {
+                ScalarRef::Null => Ok(None),
+                item => T::try_downcast_scalar(&item)
+                    .map(|v| Some(T::to_owned_scalar(v)))
+                    .ok_or_else(|| {
+                        ErrorCode::BadDataValueType(format!(
+                            "Expected a {} value at array index {}, but got {} of type {}",
+                            T::data_type(),
+                            index,
+                            item,
+                            item.infer_data_type()
+                        ))
+                    }),
+            })
+            .collect()
+    }
+
+    fn as_lossless_i128(&self) -> Option<i128> {
+        match self {
+            Scalar::Number(NumberScalar::Float32(v)) => float_as_i128(v.0 as f64),
+            Scalar::Number(NumberScalar::Float64(v)) => float_as_i128(v.0),
+            Scalar::Number(n) => number_as_i128(n),
+            Scalar::Decimal(d) => {
+                let (v, scale) = decimal_as_i256(d);
+                let base = i256::e(scale as u32);
+                if v % base != i256::ZERO {
+                    return None;
+                }
+                (v / base).to_int(0, false)
+            }
+            _ => None,
+        }
+    }
+
+    fn bad_data_value_type(&self, expected: &str) -> ErrorCode {
+        ErrorCode::BadDataValueType(format!(
+            "Expected a {} value, but got {} of type {}",
+            expected,
+            self,
+            self.as_ref().infer_data_type()
+        ))
+    }
+}
+
+fn float_as_i128(v: f64) -> Option<i128> {
+    if v.fract() != 0.0 {
+        return None;
+    }
+    num_traits::cast(v)
+}
+
 impl<'a> ScalarRef<'a> {
     pub fn to_owned(&self) -> Scalar {
         match self {
@@ -787,6 +1013,148 @@ impl PartialEq for ScalarRef<'_> {
     }
 }
 
+impl ScalarRef<'_> {
+    /// Compare two scalars, coercing numbers and decimals of different types, and dates
+    /// and timestamps, to a common type. Unlike [`PartialOrd`], which only compares values
+    /// of the same type, `Int32(1)` is less than `UInt64(2)` here.
+    ///
+    /// NULL is ordered after all the other values. Returns `None` if the values are not comparable.
+    pub fn cmp_coerced(&self, other: &ScalarRef) -> Option<Ordering> {
+        match (self, other) {
+            (ScalarRef::Null, ScalarRef::Null) => Some(Ordering::Equal),
+            (ScalarRef::Null, _) => Some(Ordering::Greater),
+            (_, ScalarRef::Null) => Some(Ordering::Less),
+            (ScalarRef::Number(n1), ScalarRef::Number(n2)) => Some(cmp_number_coerced(n1, n2)),
+            (ScalarRef::Decimal(d1), ScalarRef::Decimal(d2)) => Some(cmp_decimal_coerced(d1, d2)),
+            (ScalarRef::Number(n), ScalarRef::Decimal(d)) => Some(cmp_number_decimal(n, d)),
+            (ScalarRef::Decimal(d), ScalarRef::Number(n)) => {
+                Some(cmp_number_decimal(n, d).reverse())
+            }
+            (ScalarRef::Date(d), ScalarRef::Timestamp(t)) => {
+                Some((*d as i64 * MICROS_IN_A_DAY).cmp(t))
+            }
+            (ScalarRef::Timestamp(t), ScalarRef::Date(d)) => {
+                Some(t.cmp(&(*d as i64 * MICROS_IN_A_DAY)))
+            }
+            _ => self.partial_cmp(other),
+        }
+    }
+
+    /// Same as [`ScalarRef::cmp_coerced`], but NULL is ordered before all the other values.
+    pub fn cmp_coerced_nulls_first(&self, other: &ScalarRef) -> Option<Ordering> {
+        match (self, other) {
+            (ScalarRef::Null, ScalarRef::Null) => Some(Ordering::Equal),
+            (ScalarRef::Null, _) => Some(Ordering::Less),
+            (_, ScalarRef::Null) => Some(Ordering::Greater),
+            _ => self.cmp_coerced(other),
+        }
+    }
+
+    /// Compare two scalars with the semantics of `IS NOT DISTINCT FROM`: unlike `=`, two NULLs
+    /// are equal and NULL is unequal to any other value, so the result is never unknown.
+    pub fn not_distinct_from(&self, other: &ScalarRef) -> bool {
+        self.cmp_coerced(other) == Some(Ordering::Equal)
+    }
+}
+
+impl Scalar {
+    /// See [`ScalarRef::cmp_coerced`].
+    pub fn cmp_coerced(&self, other: &Scalar) -> Option<Ordering> {
+        self.as_ref().cmp_coerced(&other.as_ref())
+    }
+
+    /// See [`ScalarRef::cmp_coerced_nulls_first`].
+    pub fn cmp_coerced_nulls_first(&self, other: &Scalar) -> Option<Ordering> {
+        self.as_ref().cmp_coerced_nulls_first(&other.as_ref())
+    }
+
+    /// See [`ScalarRef::not_distinct_from`].
+    pub fn not_distinct_from(&self, other: &Scalar) -> bool {
+        self.as_ref().not_distinct_from(&other.as_ref())
+    }
+
+    /// Returns the smaller one of two values compared by [`Scalar::cmp_coerced`].
+    ///
+    /// NULL is ignored unless both values are NULL. `lhs` is returned if the values are
+    /// not comparable.
+    pub fn min_coerced(lhs: Scalar, rhs: Scalar) -> Scalar {
+        match lhs.cmp_coerced(&rhs) {
+            Some(Ordering::Greater) => rhs,
+            _ => lhs,
+        }
+    }
+
+    /// Returns the greater one of two values compared by [`Scalar::cmp_coerced`].
+    ///
+    /// NULL is ignored unless both values are NULL. `lhs` is returned if the values are
+    /// not comparable.
+    pub fn max_coerced(lhs: Scalar, rhs: Scalar) -> Scalar {
+        match lhs.cmp_coerced_nulls_first(&rhs) {
+            Some(Ordering::Less) => rhs,
+            _ => lhs,
+        }
+    }
+}
+
+const MICROS_IN_A_DAY: i64 = 24 * 3600 * MICROS_IN_A_SEC;
+
+fn number_as_i128(n: &NumberScalar) -> Option<i128> {
+    match *n {
+        NumberScalar::UInt8(v) => Some(v as i128),
+        NumberScalar::UInt16(v) => Some(v as i128),
+        NumberScalar::UInt32(v) => Some(v as i128),
+        NumberScalar::UInt64(v) => Some(v as i128),
+        NumberScalar::Int8(v) => Some(v as i128),
+        NumberScalar::Int16(v) => Some(v as i128),
+        NumberScalar::Int32(v) => Some(v as i128),
+        NumberScalar::Int64(v) => Some(v as i128),
+        NumberScalar::Float32(_) | NumberScalar::Float64(_) => None,
+    }
+}
+
+fn number_as_f64(n: &NumberScalar) -> F64 {
+    match *n {
+        NumberScalar::Float32(v) => F64::from(v.0 as f64),
+        NumberScalar::Float64(v) => v,
+        _ => F64::from(number_as_i128(n).unwrap() as f64),
+    }
+}
+
+fn cmp_number_coerced(n1: &NumberScalar, n2: &NumberScalar) -> Ordering {
+    match (number_as_i128(n1), number_as_i128(n2)) {
+        (Some(v1), Some(v2)) => v1.cmp(&v2),
+        _ => number_as_f64(n1).cmp(&number_as_f64(n2)),
+    }
+}
+
+fn decimal_as_i256(d: &DecimalScalar) -> (i256, u8) {
+    match *d {
+        DecimalScalar::Decimal128(v, size) => (i256::from(v), size.scale),
+        DecimalScalar::Decimal256(v, size) => (v, size.scale),
+    }
+}
+
+fn cmp_scaled_i256(v1: i256, scale1: u8, v2: i256, scale2: u8) -> Option<Ordering> {
+    let scale = scale1.max(scale2);
+    let v1 = v1.checked_mul(i256::e((scale - scale1) as u32))?;
+    let v2 = v2.checked_mul(i256::e((scale - scale2) as u32))?;
+    Some(v1.cmp(&v2))
+}
+
+fn cmp_decimal_coerced(d1: &DecimalScalar, d2: &DecimalScalar) -> Ordering {
+    let (v1, scale1) = decimal_as_i256(d1);
+    let (v2, scale2) = decimal_as_i256(d2);
+    cmp_scaled_i256(v1, scale1, v2, scale2)
+        .unwrap_or_else(|| F64::from(d1.to_float64()).cmp(&F64::from(d2.to_float64())))
+}
+
+fn cmp_number_decimal(n: &NumberScalar, d: &DecimalScalar) -> Ordering {
+    let (v, scale) = decimal_as_i256(d);
+    number_as_i128(n)
+        .and_then(|n| cmp_scaled_i256(i256::from(n), 0, v, scale))
+        .unwrap_or_else(|| number_as_f64(n).cmp(&F64::from(d.to_float64())))
+}
+
 impl Hash for ScalarRef<'_> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         match self {
@@ -0,0 +1,299 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_expression::infer_schema_type;
+use databend_common_expression::types::date::DATE_MAX;
+use databend_common_expression::types::date::DATE_MIN;
+use databend_common_expression::types::decimal::DecimalScalar;
+use databend_common_expression::types::decimal::MAX_DECIMAL128_PRECISION;
+use databend_common_expression::types::decimal::MAX_DECIMAL256_PRECISION;
+use databend_common_expression::types::timestamp::TIMESTAMP_MAX;
+use databend_common_expression::types::timestamp::TIMESTAMP_MIN;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::DecimalDataType;
+use databend_common_expression::types::DecimalSize;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::NumberScalar;
+use databend_common_expression::types::ALL_NUMERICS_TYPES;
+use databend_common_expression::Column;
+use databend_common_expression::ColumnBuilder;
+use databend_common_expression::Scalar;
+use databend_common_io::prelude::bincode_deserialize_from_slice;
+use databend_common_io::prelude::bincode_serialize_into_buf;
+use databend_common_io::prelude::borsh_deserialize_from_slice;
+use databend_common_io::prelude::borsh_serialize_into_buf;
+use ethnum::i256;
+use geo::Geometry;
+use geo::Point;
+use geozero::CoordDimensions;
+use geozero::ToWkb;
+use proptest::prelude::*;
+use roaring::RoaringTreemap;
+
+// The depth of the nested types, e.g. `Array(Tuple(Map(...)))`.
+const MAX_DEPTH: u32 = 3;
+
+fn arb_decimal_type() -> impl Strategy<Value = DataType> {
+    let decimal128 = (1..=MAX_DECIMAL128_PRECISION)
+        .prop_flat_map(|precision| (Just(precision), 0..=precision))
+        .prop_map(|(precision, scale)| {
+            DecimalDataType::Decimal128(DecimalSize { precision, scale })
+        });
+    let decimal256 = (MAX_DECIMAL128_PRECISION + 1..=MAX_DECIMAL256_PRECISION)
+        .prop_flat_map(|precision| (Just(precision), 0..=precision))
+        .prop_map(|(precision, scale)| {
+            DecimalDataType::Decimal256(DecimalSize { precision, scale })
+        });
+    prop_oneof![decimal128, decimal256].prop_map(DataType::Decimal)
+}
+
+// The types which can be the key of a map.
+fn arb_key_type() -> impl Strategy<Value = DataType> {
+    prop_oneof![
+        Just(DataType::Boolean),
+        Just(DataType::String),
+        prop::sample::select(ALL_NUMERICS_TYPES).prop_map(DataType::Number),
+        arb_decimal_type(),
+        Just(DataType::Timestamp),
+        Just(DataType::Date),
+    ]
+}
+
+fn arb_leaf_type() -> impl Strategy<Value = DataType> {
+    prop_oneof![
+        Just(DataType::Null),
+        Just(DataType::EmptyArray),
+        Just(DataType::EmptyMap),
+        Just(DataType::Binary),
+        arb_key_type(),
+        Just(DataType::Variant),
+        Just(DataType::Bitmap),
+        Just(DataType::Geometry),
+    ]
+}
+
+/// Generates any data type but `Generic`, the nested types are up to [`MAX_DEPTH`] deep.
+pub fn arb_data_type() -> BoxedStrategy<DataType> {
+    arb_leaf_type()
+        .prop_recursive(MAX_DEPTH, 16, 4, |inner| {
+            prop_oneof![
+                inner.clone().prop_map(|ty| ty.wrap_nullable()),
+                inner.clone().prop_map(|ty| DataType::Array(Box::new(ty))),
+                (arb_key_type(), inner.clone()).prop_map(|(key, value)| {
+                    DataType::Map(Box::new(DataType::Tuple(vec![key, value])))
+                }),
+                prop::collection::vec(inner, 1..4).prop_map(DataType::Tuple),
+            ]
+        })
+        .boxed()
+}
+
+fn arb_number(ty: NumberDataType) -> BoxedStrategy<Scalar> {
+    let number = match ty {
+        NumberDataType::UInt8 => any::<u8>().prop_map(NumberScalar::UInt8).boxed(),
+        NumberDataType::UInt16 => any::<u16>().prop_map(NumberScalar::UInt16).boxed(),
+        NumberDataType::UInt32 => any::<u32>().prop_map(NumberScalar::UInt32).boxed(),
+        NumberDataType::UInt64 => any::<u64>().prop_map(NumberScalar::UInt64).boxed(),
+        NumberDataType::Int8 => any::<i8>().prop_map(NumberScalar::Int8).boxed(),
+        NumberDataType::Int16 => any::<i16>().prop_map(NumberScalar::Int16).boxed(),
+        NumberDataType::Int32 => any::<i32>().prop_map(NumberScalar::Int32).boxed(),
+        NumberDataType::Int64 => any::<i64>().prop_map(NumberScalar::Int64).boxed(),
+        NumberDataType::Float32 => any::<f32>()
+            .prop_map(|v| NumberScalar::Float32(v.into()))
+            .boxed(),
+        NumberDataType::Float64 => any::<f64>()
+            .prop_map(|v| NumberScalar::Float64(v.into()))
+            .boxed(),
+    };
+    number.prop_map(Scalar::Number).boxed()
+}
+
+fn arb_decimal(ty: DecimalDataType) -> BoxedStrategy<Scalar> {
+    let decimal = match ty {
+        DecimalDataType::Decimal128(size) => {
+            let max = 10_i128.pow(size.precision as u32) - 1;
+            (-max..=max)
+                .prop_map(move |v| DecimalScalar::Decimal128(v, size))
+                .boxed()
+        }
+        // The precision is more than 38, so any i128 fits in it.
+        DecimalDataType::Decimal256(size) => any::<i128>()
+            .prop_map(move |v| DecimalScalar::Decimal256(i256::from(v), size))
+            .boxed(),
+    };
+    decimal.prop_map(Scalar::Decimal).boxed()
+}
+
+fn arb_json() -> impl Strategy<Value = serde_json::Value> {
+    let leaf = prop_oneof![
+        Just(serde_json::Value::Null),
+        any::<bool>().prop_map(serde_json::Value::from),
+        any::<i64>().prop_map(serde_json::Value::from),
+        ".*".prop_map(serde_json::Value::from),
+    ];
+    leaf.prop_recursive(2, 8, 3, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..3).prop_map(serde_json::Value::from),
+            prop::collection::btree_map(".*", inner, 0..3)
+                .prop_map(|map| serde_json::Value::Object(map.into_iter().collect())),
+        ]
+    })
+}
+
+fn build_column(items: &[Scalar], ty: &DataType) -> Column {
+    let mut builder = ColumnBuilder::with_capacity(ty, items.len());
+    for item in items {
+        builder.push(item.as_ref());
+    }
+    builder.build()
+}
+
+/// Generates the values of the data type.
+pub fn arb_scalar(ty: &DataType) -> BoxedStrategy<Scalar> {
+    match ty {
+        DataType::Null => Just(Scalar::Null).boxed(),
+        DataType::EmptyArray => Just(Scalar::EmptyArray).boxed(),
+        DataType::EmptyMap => Just(Scalar::EmptyMap).boxed(),
+        DataType::Boolean => any::<bool>().prop_map(Scalar::Boolean).boxed(),
+        DataType::Binary => prop::collection::vec(any::<u8>(), 0..16)
+            .prop_map(Scalar::Binary)
+            .boxed(),
+        DataType::String => any::<String>().prop_map(Scalar::String).boxed(),
+        DataType::Number(ty) => arb_number(*ty),
+        DataType::Decimal(ty) => arb_decimal(*ty),
+        DataType::Timestamp => (TIMESTAMP_MIN..=TIMESTAMP_MAX)
+            .prop_map(Scalar::Timestamp)
+            .boxed(),
+        DataType::Date => (DATE_MIN..=DATE_MAX).prop_map(Scalar::Date).boxed(),
+        DataType::Variant => arb_json()
+            .prop_map(|json| {
+                let value = jsonb::parse_value(json.to_string().as_bytes()).unwrap();
+                Scalar::Variant(value.to_vec())
+            })
+            .boxed(),
+        DataType::Bitmap => prop::collection::vec(any::<u64>(), 0..8)
+            .prop_map(|values| {
+                let rb = RoaringTreemap::from_iter(values);
+                let mut buf = vec![];
+                rb.serialize_into(&mut buf).unwrap();
+                Scalar::Bitmap(buf)
+            })
+            .boxed(),
+        DataType::Geometry => (-180.0..180.0_f64, -90.0..90.0_f64)
+            .prop_map(|(x, y)| {
+                let geometry = Geometry::from(Point::new(x, y));
+                Scalar::Geometry(geometry.to_ewkb(CoordDimensions::xy(), None).unwrap())
+            })
+            .boxed(),
+        DataType::Nullable(inner) => {
+            prop_oneof![1 => Just(Scalar::Null), 3 => arb_scalar(inner)].boxed()
+        }
+        DataType::Array(inner) => {
+            let inner = (**inner).clone();
+            prop::collection::vec(arb_scalar(&inner), 0..4)
+                .prop_map(move |items| Scalar::Array(build_column(&items, &inner)))
+                .boxed()
+        }
+        DataType::Map(inner) => {
+            let inner = (**inner).clone();
+            prop::collection::vec(arb_scalar(&inner), 0..4)
+                .prop_map(move |items| Scalar::Map(build_column(&items, &inner)))
+                .boxed()
+        }
+        DataType::Tuple(fields) => fields
+            .iter()
+            .map(arb_scalar)
+            .collect::<Vec<_>>()
+            .prop_map(Scalar::Tuple)
+            .boxed(),
+        DataType::Generic(_) => unreachable!(),
+    }
+}
+
+/// Generates a data type and a value of it.
+pub fn arb_typed_scalar() -> impl Strategy<Value = (DataType, Scalar)> {
+    arb_data_type().prop_flat_map(|ty| (Just(ty.clone()), arb_scalar(&ty)))
+}
+
+/// The value is of the type, and the type round-trips through the table type, so does the
+/// type inferred from the value.
+pub fn check_data_type(ty: &DataType, scalar: &Scalar) -> Result<(), TestCaseError> {
+    prop_assert!(scalar.as_ref().is_value_of_type(ty));
+
+    let inferred = scalar.as_ref().infer_data_type();
+    prop_assert!(scalar.as_ref().is_value_of_type(&inferred));
+
+    for ty in [ty, &inferred] {
+        let table_type = infer_schema_type(ty).map_err(|e| TestCaseError::fail(e.message()))?;
+        prop_assert_eq!(&DataType::from(&table_type), ty);
+    }
+    Ok(())
+}
+
+/// Formatting the value never panics.
+pub fn check_display(scalar: &Scalar) -> Result<(), TestCaseError> {
+    let _ = scalar.to_string();
+    let _ = scalar.as_ref().to_string();
+    let _ = format!("{scalar:?}");
+    Ok(())
+}
+
+/// The value round-trips through bincode and borsh.
+pub fn check_serde(scalar: &Scalar) -> Result<(), TestCaseError> {
+    let mut buf = vec![];
+    bincode_serialize_into_buf(&mut buf, scalar).unwrap();
+    let decoded: Scalar = bincode_deserialize_from_slice(&buf).unwrap();
+    prop_assert_eq!(&decoded, scalar);
+
+    let mut buf = vec![];
+    borsh_serialize_into_buf(&mut buf, scalar).unwrap();
+    let decoded: Scalar = borsh_deserialize_from_slice(&buf).unwrap();
+    prop_assert_eq!(&decoded, scalar);
+    Ok(())
+}
+
+/// A column repeating the value has the value in each row.
+pub fn check_repeat(ty: &DataType, scalar: &Scalar) -> Result<(), TestCaseError> {
+    let column = ColumnBuilder::repeat(&scalar.as_ref(), 3, ty).build();
+    prop_assert_eq!(column.len(), 3);
+    prop_assert_eq!(&column.data_type(), ty);
+
+    let value = column.index(1).unwrap().to_owned();
+    prop_assert_eq!(&value, scalar);
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn test_arbitrary_data_type((ty, scalar) in arb_typed_scalar()) {
+        check_data_type(&ty, &scalar)?;
+    }
+
+    #[test]
+    fn test_arbitrary_display((_, scalar) in arb_typed_scalar()) {
+        check_display(&scalar)?;
+    }
+
+    #[test]
+    fn test_arbitrary_serde((_, scalar) in arb_typed_scalar()) {
+        check_serde(&scalar)?;
+    }
+
+    #[test]
+    fn test_arbitrary_repeat((ty, scalar) in arb_typed_scalar()) {
+        check_repeat(&ty, &scalar)?;
+    }
+}
@@ -1,12 +1,22 @@
+use databend_common_arrow::arrow::bitmap::Bitmap;
+use databend_common_exception::ErrorCode;
+use databend_common_expression::block_debug::assert_blocks_sorted_eq;
 use databend_common_expression::block_debug::box_render;
+use databend_common_expression::block_debug::diff_table_lines;
+use databend_common_expression::block_origin;
 use databend_common_expression::types::string::StringColumnBuilder;
 use databend_common_expression::types::DataType;
 use databend_common_expression::types::Int32Type;
 use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::BlockEntry;
 use databend_common_expression::Column;
+use databend_common_expression::DataBlock;
 use databend_common_expression::DataField;
 use databend_common_expression::DataSchemaRefExt;
 use databend_common_expression::FromData;
+use databend_common_expression::Scalar;
+use databend_common_expression::Value;
 
 use crate::common::new_block;
 
@@ -25,6 +35,108 @@ fn test_split_block() {
     assert_eq!(sizes, vec![3, 3, 4]);
 }
 
+fn new_scalar_block(value: &str, num_rows: usize) -> DataBlock {
+    DataBlock::new(
+        vec![
+            BlockEntry::new(
+                DataType::Number(NumberDataType::Int32),
+                Value::Column(Int32Type::from_data(
+                    (0..num_rows as i32).collect::<Vec<_>>(),
+                )),
+            ),
+            BlockEntry::new(
+                DataType::String,
+                Value::Scalar(Scalar::String(value.to_string())),
+            ),
+        ],
+        num_rows,
+    )
+}
+
+#[test]
+fn test_slice_block() {
+    let block = new_scalar_block("abc", 10);
+
+    let sliced = block.slice(2..5);
+    assert_eq!(sliced.num_rows(), 3);
+    assert_eq!(
+        sliced.get_by_offset(0).value,
+        Value::Column(Int32Type::from_data(vec![2, 3, 4]))
+    );
+    // Slicing a constant keeps it a scalar.
+    assert_eq!(
+        sliced.get_by_offset(1).value,
+        Value::Scalar(Scalar::String("abc".to_string()))
+    );
+
+    let sizes = block
+        .split_by_rows_no_tail(4)
+        .iter()
+        .map(|b| {
+            assert!(b.get_by_offset(1).value.as_scalar().is_some());
+            b.num_rows()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(sizes, vec![4, 4, 2]);
+
+    // Slicing to the end of a scalar-only block is allowed.
+    let scalars = DataBlock::new(vec![block.get_by_offset(1).clone()], 10);
+    assert_eq!(scalars.slice(10..10).num_rows(), 0);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "out of len 10")]
+fn test_slice_scalar_out_of_range() {
+    let block = new_scalar_block("abc", 10);
+    let scalars = DataBlock::new(vec![block.get_by_offset(1).clone()], 10);
+    scalars.slice(8..12);
+}
+
+#[test]
+fn test_block_memory_size() {
+    let value = "a".repeat(100);
+    let full = new_block(&[
+        Int32Type::from_data(vec![0; 10]),
+        StringType::from_data(vec![value.as_str(); 10]),
+    ]);
+    let block = new_scalar_block(&value, 10);
+
+    // The scalar is counted once, not once per row.
+    assert_eq!(block.memory_size(), 10 * 4 + 100);
+    assert!(block.memory_size() < full.memory_size());
+    assert_eq!(block.slice(0..5).memory_size(), 5 * 4 + 100);
+}
+
+#[test]
+fn test_block_origin() {
+    let block = new_block(&[Int32Type::from_data(vec![1; 10])]);
+    assert_eq!(block.origin(), None);
+
+    // Blocks with the same data read from different positions have different origins.
+    let first = block.clone().with_origin(block_origin(b"part_1", 0));
+    let second = block.clone().with_origin(block_origin(b"part_1", 10));
+    let third = block.with_origin(block_origin(b"part_2", 0));
+    assert_ne!(first.origin(), second.origin());
+    assert_ne!(first.origin(), third.origin());
+
+    // The kernels building a block from the rows of one block keep the origin.
+    let filter = Bitmap::from_iter((0..10).map(|i| i % 2 == 0));
+    let filtered = first.clone().filter_with_bitmap(&filter).unwrap();
+    assert_eq!(filtered.origin(), first.origin());
+    let taken = first.take(&[1u32, 3, 5], &mut None).unwrap();
+    assert_eq!(taken.origin(), first.origin());
+
+    // The slices of a block start at different rows, so they have different origins.
+    let (slices, _) = first.split_by_rows(5);
+    assert!(slices.iter().all(|slice| slice.origin().is_some()));
+    assert_ne!(slices[0].origin(), slices[1].origin());
+
+    let concat = DataBlock::concat(&[first.clone(), second.clone()]).unwrap();
+    assert!(concat.origin().is_some());
+    assert_ne!(concat.origin(), first.origin());
+}
+
 #[test]
 fn test_box_render_block() {
     let value = "abc";
@@ -56,3 +168,165 @@ fn test_box_render_block() {
 └────────────────────┘"#;
     assert_eq!(d, expected);
 }
+
+#[test]
+fn test_diff_table_lines() {
+    let expected = vec![
+        "+----------+",
+        "| Column 0 |",
+        "+----------+",
+        "| 1        |",
+        "| 2        |",
+        "| 3        |",
+        "+----------+",
+    ];
+    assert_eq!(diff_table_lines(&expected, &expected), None);
+
+    let mut actual = expected.clone();
+    actual[4] = "| 4        |";
+    actual.insert(6, "| 5        |");
+    assert_eq!(
+        diff_table_lines(&expected, &actual).unwrap(),
+        "expected 3 rows, actual 4 rows
+first mismatch at line 5:
+expected: | 2        |
+actual:   | 4        |
+
+--- expected
++++ actual
+...
+ | Column 0 |
+ +----------+
+ | 1        |
++| 4        |
+-| 2        |
+ | 3        |
++| 5        |
+ +----------+
+"
+    );
+
+    let actual = vec![
+        "+----------+----------+",
+        "| Column 0 | Column 1 |",
+        "+----------+----------+",
+        "| 1        | 1        |",
+        "+----------+----------+",
+    ];
+    assert_eq!(
+        diff_table_lines(&expected, &actual).unwrap(),
+        "schema mismatch:
+expected: | Column 0 |
+actual:   | Column 0 | Column 1 |"
+    );
+}
+
+#[test]
+fn test_assert_blocks_sorted_eq() {
+    let blocks = [
+        new_block(&[Int32Type::from_data(vec![3, 1])]),
+        new_block(&[Int32Type::from_data(vec![2])]),
+    ];
+    assert_blocks_sorted_eq(
+        vec![
+            "+----------+",
+            "| Column 0 |",
+            "+----------+",
+            "| 1        |",
+            "| 2        |",
+            "| 3        |",
+            "+----------+",
+        ],
+        &blocks,
+    );
+}
+
+#[test]
+#[should_panic(expected = "schema mismatch: block 0 has columns [\"Int32\"], but block 1")]
+fn test_assert_blocks_schema_mismatch() {
+    let blocks = [
+        new_block(&[Int32Type::from_data(vec![1])]),
+        new_block(&[StringType::from_data(vec!["a"])]),
+    ];
+    assert_blocks_sorted_eq(vec![], &blocks);
+}
+
+#[test]
+fn test_check_schema() {
+    let int32 = DataType::Number(NumberDataType::Int32);
+    let int64 = DataType::Number(NumberDataType::Int64);
+    let array = DataType::Array(Box::new(int32.clone()));
+    let block = DataBlock::new(
+        vec![
+            BlockEntry::new(
+                int32.clone(),
+                Value::Column(Int32Type::from_data(vec![1, 2, 3])),
+            ),
+            BlockEntry::new(
+                array.clone(),
+                Value::Scalar(Scalar::array_from_iter::<Int32Type>([1, 2])),
+            ),
+        ],
+        3,
+    );
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", int32.clone()),
+        DataField::new("b", array.clone()),
+    ]);
+    block.check_schema(&schema).unwrap();
+
+    // Column count mismatch.
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", int32.clone())]);
+    let err = block.check_schema(&schema).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::INTERNAL);
+    assert_eq!(
+        err.message(),
+        "DataBlock corrupted, it has 2 columns, but the schema has 1 fields"
+    );
+
+    // A nested type that differs from the schema.
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", int32.clone()),
+        DataField::new("b", DataType::Array(Box::new(int64.clone()))),
+    ]);
+    let err = block.check_schema(&schema).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::INTERNAL);
+    assert_eq!(
+        err.message(),
+        "DataBlock corrupted, column 1 'b' is Array(Int32), but the schema type is Array(Int64)"
+    );
+
+    // The entry claims a type its value does not have.
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", int64.clone())]);
+    let corrupted = DataBlock::new(
+        vec![BlockEntry {
+            data_type: int64,
+            value: Value::Column(Int32Type::from_data(vec![1, 2, 3])),
+        }],
+        3,
+    );
+    let err = corrupted.check_schema(&schema).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::INTERNAL);
+    assert_eq!(
+        err.message(),
+        "DataBlock corrupted, the value of column 0 'a' is not of type Int64"
+    );
+
+    // A column longer than the block.
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", int32.clone())]);
+    let mut corrupted = DataBlock::new(
+        vec![BlockEntry::new(
+            int32,
+            Value::Column(Int32Type::from_data(vec![1, 2, 3])),
+        )],
+        3,
+    );
+    corrupted.columns_mut()[0].value = Value::Column(Int32Type::from_data(vec![1, 2, 3, 4]));
+    let err = corrupted.check_schema(&schema).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::INTERNAL);
+    assert_eq!(
+        err.message(),
+        "DataBlock corrupted, column length mismatch, col: 4, num_rows: 3"
+    );
+}
@@ -12,12 +12,44 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
+use arrow_array::LargeBinaryArray;
+use arrow_array::LargeStringArray;
+use arrow_array::RecordBatch;
+use arrow_array::StringArray;
 use databend_common_arrow::arrow::array::new_empty_array;
+use databend_common_arrow::arrow::array::DictionaryArray;
+use databend_common_arrow::arrow::array::PrimitiveArray;
+use databend_common_arrow::arrow::array::Utf8Array;
+use databend_common_arrow::arrow::bitmap::Bitmap;
 use databend_common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use databend_common_arrow::arrow::datatypes::Field as ArrowField;
+use databend_common_arrow::arrow::datatypes::IntegerType;
+use databend_common_arrow::arrow::datatypes::TimeUnit;
+use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
+use databend_common_expression::group_hash_columns;
+use databend_common_expression::types::number::NumberScalar;
+use databend_common_expression::types::AnyType;
+use databend_common_expression::types::BinaryType;
 use databend_common_expression::types::DataType;
+use databend_common_expression::types::DateType;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::Int64Type;
 use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::types::TimestampType;
 use databend_common_expression::Column;
+use databend_common_expression::ColumnBuilder;
+use databend_common_expression::DataBlock;
+use databend_common_expression::DataSchema;
+use databend_common_expression::FromData;
+use databend_common_expression::Scalar;
+use databend_common_expression::ScalarRef;
+use databend_common_expression::TableDataType;
+use databend_common_expression::TableField;
+use databend_common_expression::Value;
 
 #[test]
 fn test_from_arrow_extension_to_column() -> Result<()> {
@@ -30,3 +62,262 @@ fn test_from_arrow_extension_to_column() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_from_arrow_dictionary_to_column() -> Result<()> {
+    let values = Utf8Array::<i32>::from_slice(["a", "bb", "ccc"]).boxed();
+    let keys = PrimitiveArray::<i32>::from_slice([2, 0, 0, 1, 2]);
+    let arrow_col = DictionaryArray::try_from_keys(keys, values)?;
+
+    let arrow_type =
+        ArrowDataType::Dictionary(IntegerType::Int32, Box::new(ArrowDataType::Utf8), false);
+    let field = ArrowField::new("s", arrow_type, false);
+    let table_field = TableField::try_from(&field)?;
+    assert_eq!(table_field.data_type(), &TableDataType::String);
+
+    let column = Column::from_arrow(&arrow_col, &DataType::String)?;
+    let expected = StringType::from_data(vec!["ccc", "a", "a", "bb", "ccc"]);
+    assert_eq!(column, expected);
+
+    // Null keys and null values are both decoded into nulls.
+    let values = PrimitiveArray::<i32>::from([Some(10), None]).boxed();
+    let keys = PrimitiveArray::<i32>::from([Some(0), None, Some(1), Some(0)]);
+    let arrow_col = DictionaryArray::try_from_keys(keys, values)?;
+
+    let data_type = DataType::Number(NumberDataType::Int32).wrap_nullable();
+    let column = Column::from_arrow(&arrow_col, &data_type)?;
+    let expected = Int32Type::from_opt_data(vec![Some(10), None, None, Some(10)]);
+    assert_eq!(
+        column.iter().collect::<Vec<_>>(),
+        expected.iter().collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_from_arrow_timestamp_to_column() -> Result<()> {
+    // 1969-12-31 23:59:59.999999, 1970-01-01 00:00:00 and 2262-04-11 23:47:16.854775
+    let nanos = [-1_000, 0, i64::MAX];
+    let cases = [
+        (TimeUnit::Second, vec![-1, 0, 1], vec![
+            -1_000_000, 0, 1_000_000,
+        ]),
+        (TimeUnit::Millisecond, vec![-1, 0, 1], vec![
+            -1_000, 0, 1_000,
+        ]),
+        (TimeUnit::Microsecond, vec![-1, 0, 1], vec![-1, 0, 1]),
+        (TimeUnit::Nanosecond, vec![-1, -999, 1_999], vec![-1, -1, 1]),
+        (TimeUnit::Nanosecond, nanos.to_vec(), vec![
+            -1,
+            0,
+            9_223_372_036_854_775,
+        ]),
+    ];
+    for (unit, values, expected) in cases {
+        let arrow_col =
+            PrimitiveArray::<i64>::from_vec(values).to(ArrowDataType::Timestamp(unit, None));
+        let column = Column::from_arrow(&arrow_col, &DataType::Timestamp)?;
+        assert_eq!(column, TimestampType::from_data(expected), "unit: {unit:?}");
+    }
+
+    // Arrow stores zoned timestamps as UTC instants, so the same instant in different zones
+    // reads as the same value.
+    for tz in ["UTC", "+08:00", "America/New_York"] {
+        let arrow_type = ArrowDataType::Timestamp(TimeUnit::Millisecond, Some(tz.to_string()));
+        let field = ArrowField::new("t", arrow_type.clone(), false);
+        let table_field = TableField::try_from(&field)?;
+        assert_eq!(table_field.data_type(), &TableDataType::Timestamp);
+
+        let arrow_col = PrimitiveArray::<i64>::from_vec(vec![-1, 0, 1]).to(arrow_type);
+        let column = Column::from_arrow(&arrow_col, &DataType::Timestamp)?;
+        assert_eq!(
+            column,
+            TimestampType::from_data(vec![-1_000, 0, 1_000]),
+            "timezone: {tz}"
+        );
+    }
+
+    // Scaling up coarser units must not wrap around.
+    let arrow_col = PrimitiveArray::<i64>::from_vec(vec![0, i64::MAX / 1_000])
+        .to(ArrowDataType::Timestamp(TimeUnit::Second, None));
+    let err = Column::from_arrow(&arrow_col, &DataType::Timestamp).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::OVERFLOW);
+
+    // The values behind the null slots are not converted.
+    let arrow_col = PrimitiveArray::<i64>::from_vec(vec![1, i64::MAX])
+        .to(ArrowDataType::Timestamp(TimeUnit::Second, None))
+        .with_validity(Some(Bitmap::from([true, false])));
+    let column = Column::from_arrow(&arrow_col, &DataType::Timestamp.wrap_nullable())?;
+    assert_eq!(
+        column,
+        TimestampType::from_opt_data(vec![Some(1_000_000), None])
+    );
+    let column = Column::from_arrow(&arrow_col, &DataType::Date.wrap_nullable())?;
+    assert_eq!(column, DateType::from_opt_data(vec![Some(0), None]));
+
+    // Timestamps are truncated to the day they fall in.
+    let arrow_col = PrimitiveArray::<i64>::from_vec(vec![-1, 0, 86_399_999_999, 86_400_000_000])
+        .to(ArrowDataType::Timestamp(TimeUnit::Microsecond, None));
+    let column = Column::from_arrow(&arrow_col, &DataType::Date)?;
+    assert_eq!(column, DateType::from_data(vec![-1, 0, 0, 1]));
+
+    Ok(())
+}
+
+#[test]
+fn test_from_arrow_date_to_column() -> Result<()> {
+    let arrow_col = PrimitiveArray::<i32>::from_vec(vec![-1, 0, 19_000]).to(ArrowDataType::Date32);
+    let column = Column::from_arrow(&arrow_col, &DataType::Date)?;
+    assert_eq!(column, DateType::from_data(vec![-1, 0, 19_000]));
+
+    // Dates are the midnight of the day in UTC.
+    let column = Column::from_arrow(&arrow_col, &DataType::Timestamp)?;
+    assert_eq!(
+        column,
+        TimestampType::from_data(vec![-86_400_000_000, 0, 1_641_600_000_000_000])
+    );
+
+    let arrow_col = PrimitiveArray::<i64>::from_vec(vec![-1, 0, 86_399_999, 86_400_000])
+        .to(ArrowDataType::Date64);
+    let column = Column::from_arrow(&arrow_col, &DataType::Date)?;
+    assert_eq!(column, DateType::from_data(vec![-1, 0, 0, 1]));
+
+    Ok(())
+}
+
+#[test]
+fn test_from_arrow_string_to_temporal_column() -> Result<()> {
+    let arrow_col =
+        Utf8Array::<i32>::from([Some("1969-12-31 23:59:59.5"), None, Some("2024-03-10")]);
+    let column = Column::from_arrow(&arrow_col, &DataType::Timestamp.wrap_nullable())?;
+    assert_eq!(
+        column,
+        TimestampType::from_opt_data(vec![Some(-500_000), None, Some(1_710_028_800_000_000)])
+    );
+
+    let arrow_col = Utf8Array::<i64>::from([Some("1969-12-31"), None, Some("2024-03-10")]);
+    let column = Column::from_arrow(&arrow_col, &DataType::Date.wrap_nullable())?;
+    assert_eq!(
+        column,
+        DateType::from_opt_data(vec![Some(-1), None, Some(19_792)])
+    );
+
+    let arrow_col = Utf8Array::<i32>::from([Some("2024-13-01")]);
+    let err = Column::from_arrow(&arrow_col, &DataType::Date).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::BAD_ARGUMENTS);
+
+    Ok(())
+}
+
+#[test]
+fn test_from_arrow_rs_large_string_to_column() -> Result<()> {
+    let values = vec!["a", "bb", "a", "ccc", "bb"];
+    let schema = arrow_schema::Schema::new(vec![
+        arrow_schema::Field::new("utf8", arrow_schema::DataType::Utf8, false),
+        arrow_schema::Field::new("large_utf8", arrow_schema::DataType::LargeUtf8, false),
+        arrow_schema::Field::new("large_binary", arrow_schema::DataType::LargeBinary, false),
+    ]);
+    let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![
+        Arc::new(StringArray::from(values.clone())),
+        Arc::new(LargeStringArray::from(values.clone())),
+        Arc::new(LargeBinaryArray::from_vec(
+            values.iter().map(|v| v.as_bytes()).collect(),
+        )),
+    ])
+    .unwrap();
+
+    // The large types are read as the same types as the small ones.
+    let data_schema = DataSchema::try_from(&schema)?;
+    let data_types = data_schema
+        .fields()
+        .iter()
+        .map(|f| f.data_type().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(data_types, vec![
+        DataType::String,
+        DataType::String,
+        DataType::Binary
+    ]);
+
+    let (block, _) = DataBlock::from_record_batch(&data_schema, &batch)?;
+    let strings = StringType::from_data(values.clone());
+    let column = |i: usize| block.get_by_offset(i).value.as_column().unwrap().clone();
+    assert_eq!(column(0), strings);
+    assert_eq!(column(1), strings);
+    assert_eq!(
+        column(2),
+        BinaryType::from_data(values.iter().map(|v| v.as_bytes()).collect())
+    );
+
+    // Filter and group by the large strings.
+    let filtered = block
+        .clone()
+        .filter_with_bitmap(&Bitmap::from([true, true, false, true, false]))?;
+    assert_eq!(
+        filtered.get_by_offset(1).value.as_column().unwrap(),
+        &StringType::from_data(vec!["a", "bb", "ccc"])
+    );
+
+    let mut small_hashes = vec![0; block.num_rows()];
+    group_hash_columns(&[column(0)], &mut small_hashes);
+    let mut large_hashes = vec![0; block.num_rows()];
+    group_hash_columns(&[column(1)], &mut large_hashes);
+    assert_eq!(small_hashes, large_hashes);
+    assert_eq!(large_hashes[0], large_hashes[2]);
+    assert_eq!(large_hashes[1], large_hashes[4]);
+
+    Ok(())
+}
+
+#[test]
+fn test_convert_constant_to_full_column() {
+    let int64 = DataType::Number(NumberDataType::Int64);
+    let one = Value::<AnyType>::Scalar(Scalar::Number(NumberScalar::Int64(1)));
+    let int64_ptr = |column: &Column| column.as_number().unwrap().as_int64().unwrap().as_ptr();
+
+    // The same constant of the following blocks shares the column.
+    let column = one.convert_to_full_column(&int64, 65536);
+    let again = one.convert_to_full_column(&int64, 65536);
+    assert_eq!(int64_ptr(&column), int64_ptr(&again));
+    assert_eq!(column, Int64Type::from_data(vec![1; 65536]));
+
+    // Building on the shared column copies it, the shared column is not changed.
+    let mut builder = ColumnBuilder::from_column(again);
+    builder.push(ScalarRef::Number(NumberScalar::Int64(2)));
+    assert_eq!(builder.len(), 65537);
+    drop(builder);
+    let again = one.convert_to_full_column(&int64, 65536);
+    assert_eq!(int64_ptr(&column), int64_ptr(&again));
+    assert_eq!(again, Int64Type::from_data(vec![1; 65536]));
+
+    // The column is built again if the number of rows, the constant or the type is changed.
+    let column = one.convert_to_full_column(&int64, 10);
+    assert_eq!(column, Int64Type::from_data(vec![1; 10]));
+    let two = Value::<AnyType>::Scalar(Scalar::Number(NumberScalar::Int64(2)));
+    let column = two.convert_to_full_column(&int64, 10);
+    assert_eq!(column, Int64Type::from_data(vec![2; 10]));
+    let null = Value::<AnyType>::Scalar(Scalar::Null);
+    let column = null.convert_to_full_column(&int64.wrap_nullable(), 3);
+    assert_eq!(column, Int64Type::from_opt_data(vec![None; 3]));
+    let column = null.convert_to_full_column(&DataType::String.wrap_nullable(), 3);
+    assert_eq!(column, StringType::from_opt_data(vec![None::<&str>; 3]));
+
+    // The large constant columns are not kept by the thread.
+    let long = Value::<AnyType>::Scalar(Scalar::String("a".repeat(1024)));
+    let string_ptr = |column: &Column| column.as_string().unwrap().data().as_ptr();
+    let column = long.convert_to_full_column(&DataType::String, 65536);
+    let again = long.convert_to_full_column(&DataType::String, 65536);
+    assert_ne!(string_ptr(&column), string_ptr(&again));
+    assert_eq!(column, again);
+
+    // `-0.0` equals `0.0`, the float constants are always built.
+    let float64 = DataType::Number(NumberDataType::Float64);
+    let zero = Value::<AnyType>::Scalar(Scalar::Number(NumberScalar::Float64(0.0f64.into())));
+    let neg_zero =
+        Value::<AnyType>::Scalar(Scalar::Number(NumberScalar::Float64((-0.0f64).into())));
+    zero.convert_to_full_column(&float64, 3);
+    let column = neg_zero.convert_to_full_column(&float64, 3);
+    let values = column.as_number().unwrap().as_float64().unwrap();
+    assert!(values.iter().all(|v| v.is_sign_negative()));
+}
@@ -64,3 +64,37 @@ fn test_group_by_hash() -> Result<()> {
     ]);
     Ok(())
 }
+
+#[test]
+fn test_group_hash_columns() {
+    let hash = |cols: &[Column]| {
+        let mut values = vec![0; cols[0].len()];
+        group_hash_columns(cols, &mut values);
+        values
+    };
+
+    let ints = Int32Type::from_data(vec![1, 2, 3, 1, 2, 3]);
+    let strs =
+        StringType::from_opt_data(vec![Some("a"), None, Some("c"), Some("a"), None, Some("c")]);
+    let floats = Float64Type::from_data(vec![0.0, f64::NAN, 1.5, -0.0, -f64::NAN, 1.5]);
+    let tuples = Column::Tuple(vec![ints.clone(), strs.clone()]);
+    let cols = vec![ints, strs, floats, tuples];
+
+    // The equal rows have the same hash, even if they are in different blocks.
+    let hashes = hash(&cols);
+    let first = cols.iter().map(|c| c.slice(0..3)).collect::<Vec<_>>();
+    let second = cols.iter().map(|c| c.slice(3..6)).collect::<Vec<_>>();
+    assert_eq!(hash(&first), hashes[0..3]);
+    assert_eq!(hash(&second), hashes[3..6]);
+    assert_eq!(hashes[0..3], hashes[3..6]);
+    assert_ne!(hashes[0], hashes[1]);
+
+    // The hash of a single column is the hash of its values.
+    let column_hashes = hash(&cols[3..]);
+    for (i, h) in column_hashes.iter().enumerate() {
+        assert_eq!(*h, cols[3].index(i).unwrap().agg_hash());
+    }
+
+    // NULL has a fixed hash.
+    assert_eq!(hash(&cols[1..2])[1], ScalarRef::Null.agg_hash());
+}
@@ -16,14 +16,20 @@ use core::ops::Range;
 
 use databend_common_expression::block_debug::assert_block_value_eq;
 use databend_common_expression::types::number::*;
+use databend_common_expression::types::BooleanType;
 use databend_common_expression::types::DataType;
+use databend_common_expression::types::DateType;
 use databend_common_expression::types::NumberDataType;
 use databend_common_expression::types::StringType;
+use databend_common_expression::types::TimestampType;
+use databend_common_expression::types::VariantType;
 use databend_common_expression::BlockEntry;
 use databend_common_expression::Column;
 use databend_common_expression::DataBlock;
 use databend_common_expression::FromData;
+use databend_common_expression::Scalar;
 use databend_common_expression::Value;
+use databend_common_expression::ValueStatistics;
 use goldenfile::Mint;
 
 use crate::common::*;
@@ -531,3 +537,286 @@ pub fn test_scatter() -> databend_common_exception::Result<()> {
 
     Ok(())
 }
+
+fn value_statistics(
+    min_max: Option<(Scalar, Scalar)>,
+    null_count: usize,
+    distinct_count: Option<usize>,
+) -> ValueStatistics {
+    let (min, max) = min_max.unzip();
+    ValueStatistics {
+        min,
+        max,
+        null_count,
+        distinct_count,
+    }
+}
+
+/// This test covers statistics.rs.
+#[test]
+pub fn test_statistics() {
+    let int = |v: i32| Scalar::Number(NumberScalar::Int32(v));
+    let cases = vec![
+        (
+            Int32Type::from_opt_data(vec![Some(3), None, Some(-1), Some(7), None]),
+            value_statistics(Some((int(-1), int(7))), 2, None),
+        ),
+        (
+            Float64Type::from_data(vec![1.5f64, -0.5, 0.0]),
+            value_statistics(
+                Some((
+                    Scalar::Number(NumberScalar::Float64((-0.5).into())),
+                    Scalar::Number(NumberScalar::Float64(1.5.into())),
+                )),
+                0,
+                None,
+            ),
+        ),
+        (
+            StringType::from_opt_data(vec![Some("b"), Some("abc"), None, Some("ba")]),
+            value_statistics(
+                Some((
+                    Scalar::String("abc".to_string()),
+                    Scalar::String("ba".to_string()),
+                )),
+                1,
+                None,
+            ),
+        ),
+        (
+            BooleanType::from_data(vec![true, true]),
+            value_statistics(
+                Some((Scalar::Boolean(true), Scalar::Boolean(true))),
+                0,
+                None,
+            ),
+        ),
+        (
+            DateType::from_data(vec![19_000, -1]),
+            value_statistics(Some((Scalar::Date(-1), Scalar::Date(19_000))), 0, None),
+        ),
+        (
+            TimestampType::from_opt_data(vec![None, Some(0)]),
+            value_statistics(Some((Scalar::Timestamp(0), Scalar::Timestamp(0))), 1, None),
+        ),
+        // All the values are null.
+        (
+            Int32Type::from_opt_data(vec![None, None]),
+            value_statistics(None, 2, None),
+        ),
+        (Column::Null { len: 3 }, value_statistics(None, 3, None)),
+        // The unordered types only count the nulls.
+        (
+            VariantType::from_opt_data(vec![Some(b"1".to_vec()), None]),
+            value_statistics(None, 1, None),
+        ),
+    ];
+
+    for (column, expected) in cases {
+        assert_eq!(column.statistics(), expected, "{column:?}");
+    }
+
+    // The constants.
+    let value = Value::Scalar(int(5));
+    let data_type = DataType::Number(NumberDataType::Int32);
+    assert_eq!(
+        value.statistics(&data_type, 10),
+        value_statistics(Some((int(5), int(5))), 0, Some(1))
+    );
+    assert_eq!(
+        Value::Scalar(Scalar::Null).statistics(&data_type.wrap_nullable(), 10),
+        value_statistics(None, 10, Some(0))
+    );
+}
+
+#[test]
+pub fn test_merge_statistics() {
+    let int = |v: i32| Scalar::Number(NumberScalar::Int32(v));
+    let data_type = DataType::Number(NumberDataType::Int32).wrap_nullable();
+    let blocks = vec![
+        DataBlock::new(
+            vec![
+                BlockEntry::new(
+                    data_type.clone(),
+                    Value::Column(Int32Type::from_opt_data(vec![Some(3), None, Some(8)])),
+                ),
+                BlockEntry::new(DataType::String, Value::Scalar(Scalar::String("x".into()))),
+            ],
+            3,
+        ),
+        DataBlock::new(
+            vec![
+                BlockEntry::new(
+                    data_type.clone(),
+                    Value::Column(Int32Type::from_opt_data(vec![None, Some(-2)])),
+                ),
+                BlockEntry::new(
+                    DataType::String,
+                    Value::Column(StringType::from_data(vec!["y", "a"])),
+                ),
+            ],
+            2,
+        ),
+        // A block of nulls does not change the min and max.
+        DataBlock::new(
+            vec![
+                BlockEntry::new(data_type, Value::Scalar(Scalar::Null)),
+                BlockEntry::new(DataType::String, Value::Scalar(Scalar::String("b".into()))),
+            ],
+            4,
+        ),
+    ];
+
+    let merged = DataBlock::merge_statistics(&blocks).unwrap();
+    assert_eq!(merged, vec![
+        value_statistics(Some((int(-2), int(8))), 6, None),
+        value_statistics(
+            Some((Scalar::String("a".into()), Scalar::String("y".into()))),
+            0,
+            None
+        ),
+    ]);
+    assert_eq!(DataBlock::merge_statistics(&[]), None);
+}
+
+#[test]
+pub fn test_filter_block() -> databend_common_exception::Result<()> {
+    let block = DataBlock::new_from_columns(vec![
+        Int32Type::from_data(vec![0, 1, 2, 3, 4]),
+        StringType::from_opt_data(vec![Some("a"), None, Some("c"), Some("d"), None]),
+    ]);
+
+    // The NULLs of the predicate filter out the rows, including the first and last ones.
+    let predicate = Value::Column(BooleanType::from_opt_data(vec![
+        None,
+        Some(true),
+        Some(false),
+        Some(true),
+        None,
+    ]));
+    let filtered = block.clone().filter_block(&predicate)?;
+    assert_block_value_eq(
+        &filtered,
+        &DataBlock::new_from_columns(vec![
+            Int32Type::from_data(vec![1, 3]),
+            StringType::from_opt_data(vec![None, Some("d")]),
+        ]),
+    );
+
+    let all_true = Value::Column(BooleanType::from_data(vec![true; 5]));
+    assert_block_value_eq(&block.clone().filter_block(&all_true)?, &block);
+
+    let all_false = Value::Column(BooleanType::from_opt_data(vec![Some(false); 5]));
+    assert_eq!(block.clone().filter_block(&all_false)?.num_rows(), 0);
+
+    let all_null = Value::Column(Column::Null { len: 5 });
+    assert_eq!(block.clone().filter_block(&all_null)?.num_rows(), 0);
+
+    // The constant predicates.
+    let filtered = block
+        .clone()
+        .filter_block(&Value::Scalar(Scalar::Boolean(true)))?;
+    assert_block_value_eq(&filtered, &block);
+    let filtered = block
+        .clone()
+        .filter_block(&Value::Scalar(Scalar::Boolean(false)))?;
+    assert_eq!(filtered.num_rows(), 0);
+    assert_eq!(filtered.num_columns(), 2);
+    let filtered = block.clone().filter_block(&Value::Scalar(Scalar::Null))?;
+    assert_eq!(filtered.num_rows(), 0);
+
+    // The predicate must be a boolean of the same length as the block.
+    let short = Value::Column(BooleanType::from_data(vec![true; 4]));
+    let err = block.clone().filter_block(&short).unwrap_err();
+    assert_eq!(
+        err.code(),
+        databend_common_exception::ErrorCode::BAD_ARGUMENTS
+    );
+
+    let not_boolean = Value::Column(Int32Type::from_data(vec![1; 5]));
+    let err = block.filter_block(&not_boolean).unwrap_err();
+    assert_eq!(
+        err.code(),
+        databend_common_exception::ErrorCode::BAD_DATA_VALUE_TYPE
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn test_multi_if() -> databend_common_exception::Result<()> {
+    let data_type = DataType::Nullable(Box::new(DataType::Number(NumberDataType::Int32)));
+    let conds = vec![
+        Value::Column(BooleanType::from_opt_data(vec![
+            Some(true),
+            None,
+            Some(false),
+            Some(true),
+            Some(false),
+        ])),
+        Value::Column(BooleanType::from_data(vec![
+            true, true, false, false, false,
+        ])),
+    ];
+    let results = vec![
+        Value::Column(Int32Type::from_opt_data(vec![
+            Some(0),
+            Some(1),
+            Some(2),
+            Some(3),
+            Some(4),
+        ])),
+        Value::Scalar(Scalar::Number(NumberScalar::Int32(10))),
+    ];
+    let otherwise = Value::Scalar(Scalar::Null);
+
+    // The NULL condition of the second row is not true, it takes the second result.
+    let value = Value::multi_if(&conds, &results, &otherwise, &data_type, 5)?;
+    assert_eq!(
+        value.into_column().unwrap(),
+        Int32Type::from_opt_data(vec![Some(0), Some(10), None, Some(3), None])
+    );
+
+    let value = Value::if_then_else(&conds[1], &results[1], &results[0], &data_type, 5)?;
+    assert_eq!(
+        value.into_column().unwrap(),
+        Int32Type::from_opt_data(vec![Some(10), Some(10), Some(2), Some(3), Some(4)])
+    );
+
+    // A constant true condition takes its result as is.
+    let always = Value::Scalar(Scalar::Boolean(true));
+    let value = Value::multi_if(
+        &[Value::Scalar(Scalar::Null), always],
+        &results,
+        &otherwise,
+        &data_type,
+        5,
+    )?;
+    assert_eq!(value, results[1]);
+
+    let never = Value::Column(BooleanType::from_data(vec![false; 5]));
+    let value = Value::if_then_else(&never, &results[0], &otherwise, &data_type, 5)?;
+    assert_eq!(value, otherwise);
+
+    // The conditions must be booleans and the results of the given type.
+    let err = Value::if_then_else(&results[0], &results[1], &otherwise, &data_type, 5).unwrap_err();
+    assert_eq!(
+        err.code(),
+        databend_common_exception::ErrorCode::BAD_DATA_VALUE_TYPE
+    );
+
+    let err = Value::if_then_else(
+        &conds[1],
+        &Value::Column(Int32Type::from_data(vec![1; 5])),
+        &otherwise,
+        &data_type,
+        5,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.code(),
+        databend_common_exception::ErrorCode::BAD_DATA_VALUE_TYPE
+    );
+
+    Ok(())
+}
@@ -24,6 +24,7 @@ use databend_common_expression::DataBlock;
 
 extern crate core;
 
+mod arbitrary;
 mod block;
 mod column;
 mod common;
@@ -32,9 +33,11 @@ mod group_by;
 mod kernel;
 mod meta_scalar;
 mod row;
+mod scalar;
 mod schema;
 mod serde;
 mod sort;
+mod sql_literal;
 
 fn rand_block_for_all_types(num_rows: usize) -> DataBlock {
     let types = get_all_test_data_types();
@@ -0,0 +1,541 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+
+use chrono::NaiveDate;
+use databend_common_exception::ErrorCode;
+use databend_common_expression::types::date::date_to_days_u16;
+use databend_common_expression::types::date::days_u16_to_date;
+use databend_common_expression::types::date::DATE16_MAX;
+use databend_common_expression::types::decimal::DecimalScalar;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::DecimalSize;
+use databend_common_expression::types::Float64Type;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::Int64Type;
+use databend_common_expression::types::NullableType;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::NumberScalar;
+use databend_common_expression::types::StringType;
+use databend_common_expression::utils::display::display_f32;
+use databend_common_expression::utils::display::display_f64;
+use databend_common_expression::utils::display::display_float;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_expression::Scalar;
+use num_traits::FromPrimitive;
+use ordered_float::OrderedFloat;
+use rand::Rng;
+use rust_decimal::Decimal;
+
+use crate::rand_block_for_simple_types;
+
+fn random_integer(rng: &mut impl Rng) -> (Scalar, i128) {
+    let (number, value) = match rng.gen_range(0..8) {
+        0 => {
+            let v = rng.gen::<u8>();
+            (NumberScalar::UInt8(v), v as i128)
+        }
+        1 => {
+            let v = rng.gen::<u16>();
+            (NumberScalar::UInt16(v), v as i128)
+        }
+        2 => {
+            let v = rng.gen::<u32>();
+            (NumberScalar::UInt32(v), v as i128)
+        }
+        3 => {
+            let v = rng.gen::<u64>();
+            (NumberScalar::UInt64(v), v as i128)
+        }
+        4 => {
+            let v = rng.gen::<i8>();
+            (NumberScalar::Int8(v), v as i128)
+        }
+        5 => {
+            let v = rng.gen::<i16>();
+            (NumberScalar::Int16(v), v as i128)
+        }
+        6 => {
+            let v = rng.gen::<i32>();
+            (NumberScalar::Int32(v), v as i128)
+        }
+        _ => {
+            let v = rng.gen::<i64>();
+            (NumberScalar::Int64(v), v as i128)
+        }
+    };
+    (Scalar::Number(number), value)
+}
+
+#[test]
+fn test_cmp_coerced_numbers() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..1000 {
+        let (lhs, lhs_value) = random_integer(&mut rng);
+        let (rhs, rhs_value) = random_integer(&mut rng);
+        assert_eq!(
+            lhs.cmp_coerced(&rhs),
+            Some(lhs_value.cmp(&rhs_value)),
+            "{lhs:?} vs {rhs:?}"
+        );
+    }
+
+    let int32 = Scalar::Number(NumberScalar::Int32(1));
+    let uint64 = Scalar::Number(NumberScalar::UInt64(2));
+    assert_eq!(int32.partial_cmp(&uint64), None);
+    assert_eq!(int32.cmp_coerced(&uint64), Some(Ordering::Less));
+
+    let float64 = Scalar::Number(NumberScalar::Float64(OrderedFloat(1.5)));
+    assert_eq!(int32.cmp_coerced(&float64), Some(Ordering::Less));
+    assert_eq!(uint64.cmp_coerced(&float64), Some(Ordering::Greater));
+
+    let decimal = |v: i128, scale: u8| {
+        Scalar::Decimal(DecimalScalar::Decimal128(v, DecimalSize {
+            precision: 38,
+            scale,
+        }))
+    };
+    // 1.50 = 1.5 < 2
+    assert_eq!(
+        decimal(150, 2).cmp_coerced(&decimal(15, 1)),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(decimal(150, 2).cmp_coerced(&uint64), Some(Ordering::Less));
+    assert_eq!(
+        uint64.cmp_coerced(&decimal(150, 2)),
+        Some(Ordering::Greater)
+    );
+    assert_eq!(float64.cmp_coerced(&decimal(150, 2)), Some(Ordering::Equal));
+}
+
+#[test]
+fn test_cmp_coerced_same_type() {
+    let mut rng = rand::thread_rng();
+    let rows = 100;
+    let block = rand_block_for_simple_types(rows);
+    for entry in block.columns() {
+        let column = entry.value.as_column().unwrap();
+        for _ in 0..rows {
+            let lhs = column.index(rng.gen_range(0..rows)).unwrap();
+            let rhs = column.index(rng.gen_range(0..rows)).unwrap();
+            if lhs.is_null() || rhs.is_null() {
+                continue;
+            }
+            assert_eq!(lhs.cmp_coerced(&rhs), lhs.partial_cmp(&rhs));
+        }
+    }
+}
+
+#[test]
+fn test_cmp_coerced_others() {
+    let null = Scalar::Null;
+    let one = Scalar::Number(NumberScalar::Int8(1));
+    assert_eq!(null.cmp_coerced(&one), Some(Ordering::Greater));
+    assert_eq!(one.cmp_coerced(&null), Some(Ordering::Less));
+    assert_eq!(null.cmp_coerced(&null), Some(Ordering::Equal));
+    assert_eq!(null.cmp_coerced_nulls_first(&one), Some(Ordering::Less));
+    assert_eq!(one.cmp_coerced_nulls_first(&null), Some(Ordering::Greater));
+
+    let a = Scalar::String("a".to_string());
+    let b = Scalar::String("b".to_string());
+    assert_eq!(a.cmp_coerced(&b), Some(Ordering::Less));
+    assert_eq!(
+        Scalar::Binary(vec![1, 2]).cmp_coerced(&Scalar::Binary(vec![1, 3])),
+        Some(Ordering::Less)
+    );
+
+    // 1970-01-02 vs 1970-01-01 23:59:59
+    let date = Scalar::Date(1);
+    let timestamp = Scalar::Timestamp(86_399_000_000);
+    assert_eq!(date.cmp_coerced(&timestamp), Some(Ordering::Greater));
+    assert_eq!(timestamp.cmp_coerced(&date), Some(Ordering::Less));
+
+    // Incomparable values.
+    assert_eq!(a.cmp_coerced(&one), None);
+    assert_eq!(date.cmp_coerced(&one), None);
+}
+
+#[test]
+fn test_min_max_coerced() {
+    let null = Scalar::Null;
+    let one = Scalar::Number(NumberScalar::Int32(1));
+    let two = Scalar::Number(NumberScalar::UInt64(2));
+
+    assert_eq!(Scalar::min_coerced(one.clone(), two.clone()), one);
+    assert_eq!(Scalar::min_coerced(two.clone(), one.clone()), one);
+    assert_eq!(Scalar::max_coerced(one.clone(), two.clone()), two);
+    assert_eq!(Scalar::max_coerced(two.clone(), one.clone()), two);
+
+    // NULL is ignored.
+    assert_eq!(Scalar::min_coerced(null.clone(), one.clone()), one);
+    assert_eq!(Scalar::min_coerced(one.clone(), null.clone()), one);
+    assert_eq!(Scalar::max_coerced(null.clone(), one.clone()), one);
+    assert_eq!(Scalar::max_coerced(one.clone(), null.clone()), one);
+    assert_eq!(Scalar::max_coerced(null.clone(), null.clone()), null);
+
+    // The first value is kept if the values are incomparable.
+    let a = Scalar::String("a".to_string());
+    assert_eq!(Scalar::min_coerced(a.clone(), one.clone()), a);
+    assert_eq!(Scalar::max_coerced(one.clone(), a.clone()), one);
+}
+
+#[test]
+fn test_not_distinct_from() {
+    let null = Scalar::Null;
+    let one = Scalar::Number(NumberScalar::Int32(1));
+    let another_one = Scalar::Number(NumberScalar::UInt64(1));
+    let two = Scalar::Number(NumberScalar::UInt64(2));
+
+    assert!(null.not_distinct_from(&null));
+    assert!(!null.not_distinct_from(&one));
+    assert!(!one.not_distinct_from(&null));
+    assert!(one.not_distinct_from(&another_one));
+    assert!(!one.not_distinct_from(&two));
+
+    // Incomparable values are distinct.
+    let a = Scalar::String("a".to_string());
+    assert!(a.not_distinct_from(&a));
+    assert!(!a.not_distinct_from(&one));
+}
+
+#[test]
+fn test_try_get_integer() {
+    let decimal = |value: i128, scale: u8| {
+        Scalar::Decimal(DecimalScalar::Decimal128(value, DecimalSize {
+            precision: 38,
+            scale,
+        }))
+    };
+
+    // The matching and convertible values.
+    let i64_cases = [
+        (Scalar::Number(NumberScalar::Int64(-7)), -7),
+        (Scalar::Number(NumberScalar::UInt8(200)), 200),
+        (
+            Scalar::Number(NumberScalar::UInt64(i64::MAX as u64)),
+            i64::MAX,
+        ),
+        (
+            Scalar::Number(NumberScalar::Float64(OrderedFloat(-3.0))),
+            -3,
+        ),
+        (decimal(1200, 2), 12),
+    ];
+    for (scalar, expected) in i64_cases {
+        assert_eq!(scalar.try_get_i64().unwrap(), expected, "{scalar}");
+    }
+    assert_eq!(
+        Scalar::Number(NumberScalar::UInt64(u64::MAX))
+            .try_get_u64()
+            .unwrap(),
+        u64::MAX
+    );
+
+    // The lossy values.
+    let lossy_cases = [
+        Scalar::Number(NumberScalar::UInt64(i64::MAX as u64 + 1)),
+        Scalar::Number(NumberScalar::Float64(OrderedFloat(1.5))),
+        Scalar::Number(NumberScalar::Float64(OrderedFloat(f64::NAN))),
+        Scalar::Number(NumberScalar::Float32(OrderedFloat(1e30))),
+        decimal(1250, 2),
+    ];
+    for scalar in lossy_cases {
+        let err = scalar.try_get_i64().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::BAD_DATA_VALUE_TYPE, "{scalar}");
+    }
+    let err = Scalar::Number(NumberScalar::Int8(-1))
+        .try_get_u64()
+        .unwrap_err();
+    assert_eq!(
+        err.message(),
+        "Expected a u64 value, but got -1 of type Int8"
+    );
+
+    // The other types and NULL.
+    for scalar in [
+        Scalar::Null,
+        Scalar::String("1".to_string()),
+        Scalar::Boolean(true),
+    ] {
+        let err = scalar.try_get_u64().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::BAD_DATA_VALUE_TYPE, "{scalar}");
+    }
+}
+
+#[test]
+fn test_try_get_string_and_bool() {
+    let cases = [
+        (Scalar::String("abc".to_string()), "abc"),
+        (Scalar::Number(NumberScalar::Int32(-1)), "-1"),
+        (Scalar::Boolean(true), "true"),
+    ];
+    for (scalar, expected) in cases {
+        assert_eq!(scalar.try_get_string().unwrap(), expected);
+    }
+    for scalar in [Scalar::Null, Scalar::Binary(b"abc".to_vec())] {
+        let err = scalar.try_get_string().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::BAD_DATA_VALUE_TYPE, "{scalar}");
+    }
+
+    // The non-zero numbers are true.
+    let cases = [
+        (Scalar::Boolean(false), false),
+        (Scalar::Number(NumberScalar::UInt8(0)), false),
+        (Scalar::Number(NumberScalar::Int64(-2)), true),
+        (
+            Scalar::Number(NumberScalar::Float64(OrderedFloat(-0.0))),
+            false,
+        ),
+        (
+            Scalar::Number(NumberScalar::Float32(OrderedFloat(0.5))),
+            true,
+        ),
+        (
+            Scalar::Decimal(DecimalScalar::Decimal128(0, DecimalSize {
+                precision: 10,
+                scale: 2,
+            })),
+            false,
+        ),
+    ];
+    for (scalar, expected) in cases {
+        assert_eq!(scalar.try_get_bool().unwrap(), expected, "{scalar}");
+    }
+    for scalar in [Scalar::Null, Scalar::String("true".to_string())] {
+        let err = scalar.try_get_bool().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::BAD_DATA_VALUE_TYPE, "{scalar}");
+    }
+}
+
+#[test]
+fn test_display_float() {
+    let cases = [
+        (1.0, "1"),
+        (-1.5, "-1.5"),
+        (0.0, "0"),
+        (-0.0, "-0"),
+        (f64::NAN, "nan"),
+        (-f64::NAN, "nan"),
+        (f64::INFINITY, "inf"),
+        (f64::NEG_INFINITY, "-inf"),
+        // The fractional digits after the 10th are truncated.
+        (2f64.powi(-10), "0.0009765625"),
+        (2f64.powi(-11), "0.0004882812"),
+        (1e-11, "0"),
+        (1e20, "100000000000000000000"),
+        // Out of the range of decimal.
+        (1e30, "1000000000000000000000000000000"),
+        (-1e30, "-1000000000000000000000000000000"),
+    ];
+    for (num, expected) in cases {
+        assert_eq!(display_f64(num), expected, "{num:?}");
+    }
+    assert!(display_f64(f64::MAX).starts_with("17976931348623157"));
+    assert_eq!(display_f64(f64::MAX).len(), 309);
+
+    let cases = [
+        (1.5f32, "1.5"),
+        (-0.0, "-0"),
+        (f32::NAN, "nan"),
+        (f32::NEG_INFINITY, "-inf"),
+        (4294967296.0, "4294967296"),
+    ];
+    for (num, expected) in cases {
+        assert_eq!(display_f32(num), expected, "{num:?}");
+    }
+    assert_eq!(
+        display_float(2f64.powi(-11), Decimal::from_f64, 4),
+        "0.0004"
+    );
+}
+
+#[test]
+fn test_display_float_scalar_and_column() {
+    let values = vec![
+        1.0,
+        -0.0,
+        0.25,
+        f64::NAN,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        1e30,
+    ];
+    let displayed = values.iter().map(|v| display_f64(*v)).collect::<Vec<_>>();
+
+    // The scalars.
+    for (value, expected) in values.iter().zip(&displayed) {
+        let scalar = Scalar::Number(NumberScalar::Float64(OrderedFloat(*value)));
+        assert_eq!(scalar.to_string(), *expected);
+        assert_eq!(scalar.as_ref().to_string(), *expected);
+    }
+
+    // The column.
+    let column = Float64Type::from_data(values.clone());
+    assert_eq!(
+        format!("{column:?}"),
+        format!("Float64([{}])", displayed.join(", "))
+    );
+
+    // The rows of the block.
+    let block = DataBlock::new_from_columns(vec![column]).to_string();
+    let rows = block
+        .lines()
+        .filter(|line| line.starts_with('|'))
+        .skip(1)
+        .map(|line| line.trim_matches(|c| c == '|' || c == ' '))
+        .collect::<Vec<_>>();
+    assert_eq!(rows, displayed);
+}
+
+#[test]
+fn test_date_and_timestamp_constructors() {
+    // Dates, including a leap day and a day before the epoch.
+    let cases = [
+        ("1970-01-01", 0),
+        ("1969-12-31", -1),
+        ("2020-02-29", 18321),
+        ("1000-01-01", -354285),
+        ("9999-12-31", 2932896),
+    ];
+    for (s, days) in cases {
+        let scalar = Scalar::date_from_str(s).unwrap();
+        assert_eq!(scalar, Scalar::Date(days));
+        assert_eq!(scalar.to_string(), format!("'{s}'"));
+        assert_eq!(format!("{scalar:?}"), format!("Date({days})"));
+    }
+    for s in ["2021-02-29", "2021-13-01", "not a date"] {
+        let err = Scalar::date_from_str(s).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::BAD_ARGUMENTS);
+    }
+
+    // Timestamps.
+    let scalar = Scalar::timestamp_from_str("2021-04-01 12:30:00.5").unwrap();
+    assert_eq!(scalar, Scalar::Timestamp(1617280200500000));
+    assert_eq!(scalar.to_string(), "'2021-04-01 12:30:00.500000'");
+    assert_eq!(format!("{scalar:?}"), "Timestamp(1617280200500000)");
+    assert_eq!(
+        Scalar::timestamp_from_str("1969-12-31 23:59:59").unwrap(),
+        Scalar::Timestamp(-1000000)
+    );
+    assert!(Scalar::timestamp_from_str("2021-04-01 25:00:00").is_err());
+
+    assert_eq!(
+        Scalar::timestamp_from_secs(1617280200).unwrap(),
+        Scalar::timestamp_from_millis(1617280200000).unwrap()
+    );
+    assert_eq!(
+        Scalar::timestamp_from_millis(1617280200500).unwrap(),
+        Scalar::timestamp_from_micros(1617280200500000).unwrap()
+    );
+    for result in [
+        Scalar::timestamp_from_secs(i64::MAX),
+        Scalar::timestamp_from_millis(i64::MIN),
+        Scalar::timestamp_from_micros(i64::MAX),
+    ] {
+        assert_eq!(result.unwrap_err().code(), ErrorCode::BAD_ARGUMENTS);
+    }
+}
+
+#[test]
+fn test_array_helpers() {
+    // Homogeneous arrays.
+    let scalar = Scalar::array_from_iter::<Int64Type>([1, 2, 3]);
+    assert_eq!(
+        scalar.array_data_type(),
+        Some(DataType::Number(NumberDataType::Int64))
+    );
+    assert_eq!(scalar.to_string(), "[1, 2, 3]");
+    assert_eq!(scalar.try_into_vec::<Int64Type>().unwrap(), vec![
+        Some(1),
+        Some(2),
+        Some(3)
+    ]);
+
+    let scalar = Scalar::array_from_iter::<StringType>(["a".to_string(), "b".to_string()]);
+    assert_eq!(scalar.try_into_vec::<StringType>().unwrap(), vec![
+        Some("a".to_string()),
+        Some("b".to_string())
+    ]);
+
+    // Arrays with NULL elements.
+    let scalar = Scalar::array_from_iter::<NullableType<Int64Type>>([Some(1), None, Some(3)]);
+    assert_eq!(
+        scalar.array_data_type(),
+        Some(DataType::Number(NumberDataType::Int64).wrap_nullable())
+    );
+    assert_eq!(scalar.try_into_vec::<Int64Type>().unwrap(), vec![
+        Some(1),
+        None,
+        Some(3)
+    ]);
+
+    // Empty arrays keep the element type of the column.
+    let scalar = Scalar::array_from_iter::<Int64Type>([]);
+    assert_eq!(
+        scalar,
+        Scalar::Array(Int64Type::from_data(Vec::<i64>::new()))
+    );
+    assert_eq!(
+        scalar.array_data_type(),
+        Some(DataType::Number(NumberDataType::Int64))
+    );
+    assert_eq!(scalar.try_into_vec::<Int64Type>().unwrap(), vec![]);
+    assert_eq!(Scalar::EmptyArray.array_data_type(), None);
+    assert_eq!(
+        Scalar::EmptyArray.try_into_vec::<Int64Type>().unwrap(),
+        vec![]
+    );
+
+    // Mismatched elements report their index.
+    let scalar = Scalar::array_from_iter::<NullableType<Int32Type>>([None, Some(7)]);
+    let err = scalar.try_into_vec::<StringType>().unwrap_err();
+    assert_eq!(err.code(), ErrorCode::BAD_DATA_VALUE_TYPE);
+    assert_eq!(
+        err.message(),
+        "Expected a String value at array index 1, but got 7 of type Int32"
+    );
+    assert_eq!(scalar.try_into_vec::<Int32Type>().unwrap(), vec![
+        None,
+        Some(7)
+    ]);
+
+    let err = Scalar::from(1i64).try_into_vec::<Int64Type>().unwrap_err();
+    assert_eq!(err.code(), ErrorCode::BAD_DATA_VALUE_TYPE);
+    assert_eq!(Scalar::from(1i64).array_data_type(), None);
+}
+
+#[test]
+fn test_date_to_days_u16() {
+    let days = |y, m, d| {
+        let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        (NaiveDate::from_ymd_opt(y, m, d).unwrap() - epoch).num_days() as i32
+    };
+
+    assert_eq!(date_to_days_u16(days(1970, 1, 1)), Ok(0));
+    assert_eq!(date_to_days_u16(days(2149, 6, 6)), Ok(u16::MAX));
+    assert_eq!(days(2149, 6, 6), DATE16_MAX);
+    assert_eq!(days_u16_to_date(u16::MAX), DATE16_MAX);
+    assert_eq!(days_u16_to_date(0), 0);
+
+    assert_eq!(
+        date_to_days_u16(days(2200, 1, 1)),
+        Err(
+            "date 2200-01-01 is out of the range of ClickHouse Date [1970-01-01, 2149-06-06]"
+                .to_string()
+        )
+    );
+    assert!(date_to_days_u16(days(1969, 12, 31)).is_err());
+}
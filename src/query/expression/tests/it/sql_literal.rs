@@ -0,0 +1,133 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono_tz::Tz;
+use databend_common_expression::types::date::string_to_date;
+use databend_common_expression::types::number::NumberScalar;
+use databend_common_expression::types::timestamp::string_to_timestamp;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::StringType;
+use databend_common_expression::FromData;
+use databend_common_expression::Scalar;
+
+fn literal(scalar: Scalar) -> String {
+    scalar.to_sql_literal().unwrap()
+}
+
+#[test]
+fn test_string_literal_quoting() {
+    assert_eq!(literal(Scalar::String("abc".to_string())), "'abc'");
+    assert_eq!(literal(Scalar::String("".to_string())), "''");
+    assert_eq!(literal(Scalar::String("it's".to_string())), "'it\\'s'");
+    assert_eq!(literal(Scalar::String("a\nb".to_string())), "'a\\nb'");
+    assert_eq!(literal(Scalar::String("a\\b".to_string())), "'a\\\\b'");
+    assert_eq!(literal(Scalar::String("\"a\"".to_string())), "'\"a\"'");
+}
+
+#[test]
+fn test_temporal_literal() {
+    // 2021-05-01
+    assert_eq!(literal(Scalar::Date(18748)), "DATE '2021-05-01'");
+    // 2021-05-01 12:30:15.123456
+    assert_eq!(
+        literal(Scalar::Timestamp(1619872215123456)),
+        "TIMESTAMP '2021-05-01 12:30:15.123456Z'"
+    );
+    assert_eq!(
+        literal(Scalar::Timestamp(0)),
+        "TIMESTAMP '1970-01-01 00:00:00.000000Z'"
+    );
+}
+
+// The text between the quotes of a `TYPE 'text'` or `'text'::TYPE` literal.
+fn quoted(literal: &str) -> &str {
+    let start = literal.find('\'').unwrap() + 1;
+    let end = literal.rfind('\'').unwrap();
+    &literal[start..end]
+}
+
+#[test]
+fn test_temporal_literal_round_trip() {
+    // 2024-03-10 02:30:00 and 2023-11-05 01:30:00 don't exist or are ambiguous in New York.
+    let timestamps = [0, 1619872215123456, -1, 1710037800000000, 1699147800000000];
+    let dates = [0, 18748, -1];
+    for tz in [Tz::UTC, Tz::Asia__Shanghai, Tz::America__New_York] {
+        for ts in timestamps {
+            let text = literal(Scalar::Timestamp(ts));
+            let parsed = string_to_timestamp(quoted(&text), tz).unwrap();
+            assert_eq!(parsed.timestamp_micros(), ts, "{text} in {tz}");
+        }
+        for date in dates {
+            let text = literal(Scalar::Date(date));
+            let parsed = string_to_date(quoted(&text), tz).unwrap();
+            let days = parsed
+                .signed_duration_since(chrono::NaiveDate::from_ymd_opt(1970, 1, 1).unwrap())
+                .num_days();
+            assert_eq!(days, date as i64, "{text} in {tz}");
+        }
+    }
+}
+
+#[test]
+fn test_float_literal() {
+    let float64 = |v: f64| literal(Scalar::Number(NumberScalar::Float64(v.into())));
+    let float32 = |v: f32| literal(Scalar::Number(NumberScalar::Float32(v.into())));
+
+    assert_eq!(float64(1.0), "'1.0'::FLOAT64");
+    assert_eq!(float64(f64::NAN), "'NaN'::FLOAT64");
+    assert_eq!(float64(f64::NEG_INFINITY), "'-inf'::FLOAT64");
+    assert_eq!(float32(0.1), "'0.1'::FLOAT32");
+
+    let values = [
+        0.1 + 0.2,
+        1.0,
+        -0.0,
+        1e-300,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        std::f64::consts::PI,
+    ];
+    for v in values {
+        let parsed = quoted(&float64(v)).parse::<f64>().unwrap();
+        assert_eq!(parsed.to_bits(), v.to_bits(), "{v:?}");
+    }
+    assert!(quoted(&float64(f64::NAN)).parse::<f64>().unwrap().is_nan());
+
+    for v in [0.1f32, f32::MAX, f32::EPSILON, -3.5] {
+        let parsed = quoted(&float32(v)).parse::<f32>().unwrap();
+        assert_eq!(parsed.to_bits(), v.to_bits(), "{v:?}");
+    }
+}
+
+#[test]
+fn test_other_literals() {
+    assert_eq!(literal(Scalar::Null), "NULL");
+    assert_eq!(literal(Scalar::Boolean(true)), "true");
+    assert_eq!(literal(Scalar::Number(NumberScalar::Int32(-7))), "-7");
+    assert_eq!(literal(Scalar::Binary(vec![0xab, 0x01])), "X'AB01'");
+    assert_eq!(literal(Scalar::EmptyArray), "[]");
+    assert_eq!(
+        literal(Scalar::Array(Int32Type::from_data(vec![1, 2, 3]))),
+        "[1, 2, 3]"
+    );
+    assert_eq!(
+        literal(Scalar::Array(StringType::from_data(vec!["a'b", "c"]))),
+        "['a\\'b', 'c']"
+    );
+
+    let tuple = Scalar::Tuple(vec![Scalar::Null, Scalar::Boolean(false)]);
+    assert!(tuple.to_sql_literal().is_err());
+}
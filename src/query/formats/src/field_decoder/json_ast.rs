@@ -32,20 +32,24 @@ use databend_common_expression::types::number::Number;
 use databend_common_expression::types::string::StringColumnBuilder;
 use databend_common_expression::types::timestamp::check_timestamp;
 use databend_common_expression::types::AnyType;
+use databend_common_expression::types::DataType;
 use databend_common_expression::types::NumberColumnBuilder;
 use databend_common_expression::with_decimal_type;
 use databend_common_expression::with_number_mapped_type;
 use databend_common_expression::ColumnBuilder;
+use databend_common_expression::Scalar;
 use databend_common_io::cursor_ext::BufferReadDateTimeExt;
 use databend_common_io::cursor_ext::DateTimeResType;
 use databend_common_io::parse_bitmap;
 use databend_common_io::parse_to_ewkb;
+use databend_common_meta_app::principal::BinaryFormat;
 use lexical_core::FromLexical;
 use num::cast::AsPrimitive;
 use num_traits::NumCast;
 use roaring::RoaringTreemap;
 use serde_json::Value;
 
+use crate::binary::decode_binary;
 use crate::FieldDecoder;
 use crate::FileFormatOptionsExt;
 
@@ -72,6 +76,13 @@ impl FieldJsonAstDecoder {
         }
     }
 
+    /// Decodes a JSON value to a scalar of the data type.
+    pub fn read_scalar(&self, data_type: &DataType, value: &Value) -> Result<Scalar> {
+        let mut builder = ColumnBuilder::with_capacity(data_type, 1);
+        self.read_field(&mut builder, value)?;
+        Ok(builder.build_scalar())
+    }
+
     pub fn read_field(&self, column: &mut ColumnBuilder, value: &Value) -> Result<()> {
         match column {
             ColumnBuilder::Null { len } => self.read_null(len, value),
@@ -93,7 +104,7 @@ impl FieldJsonAstDecoder {
             }),
             ColumnBuilder::Date(c) => self.read_date(c, value),
             ColumnBuilder::Timestamp(c) => self.read_timestamp(c, value),
-            ColumnBuilder::Binary(_c) => unimplemented!("binary literal is not supported"),
+            ColumnBuilder::Binary(c) => self.read_binary(c, value),
             ColumnBuilder::String(c) => self.read_string(c, value),
             ColumnBuilder::Array(c) => self.read_array(c, value),
             ColumnBuilder::Map(c) => self.read_map(c, value),
@@ -243,6 +254,19 @@ impl FieldJsonAstDecoder {
         }
     }
 
+    fn read_binary(&self, column: &mut BinaryColumnBuilder, value: &Value) -> Result<()> {
+        match value {
+            Value::String(s) => {
+                // The JSON encoder writes binary values in the default binary format.
+                let bytes = decode_binary(s.as_bytes(), BinaryFormat::default())?;
+                column.put_slice(&bytes);
+                column.commit_row();
+                Ok(())
+            }
+            _ => Err(ErrorCode::BadBytes("Incorrect json value, must be string")),
+        }
+    }
+
     fn read_date(&self, column: &mut Vec<i32>, value: &Value) -> Result<()> {
         match value {
             Value::String(v) => {
@@ -21,7 +21,9 @@ use databend_common_io::constants::NULL_BYTES_LOWER;
 use databend_common_io::constants::TRUE_BYTES_LOWER;
 use geozero::wkb::Ewkb;
 use geozero::ToJson;
+use serde_json::Value;
 
+use crate::binary::encode_binary;
 use crate::field_encoder::helpers::write_json_string;
 use crate::field_encoder::FieldEncoderValues;
 use crate::FileFormatOptionsExt;
@@ -55,13 +57,23 @@ impl FieldEncoderJSON {
 }
 
 impl FieldEncoderJSON {
+    /// Encodes the value at `row_index` of the column as a JSON value.
+    pub fn to_json_value(&self, column: &Column, row_index: usize) -> Value {
+        let mut buf = Vec::new();
+        self.write_field(column, row_index, &mut buf);
+        // The encoder always writes valid JSON.
+        serde_json::from_slice(&buf).unwrap()
+    }
+
     pub(crate) fn write_field(&self, column: &Column, row_index: usize, out_buf: &mut Vec<u8>) {
         match &column {
             Column::Nullable(box c) => self.write_nullable(c, row_index, out_buf),
 
             Column::Binary(c) => {
+                // The raw bytes may be invalid UTF-8, so they are encoded.
                 let buf = unsafe { c.index_unchecked(row_index) };
-                self.write_string(buf, out_buf);
+                let encoded = encode_binary(buf, self.simple.common_settings.binary_format);
+                self.write_string(&encoded, out_buf);
             }
             Column::String(c) => {
                 let buf = unsafe { c.index_unchecked(row_index) };
@@ -155,7 +167,10 @@ impl FieldEncoderJSON {
                     if i != start {
                         out_buf.extend_from_slice(b",");
                     }
-                    self.write_field(&fields[0], i, out_buf);
+                    // The keys of JSON objects must be strings.
+                    let mut key = Vec::new();
+                    self.simple.write_field(&fields[0], i, &mut key, false);
+                    self.write_string(&key, out_buf);
                     out_buf.extend_from_slice(b":");
                     self.write_field(&fields[1], i, out_buf);
                 }
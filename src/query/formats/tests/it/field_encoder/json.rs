@@ -0,0 +1,104 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_exception::Result;
+use databend_common_expression::types::array::ArrayColumn;
+use databend_common_expression::types::number::Float64Type;
+use databend_common_expression::types::number::Int32Type;
+use databend_common_expression::types::number::UInt64Type;
+use databend_common_expression::types::BinaryType;
+use databend_common_expression::types::DateType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::Column;
+use databend_common_expression::FromData;
+use databend_common_formats::field_encoder::FieldEncoderJSON;
+use databend_common_formats::FieldJsonAstDecoder;
+use databend_common_formats::FileFormatOptionsExt;
+use databend_common_settings::Settings;
+use serde_json::json;
+
+fn get_options() -> Result<FileFormatOptionsExt> {
+    let settings = Settings::create("default".to_string());
+    FileFormatOptionsExt::create_from_settings(&settings, false)
+}
+
+#[test]
+fn test_json_value() -> Result<()> {
+    let options = get_options()?;
+    let encoder = FieldEncoderJSON::create(&options);
+    let decoder = FieldJsonAstDecoder::create(&options, true);
+
+    let columns = vec![
+        UInt64Type::from_data(vec![u64::MAX, 0]),
+        Int32Type::from_opt_data(vec![None, Some(-1)]),
+        BinaryType::from_data(vec![vec![0xff, 0x00], vec![]]),
+        DateType::from_data(vec![18321, -1]),
+        // A list of nullable strings.
+        Column::Array(Box::new(ArrayColumn {
+            values: StringType::from_opt_data(vec![Some("a\"b"), None, Some("")]),
+            offsets: vec![0u64, 3, 3].into(),
+        })),
+        Column::Tuple(vec![
+            StringType::from_data(vec!["x", "y"]),
+            Int32Type::from_data(vec![1, 2]),
+        ]),
+    ];
+    let expected = [
+        vec![json!(u64::MAX), json!(0)],
+        vec![json!(null), json!(-1)],
+        vec![json!("FF00"), json!("")],
+        vec![json!("2020-02-29"), json!("1969-12-31")],
+        vec![json!(["a\"b", null, ""]), json!([])],
+        vec![json!({"1": "x", "2": 1}), json!({"1": "y", "2": 2})],
+    ];
+
+    for (column, expected) in columns.iter().zip(expected) {
+        for (row, expected) in expected.iter().enumerate() {
+            let value = encoder.to_json_value(column, row);
+            assert_eq!(&value, expected);
+
+            // The encoded value is decoded back to the same scalar.
+            let scalar = decoder.read_scalar(&column.data_type(), &value)?;
+            assert_eq!(scalar.as_ref(), column.index(row).unwrap());
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_json_value_not_round_trip() -> Result<()> {
+    let encoder = FieldEncoderJSON::create(&get_options()?);
+
+    // NaN and infinity are not valid JSON numbers.
+    let column = Float64Type::from_data(vec![f64::NAN, f64::INFINITY, 1.5]);
+    assert_eq!(encoder.to_json_value(&column, 0), json!(null));
+    assert_eq!(encoder.to_json_value(&column, 1), json!(null));
+    assert_eq!(encoder.to_json_value(&column, 2), json!(1.5));
+
+    // The keys of maps are written as strings.
+    let column = Column::Map(Box::new(ArrayColumn {
+        values: Column::Tuple(vec![
+            Int32Type::from_data(vec![1, 2]),
+            StringType::from_opt_data(vec![Some("a"), None]),
+        ]),
+        offsets: vec![0u64, 2].into(),
+    }));
+    assert_eq!(
+        encoder.to_json_value(&column, 0),
+        json!({"1": "a", "2": null})
+    );
+
+    Ok(())
+}
@@ -13,10 +13,13 @@
 // limitations under the License.
 
 use databend_common_exception::Result;
+use databend_common_expression::types::array::ArrayColumn;
 use databend_common_expression::types::number::Float32Type;
 use databend_common_expression::types::number::Int32Type;
+use databend_common_expression::types::BinaryType;
 use databend_common_expression::types::NumberDataType;
 use databend_common_expression::types::StringType;
+use databend_common_expression::Column;
 use databend_common_expression::FromData;
 use databend_common_expression::TableDataType;
 use databend_common_expression::TableField;
@@ -135,6 +138,38 @@ fn test_string_escape() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_binary() -> Result<()> {
+    let (schema, block) = gen_schema_and_block(
+        vec![
+            TableField::new("c1", TableDataType::Binary),
+            TableField::new("c2", TableDataType::Array(Box::new(TableDataType::Binary))),
+        ],
+        vec![
+            BinaryType::from_data(vec![b"ab".to_vec(), vec![0xff, 0x00, 0x0a], vec![]]),
+            Column::Array(Box::new(ArrayColumn {
+                values: BinaryType::from_data(vec![vec![0xc3], vec![0x28]]),
+                offsets: vec![0u64, 2, 2, 2].into(),
+            })),
+        ],
+    );
+
+    {
+        let mut formatter = get_output_format_clickhouse("ndjson", schema)?;
+        let buffer = formatter.serialize_block(&block)?;
+
+        // The invalid UTF-8 bytes are encoded as hex.
+        let json_block = String::from_utf8(buffer)?;
+        let expect = r#"{"c1":"6162","c2":["C3","28"]}
+{"c1":"FF000A","c2":[]}
+{"c1":"","c2":[]}
+"#;
+        assert_eq!(&json_block, expect);
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_data_block_nullable() -> Result<()> {
     test_data_block(true)
@@ -20,8 +20,13 @@ mod parser;
 
 use criterion::Criterion;
 use databend_common_expression::type_check;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::Int64Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::StringType;
 use databend_common_expression::DataBlock;
 use databend_common_expression::Evaluator;
+use databend_common_expression::FromData;
 use databend_common_expression::FunctionContext;
 use databend_common_functions::BUILTIN_FUNCTIONS;
 
@@ -50,5 +55,101 @@ fn bench(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, bench);
+// `a IN (...)` is lowered to `contains` on the distinct values of the list, instead of a chain
+// of OR'd equality comparisons.
+fn bench_in_list(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bench_in_list");
+
+    let columns = [("a", DataType::Number(NumberDataType::Int64))];
+    let block =
+        DataBlock::new_from_columns(vec![Int64Type::from_data((0..10000).collect::<Vec<i64>>())]);
+    let func_ctx = FunctionContext::default();
+    let evaluator = Evaluator::new(&block, &func_ctx, &BUILTIN_FUNCTIONS);
+
+    for n in [10, 100, 1000] {
+        let values = (0..n).map(|i| i * 7).collect::<Vec<i64>>();
+
+        let contains = format!(
+            "contains([{}], a)",
+            values
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        let raw_expr = parser::parse_raw_expr(&contains, &columns);
+        let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).unwrap();
+        group.bench_function(format!("contains/{n}"), |b| b.iter(|| evaluator.run(&expr)));
+
+        let or_chain = values
+            .iter()
+            .map(|v| format!("a = {v}"))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        let raw_expr = parser::parse_raw_expr(&or_chain, &columns);
+        let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).unwrap();
+        group.bench_function(format!("or_chain/{n}"), |b| b.iter(|| evaluator.run(&expr)));
+    }
+}
+
+// The case insensitive comparison of ASCII strings with a constant should keep up with the case
+// sensitive one.
+fn bench_string_eq(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bench_string_eq");
+
+    let columns = [("a", DataType::String)];
+    let block = DataBlock::new_from_columns(vec![StringType::from_data(
+        (0..10000)
+            .map(|i| format!("User_{}", i % 100))
+            .collect::<Vec<_>>(),
+    )]);
+    let func_ctx = FunctionContext::default();
+    let evaluator = Evaluator::new(&block, &func_ctx, &BUILTIN_FUNCTIONS);
+
+    for (name, text) in [
+        ("eq", "a = 'User_42'"),
+        ("equals_ci", "equals_ci(a, 'user_42')"),
+        ("like", "a like 'User_4%'"),
+        ("ilike", "a ilike 'user_4%'"),
+    ] {
+        let raw_expr = parser::parse_raw_expr(text, &columns);
+        let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).unwrap();
+        group.bench_function(name, |b| b.iter(|| evaluator.run(&expr)));
+    }
+}
+
+// A constant LIKE pattern is analyzed once, and the prefix, suffix and substring patterns take
+// the fast paths. Each of them is compared with the regexp the pattern would translate to.
+fn bench_like(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bench_like");
+
+    let columns = [("a", DataType::String)];
+    let block = DataBlock::new_from_columns(vec![StringType::from_data(
+        (0..1_000_000)
+            .map(|i| format!("user-{}", i % 1000))
+            .collect::<Vec<_>>(),
+    )]);
+    let func_ctx = FunctionContext::default();
+    let evaluator = Evaluator::new(&block, &func_ctx, &BUILTIN_FUNCTIONS);
+
+    for (name, like, regexp) in [
+        ("eq", "user-42", "^user-42$"),
+        ("starts_with", "user-4%", "^user-4"),
+        ("ends_with", "%-42", "-42$"),
+        ("contains", "%r-4%", "r-4"),
+        ("pattern", "u%r-_2", "^u.*r-.2$"),
+    ] {
+        let raw_expr = parser::parse_raw_expr(&format!("a like '{like}'"), &columns);
+        let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).unwrap();
+        group.bench_function(format!("like/{name}"), |b| b.iter(|| evaluator.run(&expr)));
+
+        let raw_expr = parser::parse_raw_expr(&format!("regexp(a, '{regexp}')"), &columns);
+        let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).unwrap();
+        group.bench_function(format!("regexp/{name}"), |b| {
+            b.iter(|| evaluator.run(&expr))
+        });
+    }
+}
+
+criterion_group!(benches, bench, bench_in_list, bench_string_eq, bench_like);
 criterion_main!(benches);
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::io::Read;
+use std::io::Write;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
@@ -28,6 +30,7 @@ use databend_common_expression::Scalar;
 use num_traits::AsPrimitive;
 
 use super::aggregate_sum::DecimalSumState;
+use super::aggregate_sum::KahanSum;
 use super::AggregateUnaryFunction;
 use super::FunctionData;
 use super::UnaryState;
@@ -94,6 +97,74 @@ where
     }
 }
 
+// The average of Float32 or Float64, the sum is compensated, see `KahanSum`.
+struct FloatAvgState<T> {
+    pub value: KahanSum,
+    pub count: u64,
+    _t: PhantomData<T>,
+}
+
+impl<T> Default for FloatAvgState<T> {
+    fn default() -> Self {
+        Self {
+            value: KahanSum::default(),
+            count: 0,
+            _t: PhantomData,
+        }
+    }
+}
+
+// Laid out as `NumberAvgState` followed by the compensation.
+impl<T> BorshSerialize for FloatAvgState<T> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.value.serialize_sum(writer)?;
+        self.count.serialize(writer)?;
+        self.value.serialize_compensation(writer)
+    }
+}
+
+impl<T> BorshDeserialize for FloatAvgState<T> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let sum = KahanSum::deserialize_sum(reader)?;
+        let count = u64::deserialize_reader(reader)?;
+        let compensation = KahanSum::deserialize_compensation(reader)?;
+        Ok(Self {
+            value: KahanSum { sum, compensation },
+            count,
+            _t: PhantomData,
+        })
+    }
+}
+
+impl<T> UnaryState<T, Float64Type> for FloatAvgState<T>
+where
+    T: ValueType + Sync + Send,
+    T::Scalar: Number + AsPrimitive<F64>,
+{
+    fn add(&mut self, other: T::ScalarRef<'_>) -> Result<()> {
+        self.count += 1;
+        let other: F64 = T::to_owned_scalar(other).as_();
+        self.value.add(other.0);
+        Ok(())
+    }
+
+    fn merge(&mut self, rhs: &Self) -> Result<()> {
+        self.count += rhs.count;
+        self.value.merge(&rhs.value);
+        Ok(())
+    }
+
+    fn merge_result(
+        &mut self,
+        builder: &mut Vec<F64>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        let value = self.value.value() / (self.count as f64);
+        builder.push(F64::from(value));
+        Ok(())
+    }
+}
+
 struct DecimalAvgData {
     // only for decimals
     // AVG：AVG(DECIMAL(a, b)) -> DECIMAL(38 or 76, max(b, 4))。
@@ -206,6 +277,30 @@ pub fn try_create_aggregate_avg_function(
         arguments[0].clone()
     };
 
+    match &data_type {
+        DataType::Number(NumberDataType::Float32) => {
+            let return_type = Float64Type::data_type();
+            return AggregateUnaryFunction::<
+                FloatAvgState<Float32Type>,
+                Float32Type,
+                Float64Type,
+            >::try_create_unary(
+                display_name, return_type, params, arguments[0].clone()
+            );
+        }
+        DataType::Number(NumberDataType::Float64) => {
+            let return_type = Float64Type::data_type();
+            return AggregateUnaryFunction::<
+                FloatAvgState<Float64Type>,
+                Float64Type,
+                Float64Type,
+            >::try_create_unary(
+                display_name, return_type, params, arguments[0].clone()
+            );
+        }
+        _ => {}
+    }
+
     with_number_mapped_type!(|NUM| match &data_type {
         DataType::Number(NumberDataType::NUM) => {
             type TSum = <NUM as ResultTypeOfUnary>::Sum;
@@ -12,6 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Read;
+use std::io::Write;
+use std::marker::PhantomData;
+
 use borsh::BorshDeserialize;
 use borsh::BorshSerialize;
 use databend_common_arrow::arrow::bitmap::Bitmap;
@@ -108,6 +112,166 @@ where
     }
 }
 
+/// The compensated sum of floats, the low-order bits lost by the additions are kept in
+/// `compensation`, so the sum of many small values is not swamped by the rounding errors
+/// (the Kahan-Babuska summation).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct KahanSum {
+    pub sum: f64,
+    pub compensation: f64,
+}
+
+impl KahanSum {
+    pub fn add(&mut self, value: f64) {
+        let sum = self.sum + value;
+        if self.sum.abs() >= value.abs() {
+            self.compensation += (self.sum - sum) + value;
+        } else {
+            self.compensation += (value - sum) + self.sum;
+        }
+        self.sum = sum;
+    }
+
+    pub fn merge(&mut self, other: &KahanSum) {
+        self.add(other.sum);
+        self.compensation += other.compensation;
+    }
+
+    pub fn value(&self) -> f64 {
+        // The compensation is NaN once the sum is infinite.
+        match self.sum.is_finite() {
+            true => self.sum + self.compensation,
+            false => self.sum,
+        }
+    }
+
+    pub(crate) fn serialize_sum<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.sum.to_le_bytes())
+    }
+
+    pub(crate) fn serialize_compensation<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.compensation.to_le_bytes())
+    }
+
+    pub(crate) fn deserialize_sum<R: Read>(reader: &mut R) -> std::io::Result<f64> {
+        let mut buf = [0u8; 8];
+        reader.read_exact(&mut buf)?;
+        Ok(f64::from_le_bytes(buf))
+    }
+
+    // The compensation is written after the other fields of the states, the states written
+    // before it was added end without it and are read with no compensation.
+    pub(crate) fn deserialize_compensation<R: Read>(reader: &mut R) -> std::io::Result<f64> {
+        let mut buf = [0u8; 8];
+        let mut len = 0;
+        while len < buf.len() {
+            match reader.read(&mut buf[len..])? {
+                0 => break,
+                n => len += n,
+            }
+        }
+
+        match len {
+            0 => Ok(0.0),
+            8 => Ok(f64::from_le_bytes(buf)),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "Truncated compensation of the float sum state",
+            )),
+        }
+    }
+}
+
+/// Sums a Float32 or Float64 column into a Float64 with [`KahanSum`], the NULLs are skipped.
+pub fn column_sum_kahan(column: &Column) -> Result<Scalar> {
+    let (column, validity) = match column {
+        Column::Nullable(column) => (&column.column, Some(&column.validity)),
+        column => (column, None),
+    };
+
+    let values: Box<dyn Iterator<Item = f64>> = match column {
+        Column::Number(NumberColumn::Float32(values)) => {
+            Box::new(values.iter().map(|value| value.0 as f64))
+        }
+        Column::Number(NumberColumn::Float64(values)) => {
+            Box::new(values.iter().map(|value| value.0))
+        }
+        _ => {
+            return Err(ErrorCode::BadDataValueType(format!(
+                "Compensated sum does not support type '{:?}'",
+                column.data_type()
+            )));
+        }
+    };
+
+    let mut sum = KahanSum::default();
+    for (row, value) in values.enumerate() {
+        if validity.map_or(true, |validity| validity.get_bit(row)) {
+            sum.add(value);
+        }
+    }
+    Ok(Scalar::Number(NumberScalar::Float64(sum.value().into())))
+}
+
+/// The sum of Float32 or Float64, see [`KahanSum`].
+pub struct FloatSumState<T> {
+    pub value: KahanSum,
+    _t: PhantomData<T>,
+}
+
+impl<T> Default for FloatSumState<T> {
+    fn default() -> Self {
+        FloatSumState {
+            value: KahanSum::default(),
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T> BorshSerialize for FloatSumState<T> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.value.serialize_sum(writer)?;
+        self.value.serialize_compensation(writer)
+    }
+}
+
+impl<T> BorshDeserialize for FloatSumState<T> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let sum = KahanSum::deserialize_sum(reader)?;
+        let compensation = KahanSum::deserialize_compensation(reader)?;
+        Ok(FloatSumState {
+            value: KahanSum { sum, compensation },
+            _t: PhantomData,
+        })
+    }
+}
+
+impl<T> UnaryState<T, Float64Type> for FloatSumState<T>
+where
+    T: ValueType + Sync + Send,
+    T::Scalar: Number + AsPrimitive<F64>,
+{
+    fn add(&mut self, other: T::ScalarRef<'_>) -> Result<()> {
+        let other: F64 = T::to_owned_scalar(other).as_();
+        self.value.add(other.0);
+        Ok(())
+    }
+
+    fn merge(&mut self, rhs: &Self) -> Result<()> {
+        self.value.merge(&rhs.value);
+        Ok(())
+    }
+
+    fn merge_result(
+        &mut self,
+        builder: &mut Vec<F64>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        builder.push(F64::from(self.value.value()));
+        Ok(())
+    }
+}
+
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct DecimalSumState<const OVERFLOW: bool, T>
 where
@@ -174,6 +338,31 @@ pub fn try_create_aggregate_sum_function(
         data_type = Int8Type::data_type();
     }
 
+    // The floats are summed with compensation, see `KahanSum`.
+    match &data_type {
+        DataType::Number(NumberDataType::Float32) => {
+            let return_type = Float64Type::data_type();
+            return AggregateUnaryFunction::<
+                FloatSumState<Float32Type>,
+                Float32Type,
+                Float64Type,
+            >::try_create_unary(
+                display_name, return_type, params, arguments[0].clone()
+            );
+        }
+        DataType::Number(NumberDataType::Float64) => {
+            let return_type = Float64Type::data_type();
+            return AggregateUnaryFunction::<
+                FloatSumState<Float64Type>,
+                Float64Type,
+                Float64Type,
+            >::try_create_unary(
+                display_name, return_type, params, arguments[0].clone()
+            );
+        }
+        _ => {}
+    }
+
     with_number_mapped_type!(|NUM| match &data_type {
         DataType::Number(NumberDataType::NUM) => {
             type TSum = <NUM as ResultTypeOfUnary>::Sum;
@@ -47,12 +47,12 @@ pub fn register(registry: &mut FunctionRegistry) {
     }
 
     for func_name in ALL_COMP_FUNC_NAMES {
-        // Disable auto cast from strings, e.g., `1 < '1'`.
+        // Allow auto cast from strings and booleans to numbers, e.g., `col_int < '1'`.
         registry.register_additional_cast_rules(func_name, GENERAL_CAST_RULES.iter().cloned());
+        registry.register_additional_cast_rules(func_name, CAST_FROM_STRING_RULES.iter().cloned());
+        registry.register_additional_cast_rules(func_name, CAST_FROM_BOOLEAN_RULES());
         registry.register_additional_cast_rules(func_name, CAST_FROM_VARIANT_RULES());
     }
-    // for eq function: we allow cast from string to int or float, eg: col_int = '1'
-    registry.register_additional_cast_rules("eq", CAST_FROM_STRING_RULES.iter().cloned());
 
     // Timestamp/Date --> other ints and floats
     // Now it only overload 'to_int64'
@@ -206,8 +206,8 @@ pub const GENERAL_CAST_RULES: AutoCastRules = &[
 ];
 
 /// The rules for automatic casting from string to other types. For example, they are
-/// used to allow `add_hours('2023-01-01 00:00:00', '1')`. But they should be disabled
-/// for comparison functions, because `1 < '1'` should be an error.
+/// used to allow `add_hours('2023-01-01 00:00:00', '1')` and `col_int < '1'`. But they
+/// should be disabled for the cast functions.
 pub const CAST_FROM_STRING_RULES: AutoCastRules = &[
     (DataType::String, DataType::Number(NumberDataType::Int64)),
     (DataType::String, DataType::Number(NumberDataType::UInt64)),
@@ -215,6 +215,15 @@ pub const CAST_FROM_STRING_RULES: AutoCastRules = &[
     (DataType::String, DataType::Number(NumberDataType::Float32)),
 ];
 
+/// The rules for automatic casting from boolean to numbers in comparison functions,
+/// `true` is casted to 1 and `false` is casted to 0.
+#[allow(non_snake_case)]
+pub fn CAST_FROM_BOOLEAN_RULES() -> impl IntoIterator<Item = (DataType, DataType)> {
+    ALL_NUMERICS_TYPES
+        .iter()
+        .map(|num_ty| (DataType::Boolean, DataType::Number(*num_ty)))
+}
+
 #[allow(non_snake_case)]
 pub fn CAST_FROM_VARIANT_RULES() -> impl IntoIterator<Item = (DataType, DataType)> {
     [
@@ -17,6 +17,7 @@
 use std::ops::BitAnd;
 use std::ops::BitOr;
 use std::ops::BitXor;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use databend_common_arrow::arrow::bitmap::Bitmap;
@@ -68,6 +69,7 @@ use lexical_core::FormattedSize;
 use num_traits::AsPrimitive;
 
 use super::arithmetic_modulo::vectorize_modulo;
+use super::arithmetic_overflow::register_overflow_arithmetic;
 use super::decimal::register_decimal_to_int;
 use crate::scalars::decimal::register_decimal_arithmetic;
 use crate::scalars::decimal::register_decimal_to_float;
@@ -79,6 +81,11 @@ pub fn register(registry: &mut FunctionRegistry) {
     registry.register_aliases("div", &["intdiv"]);
     registry.register_aliases("modulo", &["mod"]);
     registry.register_aliases("pow", &["power"]);
+    registry.register_aliases("bit_and", &["bitand"]);
+    registry.register_aliases("bit_or", &["bitor"]);
+    registry.register_aliases("bit_xor", &["bitxor"]);
+    registry.register_aliases("bit_shift_left", &["bitshiftleft"]);
+    registry.register_aliases("bit_shift_right", &["bitshiftright"]);
 
     register_unary_minus(registry);
     register_string_to_number(registry);
@@ -86,6 +93,7 @@ pub fn register(registry: &mut FunctionRegistry) {
     register_number_to_number(registry);
     register_binary_arithmetic(registry);
     register_unary_arithmetic(registry);
+    register_overflow_arithmetic(registry);
 }
 
 macro_rules! register_plus {
@@ -379,7 +387,11 @@ macro_rules! register_bitwise_shift_left {
         $registry.register_2_arg::<NumberType<L>, NumberType<R>, NumberType<i64>, _, _>(
             "bit_shift_left",
             |_, _, _| FunctionDomain::Full,
-            |a, b, _| (AsPrimitive::<i64>::as_(a)) << (AsPrimitive::<u64>::as_(b)),
+            |a, b, _| match AsPrimitive::<u64>::as_(b) {
+                // Shifting by the bit width or more is 0, instead of overflowing.
+                b if b < 64 => (AsPrimitive::<i64>::as_(a)) << b,
+                _ => 0,
+            },
         );
     };
 }
@@ -391,7 +403,10 @@ macro_rules! register_bitwise_shift_right {
         $registry.register_2_arg::<NumberType<L>, NumberType<R>, NumberType<i64>, _, _>(
             "bit_shift_right",
             |_, _, _| FunctionDomain::Full,
-            |a, b, _| (AsPrimitive::<i64>::as_(a)) >> (AsPrimitive::<u64>::as_(b)),
+            |a, b, _| match AsPrimitive::<u64>::as_(b) {
+                b if b < 64 => (AsPrimitive::<i64>::as_(a)) >> b,
+                _ => 0,
+            },
         );
     };
 }
@@ -919,6 +934,30 @@ fn register_string_to_number(registry: &mut FunctionRegistry) {
             }
         });
     }
+
+    // Coerces the strings in arithmetic with the `loose_arithmetic_coercion` setting, the strings
+    // which are not numbers become NULL and are counted.
+    registry.register_combine_nullable_1_arg::<StringType, NumberType<F64>, _, _>(
+        "loose_to_float64",
+        |_, _| FunctionDomain::Full,
+        vectorize_with_builder_1_arg::<StringType, NullableType<NumberType<F64>>>(
+            |val, output, ctx| match val.trim().parse::<F64>() {
+                Ok(new_val) => output.push(new_val),
+                Err(_) => {
+                    let is_null = ctx
+                        .validity
+                        .as_ref()
+                        .is_some_and(|validity| !validity.get_bit(output.len()));
+                    if !is_null {
+                        ctx.func_ctx
+                            .loose_coercion_failures
+                            .fetch_add(1, Ordering::Relaxed);
+                    }
+                    output.push_null();
+                }
+            },
+        ),
+    );
 }
 
 pub fn register_number_to_string(registry: &mut FunctionRegistry) {
@@ -0,0 +1,196 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_expression::types::number::NumberType;
+use databend_common_expression::types::number::F32;
+use databend_common_expression::types::number::F64;
+use databend_common_expression::types::Number;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::SimpleDomain;
+use databend_common_expression::types::ALL_NUMERICS_TYPES;
+use databend_common_expression::vectorize_with_builder_2_arg;
+use databend_common_expression::with_number_mapped_type;
+use databend_common_expression::EvalContext;
+use databend_common_expression::FunctionDomain;
+use databend_common_expression::FunctionRegistry;
+use databend_common_expression::Value;
+use databend_common_expression::ValueRef;
+
+/// The arithmetic operations which may overflow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArithmeticOp {
+    Plus,
+    Minus,
+    Multiply,
+}
+
+/// How the integer arithmetic handles the overflow. The floats ignore the mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowMode {
+    /// Wraps around at the boundary of the type, e.g. `127_i8 + 1` is `-128`.
+    Wrapping,
+    /// Clamps to the boundary of the type, e.g. `127_i8 + 1` is `127`.
+    Saturating,
+    /// Raises an error.
+    Checked,
+}
+
+pub trait OverflowArithmetic: Number {
+    /// Returns `None` if the result overflows in the `Checked` mode.
+    fn arithmetic_op_with_mode(
+        op: ArithmeticOp,
+        mode: OverflowMode,
+        lhs: Self,
+        rhs: Self,
+    ) -> Option<Self>;
+}
+
+macro_rules! impl_integer_overflow_arithmetic {
+    ($($ty:ty),*) => {
+        $(
+            impl OverflowArithmetic for $ty {
+                fn arithmetic_op_with_mode(
+                    op: ArithmeticOp,
+                    mode: OverflowMode,
+                    lhs: Self,
+                    rhs: Self,
+                ) -> Option<Self> {
+                    match (op, mode) {
+                        (ArithmeticOp::Plus, OverflowMode::Wrapping) => Some(lhs.wrapping_add(rhs)),
+                        (ArithmeticOp::Plus, OverflowMode::Saturating) => {
+                            Some(lhs.saturating_add(rhs))
+                        }
+                        (ArithmeticOp::Plus, OverflowMode::Checked) => lhs.checked_add(rhs),
+                        (ArithmeticOp::Minus, OverflowMode::Wrapping) => Some(lhs.wrapping_sub(rhs)),
+                        (ArithmeticOp::Minus, OverflowMode::Saturating) => {
+                            Some(lhs.saturating_sub(rhs))
+                        }
+                        (ArithmeticOp::Minus, OverflowMode::Checked) => lhs.checked_sub(rhs),
+                        (ArithmeticOp::Multiply, OverflowMode::Wrapping) => {
+                            Some(lhs.wrapping_mul(rhs))
+                        }
+                        (ArithmeticOp::Multiply, OverflowMode::Saturating) => {
+                            Some(lhs.saturating_mul(rhs))
+                        }
+                        (ArithmeticOp::Multiply, OverflowMode::Checked) => lhs.checked_mul(rhs),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_float_overflow_arithmetic {
+    ($($ty:ty),*) => {
+        $(
+            impl OverflowArithmetic for $ty {
+                fn arithmetic_op_with_mode(
+                    op: ArithmeticOp,
+                    _: OverflowMode,
+                    lhs: Self,
+                    rhs: Self,
+                ) -> Option<Self> {
+                    match op {
+                        ArithmeticOp::Plus => Some(lhs + rhs),
+                        ArithmeticOp::Minus => Some(lhs - rhs),
+                        ArithmeticOp::Multiply => Some(lhs * rhs),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_overflow_arithmetic!(u8, u16, u32, u64, i8, i16, i32, i64);
+impl_float_overflow_arithmetic!(F32, F64);
+
+/// Applies the arithmetic to the columns or scalars with the overflow mode,
+/// the overflowed rows are reported as errors in the `Checked` mode.
+pub fn vectorize_arithmetic_op_with_mode<T: OverflowArithmetic>(
+    op: ArithmeticOp,
+    mode: OverflowMode,
+) -> impl Fn(
+    ValueRef<NumberType<T>>,
+    ValueRef<NumberType<T>>,
+    &mut EvalContext,
+) -> Value<NumberType<T>>
+       + Copy
+       + Send
+       + Sync {
+    vectorize_with_builder_2_arg::<NumberType<T>, NumberType<T>, NumberType<T>>(
+        move |lhs, rhs, output, ctx| match T::arithmetic_op_with_mode(op, mode, lhs, rhs) {
+            Some(value) => output.push(value),
+            None => {
+                ctx.set_error(output.len(), "number overflowed");
+                output.push(T::default());
+            }
+        },
+    )
+}
+
+pub fn register_overflow_arithmetic(registry: &mut FunctionRegistry) {
+    for num_type in ALL_NUMERICS_TYPES {
+        with_number_mapped_type!(|NUM_TYPE| match num_type {
+            NumberDataType::NUM_TYPE => {
+                register_arithmetic_with_mode::<NUM_TYPE>(
+                    registry,
+                    "plus_saturating",
+                    ArithmeticOp::Plus,
+                    OverflowMode::Saturating,
+                );
+                register_arithmetic_with_mode::<NUM_TYPE>(
+                    registry,
+                    "minus_saturating",
+                    ArithmeticOp::Minus,
+                    OverflowMode::Saturating,
+                );
+            }
+        });
+    }
+}
+
+fn register_arithmetic_with_mode<T: OverflowArithmetic>(
+    registry: &mut FunctionRegistry,
+    name: &str,
+    op: ArithmeticOp,
+    mode: OverflowMode,
+) {
+    registry
+        .register_passthrough_nullable_2_arg::<NumberType<T>, NumberType<T>, NumberType<T>, _, _>(
+            name,
+            move |_, lhs, rhs| {
+                // The saturating and checked plus and minus are monotonic.
+                let bounds = match (op, mode) {
+                    (_, OverflowMode::Wrapping) | (ArithmeticOp::Multiply, _) => None,
+                    (ArithmeticOp::Plus, _) => Some(((lhs.min, rhs.min), (lhs.max, rhs.max))),
+                    (ArithmeticOp::Minus, _) => Some(((lhs.min, rhs.max), (lhs.max, rhs.min))),
+                };
+                let Some(((min_lhs, min_rhs), (max_lhs, max_rhs))) = bounds else {
+                    return match mode {
+                        OverflowMode::Checked => FunctionDomain::MayThrow,
+                        _ => FunctionDomain::Full,
+                    };
+                };
+
+                match (
+                    T::arithmetic_op_with_mode(op, mode, min_lhs, min_rhs),
+                    T::arithmetic_op_with_mode(op, mode, max_lhs, max_rhs),
+                ) {
+                    (Some(min), Some(max)) => FunctionDomain::Domain(SimpleDomain { min, max }),
+                    _ => FunctionDomain::MayThrow,
+                }
+            },
+            vectorize_arithmetic_op_with_mode::<T>(op, mode),
+        );
+}
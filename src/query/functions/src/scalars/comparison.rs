@@ -22,6 +22,7 @@ use databend_common_expression::types::string::StringDomain;
 use databend_common_expression::types::AnyType;
 use databend_common_expression::types::ArgType;
 use databend_common_expression::types::ArrayType;
+use databend_common_expression::types::BinaryType;
 use databend_common_expression::types::BooleanType;
 use databend_common_expression::types::DataType;
 use databend_common_expression::types::DateType;
@@ -56,10 +57,13 @@ use crate::scalars::string_multi_args::regexp;
 pub fn register(registry: &mut FunctionRegistry) {
     register_variant_cmp(registry);
     register_string_cmp(registry);
+    register_binary_cmp(registry);
     register_date_cmp(registry);
     register_timestamp_cmp(registry);
-    register_number_cmp(registry);
+    // Register before the numbers, so that comparing two booleans
+    // doesn't match a number overload with the boolean auto cast.
     register_boolean_cmp(registry);
+    register_number_cmp(registry);
     register_array_cmp(registry);
     register_tuple_cmp(registry);
     register_like(registry);
@@ -159,6 +163,145 @@ macro_rules! register_simple_domain_type_cmp {
 
 fn register_string_cmp(registry: &mut FunctionRegistry) {
     register_simple_domain_type_cmp!(registry, StringType);
+
+    registry.register_passthrough_nullable_2_arg::<StringType, StringType, BooleanType, _, _>(
+        "equals_ci",
+        |_, _, _| FunctionDomain::Full,
+        vectorize_string_eq(StringCmpOptions {
+            case_insensitive: true,
+        }),
+    );
+}
+
+/// The options of the string comparisons.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StringCmpOptions {
+    /// Compares the strings by their [`simple_fold`] case folding.
+    pub case_insensitive: bool,
+}
+
+/// Checks whether two strings are equal under `options`. If both of them are ASCII, the case
+/// insensitive comparison doesn't fold them.
+#[inline]
+pub fn string_eq(lhs: &str, rhs: &str, options: StringCmpOptions) -> bool {
+    if !options.case_insensitive {
+        lhs == rhs
+    } else if lhs.is_ascii() && rhs.is_ascii() {
+        lhs.eq_ignore_ascii_case(rhs)
+    } else {
+        lhs.chars()
+            .map(simple_fold)
+            .eq(rhs.chars().map(simple_fold))
+    }
+}
+
+/// Folds the case of a char by the Unicode simple case folding, which maps a char to a single
+/// char. So `ß` is kept as is and doesn't equal `SS`, as it does by the full case folding. The
+/// Turkish dotted `İ` and dotless `ı` are kept as well, neither of them equals `i` or `I`.
+#[inline]
+pub fn simple_fold(c: char) -> char {
+    if c.is_ascii() {
+        return c.to_ascii_lowercase();
+    }
+    // `ı` has no case folding, but its uppercase `I` folds to `i`.
+    if c == 'ı' {
+        return c;
+    }
+    let upper = single_char(c.to_uppercase()).unwrap_or(c);
+    single_char(upper.to_lowercase()).unwrap_or(upper)
+}
+
+#[inline]
+fn single_char(mut chars: impl Iterator<Item = char>) -> Option<char> {
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+fn fold_str(s: &str) -> String {
+    let mut folded = String::with_capacity(s.len());
+    fold_str_into(s, &mut folded);
+    folded
+}
+
+fn fold_str_into(s: &str, buf: &mut String) {
+    buf.clear();
+    if s.is_ascii() {
+        buf.push_str(s);
+        buf.make_ascii_lowercase();
+    } else {
+        buf.extend(s.chars().map(simple_fold));
+    }
+}
+
+fn vectorize_string_eq(
+    options: StringCmpOptions,
+) -> impl Fn(ValueRef<StringType>, ValueRef<StringType>, &mut EvalContext) -> Value<BooleanType> + Copy
+{
+    move |arg1, arg2, _| match (arg1, arg2) {
+        (ValueRef::Scalar(arg1), ValueRef::Scalar(arg2)) => {
+            Value::Scalar(string_eq(arg1, arg2, options))
+        }
+        (ValueRef::Column(col), ValueRef::Scalar(scalar))
+        | (ValueRef::Scalar(scalar), ValueRef::Column(col)) => {
+            if !options.case_insensitive {
+                let it = StringType::iter_column(&col).map(|val| val == scalar);
+                return Value::Column(BooleanType::column_from_iter(it, &[]));
+            }
+
+            // The constant side is folded once, instead of for each row.
+            let folded = fold_str(scalar);
+            let is_ascii = scalar.is_ascii();
+            let it = StringType::iter_column(&col).map(|val| {
+                if is_ascii && val.is_ascii() {
+                    val.eq_ignore_ascii_case(&folded)
+                } else {
+                    val.chars().map(simple_fold).eq(folded.chars())
+                }
+            });
+            Value::Column(BooleanType::column_from_iter(it, &[]))
+        }
+        (ValueRef::Column(arg1), ValueRef::Column(arg2)) => {
+            let it = StringType::iter_column(&arg1)
+                .zip(StringType::iter_column(&arg2))
+                .map(|(arg1, arg2)| string_eq(arg1, arg2, options));
+            Value::Column(BooleanType::column_from_iter(it, &[]))
+        }
+    }
+}
+
+fn register_binary_cmp(registry: &mut FunctionRegistry) {
+    registry.register_2_arg::<BinaryType, BinaryType, BooleanType, _, _>(
+        "eq",
+        |_, _, _| FunctionDomain::Full,
+        |lhs, rhs, _| lhs == rhs,
+    );
+    registry.register_2_arg::<BinaryType, BinaryType, BooleanType, _, _>(
+        "noteq",
+        |_, _, _| FunctionDomain::Full,
+        |lhs, rhs, _| lhs != rhs,
+    );
+    registry.register_2_arg::<BinaryType, BinaryType, BooleanType, _, _>(
+        "gt",
+        |_, _, _| FunctionDomain::Full,
+        |lhs, rhs, _| lhs > rhs,
+    );
+    registry.register_2_arg::<BinaryType, BinaryType, BooleanType, _, _>(
+        "gte",
+        |_, _, _| FunctionDomain::Full,
+        |lhs, rhs, _| lhs >= rhs,
+    );
+    registry.register_2_arg::<BinaryType, BinaryType, BooleanType, _, _>(
+        "lt",
+        |_, _, _| FunctionDomain::Full,
+        |lhs, rhs, _| lhs < rhs,
+    );
+    registry.register_2_arg::<BinaryType, BinaryType, BooleanType, _, _>(
+        "lte",
+        |_, _, _| FunctionDomain::Full,
+        |lhs, rhs, _| lhs <= rhs,
+    );
 }
 
 fn register_date_cmp(registry: &mut FunctionRegistry) {
@@ -522,34 +665,13 @@ fn register_like(registry: &mut FunctionRegistry) {
             }
             FunctionDomain::Full
         },
-        vectorize_like(|str, pat, _, pattern_type| {
-            match &pattern_type {
-                PatternType::OrdinalStr => str == pat,
-                PatternType::EndOfPercent => {
-                    // fast path, can use starts_with
-                    let starts_with = &pat[..pat.len() - 1];
-                    str.starts_with(starts_with)
-                }
-                PatternType::StartOfPercent => {
-                    // fast path, can use ends_with
-                    str.ends_with(&pat[1..])
-                }
-
-                PatternType::SurroundByPercent => {
-                    if pat.len() > 2 {
-                        memmem::find(str, &pat[1..pat.len() - 1]).is_some()
-                    } else {
-                        // true for empty '%%' pattern, which follows pg/mysql way
-                        true
-                    }
-                }
+        vectorize_like(|str, pat, _, pattern_type| string_like(str, pat, pattern_type)),
+    );
 
-                PatternType::SimplePattern(simple_pattern) => {
-                    simple_like(str, simple_pattern.0, simple_pattern.1, &simple_pattern.2)
-                }
-                PatternType::ComplexPattern => like(str, pat),
-            }
-        }),
+    registry.register_passthrough_nullable_2_arg::<StringType, StringType, BooleanType, _, _>(
+        "ilike",
+        |_, _, _| FunctionDomain::Full,
+        vectorize_ilike,
     );
 
     registry.register_passthrough_nullable_2_arg::<StringType, StringType, BooleanType, _, _>(
@@ -575,6 +697,95 @@ fn register_like(registry: &mut FunctionRegistry) {
     );
 }
 
+fn string_like(str: &[u8], pat: &[u8], pattern_type: &PatternType) -> bool {
+    match pattern_type {
+        PatternType::OrdinalStr => str == pat,
+        PatternType::EndOfPercent => {
+            // fast path, can use starts_with
+            let starts_with = &pat[..pat.len() - 1];
+            str.starts_with(starts_with)
+        }
+        PatternType::StartOfPercent => {
+            // fast path, can use ends_with
+            str.ends_with(&pat[1..])
+        }
+
+        PatternType::SurroundByPercent => {
+            if pat.len() > 2 {
+                memmem::find(str, &pat[1..pat.len() - 1]).is_some()
+            } else {
+                // true for empty '%%' pattern, which follows pg/mysql way
+                true
+            }
+        }
+
+        PatternType::SimplePattern(simple_pattern) => {
+            simple_like(str, simple_pattern.0, simple_pattern.1, &simple_pattern.2)
+        }
+        PatternType::ComplexPattern => like(str, pat),
+    }
+}
+
+/// Matches the [`simple_fold`] case folding of the strings with the one of the patterns, the
+/// constant pattern is folded once.
+fn vectorize_ilike(
+    arg1: ValueRef<StringType>,
+    arg2: ValueRef<StringType>,
+    _: &mut EvalContext,
+) -> Value<BooleanType> {
+    match (arg1, arg2) {
+        (ValueRef::Scalar(arg1), ValueRef::Scalar(arg2)) => {
+            let pat = fold_str(arg2);
+            let pattern_type = check_pattern_type(pat.as_bytes(), false);
+            let str = fold_str(arg1);
+            Value::Scalar(string_like(str.as_bytes(), pat.as_bytes(), &pattern_type))
+        }
+        (ValueRef::Column(arg1), ValueRef::Scalar(arg2)) => {
+            let pat = fold_str(arg2);
+            let pattern_type = check_pattern_type(pat.as_bytes(), false);
+            let mut str = String::new();
+            // faster path for memmem to have a single instance of Finder
+            if pattern_type == PatternType::SurroundByPercent && pat.len() > 2 {
+                let finder = memmem::Finder::new(&pat[1..pat.len() - 1]);
+                let it = StringType::iter_column(&arg1).map(|arg1| {
+                    fold_str_into(arg1, &mut str);
+                    finder.find(str.as_bytes()).is_some()
+                });
+                return Value::Column(BooleanType::column_from_iter(it, &[]));
+            }
+
+            let it = StringType::iter_column(&arg1).map(|arg1| {
+                fold_str_into(arg1, &mut str);
+                string_like(str.as_bytes(), pat.as_bytes(), &pattern_type)
+            });
+            Value::Column(BooleanType::column_from_iter(it, &[]))
+        }
+        (ValueRef::Scalar(arg1), ValueRef::Column(arg2)) => {
+            let str = fold_str(arg1);
+            let mut pat = String::new();
+            let it = StringType::iter_column(&arg2).map(|arg2| {
+                fold_str_into(arg2, &mut pat);
+                let pattern_type = check_pattern_type(pat.as_bytes(), false);
+                string_like(str.as_bytes(), pat.as_bytes(), &pattern_type)
+            });
+            Value::Column(BooleanType::column_from_iter(it, &[]))
+        }
+        (ValueRef::Column(arg1), ValueRef::Column(arg2)) => {
+            let mut str = String::new();
+            let mut pat = String::new();
+            let it = StringType::iter_column(&arg1)
+                .zip(StringType::iter_column(&arg2))
+                .map(|(arg1, arg2)| {
+                    fold_str_into(arg1, &mut str);
+                    fold_str_into(arg2, &mut pat);
+                    let pattern_type = check_pattern_type(pat.as_bytes(), false);
+                    string_like(str.as_bytes(), pat.as_bytes(), &pattern_type)
+                });
+            Value::Column(BooleanType::column_from_iter(it, &[]))
+        }
+    }
+}
+
 fn vectorize_like(
     func: impl Fn(&[u8], &[u8], &mut EvalContext, &PatternType) -> bool + Copy,
 ) -> impl Fn(ValueRef<StringType>, ValueRef<StringType>, &mut EvalContext) -> Value<BooleanType> + Copy
@@ -675,13 +886,13 @@ fn variant_vectorize_like(
 
 fn vectorize_regexp(
     func: impl Fn(
-        &str,
-        &str,
-        &mut MutableBitmap,
-        &mut EvalContext,
-        &mut HashMap<String, Regex>,
-        &mut HashMap<Vec<u8>, String>,
-    ) + Copy,
+            &str,
+            &str,
+            &mut MutableBitmap,
+            &mut EvalContext,
+            &mut HashMap<String, Regex>,
+            &mut HashMap<Vec<u8>, String>,
+        ) + Copy,
 ) -> impl Fn(ValueRef<StringType>, ValueRef<StringType>, &mut EvalContext) -> Value<BooleanType> + Copy
 {
     move |arg1, arg2, ctx| {
@@ -826,13 +1037,18 @@ pub fn check_pattern_type(pattern: &[u8], is_pruning: bool) -> PatternType {
     }
 }
 
+/// Returns the first byte and the width of the first UTF-8 character, so `_` matches a
+/// character rather than a byte.
 #[inline]
 fn decode_one(data: &[u8]) -> Option<(u8, usize)> {
-    if data.is_empty() {
-        None
-    } else {
-        Some((data[0], 1))
-    }
+    let c = *data.first()?;
+    let width = match c {
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => 1,
+    };
+    Some((c, width.min(data.len())))
 }
 
 #[inline]
@@ -22,12 +22,14 @@ use databend_common_expression::types::DataType;
 use databend_common_expression::types::GenericType;
 use databend_common_expression::types::NullType;
 use databend_common_expression::types::NullableType;
+use databend_common_expression::vectorize_2_arg;
 use databend_common_expression::Domain;
 use databend_common_expression::Function;
 use databend_common_expression::FunctionDomain;
 use databend_common_expression::FunctionEval;
 use databend_common_expression::FunctionRegistry;
 use databend_common_expression::FunctionSignature;
+use databend_common_expression::ScalarRef;
 use databend_common_expression::Value;
 use databend_common_expression::ValueRef;
 
@@ -130,4 +132,38 @@ pub fn register(registry: &mut FunctionRegistry) {
             ValueRef::Scalar(Some(_)) => Value::Scalar(true),
         },
     );
+
+    // `IS [NOT] DISTINCT FROM` compares NULLs as equal values, the result is never NULL.
+    for (name, not_distinct) in [("is_not_distinct_from", true), ("is_distinct_from", false)] {
+        registry.register_2_arg_core::<NullType, NullType, BooleanType, _, _>(
+            name,
+            move |_, _, _| {
+                FunctionDomain::Domain(BooleanDomain {
+                    has_true: not_distinct,
+                    has_false: !not_distinct,
+                })
+            },
+            move |_, _, _| Value::Scalar(not_distinct),
+        );
+        registry.register_2_arg_core::<
+            NullableType<GenericType<0>>,
+            NullableType<GenericType<0>>,
+            BooleanType,
+            _,
+            _,
+        >(
+            name,
+            |_, _, _| FunctionDomain::Full,
+            vectorize_2_arg::<NullableType<GenericType<0>>, NullableType<GenericType<0>>, BooleanType>(
+                move |lhs, rhs, _| not_distinct_from(lhs, rhs) == not_distinct,
+            ),
+        );
+    }
+}
+
+fn not_distinct_from(lhs: Option<ScalarRef>, rhs: Option<ScalarRef>) -> bool {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => lhs.not_distinct_from(&rhs),
+        (lhs, rhs) => lhs.is_none() && rhs.is_none(),
+    }
 }
@@ -229,9 +229,11 @@ where
     })(value, ctx)
 }
 
+// a negative target scale rounds the integer digits, e.g. floor(1234.5, -2) -> 1200
 fn decimal_floor<T>(
     value: ValueRef<DecimalType<T>>,
     source_scale: i64,
+    target_scale: i64,
     ctx: &mut EvalContext,
 ) -> Value<DecimalType<T>>
 where
@@ -243,21 +245,24 @@ where
         + Sub<Output = T>
         + Mul<Output = T>,
 {
-    let power_of_ten = T::e(source_scale as u32);
+    let divide_power_of_ten = T::e((source_scale - target_scale) as u32);
+    let multiply_power_of_ten = T::e(target_scale.min(0).unsigned_abs() as u32);
 
     vectorize_1_arg::<DecimalType<T>, DecimalType<T>>(|a, _| {
-        if a < T::zero() {
+        let a = if a < T::zero() {
             // below 0 we ceil the number (e.g. -10.5 -> -11)
-            ((a + T::one()) / power_of_ten) - T::one()
+            ((a + T::one()) / divide_power_of_ten) - T::one()
         } else {
-            a / power_of_ten
-        }
+            a / divide_power_of_ten
+        };
+        a * multiply_power_of_ten
     })(value, ctx)
 }
 
 fn decimal_ceil<T>(
     value: ValueRef<DecimalType<T>>,
     source_scale: i64,
+    target_scale: i64,
     ctx: &mut EvalContext,
 ) -> Value<DecimalType<T>>
 where
@@ -269,14 +274,16 @@ where
         + Sub<Output = T>
         + Mul<Output = T>,
 {
-    let power_of_ten = T::e(source_scale as u32);
+    let divide_power_of_ten = T::e((source_scale - target_scale) as u32);
+    let multiply_power_of_ten = T::e(target_scale.min(0).unsigned_abs() as u32);
 
     vectorize_1_arg::<DecimalType<T>, DecimalType<T>>(|a, _| {
-        if a <= T::zero() {
-            a / power_of_ten
+        let a = if a <= T::zero() {
+            a / divide_power_of_ten
         } else {
-            ((a - T::one()) / power_of_ten) + T::one()
-        }
+            ((a - T::one()) / divide_power_of_ten) + T::one()
+        };
+        a * multiply_power_of_ten
     })(value, ctx)
 }
 
@@ -314,8 +321,8 @@ fn decimal_rounds(
                 (false, RoundMode::Truncate) => {
                     decimal_truncate_negative::<_>(value, source_scale, target_scale, ctx)
                 }
-                (_, RoundMode::Floor) => decimal_floor::<_>(value, source_scale, ctx),
-                (_, RoundMode::Ceil) => decimal_ceil::<_>(value, source_scale, ctx),
+                (_, RoundMode::Floor) => decimal_floor::<_>(value, source_scale, target_scale, ctx),
+                (_, RoundMode::Ceil) => decimal_ceil::<_>(value, source_scale, target_scale, ctx),
             };
 
             result.upcast_decimal(dest_type.size())
@@ -204,6 +204,12 @@ pub fn register(registry: &mut FunctionRegistry) {
         })
     }
 
+    registry.register_2_arg::<NumberType<F64>, NumberType<i64>, NumberType<F64>, _, _>(
+        "ceil",
+        |_, _, _| FunctionDomain::Full,
+        |val, to, _| round_to_scale(val.0, to, f64::ceil),
+    );
+
     registry.register_aliases("ceil", &["ceiling"]);
 
     registry.register_1_arg::<StringType, NumberType<u32>, _, _>(
@@ -242,6 +248,12 @@ pub fn register(registry: &mut FunctionRegistry) {
         |val, _| val.floor(),
     );
 
+    registry.register_2_arg::<NumberType<F64>, NumberType<i64>, NumberType<F64>, _, _>(
+        "floor",
+        |_, _, _| FunctionDomain::Full,
+        |val, to, _| round_to_scale(val.0, to, f64::floor),
+    );
+
     registry.register_2_arg::<NumberType<F64>, NumberType<F64>, NumberType<F64>, _, _>(
         "pow",
         |_, _, _| FunctionDomain::Full,
@@ -265,17 +277,7 @@ pub fn register(registry: &mut FunctionRegistry) {
                     .register_2_arg::<NumberType<NUM_TYPE>, NumberType<i64>, NumberType<F64>, _, _>(
                         "round",
                         |_, _, _| FunctionDomain::Full,
-                        |val, to, _| match to.cmp(&0) {
-                            Ordering::Greater => {
-                                let z = 10_f64.powi(if to > 30 { 30 } else { to as i32 });
-                                (F64::from(AsPrimitive::<f64>::as_(val)) * z).round() / z
-                            }
-                            Ordering::Less => {
-                                let z = 10_f64.powi(if to < -30 { 30 } else { -to as i32 });
-                                (F64::from(AsPrimitive::<f64>::as_(val)) / z).round() * z
-                            }
-                            Ordering::Equal => (F64::from(AsPrimitive::<f64>::as_(val))).round(),
-                        },
+                        |val, to, _| round_to_scale(AsPrimitive::<f64>::as_(val), to, f64::round),
                     );
             }
         });
@@ -296,17 +298,7 @@ pub fn register(registry: &mut FunctionRegistry) {
                     .register_2_arg::<NumberType<NUM_TYPE>, NumberType<i64>, NumberType<F64>, _, _>(
                         "truncate",
                         |_, _, _| FunctionDomain::Full,
-                        |val, to, _| match to.cmp(&0) {
-                            Ordering::Greater => {
-                                let z = 10_f64.powi(if to > 30 { 30 } else { to as i32 });
-                                (F64::from(AsPrimitive::<f64>::as_(val)) * z).trunc() / z
-                            }
-                            Ordering::Less => {
-                                let z = 10_f64.powi(if to < -30 { 30 } else { -to as i32 });
-                                (F64::from(AsPrimitive::<f64>::as_(val)) / z).trunc() * z
-                            }
-                            Ordering::Equal => (F64::from(AsPrimitive::<f64>::as_(val))).trunc(),
-                        },
+                        |val, to, _| round_to_scale(AsPrimitive::<f64>::as_(val), to, f64::trunc),
                     );
             }
         });
@@ -464,6 +456,26 @@ type LogFunction = GenericLogFunction<EBase>;
 type Log10Function = GenericLogFunction<TenBase>;
 type Log2Function = GenericLogFunction<TwoBase>;
 
+/// Rounds `val` to `to` decimal places with `round`, a negative `to` rounds to tens, hundreds
+/// and so on. `to` is clamped to the range of the finite powers of ten.
+fn round_to_scale(val: f64, to: i64, round: fn(f64) -> f64) -> F64 {
+    let to = to.clamp(-308, 308) as i32;
+    let res = match to.cmp(&0) {
+        Ordering::Greater => {
+            let z = 10_f64.powi(to);
+            let res = round(val * z) / z;
+            // `val * z` overflows only if `val` has no digits to round at this scale.
+            if res.is_finite() { res } else { val }
+        }
+        Ordering::Less => {
+            let z = 10_f64.powi(-to);
+            round(val / z) * z
+        }
+        Ordering::Equal => round(val),
+    };
+    F64::from(res)
+}
+
 fn factorial(n: i64) -> i64 {
     if n <= 0 { 1 } else { n * factorial(n - 1) }
 }
@@ -16,6 +16,7 @@ use databend_common_expression::FunctionRegistry;
 
 mod arithmetic;
 mod arithmetic_modulo;
+mod arithmetic_overflow;
 mod array;
 mod binary;
 mod bitmap;
@@ -37,9 +38,16 @@ mod tuple;
 mod variant;
 mod vector;
 
+pub use arithmetic_overflow::vectorize_arithmetic_op_with_mode;
+pub use arithmetic_overflow::ArithmeticOp;
+pub use arithmetic_overflow::OverflowArithmetic;
+pub use arithmetic_overflow::OverflowMode;
 pub use comparison::check_pattern_type;
 pub use comparison::is_like_pattern_escape;
+pub use comparison::simple_fold;
+pub use comparison::string_eq;
 pub use comparison::PatternType;
+pub use comparison::StringCmpOptions;
 pub use comparison::ALL_COMP_FUNC_NAMES;
 
 pub fn register(registry: &mut FunctionRegistry) {
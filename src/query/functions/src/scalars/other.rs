@@ -37,6 +37,7 @@ use databend_common_expression::types::NullType;
 use databend_common_expression::types::NullableType;
 use databend_common_expression::types::NumberColumn;
 use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::NumberDomain;
 use databend_common_expression::types::NumberScalar;
 use databend_common_expression::types::NumberType;
 use databend_common_expression::types::SimpleDomain;
@@ -130,22 +131,7 @@ pub fn register(registry: &mut FunctionRegistry) {
         },
     );
 
-    registry.register_0_arg_core::<NumberType<F64>, _, _>(
-        "rand",
-        |_| {
-            FunctionDomain::Domain(SimpleDomain {
-                min: OrderedFloat(0.0),
-                max: OrderedFloat(1.0),
-            })
-        },
-        |ctx| {
-            let mut rng = rand::rngs::SmallRng::from_entropy();
-            let rand_nums = (0..ctx.num_rows)
-                .map(|_| rng.gen::<F64>())
-                .collect::<Vec<_>>();
-            Value::Column(rand_nums.into())
-        },
-    );
+    register_rand(registry);
 
     registry.register_1_arg::<NumberType<u64>, NumberType<F64>, _, _>(
         "rand",
@@ -342,6 +328,56 @@ fn register_run_diff(registry: &mut FunctionRegistry) {
     );
 }
 
+/// `rand()` takes the id of its call site as parameter when `query_random_seed` is set, see
+/// `TypeChecker::resolve_function`.
+fn register_rand(registry: &mut FunctionRegistry) {
+    registry.register_function_factory("rand", |params, args_type| {
+        if !args_type.is_empty() {
+            return None;
+        }
+        let call_site = match params {
+            [] => 0,
+            [Scalar::Number(NumberScalar::UInt64(call_site))] => *call_site,
+            _ => return None,
+        };
+
+        Some(Arc::new(Function {
+            signature: FunctionSignature {
+                name: "rand".to_string(),
+                args_type: vec![],
+                return_type: DataType::Number(NumberDataType::Float64),
+            },
+            eval: FunctionEval::Scalar {
+                calc_domain: Box::new(|_, _| {
+                    FunctionDomain::Domain(Domain::Number(NumberDomain::Float64(SimpleDomain {
+                        min: OrderedFloat(0.0),
+                        max: OrderedFloat(1.0),
+                    })))
+                }),
+                eval: Box::new(move |_, ctx| {
+                    let rand_nums = match ctx.func_ctx.random_seed {
+                        Some(seed) => {
+                            // The values only depend on the seed, the call site, the position
+                            // of the block and the row.
+                            let stream = splitmix64(splitmix64(seed, call_site), ctx.random_key);
+                            (0..ctx.num_rows as u64)
+                                .map(|row| F64::from(to_unit_f64(splitmix64(stream, row))))
+                                .collect::<Vec<_>>()
+                        }
+                        None => {
+                            let mut rng = rand::rngs::SmallRng::from_entropy();
+                            (0..ctx.num_rows)
+                                .map(|_| rng.gen::<F64>())
+                                .collect::<Vec<_>>()
+                        }
+                    };
+                    Value::Column(Column::Number(NumberColumn::Float64(rand_nums.into())))
+                }),
+            },
+        }))
+    });
+}
+
 fn register_grouping(registry: &mut FunctionRegistry) {
     registry.register_function_factory("grouping", |params, arg_type| {
         if arg_type.len() != 1 {
@@ -388,3 +424,18 @@ pub fn compute_grouping(cols: &[usize], grouping_id: u32) -> u32 {
     }
     grouping
 }
+
+/// Returns the `n`-th output of SplitMix64 seeded with `seed`.
+///
+/// SplitMix64 is counter based, each value only depends on `seed` and `n`.
+fn splitmix64(seed: u64, n: u64) -> u64 {
+    let mut z = seed.wrapping_add(n.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Maps `z` to `[0, 1)`, the upper 53 bits fill the mantissa of f64.
+fn to_unit_f64(z: u64) -> f64 {
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -14,7 +14,11 @@
 
 use std::io::Write;
 
+use bumpalo::Bump;
+use databend_common_exception::Result;
 use databend_common_expression::types::decimal::Decimal128Type;
+use databend_common_expression::types::number::Float32Type;
+use databend_common_expression::types::number::Float64Type;
 use databend_common_expression::types::number::Int64Type;
 use databend_common_expression::types::number::UInt64Type;
 use databend_common_expression::types::BitmapType;
@@ -23,8 +27,13 @@ use databend_common_expression::types::DecimalSize;
 use databend_common_expression::types::StringType;
 use databend_common_expression::types::TimestampType;
 use databend_common_expression::Column;
+use databend_common_expression::ColumnBuilder;
 use databend_common_expression::FromData;
+use databend_common_expression::Scalar;
+use databend_common_expression::StateAddr;
+use databend_common_functions::aggregates::column_sum_kahan;
 use databend_common_functions::aggregates::eval_aggr;
+use databend_common_functions::aggregates::AggregateFunctionFactory;
 use goldenfile::Mint;
 use itertools::Itertools;
 use roaring::RoaringTreemap;
@@ -108,6 +117,80 @@ fn test_agg_group_by() {
     test_agg_group_array_moving_sum(file, eval_aggr);
 }
 
+#[test]
+fn test_float_sum_compensation() -> Result<()> {
+    // The large value goes first, so the naive sum rounds each 0.1 up to the ulp of it.
+    let mut values = vec![1e15f64];
+    values.extend(std::iter::repeat(0.1).take(10_000_000));
+    let expected = 1e15 + 1e6;
+    let naive = values.iter().sum::<f64>();
+    assert!((naive - expected).abs() > 1e5);
+
+    let column = Float64Type::from_data(values.clone());
+    assert!((as_f64(&column_sum_kahan(&column)?) - expected).abs() < 1.0);
+    assert!((merge_two_partial_states("sum", &column)? - expected).abs() < 1.0);
+
+    let count = values.len() as f64;
+    let avg = merge_two_partial_states("avg", &column)?;
+    assert!((avg - expected / count).abs() < 1.0 / count);
+
+    let column = Float32Type::from_data(values.iter().map(|v| *v as f32).collect::<Vec<_>>());
+    let expected = (1e15f32 as f64) + 10_000_000.0 * (0.1f32 as f64);
+    assert!((as_f64(&column_sum_kahan(&column)?) - expected).abs() < 1.0);
+    assert!((merge_two_partial_states("sum", &column)? - expected).abs() < 1.0);
+
+    // The states written without the compensation are still readable.
+    let factory = AggregateFunctionFactory::instance();
+    let arena = Bump::new();
+    for (name, state, result) in [
+        ("sum", 2.5f64.to_le_bytes().to_vec(), 2.5),
+        (
+            "avg",
+            [5.0f64.to_le_bytes(), 2u64.to_le_bytes()].concat(),
+            2.5,
+        ),
+    ] {
+        let func = factory.get(name, vec![], vec![column.data_type()])?;
+        let place: StateAddr = arena.alloc_layout(func.state_layout()).into();
+        func.init_state(place);
+        func.merge(place, &mut state.as_slice())?;
+        let mut builder = ColumnBuilder::with_capacity(&func.return_type()?, 1);
+        func.merge_result(place, &mut builder)?;
+        assert_eq!(as_f64(&builder.build_scalar()), result);
+    }
+
+    Ok(())
+}
+
+// Aggregates the halves of the column into two states, then merges their serialized states
+// as the final aggregation of two nodes does.
+fn merge_two_partial_states(name: &str, column: &Column) -> Result<f64> {
+    let factory = AggregateFunctionFactory::instance();
+    let func = factory.get(name, vec![], vec![column.data_type()])?;
+    let arena = Bump::new();
+
+    let place: StateAddr = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    let mid = column.len() / 2;
+    for part in [column.slice(0..mid), column.slice(mid..column.len())] {
+        let partial: StateAddr = arena.alloc_layout(func.state_layout()).into();
+        func.init_state(partial);
+        func.accumulate(partial, &[part.clone()], None, part.len())?;
+
+        let mut state = vec![];
+        func.serialize(partial, &mut state)?;
+        func.merge(place, &mut state.as_slice())?;
+    }
+
+    let mut builder = ColumnBuilder::with_capacity(&func.return_type()?, 1);
+    func.merge_result(place, &mut builder)?;
+    Ok(as_f64(&builder.build_scalar()))
+}
+
+fn as_f64(scalar: &Scalar) -> f64 {
+    scalar.as_number().unwrap().as_float64().unwrap().0
+}
+
 fn gen_bitmap_data() -> Column {
     // construct bitmap column with 4 row:
     // 0..5, 1..6, 2..7, 3..8
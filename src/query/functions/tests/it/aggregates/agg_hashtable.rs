@@ -26,7 +26,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ops::Range;
+
 use databend_common_expression::block_debug::assert_block_value_sort_eq;
+use databend_common_expression::types::binary::BinaryColumnBuilder;
 use databend_common_expression::types::ArgType;
 use databend_common_expression::types::BooleanType;
 use databend_common_expression::types::Float32Type;
@@ -35,10 +38,12 @@ use databend_common_expression::types::Int16Type;
 use databend_common_expression::types::Int32Type;
 use databend_common_expression::types::Int64Type;
 use databend_common_expression::types::Int8Type;
+use databend_common_expression::types::NumberScalar;
 use databend_common_expression::types::StringType;
 use databend_common_expression::types::UInt64Type;
 use databend_common_expression::types::F32;
 use databend_common_expression::types::F64;
+use databend_common_expression::AggregateFunctionRef;
 use databend_common_expression::AggregateHashTable;
 use databend_common_expression::Column;
 use databend_common_expression::DataBlock;
@@ -46,6 +51,7 @@ use databend_common_expression::FromData;
 use databend_common_expression::HashTableConfig;
 use databend_common_expression::PayloadFlushState;
 use databend_common_expression::ProbeState;
+use databend_common_expression::ScalarRef;
 use databend_common_functions::aggregates::AggregateFunctionFactory;
 use itertools::Itertools;
 
@@ -150,3 +156,120 @@ fn test_agg_hashtable() {
         assert_block_value_sort_eq(&block, &block_expected);
     }
 }
+
+// Aggregates the rows of each node, then merges the serialized states of the nodes into
+// the final hashtable, as the distributed aggregation does.
+fn aggregate_on_nodes(
+    group_columns: &[Column],
+    params: &[Vec<Column>],
+    aggrs: &[AggregateFunctionRef],
+    nodes: &[Range<usize>],
+) -> DataBlock {
+    let group_types: Vec<_> = group_columns.iter().map(|c| c.data_type()).collect();
+    let config = HashTableConfig::default();
+    let mut final_table =
+        AggregateHashTable::new(group_types.clone(), aggrs.to_vec(), config.clone());
+
+    for range in nodes {
+        let node_groups = group_columns
+            .iter()
+            .map(|c| c.slice(range.clone()))
+            .collect::<Vec<_>>();
+        let node_params = params
+            .iter()
+            .map(|p| p.iter().map(|c| c.slice(range.clone())).collect())
+            .collect::<Vec<Vec<_>>>();
+        let mut table =
+            AggregateHashTable::new(group_types.clone(), aggrs.to_vec(), config.clone());
+        table
+            .add_groups(
+                &mut ProbeState::default(),
+                &node_groups,
+                &node_params,
+                &[],
+                range.len(),
+            )
+            .unwrap();
+
+        let mut flush_state = PayloadFlushState::default();
+        while table.payload.flush(&mut flush_state) {
+            let row_count = flush_state.row_count;
+            let places = &flush_state.state_places[..row_count];
+            let states = aggrs
+                .iter()
+                .zip(table.payload.state_addr_offsets.iter())
+                .map(|(aggr, offset)| {
+                    let mut builder = BinaryColumnBuilder::with_capacity(row_count, 0);
+                    aggr.batch_serialize(places, *offset, &mut builder).unwrap();
+                    Column::Binary(builder.build())
+                })
+                .collect::<Vec<_>>();
+            let groups = flush_state.take_group_columns();
+            final_table
+                .add_groups(&mut ProbeState::default(), &groups, &[], &states, row_count)
+                .unwrap();
+        }
+    }
+
+    let mut merge_state = PayloadFlushState::default();
+    let mut blocks = Vec::new();
+    while final_table.merge_result(&mut merge_state).unwrap() {
+        let mut columns = merge_state.take_group_columns();
+        columns.extend_from_slice(&merge_state.take_aggregate_results());
+        blocks.push(DataBlock::new_from_columns(columns));
+    }
+    DataBlock::concat(&blocks).unwrap()
+}
+
+#[test]
+fn test_agg_hashtable_merge_serialized_states() {
+    let factory = AggregateFunctionFactory::instance();
+    let n = 10_000;
+
+    // Most of the rows are in group 0, and the values of group 3 are all NULL.
+    let keys = (0..n)
+        .map(|x| if x % 10 < 8 { 0 } else { (x % 7) as i64 + 1 })
+        .collect_vec();
+    let values = keys
+        .iter()
+        .enumerate()
+        .map(|(x, key)| (*key != 3 && x % 13 != 0).then_some(x as i64))
+        .collect_vec();
+    let group_columns = vec![Int64Type::from_data(keys)];
+    let value_column = Int64Type::from_opt_data(values);
+
+    let value_type = value_column.data_type();
+    let aggrs = ["min", "max", "sum", "count", "avg"]
+        .iter()
+        .map(|name| factory.get(name, vec![], vec![value_type.clone()]).unwrap())
+        .collect_vec();
+    let params = aggrs
+        .iter()
+        .map(|_| vec![value_column.clone()])
+        .collect_vec();
+
+    let single_node = aggregate_on_nodes(&group_columns, &params, &aggrs, &[0..n]);
+    assert_eq!(single_node.num_rows(), 8);
+    for nodes in [vec![0..n / 5, n / 5..n], vec![0..1, 1..n], vec![
+        0..n / 3,
+        n / 3..n / 2,
+        n / 2..n,
+    ]] {
+        let merged = aggregate_on_nodes(&group_columns, &params, &aggrs, &nodes);
+        assert_block_value_sort_eq(&single_node, &merged);
+    }
+
+    // The aggregates of the NULL-only group are NULL, except the count.
+    let keys = single_node.get_by_offset(0).value.as_column().unwrap();
+    let row = (0..single_node.num_rows())
+        .find(|row| keys.index(*row) == Some(ScalarRef::Number(NumberScalar::Int64(3))))
+        .unwrap();
+    for (i, name) in ["min", "max", "sum", "count", "avg"].iter().enumerate() {
+        let result = single_node.get_by_offset(i + 1).value.index(row).unwrap();
+        if *name == "count" {
+            assert_eq!(result, ScalarRef::Number(NumberScalar::UInt64(0)));
+        } else {
+            assert_eq!(result, ScalarRef::Null, "{name}");
+        }
+    }
+}
@@ -13,15 +13,41 @@
 // limitations under the License.
 
 use std::io::Write;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 
+use databend_common_arrow::arrow::bitmap::Bitmap;
+use databend_common_arrow::arrow::buffer::Buffer;
+use databend_common_expression::passthrough_nullable_2_arg;
+use databend_common_expression::type_check;
 use databend_common_expression::types::decimal::DecimalColumn;
 use databend_common_expression::types::decimal::DecimalSize;
+use databend_common_expression::types::nullable::NullableColumn;
 use databend_common_expression::types::number::*;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::NullableType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::BlockEntry;
 use databend_common_expression::Column;
+use databend_common_expression::ConstantFolder;
+use databend_common_expression::DataBlock;
+use databend_common_expression::EvalContext;
+use databend_common_expression::Evaluator;
+use databend_common_expression::Expr;
 use databend_common_expression::FromData;
+use databend_common_expression::FunctionContext;
+use databend_common_expression::Scalar;
+use databend_common_expression::Value;
+use databend_common_expression::ValueRef;
+use databend_common_functions::scalars::vectorize_arithmetic_op_with_mode;
+use databend_common_functions::scalars::ArithmeticOp;
+use databend_common_functions::scalars::OverflowArithmetic;
+use databend_common_functions::scalars::OverflowMode;
+use databend_common_functions::BUILTIN_FUNCTIONS;
 use ethnum::i256;
 use goldenfile::Mint;
 
+use super::parser;
 use super::run_ast;
 
 #[test]
@@ -271,3 +297,373 @@ fn test_bitwise_shift_right(file: &mut impl Write, columns: &[(&str, Column)]) {
     run_ast(file, "a2 >> 2", columns);
     run_ast(file, "c >> 2", columns);
 }
+
+#[test]
+fn test_overflow_mode_boundaries() {
+    use ArithmeticOp::*;
+    use OverflowMode::*;
+
+    fn check<T: OverflowArithmetic>(op: ArithmeticOp, lhs: T, rhs: T, expected: [Option<T>; 3]) {
+        for (mode, expected) in [Wrapping, Saturating, Checked].into_iter().zip(expected) {
+            assert_eq!(
+                T::arithmetic_op_with_mode(op, mode, lhs, rhs),
+                expected,
+                "{op:?} {mode:?} {lhs:?} {rhs:?}"
+            );
+        }
+    }
+
+    check(Plus, i8::MAX, 1, [Some(i8::MIN), Some(i8::MAX), None]);
+    check(Minus, i8::MIN, 1, [Some(i8::MAX), Some(i8::MIN), None]);
+    check(Multiply, i8::MIN, -1, [Some(i8::MIN), Some(i8::MAX), None]);
+    check(Plus, i8::MAX, -1, [Some(126); 3]);
+
+    check(Plus, u8::MAX, 1, [Some(0), Some(u8::MAX), None]);
+    check(Minus, 0u8, 1, [Some(u8::MAX), Some(0), None]);
+    check(Multiply, 16u8, 16, [Some(0), Some(u8::MAX), None]);
+
+    check(Plus, i64::MAX, 1, [Some(i64::MIN), Some(i64::MAX), None]);
+    check(Minus, i64::MIN, 1, [Some(i64::MAX), Some(i64::MIN), None]);
+    check(Minus, i64::MAX, i64::MIN, [Some(-1), Some(i64::MAX), None]);
+
+    check(Plus, u64::MAX, 1, [Some(0), Some(u64::MAX), None]);
+    check(Minus, 0u64, u64::MAX, [Some(1), Some(0), None]);
+    check(Multiply, u64::MAX, 2, [
+        Some(u64::MAX - 1),
+        Some(u64::MAX),
+        None,
+    ]);
+
+    // The floats ignore the mode.
+    check(
+        Plus,
+        F64::from(f64::MAX),
+        F64::from(f64::MAX),
+        [Some(F64::from(f64::INFINITY)); 3],
+    );
+}
+
+#[test]
+fn test_overflow_mode_scalar_and_column() {
+    let func_ctx = FunctionContext::default();
+    let new_ctx = |num_rows| EvalContext {
+        generics: &[],
+        num_rows,
+        func_ctx: &func_ctx,
+        validity: None,
+        errors: None,
+        random_key: 0,
+    };
+
+    // Both arguments are scalars.
+    for (mode, expected) in [
+        (OverflowMode::Wrapping, Some(i8::MIN)),
+        (OverflowMode::Saturating, Some(i8::MAX)),
+        (OverflowMode::Checked, None),
+    ] {
+        let mut ctx = new_ctx(1);
+        let func = vectorize_arithmetic_op_with_mode::<i8>(ArithmeticOp::Plus, mode);
+        let result = func(ValueRef::Scalar(i8::MAX), ValueRef::Scalar(1), &mut ctx);
+        match expected {
+            Some(expected) => {
+                assert!(ctx.errors.is_none());
+                assert_eq!(result.as_scalar(), Some(&expected));
+            }
+            None => assert_eq!(ctx.errors.unwrap().1, "number overflowed"),
+        }
+    }
+
+    // A column and a scalar.
+    let column: Buffer<u8> = vec![0u8, 100, 250].into();
+    for (mode, expected) in [
+        (OverflowMode::Wrapping, Some(vec![10u8, 110, 4])),
+        (OverflowMode::Saturating, Some(vec![10u8, 110, 255])),
+        (OverflowMode::Checked, None),
+    ] {
+        let mut ctx = new_ctx(3);
+        let func = vectorize_arithmetic_op_with_mode::<u8>(ArithmeticOp::Plus, mode);
+        let result = func(
+            ValueRef::Column(column.clone()),
+            ValueRef::Scalar(10),
+            &mut ctx,
+        );
+        match expected {
+            Some(expected) => {
+                assert!(ctx.errors.is_none());
+                assert_eq!(result.as_column().unwrap().as_slice(), expected);
+            }
+            None => {
+                let (rows, message) = ctx.errors.unwrap();
+                assert_eq!(rows.iter().collect::<Vec<_>>(), vec![true, true, false]);
+                assert_eq!(message, "number overflowed");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_saturating_constant_folding() {
+    let cases = [
+        (
+            "plus_saturating(127::Int8, 1::Int8)",
+            Scalar::Number(NumberScalar::Int8(i8::MAX)),
+        ),
+        (
+            "minus_saturating((-128)::Int8, 1::Int8)",
+            Scalar::Number(NumberScalar::Int8(i8::MIN)),
+        ),
+        (
+            "plus_saturating(255::UInt8, 1::UInt8)",
+            Scalar::Number(NumberScalar::UInt8(u8::MAX)),
+        ),
+        (
+            "minus_saturating(0::UInt64, 1::UInt64)",
+            Scalar::Number(NumberScalar::UInt64(0)),
+        ),
+        (
+            "minus_saturating(1.5::Float64, 2.0::Float64)",
+            Scalar::Number(NumberScalar::Float64(F64::from(-0.5))),
+        ),
+    ];
+
+    for (text, expected) in cases {
+        let raw_expr = parser::parse_raw_expr(text, &[]);
+        let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).unwrap();
+        let (folded, _) =
+            ConstantFolder::fold(&expr, &FunctionContext::default(), &BUILTIN_FUNCTIONS);
+        match folded {
+            Expr::Constant { scalar, .. } => assert_eq!(scalar, expected, "{text}"),
+            _ => panic!("{text} is not folded: {folded}"),
+        }
+    }
+}
+
+#[test]
+fn test_constant_sub_expressions_stay_scalar() {
+    fn walk(expr: &Expr, evaluator: &Evaluator, text: &str) {
+        if expr.column_refs().is_empty() {
+            let result = evaluator.run(expr).unwrap();
+            assert!(
+                matches!(result, Value::Scalar(_)),
+                "{text}: {} is materialized",
+                expr.sql_display()
+            );
+        }
+        match expr {
+            Expr::Cast { expr, .. } => walk(expr, evaluator, text),
+            Expr::FunctionCall { args, .. } => {
+                for arg in args {
+                    walk(arg, evaluator, text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let column = Int8Type::from_data(vec![1i8, 2, 3]);
+    let block = DataBlock::new(
+        vec![BlockEntry::new(
+            column.data_type(),
+            Value::Column(column.clone()),
+        )],
+        column.len(),
+    );
+    let func_ctx = FunctionContext::default();
+    let evaluator = Evaluator::new(&block, &func_ctx, &BUILTIN_FUNCTIONS);
+
+    // The expressions are evaluated without constant folding.
+    for text in [
+        "-1",
+        "-(-1)",
+        "-1 + a",
+        "-(1::Int32) * a",
+        "a - -2.5",
+        "CAST(-1 AS Int64) + a",
+        "CAST(-1 AS String)",
+        "TRY_CAST(-1 AS UInt8)",
+        "-CAST('1' AS Int32) + a",
+    ] {
+        let raw_expr = parser::parse_raw_expr(text, &[("a", column.data_type())]);
+        let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).unwrap();
+        walk(&expr, &evaluator, text);
+    }
+}
+
+#[test]
+fn test_all_null_arguments_skip_the_kernel() {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    let kernel =
+        vectorize_arithmetic_op_with_mode::<i32>(ArithmeticOp::Plus, OverflowMode::Checked);
+    let func =
+        passthrough_nullable_2_arg::<Int32Type, Int32Type, Int32Type>(move |lhs, rhs, ctx| {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+            kernel(lhs, rhs, ctx)
+        });
+
+    let func_ctx = FunctionContext::default();
+    let nullable = |values: Vec<i32>, validity: Vec<bool>| NullableColumn::<Int32Type> {
+        column: values.into(),
+        validity: Bitmap::from(validity.as_slice()),
+    };
+    let run = |lhs: &NullableColumn<Int32Type>, rhs: ValueRef<NullableType<Int32Type>>| {
+        let mut ctx = EvalContext {
+            generics: &[],
+            num_rows: lhs.len(),
+            func_ctx: &func_ctx,
+            validity: None,
+            errors: None,
+            random_key: 0,
+        };
+        let before = CALLS.load(Ordering::Relaxed);
+        let result = func(ValueRef::Column(lhs.clone()), rhs, &mut ctx);
+        assert!(ctx.errors.is_none());
+        (result, CALLS.load(Ordering::Relaxed) - before)
+    };
+
+    // All the rows are NULL, the values behind the NULLs would overflow.
+    let all_null = nullable(vec![i32::MAX; 3], vec![false; 3]);
+    let (result, calls) = run(&all_null, ValueRef::Column(all_null.clone()));
+    assert_eq!(calls, 0);
+    let result = result.into_column().unwrap();
+    assert_eq!(result.len(), 3);
+    assert_eq!(result.validity.unset_bits(), 3);
+
+    let (result, calls) = run(&all_null, ValueRef::Scalar(Some(1)));
+    assert_eq!(calls, 0);
+    assert_eq!(result.into_column().unwrap().validity.unset_bits(), 3);
+
+    // The validity of the arguments is combined.
+    let lhs = nullable(vec![1, 2, 3], vec![true, false, true]);
+    let rhs = nullable(vec![1, 2, 3], vec![false, true, false]);
+    let (result, calls) = run(&lhs, ValueRef::Column(rhs));
+    assert_eq!(calls, 0);
+    assert_eq!(result.into_column().unwrap().validity.unset_bits(), 3);
+
+    // Empty columns.
+    let empty = nullable(vec![], vec![]);
+    let (result, calls) = run(&empty, ValueRef::Column(empty.clone()));
+    assert_eq!(calls, 0);
+    assert_eq!(result.into_column().unwrap().len(), 0);
+
+    // Some of the rows are not NULL.
+    let rhs = nullable(vec![10, 20, 30], vec![true, true, false]);
+    let (result, calls) = run(&lhs, ValueRef::Column(rhs));
+    assert_eq!(calls, 1);
+    let result = result.into_column().unwrap();
+    assert_eq!(result.iter().collect::<Vec<_>>(), vec![
+        Some(11),
+        None,
+        None
+    ]);
+
+    // The scalars are not materialized.
+    let mut ctx = EvalContext {
+        generics: &[],
+        num_rows: 3,
+        func_ctx: &func_ctx,
+        validity: None,
+        errors: None,
+        random_key: 0,
+    };
+    let result = func(
+        ValueRef::Scalar(Some(1)),
+        ValueRef::Scalar(Some(2)),
+        &mut ctx,
+    );
+    assert_eq!(result.into_scalar().unwrap(), Some(3));
+    let result = func(ValueRef::Scalar(None), ValueRef::Scalar(Some(2)), &mut ctx);
+    assert_eq!(result.into_scalar().unwrap(), None);
+}
+
+#[test]
+fn test_result_nullability() {
+    let int = DataType::Number(NumberDataType::Int32);
+    let columns = [("a", int.clone()), ("n", int.wrap_nullable())];
+    let ops = [
+        "+", "-", "*", "/", "div", "%", "=", "!=", "<", "<=", ">", ">=",
+    ];
+
+    // The result is nullable only if any argument is. Dividing by zero is an error instead of
+    // NULL, so `/` and `div` are no exception.
+    for op in ops {
+        for (lhs, rhs) in [("a", "a"), ("a", "n"), ("n", "a"), ("n", "n")] {
+            let text = format!("{lhs} {op} {rhs}");
+            let raw_expr = parser::parse_raw_expr(&text, &columns);
+            let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).unwrap();
+            assert_eq!(
+                expr.data_type().is_nullable(),
+                lhs == "n" || rhs == "n",
+                "{text}: {}",
+                expr.data_type()
+            );
+        }
+    }
+
+    let block = DataBlock::new(
+        vec![
+            BlockEntry::new(
+                int.clone(),
+                Value::Column(Int32Type::from_data(vec![1, 0, 3])),
+            ),
+            BlockEntry::new(
+                int.wrap_nullable(),
+                Value::Column(Int32Type::from_data_with_validity(vec![1, 2, 0], vec![
+                    true, true, false,
+                ])),
+            ),
+        ],
+        3,
+    );
+    let func_ctx = FunctionContext::default();
+    let evaluator = Evaluator::new(&block, &func_ctx, &BUILTIN_FUNCTIONS);
+    let run = |text: &str| {
+        let raw_expr = parser::parse_raw_expr(text, &columns);
+        let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).unwrap();
+        evaluator.run(&expr)
+    };
+
+    // The divisor of the NULL row is not checked.
+    assert!(run("a / n").is_ok());
+    assert!(run("n / a").is_err());
+}
+
+#[test]
+fn test_loose_to_float64() {
+    let columns = [("s", DataType::String.wrap_nullable())];
+    let block = DataBlock::new(
+        vec![BlockEntry::new(
+            DataType::String.wrap_nullable(),
+            Value::Column(StringType::from_data_with_validity(
+                vec!["3", " 2.5 ", "abc", "", "garbage", "-1e2"],
+                vec![true, true, true, true, false, true],
+            )),
+        )],
+        6,
+    );
+    let func_ctx = FunctionContext::default();
+    let evaluator = Evaluator::new(&block, &func_ctx, &BUILTIN_FUNCTIONS);
+    let run = |text: &str| {
+        let raw_expr = parser::parse_raw_expr(text, &columns);
+        let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).unwrap();
+        evaluator.run(&expr).unwrap()
+    };
+
+    // The NULL row is not counted.
+    let result = run("loose_to_float64(s)");
+    assert_eq!(
+        result.into_column().unwrap(),
+        Float64Type::from_data_with_validity(vec![3.0, 2.5, 0.0, 0.0, 0.0, -100.0], vec![
+            true, true, false, false, false, true
+        ])
+    );
+    assert_eq!(func_ctx.loose_coercion_failures.load(Ordering::Relaxed), 2);
+
+    let result = run("loose_to_float64('12')");
+    assert_eq!(
+        result,
+        Value::Scalar(Scalar::Number(NumberScalar::Float64(12.0.into())))
+    );
+    let result = run("loose_to_float64('1 2')");
+    assert_eq!(result, Value::Scalar(Scalar::Null));
+    assert_eq!(func_ctx.loose_coercion_failures.load(Ordering::Relaxed), 3);
+}
@@ -16,7 +16,14 @@ use std::io::Write;
 
 use databend_common_expression::types::*;
 use databend_common_expression::Column;
+use databend_common_expression::ColumnBuilder;
+use databend_common_expression::DataBlock;
+use databend_common_expression::Evaluator;
 use databend_common_expression::FromData;
+use databend_common_expression::FunctionContext;
+use databend_common_expression::Scalar;
+use databend_common_expression::Value;
+use databend_common_functions::BUILTIN_FUNCTIONS;
 use goldenfile::Mint;
 use itertools::Itertools;
 use roaring::RoaringTreemap;
@@ -754,6 +761,108 @@ fn test_cast_between_binary_and_string(file: &mut impl Write, is_try: bool) {
     );
 }
 
+#[test]
+fn test_cast_scalar_and_column_parity() {
+    let samples = [
+        (DataType::Number(NumberDataType::Int64), vec![
+            Scalar::Number(NumberScalar::Int64(0)),
+            Scalar::Number(NumberScalar::Int64(-1)),
+            Scalar::Number(NumberScalar::Int64(300)),
+            Scalar::Number(NumberScalar::Int64(i64::MAX)),
+        ]),
+        (DataType::Number(NumberDataType::Float64), vec![
+            Scalar::Number(NumberScalar::Float64(1.5.into())),
+            Scalar::Number(NumberScalar::Float64((-0.0).into())),
+            Scalar::Number(NumberScalar::Float64(1e20.into())),
+            Scalar::Number(NumberScalar::Float64(f64::NAN.into())),
+        ]),
+        (DataType::String, vec![
+            Scalar::String("12".to_string()),
+            Scalar::String("1.5".to_string()),
+            Scalar::String("abc".to_string()),
+            Scalar::String("true".to_string()),
+            Scalar::String("2020-01-01".to_string()),
+            Scalar::String("2020-01-01 12:00:00".to_string()),
+        ]),
+        (DataType::Boolean, vec![Scalar::Boolean(true)]),
+        (DataType::Date, vec![Scalar::Date(18000)]),
+        (DataType::Timestamp, vec![Scalar::Timestamp(
+            1_600_000_000_000_000,
+        )]),
+        (
+            DataType::Number(NumberDataType::Int64).wrap_nullable(),
+            vec![Scalar::Null, Scalar::Number(NumberScalar::Int64(7))],
+        ),
+    ];
+    let dest_types = [
+        DataType::Number(NumberDataType::UInt8),
+        DataType::Number(NumberDataType::Int64),
+        DataType::Number(NumberDataType::Float64),
+        DataType::Decimal(DecimalDataType::Decimal128(DecimalSize {
+            precision: 10,
+            scale: 2,
+        })),
+        DataType::String,
+        DataType::Boolean,
+        DataType::Date,
+        DataType::Timestamp,
+        DataType::Variant,
+    ];
+
+    const NUM_ROWS: usize = 3;
+    let func_ctx = FunctionContext::default();
+    let block = DataBlock::new(vec![], NUM_ROWS);
+    let evaluator = Evaluator::new(&block, &func_ctx, &BUILTIN_FUNCTIONS);
+
+    // Casting a constant gives the same values and the same errors as casting the column.
+    for (src_type, scalars) in samples.iter() {
+        for scalar in scalars {
+            let column = ColumnBuilder::repeat(&scalar.as_ref(), NUM_ROWS, src_type).build();
+            for dest_type in dest_types
+                .iter()
+                .flat_map(|ty| [ty.clone(), ty.wrap_nullable()])
+            {
+                if src_type.is_nullable() && !dest_type.is_nullable() {
+                    continue;
+                }
+                let case = format!("CAST({scalar} AS {dest_type})");
+                let cast =
+                    |value| evaluator.run_cast(None, src_type, &dest_type, value, None, None);
+                let try_cast = |value| {
+                    evaluator.run_try_cast(None, src_type, &dest_type.wrap_nullable(), value)
+                };
+
+                for (scalar_result, column_result) in [
+                    (
+                        cast(Value::Scalar(scalar.clone())),
+                        cast(Value::Column(column.clone())),
+                    ),
+                    (
+                        try_cast(Value::Scalar(scalar.clone())),
+                        try_cast(Value::Column(column.clone())),
+                    ),
+                ] {
+                    match (scalar_result, column_result) {
+                        (Ok(Value::Scalar(expected)), Ok(Value::Column(column))) => {
+                            assert_eq!(column.len(), NUM_ROWS, "{case}");
+                            for row in 0..NUM_ROWS {
+                                assert_eq!(column.index(row).unwrap(), expected.as_ref(), "{case}");
+                            }
+                        }
+                        (Err(scalar_err), Err(column_err)) => {
+                            assert_eq!(scalar_err.code(), column_err.code(), "{case}");
+                            assert_eq!(scalar_err.message(), column_err.message(), "{case}");
+                        }
+                        (scalar_result, column_result) => {
+                            panic!("{case}: {scalar_result:?} is not {column_result:?}")
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 fn gen_bitmap_data() -> Column {
     // construct bitmap column with 4 row:
     // 0..5, 1..6, 2..7, 3..8
@@ -16,6 +16,9 @@ use std::io::Write;
 
 use databend_common_expression::types::*;
 use databend_common_expression::FromData;
+use databend_common_functions::scalars::simple_fold;
+use databend_common_functions::scalars::string_eq;
+use databend_common_functions::scalars::StringCmpOptions;
 use goldenfile::Mint;
 
 use super::run_ast;
@@ -35,6 +38,46 @@ fn test_comparison() {
     test_regexp(file);
 }
 
+#[test]
+fn test_string_eq_ci() {
+    let ci = StringCmpOptions {
+        case_insensitive: true,
+    };
+    let cs = StringCmpOptions::default();
+
+    assert!(string_eq("Alice", "aLICE", ci));
+    assert!(!string_eq("Alice", "aLICE", cs));
+    assert!(!string_eq("alice", "alice ", ci));
+    assert!(string_eq("", "", ci));
+
+    // The ASCII fast path agrees with the folding of the chars.
+    for (lhs, rhs) in [
+        ("Hello", "hELLO"),
+        ("a_b%C", "A_B%c"),
+        ("abc", "abd"),
+        ("Z", "z "),
+    ] {
+        assert_eq!(
+            string_eq(lhs, rhs, ci),
+            lhs.chars()
+                .map(simple_fold)
+                .eq(rhs.chars().map(simple_fold))
+        );
+    }
+
+    assert!(string_eq("Straße", "STRAẞE", ci));
+    assert!(string_eq("ΣΊΣΥΦΟΣ", "σίσυφος", ci));
+    assert!(string_eq("\u{212A}elvin", "kelvin", ci));
+    // The simple case folding doesn't expand `ß` to `ss`.
+    assert!(!string_eq("Straße", "STRASSE", ci));
+    // The Turkish dotted and dotless i only equal themselves.
+    assert!(string_eq("ı", "ı", ci));
+    assert!(!string_eq("ı", "I", ci));
+    assert!(!string_eq("ı", "i", ci));
+    assert!(!string_eq("İ", "i", ci));
+    assert!(!string_eq("İ", "I", ci));
+}
+
 fn test_eq(file: &mut impl Write) {
     run_ast(file, "'1'='2'", &[]);
     run_ast(file, "null=null", &[]);
@@ -358,6 +401,8 @@ fn test_like(file: &mut impl Write) {
     run_ast(file, "'h\n' like 'h_'", &[]);
     run_ast(file, r#"'%' like '\%'"#, &[]);
     run_ast(file, r#"'v%xx' like '_\%%'"#, &[]);
+    run_ast(file, "'中文' like '_文'", &[]);
+    run_ast(file, "'数据库' like '__库'", &[]);
 
     let columns = [(
         "lhs",
@@ -184,6 +184,9 @@ pub fn transform_expr(ast: AExpr, columns: &[(&str, DataType)]) -> RawExpr {
             BinaryOperator::NotLike => {
                 unimplemented!("please use `not (a like b)` instead")
             }
+            BinaryOperator::NotILike => {
+                unimplemented!("please use `not (a ilike b)` instead")
+            }
             BinaryOperator::NotRLike | BinaryOperator::NotRegexp => {
                 unimplemented!("please use `not (a regexp b)` instead")
             }
@@ -0,0 +1,595 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use databend_common_arrow::arrow::bitmap::Bitmap;
+use databend_common_arrow::arrow::buffer::Buffer;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::types::array::ArrayColumn;
+use databend_common_expression::types::nullable::NullableColumn;
+use databend_common_expression::types::number::NumberScalar;
+use databend_common_expression::types::BooleanType;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::DateType;
+use databend_common_expression::types::Float32Type;
+use databend_common_expression::types::Float64Type;
+use databend_common_expression::types::Int16Type;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::Int64Type;
+use databend_common_expression::types::Int8Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::types::TimestampType;
+use databend_common_expression::types::UInt16Type;
+use databend_common_expression::types::UInt32Type;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::types::UInt8Type;
+use databend_common_expression::BlockEntry;
+use databend_common_expression::BlockMetaInfo;
+use databend_common_expression::Column;
+use databend_common_expression::ColumnBuilder;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_expression::Scalar;
+use databend_common_expression::ScalarRef;
+use databend_common_expression::Value;
+use databend_common_meta_app::principal::StageInfo;
+use databend_common_pipeline_core::Pipeline;
+use databend_common_settings::Settings;
+use databend_common_storage::StageFileInfo;
+use log::debug;
+use opendal::Operator;
+
+use crate::input_formats::input_pipeline::AligningStateTrait;
+use crate::input_formats::input_pipeline::BlockBuilderTrait;
+use crate::input_formats::input_pipeline::InputFormatPipe;
+use crate::input_formats::input_pipeline::RowBatchTrait;
+use crate::input_formats::InputContext;
+use crate::input_formats::InputFormat;
+use crate::input_formats::SplitInfo;
+
+/// The ClickHouse `Native` format, which the ClickHouse drivers use to send the data of inserts.
+///
+/// The data is a sequence of blocks. A block starts with the number of columns and rows,
+/// followed by the columns, each with its name, ClickHouse type and data. The columns are
+/// matched to the fields of the table by name, and converted to the types of the fields.
+pub struct InputFormatNative;
+
+#[async_trait::async_trait]
+impl InputFormat for InputFormatNative {
+    #[async_backtrace::framed]
+    async fn get_splits(
+        &self,
+        _file_infos: Vec<StageFileInfo>,
+        _stage_info: &StageInfo,
+        _op: &Operator,
+        _settings: &Arc<Settings>,
+    ) -> Result<Vec<Arc<SplitInfo>>> {
+        unimplemented!("InputFormatNative::get_splits")
+    }
+
+    fn exec_copy(&self, _ctx: Arc<InputContext>, _pipeline: &mut Pipeline) -> Result<()> {
+        unimplemented!("InputFormatNative::exec_copy")
+    }
+
+    fn exec_stream(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        NativeFormatPipe::execute_stream(ctx, pipeline)
+    }
+}
+
+pub struct NativeFormatPipe;
+
+#[async_trait::async_trait]
+impl InputFormatPipe for NativeFormatPipe {
+    type SplitMeta = ();
+    type ReadBatch = Vec<u8>;
+    type RowBatch = NativeBlock;
+    type AligningState = NativeAligningState;
+    type BlockBuilder = NativeBlockBuilder;
+
+    fn try_create_align_state(
+        _ctx: &Arc<InputContext>,
+        _split_info: &Arc<SplitInfo>,
+    ) -> Result<NativeAligningState> {
+        Ok(NativeAligningState {
+            buffer: vec![],
+            offset: 0,
+            retry_len: 0,
+        })
+    }
+
+    fn try_create_block_builder(ctx: &Arc<InputContext>) -> Result<NativeBlockBuilder> {
+        Ok(NativeBlockBuilder { ctx: ctx.clone() })
+    }
+}
+
+/// A block of the Native data, the columns have the types of the ClickHouse types.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct NativeBlock {
+    num_rows: usize,
+    columns: Vec<(String, Column)>,
+}
+
+impl RowBatchTrait for NativeBlock {
+    fn size(&self) -> usize {
+        self.columns
+            .iter()
+            .map(|(_, column)| column.memory_size())
+            .sum()
+    }
+
+    fn rows(&self) -> usize {
+        self.num_rows
+    }
+}
+
+#[typetag::serde(name = "row_batch_native")]
+impl BlockMetaInfo for NativeBlock {
+    fn equals(&self, _info: &Box<dyn BlockMetaInfo>) -> bool {
+        unreachable!("NativeBlock as BlockMetaInfo is not expected to be compared.")
+    }
+
+    fn clone_self(&self) -> Box<dyn BlockMetaInfo> {
+        unreachable!("NativeBlock as BlockMetaInfo is not expected to be cloned.")
+    }
+}
+
+/// The blocks are decoded as soon as all their data is read, only the data of the block being
+/// read is buffered.
+pub struct NativeAligningState {
+    buffer: Vec<u8>,
+    // The position of `buffer` in the whole input.
+    offset: usize,
+    // The blocks are not length-prefixed, an incomplete block is only found by decoding it. It
+    // is decoded again once the buffer doubles, so a large block is not decoded for every batch.
+    retry_len: usize,
+}
+
+impl AligningStateTrait for NativeAligningState {
+    type Pipe = NativeFormatPipe;
+
+    fn align(&mut self, read_batch: Option<Vec<u8>>) -> Result<Vec<NativeBlock>> {
+        let is_end = read_batch.is_none();
+        if let Some(batch) = read_batch {
+            self.buffer.extend_from_slice(&batch);
+            if self.buffer.len() < self.retry_len {
+                return Ok(vec![]);
+            }
+        }
+
+        let mut reader = NativeReader {
+            data: &self.buffer,
+            pos: 0,
+            offset: self.offset,
+            truncated: false,
+        };
+        let mut blocks = vec![];
+        let mut consumed = 0;
+        while !reader.is_empty() {
+            match reader.read_block() {
+                Ok(block) => {
+                    consumed = reader.pos;
+                    blocks.extend(block);
+                }
+                // The rest of the block is not read yet.
+                Err(_) if reader.truncated && !is_end => break,
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.buffer.drain(..consumed);
+        self.offset += consumed;
+        self.retry_len = self.buffer.len() * 2;
+        debug!(
+            "align {} bytes of native data to {} blocks, {} bytes left",
+            consumed,
+            blocks.len(),
+            self.buffer.len()
+        );
+        Ok(blocks)
+    }
+}
+
+pub struct NativeBlockBuilder {
+    ctx: Arc<InputContext>,
+}
+
+impl BlockBuilderTrait for NativeBlockBuilder {
+    type Pipe = NativeFormatPipe;
+
+    fn deserialize(&mut self, batch: Option<NativeBlock>) -> Result<Vec<DataBlock>> {
+        let Some(batch) = batch else {
+            return Ok(vec![]);
+        };
+
+        let fields = self.ctx.schema.fields();
+        let mut entries = Vec::with_capacity(fields.len());
+        for field in fields {
+            let Some((name, column)) = batch
+                .columns
+                .iter()
+                .rev()
+                .find(|(name, _)| name.eq_ignore_ascii_case(field.name()))
+            else {
+                return Err(ErrorCode::TableSchemaMismatch(format!(
+                    "Column {} is not found in the Native data",
+                    field.name()
+                )));
+            };
+            let data_type = DataType::from(field.data_type());
+            let column = convert_column(column, name, &data_type)?;
+            entries.push(BlockEntry::new(data_type, Value::Column(column)));
+        }
+        Ok(vec![DataBlock::new(entries, batch.num_rows)])
+    }
+}
+
+struct NativeReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    // The position of `data` in the whole input.
+    offset: usize,
+    // Whether the last error is caused by the end of the data, which is not an error if more
+    // data is to be read.
+    truncated: bool,
+}
+
+impl<'a> NativeReader<'a> {
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn position(&self) -> usize {
+        self.offset + self.pos
+    }
+
+    // Reads the next block, None if it has no rows.
+    fn read_block(&mut self) -> Result<Option<NativeBlock>> {
+        // Each column takes at least the lengths of its name and type.
+        let num_columns = self.read_count(2, "columns")?;
+        let num_rows = self.read_varuint()? as usize;
+        let mut columns = vec![];
+        for _ in 0..num_columns {
+            let name = self.read_utf8()?;
+            let type_name = self.read_utf8()?;
+            let native_type = NativeType::parse(&type_name).ok_or_else(|| {
+                ErrorCode::BadBytes(format!(
+                    "Unsupported ClickHouse type {} of column {}",
+                    type_name, name
+                ))
+            })?;
+            let column = native_type
+                .read_column(self, num_rows)
+                .map_err(|e| e.add_message_back(format!("(while reading column {})", name)))?;
+            columns.push((name, column));
+        }
+        // The drivers may end the data with an empty block.
+        Ok((num_rows > 0).then_some(NativeBlock { num_rows, columns }))
+    }
+
+    fn read_bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.data.len() - self.pos < n {
+            self.truncated = true;
+            return Err(ErrorCode::BadBytes(format!(
+                "Unexpected end of the Native data at byte {}, expect {} more bytes",
+                self.position(),
+                n
+            )));
+        }
+        let bytes = &self.data[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    fn read_fixed<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.read_bytes(N)?.try_into().unwrap())
+    }
+
+    // LEB128, the lengths and the counts are written as varuints.
+    fn read_varuint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for i in 0..10 {
+            let [byte] = self.read_fixed::<1>()?;
+            value |= ((byte & 0x7f) as u64) << (7 * i);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(ErrorCode::BadBytes(format!(
+            "Invalid varuint in the Native data at byte {}",
+            self.position()
+        )))
+    }
+
+    // The counts come from the client, so they are checked against the remaining data before
+    // anything is sized by them. Each item takes at least `min_size` bytes.
+    fn check_count(&mut self, count: usize, min_size: usize, what: &str) -> Result<usize> {
+        let remaining = self.data.len() - self.pos;
+        if count > remaining / min_size {
+            self.truncated = true;
+            return Err(ErrorCode::BadBytes(format!(
+                "The Native data declares {} {} at byte {}, but only {} bytes remain",
+                count,
+                what,
+                self.position(),
+                remaining
+            )));
+        }
+        Ok(count)
+    }
+
+    fn read_count(&mut self, min_size: usize, what: &str) -> Result<usize> {
+        let count = self.read_varuint()?;
+        self.check_count(usize::try_from(count).unwrap_or(usize::MAX), min_size, what)
+    }
+
+    fn read_utf8(&mut self) -> Result<String> {
+        let len = self.read_varuint()? as usize;
+        let bytes = self.read_bytes(len)?;
+        to_utf8(bytes)
+    }
+
+    fn read_values<T, const N: usize>(
+        &mut self,
+        rows: usize,
+        f: impl Fn([u8; N]) -> T,
+    ) -> Result<Vec<T>> {
+        (0..rows).map(|_| Ok(f(self.read_fixed()?))).collect()
+    }
+}
+
+fn to_utf8(bytes: &[u8]) -> Result<String> {
+    String::from_utf8(bytes.to_vec())
+        .map_err(|e| ErrorCode::BadBytes(format!("Invalid UTF-8 string in the Native data: {}", e)))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum NativeType {
+    Number(NumberDataType),
+    Boolean,
+    String,
+    FixedString(usize),
+    Date,
+    Date32,
+    DateTime,
+    Nullable(Box<NativeType>),
+    Array(Box<NativeType>),
+}
+
+impl NativeType {
+    fn parse(name: &str) -> Option<NativeType> {
+        let name = name.trim();
+        if let Some(inner) = strip_type_args(name, "Nullable") {
+            return Some(NativeType::Nullable(Box::new(Self::parse(inner)?)));
+        }
+        if let Some(inner) = strip_type_args(name, "Array") {
+            return Some(NativeType::Array(Box::new(Self::parse(inner)?)));
+        }
+        if let Some(n) = strip_type_args(name, "FixedString") {
+            // `FixedString(0)` is invalid, every value takes at least one byte.
+            let n = n.trim().parse().ok().filter(|n| *n > 0)?;
+            return Some(NativeType::FixedString(n));
+        }
+        // The timezone of `DateTime('tz')` does not change the values.
+        if strip_type_args(name, "DateTime").is_some() {
+            return Some(NativeType::DateTime);
+        }
+
+        let ty = match name {
+            "Int8" => NativeType::Number(NumberDataType::Int8),
+            "Int16" => NativeType::Number(NumberDataType::Int16),
+            "Int32" => NativeType::Number(NumberDataType::Int32),
+            "Int64" => NativeType::Number(NumberDataType::Int64),
+            "UInt8" => NativeType::Number(NumberDataType::UInt8),
+            "UInt16" => NativeType::Number(NumberDataType::UInt16),
+            "UInt32" => NativeType::Number(NumberDataType::UInt32),
+            "UInt64" => NativeType::Number(NumberDataType::UInt64),
+            "Float32" => NativeType::Number(NumberDataType::Float32),
+            "Float64" => NativeType::Number(NumberDataType::Float64),
+            "Bool" => NativeType::Boolean,
+            "String" => NativeType::String,
+            "Date" => NativeType::Date,
+            "Date32" => NativeType::Date32,
+            "DateTime" => NativeType::DateTime,
+            _ => return None,
+        };
+        Some(ty)
+    }
+
+    // Reads the data of `rows` values, the nested types are written after the prefix
+    // of the outer type, e.g. the offsets of an array are followed by all its items.
+    fn read_column(&self, reader: &mut NativeReader, rows: usize) -> Result<Column> {
+        // Every type takes at least one byte per value.
+        reader.check_count(rows, 1, "rows")?;
+        let column = match self {
+            NativeType::Number(ty) => match ty {
+                NumberDataType::UInt8 => {
+                    UInt8Type::from_data(reader.read_values(rows, u8::from_le_bytes)?)
+                }
+                NumberDataType::UInt16 => {
+                    UInt16Type::from_data(reader.read_values(rows, u16::from_le_bytes)?)
+                }
+                NumberDataType::UInt32 => {
+                    UInt32Type::from_data(reader.read_values(rows, u32::from_le_bytes)?)
+                }
+                NumberDataType::UInt64 => {
+                    UInt64Type::from_data(reader.read_values(rows, u64::from_le_bytes)?)
+                }
+                NumberDataType::Int8 => {
+                    Int8Type::from_data(reader.read_values(rows, i8::from_le_bytes)?)
+                }
+                NumberDataType::Int16 => {
+                    Int16Type::from_data(reader.read_values(rows, i16::from_le_bytes)?)
+                }
+                NumberDataType::Int32 => {
+                    Int32Type::from_data(reader.read_values(rows, i32::from_le_bytes)?)
+                }
+                NumberDataType::Int64 => {
+                    Int64Type::from_data(reader.read_values(rows, i64::from_le_bytes)?)
+                }
+                NumberDataType::Float32 => {
+                    Float32Type::from_data(reader.read_values(rows, f32::from_le_bytes)?)
+                }
+                NumberDataType::Float64 => {
+                    Float64Type::from_data(reader.read_values(rows, f64::from_le_bytes)?)
+                }
+            },
+            NativeType::Boolean => {
+                BooleanType::from_data(reader.read_values(rows, |[v]: [u8; 1]| v != 0)?)
+            }
+            NativeType::String => {
+                let values = (0..rows)
+                    .map(|_| reader.read_utf8())
+                    .collect::<Result<Vec<_>>>()?;
+                StringType::from_data(values)
+            }
+            NativeType::FixedString(n) => {
+                // The values shorter than `n` are padded with zeros.
+                let values = (0..rows)
+                    .map(|_| {
+                        let bytes = reader.read_bytes(*n)?;
+                        let len = bytes.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+                        to_utf8(&bytes[..len])
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                StringType::from_data(values)
+            }
+            NativeType::Date => DateType::from_data(
+                reader.read_values(rows, |v: [u8; 2]| u16::from_le_bytes(v) as i32)?,
+            ),
+            NativeType::Date32 => {
+                DateType::from_data(reader.read_values(rows, i32::from_le_bytes)?)
+            }
+            NativeType::DateTime => TimestampType::from_data(
+                reader.read_values(rows, |v: [u8; 4]| u32::from_le_bytes(v) as i64 * 1_000_000)?,
+            ),
+            NativeType::Nullable(inner) => {
+                let nulls = reader.read_bytes(rows)?;
+                let validity = Bitmap::from_iter(nulls.iter().map(|null| *null == 0));
+                let column = inner.read_column(reader, rows)?;
+                Column::Nullable(Box::new(NullableColumn { column, validity }))
+            }
+            NativeType::Array(inner) => {
+                let ends = reader.read_values(rows, u64::from_le_bytes)?;
+                let mut offsets = Vec::with_capacity(rows + 1);
+                offsets.push(0);
+                for end in ends {
+                    if end < *offsets.last().unwrap() {
+                        return Err(ErrorCode::BadBytes(format!(
+                            "Invalid array offsets in the Native data at byte {}",
+                            reader.position()
+                        )));
+                    }
+                    offsets.push(end);
+                }
+                let values = inner.read_column(reader, *offsets.last().unwrap() as usize)?;
+                Column::Array(Box::new(ArrayColumn {
+                    values,
+                    offsets: Buffer::from(offsets),
+                }))
+            }
+        };
+        Ok(column)
+    }
+}
+
+// `Nullable(String)` => `String`
+fn strip_type_args<'a>(name: &'a str, type_name: &str) -> Option<&'a str> {
+    name.strip_prefix(type_name)?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')
+}
+
+// Converts the column to the type of the field, if the ClickHouse type is not the same.
+fn convert_column(column: &Column, name: &str, data_type: &DataType) -> Result<Column> {
+    let from_type = column.data_type();
+    if &from_type == data_type {
+        return Ok(column.clone());
+    }
+
+    let mut builder = ColumnBuilder::with_capacity(data_type, column.len());
+    for (row, value) in column.iter().enumerate() {
+        match convert_scalar(value.clone(), data_type) {
+            Some(value) => builder.push(value.as_ref()),
+            None => {
+                return Err(ErrorCode::BadBytes(format!(
+                    "Cannot convert value {} of column {} at row {} from {} to {}",
+                    value, name, row, from_type, data_type
+                )));
+            }
+        }
+    }
+    Ok(builder.build())
+}
+
+fn convert_scalar(value: ScalarRef, data_type: &DataType) -> Option<Scalar> {
+    match (value, data_type) {
+        (ScalarRef::Null, DataType::Nullable(_)) => Some(Scalar::Null),
+        (value, DataType::Nullable(inner)) => convert_scalar(value, inner),
+        (ScalarRef::Number(n), DataType::Number(ty)) => convert_number(n, *ty).map(Scalar::Number),
+        // The older drivers send `Bool` as `UInt8`.
+        (ScalarRef::Number(n), DataType::Boolean) => {
+            Some(Scalar::Boolean(n.integer_to_i128()? != 0))
+        }
+        (ScalarRef::Boolean(v), DataType::Boolean) => Some(Scalar::Boolean(v)),
+        (ScalarRef::String(v), DataType::String) => Some(Scalar::String(v.to_string())),
+        (ScalarRef::Date(v), DataType::Date) => Some(Scalar::Date(v)),
+        (ScalarRef::Date(v), DataType::Timestamp) => {
+            Some(Scalar::Timestamp(v as i64 * 86_400_000_000))
+        }
+        (ScalarRef::Timestamp(v), DataType::Timestamp) => Some(Scalar::Timestamp(v)),
+        (ScalarRef::Array(values), DataType::Array(inner)) => {
+            let mut builder = ColumnBuilder::with_capacity(inner, values.len());
+            for value in values.iter() {
+                builder.push(convert_scalar(value, inner)?.as_ref());
+            }
+            Some(Scalar::Array(builder.build()))
+        }
+        (ScalarRef::EmptyArray, DataType::Array(inner)) => Some(Scalar::Array(
+            ColumnBuilder::with_capacity(inner, 0).build(),
+        )),
+        _ => None,
+    }
+}
+
+// The integers are converted if they are in the range of the type, the floats are only
+// converted to floats.
+fn convert_number(value: NumberScalar, ty: NumberDataType) -> Option<NumberScalar> {
+    let float = match value {
+        NumberScalar::Float32(v) => v.0 as f64,
+        NumberScalar::Float64(v) => v.0,
+        _ => value.integer_to_i128()? as f64,
+    };
+    match ty {
+        NumberDataType::Float32 => Some(NumberScalar::Float32((float as f32).into())),
+        NumberDataType::Float64 => Some(NumberScalar::Float64(float.into())),
+        _ => {
+            let value = value.integer_to_i128()?;
+            match ty {
+                NumberDataType::UInt8 => u8::try_from(value).ok().map(NumberScalar::UInt8),
+                NumberDataType::UInt16 => u16::try_from(value).ok().map(NumberScalar::UInt16),
+                NumberDataType::UInt32 => u32::try_from(value).ok().map(NumberScalar::UInt32),
+                NumberDataType::UInt64 => u64::try_from(value).ok().map(NumberScalar::UInt64),
+                NumberDataType::Int8 => i8::try_from(value).ok().map(NumberScalar::Int8),
+                NumberDataType::Int16 => i16::try_from(value).ok().map(NumberScalar::Int16),
+                NumberDataType::Int32 => i32::try_from(value).ok().map(NumberScalar::Int32),
+                NumberDataType::Int64 => i64::try_from(value).ok().map(NumberScalar::Int64),
+                NumberDataType::Float32 | NumberDataType::Float64 => unreachable!(),
+            }
+        }
+    }
+}
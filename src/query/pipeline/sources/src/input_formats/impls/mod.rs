@@ -13,12 +13,14 @@
 // limitations under the License.
 
 mod input_format_csv;
+mod input_format_native;
 mod input_format_ndjson;
 mod input_format_parquet;
 mod input_format_tsv;
 mod input_format_xml;
 
 pub use input_format_csv::InputFormatCSV;
+pub use input_format_native::InputFormatNative;
 pub use input_format_ndjson::InputFormatNDJson;
 pub use input_format_parquet::InputFormatParquet;
 pub use input_format_tsv::InputFormatTSV;
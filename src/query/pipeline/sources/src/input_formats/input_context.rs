@@ -38,6 +38,7 @@ use databend_common_formats::FileFormatOptionsExt;
 use databend_common_meta_app::principal::FileFormatParams;
 use databend_common_meta_app::principal::OnErrorMode;
 use databend_common_meta_app::principal::StageFileCompression;
+use databend_common_meta_app::principal::StageFileFormatType;
 use databend_common_meta_app::principal::StageInfo;
 use databend_common_pipeline_core::InputError;
 use databend_common_settings::Settings;
@@ -47,6 +48,7 @@ use opendal::Operator;
 
 use crate::input_formats::impls::InputFormatCSV;
 use crate::input_formats::impls::InputFormatNDJson;
+use crate::input_formats::impls::InputFormatNative;
 use crate::input_formats::impls::InputFormatParquet;
 use crate::input_formats::impls::InputFormatTSV;
 use crate::input_formats::impls::InputFormatXML;
@@ -221,7 +223,16 @@ impl InputContext {
         scan_progress: Arc<Progress>,
         block_compact_thresholds: BlockThresholds,
     ) -> Result<Self> {
-        let typ = ClickhouseFormatType::parse_clickhouse_format(format_name)?;
+        // The Native blocks describe their columns, like the Parquet files, so the default
+        // Parquet params are used for the Native format.
+        let is_native = format_name.eq_ignore_ascii_case("native");
+        let typ = match is_native {
+            true => ClickhouseFormatType {
+                typ: StageFileFormatType::Parquet,
+                ..Default::default()
+            },
+            false => ClickhouseFormatType::parse_clickhouse_format(format_name)?,
+        };
         let file_format_options_ext =
             FileFormatOptionsExt::create_from_clickhouse_format(typ.clone(), &settings)?;
         let mut file_format_params = FileFormatParams::default_by_type(typ.typ)?;
@@ -239,7 +250,10 @@ impl InputContext {
             }
         }
 
-        let format = Self::get_input_format(&file_format_params)?;
+        let format: Arc<dyn InputFormat> = match is_native {
+            true => Arc::new(InputFormatNative),
+            false => Self::get_input_format(&file_format_params)?,
+        };
         let read_batch_size = settings.get_input_read_buffer_size()? as usize;
         let compression = StageFileCompression::Auto;
         let plan = StreamPlan {
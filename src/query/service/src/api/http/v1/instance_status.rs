@@ -14,11 +14,13 @@
 
 use std::time::SystemTime;
 
+use databend_common_meta_types::NodeStatus;
 use poem::web::Json;
 use poem::IntoResponse;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::clusters::ClusterDiscovery;
 use crate::sessions::SessionManager;
 
 #[derive(Serialize, Deserialize, Eq, PartialEq, Debug)]
@@ -35,6 +37,10 @@ pub struct InstanceStatus {
     pub instance_started_at: u64,
     // the local timestamp, may be useful to avoid the clock drift issues
     pub instance_timestamp: u64,
+    // the status of the node in the cluster: running, draining or drained
+    pub node_status: String,
+    // the node is drained and can be shut down safely
+    pub exit_ready: bool,
 }
 
 // lightweight way to get status
@@ -44,6 +50,7 @@ pub struct InstanceStatus {
 pub async fn instance_status_handler() -> poem::Result<impl IntoResponse> {
     let session_manager = SessionManager::instance();
     let status = session_manager.get_current_session_status();
+    let node_status = ClusterDiscovery::instance().local_status();
     let status = InstanceStatus {
         running_queries_count: status.running_queries_count,
         active_sessions_count: status.active_sessions_count,
@@ -51,6 +58,8 @@ pub async fn instance_status_handler() -> poem::Result<impl IntoResponse> {
         last_query_finished_at: status.last_query_finished_at.map(unix_timestamp_secs),
         instance_started_at: unix_timestamp_secs(status.instance_started_at),
         instance_timestamp: unix_timestamp_secs(SystemTime::now()),
+        node_status: node_status.to_string(),
+        exit_ready: node_status == NodeStatus::Drained,
     };
     Ok(Json(status))
 }
@@ -15,6 +15,10 @@
 // The api module only used for internal communication, such as GRPC between cluster and the managed HTTP REST API.
 
 pub use http_service::HttpService;
+pub use rpc::exchange_shuffle;
+pub use rpc::query_spill_dir;
+pub use rpc::remove_stale_spill_dirs;
+pub use rpc::resumable_receiver;
 pub use rpc::serialize_block;
 pub use rpc::BroadcastExchange;
 pub use rpc::BroadcastFlightScatter;
@@ -24,27 +28,51 @@ pub use rpc::DataExchangeManager;
 pub use rpc::DataPacket;
 pub use rpc::DatabendQueryFlightService;
 pub use rpc::DefaultExchangeInjector;
+pub use rpc::DrainNodePacket;
 pub use rpc::ExchangeDeserializeMeta;
 pub use rpc::ExchangeInjector;
 pub use rpc::ExchangeSerializeMeta;
 pub use rpc::ExchangeShuffleMeta;
+pub use rpc::ExchangeSortDesc;
 pub use rpc::ExchangeSorting;
 pub use rpc::ExecutePartialQueryPacket;
 pub use rpc::FlightAction;
 pub use rpc::FlightClient;
+pub use rpc::FlightReceiver;
 pub use rpc::FlightScatter;
+pub use rpc::FlightSender;
 pub use rpc::FragmentData;
 pub use rpc::FragmentPlanPacket;
+pub use rpc::FragmentResultCache;
+pub use rpc::FragmentStreamId;
 pub use rpc::HashFlightScatter;
 pub use rpc::InitNodesChannelPacket;
+pub use rpc::KillQueryFragmentsPacket;
 pub use rpc::KillQueryPacket;
 pub use rpc::MergeExchange;
 pub use rpc::MergeExchangeParams;
+pub use rpc::OrderedFlightReceiver;
 pub use rpc::Packet;
+pub use rpc::PolledStream;
 pub use rpc::QueryFragmentsPlanPacket;
+pub use rpc::RangeFlightScatter;
+pub use rpc::ResetQueryProfilePacket;
+pub use rpc::ResumableStream;
+pub use rpc::RoundRobinFlightScatter;
+pub use rpc::RunningStageInfo;
+pub use rpc::RunningStreamInfo;
+pub use rpc::ScatterPolicy;
 pub use rpc::ShuffleDataExchange;
 pub use rpc::ShuffleExchangeParams;
+pub use rpc::SortBoundsMeta;
+pub use rpc::SortedExchangeMerge;
+pub use rpc::StreamLagLimits;
+pub use rpc::StreamLiveness;
+pub use rpc::StreamResumeConf;
+pub use rpc::StreamSentStatistics;
+pub use rpc::StreamSpillConf;
 pub use rpc::TransformExchangeDeserializer;
+pub use rpc::TransformSortBounds;
 pub use rpc::TruncateTablePacket;
 pub use rpc_service::RpcService;
 
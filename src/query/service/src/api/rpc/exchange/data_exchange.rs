@@ -12,7 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::DataSchema;
 use databend_common_expression::RemoteExpr;
+use databend_common_expression::Scalar;
+use databend_common_functions::BUILTIN_FUNCTIONS;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum DataExchange {
@@ -29,28 +34,115 @@ impl DataExchange {
             DataExchange::ShuffleDataExchange(exchange) => exchange.destination_ids.clone(),
         }
     }
+
+    /// Checks the scatter keys against the schema of the blocks sent by the fragment, so a bad
+    /// key fails the preparation of the fragment instead of its first block.
+    pub fn check_schema(&self, schema: &DataSchema) -> Result<()> {
+        match self {
+            DataExchange::ShuffleDataExchange(exchange) => match &exchange.policy {
+                ScatterPolicy::Hash(keys) => keys.iter().try_for_each(|key| check_key(key, schema)),
+                ScatterPolicy::RoundRobin => Ok(()),
+                ScatterPolicy::Range(key, _) => check_key(key, schema),
+            },
+            DataExchange::Merge(_) | DataExchange::Broadcast(_) => Ok(()),
+        }
+    }
+}
+
+fn check_key(key: &RemoteExpr, schema: &DataSchema) -> Result<()> {
+    match key {
+        RemoteExpr::Constant { .. } => Ok(()),
+        RemoteExpr::ColumnRef {
+            id,
+            data_type,
+            display_name,
+            ..
+        } => match schema.fields().get(*id) {
+            Some(field) if field.data_type() == data_type => Ok(()),
+            Some(field) => Err(ErrorCode::Internal(format!(
+                "Scatter key {} expects column {} to be {}, but it is {}",
+                display_name,
+                id,
+                data_type,
+                field.data_type()
+            ))),
+            None => Err(ErrorCode::Internal(format!(
+                "Scatter key {} refers to column {}, but the fragment outputs {} columns",
+                display_name,
+                id,
+                schema.num_fields()
+            ))),
+        },
+        RemoteExpr::Cast { expr, .. } => check_key(expr, schema),
+        RemoteExpr::FunctionCall { id, args, .. } => {
+            let function = BUILTIN_FUNCTIONS.get(id).ok_or_else(|| {
+                ErrorCode::Internal(format!("Unknown function {} in scatter key", id.name()))
+            })?;
+            if function.signature.args_type.len() != args.len() {
+                return Err(ErrorCode::Internal(format!(
+                    "Function {} in scatter key expects {} arguments, but got {}",
+                    function.signature.name,
+                    function.signature.args_type.len(),
+                    args.len()
+                )));
+            }
+            args.iter().try_for_each(|arg| check_key(arg, schema))
+        }
+        RemoteExpr::LambdaFunctionCall { args, .. } => {
+            args.iter().try_for_each(|arg| check_key(arg, schema))
+        }
+    }
+}
+
+/// How the rows are distributed to the destinations of a shuffle exchange.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum ScatterPolicy {
+    /// The rows with the same keys are sent to the same destination.
+    Hash(Vec<RemoteExpr>),
+    /// The rows are sent to the destinations in turn, regardless of their values.
+    RoundRobin,
+    /// The rows are sent to the destinations by the range of the key, `n` sorted boundaries
+    /// split the key into `n + 1` ranges. The rows whose key is NULL are sent to the first one.
+    Range(RemoteExpr, Vec<Scalar>),
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ShuffleDataExchange {
     pub destination_ids: Vec<String>,
-    pub shuffle_keys: Vec<RemoteExpr>,
+    pub policy: ScatterPolicy,
 }
 
 impl ShuffleDataExchange {
     pub fn create(destination_ids: Vec<String>, shuffle_keys: Vec<RemoteExpr>) -> DataExchange {
+        Self::create_with_policy(destination_ids, ScatterPolicy::Hash(shuffle_keys))
+    }
+
+    pub fn create_with_policy(destination_ids: Vec<String>, policy: ScatterPolicy) -> DataExchange {
         DataExchange::ShuffleDataExchange(ShuffleDataExchange {
             destination_ids,
-            shuffle_keys,
+            policy,
         })
     }
 }
 
+/// A sort key of a sorted merge exchange, the column at `offset` of the exchanged blocks.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ExchangeSortDesc {
+    pub offset: usize,
+    pub asc: bool,
+    pub nulls_first: bool,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct MergeExchange {
     pub destination_id: String,
     pub ignore_exchange: bool,
     pub allow_adjust_parallelism: bool,
+    /// The sort key of the blocks if the fragment outputs a sorted stream. Each block then
+    /// carries its sort bounds, see `TransformSortBounds`, and the streams can be merged by
+    /// `SortedExchangeMerge`. The fragmenter doesn't set it yet, so distributed ORDER BY still
+    /// sorts the merged streams after the exchange.
+    pub sort_desc: Option<Vec<ExchangeSortDesc>>,
 }
 
 impl MergeExchange {
@@ -63,6 +155,20 @@ impl MergeExchange {
             destination_id,
             ignore_exchange,
             allow_adjust_parallelism,
+            sort_desc: None,
+        })
+    }
+
+    pub fn create_sorted(
+        destination_id: String,
+        allow_adjust_parallelism: bool,
+        sort_desc: Vec<ExchangeSortDesc>,
+    ) -> DataExchange {
+        DataExchange::Merge(MergeExchange {
+            destination_id,
+            ignore_exchange: false,
+            allow_adjust_parallelism,
+            sort_desc: Some(sort_desc),
         })
     }
 }
@@ -28,6 +28,9 @@ use crate::api::BroadcastFlightScatter;
 use crate::api::DataExchange;
 use crate::api::ExchangeSorting;
 use crate::api::HashFlightScatter;
+use crate::api::RangeFlightScatter;
+use crate::api::RoundRobinFlightScatter;
+use crate::api::ScatterPolicy;
 use crate::api::ShuffleExchangeParams;
 use crate::sessions::QueryContext;
 
@@ -86,11 +89,24 @@ impl ExchangeInjector for DefaultExchangeInjector {
             DataExchange::Broadcast(exchange) => Box::new(BroadcastFlightScatter::try_create(
                 exchange.destination_ids.len(),
             )?),
-            DataExchange::ShuffleDataExchange(exchange) => HashFlightScatter::try_create(
-                ctx.get_function_context()?,
-                exchange.shuffle_keys.clone(),
-                exchange.destination_ids.len(),
-            )?,
+            DataExchange::ShuffleDataExchange(exchange) => match &exchange.policy {
+                ScatterPolicy::Hash(shuffle_keys) => HashFlightScatter::try_create(
+                    ctx.get_function_context()?,
+                    shuffle_keys.clone(),
+                    exchange.destination_ids.len(),
+                )?,
+                ScatterPolicy::RoundRobin => Box::new(RoundRobinFlightScatter::try_create(
+                    exchange.destination_ids.len(),
+                )?),
+                ScatterPolicy::Range(range_key, boundaries) => {
+                    Box::new(RangeFlightScatter::try_create(
+                        ctx.get_function_context()?,
+                        range_key,
+                        boundaries.clone(),
+                        exchange.destination_ids.len(),
+                    )?)
+                }
+            },
         }))
     }
 
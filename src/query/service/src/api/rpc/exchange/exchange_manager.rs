@@ -15,22 +15,33 @@
 use std::cell::SyncUnsafeCell;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::ops::Deref;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
 
 use async_channel::Receiver;
 use databend_common_arrow::arrow_format::flight::data::FlightData;
-use databend_common_arrow::arrow_format::flight::service::flight_service_client::FlightServiceClient;
+use databend_common_base::base::tokio;
 use databend_common_base::base::GlobalInstance;
 use databend_common_base::runtime::profile::Profile;
 use databend_common_base::runtime::GlobalIORuntime;
 use databend_common_base::runtime::Thread;
 use databend_common_base::runtime::TrySpawn;
 use databend_common_base::GLOBAL_TASK;
+use databend_common_catalog::table_context::FinishedStageInfo;
+use databend_common_catalog::table_context::FinishedStageStatus;
+use databend_common_catalog::table_context::FinishedStreamInfo;
 use databend_common_config::GlobalConfig;
+use databend_common_config::InnerConfig;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
-use databend_common_grpc::ConnectionFactory;
 use databend_common_sql::executor::PhysicalPlan;
 use minitrace::prelude::*;
 use parking_lot::Mutex;
@@ -42,12 +53,22 @@ use crate::api::rpc::exchange::exchange_params::MergeExchangeParams;
 use crate::api::rpc::exchange::exchange_params::ShuffleExchangeParams;
 use crate::api::rpc::exchange::exchange_sink::ExchangeSink;
 use crate::api::rpc::exchange::exchange_transform::ExchangeTransform;
+use crate::api::rpc::exchange::fragment_result_cache::FragmentResultCache;
 use crate::api::rpc::exchange::statistics_receiver::StatisticsReceiver;
 use crate::api::rpc::exchange::statistics_sender::StatisticsSender;
+use crate::api::rpc::exchange_spill_root;
 use crate::api::rpc::flight_client::FlightExchange;
 use crate::api::rpc::flight_client::FlightReceiver;
 use crate::api::rpc::flight_client::FlightSender;
+use crate::api::rpc::query_spill_dir;
+use crate::api::rpc::remove_spill_dir;
+use crate::api::rpc::remove_stale_spill_dirs;
+use crate::api::rpc::FragmentStreamId;
 use crate::api::rpc::Packet;
+use crate::api::rpc::ResumableStream;
+use crate::api::rpc::StreamLiveness;
+use crate::api::rpc::StreamResumeConf;
+use crate::api::rpc::StreamSentStatistics;
 use crate::api::DataExchange;
 use crate::api::DefaultExchangeInjector;
 use crate::api::ExchangeInjector;
@@ -64,14 +85,51 @@ use crate::schedulers::QueryFragmentsActions;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
 
+// The number of the recently finished queries whose streams are remembered by a node.
+const MAX_FINISHED_QUERIES: usize = 1024;
+
 pub struct DataExchangeManager {
     queries_coordinator: ReentrantMutex<SyncUnsafeCell<HashMap<String, QueryCoordinator>>>,
+    fragment_result_cache: Arc<FragmentResultCache>,
+    // The streams of the finished queries, the requests of them are rejected instead of
+    // waiting on the streams which would never be sent.
+    finished_queries: Mutex<FinishedQueries>,
+    // Set when the node is shutting down, the new queries and streams are rejected.
+    shutting_down: AtomicBool,
+    // The partial queries executed and waiting to be executed on this node.
+    stages: Mutex<Stages>,
+    max_concurrent_stages: usize,
+    max_queued_stages: usize,
+    // A queued partial query is aborted after this, a query holding slots on some nodes and
+    // queued on others would otherwise wait for ever.
+    max_stage_queued_time: Option<Duration>,
+    // The summaries of the recently finished partial queries, see `get_finished_stages`.
+    finished_stages: Mutex<FinishedStages>,
+    // The exchange streams of a query spill to a directory of its own under it.
+    spill_root: PathBuf,
 }
 
 impl DataExchangeManager {
-    pub fn init() -> Result<()> {
+    pub fn init(config: &InnerConfig) -> Result<()> {
+        let spill_root = exchange_spill_root(config);
+        remove_stale_spill_dirs(&spill_root);
+
         GlobalInstance::set(Arc::new(DataExchangeManager {
             queries_coordinator: ReentrantMutex::new(SyncUnsafeCell::new(HashMap::new())),
+            fragment_result_cache: FragmentResultCache::create(),
+            finished_queries: Mutex::new(FinishedQueries::default()),
+            shutting_down: AtomicBool::new(false),
+            stages: Mutex::new(Stages::default()),
+            max_concurrent_stages: config.query.max_concurrent_stages as usize,
+            max_queued_stages: config.query.max_queued_stages as usize,
+            max_stage_queued_time: match config.query.max_stage_queued_secs {
+                0 => None,
+                secs => Some(Duration::from_secs(secs)),
+            },
+            finished_stages: Mutex::new(FinishedStages::create(
+                config.query.max_finished_stages as usize,
+            )),
+            spill_root,
         }));
 
         Ok(())
@@ -81,6 +139,14 @@ impl DataExchangeManager {
         GlobalInstance::get()
     }
 
+    pub fn get_fragment_result_cache(&self) -> Arc<FragmentResultCache> {
+        self.fragment_result_cache.clone()
+    }
+
+    pub fn get_spill_dir(&self, query_id: &str) -> PathBuf {
+        query_spill_dir(&self.spill_root, query_id)
+    }
+
     pub fn get_query_ctx(&self, query_id: &str) -> Result<Arc<QueryContext>> {
         let queries_coordinator_guard = self.queries_coordinator.lock();
         let queries_coordinator = unsafe { &mut *queries_coordinator_guard.deref().get() };
@@ -132,12 +198,25 @@ impl DataExchangeManager {
                 let mut flight_client =
                     Self::create_client(address, create_rpc_client_with_current_rt).await?;
 
-                targets_exchanges.insert(
-                    (connection_info.source.id.clone(), *fragment),
-                    flight_client
-                        .do_get(&packet.query_id, target, *fragment)
-                        .await?,
-                );
+                let exchange = match packet.stream_resume.is_enabled() {
+                    true => {
+                        flight_client
+                            .do_get_resumable(
+                                &packet.query_id,
+                                target,
+                                *fragment,
+                                &packet.stream_resume,
+                            )
+                            .await?
+                    }
+                    false => {
+                        flight_client
+                            .do_get(&packet.query_id, target, *fragment)
+                            .await?
+                    }
+                };
+
+                targets_exchanges.insert((connection_info.source.id.clone(), *fragment), exchange);
             }
         }
 
@@ -174,21 +253,7 @@ impl DataExchangeManager {
     pub async fn create_client(address: &str, use_current_rt: bool) -> Result<FlightClient> {
         let config = GlobalConfig::instance();
         let address = address.to_string();
-        let task = async move {
-            match config.tls_query_cli_enabled() {
-                true => Ok(FlightClient::new(FlightServiceClient::new(
-                    ConnectionFactory::create_rpc_channel(
-                        address.to_owned(),
-                        None,
-                        Some(config.query.to_rpc_client_tls_config()),
-                    )
-                    .await?,
-                ))),
-                false => Ok(FlightClient::new(FlightServiceClient::new(
-                    ConnectionFactory::create_rpc_channel(address.to_owned(), None, None).await?,
-                ))),
-            }
-        };
+        let task = async move { FlightClient::connect(&config, &address, None).await };
         if use_current_rt {
             task.await
         } else {
@@ -200,6 +265,10 @@ impl DataExchangeManager {
     }
 
     // Execute query in background
+    //
+    // The partial query waits in the queue if `max_concurrent_stages` of them are running,
+    // it is started when one of them is finished. It's aborted if it waits longer than
+    // `max_stage_queued_secs`.
     #[minitrace::trace]
     pub fn execute_partial_query(&self, query_id: &str) -> Result<()> {
         let queries_coordinator_guard = self.queries_coordinator.lock();
@@ -210,7 +279,144 @@ impl DataExchangeManager {
                 "Query {} not found in cluster.",
                 query_id
             ))),
-            Some(coordinator) => coordinator.execute_pipeline(),
+            // The request server has no fragments to execute, it is never queued, otherwise
+            // the nodes could wait for each other.
+            Some(coordinator) if coordinator.fragments_coordinator.is_empty() => {
+                coordinator.execute_pipeline()
+            }
+            Some(coordinator) => match self.acquire_stage(query_id)? {
+                true => coordinator.execute_pipeline(),
+                false => Ok(()),
+            },
+        }
+    }
+
+    /// Returns the number of the running and the queued partial queries on this node.
+    pub fn get_stages_status(&self) -> (usize, usize) {
+        let stages = self.stages.lock();
+        (stages.running.len(), stages.queued.len())
+    }
+
+    /// Returns the summaries of the partial queries finished on this node, the oldest first.
+    ///
+    /// At most `max_finished_stages` of them are kept, the oldest ones are forgotten first.
+    pub fn get_finished_stages(&self) -> Vec<FinishedStageInfo> {
+        self.finished_stages.lock().stages.iter().cloned().collect()
+    }
+
+    /// Returns the partial queries prepared on this node and not finished yet, with the
+    /// streams they send to the other nodes, ordered by query and stage.
+    ///
+    /// A stage is listed as soon as it is prepared, its `started_time` is set once it is
+    /// executed. It's removed when the query is finished or killed on this node.
+    pub fn get_running_stages(&self) -> Vec<RunningStageInfo> {
+        let queries_coordinator_guard = self.queries_coordinator.lock();
+        let queries_coordinator = unsafe { &*queries_coordinator_guard.deref().get() };
+
+        let mut stages = queries_coordinator
+            .iter()
+            .flat_map(|(query_id, coordinator)| coordinator.running_stages(query_id))
+            .collect::<Vec<_>>();
+        stages.sort_by(|a, b| (&a.query_id, a.stage_id).cmp(&(&b.query_id, b.stage_id)));
+        stages
+    }
+
+    // Returns true if the partial query can be started now, false if it is queued.
+    fn acquire_stage(&self, query_id: &str) -> Result<bool> {
+        let mut stages = self.stages.lock();
+        if self.max_concurrent_stages == 0 || stages.running.len() < self.max_concurrent_stages {
+            stages.running.insert(query_id.to_string());
+            return Ok(true);
+        }
+
+        if stages.queued.len() >= self.max_queued_stages {
+            return Err(ErrorCode::TooManyStages(format!(
+                "Query {} is rejected, {} partial queries are running and {} are queued",
+                query_id,
+                stages.running.len(),
+                stages.queued.len()
+            )));
+        }
+
+        stages.queued.push_back(query_id.to_string());
+        if let Some(timeout) = self.max_stage_queued_time {
+            let query_id = query_id.to_string();
+            GlobalIORuntime::instance().spawn(GLOBAL_TASK, async move {
+                tokio::time::sleep(timeout).await;
+                DataExchangeManager::instance().abort_queued_stage(&query_id, timeout);
+            });
+        }
+        Ok(false)
+    }
+
+    // Aborts the partial query if it's still queued. Each node admits the partial queries on
+    // its own, so two queries may each hold the slot on one node and wait for the other one.
+    // Aborting fails the whole query, its slots on the other nodes are released.
+    fn abort_queued_stage(&self, query_id: &str, timeout: Duration) {
+        let running = {
+            let mut stages = self.stages.lock();
+            let queued = stages.queued.len();
+            stages.queued.retain(|queued| queued != query_id);
+            if stages.queued.len() == queued {
+                return;
+            }
+            stages.running.len()
+        };
+
+        self.kill_query_fragments(
+            query_id,
+            ErrorCode::TooManyStages(format!(
+                "Query {} is aborted, it waited {:?} for one of {} running partial queries",
+                query_id, timeout, running
+            )),
+        );
+    }
+
+    // Releases the slot or the queue entry of a finished partial query, and starts the queued
+    // ones in the order they arrived.
+    fn release_stage(&self, query_id: &str) {
+        {
+            let mut stages = self.stages.lock();
+            if !stages.running.remove(query_id) {
+                stages.queued.retain(|queued| queued != query_id);
+                return;
+            }
+        }
+
+        loop {
+            let query_id = {
+                let mut stages = self.stages.lock();
+                if self.max_concurrent_stages != 0
+                    && stages.running.len() >= self.max_concurrent_stages
+                {
+                    return;
+                }
+
+                match stages.queued.pop_front() {
+                    None => return,
+                    Some(query_id) => {
+                        stages.running.insert(query_id.clone());
+                        query_id
+                    }
+                }
+            };
+
+            let res = {
+                let queries_coordinator_guard = self.queries_coordinator.lock();
+                let queries_coordinator = unsafe { &mut *queries_coordinator_guard.deref().get() };
+                match queries_coordinator.get_mut(&query_id) {
+                    Some(coordinator) => coordinator.execute_pipeline(),
+                    None => {
+                        self.stages.lock().running.remove(&query_id);
+                        continue;
+                    }
+                }
+            };
+
+            if let Err(cause) = res {
+                // The requester is answered already, the error is sent through the exchanges.
+                self.kill_query_fragments(&query_id, cause);
+            }
         }
     }
 
@@ -221,6 +427,7 @@ impl DataExchangeManager {
         ctx: &Arc<QueryContext>,
         packet: &QueryFragmentsPlanPacket,
     ) -> Result<()> {
+        self.check_not_shutting_down()?;
         let queries_coordinator_guard = self.queries_coordinator.lock();
         let queries_coordinator = unsafe { &mut *queries_coordinator_guard.deref().get() };
 
@@ -240,6 +447,7 @@ impl DataExchangeManager {
         id: String,
         target: String,
     ) -> Result<Receiver<Result<FlightData, Status>>> {
+        self.check_not_shutting_down()?;
         let queries_coordinator_guard = self.queries_coordinator.lock();
         let queries_coordinator = unsafe { &mut *queries_coordinator_guard.deref().get() };
 
@@ -251,17 +459,29 @@ impl DataExchangeManager {
         }
     }
 
+    /// Handles the request of a fragment stream.
+    ///
+    /// A stream is handed out only once, the following requests of it fail with
+    /// `StreamAlreadyConsumed`, or with `UnknownFragmentExchange` once the query is finished.
+    /// The new streams are rejected with `ServerShuttingDown` once the node is shutting down.
+    /// The stages prepared again for the same query have new fragment ids, so their
+    /// streams are not affected.
     #[minitrace::trace]
     pub fn handle_exchange_fragment(
         &self,
-        query: String,
-        target: String,
-        fragment: usize,
+        stream_id: FragmentStreamId,
     ) -> Result<Receiver<Result<FlightData, Status>>> {
         let queries_coordinator_guard = self.queries_coordinator.lock();
         let queries_coordinator = unsafe { &mut *queries_coordinator_guard.deref().get() };
 
-        match queries_coordinator.entry(query) {
+        self.check_not_shutting_down()?;
+        self.check_not_finished(&stream_id)?;
+        let FragmentStreamId {
+            query_id,
+            target,
+            fragment,
+        } = stream_id;
+        match queries_coordinator.entry(query_id) {
             Entry::Occupied(mut v) => v.get_mut().add_fragment_exchange(target, fragment),
             Entry::Vacant(v) => v
                 .insert(QueryCoordinator::create())
@@ -269,6 +489,115 @@ impl DataExchangeManager {
         }
     }
 
+    /// Handles the request of a resumable fragment stream, see [`ResumableStream`].
+    ///
+    /// The stream is created by the first request, and resumed by the following
+    /// requests with `resume_from`.
+    #[minitrace::trace]
+    pub fn handle_resumable_exchange_fragment(
+        &self,
+        stream_id: FragmentStreamId,
+        conf: &StreamResumeConf,
+        resume_from: Option<u64>,
+    ) -> Result<Receiver<Result<FlightData, Status>>> {
+        let queries_coordinator_guard = self.queries_coordinator.lock();
+        let queries_coordinator = unsafe { &mut *queries_coordinator_guard.deref().get() };
+
+        if resume_from.is_none() {
+            self.check_not_shutting_down()?;
+            self.check_not_finished(&stream_id)?;
+        }
+
+        let target = stream_id.target.clone();
+        let fragment = stream_id.fragment;
+        match (
+            queries_coordinator.entry(stream_id.query_id.clone()),
+            resume_from,
+        ) {
+            (Entry::Occupied(v), Some(resume_from)) => v
+                .get()
+                .resume_fragment_exchange(target, fragment, resume_from)
+                .map_err(|cause| with_query_context(cause, &stream_id.query_id)),
+            (Entry::Vacant(_), Some(_)) => Err(ErrorCode::UnknownFragmentExchange(format!(
+                "Cannot resume the fragment stream {}, the query is finished or unknown",
+                stream_id
+            ))),
+            (Entry::Occupied(mut v), None) => v
+                .get_mut()
+                .add_resumable_fragment_exchange(target, fragment, conf),
+            (Entry::Vacant(v), None) => v
+                .insert(QueryCoordinator::create())
+                .add_resumable_fragment_exchange(target, fragment, conf),
+        }
+    }
+
+    /// The liveness of the consumer of a fragment stream handed out by this node, None if the
+    /// query is finished or unknown.
+    pub fn get_stream_liveness(&self, stream_id: &FragmentStreamId) -> Option<Arc<StreamLiveness>> {
+        let queries_coordinator_guard = self.queries_coordinator.lock();
+        let queries_coordinator = unsafe { &*queries_coordinator_guard.deref().get() };
+
+        queries_coordinator
+            .get(&stream_id.query_id)?
+            .stream_liveness
+            .get(&(stream_id.target.clone(), stream_id.fragment))
+            .cloned()
+    }
+
+    fn check_not_finished(&self, stream_id: &FragmentStreamId) -> Result<()> {
+        match self.finished_queries.lock().contains(stream_id) {
+            true => Err(ErrorCode::UnknownFragmentExchange(format!(
+                "The fragment stream {} is finished",
+                stream_id
+            ))),
+            false => Ok(()),
+        }
+    }
+
+    fn check_not_shutting_down(&self) -> Result<()> {
+        match self.shutting_down.load(Ordering::Acquire) {
+            true => Err(ErrorCode::ServerShuttingDown(
+                "The node is shutting down, it does not accept new queries or streams",
+            )),
+            false => Ok(()),
+        }
+    }
+
+    /// Shuts down the exchanges of this node.
+    ///
+    /// The new queries and streams are rejected with `ServerShuttingDown` from now on. The
+    /// running queries are waited until `timeout`, then the rest of them are aborted, their
+    /// streams are closed with `AbortedSession` instead of being reset with the connections.
+    #[async_backtrace::framed]
+    pub async fn shutdown(&self, timeout: Duration) {
+        self.shutting_down.store(true, Ordering::Release);
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let now = Instant::now();
+            if self.running_queries().is_empty() || now >= deadline {
+                break;
+            }
+            tokio::time::sleep((deadline - now).min(Duration::from_millis(100))).await;
+        }
+
+        for query_id in self.running_queries() {
+            self.kill_query_fragments(
+                &query_id,
+                ErrorCode::AbortedSession(format!(
+                    "Aborted query {}, the node is shutting down",
+                    query_id
+                )),
+            );
+        }
+    }
+
+    fn running_queries(&self) -> Vec<String> {
+        let queries_coordinator_guard = self.queries_coordinator.lock();
+        let queries_coordinator = unsafe { &*queries_coordinator_guard.deref().get() };
+        queries_coordinator.keys().cloned().collect()
+    }
+
     pub fn shutdown_query(&self, query_id: &str) {
         let queries_coordinator_guard = self.queries_coordinator.lock();
         let queries_coordinator = unsafe { &mut *queries_coordinator_guard.deref().get() };
@@ -278,17 +607,90 @@ impl DataExchangeManager {
         }
     }
 
+    /// Abort the fragments of a killed query on this node. The exchanges not yet
+    /// consumed by the pipelines are closed with `cause`.
+    ///
+    /// It's a no-op if the query is already finished on this node.
+    pub fn kill_query_fragments(&self, query_id: &str, cause: ErrorCode) {
+        let queries_coordinator_guard = self.queries_coordinator.lock();
+        let queries_coordinator = unsafe { &mut *queries_coordinator_guard.deref().get() };
+
+        if let Some(mut query_coordinator) = queries_coordinator.remove(query_id) {
+            let consumed_streams = std::mem::take(&mut query_coordinator.consumed_streams);
+            self.finished_queries
+                .lock()
+                .insert(query_id.to_string(), consumed_streams);
+            self.finished_stages.lock().extend(
+                query_coordinator.finished_stages(query_id, FinishedStageStatus::Cancelled),
+            );
+
+            // Drop mutex guard to avoid deadlock during shutdown,
+            drop(queries_coordinator_guard);
+
+            query_coordinator.abort_query(cause);
+            query_coordinator.on_finished();
+            self.release_stage(query_id);
+        }
+    }
+
+    /// Abort the queries prepared on this node by a closed session.
+    ///
+    /// The coordinators are removed with the fragments and streams of the queries, so they
+    /// are not leaked when the client is gone before the queries are finished. The queries
+    /// of the other sessions are untouched.
+    pub fn on_session_closed(&self, session_id: &str) {
+        let query_ids = {
+            let queries_coordinator_guard = self.queries_coordinator.lock();
+            let queries_coordinator = unsafe { &*queries_coordinator_guard.deref().get() };
+            queries_coordinator
+                .iter()
+                .filter(|(_, coordinator)| {
+                    coordinator
+                        .info
+                        .as_ref()
+                        .is_some_and(|info| info.query_ctx.get_current_session_id() == session_id)
+                })
+                .map(|(query_id, _)| query_id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        for query_id in query_ids {
+            self.kill_query_fragments(
+                &query_id,
+                ErrorCode::AbortedSession(format!(
+                    "Aborted query {}, the session {} is closed",
+                    query_id, session_id
+                )),
+            );
+        }
+    }
+
     #[minitrace::trace]
     pub fn on_finished_query(&self, query_id: &str) {
+        self.finish_query(query_id, FinishedStageStatus::Finished);
+    }
+
+    // The executed stages of the query are kept with `status`.
+    fn finish_query(&self, query_id: &str, status: FinishedStageStatus) {
         let queries_coordinator_guard = self.queries_coordinator.lock();
         let queries_coordinator = unsafe { &mut *queries_coordinator_guard.deref().get() };
 
         if let Some(mut query_coordinator) = queries_coordinator.remove(query_id) {
+            let consumed_streams = std::mem::take(&mut query_coordinator.consumed_streams);
+            self.finished_queries
+                .lock()
+                .insert(query_id.to_string(), consumed_streams);
+            self.finished_stages
+                .lock()
+                .extend(query_coordinator.finished_stages(query_id, status));
+
             // Drop mutex guard to avoid deadlock during shutdown,
             drop(queries_coordinator_guard);
 
             query_coordinator.shutdown_query();
             query_coordinator.on_finished();
+            self.release_stage(query_id);
+            remove_spill_dir(&self.get_spill_dir(query_id));
         }
     }
 
@@ -363,7 +765,8 @@ impl DataExchangeManager {
                     let mut statistics_receiver = statistics_receiver.lock();
 
                     statistics_receiver.shutdown(may_error.is_err());
-                    ctx.get_exchange_manager().on_finished_query(&query_id);
+                    ctx.get_exchange_manager()
+                        .finish_query(&query_id, stage_status(may_error.as_ref().err()));
                     statistics_receiver.wait_shutdown()?;
 
                     on_finished(may_error)?;
@@ -383,9 +786,12 @@ impl DataExchangeManager {
         let queries_coordinator_guard = self.queries_coordinator.lock();
         let queries_coordinator = unsafe { &mut *queries_coordinator_guard.deref().get() };
 
-        match queries_coordinator.get_mut(&params.get_query_id()) {
-            None => Err(ErrorCode::Internal("Query not exists.")),
-            Some(coordinator) => coordinator.get_flight_senders(params),
+        let query_id = params.get_query_id();
+        match queries_coordinator.get_mut(&query_id) {
+            None => Err(query_not_exists(&query_id)),
+            Some(coordinator) => coordinator
+                .get_flight_senders(params)
+                .map_err(|cause| with_query_context(cause, &query_id)),
         }
     }
 
@@ -396,9 +802,12 @@ impl DataExchangeManager {
         let queries_coordinator_guard = self.queries_coordinator.lock();
         let queries_coordinator = unsafe { &mut *queries_coordinator_guard.deref().get() };
 
-        match queries_coordinator.get_mut(&params.get_query_id()) {
-            None => Err(ErrorCode::Internal("Query not exists.")),
-            Some(coordinator) => coordinator.get_flight_receiver(params),
+        let query_id = params.get_query_id();
+        match queries_coordinator.get_mut(&query_id) {
+            None => Err(query_not_exists(&query_id)),
+            Some(coordinator) => coordinator
+                .get_flight_receiver(params)
+                .map_err(|cause| with_query_context(cause, &query_id)),
         }
     }
 
@@ -412,7 +821,7 @@ impl DataExchangeManager {
         let queries_coordinator = unsafe { &mut *queries_coordinator_guard.deref().get() };
 
         match queries_coordinator.get_mut(query_id) {
-            None => Err(ErrorCode::Internal("Query not exists.")),
+            None => Err(query_not_exists(query_id)),
             Some(query_coordinator) => {
                 let query_ctx = query_coordinator
                     .info
@@ -427,6 +836,39 @@ impl DataExchangeManager {
     }
 }
 
+// The errors of the exchanges are reported on the other nodes, the query id
+// correlates them with the logs of the query.
+fn with_query_context(cause: ErrorCode, query_id: &str) -> ErrorCode {
+    cause.add_message_back(format!("(query: {})", query_id))
+}
+
+// A stage ended by an abort is cancelled rather than failed.
+fn stage_status(error: Option<&ErrorCode>) -> FinishedStageStatus {
+    match error {
+        None => FinishedStageStatus::Finished,
+        Some(error)
+            if error.code() == ErrorCode::ABORTED_QUERY
+                || error.code() == ErrorCode::ABORTED_SESSION =>
+        {
+            FinishedStageStatus::Cancelled
+        }
+        Some(_) => FinishedStageStatus::Failed,
+    }
+}
+
+fn query_not_exists(query_id: &str) -> ErrorCode {
+    ErrorCode::Internal(format!("Query {} not exists.", query_id))
+}
+
+// Lists the streams as `target/fragment`, sorted.
+fn format_streams<'a>(streams: impl Iterator<Item = (&'a String, &'a usize)>) -> String {
+    let mut streams = streams
+        .map(|(target, fragment)| format!("{}/{}", target, fragment))
+        .collect::<Vec<_>>();
+    streams.sort();
+    format!("[{}]", streams.join(", "))
+}
+
 struct QueryInfo {
     query_id: String,
     current_executor: String,
@@ -440,9 +882,22 @@ static FLIGHT_RECEIVER: u8 = 2;
 struct QueryCoordinator {
     info: Option<QueryInfo>,
     fragments_coordinator: HashMap<usize, Box<FragmentCoordinator>>,
+    // All fragments prepared on this node, including those already merged into other fragments.
+    prepared_fragments: HashSet<usize>,
+    // The execution was requested while some fragments were still waiting for their sources.
+    pending_execute: bool,
 
     statistics_exchanges: HashMap<String, FlightExchange>,
     fragment_exchanges: HashMap<(String, usize, u8), FlightExchange>,
+    resumable_streams: HashMap<(String, usize), Arc<ResumableStream>>,
+    // The fragment streams handed out to the consumers, by target and fragment.
+    consumed_streams: HashSet<(String, usize)>,
+    stream_liveness: HashMap<(String, usize), Arc<StreamLiveness>>,
+    sent_statistics: HashMap<(String, usize), Arc<StreamSentStatistics>>,
+    // The fragments executed by this node and the time the execution started.
+    executed_fragments: Vec<usize>,
+    started: Option<Instant>,
+    started_time: Option<SystemTime>,
 }
 
 impl QueryCoordinator {
@@ -450,8 +905,17 @@ impl QueryCoordinator {
         QueryCoordinator {
             info: None,
             fragments_coordinator: HashMap::new(),
+            prepared_fragments: HashSet::new(),
+            pending_execute: false,
             fragment_exchanges: HashMap::new(),
             statistics_exchanges: HashMap::new(),
+            resumable_streams: HashMap::new(),
+            consumed_streams: HashSet::new(),
+            stream_liveness: HashMap::new(),
+            sent_statistics: HashMap::new(),
+            executed_fragments: vec![],
+            started: None,
+            started_time: None,
         }
     }
 
@@ -491,14 +955,81 @@ impl QueryCoordinator {
         target: String,
         fragment: usize,
     ) -> Result<Receiver<Result<FlightData, Status>>> {
+        self.consume_stream(&target, fragment)?;
         let (tx, rx) = async_channel::bounded(8);
+        let liveness = StreamLiveness::create();
+        let sent_statistics = StreamSentStatistics::create();
+        self.fragment_exchanges.insert(
+            (target.clone(), fragment, FLIGHT_SENDER),
+            FlightExchange::create_fragment_sender(tx, liveness.clone(), sent_statistics.clone()),
+        );
+        self.stream_liveness
+            .insert((target.clone(), fragment), liveness);
+        self.sent_statistics
+            .insert((target, fragment), sent_statistics);
+        Ok(rx)
+    }
+
+    pub fn add_resumable_fragment_exchange(
+        &mut self,
+        target: String,
+        fragment: usize,
+        conf: &StreamResumeConf,
+    ) -> Result<Receiver<Result<FlightData, Status>>> {
+        self.consume_stream(&target, fragment)?;
+        let timeout = Duration::from_secs(conf.timeout_secs);
+        let (stream, tx, rx) = ResumableStream::create(conf.retained_blocks, timeout);
+        let liveness = StreamLiveness::create();
+        let sent_statistics = StreamSentStatistics::create();
         self.fragment_exchanges.insert(
-            (target, fragment, FLIGHT_SENDER),
-            FlightExchange::create_sender(tx),
+            (target.clone(), fragment, FLIGHT_SENDER),
+            FlightExchange::create_fragment_sender(tx, liveness.clone(), sent_statistics.clone()),
         );
+        self.stream_liveness
+            .insert((target.clone(), fragment), liveness);
+        self.sent_statistics
+            .insert((target.clone(), fragment), sent_statistics);
+        self.resumable_streams.insert((target, fragment), stream);
         Ok(rx)
     }
 
+    // A second consumer would split the rows of the stream with the first one.
+    fn consume_stream(&mut self, target: &str, fragment: usize) -> Result<()> {
+        match self.consumed_streams.insert((target.to_string(), fragment)) {
+            true => Ok(()),
+            false => Err(ErrorCode::StreamAlreadyConsumed(format!(
+                "The stream of fragment {} to {} is already consumed",
+                fragment, target
+            ))),
+        }
+    }
+
+    pub fn resume_fragment_exchange(
+        &self,
+        target: String,
+        fragment: usize,
+        resume_from: u64,
+    ) -> Result<Receiver<Result<FlightData, Status>>> {
+        match self.resumable_streams.get(&(target, fragment)) {
+            Some(stream) => stream.resume(resume_from),
+            None => Err(ErrorCode::UnknownFragmentExchange(format!(
+                "Unknown resumable stream of fragment {}, available streams: {}",
+                fragment,
+                format_streams(self.resumable_streams.keys().map(|(t, f)| (t, f)))
+            ))),
+        }
+    }
+
+    // The sender or receiver streams which are not taken by the pipelines yet.
+    fn available_streams(&self, direction: u8) -> String {
+        format_streams(
+            self.fragment_exchanges
+                .keys()
+                .filter(|(_, _, d)| *d == direction)
+                .map(|(target, fragment, _)| (target, fragment)),
+        )
+    }
+
     pub fn add_fragment_exchanges(
         &mut self,
         exchanges: HashMap<(String, usize), FlightExchange>,
@@ -531,8 +1062,10 @@ impl QueryCoordinator {
                         )) {
                             Some(exchange_channel) => Ok(exchange_channel.convert_to_sender()),
                             None => Err(ErrorCode::UnknownFragmentExchange(format!(
-                                "Unknown fragment exchange channel, {}, {}",
-                                destination, params.fragment_id
+                                "Unknown fragment exchange channel, {}, {}, available streams: {}",
+                                destination,
+                                params.fragment_id,
+                                self.available_streams(FLIGHT_SENDER)
                             ))),
                         },
                     }?);
@@ -568,8 +1101,10 @@ impl QueryCoordinator {
                             )) {
                                 Some(v) => Ok(v.convert_to_receiver()),
                                 _ => Err(ErrorCode::UnknownFragmentExchange(format!(
-                                    "Unknown fragment flight receiver, {}, {}",
-                                    destination, params.fragment_id
+                                    "Unknown fragment flight receiver, {}, {}, available streams: {}",
+                                    destination,
+                                    params.fragment_id,
+                                    self.available_streams(FLIGHT_RECEIVER)
                                 ))),
                             },
                         }?,
@@ -586,22 +1121,68 @@ impl QueryCoordinator {
         ctx: &Arc<QueryContext>,
         packet: &QueryFragmentsPlanPacket,
     ) -> Result<()> {
-        self.info = Some(QueryInfo {
-            query_ctx: ctx.clone(),
-            query_id: packet.query_id.clone(),
-            current_executor: packet.executor.clone(),
-            query_executor: None,
-        });
+        for (index, fragment) in packet.fragments.iter().enumerate() {
+            let fragment_id = fragment.fragment_id;
+            if self.prepared_fragments.contains(&fragment_id)
+                || packet.fragments[..index]
+                    .iter()
+                    .any(|x| x.fragment_id == fragment_id)
+            {
+                return Err(ErrorCode::FragmentAlreadyExists(format!(
+                    "Fragment {} of query {} has already been prepared",
+                    fragment_id, packet.query_id
+                )));
+            }
+
+            if let Some(data_exchange) = &fragment.data_exchange {
+                data_exchange.check_schema(&fragment.physical_plan.output_schema()?)?;
+            }
+        }
+
+        if self.info.is_none() {
+            self.info = Some(QueryInfo {
+                query_ctx: ctx.clone(),
+                query_id: packet.query_id.clone(),
+                current_executor: packet.executor.clone(),
+                query_executor: None,
+            });
+        }
 
         for fragment in &packet.fragments {
+            self.prepared_fragments.insert(fragment.fragment_id);
             self.fragments_coordinator.insert(
                 fragment.fragment_id.to_owned(),
                 FragmentCoordinator::create(fragment),
             );
         }
 
-        for fragment in &packet.fragments {
-            let fragment_id = fragment.fragment_id;
+        self.prepare_ready_fragments(ctx)?;
+
+        if self.pending_execute && self.all_fragments_initialized() {
+            self.pending_execute = false;
+            return self.execute_pipeline();
+        }
+
+        Ok(())
+    }
+
+    // Build the pipelines of the fragments whose source fragments are all registered. Others
+    // are deferred until their sources are prepared on this node.
+    fn prepare_ready_fragments(&mut self, ctx: &Arc<QueryContext>) -> Result<()> {
+        let ready_fragments = self
+            .fragments_coordinator
+            .values()
+            .filter(|coordinator| !coordinator.initialized)
+            .filter(|coordinator| {
+                coordinator
+                    .source_fragments
+                    .iter()
+                    .all(|source| self.is_source_registered(*source))
+            })
+            .map(|coordinator| coordinator.fragment_id)
+            .collect::<Vec<_>>();
+
+        for fragment_id in ready_fragments {
             if let Some(coordinator) = self.fragments_coordinator.get_mut(&fragment_id) {
                 coordinator.prepare_pipeline(ctx.clone())?;
             }
@@ -610,6 +1191,22 @@ impl QueryCoordinator {
         Ok(())
     }
 
+    // A source fragment is registered if it is prepared locally,
+    // or its data is received from remote nodes.
+    fn is_source_registered(&self, fragment_id: usize) -> bool {
+        self.prepared_fragments.contains(&fragment_id)
+            || self
+                .fragment_exchanges
+                .keys()
+                .any(|(_, f, r)| *f == fragment_id && *r == FLIGHT_RECEIVER)
+    }
+
+    fn all_fragments_initialized(&self) -> bool {
+        self.fragments_coordinator
+            .values()
+            .all(|coordinator| coordinator.initialized)
+    }
+
     pub fn subscribe_fragment(
         &mut self,
         ctx: &Arc<QueryContext>,
@@ -667,16 +1264,113 @@ impl QueryCoordinator {
         }
     }
 
+    pub fn abort_query(&mut self, cause: ErrorCode) {
+        let exchanges = self
+            .fragment_exchanges
+            .drain()
+            .map(|(_, exchange)| exchange)
+            .chain(
+                self.statistics_exchanges
+                    .drain()
+                    .map(|(_, exchange)| exchange),
+            );
+
+        for exchange in exchanges {
+            if let FlightExchange::Sender { sender: tx, .. } = exchange {
+                let _ = tx.try_send(Err(Status::from(cause.clone())));
+                tx.close();
+            }
+        }
+
+        if let Some(query_info) = &self.info {
+            if let Some(query_executor) = &query_info.query_executor {
+                query_executor.finish(Some(cause));
+            }
+        }
+    }
+
     pub fn on_finished(self) {
         // Do something when query finished.
     }
 
+    // The summaries of the stages executed by this node, none if the execution never started.
+    fn finished_stages(
+        &self,
+        query_id: &str,
+        status: FinishedStageStatus,
+    ) -> Vec<FinishedStageInfo> {
+        let Some(started) = self.started else {
+            return vec![];
+        };
+
+        let duration = started.elapsed();
+        let finished_time = SystemTime::now();
+        self.executed_fragments
+            .iter()
+            .map(|fragment| {
+                let mut streams = self
+                    .sent_statistics
+                    .iter()
+                    .filter(|((_, f), _)| f == fragment)
+                    .map(|((target, _), statistics)| FinishedStreamInfo {
+                        target: target.clone(),
+                        sent_rows: statistics.sent_rows(),
+                        sent_bytes: statistics.sent_bytes(),
+                    })
+                    .collect::<Vec<_>>();
+                streams.sort_by(|a, b| a.target.cmp(&b.target));
+
+                FinishedStageInfo {
+                    query_id: query_id.to_string(),
+                    stage_id: *fragment,
+                    status,
+                    duration,
+                    finished_time,
+                    streams,
+                }
+            })
+            .collect()
+    }
+
+    // The stages prepared by this node, with the streams requested from them.
+    fn running_stages(&self, query_id: &str) -> Vec<RunningStageInfo> {
+        self.fragments_coordinator
+            .keys()
+            .map(|fragment| {
+                let mut streams = self
+                    .sent_statistics
+                    .iter()
+                    .filter(|((_, f), _)| f == fragment)
+                    .map(|((target, _), statistics)| RunningStreamInfo {
+                        target: target.clone(),
+                        sent_rows: statistics.sent_rows(),
+                        sent_bytes: statistics.sent_bytes(),
+                    })
+                    .collect::<Vec<_>>();
+                streams.sort_by(|a, b| a.target.cmp(&b.target));
+
+                RunningStageInfo {
+                    query_id: query_id.to_string(),
+                    stage_id: *fragment,
+                    started_time: self.started_time,
+                    streams,
+                }
+            })
+            .collect()
+    }
+
     pub fn execute_pipeline(&mut self) -> Result<()> {
         if self.fragments_coordinator.is_empty() {
             // Empty fragments if it is a request server, because the pipelines may have been linked.
             return Ok(());
         }
 
+        if !self.all_fragments_initialized() {
+            // Start the execution when the deferred fragments are prepared.
+            self.pending_execute = true;
+            return Ok(());
+        }
+
         let info = self.info.as_ref().expect("Query info is None");
 
         let max_threads = info.query_ctx.get_settings().get_max_threads()?;
@@ -743,8 +1437,15 @@ impl QueryCoordinator {
         let mut statistics_sender =
             StatisticsSender::spawn_sender(&query_id, ctx, request_server_exchange);
 
+        self.executed_fragments = self.fragments_coordinator.keys().copied().collect();
+        self.executed_fragments.sort();
+        self.started = Some(Instant::now());
+        self.started_time = Some(SystemTime::now());
+
         let span = if let Some(parent) = SpanContext::current_local_parent() {
             Span::root("Distributed-Executor", parent)
+                .with_property(|| ("query_id", query_id.clone()))
+                .with_property(|| ("stage_ids", format!("{:?}", self.executed_fragments)))
         } else {
             Span::noop()
         };
@@ -752,11 +1453,12 @@ impl QueryCoordinator {
         Thread::named_spawn(Some(String::from("Distributed-Executor")), move || {
             let _g = span.set_local_parent();
             let res = executor.execute().err();
+            let status = stage_status(res.as_ref());
             let profiles = executor.get_inner().get_profiles();
             statistics_sender.shutdown(res, profiles);
             query_ctx
                 .get_exchange_manager()
-                .on_finished_query(&query_id);
+                .finish_query(&query_id, status);
         });
 
         Ok(())
@@ -766,6 +1468,7 @@ impl QueryCoordinator {
 struct FragmentCoordinator {
     initialized: bool,
     fragment_id: usize,
+    source_fragments: Vec<usize>,
     physical_plan: PhysicalPlan,
     data_exchange: Option<DataExchange>,
     pipeline_build_res: Option<PipelineBuildResult>,
@@ -777,6 +1480,7 @@ impl FragmentCoordinator {
             initialized: false,
             physical_plan: packet.physical_plan.clone(),
             fragment_id: packet.fragment_id,
+            source_fragments: packet.source_fragments.clone(),
             data_exchange: packet.data_exchange.clone(),
             pipeline_build_res: None,
         })
@@ -798,6 +1502,7 @@ impl FragmentCoordinator {
                         destination_id: exchange.destination_id.clone(),
                         allow_adjust_parallelism: exchange.allow_adjust_parallelism,
                         ignore_exchange: exchange.ignore_exchange,
+                        sort_desc: exchange.sort_desc.clone().map(Arc::new),
                     }))
                 }
                 DataExchange::Broadcast(exchange) => {
@@ -835,15 +1540,38 @@ impl FragmentCoordinator {
             self.initialized = true;
 
             let pipeline_ctx = QueryContext::create_from(ctx);
+            let settings = pipeline_ctx.get_settings();
+            let cache = pipeline_ctx
+                .get_exchange_manager()
+                .get_fragment_result_cache();
+            let cache_key = FragmentResultCache::cache_key(&pipeline_ctx, &self.physical_plan)?;
+
+            if let Some(key) = &cache_key {
+                if let Some(blocks) = cache.get(key, &settings)? {
+                    let res =
+                        FragmentResultCache::create_cached_pipeline(&self.physical_plan, blocks)?;
+                    self.pipeline_build_res = Some(res);
+                    return Ok(());
+                }
+            }
 
             let pipeline_builder = PipelineBuilder::create(
                 pipeline_ctx.get_function_context()?,
-                pipeline_ctx.get_settings(),
+                settings.clone(),
                 pipeline_ctx,
                 vec![],
             );
 
-            let res = pipeline_builder.finalize(&self.physical_plan)?;
+            let mut res = pipeline_builder.finalize(&self.physical_plan)?;
+
+            if let Some(key) = cache_key {
+                cache.collect_result(
+                    key,
+                    &settings,
+                    &self.physical_plan,
+                    &mut res.main_pipeline,
+                )?;
+            }
 
             self.pipeline_build_res = Some(res);
         }
@@ -851,3 +1579,83 @@ impl FragmentCoordinator {
         Ok(())
     }
 }
+
+/// A partial query prepared on this node, see `DataExchangeManager::get_running_stages`.
+#[derive(Debug, Clone)]
+pub struct RunningStageInfo {
+    pub query_id: String,
+    pub stage_id: usize,
+    /// The time the execution started, none if the stage is not executed yet.
+    pub started_time: Option<SystemTime>,
+    pub streams: Vec<RunningStreamInfo>,
+}
+
+/// A stream requested from a running stage by another node, with the data sent so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunningStreamInfo {
+    pub target: String,
+    pub sent_rows: usize,
+    pub sent_bytes: usize,
+}
+
+// The partial queries of this node, see `DataExchangeManager::execute_partial_query`.
+#[derive(Default)]
+struct Stages {
+    running: HashSet<String>,
+    queued: VecDeque<String>,
+}
+
+// The summaries of the recently finished stages, the oldest stage is forgotten first.
+struct FinishedStages {
+    stages: VecDeque<FinishedStageInfo>,
+    capacity: usize,
+}
+
+impl FinishedStages {
+    fn create(capacity: usize) -> FinishedStages {
+        FinishedStages {
+            stages: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    fn extend(&mut self, stages: Vec<FinishedStageInfo>) {
+        self.stages.extend(stages);
+        while self.stages.len() > self.capacity {
+            self.stages.pop_front();
+        }
+    }
+}
+
+// The consumed streams of the recently finished queries, the oldest query is forgotten first.
+#[derive(Default)]
+struct FinishedQueries {
+    streams: HashMap<String, HashSet<(String, usize)>>,
+    order: VecDeque<String>,
+}
+
+impl FinishedQueries {
+    fn insert(&mut self, query_id: String, streams: HashSet<(String, usize)>) {
+        match self.streams.entry(query_id) {
+            Entry::Occupied(mut v) => v.get_mut().extend(streams),
+            Entry::Vacant(v) => {
+                self.order.push_back(v.key().clone());
+                v.insert(streams);
+            }
+        }
+
+        while self.order.len() > MAX_FINISHED_QUERIES {
+            if let Some(query_id) = self.order.pop_front() {
+                self.streams.remove(&query_id);
+            }
+        }
+    }
+
+    fn contains(&self, stream_id: &FragmentStreamId) -> bool {
+        self.streams
+            .get(&stream_id.query_id)
+            .is_some_and(|streams| {
+                streams.contains(&(stream_id.target.clone(), stream_id.fragment))
+            })
+    }
+}
@@ -18,6 +18,7 @@ use databend_common_arrow::arrow::io::ipc::write::WriteOptions;
 use databend_common_arrow::arrow::io::ipc::IpcField;
 use databend_common_expression::DataSchemaRef;
 
+use crate::api::rpc::exchange::data_exchange::ExchangeSortDesc;
 use crate::api::rpc::flight_scatter::FlightScatter;
 use crate::api::ExchangeInjector;
 
@@ -47,6 +48,7 @@ pub struct MergeExchangeParams {
     pub schema: DataSchemaRef,
     pub ignore_exchange: bool,
     pub allow_adjust_parallelism: bool,
+    pub sort_desc: Option<Arc<Vec<ExchangeSortDesc>>>,
     pub exchange_injector: Arc<dyn ExchangeInjector>,
 }
 
@@ -25,6 +25,7 @@ use databend_common_pipeline_core::Pipeline;
 
 use crate::api::rpc::exchange::exchange_params::ExchangeParams;
 use crate::api::rpc::exchange::exchange_sink_writer::create_writer_item;
+use crate::api::rpc::exchange::exchange_sort_bounds::TransformSortBounds;
 use crate::api::rpc::exchange::exchange_sorting::ExchangeSorting;
 use crate::api::rpc::exchange::exchange_sorting::TransformExchangeSorting;
 use crate::api::rpc::exchange::exchange_transform_shuffle::exchange_shuffle;
@@ -58,6 +59,16 @@ impl ExchangeSink {
                 let exchange_injector = &params.exchange_injector;
 
                 if !params.ignore_exchange {
+                    if let Some(sort_desc) = &params.sort_desc {
+                        pipeline.add_transform(|input, output| {
+                            Ok(TransformSortBounds::create(
+                                input,
+                                output,
+                                sort_desc.clone(),
+                            ))
+                        })?;
+                    }
+
                     let settings = ctx.get_settings();
                     let compression = settings.get_query_flight_compression()?;
                     exchange_injector.apply_merge_serializer(params, compression, pipeline)?;
@@ -86,7 +97,7 @@ impl ExchangeSink {
                     &params.destination_id,
                     params.fragment_id,
                     &ctx.get_cluster().local_id(),
-                )]));
+                )?]));
                 Ok(())
             }
             ExchangeParams::ShuffleExchange(params) => {
@@ -104,7 +115,7 @@ impl ExchangeSink {
                         destination_id,
                         params.fragment_id,
                         &ctx.get_cluster().local_id(),
-                    ));
+                    )?);
                 }
 
                 pipeline.add_pipe(Pipe::create(len, 0, items));
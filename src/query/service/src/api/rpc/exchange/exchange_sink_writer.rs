@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use databend_common_base::runtime::profile::Profile;
 use databend_common_base::runtime::profile::ProfileStatisticsName;
@@ -30,8 +31,11 @@ use databend_common_pipeline_sinks::AsyncSink;
 use databend_common_pipeline_sinks::AsyncSinker;
 use databend_common_pipeline_sinks::Sink;
 use databend_common_pipeline_sinks::Sinker;
+use log::warn;
 
 use crate::api::rpc::flight_client::FlightSender;
+use crate::api::rpc::flight_client::StreamLagLimits;
+use crate::api::rpc::StreamSpillConf;
 use crate::api::ExchangeSerializeMeta;
 use crate::sessions::QueryContext;
 
@@ -91,6 +95,16 @@ impl AsyncSink for ExchangeWriterSink {
                     return Ok(true);
                 }
 
+                // Only the stream of the lost consumer is cancelled, the other streams of the
+                // fragment keep going.
+                if error.code() == ErrorCode::EXCHANGE_CONSUMER_TIMEOUT {
+                    warn!(
+                        "Cancel the exchange stream of fragment {} to {}: {}",
+                        self.fragment, self.destination, error
+                    );
+                    return Ok(true);
+                }
+
                 return Err(error);
             }
         }
@@ -151,15 +165,32 @@ pub fn create_writer_item(
     destination_id: &str,
     fragment_id: usize,
     source_id: &str,
-) -> PipeItem {
+) -> Result<PipeItem> {
+    let settings = ctx.get_settings();
+    let exchange = exchange.with_lag_limits(StreamLagLimits {
+        max_blocks: settings.get_max_stream_lag_blocks()?,
+        max_duration: Duration::from_secs(settings.get_max_stream_lag_seconds()?),
+    });
+    let exchange = exchange.with_idle_timeout(Duration::from_secs(
+        settings.get_stream_idle_timeout_secs()?,
+    ));
+    let exchange = exchange.with_spill(StreamSpillConf {
+        max_memory_bytes: settings.get_exchange_spilling_bytes_threshold()?,
+        spill_dir: ctx.get_exchange_manager().get_spill_dir(&ctx.get_id()),
+    });
+
     let input = InputPort::create();
-    PipeItem::create(
+    Ok(PipeItem::create(
         match ignore {
             true => ProcessorPtr::create(IgnoreExchangeSink::create(input.clone(), exchange)),
             false => ProcessorPtr::create(ExchangeWriterSink::create(
-                ctx,
+                ctx.clone(),
                 input.clone(),
-                exchange,
+                exchange.with_metrics(ExchangeStreamMetrics::create(
+                    &ctx.get_id(),
+                    fragment_id,
+                    destination_id,
+                )),
                 source_id,
                 destination_id,
                 fragment_id,
@@ -167,5 +198,5 @@ pub fn create_writer_item(
         },
         vec![input],
         vec![],
-    )
+    ))
 }
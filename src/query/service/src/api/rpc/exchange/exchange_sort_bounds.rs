@@ -0,0 +1,149 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::BlockMetaInfo;
+use databend_common_expression::BlockMetaInfoDowncast;
+use databend_common_expression::BlockMetaInfoPtr;
+use databend_common_expression::DataBlock;
+use databend_common_expression::Scalar;
+use databend_common_expression::ScalarRef;
+use databend_common_pipeline_core::processors::InputPort;
+use databend_common_pipeline_core::processors::OutputPort;
+use databend_common_pipeline_core::processors::ProcessorPtr;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_common_pipeline_transforms::processors::Transformer;
+
+use crate::api::rpc::exchange::data_exchange::ExchangeSortDesc;
+
+/// The first and the last sort key of a sorted block. It's sent in the meta of the block,
+/// which the receiver reads without decoding the columns.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct SortBoundsMeta {
+    pub min: Vec<Scalar>,
+    pub max: Vec<Scalar>,
+}
+
+impl SortBoundsMeta {
+    pub fn create(min: Vec<Scalar>, max: Vec<Scalar>) -> BlockMetaInfoPtr {
+        Box::new(SortBoundsMeta { min, max })
+    }
+}
+
+#[typetag::serde(name = "exchange_sort_bounds")]
+impl BlockMetaInfo for SortBoundsMeta {
+    fn equals(&self, info: &Box<dyn BlockMetaInfo>) -> bool {
+        SortBoundsMeta::downcast_ref_from(info).is_some_and(|other| self == other)
+    }
+
+    fn clone_self(&self) -> Box<dyn BlockMetaInfo> {
+        Box::new(self.clone())
+    }
+}
+
+/// The sort key of a row of the block.
+pub fn row_sort_key<'a>(
+    data_block: &'a DataBlock,
+    row: usize,
+    sort_desc: &[ExchangeSortDesc],
+) -> Vec<ScalarRef<'a>> {
+    sort_desc
+        .iter()
+        .map(|desc| {
+            let column = data_block.get_by_offset(desc.offset);
+            column.value.index(row).unwrap()
+        })
+        .collect()
+}
+
+/// Compares the sort keys in the order of the sort, NULLs go first or last as it says.
+pub fn compare_sort_keys(
+    lhs: &[ScalarRef],
+    rhs: &[ScalarRef],
+    sort_desc: &[ExchangeSortDesc],
+) -> Ordering {
+    for ((lhs, rhs), desc) in lhs.iter().zip(rhs).zip(sort_desc) {
+        let ordering = match (lhs, rhs) {
+            (ScalarRef::Null, ScalarRef::Null) => Ordering::Equal,
+            (ScalarRef::Null, _) if desc.nulls_first => Ordering::Less,
+            (ScalarRef::Null, _) => Ordering::Greater,
+            (_, ScalarRef::Null) if desc.nulls_first => Ordering::Greater,
+            (_, ScalarRef::Null) => Ordering::Less,
+            (lhs, rhs) if desc.asc => lhs.cmp(rhs),
+            (lhs, rhs) => rhs.cmp(lhs),
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    Ordering::Equal
+}
+
+/// Attaches the [`SortBoundsMeta`] to each block of a sorted merge exchange before it's
+/// serialized. The blocks are sorted by the plan, so the bounds are the first and the last row.
+pub struct TransformSortBounds {
+    sort_desc: Arc<Vec<ExchangeSortDesc>>,
+}
+
+impl TransformSortBounds {
+    pub fn create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        sort_desc: Arc<Vec<ExchangeSortDesc>>,
+    ) -> ProcessorPtr {
+        ProcessorPtr::create(Transformer::create(input, output, TransformSortBounds {
+            sort_desc,
+        }))
+    }
+
+    pub fn attach_bounds(
+        data_block: DataBlock,
+        sort_desc: &[ExchangeSortDesc],
+    ) -> Result<DataBlock> {
+        // Empty blocks are not sent.
+        if data_block.is_empty() {
+            return Ok(data_block);
+        }
+
+        if data_block.get_meta().is_some() {
+            return Err(ErrorCode::Internal(
+                "The blocks of a sorted merge exchange must not have meta",
+            ));
+        }
+
+        let to_owned =
+            |key: Vec<ScalarRef>| -> Vec<Scalar> { key.iter().map(ScalarRef::to_owned).collect() };
+        let min = to_owned(row_sort_key(&data_block, 0, sort_desc));
+        let max = to_owned(row_sort_key(
+            &data_block,
+            data_block.num_rows() - 1,
+            sort_desc,
+        ));
+        data_block.add_meta(Some(SortBoundsMeta::create(min, max)))
+    }
+}
+
+impl Transform for TransformSortBounds {
+    const NAME: &'static str = "TransformSortBounds";
+
+    fn transform(&mut self, data_block: DataBlock) -> Result<DataBlock> {
+        Self::attach_bounds(data_block, &self.sort_desc)
+    }
+}
@@ -0,0 +1,217 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+
+use databend_common_arrow::arrow_format::flight::data::FlightData;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::BlockMetaInfoDowncast;
+use databend_common_expression::BlockMetaInfoPtr;
+use databend_common_expression::DataBlock;
+use databend_common_expression::DataSchemaRef;
+use databend_common_expression::Scalar;
+use databend_common_expression::ScalarRef;
+use databend_common_io::prelude::bincode_deserialize_from_slice;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+
+use crate::api::rpc::exchange::data_exchange::ExchangeSortDesc;
+use crate::api::rpc::exchange::exchange_sort_bounds::compare_sort_keys;
+use crate::api::rpc::exchange::exchange_sort_bounds::row_sort_key;
+use crate::api::rpc::exchange::exchange_sort_bounds::SortBoundsMeta;
+use crate::api::rpc::exchange::serde::exchange_deserializer::TransformExchangeDeserializer;
+use crate::api::DataPacket;
+use crate::api::FragmentData;
+
+// A block received but not decoded yet.
+struct PendingBlock {
+    dictionaries: Vec<DataPacket>,
+    fragment: FragmentData,
+    min: Vec<Scalar>,
+}
+
+struct MergeInput {
+    stream: BoxStream<'static, Result<FlightData>>,
+    finished: bool,
+    pending: Option<PendingBlock>,
+    // The decoded block and its next row.
+    cursor: Option<(DataBlock, usize)>,
+}
+
+impl MergeInput {
+    // Receives the next block of the stream, only the sort bounds in its meta are read.
+    async fn receive(&mut self) -> Result<()> {
+        let mut dictionaries = vec![];
+        while let Some(flight_data) = self.stream.next().await {
+            match DataPacket::try_from(flight_data?)? {
+                DataPacket::ErrorCode(error) => return Err(error),
+                DataPacket::Dictionary(data) => dictionaries.push(DataPacket::Dictionary(data)),
+                DataPacket::FragmentData(fragment) => {
+                    let min = read_sort_bounds(&fragment)?.min;
+                    self.pending = Some(PendingBlock {
+                        dictionaries,
+                        fragment,
+                        min,
+                    });
+                    return Ok(());
+                }
+                // The statistics of the stream are not needed by the merge.
+                _ => continue,
+            }
+        }
+
+        self.finished = true;
+        Ok(())
+    }
+}
+
+fn read_sort_bounds(fragment: &FragmentData) -> Result<SortBoundsMeta> {
+    const ROW_HEADER_SIZE: usize = std::mem::size_of::<u32>();
+
+    let meta: Option<BlockMetaInfoPtr> =
+        bincode_deserialize_from_slice(&fragment.get_meta()[ROW_HEADER_SIZE..])
+            .map_err(|_| ErrorCode::BadBytes("block meta deserialize error when exchange"))?;
+    meta.and_then(SortBoundsMeta::downcast_from).ok_or_else(|| {
+        ErrorCode::Internal("The block of a sorted merge exchange has no sort bounds")
+    })
+}
+
+/// Merges the streams of a sorted merge exchange into a sorted stream of blocks. Each stream
+/// is sorted, and its blocks carry their [`SortBoundsMeta`], so a block is only decoded once
+/// its first row is the next one of the result. With a limit, the blocks past the limit are
+/// never decoded.
+pub struct SortedExchangeMerge {
+    deserializer: TransformExchangeDeserializer,
+    sort_desc: Vec<ExchangeSortDesc>,
+    inputs: Vec<MergeInput>,
+    limit: Option<usize>,
+    output_rows: usize,
+    decoded_blocks: usize,
+}
+
+impl SortedExchangeMerge {
+    pub fn create(
+        schema: &DataSchemaRef,
+        sort_desc: Vec<ExchangeSortDesc>,
+        streams: Vec<BoxStream<'static, Result<FlightData>>>,
+        limit: Option<usize>,
+    ) -> SortedExchangeMerge {
+        let inputs = streams
+            .into_iter()
+            .map(|stream| MergeInput {
+                stream,
+                finished: false,
+                pending: None,
+                cursor: None,
+            })
+            .collect();
+
+        SortedExchangeMerge {
+            deserializer: TransformExchangeDeserializer::new(schema),
+            sort_desc,
+            inputs,
+            limit,
+            output_rows: 0,
+            decoded_blocks: 0,
+        }
+    }
+
+    /// The number of the blocks decoded so far.
+    pub fn decoded_blocks(&self) -> usize {
+        self.decoded_blocks
+    }
+
+    /// Returns the next sorted block, a slice of a received block.
+    #[async_backtrace::framed]
+    pub async fn next_block(&mut self) -> Result<Option<DataBlock>> {
+        loop {
+            if self.limit.is_some_and(|limit| self.output_rows >= limit) {
+                return Ok(None);
+            }
+
+            for input in self.inputs.iter_mut() {
+                if input.cursor.is_none() && input.pending.is_none() && !input.finished {
+                    input.receive().await?;
+                }
+            }
+
+            // The smallest head of the inputs and the second smallest one, the head is the
+            // next row of the decoded block, or the min of the received block.
+            let mut first: Option<(usize, Vec<ScalarRef>)> = None;
+            let mut second: Option<Vec<ScalarRef>> = None;
+            for (index, input) in self.inputs.iter().enumerate() {
+                let head = match (&input.cursor, &input.pending) {
+                    (Some((block, row)), _) => row_sort_key(block, *row, &self.sort_desc),
+                    (None, Some(pending)) => pending.min.iter().map(Scalar::as_ref).collect(),
+                    (None, None) => continue,
+                };
+
+                let less_than = |other: &[ScalarRef]| {
+                    compare_sort_keys(&head, other, &self.sort_desc) == Ordering::Less
+                };
+                if first
+                    .as_ref()
+                    .map_or(true, |(_, min)| less_than(min.as_slice()))
+                {
+                    second = first.replace((index, head)).map(|(_, head)| head);
+                } else if second.as_deref().map_or(true, less_than) {
+                    second = Some(head);
+                }
+            }
+
+            let Some((index, _)) = first else {
+                return Ok(None);
+            };
+            let bound: Option<Vec<Scalar>> =
+                second.map(|bound| bound.iter().map(ScalarRef::to_owned).collect());
+
+            let input = &mut self.inputs[index];
+            let Some((block, start)) = input.cursor.take() else {
+                let pending = input.pending.take().unwrap();
+                let block = self
+                    .deserializer
+                    .recv_data(pending.dictionaries, pending.fragment)?;
+                self.decoded_blocks += 1;
+                input.cursor = Some((block, 0));
+                continue;
+            };
+
+            // Takes the rows up to the head of the other inputs.
+            let end = match &bound {
+                None => block.num_rows(),
+                Some(bound) => {
+                    let bound = bound.iter().map(Scalar::as_ref).collect::<Vec<_>>();
+                    (start + 1..block.num_rows())
+                        .find(|row| {
+                            let key = row_sort_key(&block, *row, &self.sort_desc);
+                            compare_sort_keys(&key, &bound, &self.sort_desc) == Ordering::Greater
+                        })
+                        .unwrap_or(block.num_rows())
+                }
+            };
+            let end = match self.limit {
+                Some(limit) => end.min(start + limit - self.output_rows),
+                None => end,
+            };
+
+            let output = block.slice(start..end);
+            if end < block.num_rows() {
+                input.cursor = Some((block, end));
+            }
+            self.output_rows += output.num_rows();
+            return Ok(Some(output));
+        }
+    }
+}
@@ -18,6 +18,7 @@ use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use databend_common_exception::Result;
+use databend_common_expression::block_origin;
 use databend_common_expression::DataBlock;
 use databend_common_metrics::transform::*;
 use databend_common_pipeline_core::processors::Event;
@@ -40,6 +41,9 @@ pub struct ExchangeSourceReader {
     source: String,
     destination: String,
     fragment: usize,
+    /// Number of the data packets received, the origin of a received block is derived from
+    /// the source, the fragment and this sequence.
+    sequence: u64,
 }
 
 impl ExchangeSourceReader {
@@ -58,6 +62,7 @@ impl ExchangeSourceReader {
             finished: AtomicBool::new(false),
             output_data: vec![],
             fragment,
+            sequence: 0,
         }))
     }
 }
@@ -92,7 +97,10 @@ impl Processor for ExchangeSourceReader {
 
         if !self.output_data.is_empty() {
             let packets = std::mem::take(&mut self.output_data);
-            let exchange_source_meta = ExchangeDeserializeMeta::create(packets);
+            let source_key = format!("{}/{}", self.source, self.fragment);
+            let origin = block_origin(source_key.as_bytes(), self.sequence);
+            self.sequence += 1;
+            let exchange_source_meta = ExchangeDeserializeMeta::create(packets, origin);
             self.output
                 .push_data(Ok(DataBlock::empty_with_meta(exchange_source_meta)));
         }
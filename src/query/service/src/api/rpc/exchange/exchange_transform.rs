@@ -67,7 +67,7 @@ impl ExchangeTransform {
                             destination_id,
                             params.fragment_id,
                             &ctx.get_cluster().local_id(),
-                        ),
+                        )?,
                     });
                 }
 
@@ -396,6 +396,12 @@ impl ExchangeShuffleTransform {
 }
 
 // Scatter the data block and push it to the corresponding output port
+/// Scatters the blocks of the pipeline to the destinations of the shuffle exchange.
+///
+/// Each output of the pipeline scatters its own blocks, so the blocks are scattered by as many
+/// workers as the pipeline has outputs. The scattered blocks are merged into one output for each
+/// destination, an output is finished after all the inputs are finished. The blocks of a
+/// destination are not in order.
 pub fn exchange_shuffle(
     ctx: &Arc<QueryContext>,
     params: &ShuffleExchangeParams,
@@ -0,0 +1,488 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use databend_common_base::runtime::profile::Profile;
+use databend_common_base::runtime::profile::ProfileLabel;
+use databend_common_base::runtime::profile::ProfileStatisticsName;
+use databend_common_catalog::plan::DataSourceInfo;
+use databend_common_catalog::table_context::TableContext;
+use databend_common_exception::Result;
+use databend_common_expression::DataBlock;
+use databend_common_metrics::cache::*;
+use databend_common_pipeline_core::processors::Event;
+use databend_common_pipeline_core::processors::InputPort;
+use databend_common_pipeline_core::processors::OutputPort;
+use databend_common_pipeline_core::processors::PlanScope;
+use databend_common_pipeline_core::processors::Processor;
+use databend_common_pipeline_core::processors::ProcessorPtr;
+use databend_common_pipeline_core::Pipeline;
+use databend_common_settings::Settings;
+use databend_common_sql::executor::PhysicalPlan;
+use databend_storages_common_table_meta::table::OPT_KEY_SNAPSHOT_LOCATION;
+use highway::HighwayHash;
+use highway::PortableHash;
+use parking_lot::Mutex;
+
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+
+const FRAGMENT_RESULT_CACHE_NAME: &str = "fragment_result_cache";
+
+/// Node-local cache of the results of deterministic query fragments.
+///
+/// A result is keyed by the content hash of the fragment plan, the versions of the tables it
+/// reads and the settings affecting the evaluation of expressions. Any change of the tables
+/// changes the key, so the results of the old versions are never served again. They are
+/// evicted when expired or when the cache is full.
+pub struct FragmentResultCache {
+    state: Mutex<CacheState>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<String, CachedResult>,
+    bytes: usize,
+}
+
+struct CachedResult {
+    blocks: Arc<Vec<DataBlock>>,
+    bytes: usize,
+    created_on: Instant,
+}
+
+impl FragmentResultCache {
+    pub fn create() -> Arc<FragmentResultCache> {
+        Arc::new(FragmentResultCache {
+            state: Mutex::new(CacheState::default()),
+        })
+    }
+
+    /// The cache key of the fragment plan, None if its result can't be cached.
+    pub fn cache_key(ctx: &QueryContext, plan: &PhysicalPlan) -> Result<Option<String>> {
+        let settings = ctx.get_settings();
+        if !settings.get_enable_fragment_result_cache()? {
+            return Ok(None);
+        }
+
+        // The sink carries the query id, the data exchange is not part of the cached result.
+        let plan = match plan {
+            PhysicalPlan::ExchangeSink(sink) => sink.input.as_ref(),
+            plan => plan,
+        };
+
+        // The partial aggregation outputs the aggregate states as block meta, which can't be cached.
+        if matches!(plan, PhysicalPlan::AggregatePartial(_)) || !plan.is_deterministic() {
+            return Ok(None);
+        }
+
+        let mut versions = vec![];
+        if !collect_table_versions(plan, &mut versions) {
+            return Ok(None);
+        }
+
+        let mut hasher = PortableHash::default();
+        hasher.append(&serde_json::to_vec(plan)?);
+        for (table_id, seq, snapshot_location) in versions {
+            hasher.append(&table_id.to_le_bytes());
+            hasher.append(&seq.to_le_bytes());
+            hasher.append(snapshot_location.as_bytes());
+        }
+        hasher.append(settings.get_timezone()?.as_bytes());
+        hasher.append(settings.get_numeric_cast_option()?.as_bytes());
+        hasher.append(&[settings.get_disable_variant_check()? as u8]);
+
+        let [hash_high, hash_low] = hasher.finalize128();
+        Ok(Some(format!("{:016x}{:016x}", hash_high, hash_low)))
+    }
+
+    pub fn get(&self, key: &str, settings: &Settings) -> Result<Option<Arc<Vec<DataBlock>>>> {
+        let ttl = Duration::from_secs(settings.get_fragment_result_cache_ttl_secs()?);
+        let mut state = self.state.lock();
+        state.evict_expired(ttl);
+
+        metrics_inc_cache_access_count(1, FRAGMENT_RESULT_CACHE_NAME);
+        match state.entries.get(key) {
+            None => {
+                metrics_inc_cache_miss_count(1, FRAGMENT_RESULT_CACHE_NAME);
+                Ok(None)
+            }
+            Some(result) => {
+                metrics_inc_cache_hit_count(1, FRAGMENT_RESULT_CACHE_NAME);
+                Ok(Some(result.blocks.clone()))
+            }
+        }
+    }
+
+    pub fn put(&self, key: String, blocks: Vec<DataBlock>, max_bytes: usize, ttl: Duration) {
+        let bytes = blocks
+            .iter()
+            .map(|block| block.memory_size())
+            .sum::<usize>();
+        if bytes > max_bytes {
+            return;
+        }
+
+        let mut state = self.state.lock();
+        state.evict_expired(ttl);
+        state.remove(&key);
+
+        // Evict the oldest results, which are also the first to expire.
+        while state.bytes + bytes > max_bytes {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, result)| result.created_on)
+                .map(|(key, _)| key.clone());
+
+            match oldest {
+                None => break,
+                Some(oldest) => state.remove(&oldest),
+            }
+        }
+
+        state.bytes += bytes;
+        state.entries.insert(key, CachedResult {
+            blocks: Arc::new(blocks),
+            bytes,
+            created_on: Instant::now(),
+        });
+    }
+
+    /// Create the pipeline serving the cached result of the fragment plan.
+    pub fn create_cached_pipeline(
+        plan: &PhysicalPlan,
+        blocks: Arc<Vec<DataBlock>>,
+    ) -> Result<PipelineBuildResult> {
+        let mut build_res = PipelineBuildResult::create();
+        let _guard = build_res.main_pipeline.add_plan_scope(plan_scope(plan)?);
+        build_res.main_pipeline.add_source(
+            |output| FragmentResultCacheSource::create(output, blocks.clone()),
+            1,
+        )?;
+        Ok(build_res)
+    }
+
+    /// Collect the result of the fragment pipeline into the cache once the pipeline
+    /// finished with its whole input.
+    pub fn collect_result(
+        self: &Arc<Self>,
+        key: String,
+        settings: &Settings,
+        plan: &PhysicalPlan,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        let collector = Arc::new(FragmentResultCollector {
+            key,
+            cache: self.clone(),
+            max_bytes: settings.get_fragment_result_cache_max_bytes()?,
+            ttl: Duration::from_secs(settings.get_fragment_result_cache_ttl_secs()?),
+            recorded_miss: AtomicBool::new(false),
+            state: Mutex::new(CollectorState {
+                blocks: vec![],
+                bytes: 0,
+                running: pipeline.output_len(),
+                abandoned: false,
+            }),
+        });
+
+        let _guard = pipeline.add_plan_scope(plan_scope(plan)?);
+        pipeline.add_transform(|input, output| {
+            Ok(TransformCollectFragmentResult::create(
+                input,
+                output,
+                collector.clone(),
+            ))
+        })
+    }
+}
+
+impl CacheState {
+    fn remove(&mut self, key: &str) {
+        if let Some(result) = self.entries.remove(key) {
+            self.bytes -= result.bytes;
+        }
+    }
+
+    fn evict_expired(&mut self, ttl: Duration) {
+        let expired = self
+            .entries
+            .iter()
+            .filter(|(_, result)| result.created_on.elapsed() > ttl)
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+
+        for key in expired {
+            self.remove(&key);
+        }
+    }
+}
+
+// Collect the versions of the tables read by the plan. Returns false if any of them is not
+// a fuse table, whose version is unknown.
+fn collect_table_versions(plan: &PhysicalPlan, versions: &mut Vec<(u64, u64, String)>) -> bool {
+    if let PhysicalPlan::TableScan(scan) = plan {
+        return match &scan.source.source_info {
+            DataSourceInfo::TableSource(info) if info.engine().eq_ignore_ascii_case("FUSE") => {
+                let snapshot_location = info
+                    .options()
+                    .get(OPT_KEY_SNAPSHOT_LOCATION)
+                    .cloned()
+                    .unwrap_or_default();
+                versions.push((info.ident.table_id, info.ident.seq, snapshot_location));
+                true
+            }
+            _ => false,
+        };
+    }
+
+    plan.children()
+        .all(|child| collect_table_versions(child, versions))
+}
+
+fn plan_scope(plan: &PhysicalPlan) -> Result<PlanScope> {
+    let labels = plan
+        .get_labels()?
+        .into_iter()
+        .map(|(name, value)| ProfileLabel::create(name, value))
+        .collect::<Vec<_>>();
+
+    Ok(PlanScope::create(
+        plan.get_id(),
+        plan.name(),
+        Arc::new(plan.get_desc()?),
+        Arc::new(labels),
+    ))
+}
+
+struct FragmentResultCollector {
+    key: String,
+    cache: Arc<FragmentResultCache>,
+    max_bytes: usize,
+    ttl: Duration,
+    recorded_miss: AtomicBool,
+    state: Mutex<CollectorState>,
+}
+
+struct CollectorState {
+    blocks: Vec<DataBlock>,
+    bytes: usize,
+    // The number of collecting processors not yet finished.
+    running: usize,
+    abandoned: bool,
+}
+
+impl FragmentResultCollector {
+    // The miss is recorded once for all the collecting processors.
+    fn record_miss(&self) {
+        if !self.recorded_miss.swap(true, Ordering::SeqCst) {
+            Profile::record_usize_profile(ProfileStatisticsName::FragmentCacheMisses, 1);
+        }
+    }
+
+    fn collect(&self, data_block: &DataBlock) {
+        self.record_miss();
+
+        let mut state = self.state.lock();
+        if state.abandoned {
+            return;
+        }
+
+        state.bytes += data_block.memory_size();
+        // The blocks with meta are not plain results.
+        if data_block.get_meta().is_some() || state.bytes > self.max_bytes {
+            state.abandoned = true;
+            state.blocks.clear();
+            return;
+        }
+
+        state.blocks.push(data_block.clone());
+    }
+
+    fn finish(&self, completed: bool) {
+        let mut state = self.state.lock();
+        state.running -= 1;
+        state.abandoned |= !completed;
+
+        if state.running == 0 && !state.abandoned {
+            let blocks = std::mem::take(&mut state.blocks);
+            drop(state);
+
+            let key = self.key.clone();
+            self.cache.put(key, blocks, self.max_bytes, self.ttl);
+        }
+    }
+}
+
+/// Pass the blocks through and collect them into the fragment result cache.
+///
+/// The result is abandoned if the downstream finished before the whole input is collected.
+struct TransformCollectFragmentResult {
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    input_data: Option<DataBlock>,
+    output_data: Option<DataBlock>,
+    collector: Option<Arc<FragmentResultCollector>>,
+}
+
+impl TransformCollectFragmentResult {
+    fn create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        collector: Arc<FragmentResultCollector>,
+    ) -> ProcessorPtr {
+        ProcessorPtr::create(Box::new(TransformCollectFragmentResult {
+            input,
+            output,
+            input_data: None,
+            output_data: None,
+            collector: Some(collector),
+        }))
+    }
+}
+
+impl Processor for TransformCollectFragmentResult {
+    fn name(&self) -> String {
+        String::from("CollectFragmentResultTransform")
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            if let Some(collector) = self.collector.take() {
+                collector.finish(false);
+            }
+
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            self.input.set_not_need_data();
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(data_block) = self.output_data.take() {
+            self.output.push_data(Ok(data_block));
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.input.has_data() {
+            self.input_data = Some(self.input.pull_data().unwrap()?);
+            return Ok(Event::Sync);
+        }
+
+        if self.input.is_finished() {
+            return match self.collector.is_some() {
+                true => Ok(Event::Sync),
+                false => {
+                    self.output.finish();
+                    Ok(Event::Finished)
+                }
+            };
+        }
+
+        self.input.set_need_data();
+        Ok(Event::NeedData)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        if let Some(data_block) = self.input_data.take() {
+            if let Some(collector) = &self.collector {
+                collector.collect(&data_block);
+            }
+
+            self.output_data = Some(data_block);
+            return Ok(());
+        }
+
+        if let Some(collector) = self.collector.take() {
+            collector.record_miss();
+            collector.finish(true);
+        }
+
+        Ok(())
+    }
+}
+
+/// Serve the cached result of a fragment instead of executing it.
+struct FragmentResultCacheSource {
+    output: Arc<OutputPort>,
+    blocks: Arc<Vec<DataBlock>>,
+    next_block: usize,
+    recorded_hit: bool,
+}
+
+impl FragmentResultCacheSource {
+    fn create(output: Arc<OutputPort>, blocks: Arc<Vec<DataBlock>>) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(FragmentResultCacheSource {
+            output,
+            blocks,
+            next_block: 0,
+            recorded_hit: false,
+        })))
+    }
+}
+
+impl Processor for FragmentResultCacheSource {
+    fn name(&self) -> String {
+        String::from("FragmentResultCacheSource")
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            return Ok(Event::NeedConsume);
+        }
+
+        if !self.recorded_hit {
+            return Ok(Event::Sync);
+        }
+
+        match self.blocks.get(self.next_block) {
+            None => {
+                self.output.finish();
+                Ok(Event::Finished)
+            }
+            Some(data_block) => {
+                self.next_block += 1;
+                self.output.push_data(Ok(data_block.clone()));
+                Ok(Event::NeedConsume)
+            }
+        }
+    }
+
+    fn process(&mut self) -> Result<()> {
+        self.recorded_hit = true;
+        Profile::record_usize_profile(ProfileStatisticsName::FragmentCacheHits, 1);
+        Ok(())
+    }
+}
@@ -18,27 +18,39 @@ mod exchange_manager;
 mod exchange_params;
 mod exchange_sink;
 mod exchange_sink_writer;
+mod exchange_sort_bounds;
+mod exchange_sorted_merge;
 mod exchange_sorting;
 mod exchange_source;
 mod exchange_source_reader;
 mod exchange_transform;
 mod exchange_transform_scatter;
 mod exchange_transform_shuffle;
+mod fragment_result_cache;
 mod serde;
 mod statistics_receiver;
 mod statistics_sender;
 
 pub use data_exchange::BroadcastExchange;
 pub use data_exchange::DataExchange;
+pub use data_exchange::ExchangeSortDesc;
 pub use data_exchange::MergeExchange;
+pub use data_exchange::ScatterPolicy;
 pub use data_exchange::ShuffleDataExchange;
 pub use exchange_injector::DefaultExchangeInjector;
 pub use exchange_injector::ExchangeInjector;
 pub use exchange_manager::DataExchangeManager;
+pub use exchange_manager::RunningStageInfo;
+pub use exchange_manager::RunningStreamInfo;
 pub use exchange_params::MergeExchangeParams;
 pub use exchange_params::ShuffleExchangeParams;
+pub use exchange_sort_bounds::SortBoundsMeta;
+pub use exchange_sort_bounds::TransformSortBounds;
+pub use exchange_sorted_merge::SortedExchangeMerge;
 pub use exchange_sorting::ExchangeSorting;
+pub use exchange_transform_shuffle::exchange_shuffle;
 pub use exchange_transform_shuffle::ExchangeShuffleMeta;
+pub use fragment_result_cache::FragmentResultCache;
 
 pub use self::serde::exchange_deserializer::ExchangeDeserializeMeta;
 pub use self::serde::exchange_deserializer::TransformExchangeDeserializer;
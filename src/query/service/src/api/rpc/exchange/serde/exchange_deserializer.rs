@@ -25,6 +25,7 @@ use databend_common_arrow::arrow::io::ipc::IpcSchema;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
 use databend_common_expression::BlockMetaInfo;
+use databend_common_expression::BlockMetaInfoDowncast;
 use databend_common_expression::BlockMetaInfoPtr;
 use databend_common_expression::DataBlock;
 use databend_common_expression::DataSchemaRef;
@@ -39,6 +40,7 @@ use databend_common_pipeline_transforms::processors::UnknownMode;
 use serde::Deserializer;
 use serde::Serializer;
 
+use crate::api::rpc::exchange::exchange_sort_bounds::SortBoundsMeta;
 use crate::api::DataPacket;
 use crate::api::FragmentData;
 
@@ -54,6 +56,14 @@ impl TransformExchangeDeserializer {
         output: Arc<OutputPort>,
         schema: &DataSchemaRef,
     ) -> ProcessorPtr {
+        ProcessorPtr::create(BlockMetaTransformer::create(
+            input,
+            output,
+            TransformExchangeDeserializer::new(schema),
+        ))
+    }
+
+    pub fn new(schema: &DataSchemaRef) -> TransformExchangeDeserializer {
         let arrow_schema = ArrowSchema::from(schema.as_ref());
         let ipc_fields = default_ipc_fields(&arrow_schema.fields);
         let ipc_schema = IpcSchema {
@@ -61,22 +71,25 @@ impl TransformExchangeDeserializer {
             is_little_endian: true,
         };
 
-        ProcessorPtr::create(BlockMetaTransformer::create(
-            input,
-            output,
-            TransformExchangeDeserializer {
-                ipc_schema,
-                arrow_schema: Arc::new(arrow_schema),
-                schema: schema.clone(),
-            },
-        ))
+        TransformExchangeDeserializer {
+            ipc_schema,
+            arrow_schema: Arc::new(arrow_schema),
+            schema: schema.clone(),
+        }
     }
 
-    fn recv_data(&self, dict: Vec<DataPacket>, fragment_data: FragmentData) -> Result<DataBlock> {
+    pub fn recv_data(
+        &self,
+        dict: Vec<DataPacket>,
+        fragment_data: FragmentData,
+    ) -> Result<DataBlock> {
         const ROW_HEADER_SIZE: usize = std::mem::size_of::<u32>();
 
-        let meta = bincode_deserialize_from_slice(&fragment_data.get_meta()[ROW_HEADER_SIZE..])
-            .map_err(|_| ErrorCode::BadBytes("block meta deserialize error when exchange"))?;
+        let meta: Option<BlockMetaInfoPtr> =
+            bincode_deserialize_from_slice(&fragment_data.get_meta()[ROW_HEADER_SIZE..])
+                .map_err(|_| ErrorCode::BadBytes("block meta deserialize error when exchange"))?;
+        // The sort bounds are only read by the receiver, see `SortedExchangeMerge`.
+        let meta = meta.filter(|meta| SortBoundsMeta::downcast_ref_from(meta).is_none());
         let mut row_count_meta = &fragment_data.get_meta()[..ROW_HEADER_SIZE];
         let row_count: u32 = row_count_meta.read_scalar()?;
 
@@ -110,6 +123,10 @@ impl TransformExchangeDeserializer {
             return Ok(DataBlock::new_with_meta(vec![], row_count as usize, meta));
         }
 
+        if cfg!(debug_assertions) {
+            data_block.check_schema(&self.schema)?;
+        }
+
         data_block.add_meta(meta)
     }
 }
@@ -127,18 +144,22 @@ impl BlockMetaTransform<ExchangeDeserializeMeta> for TransformExchangeDeserializ
             DataPacket::MergeStatus { .. } => unreachable!(),
             DataPacket::QueryProfiles(_) => unreachable!(),
             DataPacket::DataCacheMetrics(_) => unreachable!(),
-            DataPacket::FragmentData(v) => self.recv_data(meta.packet, v),
+            DataPacket::FragmentData(v) => {
+                Ok(self.recv_data(meta.packet, v)?.with_origin(meta.origin))
+            }
         }
     }
 }
 
 pub struct ExchangeDeserializeMeta {
     pub packet: Vec<DataPacket>,
+    /// Origin of the received block, see `ExchangeSourceReader`.
+    pub origin: u64,
 }
 
 impl ExchangeDeserializeMeta {
-    pub fn create(packet: Vec<DataPacket>) -> BlockMetaInfoPtr {
-        Box::new(ExchangeDeserializeMeta { packet })
+    pub fn create(packet: Vec<DataPacket>, origin: u64) -> BlockMetaInfoPtr {
+        Box::new(ExchangeDeserializeMeta { packet, origin })
     }
 }
 
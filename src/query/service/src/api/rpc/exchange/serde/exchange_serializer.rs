@@ -30,6 +30,8 @@ use databend_common_exception::Result;
 use databend_common_expression::BlockMetaInfo;
 use databend_common_expression::BlockMetaInfoPtr;
 use databend_common_expression::DataBlock;
+use databend_common_expression::DataSchema;
+use databend_common_expression::DataSchemaRef;
 use databend_common_io::prelude::bincode_serialize_into_buf;
 use databend_common_io::prelude::BinaryWrite;
 use databend_common_pipeline_core::processors::InputPort;
@@ -97,6 +99,7 @@ impl BlockMetaInfo for ExchangeSerializeMeta {
 
 pub struct TransformExchangeSerializer {
     options: WriteOptions,
+    schema: DataSchemaRef,
     ipc_fields: Vec<IpcField>,
 }
 
@@ -122,6 +125,7 @@ impl TransformExchangeSerializer {
             output,
             TransformExchangeSerializer {
                 ipc_fields,
+                schema: params.schema.clone(),
                 options: WriteOptions { compression },
             },
         )))
@@ -133,6 +137,7 @@ impl Transform for TransformExchangeSerializer {
 
     fn transform(&mut self, data_block: DataBlock) -> Result<DataBlock> {
         Profile::record_usize_profile(ProfileStatisticsName::ExchangeRows, data_block.num_rows());
+        check_block_schema(&data_block, &self.schema)?;
         serialize_block(0, data_block, &self.ipc_fields, &self.options)
     }
 }
@@ -140,6 +145,7 @@ impl Transform for TransformExchangeSerializer {
 pub struct TransformScatterExchangeSerializer {
     local_pos: usize,
     options: WriteOptions,
+    schema: DataSchemaRef,
     ipc_fields: Vec<IpcField>,
 }
 
@@ -166,6 +172,7 @@ impl TransformScatterExchangeSerializer {
             output,
             TransformScatterExchangeSerializer {
                 ipc_fields,
+                schema: params.schema.clone(),
                 options: WriteOptions { compression },
                 local_pos: params
                     .destination_ids
@@ -189,6 +196,7 @@ impl BlockMetaTransform<ExchangeShuffleMeta> for TransformScatterExchangeSeriali
                 continue;
             }
 
+            check_block_schema(&block, &self.schema)?;
             new_blocks.push(match self.local_pos == index {
                 true => block,
                 false => serialize_block(0, block, &self.ipc_fields, &self.options)?,
@@ -201,6 +209,17 @@ impl BlockMetaTransform<ExchangeShuffleMeta> for TransformScatterExchangeSeriali
     }
 }
 
+// Debug builds check every block against the exchange schema before it is encoded, so a
+// mismatched column fails here instead of being decoded as garbage on the remote node.
+fn check_block_schema(data_block: &DataBlock, schema: &DataSchema) -> Result<()> {
+    // Blocks without rows or columns only carry the row count and the meta.
+    if cfg!(debug_assertions) && !data_block.is_empty() && data_block.num_columns() != 0 {
+        data_block.check_schema(schema)?;
+    }
+
+    Ok(())
+}
+
 pub fn serialize_block(
     block_num: isize,
     data_block: DataBlock,
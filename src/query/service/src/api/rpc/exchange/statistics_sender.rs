@@ -233,6 +233,12 @@ impl StatisticsSender {
             progress_info.push(ProgressInfo::ResultProgress(result_progress_values));
         }
 
+        let loose_coercion_failures = ctx.fetch_loose_coercion_failures();
+
+        if loose_coercion_failures != 0 {
+            progress_info.push(ProgressInfo::LooseCoercionFailures(loose_coercion_failures));
+        }
+
         Ok(progress_info)
     }
 }
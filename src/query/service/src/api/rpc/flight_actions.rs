@@ -139,6 +139,9 @@ pub enum FlightAction {
     ExecutePartialQuery(String),
     TruncateTable(TruncateTable),
     KillQuery(KillQuery),
+    KillQueryFragments(String),
+    DrainNode(u64),
+    ResetQueryProfile,
 }
 
 impl TryInto<FlightAction> for Action {
@@ -158,6 +161,16 @@ impl TryInto<FlightAction> for Action {
             },
             "TruncateTable" => Ok(FlightAction::TruncateTable(self.body.try_into()?)),
             "KillQuery" => Ok(FlightAction::KillQuery(self.body.try_into()?)),
+            "KillQueryFragments" => match String::from_utf8(self.body) {
+                Ok(query_id) => Ok(FlightAction::KillQueryFragments(query_id)),
+                Err(cause) => Err(Status::invalid_argument(cause.to_string())),
+            },
+            "DrainNode" => match String::from_utf8(self.body).map(|s| s.parse::<u64>()) {
+                Ok(Ok(timeout_secs)) => Ok(FlightAction::DrainNode(timeout_secs)),
+                Ok(Err(cause)) => Err(Status::invalid_argument(cause.to_string())),
+                Err(cause) => Err(Status::invalid_argument(cause.to_string())),
+            },
+            "ResetQueryProfile" => Ok(FlightAction::ResetQueryProfile),
             un_implemented => Err(Status::unimplemented(format!(
                 "UnImplement action {}",
                 un_implemented
@@ -191,6 +204,18 @@ impl TryInto<Action> for FlightAction {
                 r#type: String::from("KillQuery"),
                 body: kill_query.try_into()?,
             }),
+            FlightAction::KillQueryFragments(query_id) => Ok(Action {
+                r#type: String::from("KillQueryFragments"),
+                body: query_id.into_bytes(),
+            }),
+            FlightAction::DrainNode(timeout_secs) => Ok(Action {
+                r#type: String::from("DrainNode"),
+                body: timeout_secs.to_string().into_bytes(),
+            }),
+            FlightAction::ResetQueryProfile => Ok(Action {
+                r#type: String::from("ResetQueryProfile"),
+                body: vec![],
+            }),
         }
     }
 }
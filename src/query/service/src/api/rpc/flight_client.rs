@@ -12,9 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
 use std::convert::TryInto;
 use std::error::Error;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Instant;
 
 use async_channel::Receiver;
 use async_channel::Sender;
@@ -22,15 +27,23 @@ use databend_common_arrow::arrow_format::flight::data::Action;
 use databend_common_arrow::arrow_format::flight::data::FlightData;
 use databend_common_arrow::arrow_format::flight::data::Ticket;
 use databend_common_arrow::arrow_format::flight::service::flight_service_client::FlightServiceClient;
+use databend_common_base::base::tokio;
 use databend_common_base::base::tokio::time::Duration;
 use databend_common_base::runtime::drop_guard;
+use databend_common_config::InnerConfig;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
+use databend_common_expression::DataBlock;
+use databend_common_expression::DataSchemaRef;
+use databend_common_grpc::ConnectionFactory;
+use databend_common_grpc::GrpcConnectionError;
+use databend_common_metrics::transform::ExchangeStreamMetrics;
 use futures::StreamExt;
 use futures_util::future::Either;
 use minitrace::full_name;
 use minitrace::future::FutureExt;
 use minitrace::Span;
+use parking_lot::Mutex;
 use tonic::transport::channel::Channel;
 use tonic::Request;
 use tonic::Status;
@@ -39,6 +52,13 @@ use tonic::Streaming;
 use crate::api::rpc::flight_actions::FlightAction;
 use crate::api::rpc::packets::DataPacket;
 use crate::api::rpc::request_builder::RequestBuilder;
+use crate::api::rpc::resumable_receiver;
+use crate::api::rpc::FragmentStreamId;
+use crate::api::rpc::StreamLiveness;
+use crate::api::rpc::StreamResumeConf;
+use crate::api::rpc::StreamSpillConf;
+use crate::api::rpc::StreamSpiller;
+use crate::api::rpc::TransformExchangeDeserializer;
 use crate::pipelines::executor::WatchNotify;
 
 pub struct FlightClient {
@@ -54,6 +74,35 @@ impl FlightClient {
         FlightClient { inner }
     }
 
+    /// Connects to the flight service of the node at `address`, with TLS if the root CA cert of
+    /// the cluster is configured.
+    #[async_backtrace::framed]
+    pub async fn connect(
+        config: &InnerConfig,
+        address: &str,
+        timeout: Option<Duration>,
+    ) -> Result<FlightClient> {
+        let tls_config = match config.tls_query_cli_enabled() {
+            true => Some(config.query.to_rpc_client_tls_config()),
+            false => None,
+        };
+        let tls = match tls_config.is_some() {
+            true => "enabled",
+            false => "disabled",
+        };
+
+        match ConnectionFactory::create_rpc_channel(address, timeout, tls_config).await {
+            Ok(channel) => Ok(FlightClient::new(FlightServiceClient::new(channel))),
+            // e.g. the TLS handshake with a node serving plaintext fails.
+            Err(cause @ GrpcConnectionError::CannotConnect { .. }) => {
+                Err(ErrorCode::from(cause).add_message_back(format!(
+                    "rpc tls is {tls} on this node, all the nodes of the cluster must agree on the rpc_tls_* settings"
+                )))
+            }
+            Err(cause) => Err(ErrorCode::from(cause)),
+        }
+    }
+
     #[async_backtrace::framed]
     pub async fn execute_action(&mut self, action: FlightAction, timeout: u64) -> Result<()> {
         if let Err(cause) = self.do_action(action, timeout).await {
@@ -91,20 +140,75 @@ impl FlightClient {
         target: &str,
         fragment: usize,
     ) -> Result<FlightExchange> {
-        let request = RequestBuilder::create(Ticket::default())
-            .with_metadata("x-type", "exchange_fragment")?
-            .with_metadata("x-target", target)?
-            .with_metadata("x-query-id", query_id)?
-            .with_metadata("x-fragment-id", &fragment.to_string())?
-            .build();
-        let request = databend_common_tracing::inject_span_to_tonic_request(request);
-
+        let conf = StreamResumeConf::default();
+        let stream_id = FragmentStreamId::create(query_id, target, fragment);
+        let request = Self::fragment_request(&stream_id, &conf, None)?;
         let streaming = self.get_streaming(request).await?;
 
         let (notify, rx) = Self::streaming_receiver(streaming);
         Ok(FlightExchange::create_receiver(notify, rx))
     }
 
+    /// Like `do_get`, but the stream is requested again from the last received packet
+    /// if the connection is lost, see [`ResumableStream`].
+    ///
+    /// [`ResumableStream`]: crate::api::rpc::ResumableStream
+    #[async_backtrace::framed]
+    #[minitrace::trace]
+    pub async fn do_get_resumable(
+        &mut self,
+        query_id: &str,
+        target: &str,
+        fragment: usize,
+        conf: &StreamResumeConf,
+    ) -> Result<FlightExchange> {
+        let stream_id = FragmentStreamId::create(query_id, target, fragment);
+        let request = Self::fragment_request(&stream_id, conf, None)?;
+        let streaming = self.get_streaming(request).await?;
+
+        let connect = {
+            let inner = self.inner.clone();
+            let conf = conf.clone();
+            move |resume_from: u64| {
+                let mut inner = inner.clone();
+                let request = Self::fragment_request(&stream_id, &conf, Some(resume_from));
+                async move {
+                    match inner.do_get(request?).await {
+                        Ok(res) => Ok(res.into_inner()),
+                        Err(status) => Err(ErrorCode::from(status)
+                            .add_message_back("(while resuming query flight)")),
+                    }
+                }
+            }
+        };
+
+        let (notify, rx) = resumable_receiver(streaming, connect, conf.max_retries);
+        Ok(FlightExchange::create_receiver(notify, rx))
+    }
+
+    fn fragment_request(
+        stream_id: &FragmentStreamId,
+        conf: &StreamResumeConf,
+        resume_from: Option<u64>,
+    ) -> Result<Request<Ticket>> {
+        let mut builder = RequestBuilder::create(stream_id.to_ticket())
+            .with_metadata("x-type", "exchange_fragment")?;
+
+        if conf.is_enabled() {
+            builder = builder
+                .with_metadata("x-retained-blocks", &conf.retained_blocks.to_string())?
+                .with_metadata("x-resume-timeout", &conf.timeout_secs.to_string())?;
+        }
+
+        if let Some(resume_from) = resume_from {
+            builder = builder.with_metadata("x-resume-from", &resume_from.to_string())?;
+        }
+
+        Ok(databend_common_tracing::inject_span_to_tonic_request(
+            builder.build(),
+        ))
+    }
+
     fn streaming_receiver(
         mut streaming: Streaming<FlightData>,
     ) -> (Arc<WatchNotify>, Receiver<Result<FlightData>>) {
@@ -211,19 +315,274 @@ impl FlightReceiver {
         }
     }
 
+    /// Collects the blocks of a fragment stream until `max_rows` rows are received.
+    ///
+    /// The last block is trimmed to the limit. The receiver is closed as soon as the limit
+    /// is reached, which cancels the remote stream: the flight client stops polling it, and
+    /// the remote sender sees the channel closed and stops producing.
+    #[async_backtrace::framed]
+    pub async fn collect_with_limit(
+        self,
+        schema: &DataSchemaRef,
+        max_rows: usize,
+    ) -> Result<Vec<DataBlock>> {
+        let deserializer = TransformExchangeDeserializer::new(schema);
+
+        let mut num_rows = 0;
+        let mut blocks = vec![];
+        let mut dictionaries = vec![];
+        while num_rows < max_rows {
+            match self.recv().await? {
+                None => break,
+                Some(DataPacket::ErrorCode(cause)) => return Err(cause),
+                Some(DataPacket::FragmentData(data)) => {
+                    let dict = std::mem::take(&mut dictionaries);
+                    let block = deserializer.recv_data(dict, data)?;
+                    let block = match block.num_rows() > max_rows - num_rows {
+                        true => block.slice(0..max_rows - num_rows),
+                        false => block,
+                    };
+
+                    num_rows += block.num_rows();
+                    blocks.push(block);
+                }
+                Some(packet) => dictionaries.push(packet),
+            }
+        }
+
+        self.close();
+        Ok(blocks)
+    }
+
+    /// Receives the packets sent by [`FlightSender::send_sequenced`] in the order of their
+    /// sequence numbers, at most `max_buffered` packets are held while waiting for a missing one.
+    pub fn into_ordered(self, max_buffered: usize) -> OrderedFlightReceiver {
+        OrderedFlightReceiver {
+            inner: self,
+            next_sequence: 0,
+            max_buffered,
+            buffered: BTreeMap::new(),
+        }
+    }
+
     pub fn close(&self) {
         self.rx.close();
         self.notify.notify_waiters();
     }
 }
 
+// The type of the packets tagged with a sequence number, the number is written before it.
+const SEQUENCED_PACKET: u8 = 0x09;
+
+fn tag_sequence(data: &mut FlightData, sequence: u64) {
+    data.app_metadata.extend_from_slice(&sequence.to_be_bytes());
+    data.app_metadata.push(SEQUENCED_PACKET);
+}
+
+fn take_sequence(data: &mut FlightData) -> Option<u64> {
+    let len = data.app_metadata.len();
+    // The tagged packet keeps the type of the original packet before the sequence number.
+    if len < 10 || data.app_metadata[len - 1] != SEQUENCED_PACKET {
+        return None;
+    }
+
+    let sequence = u64::from_be_bytes(data.app_metadata[len - 9..len - 1].try_into().unwrap());
+    data.app_metadata.truncate(len - 9);
+    Some(sequence)
+}
+
+/// Receives the packets of an ordered stream in the order of their sequence numbers, which
+/// start from 0.
+///
+/// The packets arriving early are buffered until the missing ones arrive. The packets without
+/// a sequence number, like the errors, are received as soon as they arrive.
+pub struct OrderedFlightReceiver {
+    inner: FlightReceiver,
+    next_sequence: u64,
+    max_buffered: usize,
+    buffered: BTreeMap<u64, FlightData>,
+}
+
+impl OrderedFlightReceiver {
+    #[async_backtrace::framed]
+    pub async fn recv(&mut self) -> Result<Option<DataPacket>> {
+        loop {
+            if let Some(data) = self.buffered.remove(&self.next_sequence) {
+                self.next_sequence += 1;
+                return Ok(Some(DataPacket::try_from(data)?));
+            }
+
+            let mut data = match self.inner.rx.recv().await {
+                Err(_) => return self.finish(),
+                Ok(Err(error)) => return Err(error),
+                Ok(Ok(data)) => data,
+            };
+
+            match take_sequence(&mut data) {
+                None => return Ok(Some(DataPacket::try_from(data)?)),
+                Some(sequence) if sequence == self.next_sequence => {
+                    self.next_sequence += 1;
+                    return Ok(Some(DataPacket::try_from(data)?));
+                }
+                Some(sequence)
+                    if sequence < self.next_sequence || self.buffered.contains_key(&sequence) =>
+                {
+                    return Err(ErrorCode::Internal(format!(
+                        "Duplicated packet {} in the ordered flight stream",
+                        sequence
+                    )));
+                }
+                Some(sequence) => {
+                    if self.buffered.len() >= self.max_buffered {
+                        return Err(ErrorCode::Internal(format!(
+                            "Too many packets are buffered in the ordered flight stream, packet {} is missing, the max buffered packets is {}",
+                            self.next_sequence, self.max_buffered
+                        )));
+                    }
+
+                    self.buffered.insert(sequence, data);
+                }
+            }
+        }
+    }
+
+    fn finish(&self) -> Result<Option<DataPacket>> {
+        match self.buffered.is_empty() {
+            true => Ok(None),
+            false => Err(ErrorCode::Internal(format!(
+                "The ordered flight stream is finished without packet {}, {} packets are buffered",
+                self.next_sequence,
+                self.buffered.len()
+            ))),
+        }
+    }
+
+    pub fn close(&self) {
+        self.inner.close();
+    }
+}
+
+/// Limits how far the consumer of a flight stream may fall behind the sender.
+///
+/// A packet is lagging from the time it is sent until the consumer takes it from the
+/// channel. Zero disables the corresponding limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamLagLimits {
+    pub max_blocks: usize,
+    pub max_duration: Duration,
+}
+
+impl StreamLagLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_blocks == 0 && self.max_duration.is_zero()
+    }
+}
+
+/// The rows and bytes sent by a fragment stream, kept in the summary of the stage once it is
+/// finished.
+#[derive(Default)]
+pub struct StreamSentStatistics {
+    rows: AtomicUsize,
+    bytes: AtomicUsize,
+}
+
+impl StreamSentStatistics {
+    pub fn create() -> Arc<StreamSentStatistics> {
+        Arc::new(StreamSentStatistics::default())
+    }
+
+    pub fn record_send(&self, rows: usize, bytes: usize) {
+        self.rows.fetch_add(rows, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn sent_rows(&self) -> usize {
+        self.rows.load(Ordering::Relaxed)
+    }
+
+    pub fn sent_bytes(&self) -> usize {
+        self.bytes.load(Ordering::Relaxed)
+    }
+}
+
 pub struct FlightSender {
     tx: Sender<Result<FlightData, Status>>,
+    lag_limits: StreamLagLimits,
+    // The send time of the packets which are not consumed yet, in the send order.
+    lagging: Mutex<VecDeque<Instant>>,
+    metrics: Option<ExchangeStreamMetrics>,
+    spiller: Option<Arc<StreamSpiller>>,
+    liveness: Option<Arc<StreamLiveness>>,
+    idle_timeout: Duration,
+    sent_statistics: Option<Arc<StreamSentStatistics>>,
+}
+
+impl Drop for FlightSender {
+    fn drop(&mut self) {
+        // The spilled packets are still sent after the sender is dropped.
+        if let Some(spiller) = &self.spiller {
+            spiller.finish();
+        }
+    }
 }
 
 impl FlightSender {
     pub fn create(tx: Sender<Result<FlightData, Status>>) -> FlightSender {
-        FlightSender { tx }
+        FlightSender {
+            tx,
+            lag_limits: StreamLagLimits::default(),
+            lagging: Mutex::new(VecDeque::new()),
+            metrics: None,
+            spiller: None,
+            liveness: None,
+            idle_timeout: Duration::ZERO,
+            sent_statistics: None,
+        }
+    }
+
+    /// Fails the sending once the consumer falls behind the `limits`, instead of
+    /// holding the produced data until the consumer catches up.
+    pub fn with_lag_limits(mut self, limits: StreamLagLimits) -> FlightSender {
+        self.lag_limits = limits;
+        self
+    }
+
+    /// Publishes the sent rows and bytes and the queued blocks of the stream to the metrics,
+    /// until the sender is dropped.
+    pub fn with_metrics(mut self, metrics: ExchangeStreamMetrics) -> FlightSender {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Spills the packets to the local disk once the consumer falls behind by more than
+    /// `conf.max_memory_bytes`, instead of waiting for it. The lag limits don't apply to a
+    /// spilling sender.
+    pub fn with_spill(mut self, conf: StreamSpillConf) -> FlightSender {
+        if conf.is_enabled() {
+            self.spiller = Some(StreamSpiller::create(conf, self.tx.clone()));
+        }
+        self
+    }
+
+    /// Tracks whether the consumer of the stream still polls it, see [`StreamLiveness`].
+    pub fn with_liveness(mut self, liveness: Arc<StreamLiveness>) -> FlightSender {
+        self.liveness = Some(liveness);
+        self
+    }
+
+    /// Cancels the stream with [`ErrorCode::ExchangeConsumerTimeout`] once its channel is full
+    /// and the consumer has not polled it for `timeout`, instead of waiting for the consumer
+    /// forever. Zero disables the timeout, and so does a spilling sender or one without
+    /// liveness.
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> FlightSender {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// Counts the rows and bytes sent by the stream to the `statistics`.
+    pub fn with_sent_statistics(mut self, statistics: Arc<StreamSentStatistics>) -> FlightSender {
+        self.sent_statistics = Some(statistics);
+        self
     }
 
     pub fn is_closed(&self) -> bool {
@@ -232,17 +591,163 @@ impl FlightSender {
 
     #[async_backtrace::framed]
     pub async fn send(&self, data: DataPacket) -> Result<()> {
-        if let Err(_cause) = self.tx.send(Ok(FlightData::try_from(data)?)).await {
-            return Err(ErrorCode::AbortedQuery(
-                "Aborted query, because the remote flight channel is closed.",
-            ));
+        self.send_packet(data, None).await
+    }
+
+    /// Sends the packet tagged with its sequence number, an [`OrderedFlightReceiver`] receives
+    /// the packets in the order of the numbers instead of the order they are sent.
+    #[async_backtrace::framed]
+    pub async fn send_sequenced(&self, sequence: u64, data: DataPacket) -> Result<()> {
+        self.send_packet(data, Some(sequence)).await
+    }
+
+    #[async_backtrace::framed]
+    async fn send_packet(&self, data: DataPacket, sequence: Option<u64>) -> Result<()> {
+        let sent_size = match self.metrics.is_some() || self.sent_statistics.is_some() {
+            true => Some(match &data {
+                DataPacket::FragmentData(fragment) => (fragment.num_rows(), data.bytes_size()),
+                _ => (0, data.bytes_size()),
+            }),
+            false => None,
+        };
+        let mut data = FlightData::try_from(data)?;
+        if let Some(sequence) = sequence {
+            tag_sequence(&mut data, sequence);
+        }
+
+        match &self.spiller {
+            Some(spiller) => spiller.send(data).await?,
+            None => self.send_to_channel(data).await?,
+        }
+
+        if let (Some(metrics), Some((rows, bytes))) = (&self.metrics, sent_size) {
+            metrics.record_send(rows, bytes, self.tx.len());
+        }
+
+        if let (Some(statistics), Some((rows, bytes))) = (&self.sent_statistics, sent_size) {
+            statistics.record_send(rows, bytes);
         }
 
         Ok(())
     }
 
+    #[async_backtrace::framed]
+    async fn send_to_channel(&self, data: FlightData) -> Result<()> {
+        let deadline = match self.lag_limits.is_unlimited() {
+            true => None,
+            false => self.check_lag()?,
+        };
+
+        let sent = self.send_before(data, deadline).await;
+        if !self.lag_limits.is_unlimited() {
+            self.lagging.lock().push_back(Instant::now());
+        }
+        sent
+    }
+
+    // Waits for the channel to take the packet, until the lag `deadline` or the idle timeout of
+    // the consumer.
+    async fn send_before(&self, data: FlightData, deadline: Option<Instant>) -> Result<()> {
+        let liveness = match self.idle_timeout.is_zero() {
+            true => None,
+            false => self.liveness.as_ref(),
+        };
+
+        let mut send = std::pin::pin!(self.tx.send(Ok(data)));
+
+        loop {
+            let idle_deadline = liveness.map(|liveness| liveness.last_poll() + self.idle_timeout);
+            let wait_until = match (deadline, idle_deadline) {
+                (Some(deadline), Some(idle_deadline)) => Some(deadline.min(idle_deadline)),
+                (deadline, idle_deadline) => deadline.or(idle_deadline),
+            };
+
+            let sent = match wait_until {
+                None => Some(send.as_mut().await),
+                Some(wait_until) => tokio::time::timeout_at(wait_until.into(), send.as_mut())
+                    .await
+                    .ok(),
+            };
+
+            match sent {
+                Some(Ok(_)) => return Ok(()),
+                Some(Err(_cause)) => {
+                    return Err(ErrorCode::AbortedQuery(
+                        "Aborted query, because the remote flight channel is closed.",
+                    ));
+                }
+                None => {}
+            }
+
+            if deadline.is_some_and(|deadline| deadline <= Instant::now()) {
+                return Err(self.too_slow());
+            }
+
+            if let Some(liveness) = liveness {
+                // The consumer doesn't poll the stream anymore, cancel only this stream.
+                if liveness.last_poll() + self.idle_timeout <= Instant::now() {
+                    let error = self.consumer_timeout();
+                    liveness.cancel(error.clone());
+                    self.tx.close();
+                    return Err(error);
+                }
+            }
+
+            // The consumer polled the stream in the meantime, keep waiting.
+        }
+    }
+
+    fn consumer_timeout(&self) -> ErrorCode {
+        ErrorCode::ExchangeConsumerTimeout(format!(
+            "Exchange consumer timeout, the stream is full and not polled for {} seconds, the limit is stream_idle_timeout_secs.",
+            self.idle_timeout.as_secs(),
+        ))
+    }
+
+    // Returns the deadline of sending the next packet.
+    fn check_lag(&self) -> Result<Option<Instant>> {
+        let mut lagging = self.lagging.lock();
+
+        // The packets remaining in the channel are the latest ones. The sender is the only
+        // producer of its channel: a fragment channel is created for a single destination and
+        // taken once by `get_flight_senders`, and the spiller, the other holder of `tx`, is
+        // never combined with the lag limits.
+        while lagging.len() > self.tx.len() {
+            lagging.pop_front();
+        }
+
+        if self.lag_limits.max_blocks != 0 && lagging.len() >= self.lag_limits.max_blocks {
+            return Err(self.too_slow());
+        }
+
+        if self.lag_limits.max_duration.is_zero() {
+            return Ok(None);
+        }
+
+        let oldest = lagging.front().copied().unwrap_or_else(Instant::now);
+        let deadline = oldest + self.lag_limits.max_duration;
+        match deadline <= Instant::now() {
+            true => Err(self.too_slow()),
+            false => Ok(Some(deadline)),
+        }
+    }
+
+    fn too_slow(&self) -> ErrorCode {
+        ErrorCode::ExchangeConsumerTooSlow(format!(
+            "Exchange consumer too slow, {} blocks are not consumed, the limits are max_stream_lag_blocks {} and max_stream_lag_seconds {}.",
+            self.tx.len(),
+            self.lag_limits.max_blocks,
+            self.lag_limits.max_duration.as_secs(),
+        ))
+    }
+
     pub fn close(&self) {
-        self.tx.close();
+        match &self.spiller {
+            Some(spiller) => spiller.finish(),
+            None => {
+                self.tx.close();
+            }
+        }
     }
 }
 
@@ -252,12 +757,34 @@ pub enum FlightExchange {
         notify: Arc<WatchNotify>,
         receiver: Receiver<Result<FlightData>>,
     },
-    Sender(Sender<Result<FlightData, Status>>),
+    Sender {
+        sender: Sender<Result<FlightData, Status>>,
+        liveness: Option<Arc<StreamLiveness>>,
+        sent_statistics: Option<Arc<StreamSentStatistics>>,
+    },
 }
 
 impl FlightExchange {
     pub fn create_sender(sender: Sender<Result<FlightData, Status>>) -> FlightExchange {
-        FlightExchange::Sender(sender)
+        FlightExchange::Sender {
+            sender,
+            liveness: None,
+            sent_statistics: None,
+        }
+    }
+
+    /// The sender of a fragment stream, whose consumer polls register to the `liveness`, and
+    /// whose sent rows and bytes are counted to the `sent_statistics`.
+    pub fn create_fragment_sender(
+        sender: Sender<Result<FlightData, Status>>,
+        liveness: Arc<StreamLiveness>,
+        sent_statistics: Arc<StreamSentStatistics>,
+    ) -> FlightExchange {
+        FlightExchange::Sender {
+            sender,
+            liveness: Some(liveness),
+            sent_statistics: Some(sent_statistics),
+        }
     }
 
     pub fn create_receiver(
@@ -269,7 +796,20 @@ impl FlightExchange {
 
     pub fn convert_to_sender(self) -> FlightSender {
         match self {
-            FlightExchange::Sender(tx) => FlightSender { tx },
+            FlightExchange::Sender {
+                sender,
+                liveness,
+                sent_statistics,
+            } => {
+                let mut flight_sender = FlightSender::create(sender);
+                if let Some(liveness) = liveness {
+                    flight_sender = flight_sender.with_liveness(liveness);
+                }
+                if let Some(sent_statistics) = sent_statistics {
+                    flight_sender = flight_sender.with_sent_statistics(sent_statistics);
+                }
+                flight_sender
+            }
             _ => unreachable!(),
         }
     }
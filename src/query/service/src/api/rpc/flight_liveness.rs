@@ -0,0 +1,107 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Instant;
+
+use async_channel::Receiver;
+use databend_common_arrow::arrow_format::flight::data::FlightData;
+use databend_common_exception::ErrorCode;
+use futures::Stream;
+use futures::StreamExt;
+use parking_lot::Mutex;
+use tonic::Status;
+
+/// Whether the consumer of a fragment stream is still there, that is when it last polled
+/// the DoGet response stream.
+///
+/// A consumer which is dropped by a network partition without closing the connection never
+/// polls the stream again, while a slow one keeps polling as it catches up.
+pub struct StreamLiveness {
+    state: Mutex<LivenessState>,
+}
+
+struct LivenessState {
+    last_poll: Instant,
+    // The stream is cancelled, its consumer receives the error instead of the packets.
+    cancelled: Option<ErrorCode>,
+}
+
+impl StreamLiveness {
+    pub fn create() -> Arc<StreamLiveness> {
+        Arc::new(StreamLiveness {
+            state: Mutex::new(LivenessState {
+                last_poll: Instant::now(),
+                cancelled: None,
+            }),
+        })
+    }
+
+    /// The last time the consumer polled the stream, or the time the stream was requested.
+    pub fn last_poll(&self) -> Instant {
+        self.state.lock().last_poll
+    }
+
+    /// Cancels the stream, the packets not received yet are dropped.
+    pub fn cancel(&self, error: ErrorCode) {
+        self.state.lock().cancelled = Some(error);
+    }
+
+    fn poll(&self) -> Option<ErrorCode> {
+        let mut state = self.state.lock();
+        state.last_poll = Instant::now();
+        state.cancelled.clone()
+    }
+}
+
+/// The DoGet response stream of a fragment stream, which registers each poll of the consumer
+/// to the [`StreamLiveness`] of the stream.
+pub struct PolledStream {
+    inner: Receiver<Result<FlightData, Status>>,
+    liveness: Arc<StreamLiveness>,
+    finished: bool,
+}
+
+impl PolledStream {
+    pub fn create(
+        inner: Receiver<Result<FlightData, Status>>,
+        liveness: Arc<StreamLiveness>,
+    ) -> PolledStream {
+        PolledStream {
+            inner,
+            liveness,
+            finished: false,
+        }
+    }
+}
+
+impl Stream for PolledStream {
+    type Item = Result<FlightData, Status>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+
+        if let Some(error) = self.liveness.poll() {
+            self.finished = true;
+            return Poll::Ready(Some(Err(Status::from(error))));
+        }
+
+        self.inner.poll_next_unpin(cx)
+    }
+}
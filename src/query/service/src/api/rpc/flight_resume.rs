@@ -0,0 +1,278 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_channel::Receiver;
+use async_channel::Sender;
+use databend_common_arrow::arrow_format::flight::data::FlightData;
+use databend_common_base::base::tokio;
+use databend_common_base::base::tokio::sync::Notify;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use futures::Stream;
+use futures::StreamExt;
+use futures_util::future::Either;
+use log::warn;
+use minitrace::full_name;
+use minitrace::future::FutureExt;
+use minitrace::Span;
+use parking_lot::Mutex;
+use tonic::Code;
+use tonic::Status;
+
+use crate::pipelines::executor::WatchNotify;
+
+/// How the fragment streams of a query are resumed after the connection between
+/// the nodes is lost.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct StreamResumeConf {
+    /// The number of the latest packets retained by the producer, 0 disables resuming.
+    pub retained_blocks: usize,
+    /// The max number of reconnections of a consumer.
+    pub max_retries: usize,
+    /// How long the producer waits for a lost consumer to reconnect.
+    pub timeout_secs: u64,
+}
+
+impl StreamResumeConf {
+    pub fn is_enabled(&self) -> bool {
+        self.retained_blocks != 0
+    }
+}
+
+/// The producer side of a resumable fragment stream.
+///
+/// The packets are numbered from 0 in the send order and the latest of them are
+/// retained. A consumer which loses the connection requests the stream again with
+/// the number of the packets it received, and gets the remaining packets from there.
+pub struct ResumableStream {
+    state: Mutex<ResumableState>,
+    resumed: Notify,
+    max_retained: usize,
+    timeout: Duration,
+}
+
+struct ResumableState {
+    tx: Sender<Result<FlightData, Status>>,
+    // Incremented whenever the consumer resumes the stream with a new channel.
+    generation: u64,
+    // The number of the first retained packet.
+    first_retained: u64,
+    retained: VecDeque<FlightData>,
+    finished: bool,
+}
+
+impl ResumableStream {
+    /// Returns the stream, the sender of the packets and the receiver of the first
+    /// connection of the consumer.
+    pub fn create(
+        max_retained: usize,
+        timeout: Duration,
+    ) -> (
+        Arc<ResumableStream>,
+        Sender<Result<FlightData, Status>>,
+        Receiver<Result<FlightData, Status>>,
+    ) {
+        let (input_tx, input_rx) = async_channel::bounded(8);
+        let (tx, rx) = async_channel::bounded(8);
+        let stream = Arc::new(ResumableStream {
+            state: Mutex::new(ResumableState {
+                tx,
+                generation: 0,
+                first_retained: 0,
+                retained: VecDeque::with_capacity(max_retained),
+                finished: false,
+            }),
+            resumed: Notify::new(),
+            max_retained,
+            timeout,
+        });
+
+        let fut = stream
+            .clone()
+            .forward(input_rx)
+            .in_span(Span::enter_with_local_parent(full_name!()));
+        databend_common_base::runtime::spawn(fut);
+
+        (stream, input_tx, rx)
+    }
+
+    /// Replaces the connection of the consumer, which has received `resume_from` packets.
+    pub fn resume(&self, resume_from: u64) -> Result<Receiver<Result<FlightData, Status>>> {
+        let mut state = self.state.lock();
+        let next = state.first_retained + state.retained.len() as u64;
+        if resume_from < state.first_retained || resume_from > next {
+            return Err(ErrorCode::UnknownFragmentExchange(format!(
+                "Cannot resume the fragment stream from packet {}, the retained packets are [{}, {})",
+                resume_from, state.first_retained, next
+            )));
+        }
+
+        let replay = (resume_from - state.first_retained) as usize;
+        let (tx, rx) = async_channel::bounded(state.retained.len() - replay + 8);
+        for data in state.retained.iter().skip(replay) {
+            // The channel has enough capacity for the replayed packets.
+            let _ = tx.try_send(Ok(data.clone()));
+        }
+
+        if state.finished {
+            tx.close();
+        }
+
+        let old_tx = std::mem::replace(&mut state.tx, tx);
+        old_tx.close();
+        state.generation += 1;
+        drop(state);
+
+        self.resumed.notify_waiters();
+        Ok(rx)
+    }
+
+    async fn forward(self: Arc<Self>, input: Receiver<Result<FlightData, Status>>) {
+        while let Ok(message) = input.recv().await {
+            let (tx, generation) = {
+                let mut state = self.state.lock();
+                if let Ok(data) = &message {
+                    state.retained.push_back(data.clone());
+                    if state.retained.len() > self.max_retained {
+                        state.retained.pop_front();
+                        state.first_retained += 1;
+                    }
+                }
+
+                (state.tx.clone(), state.generation)
+            };
+
+            // The retained packet is sent again if the consumer resumes.
+            if tx.send(message).await.is_err() && !self.wait_resumed(generation).await {
+                // The consumer does not come back, it's the same as the consumer closed the stream.
+                input.close();
+                return;
+            }
+        }
+
+        let mut state = self.state.lock();
+        state.finished = true;
+        state.tx.close();
+    }
+
+    async fn wait_resumed(&self, generation: u64) -> bool {
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            let resumed = self.resumed.notified();
+            if self.state.lock().generation != generation {
+                return true;
+            }
+
+            if tokio::time::timeout_at(deadline.into(), resumed)
+                .await
+                .is_err()
+            {
+                return false;
+            }
+        }
+    }
+}
+
+/// Whether the status is an error of the connection rather than of the query,
+/// so the stream can be requested again.
+fn is_transient(status: &Status) -> bool {
+    match status.code() {
+        Code::Unavailable | Code::Internal | Code::Aborted | Code::Cancelled => true,
+        // The errors of the query are also Code::Unknown, but with the error code in the details.
+        Code::Unknown => status.details().is_empty(),
+        _ => false,
+    }
+}
+
+/// Receives the packets of a resumable fragment stream.
+///
+/// On a transient error, the stream is requested again by `connect` with the number of
+/// the received packets, up to `max_retries` times. The other errors are passed to the
+/// receiver.
+pub fn resumable_receiver<S, F, Fut>(
+    streaming: S,
+    connect: F,
+    max_retries: usize,
+) -> (Arc<WatchNotify>, Receiver<Result<FlightData>>)
+where
+    S: Stream<Item = Result<FlightData, Status>> + Send + Unpin + 'static,
+    F: Fn(u64) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<S>> + Send + 'static,
+{
+    let (tx, rx) = async_channel::bounded(1);
+    let notify = Arc::new(WatchNotify::new());
+    let fut = {
+        let notify = notify.clone();
+        async move {
+            let mut streaming = streaming;
+            let mut received = 0;
+            let mut retries = 0;
+            let mut notified = Box::pin(notify.notified());
+
+            loop {
+                let message = match futures::future::select(notified, streaming.next()).await {
+                    Either::Left((_, _)) | Either::Right((None, _)) => {
+                        break;
+                    }
+                    Either::Right((Some(message), next_notified)) => {
+                        notified = next_notified;
+                        message
+                    }
+                };
+
+                match message {
+                    Ok(message) => {
+                        received += 1;
+                        if tx.send(Ok(message)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(status) if is_transient(&status) && retries < max_retries => {
+                        retries += 1;
+                        warn!(
+                            "Fragment stream is broken after {} packets, resume it, retry {}: {:?}",
+                            received, retries, status
+                        );
+
+                        match connect(received).await {
+                            Ok(resumed) => streaming = resumed,
+                            Err(cause) => {
+                                let _ = tx.send(Err(cause)).await;
+                                break;
+                            }
+                        }
+                    }
+                    Err(status) => {
+                        let _ = tx.send(Err(ErrorCode::from(status))).await;
+                        break;
+                    }
+                }
+            }
+
+            drop(streaming);
+            tx.close();
+        }
+    }
+    .in_span(Span::enter_with_local_parent(full_name!()));
+
+    databend_common_base::runtime::spawn(fut);
+
+    (notify, rx)
+}
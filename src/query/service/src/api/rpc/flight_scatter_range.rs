@@ -0,0 +1,125 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::DataBlock;
+use databend_common_expression::Evaluator;
+use databend_common_expression::Expr;
+use databend_common_expression::FunctionContext;
+use databend_common_expression::RemoteExpr;
+use databend_common_expression::Scalar;
+use databend_common_expression::ScalarRef;
+use databend_common_functions::BUILTIN_FUNCTIONS;
+
+use crate::api::rpc::flight_scatter::FlightScatter;
+
+/// Sends the rows to the destinations by the range of the key.
+///
+/// The `n` boundaries split the key into `n + 1` ranges, the row is sent to the `i`-th
+/// destination if `boundaries[i - 1] <= key < boundaries[i]`. The values are compared with
+/// [`ScalarRef::cmp_coerced_nulls_first`], and the rows whose key is NULL are sent to the
+/// first destination.
+pub struct RangeFlightScatter {
+    func_ctx: FunctionContext,
+    range_key: Expr,
+    boundaries: Vec<Scalar>,
+    scatter_size: usize,
+}
+
+impl RangeFlightScatter {
+    pub fn try_create(
+        func_ctx: FunctionContext,
+        range_key: &RemoteExpr,
+        boundaries: Vec<Scalar>,
+        scatter_size: usize,
+    ) -> Result<Self> {
+        if boundaries.len() + 1 != scatter_size {
+            return Err(ErrorCode::Internal(format!(
+                "Range scatter to {} destinations needs {} boundaries, but got {}",
+                scatter_size,
+                scatter_size.saturating_sub(1),
+                boundaries.len()
+            )));
+        }
+
+        for pair in boundaries.windows(2) {
+            match pair[0].cmp_coerced_nulls_first(&pair[1]) {
+                Some(Ordering::Less) | Some(Ordering::Equal) => {}
+                _ => {
+                    return Err(ErrorCode::Internal(format!(
+                        "The boundaries of range scatter must be sorted, but got {} before {}",
+                        pair[0], pair[1]
+                    )));
+                }
+            }
+        }
+
+        Ok(RangeFlightScatter {
+            func_ctx,
+            range_key: range_key.as_expr(&BUILTIN_FUNCTIONS),
+            boundaries,
+            scatter_size,
+        })
+    }
+
+    fn partition(&self, key: ScalarRef) -> Result<u64> {
+        if matches!(key, ScalarRef::Null) {
+            return Ok(0);
+        }
+
+        // The number of the boundaries which are not greater than the key.
+        let (mut low, mut high) = (0, self.boundaries.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            match self.boundaries[mid].as_ref().cmp_coerced_nulls_first(&key) {
+                Some(Ordering::Greater) => high = mid,
+                Some(_) => low = mid + 1,
+                None => {
+                    return Err(ErrorCode::Internal(format!(
+                        "Cannot compare the range scatter key {} with the boundary {}",
+                        key, self.boundaries[mid]
+                    )));
+                }
+            }
+        }
+
+        Ok(low as u64)
+    }
+}
+
+impl FlightScatter for RangeFlightScatter {
+    fn execute(&self, data_block: DataBlock) -> Result<Vec<DataBlock>> {
+        let evaluator = Evaluator::new(&data_block, &self.func_ctx, &BUILTIN_FUNCTIONS);
+        let num_rows = data_block.num_rows();
+        let keys = evaluator.run(&self.range_key)?;
+
+        let mut indices = Vec::with_capacity(num_rows);
+        for row in 0..num_rows {
+            indices.push(self.partition(keys.index(row).unwrap())?);
+        }
+
+        let block_meta = data_block.get_meta();
+        let data_blocks = DataBlock::scatter(&data_block, &indices, self.scatter_size)?;
+
+        let mut res = Vec::with_capacity(data_blocks.len());
+        for data_block in data_blocks {
+            res.push(data_block.add_meta(block_meta.cloned())?);
+        }
+
+        Ok(res)
+    }
+}
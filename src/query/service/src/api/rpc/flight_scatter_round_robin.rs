@@ -0,0 +1,57 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use databend_common_exception::Result;
+use databend_common_expression::DataBlock;
+
+use crate::api::rpc::flight_scatter::FlightScatter;
+
+/// Sends the rows to the destinations in turn. The turn goes on across the blocks,
+/// so the destinations receive about the same number of rows even for small blocks.
+pub struct RoundRobinFlightScatter {
+    scatter_size: usize,
+    next: AtomicUsize,
+}
+
+impl RoundRobinFlightScatter {
+    pub fn try_create(scatter_size: usize) -> Result<Self> {
+        Ok(RoundRobinFlightScatter {
+            scatter_size,
+            next: AtomicUsize::new(0),
+        })
+    }
+}
+
+impl FlightScatter for RoundRobinFlightScatter {
+    fn execute(&self, data_block: DataBlock) -> Result<Vec<DataBlock>> {
+        let num_rows = data_block.num_rows();
+        let start = self.next.fetch_add(num_rows, Ordering::Relaxed);
+        let indices = (0..num_rows)
+            .map(|row| (start.wrapping_add(row) % self.scatter_size) as u64)
+            .collect::<Vec<_>>();
+
+        let block_meta = data_block.get_meta();
+        let data_blocks = DataBlock::scatter(&data_block, &indices, self.scatter_size)?;
+
+        let mut res = Vec::with_capacity(data_blocks.len());
+        for data_block in data_blocks {
+            res.push(data_block.add_meta(block_meta.cloned())?);
+        }
+
+        Ok(res)
+    }
+}
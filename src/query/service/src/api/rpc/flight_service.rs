@@ -13,7 +13,10 @@
 // limitations under the License.
 
 use std::convert::TryInto;
+use std::fmt::Debug;
 use std::pin::Pin;
+use std::str::FromStr;
+use std::time::Duration;
 
 use databend_common_arrow::arrow_format::flight::data::Action;
 use databend_common_arrow::arrow_format::flight::data::ActionType;
@@ -33,7 +36,9 @@ use databend_common_base::match_join_handle;
 use databend_common_base::runtime::TrySpawn;
 use databend_common_catalog::table_context::TableContext;
 use databend_common_config::GlobalConfig;
+use databend_common_exception::ErrorCode;
 use databend_common_settings::Settings;
+use databend_common_storages_system::QueryProfileStore;
 use minitrace::full_name;
 use minitrace::prelude::*;
 use tokio_stream::Stream;
@@ -44,7 +49,12 @@ use tonic::Streaming;
 
 use crate::api::rpc::flight_actions::FlightAction;
 use crate::api::rpc::request_builder::RequestGetter;
+use crate::api::rpc::FragmentStreamId;
+use crate::api::rpc::PolledStream;
+use crate::api::rpc::StreamLiveness;
 use crate::api::DataExchangeManager;
+use crate::api::StreamResumeConf;
+use crate::clusters::ClusterDiscovery;
 use crate::interpreters::Interpreter;
 use crate::interpreters::KillInterpreter;
 use crate::interpreters::TruncateTableInterpreter;
@@ -124,17 +134,39 @@ impl FlightService for DatabendQueryFlightService {
                 )))
             }
             "exchange_fragment" => {
-                let target = request.get_metadata("x-target")?;
-                let query_id = request.get_metadata("x-query-id")?;
-                let fragment = request
-                    .get_metadata("x-fragment-id")?
-                    .parse::<usize>()
-                    .unwrap();
-
-                Ok(RawResponse::new(Box::pin(
-                    DataExchangeManager::instance()
-                        .handle_exchange_fragment(query_id, target, fragment)?,
-                )))
+                let stream_id = FragmentStreamId::from_request(&request)?;
+
+                let exchange_manager = DataExchangeManager::instance();
+                let receiver = match request.get_opt_metadata("x-retained-blocks")? {
+                    None => exchange_manager.handle_exchange_fragment(stream_id.clone())?,
+                    Some(retained_blocks) => {
+                        let conf = StreamResumeConf {
+                            retained_blocks: parse_metadata(&retained_blocks)?,
+                            timeout_secs: parse_metadata(
+                                &request.get_metadata("x-resume-timeout")?,
+                            )?,
+                            ..Default::default()
+                        };
+                        let resume_from = match request.get_opt_metadata("x-resume-from")? {
+                            None => None,
+                            Some(resume_from) => Some(parse_metadata(&resume_from)?),
+                        };
+
+                        exchange_manager.handle_resumable_exchange_fragment(
+                            stream_id.clone(),
+                            &conf,
+                            resume_from,
+                        )?
+                    }
+                };
+
+                // The polls of the consumer tell the sender whether the consumer is still there.
+                let liveness = exchange_manager
+                    .get_stream_liveness(&stream_id)
+                    .unwrap_or_else(StreamLiveness::create);
+                Ok(RawResponse::new(Box::pin(PolledStream::create(
+                    receiver, liveness,
+                ))))
             }
             exchange_type => Err(Status::unimplemented(format!(
                 "Unimplemented exchange type: {:?}",
@@ -250,6 +282,25 @@ impl FlightService for DatabendQueryFlightService {
                     interpreter.execute2().await?;
                     FlightResult { body: vec![] }
                 }
+                FlightAction::KillQueryFragments(query_id) => {
+                    DataExchangeManager::instance().kill_query_fragments(
+                        &query_id,
+                        ErrorCode::AbortedQuery(
+                            "Aborted query, because the query was killed on the coordinator node",
+                        ),
+                    );
+                    FlightResult { body: vec![] }
+                }
+                FlightAction::DrainNode(timeout_secs) => {
+                    ClusterDiscovery::instance()
+                        .drain(Duration::from_secs(timeout_secs))
+                        .await?;
+                    FlightResult { body: vec![] }
+                }
+                FlightAction::ResetQueryProfile => {
+                    QueryProfileStore::instance().reset();
+                    FlightResult { body: vec![] }
+                }
             };
 
             Ok(RawResponse::new(
@@ -280,3 +331,16 @@ impl FlightService for DatabendQueryFlightService {
             .await
     }
 }
+
+fn parse_metadata<T>(value: &str) -> Result<T, Status>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    value.parse::<T>().map_err(|cause| {
+        Status::invalid_argument(format!(
+            "Cannot parse metadata value {:?}, cause: {:?}",
+            value, cause
+        ))
+    })
+}
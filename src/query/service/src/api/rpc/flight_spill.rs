@@ -0,0 +1,321 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_channel::Sender;
+use databend_common_arrow::arrow_format::flight::data::FlightData;
+use databend_common_base::base::tokio;
+use databend_common_base::base::tokio::sync::Notify;
+use databend_common_config::InnerConfig;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use log::warn;
+use minitrace::full_name;
+use minitrace::future::FutureExt;
+use minitrace::Span;
+use parking_lot::Mutex;
+use prost::Message;
+use tonic::Status;
+use uuid::Uuid;
+
+/// When and where the packets of a fragment stream are spilled to the local disk.
+#[derive(Debug, Clone, Default)]
+pub struct StreamSpillConf {
+    /// The max bytes of the packets sent but not consumed yet held in memory, 0 disables
+    /// spilling.
+    pub max_memory_bytes: usize,
+    /// The directory of the spill files of the query, created when the first packet is
+    /// spilled and removed when the query is finished.
+    pub spill_dir: PathBuf,
+}
+
+impl StreamSpillConf {
+    pub fn is_enabled(&self) -> bool {
+        self.max_memory_bytes != 0
+    }
+}
+
+/// The producer side of a fragment stream which spills the packets its consumer falls
+/// behind on to the local disk, instead of blocking the producer.
+///
+/// The packets are sent to the channel while the packets in it take less than
+/// `max_memory_bytes`, an empty channel takes a packet of any size. The following packets are written to a file each, and sent to the
+/// channel in the send order as the consumer catches up. A file is deleted once its packet
+/// is sent, or when the stream is dropped. The packets are sent by one producer at a time.
+pub struct StreamSpiller {
+    conf: StreamSpillConf,
+    tx: Sender<Result<FlightData, Status>>,
+    state: Mutex<SpillState>,
+    spilled: Notify,
+}
+
+#[derive(Default)]
+struct SpillState {
+    // The size of the packets in the channel, in the send order.
+    in_memory: VecDeque<usize>,
+    // The files of the spilled packets, in the send order.
+    spilled: VecDeque<PathBuf>,
+    replaying: bool,
+    // No more packets are sent, the channel is closed once the spilled packets are sent.
+    finished: bool,
+    error: Option<ErrorCode>,
+}
+
+impl StreamSpiller {
+    pub fn create(
+        conf: StreamSpillConf,
+        tx: Sender<Result<FlightData, Status>>,
+    ) -> Arc<StreamSpiller> {
+        Arc::new(StreamSpiller {
+            conf,
+            tx,
+            state: Mutex::new(SpillState::default()),
+            spilled: Notify::new(),
+        })
+    }
+
+    #[async_backtrace::framed]
+    pub async fn send(self: &Arc<Self>, data: FlightData) -> Result<()> {
+        let size = data.encoded_len();
+        {
+            let mut state = self.state.lock();
+            if let Some(error) = &state.error {
+                return Err(error.clone());
+            }
+
+            if self.tx.is_closed() {
+                return Err(channel_closed());
+            }
+
+            // The packets remaining in the channel are the latest ones.
+            while state.in_memory.len() > self.tx.len() {
+                state.in_memory.pop_front();
+            }
+
+            // The consumer waiting on an empty channel is never left waiting on the disk.
+            let in_memory = state.in_memory.iter().sum::<usize>();
+            if state.spilled.is_empty()
+                && !self.tx.is_full()
+                && (state.in_memory.is_empty() || in_memory + size <= self.conf.max_memory_bytes)
+            {
+                state.in_memory.push_back(size);
+                return match self.tx.try_send(Ok(data)) {
+                    Ok(_) => Ok(()),
+                    Err(_) => Err(channel_closed()),
+                };
+            }
+        }
+
+        let path = self.spill(&data).await.map_err(|cause| {
+            ErrorCode::ExchangeSpillFailed(format!(
+                "Cannot spill the exchange stream to {:?}, cause: {}",
+                self.conf.spill_dir, cause
+            ))
+        })?;
+
+        let mut state = self.state.lock();
+        if let Some(error) = &state.error {
+            remove_spill_file(&path);
+            return Err(error.clone());
+        }
+
+        state.spilled.push_back(path);
+        if !state.replaying {
+            state.replaying = true;
+            let fut = self
+                .clone()
+                .replay()
+                .in_span(Span::enter_with_local_parent(full_name!()));
+            databend_common_base::runtime::spawn(fut);
+        }
+
+        self.spilled.notify_one();
+        Ok(())
+    }
+
+    async fn spill(&self, data: &FlightData) -> std::io::Result<PathBuf> {
+        let path = self
+            .conf
+            .spill_dir
+            .join(format!("{}.exchange", Uuid::new_v4().simple()));
+
+        tokio::fs::create_dir_all(&self.conf.spill_dir).await?;
+        if let Err(cause) = tokio::fs::write(&path, data.encode_to_vec()).await {
+            remove_spill_file(&path);
+            return Err(cause);
+        }
+
+        Ok(path)
+    }
+
+    /// No more packets are sent. The channel is closed after the spilled packets are sent.
+    pub fn finish(&self) {
+        let mut state = self.state.lock();
+        state.finished = true;
+        if state.replaying {
+            self.spilled.notify_one();
+        } else {
+            self.tx.close();
+        }
+    }
+
+    // Sends the spilled packets to the channel in the send order, until the stream is finished.
+    async fn replay(self: Arc<Self>) {
+        loop {
+            let path = {
+                let state = self.state.lock();
+                match (state.spilled.front(), state.finished) {
+                    (Some(path), _) => Some(path.clone()),
+                    (None, true) => break,
+                    (None, false) => None,
+                }
+            };
+
+            let Some(path) = path else {
+                self.spilled.notified().await;
+                continue;
+            };
+
+            let data = match tokio::fs::read(&path).await {
+                Ok(bytes) => {
+                    FlightData::decode(bytes.as_slice()).map_err(|cause| cause.to_string())
+                }
+                Err(cause) => Err(cause.to_string()),
+            };
+
+            let data = match data {
+                Ok(data) => data,
+                Err(cause) => {
+                    let error = ErrorCode::ExchangeSpillFailed(format!(
+                        "Cannot read the spilled exchange stream from {:?}, cause: {}",
+                        path, cause
+                    ));
+
+                    // Only the stream fails, its consumer receives the error.
+                    let _ = self.tx.send(Err(Status::from(error.clone()))).await;
+                    self.abort(error);
+                    return;
+                }
+            };
+
+            let size = data.encoded_len();
+            if self.tx.send(Ok(data)).await.is_err() {
+                // The consumer closed the stream, the spilled packets are not needed anymore.
+                self.abort(channel_closed());
+                return;
+            }
+
+            let mut state = self.state.lock();
+            state.spilled.pop_front();
+            state.in_memory.push_back(size);
+            remove_spill_file(&path);
+        }
+
+        self.tx.close();
+    }
+
+    fn abort(&self, error: ErrorCode) {
+        let mut state = self.state.lock();
+        for path in state.spilled.drain(..) {
+            remove_spill_file(&path);
+        }
+
+        state.error = Some(error);
+        self.tx.close();
+    }
+}
+
+impl Drop for StreamSpiller {
+    fn drop(&mut self) {
+        for path in self.state.get_mut().spilled.drain(..) {
+            remove_spill_file(&path);
+        }
+    }
+}
+
+/// The directory of the exchange spill files of all the queries, which holds a directory
+/// for each query.
+pub fn exchange_spill_root(config: &InnerConfig) -> PathBuf {
+    match config.query.exchange_spill_dir.as_str() {
+        "" => std::env::temp_dir().join("databend_exchange_spill"),
+        spill_dir => PathBuf::from(spill_dir),
+    }
+}
+
+/// The directory of the exchange spill files of a query.
+pub fn query_spill_dir(spill_root: &Path, query_id: &str) -> PathBuf {
+    // The query id may be set by the client, it must not escape the root.
+    let name = query_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    spill_root.join(name)
+}
+
+/// Removes the directories of the queries left behind, e.g. by a crash, when the node starts.
+pub fn remove_stale_spill_dirs(spill_root: &Path) {
+    let entries = match std::fs::read_dir(spill_root) {
+        Ok(entries) => entries,
+        Err(cause) if cause.kind() == std::io::ErrorKind::NotFound => return,
+        Err(cause) => {
+            warn!(
+                "Cannot list the exchange spill directory {:?}: {:?}",
+                spill_root, cause
+            );
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+            remove_spill_dir(&entry.path());
+        }
+    }
+}
+
+pub fn remove_spill_dir(path: &Path) {
+    if let Err(cause) = std::fs::remove_dir_all(path) {
+        if cause.kind() != std::io::ErrorKind::NotFound {
+            warn!(
+                "Cannot remove the exchange spill directory {:?}: {:?}",
+                path, cause
+            );
+        }
+    }
+}
+
+fn remove_spill_file(path: &Path) {
+    if let Err(cause) = std::fs::remove_file(path) {
+        if cause.kind() != std::io::ErrorKind::NotFound {
+            warn!(
+                "Cannot remove the exchange spill file {:?}: {:?}",
+                path, cause
+            );
+        }
+    }
+}
+
+fn channel_closed() -> ErrorCode {
+    ErrorCode::AbortedQuery("Aborted query, because the remote flight channel is closed.")
+}
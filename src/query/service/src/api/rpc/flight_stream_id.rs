@@ -0,0 +1,135 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use databend_common_arrow::arrow_format::flight::data::Ticket;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use tonic::Request;
+
+use crate::api::rpc::request_builder::RequestGetter;
+
+/// The identifier of the stream of a fragment sent to a target node, it's
+/// displayed as `query_id/target/fragment` and carried in the flight ticket.
+///
+/// The `/` and `%` in the query id and the target are escaped as `%2F` and `%25`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FragmentStreamId {
+    pub query_id: String,
+    pub target: String,
+    pub fragment: usize,
+}
+
+impl FragmentStreamId {
+    pub fn create(query_id: &str, target: &str, fragment: usize) -> Self {
+        FragmentStreamId {
+            query_id: query_id.to_string(),
+            target: target.to_string(),
+            fragment,
+        }
+    }
+
+    pub fn to_ticket(&self) -> Ticket {
+        Ticket {
+            ticket: self.to_string().into_bytes(),
+        }
+    }
+
+    /// Reads the id from the ticket of the request. The requests of the older nodes
+    /// have an empty ticket, the id is read from the `x-query-id`, `x-target` and
+    /// `x-fragment-id` metadata.
+    pub fn from_request(request: &Request<Ticket>) -> Result<Self> {
+        let ticket = &request.get_ref().ticket;
+        if !ticket.is_empty() {
+            return match std::str::from_utf8(ticket) {
+                Ok(ticket) => ticket.parse(),
+                Err(cause) => Err(ErrorCode::BadBytes(format!(
+                    "Cannot parse fragment stream ticket, cause: {:?}",
+                    cause
+                ))),
+            };
+        }
+
+        let query_id = request.get_metadata("x-query-id")?;
+        let target = request.get_metadata("x-target")?;
+        let fragment = request.get_metadata("x-fragment-id")?;
+        match fragment.parse::<usize>() {
+            Ok(fragment) => Ok(Self::create(&query_id, &target, fragment)),
+            Err(cause) => Err(ErrorCode::BadArguments(format!(
+                "Invalid fragment id {:?} of fragment stream, cause: {:?}",
+                fragment, cause
+            ))),
+        }
+    }
+}
+
+impl Display for FragmentStreamId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}/{}",
+            escape(&self.query_id),
+            escape(&self.target),
+            self.fragment
+        )
+    }
+}
+
+impl FromStr for FragmentStreamId {
+    type Err = ErrorCode;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            ErrorCode::BadArguments(format!(
+                "Invalid fragment stream id {:?}, expect query_id/target/fragment",
+                s
+            ))
+        };
+
+        let components = s.split('/').collect::<Vec<_>>();
+        match components.as_slice() {
+            [query_id, target, fragment] if !query_id.is_empty() && !target.is_empty() => {
+                Ok(FragmentStreamId {
+                    query_id: unescape(query_id).ok_or_else(invalid)?,
+                    target: unescape(target).ok_or_else(invalid)?,
+                    fragment: fragment.parse::<usize>().map_err(|_| invalid())?,
+                })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+fn escape(component: &str) -> String {
+    component.replace('%', "%25").replace('/', "%2F")
+}
+
+fn unescape(component: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(component.len());
+    let mut rest = component;
+    while let Some(pos) = rest.find('%') {
+        unescaped.push_str(&rest[..pos]);
+        match rest.get(pos + 1..pos + 3) {
+            Some("25") => unescaped.push('%'),
+            Some("2F") | Some("2f") => unescaped.push('/'),
+            _ => return None,
+        }
+        rest = &rest[pos + 3..];
+    }
+    unescaped.push_str(rest);
+    Some(unescaped)
+}
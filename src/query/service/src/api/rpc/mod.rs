@@ -14,18 +14,42 @@
 
 pub use flight_actions::FlightAction;
 pub use flight_client::FlightClient;
+pub use flight_client::FlightReceiver;
+pub use flight_client::FlightSender;
+pub use flight_client::OrderedFlightReceiver;
+pub use flight_client::StreamLagLimits;
+pub use flight_client::StreamSentStatistics;
+pub use flight_liveness::PolledStream;
+pub use flight_liveness::StreamLiveness;
+pub use flight_resume::resumable_receiver;
+pub use flight_resume::ResumableStream;
+pub use flight_resume::StreamResumeConf;
 pub use flight_service::DatabendQueryFlightService;
+pub use flight_spill::exchange_spill_root;
+pub use flight_spill::query_spill_dir;
+pub use flight_spill::remove_spill_dir;
+pub use flight_spill::remove_stale_spill_dirs;
+pub use flight_spill::StreamSpillConf;
+pub use flight_spill::StreamSpiller;
+pub use flight_stream_id::FragmentStreamId;
 
 mod exchange;
 mod flight_actions;
 mod flight_client;
+mod flight_liveness;
+mod flight_resume;
 mod flight_scatter;
 mod flight_scatter_broadcast;
 mod flight_scatter_hash;
+mod flight_scatter_range;
+mod flight_scatter_round_robin;
 mod flight_service;
+mod flight_spill;
+mod flight_stream_id;
 mod packets;
 mod request_builder;
 
+pub use exchange::exchange_shuffle;
 pub use exchange::serialize_block;
 pub use exchange::BroadcastExchange;
 pub use exchange::DataExchange;
@@ -35,22 +59,35 @@ pub use exchange::ExchangeDeserializeMeta;
 pub use exchange::ExchangeInjector;
 pub use exchange::ExchangeSerializeMeta;
 pub use exchange::ExchangeShuffleMeta;
+pub use exchange::ExchangeSortDesc;
 pub use exchange::ExchangeSorting;
+pub use exchange::FragmentResultCache;
 pub use exchange::MergeExchange;
 pub use exchange::MergeExchangeParams;
+pub use exchange::RunningStageInfo;
+pub use exchange::RunningStreamInfo;
+pub use exchange::ScatterPolicy;
 pub use exchange::ShuffleDataExchange;
 pub use exchange::ShuffleExchangeParams;
+pub use exchange::SortBoundsMeta;
+pub use exchange::SortedExchangeMerge;
 pub use exchange::TransformExchangeDeserializer;
+pub use exchange::TransformSortBounds;
 pub use flight_scatter::FlightScatter;
 pub use flight_scatter_broadcast::BroadcastFlightScatter;
 pub use flight_scatter_hash::HashFlightScatter;
+pub use flight_scatter_range::RangeFlightScatter;
+pub use flight_scatter_round_robin::RoundRobinFlightScatter;
 pub use packets::ConnectionInfo;
 pub use packets::DataPacket;
+pub use packets::DrainNodePacket;
 pub use packets::ExecutePartialQueryPacket;
 pub use packets::FragmentData;
 pub use packets::FragmentPlanPacket;
 pub use packets::InitNodesChannelPacket;
+pub use packets::KillQueryFragmentsPacket;
 pub use packets::KillQueryPacket;
 pub use packets::Packet;
 pub use packets::QueryFragmentsPlanPacket;
+pub use packets::ResetQueryProfilePacket;
 pub use packets::TruncateTablePacket;
@@ -15,21 +15,27 @@
 mod packet;
 mod packet_data;
 mod packet_data_progressinfo;
+mod packet_drain_node;
 mod packet_execute;
 mod packet_executor;
 mod packet_fragment;
 mod packet_kill_query;
+mod packet_kill_query_fragments;
 mod packet_publisher;
+mod packet_reset_query_profile;
 mod packet_truncate_table;
 
 pub use packet::Packet;
 pub use packet_data::DataPacket;
 pub use packet_data::FragmentData;
 pub use packet_data_progressinfo::ProgressInfo;
+pub use packet_drain_node::DrainNodePacket;
 pub use packet_execute::ExecutePartialQueryPacket;
 pub use packet_executor::QueryFragmentsPlanPacket;
 pub use packet_fragment::FragmentPlanPacket;
 pub use packet_kill_query::KillQueryPacket;
+pub use packet_kill_query_fragments::KillQueryFragmentsPacket;
 pub use packet_publisher::ConnectionInfo;
 pub use packet_publisher::InitNodesChannelPacket;
+pub use packet_reset_query_profile::ResetQueryProfilePacket;
 pub use packet_truncate_table::TruncateTablePacket;
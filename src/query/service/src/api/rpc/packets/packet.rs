@@ -12,10 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use databend_common_arrow::arrow_format::flight::service::flight_service_client::FlightServiceClient;
 use databend_common_config::InnerConfig;
 use databend_common_exception::Result;
-use databend_common_grpc::ConnectionFactory;
 
 use crate::api::FlightClient;
 
@@ -38,17 +36,5 @@ impl<T: Packet> Packet for Vec<T> {
 
 #[async_backtrace::framed]
 pub async fn create_client(config: &InnerConfig, address: &str) -> Result<FlightClient> {
-    match config.tls_query_cli_enabled() {
-        true => Ok(FlightClient::new(FlightServiceClient::new(
-            ConnectionFactory::create_rpc_channel(
-                address.to_owned(),
-                None,
-                Some(config.query.to_rpc_client_tls_config()),
-            )
-            .await?,
-        ))),
-        false => Ok(FlightClient::new(FlightServiceClient::new(
-            ConnectionFactory::create_rpc_channel(address.to_owned(), None, None).await?,
-        ))),
-    }
+    FlightClient::connect(config, address, None).await
 }
@@ -23,6 +23,7 @@ use databend_common_arrow::arrow_format::flight::data::FlightData;
 use databend_common_catalog::statistics::data_cache_statistics::DataCacheMetricValues;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
+use databend_common_io::prelude::BinaryRead;
 use databend_common_pipeline_core::processors::PlanProfile;
 use databend_common_storage::CopyStatus;
 use databend_common_storage::MergeStatus;
@@ -43,6 +44,12 @@ impl FragmentData {
     pub fn create(meta: Vec<u8>, data: FlightData) -> FragmentData {
         FragmentData { meta, data }
     }
+
+    /// The number of rows, it's the header of the meta.
+    pub fn num_rows(&self) -> usize {
+        let mut header = self.get_meta();
+        header.read_scalar::<u32>().unwrap_or_default() as usize
+    }
 }
 
 impl Debug for FragmentData {
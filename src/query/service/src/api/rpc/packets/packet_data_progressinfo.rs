@@ -33,6 +33,8 @@ pub enum ProgressInfo {
     ScanProgress(ProgressValues),
     WriteProgress(ProgressValues),
     ResultProgress(ProgressValues),
+    /// Strings in arithmetic which became NULL with `loose_arithmetic_coercion`.
+    LooseCoercionFailures(u64),
 }
 
 impl ProgressInfo {
@@ -41,6 +43,9 @@ impl ProgressInfo {
             ProgressInfo::ScanProgress(values) => ctx.get_scan_progress().incr(values),
             ProgressInfo::WriteProgress(values) => ctx.get_write_progress().incr(values),
             ProgressInfo::ResultProgress(values) => ctx.get_result_progress().incr(values),
+            ProgressInfo::LooseCoercionFailures(failures) => {
+                ctx.add_loose_coercion_failures(*failures)
+            }
         };
     }
 
@@ -49,6 +54,10 @@ impl ProgressInfo {
             ProgressInfo::ScanProgress(values) => (1_u8, values),
             ProgressInfo::WriteProgress(values) => (2_u8, values),
             ProgressInfo::ResultProgress(values) => (3_u8, values),
+            ProgressInfo::LooseCoercionFailures(failures) => (4_u8, ProgressValues {
+                rows: failures as usize,
+                bytes: 0,
+            }),
         };
 
         bytes.write_u8(info_type)?;
@@ -66,6 +75,7 @@ impl ProgressInfo {
             1 => Ok(ProgressInfo::ScanProgress(ProgressValues { rows, bytes })),
             2 => Ok(ProgressInfo::WriteProgress(ProgressValues { rows, bytes })),
             3 => Ok(ProgressInfo::ResultProgress(ProgressValues { rows, bytes })),
+            4 => Ok(ProgressInfo::LooseCoercionFailures(rows as u64)),
             _ => Err(ErrorCode::Unimplemented(format!(
                 "Unimplemented progress info type, {}",
                 info_type
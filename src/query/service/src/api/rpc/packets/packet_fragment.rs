@@ -23,6 +23,8 @@ pub struct FragmentPlanPacket {
     pub physical_plan: PhysicalPlan,
     pub fragment_id: usize,
     pub data_exchange: Option<DataExchange>,
+    // The fragments whose data is consumed by this fragment.
+    pub source_fragments: Vec<usize>,
 }
 
 impl FragmentPlanPacket {
@@ -31,10 +33,12 @@ impl FragmentPlanPacket {
         physical_plan: PhysicalPlan,
         data_exchange: Option<DataExchange>,
     ) -> FragmentPlanPacket {
+        let source_fragments = physical_plan.source_fragment_ids();
         FragmentPlanPacket {
             physical_plan,
             fragment_id,
             data_exchange,
+            source_fragments,
         }
     }
 }
@@ -45,6 +49,7 @@ impl Debug for FragmentPlanPacket {
             .field("physical_plan", &self.physical_plan)
             .field("fragment_id", &self.fragment_id)
             .field("exchange", &self.data_exchange)
+            .field("source_fragments", &self.source_fragments)
             .finish()
     }
 }
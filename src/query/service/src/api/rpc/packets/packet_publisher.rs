@@ -21,6 +21,7 @@ use databend_common_meta_types::NodeInfo;
 use crate::api::rpc::flight_actions::InitNodesChannel;
 use crate::api::rpc::packets::packet::create_client;
 use crate::api::rpc::Packet;
+use crate::api::rpc::StreamResumeConf;
 use crate::api::FlightAction;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -42,6 +43,7 @@ pub struct InitNodesChannelPacket {
     pub fragment_connections_info: Vec<ConnectionInfo>,
     pub statistics_connections_info: Vec<ConnectionInfo>,
     pub create_rpc_clint_with_current_rt: bool,
+    pub stream_resume: StreamResumeConf,
 }
 
 impl InitNodesChannelPacket {
@@ -51,6 +53,7 @@ impl InitNodesChannelPacket {
         fragment_connections_info: Vec<ConnectionInfo>,
         statistics_connections_info: Vec<ConnectionInfo>,
         create_rpc_clint_with_current_rt: bool,
+        stream_resume: StreamResumeConf,
     ) -> InitNodesChannelPacket {
         InitNodesChannelPacket {
             query_id,
@@ -58,6 +61,7 @@ impl InitNodesChannelPacket {
             fragment_connections_info,
             statistics_connections_info,
             create_rpc_clint_with_current_rt,
+            stream_resume,
         }
     }
 }
@@ -52,6 +52,8 @@ impl<T> RequestBuilder<T> {
 
 pub trait RequestGetter {
     fn get_metadata(&self, key: &str) -> Result<String>;
+
+    fn get_opt_metadata(&self, key: &str) -> Result<Option<String>>;
 }
 
 impl<T> RequestGetter for Request<T> {
@@ -70,4 +72,11 @@ impl<T> RequestGetter for Request<T> {
             },
         }
     }
+
+    fn get_opt_metadata(&self, key: &str) -> Result<Option<String>> {
+        match self.metadata().get(key) {
+            None => Ok(None),
+            Some(_) => self.get_metadata(key).map(Some),
+        }
+    }
 }
@@ -15,6 +15,7 @@
 use std::future::Future;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use databend_common_arrow::arrow_format::flight::service::flight_service_server::FlightServiceServer;
 use databend_common_base::base::tokio;
@@ -29,6 +30,7 @@ use tonic::transport::Server;
 use tonic::transport::ServerTlsConfig;
 
 use crate::api::rpc::DatabendQueryFlightService;
+use crate::api::DataExchangeManager;
 use crate::servers::Server as DatabendQueryServer;
 
 pub struct RpcService {
@@ -53,25 +55,38 @@ impl RpcService {
 
     #[async_backtrace::framed]
     async fn server_tls_config(conf: &InnerConfig) -> Result<ServerTlsConfig> {
-        let cert = tokio::fs::read(conf.query.rpc_tls_server_cert.as_str()).await?;
-        let key = tokio::fs::read(conf.query.rpc_tls_server_key.as_str()).await?;
+        let cert =
+            Self::read_tls_file("rpc_tls_server_cert", &conf.query.rpc_tls_server_cert).await?;
+        let key = Self::read_tls_file("rpc_tls_server_key", &conf.query.rpc_tls_server_key).await?;
         let server_identity = Identity::from_pem(cert, key);
         let tls_conf = ServerTlsConfig::new().identity(server_identity);
         Ok(tls_conf)
     }
 
+    #[async_backtrace::framed]
+    async fn read_tls_file(name: &str, path: &str) -> Result<Vec<u8>> {
+        tokio::fs::read(path).await.map_err(|e| {
+            ErrorCode::TLSConfigurationFailure(format!("cannot read {name} \"{path}\": {e}"))
+        })
+    }
+
     #[async_backtrace::framed]
     pub async fn start_with_incoming(&mut self, addr: SocketAddr) -> Result<()> {
+        // A node with only one of them set would silently serve plaintext.
+        if self.config.query.rpc_tls_server_cert.is_empty()
+            != self.config.query.rpc_tls_server_key.is_empty()
+        {
+            return Err(ErrorCode::TLSConfigurationFailure(
+                "rpc_tls_server_cert and rpc_tls_server_key must be set together",
+            ));
+        }
+
         let flight_api_service = DatabendQueryFlightService::create();
         let builder = Server::builder();
         let mut builder = if self.config.tls_rpc_server_enabled() {
             info!("databend query tls rpc enabled");
             builder
-                .tls_config(Self::server_tls_config(&self.config).await.map_err(|e| {
-                    ErrorCode::TLSConfigurationFailure(format!(
-                        "failed to load server tls config: {e}",
-                    ))
-                })?)
+                .tls_config(Self::server_tls_config(&self.config).await?)
                 .map_err(|e| {
                     ErrorCode::TLSConfigurationFailure(format!("failed to invoke tls_config: {e}",))
                 })?
@@ -97,7 +112,15 @@ impl RpcService {
 #[async_trait::async_trait]
 impl DatabendQueryServer for RpcService {
     #[async_backtrace::framed]
-    async fn shutdown(&mut self, _graceful: bool) {}
+    async fn shutdown(&mut self, graceful: bool) {
+        // The node keeps serving the cluster until it is unregistered and the sessions are
+        // finished, then drains the exchanges before closing the listener.
+        if !graceful {
+            let timeout = Duration::from_millis(self.config.query.shutdown_wait_timeout_ms);
+            DataExchangeManager::instance().shutdown(timeout).await;
+            self.abort_notify.notify_waiters();
+        }
+    }
 
     #[async_backtrace::framed]
     async fn start(&mut self, addr: SocketAddr) -> Result<SocketAddr> {
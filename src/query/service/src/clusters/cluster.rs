@@ -21,7 +21,6 @@ use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 
-use databend_common_arrow::arrow_format::flight::service::flight_service_client::FlightServiceClient;
 use databend_common_base::base::tokio::sync::Mutex;
 use databend_common_base::base::tokio::sync::Notify;
 use databend_common_base::base::tokio::task::JoinHandle;
@@ -35,27 +34,31 @@ use databend_common_config::InnerConfig;
 use databend_common_config::DATABEND_COMMIT_VERSION;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
-use databend_common_grpc::ConnectionFactory;
 use databend_common_management::ClusterApi;
 use databend_common_management::ClusterMgr;
 use databend_common_meta_store::MetaStore;
 use databend_common_meta_store::MetaStoreProvider;
 use databend_common_meta_types::MatchSeq;
 use databend_common_meta_types::NodeInfo;
+use databend_common_meta_types::NodeStatus;
 use databend_common_metrics::cluster::*;
 use futures::future::select;
 use futures::future::Either;
 use futures::Future;
 use futures::StreamExt;
 use log::error;
+use log::info;
 use log::warn;
+use parking_lot::RwLock;
 use rand::thread_rng;
 use rand::Rng;
 
 use crate::api::FlightClient;
+use crate::sessions::SessionManager;
 
 pub struct ClusterDiscovery {
     local_id: String,
+    local_node: Arc<RwLock<NodeInfo>>,
     heartbeat: Mutex<ClusterHeartbeat>,
     api_provider: Arc<dyn ClusterApi>,
     cluster_id: String,
@@ -78,13 +81,18 @@ pub trait ClusterHelper {
 #[async_trait::async_trait]
 impl ClusterHelper for Cluster {
     fn create(nodes: Vec<Arc<NodeInfo>>, local_id: String) -> Arc<Cluster> {
-        Arc::new(Cluster { local_id, nodes })
+        Arc::new(Cluster {
+            local_id,
+            nodes,
+            draining_nodes: Vec::new(),
+        })
     }
 
     fn empty() -> Arc<Cluster> {
         Arc::new(Cluster {
             local_id: String::from(""),
             nodes: Vec::new(),
+            draining_nodes: Vec::new(),
         })
     }
 
@@ -104,24 +112,7 @@ impl ClusterHelper for Cluster {
     async fn create_node_conn(&self, name: &str, config: &InnerConfig) -> Result<FlightClient> {
         for node in &self.nodes {
             if node.id == name {
-                return match config.tls_query_cli_enabled() {
-                    true => Ok(FlightClient::new(FlightServiceClient::new(
-                        ConnectionFactory::create_rpc_channel(
-                            node.flight_address.clone(),
-                            None,
-                            Some(config.query.to_rpc_client_tls_config()),
-                        )
-                        .await?,
-                    ))),
-                    false => Ok(FlightClient::new(FlightServiceClient::new(
-                        ConnectionFactory::create_rpc_channel(
-                            node.flight_address.clone(),
-                            None,
-                            None,
-                        )
-                        .await?,
-                    ))),
-                };
+                return FlightClient::connect(config, &node.flight_address, None).await;
             }
         }
 
@@ -165,6 +156,7 @@ impl ClusterDiscovery {
 
         Ok(Arc::new(ClusterDiscovery {
             local_id: cfg.query.node_id.clone(),
+            local_node: Arc::new(RwLock::new(NodeInfo::default())),
             api_provider: provider.clone(),
             heartbeat: Mutex::new(ClusterHeartbeat::create(
                 lift_time,
@@ -211,8 +203,15 @@ impl ClusterDiscovery {
             }
             Ok(cluster_nodes) => {
                 let mut res = Vec::with_capacity(cluster_nodes.len());
+                let mut draining_nodes = vec![];
                 for node in &cluster_nodes {
                     if node.id != self.local_id {
+                        // Don't place new stages on the nodes going to shutdown.
+                        if !node.is_schedulable() {
+                            draining_nodes.push(Arc::new(node.clone()));
+                            continue;
+                        }
+
                         let start_at = Instant::now();
                         if let Err(cause) = create_client(config, &node.flight_address).await {
                             warn!(
@@ -236,7 +235,11 @@ impl ClusterDiscovery {
                     &self.flight_address,
                     cluster_nodes.len() as f64,
                 );
-                Ok(Cluster::create(res, self.local_id.clone()))
+                Ok(Arc::new(Cluster {
+                    local_id: self.local_id.clone(),
+                    nodes: res,
+                    draining_nodes,
+                }))
             }
         }
     }
@@ -338,18 +341,114 @@ impl ClusterDiscovery {
         );
 
         self.drop_invalid_nodes(&node_info).await?;
-        match self.api_provider.add_node(node_info.clone()).await {
-            Ok(_) => self.start_heartbeat(node_info).await,
+        *self.local_node.write() = node_info.clone();
+        match self.api_provider.add_node(node_info).await {
+            Ok(_) => self.start_heartbeat().await,
             Err(cause) => Err(cause.add_message_back("(while cluster api add_node).")),
         }
     }
 
     #[async_backtrace::framed]
-    async fn start_heartbeat(self: &Arc<Self>, node_info: NodeInfo) -> Result<()> {
+    async fn start_heartbeat(self: &Arc<Self>) -> Result<()> {
         let mut heartbeat = self.heartbeat.lock().await;
-        heartbeat.start(node_info);
+        heartbeat.start(self.local_node.clone());
         Ok(())
     }
+
+    pub fn local_status(&self) -> NodeStatus {
+        self.local_node.read().status
+    }
+
+    /// Drain the local node for a restart.
+    ///
+    /// The node is marked draining in the cluster membership at once, so the peers stop
+    /// placing new stages on it and new client queries are refused. It reports drained
+    /// once the running queries are finished, or the `timeout` is reached.
+    #[async_backtrace::framed]
+    pub async fn drain(self: &Arc<Self>, timeout: Duration) -> Result<()> {
+        let node_info = {
+            let mut local_node = self.local_node.write();
+            if local_node.status != NodeStatus::Running {
+                return Ok(());
+            }
+            local_node.status = NodeStatus::Draining;
+            local_node.clone()
+        };
+
+        if let Err(cause) = self
+            .api_provider
+            .update_node(node_info, MatchSeq::GE(1))
+            .await
+        {
+            self.local_node.write().status = NodeStatus::Running;
+            return Err(cause.add_message_back("(while cluster api update_node)."));
+        }
+        info!("Node {} is draining, timeout {:?}", self.local_id, timeout);
+
+        let discovery = self.clone();
+        databend_common_base::runtime::spawn(async move {
+            let deadline = Instant::now() + timeout;
+            let mut running_queries = Self::running_queries();
+            while running_queries > 0 && Instant::now() < deadline {
+                tokio_async_sleep(Duration::from_millis(100)).await;
+                running_queries = Self::running_queries();
+            }
+
+            if running_queries > 0 {
+                warn!(
+                    "Node {} is drained with {} queries still running after {:?}",
+                    discovery.local_id, running_queries, timeout
+                );
+            }
+
+            let node_info = {
+                let mut local_node = discovery.local_node.write();
+                local_node.status = NodeStatus::Drained;
+                local_node.clone()
+            };
+            match discovery
+                .api_provider
+                .update_node(node_info, MatchSeq::GE(1))
+                .await
+            {
+                Ok(_) => info!("Node {} is drained", discovery.local_id),
+                Err(cause) => error!(
+                    "Cannot mark node {} drained: {:?}",
+                    discovery.local_id, cause
+                ),
+            }
+        });
+        Ok(())
+    }
+
+    // The stages of the distributed queries run in FlightRPC sessions, so they are counted too.
+    fn running_queries() -> u64 {
+        SessionManager::instance()
+            .get_current_session_status()
+            .running_queries_count
+    }
+
+    /// Refuse new client queries once the local node is draining, the error lists the
+    /// running nodes of `cluster` to retry on.
+    pub fn check_admission(&self, cluster: &Cluster) -> Result<()> {
+        let status = self.local_status();
+        if status == NodeStatus::Running {
+            return Ok(());
+        }
+
+        let healthy_nodes = cluster
+            .nodes
+            .iter()
+            .filter(|node| !cluster.is_local(node) && node.status == NodeStatus::Running)
+            .map(|node| format!("{}({})", node.id, node.flight_address))
+            .collect::<Vec<_>>();
+        Err(ErrorCode::ClusterNodeDraining(format!(
+            "Node '{}' is {} and does not accept new queries, retry on one of the healthy nodes: [{}]",
+            self.local_id,
+            status,
+            healthy_nodes.join(", ")
+        )))
+    }
 }
 
 struct ClusterHeartbeat {
@@ -380,7 +479,7 @@ impl ClusterHeartbeat {
         }
     }
 
-    fn heartbeat_loop(&self, node: NodeInfo) -> impl Future<Output = ()> + 'static {
+    fn heartbeat_loop(&self, node: Arc<RwLock<NodeInfo>>) -> impl Future<Output = ()> + 'static {
         let shutdown = self.shutdown.clone();
         let shutdown_notify = self.shutdown_notify.clone();
         let cluster_api = self.cluster_api.clone();
@@ -405,6 +504,8 @@ impl ClusterHeartbeat {
                     }
                     Either::Right((_, new_shutdown_notified)) => {
                         shutdown_notified = new_shutdown_notified;
+                        // Read the latest info, the status may be changed by drain.
+                        let node = node.read().clone();
                         let heartbeat = cluster_api.heartbeat(&node, MatchSeq::GE(1));
                         if let Err(failure) = heartbeat.await {
                             metric_incr_cluster_heartbeat_count(
@@ -426,7 +527,7 @@ impl ClusterHeartbeat {
         (duration / 3).as_millis()..=((duration / 3) * 2).as_millis()
     }
 
-    pub fn start(&mut self, node_info: NodeInfo) {
+    pub fn start(&mut self, node_info: Arc<RwLock<NodeInfo>>) {
         self.shutdown_handler = Some(databend_common_base::runtime::spawn(
             self.heartbeat_loop(node_info),
         ));
@@ -456,13 +557,5 @@ pub async fn create_client(config: &InnerConfig, address: &str) -> Result<Flight
         None
     };
 
-    let rpc_tls_config = if config.tls_query_cli_enabled() {
-        Some(config.query.to_rpc_client_tls_config())
-    } else {
-        None
-    };
-
-    Ok(FlightClient::new(FlightServiceClient::new(
-        ConnectionFactory::create_rpc_channel(address.to_owned(), timeout, rpc_tls_config).await?,
-    )))
+    FlightClient::connect(config, address, timeout).await
 }
@@ -30,6 +30,7 @@ use databend_common_storage::DataOperator;
 use databend_common_storage::ShareTableConfig;
 use databend_common_storages_hive::HiveCreator;
 use databend_common_storages_iceberg::IcebergCreator;
+use databend_common_storages_system::QueryProfileStore;
 use databend_common_tracing::GlobalLogger;
 use databend_common_users::RoleCacheManager;
 use databend_common_users::UserApiProvider;
@@ -96,9 +97,10 @@ impl GlobalServices {
         }
 
         HttpQueryManager::init(config).await?;
-        DataExchangeManager::init()?;
+        DataExchangeManager::init(config)?;
         SessionManager::init(config)?;
         LockManager::init()?;
+        QueryProfileStore::init(config.query.max_query_log_size)?;
         AuthMgr::init(config)?;
         UserApiProvider::init(
             config.meta.to_meta_grpc_client_conf(),
@@ -23,6 +23,7 @@ mod util;
 pub use grant::validate_grant_object_exists;
 pub use query_log::InterpreterQueryLog;
 pub use stream::build_update_stream_meta_seq;
+pub use table::check_referenced_check_constraints;
 pub use table::check_referenced_computed_columns;
 pub use task::get_client_config;
 pub use task::make_schedule_options;
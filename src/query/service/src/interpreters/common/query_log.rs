@@ -20,9 +20,12 @@ use databend_common_config::GlobalConfig;
 use databend_common_config::DATABEND_COMMIT_VERSION;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
+use databend_common_sql::query_fingerprint;
 use databend_common_storages_system::LogType;
 use databend_common_storages_system::QueryLogElement;
 use databend_common_storages_system::QueryLogQueue;
+use databend_common_storages_system::QueryProfileRecord;
+use databend_common_storages_system::QueryProfileStore;
 use log::error;
 use log::info;
 use serde_json;
@@ -195,7 +198,7 @@ impl InterpreterQueryLog {
             stack_trace,
             server_version: DATABEND_COMMIT_VERSION.to_string(),
             session_settings,
-            extra: "".to_string(),
+            extra: query_log_extra(ctx),
             has_profiles: false,
         })
     }
@@ -288,6 +291,16 @@ impl InterpreterQueryLog {
             error_fields(LogType::Finish, err);
         let log_type_name = log_type.as_string();
 
+        QueryProfileStore::instance().record(QueryProfileRecord {
+            fingerprint: query_fingerprint(&query_text),
+            query_text: query_text.clone(),
+            duration_ms: query_duration_ms as u64,
+            scan_rows,
+            scan_bytes,
+            is_error: exception_code != 0,
+            event_time,
+        });
+
         Self::write_log(QueryLogElement {
             log_type,
             log_type_name,
@@ -343,8 +356,23 @@ impl InterpreterQueryLog {
             stack_trace,
             server_version: DATABEND_COMMIT_VERSION.to_string(),
             session_settings,
-            extra: "".to_string(),
+            extra: query_log_extra(ctx),
             has_profiles,
         })
     }
 }
+
+fn query_log_extra(ctx: &QueryContext) -> String {
+    let mut extra = serde_json::Map::new();
+    if let Some(batch_id) = ctx.get_batch_id() {
+        extra.insert("batch_id".to_string(), batch_id.into());
+    }
+    if let Some(lineage) = ctx.get_query_lineage() {
+        extra.insert("lineage".to_string(), lineage);
+    }
+
+    match extra.is_empty() {
+        true => "".to_string(),
+        false => serde_json::Value::Object(extra).to_string(),
+    }
+}
@@ -12,14 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use databend_common_catalog::table_context::TableContext;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
+use databend_common_expression::types::DataType;
 use databend_common_expression::ComputedExpr;
 use databend_common_expression::DataSchemaRef;
 use databend_common_sql::parse_computed_expr;
+use databend_storages_common_table_meta::table::CheckConstraint;
 
 pub fn check_referenced_computed_columns(
     ctx: Arc<dyn TableContext>,
@@ -55,3 +58,24 @@ pub fn check_referenced_computed_columns(
     }
     Ok(())
 }
+
+/// Checks the CHECK constraints of the table are still boolean expressions over the altered
+/// schema, so an ALTER of a referenced column can't disable them.
+pub fn check_referenced_check_constraints(
+    ctx: Arc<dyn TableContext>,
+    schema: DataSchemaRef,
+    options: &BTreeMap<String, String>,
+    column: &str,
+) -> Result<()> {
+    for constraint in CheckConstraint::decode_from_options(options)? {
+        let is_valid = parse_computed_expr(ctx.clone(), schema.clone(), &constraint.expr)
+            .is_ok_and(|expr| expr.data_type().remove_nullable() == DataType::Boolean);
+        if !is_valid {
+            return Err(ErrorCode::ColumnReferencedByCheckConstraint(format!(
+                "column `{}` is referenced by CHECK constraint `{}` ({}), drop the constraint first",
+                column, constraint.name, constraint.expr
+            )));
+        }
+    }
+    Ok(())
+}
@@ -28,6 +28,7 @@ use databend_common_pipeline_core::SourcePipeBuilder;
 use log::error;
 use log::info;
 
+use crate::clusters::ClusterDiscovery;
 use crate::interpreters::interpreter_txn_commit::CommitInterpreter;
 use crate::interpreters::InterpreterMetrics;
 use crate::interpreters::InterpreterQueryLog;
@@ -51,8 +52,20 @@ pub trait Interpreter: Sync + Send {
         false
     }
 
+    /// The SET and UNSET statements skip the settings validation, so that
+    /// the invalid settings can be fixed.
+    fn is_setting_command(&self) -> bool {
+        false
+    }
+
     fn is_ddl(&self) -> bool;
 
+    /// The statements which only read the state of the node and the cluster, like SHOW,
+    /// SYSTEM and the queries of the system tables. A draining node still accepts them.
+    fn is_system_statement(&self) -> bool {
+        false
+    }
+
     /// The core of the databend processor which will execute the logical plan and get the DataBlock
     #[async_backtrace::framed]
     #[minitrace::trace]
@@ -65,6 +78,18 @@ pub trait Interpreter: Sync + Send {
             log_query_finished(&ctx, Some(err.clone()), false);
             return Err(err);
         }
+        if !self.is_system_statement() {
+            if let Err(err) = ClusterDiscovery::instance().check_admission(&ctx.get_cluster()) {
+                log_query_finished(&ctx, Some(err.clone()), false);
+                return Err(err);
+            }
+        }
+        if !self.is_setting_command() {
+            if let Err(err) = ctx.get_settings().check_interactions() {
+                log_query_finished(&ctx, Some(err.clone()), false);
+                return Err(err);
+            }
+        }
         if self.is_ddl() {
             CommitInterpreter::try_create(ctx.clone())?
                 .execute2()
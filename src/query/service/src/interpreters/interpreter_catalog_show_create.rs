@@ -51,6 +51,10 @@ impl Interpreter for ShowCreateCatalogInterpreter {
         true
     }
 
+    fn is_system_statement(&self) -> bool {
+        true
+    }
+
     #[async_backtrace::framed]
     async fn execute2(&self) -> Result<PipelineBuildResult> {
         let catalog = self.ctx.get_catalog(self.plan.catalog.as_str()).await?;
@@ -48,6 +48,10 @@ impl Interpreter for ShowConnectionsInterpreter {
         true
     }
 
+    fn is_system_statement(&self) -> bool {
+        true
+    }
+
     #[minitrace::trace]
     #[async_backtrace::framed]
     async fn execute2(&self) -> Result<PipelineBuildResult> {
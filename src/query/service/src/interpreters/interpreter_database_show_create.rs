@@ -49,6 +49,10 @@ impl Interpreter for ShowCreateDatabaseInterpreter {
         true
     }
 
+    fn is_system_statement(&self) -> bool {
+        true
+    }
+
     #[async_backtrace::framed]
     async fn execute2(&self) -> Result<PipelineBuildResult> {
         let tenant = self.ctx.get_tenant();
@@ -15,6 +15,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use databend_common_ast::ast::ExplainFormat;
 use databend_common_ast::ast::ExplainKind;
 use databend_common_ast::ast::FormatTreeNode;
 use databend_common_catalog::table_context::TableContext;
@@ -25,6 +26,8 @@ use databend_common_expression::DataBlock;
 use databend_common_expression::FromData;
 use databend_common_pipeline_core::processors::PlanProfile;
 use databend_common_sql::binder::ExplainConfig;
+use databend_common_sql::extract_lineage;
+use databend_common_sql::lineage_block;
 use databend_common_sql::optimizer::ColumnSet;
 use databend_common_sql::plans::UpdatePlan;
 use databend_common_sql::BindContext;
@@ -69,7 +72,7 @@ impl Interpreter for ExplainInterpreter {
 
     #[async_backtrace::framed]
     async fn execute2(&self) -> Result<PipelineBuildResult> {
-        let blocks = match &self.kind {
+        let nodes = match &self.kind {
             ExplainKind::Raw | ExplainKind::Optimized => self.explain_plan(&self.plan)?,
             ExplainKind::Plan if self.config.logical => self.explain_plan(&self.plan)?,
             ExplainKind::Plan => match &self.plan {
@@ -100,7 +103,7 @@ impl Interpreter for ExplainInterpreter {
                             res.extend(query);
                         }
                     }
-                    vec![DataBlock::concat(&res)?]
+                    res
                 }
                 Plan::CreateTable(plan) => match &plan.as_select {
                     Some(box Plan::Query {
@@ -110,15 +113,15 @@ impl Interpreter for ExplainInterpreter {
                         formatted_ast,
                         ..
                     }) => {
-                        let mut res =
-                            vec![DataBlock::new_from_columns(vec![StringType::from_data(
-                                vec!["CreateTableAsSelect:", ""],
-                            )])];
+                        let mut res = vec![
+                            FormatTreeNode::new("CreateTableAsSelect:".to_string()),
+                            FormatTreeNode::new("".to_string()),
+                        ];
                         res.extend(
                             self.explain_query(s_expr, metadata, bind_context, formatted_ast)
                                 .await?,
                         );
-                        vec![DataBlock::concat(&res)?]
+                        res
                     }
                     _ => self.explain_plan(&self.plan)?,
                 },
@@ -213,12 +216,26 @@ impl Interpreter for ExplainInterpreter {
                 ));
             }
 
+            ExplainKind::Lineage => {
+                let lineage = extract_lineage(&self.plan)?.ok_or_else(|| {
+                    ErrorCode::Unimplemented(
+                        "EXPLAIN LINEAGE only supports SELECT, INSERT SELECT and CREATE TABLE AS SELECT statements",
+                    )
+                })?;
+                return PipelineBuildResult::from_blocks(vec![lineage_block(&lineage)]);
+            }
+
             ExplainKind::Ast(display_string)
             | ExplainKind::Syntax(display_string)
-            | ExplainKind::Memo(display_string) => {
-                let line_split_result: Vec<&str> = display_string.lines().collect();
-                let column = StringType::from_data(line_split_result);
-                vec![DataBlock::new_from_columns(vec![column])]
+            | ExplainKind::Memo(display_string) => format_lines(display_string),
+        };
+
+        let blocks = match self.config.format {
+            ExplainFormat::Text => format_text(&nodes)?,
+            ExplainFormat::Json => {
+                // The join tree has no attributes, its leaves are the scanned tables.
+                let with_attributes = !matches!(self.kind, ExplainKind::Join);
+                format_json(&nodes, with_attributes)?
             }
         };
 
@@ -241,11 +258,9 @@ impl ExplainInterpreter {
         })
     }
 
-    pub fn explain_plan(&self, plan: &Plan) -> Result<Vec<DataBlock>> {
+    pub fn explain_plan(&self, plan: &Plan) -> Result<Vec<FormatTreeNode<String>>> {
         let result = plan.format_indent(self.config.verbose)?;
-        let line_split_result: Vec<&str> = result.lines().collect();
-        let formatted_plan = StringType::from_data(line_split_result);
-        Ok(vec![DataBlock::new_from_columns(vec![formatted_plan])])
+        Ok(format_lines(&result))
     }
 
     pub async fn explain_physical_plan(
@@ -253,7 +268,7 @@ impl ExplainInterpreter {
         plan: &PhysicalPlan,
         metadata: &MetadataRef,
         formatted_ast: &Option<String>,
-    ) -> Result<Vec<DataBlock>> {
+    ) -> Result<Vec<FormatTreeNode<String>>> {
         if self.ctx.get_settings().get_enable_query_result_cache()? && self.ctx.get_cacheable() {
             let key = gen_result_cache_key(formatted_ast.as_ref().unwrap());
             let kv_store = UserApiProvider::instance().get_meta_store_client();
@@ -275,52 +290,48 @@ impl ExplainInterpreter {
 
                 let format_tree =
                     FormatTreeNode::with_children("ReadQueryResultCache".to_string(), children);
-
-                let result = format_tree.format_pretty()?;
-                let line_split_result: Vec<&str> = result.lines().collect();
-                let formatted_plan = StringType::from_data(line_split_result);
-                return Ok(vec![DataBlock::new_from_columns(vec![formatted_plan])]);
+                return Ok(vec![format_tree]);
             }
         }
 
-        let result = plan
-            .format(metadata.clone(), Default::default())?
-            .format_pretty()?;
-        let line_split_result: Vec<&str> = result.lines().collect();
-        let formatted_plan = StringType::from_data(line_split_result);
-        Ok(vec![DataBlock::new_from_columns(vec![formatted_plan])])
+        let mut nodes = Self::format_hints(metadata);
+        nodes.push(plan.format(metadata.clone(), Default::default())?);
+        Ok(nodes)
     }
 
     pub fn explain_join_order(
         &self,
         plan: &PhysicalPlan,
         metadata: &MetadataRef,
-    ) -> Result<Vec<DataBlock>> {
-        let result = plan.format_join(metadata)?.format_pretty()?;
-        let line_split_result: Vec<&str> = result.lines().collect();
-        let formatted_plan = StringType::from_data(line_split_result);
-        Ok(vec![DataBlock::new_from_columns(vec![formatted_plan])])
+    ) -> Result<Vec<FormatTreeNode<String>>> {
+        let mut nodes = Self::format_hints(metadata);
+        nodes.push(plan.format_join(metadata)?);
+        Ok(nodes)
     }
 
-    fn format_pipeline(build_res: &PipelineBuildResult) -> Vec<DataBlock> {
-        let mut blocks = Vec::with_capacity(1 + build_res.sources_pipelines.len());
+    // Format the optimizer hints of the query and whether they took effect.
+    fn format_hints(metadata: &MetadataRef) -> Vec<FormatTreeNode<String>> {
+        let metadata = metadata.read();
+        if metadata.hints().is_empty() {
+            return vec![];
+        }
+
+        let children = metadata
+            .hints()
+            .iter()
+            .map(|(hint, state)| FormatTreeNode::new(format!("{}: {}", hint, state)))
+            .collect();
+        vec![FormatTreeNode::with_children("Hints".to_string(), children)]
+    }
+
+    fn format_pipeline(build_res: &PipelineBuildResult) -> Vec<FormatTreeNode<String>> {
         // Format root pipeline
-        let line_split_result = format!("{}", build_res.main_pipeline.display_indent())
-            .lines()
-            .map(|l| l.to_string())
-            .collect::<Vec<_>>();
-        let column = StringType::from_data(line_split_result);
-        blocks.push(DataBlock::new_from_columns(vec![column]));
+        let mut nodes = format_lines(&format!("{}", build_res.main_pipeline.display_indent()));
         // Format child pipelines
         for pipeline in build_res.sources_pipelines.iter() {
-            let line_split_result = format!("\n{}", pipeline.display_indent())
-                .lines()
-                .map(|l| l.to_string())
-                .collect::<Vec<_>>();
-            let column = StringType::from_data(line_split_result);
-            blocks.push(DataBlock::new_from_columns(vec![column]));
+            nodes.extend(format_lines(&format!("\n{}", pipeline.display_indent())));
         }
-        blocks
+        nodes
     }
 
     #[async_backtrace::framed]
@@ -329,7 +340,7 @@ impl ExplainInterpreter {
         s_expr: SExpr,
         metadata: MetadataRef,
         required: ColumnSet,
-    ) -> Result<Vec<DataBlock>> {
+    ) -> Result<Vec<FormatTreeNode<String>>> {
         let ctx = self.ctx.clone();
         let plan = PhysicalPlanBuilder::new(metadata.clone(), self.ctx.clone(), true)
             .build(&s_expr, required)
@@ -341,13 +352,14 @@ impl ExplainInterpreter {
         root_fragment.get_actions(ctx, &mut fragments_actions)?;
 
         let display_string = fragments_actions.display_indent(&metadata).to_string();
-        let line_split_result = display_string.lines().collect::<Vec<_>>();
-        let formatted_plan = StringType::from_data(line_split_result);
-        Ok(vec![DataBlock::new_from_columns(vec![formatted_plan])])
+        Ok(format_lines(&display_string))
     }
 
     #[async_backtrace::framed]
-    async fn explain_update_fragments(&self, update: &UpdatePlan) -> Result<Vec<DataBlock>> {
+    async fn explain_update_fragments(
+        &self,
+        update: &UpdatePlan,
+    ) -> Result<Vec<FormatTreeNode<String>>> {
         let interpreter = UpdateInterpreter::try_create(self.ctx.clone(), update.clone())?;
         let display_string = if let Some(plan) = interpreter.get_physical_plan().await? {
             let root_fragment = Fragmenter::try_create(self.ctx.clone())?.build_fragment(&plan)?;
@@ -360,9 +372,7 @@ impl ExplainInterpreter {
         } else {
             "Nothing to update".to_string()
         };
-        let line_split_result = display_string.lines().collect::<Vec<_>>();
-        let formatted_plan = StringType::from_data(line_split_result);
-        Ok(vec![DataBlock::new_from_columns(vec![formatted_plan])])
+        Ok(format_lines(&display_string))
     }
 
     #[async_backtrace::framed]
@@ -372,7 +382,7 @@ impl ExplainInterpreter {
         metadata: &MetadataRef,
         required: ColumnSet,
         ignore_result: bool,
-    ) -> Result<Vec<DataBlock>> {
+    ) -> Result<Vec<FormatTreeNode<String>>> {
         let mut builder = PhysicalPlanBuilder::new(metadata.clone(), self.ctx.clone(), true);
         let plan = builder.build(s_expr, required).await?;
         let build_res = build_query_pipeline(&self.ctx, &[], &plan, ignore_result).await?;
@@ -380,12 +390,13 @@ impl ExplainInterpreter {
         // Drain the data
         let query_profiles = self.execute_and_get_profiles(build_res)?;
 
-        let result = plan
-            .format(metadata.clone(), query_profiles)?
-            .format_pretty()?;
-        let line_split_result: Vec<&str> = result.lines().collect();
-        let formatted_plan = StringType::from_data(line_split_result);
-        Ok(vec![DataBlock::new_from_columns(vec![formatted_plan])])
+        let format_tree = if self.config.verbose {
+            let factor = self.ctx.get_settings().get_explain_misestimation_factor()?;
+            plan.format_with_estimation(metadata.clone(), query_profiles, factor as f64)?
+        } else {
+            plan.format(metadata.clone(), query_profiles)?
+        };
+        Ok(vec![format_tree])
     }
 
     fn execute_and_get_profiles(
@@ -445,7 +456,7 @@ impl ExplainInterpreter {
         metadata: &MetadataRef,
         bind_context: &BindContext,
         formatted_ast: &Option<String>,
-    ) -> Result<Vec<DataBlock>> {
+    ) -> Result<Vec<FormatTreeNode<String>>> {
         let ctx = self.ctx.clone();
         // If `formatted_ast` is Some, it means we may use query result cache.
         // If we use result cache for this query,
@@ -457,3 +468,58 @@ impl ExplainInterpreter {
             .await
     }
 }
+
+/// Each line of the text output is a node without children.
+fn format_lines(text: &str) -> Vec<FormatTreeNode<String>> {
+    text.lines()
+        .map(|line| FormatTreeNode::new(line.to_string()))
+        .collect()
+}
+
+fn format_text(nodes: &[FormatTreeNode<String>]) -> Result<Vec<DataBlock>> {
+    let mut lines = Vec::new();
+    for node in nodes {
+        let result = node.format_pretty()?;
+        lines.extend(result.lines().map(|line| line.to_string()));
+    }
+    let formatted_plan = StringType::from_data(lines);
+    Ok(vec![DataBlock::new_from_columns(vec![formatted_plan])])
+}
+
+/// Format the explain result as one JSON document:
+///
+/// ```text
+/// document: {"nodes": [<node>, ...]}
+/// node:     {"name": <string>, "attributes": {<string>: <string>, ...}, "children": [<node>, ...]}
+/// ```
+///
+/// The `key: value` leaves of a plan node become its attributes, other children are
+/// nested nodes. The outputs without a tree structure, like `EXPLAIN PIPELINE`, are
+/// a list of nodes, one per line.
+fn format_json(nodes: &[FormatTreeNode<String>], with_attributes: bool) -> Result<Vec<DataBlock>> {
+    let nodes = nodes
+        .iter()
+        .map(|node| node_to_json(node, with_attributes))
+        .collect::<Vec<_>>();
+    let document = serde_json::json!({ "nodes": nodes });
+    let formatted_plan = StringType::from_data(vec![document.to_string()]);
+    Ok(vec![DataBlock::new_from_columns(vec![formatted_plan])])
+}
+
+fn node_to_json(node: &FormatTreeNode<String>, with_attributes: bool) -> serde_json::Value {
+    let mut attributes = serde_json::Map::new();
+    let mut children = Vec::new();
+    for child in node.children.iter() {
+        match child.payload.split_once(": ") {
+            Some((key, value)) if with_attributes && child.children.is_empty() => {
+                attributes.insert(key.to_string(), serde_json::Value::from(value));
+            }
+            _ => children.push(node_to_json(child, with_attributes)),
+        }
+    }
+    serde_json::json!({
+        "name": node.payload,
+        "attributes": attributes,
+        "children": children,
+    })
+}
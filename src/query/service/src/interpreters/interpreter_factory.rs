@@ -16,7 +16,6 @@ use std::sync::Arc;
 
 use databend_common_ast::ast::ExplainKind;
 use databend_common_exception::Result;
-use databend_common_sql::binder::ExplainConfig;
 use log::error;
 
 use super::interpreter_catalog_create::CreateCatalogInterpreter;
@@ -104,23 +103,29 @@ impl InterpreterFactory {
                 kind.clone(),
                 config.clone(),
             )?)),
-            Plan::ExplainAst { formatted_string } => Ok(Arc::new(ExplainInterpreter::try_create(
+            Plan::ExplainAst {
+                formatted_string,
+                config,
+            } => Ok(Arc::new(ExplainInterpreter::try_create(
                 ctx,
                 plan.clone(),
                 ExplainKind::Ast(formatted_string.clone()),
-                ExplainConfig::default(),
+                config.clone(),
             )?)),
-            Plan::ExplainSyntax { formatted_sql } => Ok(Arc::new(ExplainInterpreter::try_create(
+            Plan::ExplainSyntax {
+                formatted_sql,
+                config,
+            } => Ok(Arc::new(ExplainInterpreter::try_create(
                 ctx,
                 plan.clone(),
                 ExplainKind::Syntax(formatted_sql.clone()),
-                ExplainConfig::default(),
+                config.clone(),
             )?)),
-            Plan::ExplainAnalyze { plan } => Ok(Arc::new(ExplainInterpreter::try_create(
+            Plan::ExplainAnalyze { config, plan } => Ok(Arc::new(ExplainInterpreter::try_create(
                 ctx,
                 *plan.clone(),
                 ExplainKind::AnalyzePlan,
-                ExplainConfig::default(),
+                config.clone(),
             )?)),
 
             Plan::CopyIntoTable(copy_plan) => Ok(Arc::new(CopyIntoTableInterpreter::try_create(
@@ -201,6 +206,12 @@ impl InterpreterFactory {
             Plan::DropTableColumn(drop_table_column) => Ok(Arc::new(
                 DropTableColumnInterpreter::try_create(ctx, *drop_table_column.clone())?,
             )),
+            Plan::AddTableConstraint(add_table_constraint) => Ok(Arc::new(
+                AddTableConstraintInterpreter::try_create(ctx, *add_table_constraint.clone())?,
+            )),
+            Plan::DropTableConstraint(drop_table_constraint) => Ok(Arc::new(
+                DropTableConstraintInterpreter::try_create(ctx, *drop_table_constraint.clone())?,
+            )),
             Plan::AlterTableClusterKey(alter_table_cluster_key) => Ok(Arc::new(
                 AlterTableClusterKeyInterpreter::try_create(ctx, *alter_table_cluster_key.clone())?,
             )),
@@ -409,6 +420,7 @@ impl InterpreterFactory {
                 *p.clone(),
             )?)),
             Plan::Kill(p) => Ok(Arc::new(KillInterpreter::try_create(ctx, *p.clone())?)),
+            Plan::System(p) => Ok(Arc::new(SystemInterpreter::try_create(ctx, *p.clone())?)),
 
             // share plans
             Plan::CreateShareEndpoint(p) => Ok(Arc::new(
@@ -47,6 +47,10 @@ impl Interpreter for ShowFileFormatsInterpreter {
         true
     }
 
+    fn is_system_statement(&self) -> bool {
+        true
+    }
+
     #[minitrace::trace]
     #[async_backtrace::framed]
     async fn execute2(&self) -> Result<PipelineBuildResult> {
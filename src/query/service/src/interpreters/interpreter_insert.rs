@@ -22,6 +22,7 @@ use databend_common_exception::Result;
 use databend_common_expression::DataSchema;
 use databend_common_meta_app::principal::StageFileFormatType;
 use databend_common_pipeline_sources::AsyncSourcer;
+use databend_common_sql::binder::check_null_coercion;
 use databend_common_sql::executor::physical_plans::DistributedInsertSelect;
 use databend_common_sql::executor::PhysicalPlan;
 use databend_common_sql::executor::PhysicalPlanBuilder;
@@ -66,6 +67,16 @@ impl InsertInterpreter {
             )));
         }
 
+        for (select_field, output_field) in
+            select_schema.fields().iter().zip(output_schema.fields())
+        {
+            check_null_coercion(
+                select_field.data_type(),
+                &output_field.data_type().into(),
+                output_field.name(),
+            )?;
+        }
+
         // check if cast needed
         let cast_needed = select_schema != DataSchema::from(output_schema.as_ref()).into();
         Ok(cast_needed)
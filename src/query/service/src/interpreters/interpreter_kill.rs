@@ -19,7 +19,9 @@ use databend_common_config::GlobalConfig;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
 use databend_common_sql::plans::KillPlan;
+use log::warn;
 
+use crate::api::KillQueryFragmentsPacket;
 use crate::api::KillQueryPacket;
 use crate::api::Packet;
 use crate::interpreters::Interpreter;
@@ -88,6 +90,37 @@ impl KillInterpreter {
         )))
     }
 
+    // The fragments of the query on the other nodes are not aware of the local kill,
+    // abort them explicitly.
+    #[async_backtrace::framed]
+    async fn kill_cluster_query_fragments(
+        &self,
+        query_id: Option<String>,
+    ) -> Result<PipelineBuildResult> {
+        let Some(query_id) = query_id else {
+            return Ok(PipelineBuildResult::create());
+        };
+
+        let settings = self.ctx.get_settings();
+        let timeout = settings.get_flight_client_timeout()?;
+        let conf = GlobalConfig::instance();
+        let cluster = self.ctx.get_cluster();
+        for node_info in &cluster.nodes {
+            if node_info.id != cluster.local_id {
+                let packet = KillQueryFragmentsPacket::create(query_id.clone(), node_info.clone());
+
+                if let Err(cause) = packet.commit(conf.as_ref(), timeout).await {
+                    warn!(
+                        "Cannot kill the fragments of query {} on node {}, cause: {:?}",
+                        query_id, node_info.id, cause
+                    );
+                }
+            }
+        }
+
+        Ok(PipelineBuildResult::create())
+    }
+
     #[async_backtrace::framed]
     async fn execute_kill(&self, session_id: &String) -> Result<PipelineBuildResult> {
         match self.ctx.get_session_by_id(session_id) {
@@ -99,14 +132,16 @@ impl KillInterpreter {
                 ))),
             },
             Some(kill_session) if self.plan.kill_connection => {
+                let query_id = kill_session.get_current_query_id();
                 kill_session.force_kill_session();
-                Ok(PipelineBuildResult::create())
+                self.kill_cluster_query_fragments(query_id).await
             }
             Some(kill_session) => {
+                let query_id = kill_session.get_current_query_id();
                 kill_session.force_kill_query(ErrorCode::AbortedQuery(
                     "Aborted query, because the server is shutting down or the query was killed",
                 ));
-                Ok(PipelineBuildResult::create())
+                self.kill_cluster_query_fragments(query_id).await
             }
         }
     }
@@ -46,6 +46,10 @@ impl Interpreter for ShowNetworkPoliciesInterpreter {
         true
     }
 
+    fn is_system_statement(&self) -> bool {
+        true
+    }
+
     #[async_backtrace::framed]
     async fn execute2(&self) -> Result<PipelineBuildResult> {
         let tenant = self.ctx.get_tenant();
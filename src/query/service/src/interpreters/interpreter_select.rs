@@ -224,6 +224,15 @@ impl Interpreter for SelectInterpreter {
         false
     }
 
+    fn is_system_statement(&self) -> bool {
+        let metadata = self.metadata.read();
+        let tables = metadata.tables();
+        !tables.is_empty()
+            && tables
+                .iter()
+                .all(|table| matches!(table.database(), "system" | "information_schema"))
+    }
+
     /// This method will create a new pipeline
     /// The QueryPipelineBuilder will use the optimized plan to generate a Pipeline
     #[minitrace::trace]
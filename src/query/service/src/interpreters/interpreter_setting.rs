@@ -62,6 +62,10 @@ impl Interpreter for SettingInterpreter {
         false
     }
 
+    fn is_setting_command(&self) -> bool {
+        true
+    }
+
     #[async_backtrace::framed]
     async fn execute2(&self) -> Result<PipelineBuildResult> {
         let plan = self.set.clone();
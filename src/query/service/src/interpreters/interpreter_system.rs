@@ -0,0 +1,114 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use databend_common_catalog::table_context::TableContext;
+use databend_common_config::GlobalConfig;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_sql::plans::SystemPlan;
+use databend_common_storages_system::QueryProfileStore;
+
+use crate::api::DrainNodePacket;
+use crate::api::Packet;
+use crate::api::ResetQueryProfilePacket;
+use crate::clusters::ClusterDiscovery;
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+
+pub struct SystemInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: SystemPlan,
+}
+
+impl SystemInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: SystemPlan) -> Result<Self> {
+        Ok(SystemInterpreter { ctx, plan })
+    }
+
+    #[async_backtrace::framed]
+    async fn drain_node(&self, node_id: &str) -> Result<PipelineBuildResult> {
+        let settings = self.ctx.get_settings();
+        let timeout_secs = settings.get_drain_node_timeout()?;
+        let cluster = self.ctx.get_cluster();
+        if node_id == cluster.local_id {
+            ClusterDiscovery::instance()
+                .drain(Duration::from_secs(timeout_secs))
+                .await?;
+            return Ok(PipelineBuildResult::create());
+        }
+
+        let Some(node_info) = cluster
+            .nodes
+            .iter()
+            .chain(cluster.draining_nodes.iter())
+            .find(|node| node.id == node_id)
+        else {
+            return Err(ErrorCode::ClusterUnknownNode(format!(
+                "Node with ID '{}' does not exist in the cluster.",
+                node_id
+            )));
+        };
+
+        let packet = DrainNodePacket::create(timeout_secs, node_info.clone());
+        let timeout = settings.get_flight_client_timeout()?;
+        packet
+            .commit(GlobalConfig::instance().as_ref(), timeout)
+            .await?;
+        Ok(PipelineBuildResult::create())
+    }
+
+    #[async_backtrace::framed]
+    async fn reset_query_profile(&self) -> Result<PipelineBuildResult> {
+        QueryProfileStore::instance().reset();
+
+        let cluster = self.ctx.get_cluster();
+        let timeout = self.ctx.get_settings().get_flight_client_timeout()?;
+        for node_info in cluster.nodes.iter() {
+            if node_info.id != cluster.local_id {
+                ResetQueryProfilePacket::create(node_info.clone())
+                    .commit(GlobalConfig::instance().as_ref(), timeout)
+                    .await?;
+            }
+        }
+        Ok(PipelineBuildResult::create())
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for SystemInterpreter {
+    fn name(&self) -> &str {
+        "SystemInterpreter"
+    }
+
+    fn is_ddl(&self) -> bool {
+        false
+    }
+
+    fn is_system_statement(&self) -> bool {
+        true
+    }
+
+    #[async_backtrace::framed]
+    #[minitrace::trace]
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        match &self.plan {
+            SystemPlan::DrainNode { node_id } => self.drain_node(node_id).await,
+            SystemPlan::ResetQueryProfile => self.reset_query_profile().await,
+        }
+    }
+}
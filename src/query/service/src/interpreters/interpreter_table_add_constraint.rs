@@ -0,0 +1,177 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use databend_common_base::runtime::GlobalIORuntime;
+use databend_common_catalog::table::TableExt;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_meta_app::schema::DatabaseType;
+use databend_common_meta_app::schema::UpsertTableOptionReq;
+use databend_common_meta_types::MatchSeq;
+use databend_common_pipeline_core::processors::ProcessorPtr;
+use databend_common_pipeline_sinks::EmptySink;
+use databend_common_sql::executor::PhysicalPlanBuilder;
+use databend_common_sql::plans::AddTableConstraintPlan;
+use databend_common_sql::plans::Plan;
+use databend_common_sql::Planner;
+use databend_common_storages_stream::stream_table::STREAM_ENGINE;
+use databend_common_storages_view::view_table::VIEW_ENGINE;
+use databend_storages_common_table_meta::table::CheckConstraint;
+use databend_storages_common_table_meta::table::OPT_KEY_CHECK_CONSTRAINTS;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::processors::transforms::TransformCheckConstraints;
+use crate::pipelines::PipelineBuildResult;
+use crate::schedulers::build_query_pipeline_without_render_result_set;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct AddTableConstraintInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: AddTableConstraintPlan,
+}
+
+impl AddTableConstraintInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: AddTableConstraintPlan) -> Result<Self> {
+        Ok(AddTableConstraintInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AddTableConstraintInterpreter {
+    fn name(&self) -> &str {
+        "AddTableConstraintInterpreter"
+    }
+
+    fn is_ddl(&self) -> bool {
+        true
+    }
+
+    #[async_backtrace::framed]
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let catalog_name = self.plan.catalog.as_str();
+        let db_name = self.plan.database.as_str();
+        let tbl_name = self.plan.table.as_str();
+        let catalog = self.ctx.get_catalog(catalog_name).await?;
+        let table = catalog
+            .get_table(self.ctx.get_tenant().as_str(), db_name, tbl_name)
+            .await?;
+
+        // check mutability
+        table.check_mutable()?;
+
+        let table_info = table.get_table_info();
+        let engine = table_info.engine();
+        if matches!(engine, VIEW_ENGINE | STREAM_ENGINE) {
+            return Err(ErrorCode::TableEngineNotSupported(format!(
+                "{}.{} engine is {} that doesn't support alter",
+                db_name, tbl_name, engine
+            )));
+        }
+        if table_info.db_type != DatabaseType::NormalDB {
+            return Err(ErrorCode::TableEngineNotSupported(format!(
+                "{}.{} doesn't support alter",
+                db_name, tbl_name
+            )));
+        }
+
+        let mut constraints = CheckConstraint::decode_from_options(table_info.options())?;
+        if constraints
+            .iter()
+            .any(|constraint| constraint.name == self.plan.constraint.name)
+        {
+            return Err(ErrorCode::BadArguments(format!(
+                "Duplicated constraint name: {}",
+                self.plan.constraint.name
+            )));
+        }
+        constraints.push(self.plan.constraint.clone());
+        let options = HashMap::from([(
+            OPT_KEY_CHECK_CONSTRAINTS.to_owned(),
+            Some(CheckConstraint::encode(&constraints)?),
+        )]);
+        let req = UpsertTableOptionReq {
+            table_id: table.get_id(),
+            // Any write that lands while the existing rows are validated bumps the seq,
+            // so the constraint is never attached to rows it has not seen.
+            seq: MatchSeq::Exact(table_info.ident.seq),
+            options,
+        };
+
+        // Validate the existing rows before the constraint is attached to the table.
+        let sql = format!(
+            "SELECT * FROM `{}`.`{}`.`{}`",
+            catalog_name, db_name, tbl_name
+        );
+        let mut planner = Planner::new(self.ctx.clone());
+        let (plan, _) = planner.plan_sql(&sql).await?;
+        let (select_plan, schema) = match plan {
+            Plan::Query {
+                s_expr,
+                metadata,
+                bind_context,
+                ..
+            } => {
+                let mut builder =
+                    PhysicalPlanBuilder::new(metadata.clone(), self.ctx.clone(), false);
+                (
+                    builder.build(&s_expr, bind_context.column_set()).await?,
+                    bind_context.output_schema(),
+                )
+            }
+            _ => unreachable!(),
+        };
+
+        let constraints = TransformCheckConstraints::try_bind_constraints(
+            self.ctx.clone(),
+            schema.clone(),
+            std::slice::from_ref(&self.plan.constraint),
+        )?;
+        let mut build_res =
+            build_query_pipeline_without_render_result_set(&self.ctx, &select_plan).await?;
+        build_res
+            .main_pipeline
+            .add_transform(|transform_input_port, transform_output_port| {
+                TransformCheckConstraints::try_create(
+                    self.ctx.clone(),
+                    transform_input_port,
+                    transform_output_port,
+                    schema.clone(),
+                    constraints.clone(),
+                )
+            })?;
+        build_res
+            .main_pipeline
+            .add_sink(|input| Ok(ProcessorPtr::create(EmptySink::create(input))))?;
+
+        let ctx = self.ctx.clone();
+        let db_name = db_name.to_string();
+        build_res
+            .main_pipeline
+            .set_on_finished(move |may_error| match may_error {
+                Ok(_) => GlobalIORuntime::instance().block_on(async move {
+                    catalog
+                        .upsert_table_option(ctx.get_tenant().as_str(), &db_name, req)
+                        .await?;
+                    Ok(())
+                }),
+                Err(error_code) => Err(error_code.clone()),
+            });
+
+        Ok(build_res)
+    }
+}
@@ -28,6 +28,7 @@ use databend_common_storages_stream::stream_table::STREAM_ENGINE;
 use databend_common_storages_view::view_table::VIEW_ENGINE;
 use databend_storages_common_table_meta::table::OPT_KEY_BLOOM_INDEX_COLUMNS;
 
+use crate::interpreters::common::check_referenced_check_constraints;
 use crate::interpreters::common::check_referenced_computed_columns;
 use crate::interpreters::interpreter_table_add_column::generate_new_snapshot;
 use crate::interpreters::Interpreter;
@@ -88,15 +89,24 @@ impl Interpreter for DropTableColumnInterpreter {
 
         let mut schema: DataSchema = table_info.schema().into();
         let field = schema.field_with_name(self.plan.column.as_str())?;
-        if field.computed_expr().is_none() {
-            schema.drop_column(self.plan.column.as_str())?;
+        let is_computed = field.computed_expr().is_some();
+        schema.drop_column(self.plan.column.as_str())?;
+        let schema = Arc::new(schema);
+        if !is_computed {
             // Check if this column is referenced by computed columns.
             check_referenced_computed_columns(
                 self.ctx.clone(),
-                Arc::new(schema),
+                schema.clone(),
                 self.plan.column.as_str(),
             )?;
         }
+        // Check if this column is referenced by check constraints.
+        check_referenced_check_constraints(
+            self.ctx.clone(),
+            schema,
+            table_info.options(),
+            self.plan.column.as_str(),
+        )?;
 
         let catalog = self.ctx.get_catalog(catalog_name).await?;
         let mut new_table_meta = table.get_table_info().meta.clone();
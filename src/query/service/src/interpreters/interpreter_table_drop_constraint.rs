@@ -0,0 +1,95 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use databend_common_catalog::table::TableExt;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_meta_app::schema::UpsertTableOptionReq;
+use databend_common_meta_types::MatchSeq;
+use databend_common_sql::plans::DropTableConstraintPlan;
+use databend_storages_common_table_meta::table::CheckConstraint;
+use databend_storages_common_table_meta::table::OPT_KEY_CHECK_CONSTRAINTS;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct DropTableConstraintInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: DropTableConstraintPlan,
+}
+
+impl DropTableConstraintInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: DropTableConstraintPlan) -> Result<Self> {
+        Ok(DropTableConstraintInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for DropTableConstraintInterpreter {
+    fn name(&self) -> &str {
+        "DropTableConstraintInterpreter"
+    }
+
+    fn is_ddl(&self) -> bool {
+        true
+    }
+
+    #[async_backtrace::framed]
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let db_name = self.plan.database.as_str();
+        let tbl_name = self.plan.table.as_str();
+        let catalog = self.ctx.get_catalog(self.plan.catalog.as_str()).await?;
+        let table = catalog
+            .get_table(self.ctx.get_tenant().as_str(), db_name, tbl_name)
+            .await?;
+
+        // check mutability
+        table.check_mutable()?;
+
+        let table_info = table.get_table_info();
+        let mut constraints = CheckConstraint::decode_from_options(table_info.options())?;
+        let Some(pos) = constraints
+            .iter()
+            .position(|constraint| constraint.name == self.plan.name)
+        else {
+            return Err(ErrorCode::BadArguments(format!(
+                "CHECK constraint `{}` does not exist in table {}.{}",
+                self.plan.name, db_name, tbl_name
+            )));
+        };
+        constraints.remove(pos);
+
+        // `None` removes the option.
+        let value = if constraints.is_empty() {
+            None
+        } else {
+            Some(CheckConstraint::encode(&constraints)?)
+        };
+        let req = UpsertTableOptionReq {
+            table_id: table.get_id(),
+            seq: MatchSeq::Exact(table_info.ident.seq),
+            options: HashMap::from([(OPT_KEY_CHECK_CONSTRAINTS.to_owned(), value)]),
+        };
+
+        catalog
+            .upsert_table_option(self.ctx.get_tenant().as_str(), db_name, req)
+            .await?;
+        Ok(PipelineBuildResult::create())
+    }
+}
@@ -51,6 +51,7 @@ use databend_enterprise_data_mask_feature::get_datamask_handler;
 use databend_storages_common_index::BloomIndex;
 use databend_storages_common_table_meta::table::OPT_KEY_BLOOM_INDEX_COLUMNS;
 
+use super::common::check_referenced_check_constraints;
 use super::common::check_referenced_computed_columns;
 use crate::interpreters::Interpreter;
 use crate::locks::LockManager;
@@ -250,9 +251,17 @@ impl ModifyTableColumnInterpreter {
                     // Check if this column is referenced by computed columns.
                     let mut data_schema: DataSchema = table_info.schema().into();
                     data_schema.set_field_type(i, data_type.into());
+                    let data_schema = Arc::new(data_schema);
                     check_referenced_computed_columns(
                         self.ctx.clone(),
-                        Arc::new(data_schema),
+                        data_schema.clone(),
+                        column,
+                    )?;
+                    // Check if this column is referenced by check constraints.
+                    check_referenced_check_constraints(
+                        self.ctx.clone(),
+                        data_schema,
+                        table_info.options(),
                         column,
                     )?;
 
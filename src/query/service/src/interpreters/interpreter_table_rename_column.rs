@@ -28,6 +28,7 @@ use databend_common_storages_stream::stream_table::STREAM_ENGINE;
 use databend_common_storages_view::view_table::VIEW_ENGINE;
 use databend_storages_common_table_meta::table::OPT_KEY_BLOOM_INDEX_COLUMNS;
 
+use crate::interpreters::common::check_referenced_check_constraints;
 use crate::interpreters::common::check_referenced_computed_columns;
 use crate::interpreters::interpreter_table_create::is_valid_column;
 use crate::interpreters::Interpreter;
@@ -95,16 +96,25 @@ impl Interpreter for RenameTableColumnInterpreter {
 
             let mut schema: DataSchema = table_info.schema().into();
             let field = schema.field_with_name(self.plan.old_column.as_str())?;
-            if field.computed_expr().is_none() {
-                let index = schema.index_of(self.plan.old_column.as_str())?;
-                schema.rename_field(index, self.plan.new_column.as_str());
+            let is_computed = field.computed_expr().is_some();
+            let index = schema.index_of(self.plan.old_column.as_str())?;
+            schema.rename_field(index, self.plan.new_column.as_str());
+            let schema = Arc::new(schema);
+            if !is_computed {
                 // Check if old column is referenced by computed columns.
                 check_referenced_computed_columns(
                     self.ctx.clone(),
-                    Arc::new(schema),
+                    schema.clone(),
                     self.plan.old_column.as_str(),
                 )?;
             }
+            // Check if old column is referenced by check constraints.
+            check_referenced_check_constraints(
+                self.ctx.clone(),
+                schema,
+                table_info.options(),
+                self.plan.old_column.as_str(),
+            )?;
 
             new_table_meta.schema = Arc::new(self.plan.schema.clone());
 
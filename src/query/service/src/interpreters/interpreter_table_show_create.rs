@@ -29,6 +29,7 @@ use databend_common_storages_stream::stream_table::STREAM_ENGINE;
 use databend_common_storages_view::view_table::QUERY;
 use databend_common_storages_view::view_table::VIEW_ENGINE;
 use databend_storages_common_table_meta::table::is_internal_opt_key;
+use databend_storages_common_table_meta::table::CheckConstraint;
 use databend_storages_common_table_meta::table::OPT_KEY_STORAGE_PREFIX;
 use databend_storages_common_table_meta::table::OPT_KEY_TABLE_ATTACHED_DATA_URI;
 use databend_storages_common_table_meta::table::OPT_KEY_TABLE_ATTACHED_READ_ONLY;
@@ -60,6 +61,10 @@ impl Interpreter for ShowCreateTableInterpreter {
         true
     }
 
+    fn is_system_statement(&self) -> bool {
+        true
+    }
+
     #[async_backtrace::framed]
     async fn execute2(&self) -> Result<PipelineBuildResult> {
         let tenant = self.ctx.get_tenant();
@@ -141,6 +146,12 @@ impl ShowCreateTableInterpreter {
 
                 columns.push(column);
             }
+            for constraint in CheckConstraint::decode_from_options(table.options())? {
+                columns.push(format!(
+                    "  CONSTRAINT `{}` CHECK ({})",
+                    constraint.name, constraint.expr
+                ));
+            }
             // Format is:
             //  (
             //      x,
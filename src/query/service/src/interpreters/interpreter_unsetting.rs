@@ -45,6 +45,10 @@ impl Interpreter for UnSettingInterpreter {
         false
     }
 
+    fn is_setting_command(&self) -> bool {
+        true
+    }
+
     #[async_backtrace::framed]
     async fn execute2(&self) -> Result<PipelineBuildResult> {
         let plan = self.set.clone();
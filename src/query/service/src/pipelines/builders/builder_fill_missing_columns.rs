@@ -17,9 +17,12 @@ use std::sync::Arc;
 use databend_common_catalog::table::Table;
 use databend_common_exception::Result;
 use databend_common_expression::DataSchemaRef;
+use databend_common_expression::Expr;
 use databend_common_pipeline_core::Pipeline;
+use databend_storages_common_table_meta::table::CheckConstraint;
 
 use crate::pipelines::processors::transforms::TransformAddComputedColumns;
+use crate::pipelines::processors::transforms::TransformCheckConstraints;
 use crate::pipelines::processors::TransformResortAddOn;
 use crate::pipelines::PipelineBuilder;
 use crate::sessions::QueryContext;
@@ -28,6 +31,7 @@ use crate::sessions::QueryContext;
 impl PipelineBuilder {
     // Fill missing columns with default or compute expr
     // ** Also reorder the block into table's schema order **
+    // ** Also validate the CHECK constraints of the table **
     pub fn fill_and_reorder_columns(
         ctx: Arc<QueryContext>,
         pipeline: &mut Pipeline,
@@ -66,6 +70,43 @@ impl PipelineBuilder {
             })?;
         }
 
+        // Validate check constraints.
+        Self::add_check_constraints(ctx, pipeline, table.as_ref(), computed_schema)
+    }
+
+    /// Bind the CHECK constraints of `table` against `schema`, the schema of the blocks
+    /// written to the table. Returns `None` if the table has no CHECK constraints.
+    pub fn bind_check_constraints(
+        ctx: Arc<QueryContext>,
+        table: &dyn Table,
+        schema: DataSchemaRef,
+    ) -> Result<Option<Arc<Vec<(String, Expr)>>>> {
+        let constraints = CheckConstraint::decode_from_options(table.options())?;
+        if constraints.is_empty() {
+            return Ok(None);
+        }
+        TransformCheckConstraints::try_bind_constraints(ctx, schema, &constraints).map(Some)
+    }
+
+    // Validate the CHECK constraints of the table on every block of the pipeline.
+    pub fn add_check_constraints(
+        ctx: Arc<QueryContext>,
+        pipeline: &mut Pipeline,
+        table: &dyn Table,
+        schema: DataSchemaRef,
+    ) -> Result<()> {
+        if let Some(constraints) = Self::bind_check_constraints(ctx.clone(), table, schema.clone())?
+        {
+            pipeline.add_transform(|transform_input_port, transform_output_port| {
+                TransformCheckConstraints::try_create(
+                    ctx.clone(),
+                    transform_input_port,
+                    transform_output_port,
+                    schema.clone(),
+                    constraints.clone(),
+                )
+            })?;
+        }
         Ok(())
     }
 }
@@ -54,6 +54,7 @@ use databend_common_storages_fuse::FuseTable;
 use crate::pipelines::processors::transforms::AccumulateRowNumber;
 use crate::pipelines::processors::transforms::ExtractHashTableByRowNumber;
 use crate::pipelines::processors::transforms::TransformAddComputedColumns;
+use crate::pipelines::processors::transforms::TransformCheckConstraints;
 use crate::pipelines::processors::DeduplicateRowNumber;
 use crate::pipelines::processors::TransformResortAddOnWithoutSourceSchema;
 use crate::pipelines::PipelineBuilder;
@@ -229,6 +230,28 @@ impl PipelineBuilder {
                 self.main_pipeline.add_pipe(builder.finalize());
             }
 
+            // validate check constraints
+            if let Some(constraints) = Self::bind_check_constraints(
+                self.ctx.clone(),
+                tbl.as_ref(),
+                computed_schema.clone(),
+            )? {
+                builder = self.main_pipeline.add_transform_with_specified_len(
+                    |transform_input_port, transform_output_port| {
+                        TransformCheckConstraints::try_create(
+                            self.ctx.clone(),
+                            transform_input_port,
+                            transform_output_port,
+                            computed_schema.clone(),
+                            constraints.clone(),
+                        )
+                    },
+                    1,
+                )?;
+                builder.add_items(vec![create_dummy_item()]);
+                self.main_pipeline.add_pipe(builder.finalize());
+            }
+
             // 3. we should avoid too much little block write, because for s3 write, there are too many
             // little blocks, it will cause high latency.
             let block_thresholds = table.get_block_thresholds();
@@ -733,6 +756,26 @@ impl PipelineBuilder {
                 .add_pipe(add_builder_pipe(builder, distributed));
         }
 
+        // validate check constraints
+        if let Some(constraints) =
+            Self::bind_check_constraints(self.ctx.clone(), tbl.as_ref(), computed_schema.clone())?
+        {
+            builder = self.main_pipeline.add_transform_with_specified_len(
+                |transform_input_port, transform_output_port| {
+                    TransformCheckConstraints::try_create(
+                        self.ctx.clone(),
+                        transform_input_port,
+                        transform_output_port,
+                        computed_schema.clone(),
+                        constraints.clone(),
+                    )
+                },
+                fill_default_len,
+            )?;
+            self.main_pipeline
+                .add_pipe(add_builder_pipe(builder, distributed));
+        }
+
         let max_threads = self.settings.get_max_threads()?;
         let io_request_semaphore = Arc::new(Semaphore::new(max_threads as usize));
 
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use databend_common_catalog::table::Table;
 use databend_common_catalog::table_context::TableContext;
 use databend_common_exception::Result;
@@ -48,6 +50,15 @@ impl PipelineBuilder {
             &mut self.main_pipeline,
         )?;
 
+        // Validate the CHECK constraints of the table on the updated rows.
+        let schema = table.schema().remove_virtual_computed_fields();
+        Self::add_check_constraints(
+            self.ctx.clone(),
+            &mut self.main_pipeline,
+            table,
+            Arc::new((&schema).into()),
+        )?;
+
         if table.change_tracking_enabled() {
             let func_ctx = self.ctx.get_function_context()?;
             let (stream, operators) = gen_mutation_stream_operator(
@@ -24,6 +24,7 @@ mod transform_add_const_columns;
 mod transform_add_internal_columns;
 mod transform_add_stream_columns;
 mod transform_cast_schema;
+mod transform_check_constraints;
 mod transform_create_sets;
 mod transform_filter;
 mod transform_limit;
@@ -47,6 +48,7 @@ pub use transform_add_const_columns::TransformAddConstColumns;
 pub use transform_add_internal_columns::TransformAddInternalColumns;
 pub use transform_add_stream_columns::TransformAddStreamColumns;
 pub use transform_cast_schema::TransformCastSchema;
+pub use transform_check_constraints::TransformCheckConstraints;
 pub use transform_create_sets::TransformCreateSets;
 pub use transform_filter::TransformFilter;
 pub use transform_limit::TransformLimit;
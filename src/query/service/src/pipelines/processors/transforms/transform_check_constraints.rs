@@ -0,0 +1,147 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use databend_common_catalog::table_context::TableContext;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::types::AnyType;
+use databend_common_expression::Column;
+use databend_common_expression::DataBlock;
+use databend_common_expression::DataSchemaRef;
+use databend_common_expression::Evaluator;
+use databend_common_expression::Expr;
+use databend_common_expression::FunctionContext;
+use databend_common_expression::Scalar;
+use databend_common_expression::Value;
+use databend_common_functions::BUILTIN_FUNCTIONS;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_common_pipeline_transforms::processors::Transformer;
+use databend_common_sql::parse_computed_expr;
+use databend_storages_common_table_meta::table::CheckConstraint;
+
+use crate::pipelines::processors::InputPort;
+use crate::pipelines::processors::OutputPort;
+use crate::pipelines::processors::ProcessorPtr;
+use crate::sessions::QueryContext;
+
+/// Validates every ingested block against the CHECK constraints of the table.
+///
+/// A constraint passes when its expression evaluates to TRUE or NULL.
+pub struct TransformCheckConstraints {
+    func_ctx: FunctionContext,
+    schema: DataSchemaRef,
+    constraints: Arc<Vec<(String, Expr)>>,
+}
+
+impl TransformCheckConstraints
+where Self: Transform
+{
+    /// Bind the constraints against `schema`, the schema of the blocks to be written.
+    ///
+    /// The ALTERs which would break a constraint are rejected, so a constraint that can't be
+    /// bound fails the write instead of being skipped.
+    pub fn try_bind_constraints(
+        ctx: Arc<QueryContext>,
+        schema: DataSchemaRef,
+        constraints: &[CheckConstraint],
+    ) -> Result<Arc<Vec<(String, Expr)>>> {
+        let mut exprs = Vec::with_capacity(constraints.len());
+        for constraint in constraints {
+            let expr = parse_computed_expr(ctx.clone(), schema.clone(), &constraint.expr).map_err(
+                |e| {
+                    e.add_message_back(format!(
+                        " (while binding CHECK constraint `{}`: {})",
+                        constraint.name, constraint.expr
+                    ))
+                },
+            )?;
+            exprs.push((constraint.name.clone(), expr));
+        }
+        Ok(Arc::new(exprs))
+    }
+
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        schema: DataSchemaRef,
+        constraints: Arc<Vec<(String, Expr)>>,
+    ) -> Result<ProcessorPtr> {
+        let func_ctx = ctx.get_function_context()?;
+        Ok(ProcessorPtr::create(Transformer::create(
+            input,
+            output,
+            Self {
+                func_ctx,
+                schema,
+                constraints,
+            },
+        )))
+    }
+
+    fn violation_error(&self, name: &str, expr: &Expr, block: &DataBlock, row: usize) -> ErrorCode {
+        let mut refs = expr.column_refs().into_keys().collect::<Vec<_>>();
+        refs.sort();
+        let values = refs
+            .into_iter()
+            .map(|index| {
+                let value = block
+                    .get_by_offset(index)
+                    .value
+                    .index(row)
+                    .map(|v| v.to_string())
+                    .unwrap_or_default();
+                format!("{} = {}", self.schema.field(index).name(), value)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        ErrorCode::CheckConstraintViolation(format!(
+            "CHECK constraint `{name}` is violated by row ({values})"
+        ))
+    }
+}
+
+impl Transform for TransformCheckConstraints {
+    const NAME: &'static str = "CheckConstraintsTransform";
+
+    fn transform(&mut self, block: DataBlock) -> Result<DataBlock> {
+        let evaluator = Evaluator::new(&block, &self.func_ctx, &BUILTIN_FUNCTIONS);
+        for (name, expr) in self.constraints.iter() {
+            let value = evaluator.run(expr)?;
+            if let Some(row) = first_violated_row(&value, block.num_rows()) {
+                return Err(self.violation_error(name, expr, &block, row));
+            }
+        }
+        Ok(block)
+    }
+}
+
+/// Returns the first row at which the check result is FALSE. NULL results pass.
+fn first_violated_row(value: &Value<AnyType>, num_rows: usize) -> Option<usize> {
+    match value {
+        Value::Scalar(Scalar::Boolean(false)) if num_rows > 0 => Some(0),
+        Value::Scalar(_) => None,
+        Value::Column(Column::Boolean(bitmap)) => bitmap.iter().position(|v| !v),
+        Value::Column(Column::Nullable(box nullable)) => match &nullable.column {
+            Column::Boolean(bitmap) => bitmap
+                .iter()
+                .zip(nullable.validity.iter())
+                .position(|(v, valid)| valid && !v),
+            _ => None,
+        },
+        Value::Column(_) => None,
+    }
+}
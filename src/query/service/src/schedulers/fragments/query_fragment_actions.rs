@@ -30,6 +30,7 @@ use crate::api::ExecutePartialQueryPacket;
 use crate::api::FragmentPlanPacket;
 use crate::api::InitNodesChannelPacket;
 use crate::api::QueryFragmentsPlanPacket;
+use crate::api::StreamResumeConf;
 use crate::clusters::ClusterHelper;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
@@ -211,6 +212,13 @@ impl QueryFragmentsActions {
         let connections_info = self.fragments_connections();
         let statistics_connections = self.statistics_connections();
 
+        let settings = self.ctx.get_settings();
+        let stream_resume = StreamResumeConf {
+            retained_blocks: settings.get_flight_stream_retained_blocks()?,
+            max_retries: settings.get_flight_stream_max_retries()?,
+            timeout_secs: settings.get_flight_client_timeout()?,
+        };
+
         let mut init_nodes_channel_packets = Vec::with_capacity(connections_info.len());
 
         for (executor, fragments_connections) in &connections_info {
@@ -248,9 +256,8 @@ impl QueryFragmentsActions {
                     true => statistics_connections.clone(),
                     false => vec![],
                 },
-                self.ctx
-                    .get_settings()
-                    .get_create_query_flight_client_with_current_rt()?,
+                settings.get_create_query_flight_client_with_current_rt()?,
+                stream_resume.clone(),
             ));
         }
 
@@ -54,4 +54,61 @@ impl FederatedHelper {
         }
         None
     }
+
+    /// Returns the first statement of the query, without the leading whitespaces and comments,
+    /// the trailing whitespaces and the `;`.
+    ///
+    /// The comments and the `;` in the string literals and the quoted identifiers are kept.
+    pub(crate) fn first_statement(query: &str) -> &str {
+        let query = Self::skip_leading_comments(query);
+        let bytes = query.as_bytes();
+        let mut end = bytes.len();
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b';' => {
+                    end = i;
+                    break;
+                }
+                quote @ (b'\'' | b'"' | b'`') => {
+                    i += 1;
+                    while i < bytes.len() && bytes[i] != quote {
+                        // A backslash escapes the next character, a doubled quote closes
+                        // the string and opens it again.
+                        if bytes[i] == b'\\' {
+                            i += 1;
+                        }
+                        i += 1;
+                    }
+                }
+                b'-' if bytes[i..].starts_with(b"--") => {
+                    while i < bytes.len() && bytes[i] != b'\n' {
+                        i += 1;
+                    }
+                }
+                b'/' if bytes[i..].starts_with(b"/*") => {
+                    i = match query[i + 2..].find("*/") {
+                        Some(pos) => i + 2 + pos + 1,
+                        None => bytes.len(),
+                    };
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        query[..end].trim_end()
+    }
+
+    fn skip_leading_comments(mut query: &str) -> &str {
+        loop {
+            query = query.trim_start();
+            if let Some(rest) = query.strip_prefix("--") {
+                query = rest.find('\n').map_or("", |pos| &rest[pos + 1..]);
+            } else if let Some(rest) = query.strip_prefix("/*") {
+                query = rest.find("*/").map_or("", |pos| &rest[pos + 2..]);
+            } else {
+                return query;
+            }
+        }
+    }
 }
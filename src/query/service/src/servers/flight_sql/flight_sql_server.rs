@@ -28,7 +28,7 @@ use tonic::transport::Identity;
 use tonic::transport::Server;
 use tonic::transport::ServerTlsConfig;
 
-use crate::servers::flight_sql::flight_sql_service::FlightSqlServiceImpl;
+use crate::servers::flight_sql::flight_sql_service::FlightServiceImpl;
 use crate::servers::Server as DatabendQueryServer;
 
 pub struct FlightSQLServer {
@@ -63,7 +63,7 @@ impl FlightSQLServer {
 
     #[async_backtrace::framed]
     pub async fn start_with_incoming(&mut self, addr: SocketAddr) -> Result<()> {
-        let flight_sql_service = FlightSqlServiceImpl::create();
+        let flight_service = FlightServiceImpl::create();
         let builder = Server::builder();
         let mut builder = if self.config.flight_sql_tls_server_enabled() {
             info!("databend query tls flight sql enabled");
@@ -84,7 +84,7 @@ impl FlightSQLServer {
             .map_err(|e| ErrorCode::CannotListenerPort(format!("{e}")))?;
 
         let server = builder
-            .add_service(FlightServiceServer::new(flight_sql_service))
+            .add_service(FlightServiceServer::new(flight_service))
             .serve_with_incoming_shutdown(incoming, self.shutdown_notify());
 
         databend_common_base::runtime::spawn(server);
@@ -19,6 +19,7 @@ mod query;
 mod service;
 mod session;
 mod sql_info;
+mod sql_ticket;
 
 use std::pin::Pin;
 use std::sync::Arc;
@@ -31,6 +32,8 @@ use databend_common_sql::PlanExtras;
 use futures::Stream;
 use parking_lot::Mutex;
 use sql_info::SqlInfoProvider;
+pub use sql_ticket::FlightServiceImpl;
+pub use sql_ticket::SqlTicket;
 use tonic::Status;
 use uuid::Uuid;
 
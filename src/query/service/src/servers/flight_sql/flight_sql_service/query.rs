@@ -36,6 +36,7 @@ use serde::Deserialize;
 use serde::Serialize;
 use tonic::Status;
 
+use super::sql_ticket::error_status;
 use super::status;
 use super::DoGetStream;
 use super::FlightSqlServiceImpl;
@@ -134,6 +135,7 @@ impl FlightSqlServiceImpl {
             .await;
 
         let s1 = sender.clone();
+        let query_ctx = context.clone();
         databend_common_base::runtime::spawn(async move {
             let mut data_stream = data_stream;
 
@@ -146,12 +148,18 @@ impl FlightSqlServiceImpl {
                                 Err(err) => Err(status!("Could not convert batches", err)),
                             };
 
-                        let _ = s1.send(res).await;
+                        if s1.send(res).await.is_err() {
+                            // The client dropped the stream, nobody reads the rest of the result.
+                            query_ctx.get_current_session().force_kill_query(
+                                ErrorCode::AbortedQuery(
+                                    "Aborted query, because the client dropped the result stream",
+                                ),
+                            );
+                            break;
+                        }
                     }
                     Err(err) => {
-                        let _ = s1
-                            .send(Err(status!("Could not convert batches", err)))
-                            .await;
+                        let _ = s1.send(Err(error_status(err))).await;
                         break;
                     }
                 }
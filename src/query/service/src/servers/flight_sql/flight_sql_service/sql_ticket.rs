@@ -0,0 +1,267 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use arrow_flight::flight_service_server::FlightService;
+use arrow_flight::Action;
+use arrow_flight::ActionType;
+use arrow_flight::Criteria;
+use arrow_flight::Empty;
+use arrow_flight::FlightData;
+use arrow_flight::FlightDescriptor;
+use arrow_flight::FlightEndpoint;
+use arrow_flight::FlightInfo;
+use arrow_flight::HandshakeRequest;
+use arrow_flight::IpcMessage;
+use arrow_flight::SchemaAsIpc;
+use arrow_flight::SchemaResult;
+use arrow_flight::Ticket;
+use arrow_ipc::writer::IpcWriteOptions;
+use arrow_schema::Schema as ArrowSchema;
+use databend_common_exception::ErrorCode;
+use log::info;
+use prost::bytes::Bytes;
+use serde::Deserialize;
+use serde::Serialize;
+use tonic::metadata::MetadataMap;
+use tonic::Code;
+use tonic::Request;
+use tonic::Status;
+use tonic::Streaming;
+
+use super::status;
+use super::DoGetStream;
+use super::FlightSqlServiceImpl;
+use crate::sessions::Session;
+
+/// The ticket of a query for the Arrow Flight clients which don't speak Flight SQL, such as
+/// `pyarrow.flight`. It is the JSON of the query and the credentials of the user, e.g.
+/// `{"sql": "SELECT 1", "database": "default", "user": "root", "password": ""}`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SqlTicket {
+    pub sql: String,
+    #[serde(default)]
+    pub database: Option<String>,
+    pub user: String,
+    #[serde(default)]
+    pub password: String,
+}
+
+impl SqlTicket {
+    /// Returns `None` if the bytes are a Flight SQL command instead. The commands are protobuf
+    /// `Any` messages, which start with the tag of `type_url` rather than `{`.
+    pub fn try_decode(bytes: &[u8]) -> Result<Option<SqlTicket>, Status> {
+        if bytes.first() != Some(&b'{') {
+            return Ok(None);
+        }
+
+        serde_json::from_slice(bytes)
+            .map(Some)
+            .map_err(|e| Status::invalid_argument(format!("invalid sql ticket: {e}")))
+    }
+}
+
+/// Converts the error of a query to a Flight status, with the error code in the metadata
+/// `x-databend-error-code`.
+pub(super) fn error_status(err: ErrorCode) -> Status {
+    let code = match err.code() {
+        ErrorCode::SYNTAX_EXCEPTION | ErrorCode::SEMANTIC_ERROR | ErrorCode::BAD_ARGUMENTS => {
+            Code::InvalidArgument
+        }
+        ErrorCode::UNKNOWN_DATABASE | ErrorCode::UNKNOWN_TABLE | ErrorCode::UNKNOWN_COLUMN => {
+            Code::NotFound
+        }
+        ErrorCode::AUTHENTICATE_FAILURE => Code::Unauthenticated,
+        ErrorCode::PERMISSION_DENIED => Code::PermissionDenied,
+        ErrorCode::ABORTED_QUERY => Code::Cancelled,
+        _ => Code::Internal,
+    };
+
+    let mut metadata = MetadataMap::new();
+    metadata.insert("x-databend-error-code", err.code().into());
+    Status::with_metadata(code, err.message(), metadata)
+}
+
+impl FlightSqlServiceImpl {
+    #[async_backtrace::framed]
+    async fn sql_ticket_session(
+        ticket: &SqlTicket,
+        client_ip: Option<String>,
+    ) -> Result<Arc<Session>, Status> {
+        let session = Self::auth_user_password(
+            ticket.user.clone(),
+            ticket.password.clone(),
+            client_ip.as_deref(),
+        )
+        .await?;
+        if let Some(database) = &ticket.database {
+            session.set_current_database(database.clone());
+        }
+        Ok(session)
+    }
+
+    #[async_backtrace::framed]
+    pub(super) async fn do_get_sql_ticket(
+        &self,
+        ticket: SqlTicket,
+        client_ip: Option<String>,
+    ) -> Result<DoGetStream, Status> {
+        info!(
+            "do_get_sql_ticket(user={}, sql={})",
+            ticket.user, ticket.sql
+        );
+        let session = Self::sql_ticket_session(&ticket, client_ip).await?;
+        let (plan, plan_extras) = self
+            .plan_sql(&session, &ticket.sql)
+            .await
+            .map_err(error_status)?;
+        self.execute_query(session, &plan, &plan_extras)
+            .await
+            .map_err(error_status)
+    }
+
+    /// Plans the query of the ticket without executing it, the schema of the result is in the
+    /// returned [`FlightInfo`], and the ticket to fetch the result is the same one.
+    #[async_backtrace::framed]
+    pub(super) async fn get_flight_info_sql_ticket(
+        &self,
+        ticket: SqlTicket,
+        cmd: Bytes,
+        client_ip: Option<String>,
+    ) -> Result<FlightInfo, Status> {
+        info!(
+            "get_flight_info_sql_ticket(user={}, sql={})",
+            ticket.user, ticket.sql
+        );
+        let session = Self::sql_ticket_session(&ticket, client_ip).await?;
+        let (plan, _) = self
+            .plan_sql(&session, &ticket.sql)
+            .await
+            .map_err(error_status)?;
+
+        let schema = ArrowSchema::from(plan.schema().as_ref());
+        let IpcMessage(schema) = SchemaAsIpc::new(&schema, &IpcWriteOptions::default())
+            .try_into()
+            .map_err(|e| status!("Unable to serialize schema", e))?;
+
+        Ok(FlightInfo {
+            schema,
+            flight_descriptor: Some(FlightDescriptor::new_cmd(cmd.clone())),
+            endpoint: vec![FlightEndpoint {
+                ticket: Some(Ticket { ticket: cmd }),
+                location: vec![],
+            }],
+            total_records: -1,
+            total_bytes: -1,
+            ordered: false,
+        })
+    }
+}
+
+type Response<T> = Result<tonic::Response<T>, Status>;
+
+/// The Arrow Flight service of the Flight SQL server. It takes a [`SqlTicket`] in `DoGet` and
+/// `GetFlightInfo` besides the Flight SQL commands, which are served by [`FlightSqlServiceImpl`].
+pub struct FlightServiceImpl {
+    flight_sql: FlightSqlServiceImpl,
+}
+
+impl FlightServiceImpl {
+    pub fn create() -> Self {
+        FlightServiceImpl {
+            flight_sql: FlightSqlServiceImpl::create(),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl FlightService for FlightServiceImpl {
+    type HandshakeStream = <FlightSqlServiceImpl as FlightService>::HandshakeStream;
+    type ListFlightsStream = <FlightSqlServiceImpl as FlightService>::ListFlightsStream;
+    type DoGetStream = <FlightSqlServiceImpl as FlightService>::DoGetStream;
+    type DoPutStream = <FlightSqlServiceImpl as FlightService>::DoPutStream;
+    type DoActionStream = <FlightSqlServiceImpl as FlightService>::DoActionStream;
+    type ListActionsStream = <FlightSqlServiceImpl as FlightService>::ListActionsStream;
+    type DoExchangeStream = <FlightSqlServiceImpl as FlightService>::DoExchangeStream;
+
+    #[async_backtrace::framed]
+    async fn handshake(
+        &self,
+        request: Request<Streaming<HandshakeRequest>>,
+    ) -> Response<Self::HandshakeStream> {
+        self.flight_sql.handshake(request).await
+    }
+
+    #[async_backtrace::framed]
+    async fn list_flights(&self, request: Request<Criteria>) -> Response<Self::ListFlightsStream> {
+        self.flight_sql.list_flights(request).await
+    }
+
+    #[async_backtrace::framed]
+    async fn get_flight_info(&self, request: Request<FlightDescriptor>) -> Response<FlightInfo> {
+        match SqlTicket::try_decode(&request.get_ref().cmd)? {
+            Some(ticket) => {
+                let client_ip = request.remote_addr().map(|a| a.ip().to_string());
+                let cmd = request.into_inner().cmd;
+                let info = self
+                    .flight_sql
+                    .get_flight_info_sql_ticket(ticket, cmd, client_ip)
+                    .await?;
+                Ok(tonic::Response::new(info))
+            }
+            None => self.flight_sql.get_flight_info(request).await,
+        }
+    }
+
+    #[async_backtrace::framed]
+    async fn get_schema(&self, request: Request<FlightDescriptor>) -> Response<SchemaResult> {
+        self.flight_sql.get_schema(request).await
+    }
+
+    #[async_backtrace::framed]
+    async fn do_get(&self, request: Request<Ticket>) -> Response<Self::DoGetStream> {
+        match SqlTicket::try_decode(&request.get_ref().ticket)? {
+            Some(ticket) => {
+                let client_ip = request.remote_addr().map(|a| a.ip().to_string());
+                let stream = self.flight_sql.do_get_sql_ticket(ticket, client_ip).await?;
+                Ok(tonic::Response::new(stream))
+            }
+            None => self.flight_sql.do_get(request).await,
+        }
+    }
+
+    #[async_backtrace::framed]
+    async fn do_put(&self, request: Request<Streaming<FlightData>>) -> Response<Self::DoPutStream> {
+        self.flight_sql.do_put(request).await
+    }
+
+    #[async_backtrace::framed]
+    async fn do_action(&self, request: Request<Action>) -> Response<Self::DoActionStream> {
+        self.flight_sql.do_action(request).await
+    }
+
+    #[async_backtrace::framed]
+    async fn list_actions(&self, request: Request<Empty>) -> Response<Self::ListActionsStream> {
+        self.flight_sql.list_actions(request).await
+    }
+
+    #[async_backtrace::framed]
+    async fn do_exchange(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> Response<Self::DoExchangeStream> {
+        self.flight_sql.do_exchange(request).await
+    }
+}
@@ -12,19 +12,352 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use ctor::ctor;
+use databend_common_catalog::table_context::TableContext;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::types::Int64Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::types::UInt32Type;
+use databend_common_expression::types::UInt8Type;
+use databend_common_expression::utils::FromData;
+use databend_common_expression::DataBlock;
+use databend_common_expression::TableDataType;
+use databend_common_expression::TableField;
+use databend_common_expression::TableSchemaRef;
+use databend_common_expression::TableSchemaRefExt;
+use databend_common_meta_app::principal::UserSettingValue;
+use databend_common_metrics::MetricValue;
+use databend_common_settings::ScopeLevel;
+use databend_common_settings::Settings;
 use regex::Regex;
 
+use crate::servers::federated_helper::FederatedHelper;
+use crate::servers::http::clickhouse_settings::map_clickhouse_setting;
+use crate::sessions::QueryContext;
+use crate::sessions::SessionManager;
+
 pub struct ClickHouseFederated {}
 
 #[ctor]
 static FORMAT_REGEX: Regex = Regex::new(r".*(?i)FORMAT\s*([[:alpha:]]*)\s*;?$").unwrap();
 
+// SET name = value[, name = value ...]
+#[ctor]
+static SET_REGEX: Regex = Regex::new(r"(?i)^\s*SET\s+([[:word:]]+\s*=.+?)\s*;?\s*$").unwrap();
+
+// name = value
+#[ctor]
+static SET_ASSIGNMENT_REGEX: Regex = Regex::new(r"^\s*([[:word:]]+)\s*=\s*(.+?)\s*$").unwrap();
+
+// SHOW [CHANGED] SETTINGS [LIKE | ILIKE 'pattern'] [FORMAT format]
+#[ctor]
+static SHOW_SETTINGS_REGEX: Regex = Regex::new(
+    r"(?i)^\s*SHOW\s+(CHANGED\s+)?SETTINGS(?:\s+(I?LIKE)\s+'([^']*)')?(?:\s+FORMAT\s+[[:alpha:]]+)?\s*;?\s*$",
+)
+.unwrap();
+
+// SELECT * FROM system.one [FORMAT format]
+#[ctor]
+static SYSTEM_ONE_REGEX: Regex =
+    Regex::new(r"(?i)^\s*SELECT\s+\*\s+FROM\s+system\.one(?:\s+FORMAT\s+[[:alpha:]]+)?\s*;?\s*$")
+        .unwrap();
+
+// SELECT uptime() [FORMAT format]
+#[ctor]
+static UPTIME_REGEX: Regex =
+    Regex::new(r"(?i)^\s*SELECT\s+uptime\(\s*\)(?:\s+FORMAT\s+[[:alpha:]]+)?\s*;?\s*$").unwrap();
+
+// SELECT * | metric, value FROM system.metrics [FORMAT format]
+#[ctor]
+static SYSTEM_METRICS_REGEX: Regex = Regex::new(
+    r"(?i)^\s*SELECT\s+(\*|metric\s*,\s*value)\s+FROM\s+system\.metrics(?:\s+FORMAT\s+[[:alpha:]]+)?\s*;?\s*$",
+)
+.unwrap();
+
 impl ClickHouseFederated {
     pub fn get_format(query: &str) -> Option<String> {
+        let query = FederatedHelper::first_statement(query);
         match FORMAT_REGEX.captures(query) {
             Some(x) => x.get(1).map(|s| s.as_str().to_owned()),
             None => None,
         }
     }
+
+    /// Answers the statements which ClickHouse clients send with ClickHouse semantics,
+    /// returns None if the query should be planned as usual.
+    ///
+    /// The settings of the session of the context are changed by `SET` statements.
+    ///
+    /// Only the first statement is checked, and the leading comments are skipped, some clients
+    /// always prepend a comment to the queries.
+    pub fn check(
+        ctx: &Arc<QueryContext>,
+        query: &str,
+    ) -> Option<Result<(TableSchemaRef, DataBlock)>> {
+        let query = FederatedHelper::first_statement(query);
+        let settings = ctx.get_shared_settings();
+
+        if let Some(captures) = SET_REGEX.captures(query) {
+            return Self::set_settings(&settings, &captures[1]);
+        }
+
+        if let Some(captures) = SHOW_SETTINGS_REGEX.captures(query) {
+            let changed = captures.get(1).is_some();
+            let case_insensitive = captures
+                .get(2)
+                .is_some_and(|like| like.as_str().eq_ignore_ascii_case("ILIKE"));
+            let pattern = captures.get(3).map(|pattern| pattern.as_str());
+            return Some(Self::show_settings_block(
+                &settings,
+                changed,
+                pattern,
+                case_insensitive,
+            ));
+        }
+
+        if SYSTEM_ONE_REGEX.is_match(query) {
+            return Some(Ok(Self::system_one_block()));
+        }
+
+        if UPTIME_REGEX.is_match(query) {
+            return Some(Ok(Self::uptime_block()));
+        }
+
+        if let Some(captures) = SYSTEM_METRICS_REGEX.captures(query) {
+            let with_description = &captures[1] == "*";
+            return Some(Self::system_metrics_block(with_description));
+        }
+
+        None
+    }
+
+    // Every setting of `SET a = 1, b = 2` is set, like ClickHouse does. The ClickHouse settings
+    // are applied to the mapped settings, and the ClickHouse-only settings are acknowledged
+    // with an empty result. A statement of only Databend settings is planned as usual.
+    fn set_settings(
+        settings: &Settings,
+        assignments: &str,
+    ) -> Option<Result<(TableSchemaRef, DataBlock)>> {
+        let mut federated = false;
+        let mut values = vec![];
+        for assignment in split_assignments(assignments) {
+            let captures = SET_ASSIGNMENT_REGEX.captures(assignment)?;
+            let value = &captures[2];
+            let value = value
+                .strip_prefix('\'')
+                .and_then(|value| value.strip_suffix('\''))
+                .unwrap_or(value)
+                .to_string();
+
+            let name = captures[1].to_lowercase();
+            match map_clickhouse_setting(&name) {
+                Some(name) => {
+                    federated = true;
+                    values.push((name.to_string(), value));
+                }
+                None => match settings.has_setting(&name) {
+                    Ok(true) => values.push((name, value)),
+                    Ok(false) => federated = true,
+                    Err(e) => return Some(Err(e)),
+                },
+            }
+        }
+
+        if !federated {
+            return None;
+        }
+        for (name, value) in values {
+            if let Err(e) = settings.set_setting(name, value) {
+                return Some(Err(e));
+            }
+        }
+        Some(Ok(Self::empty_block()))
+    }
+
+    // Build block for show settings statement.
+    // Format is:
+    // | name | type | value |
+    fn show_settings_block(
+        settings: &Settings,
+        changed: bool,
+        pattern: Option<&str>,
+        case_insensitive: bool,
+    ) -> Result<(TableSchemaRef, DataBlock)> {
+        let pattern = pattern
+            .map(|pattern| like_to_regex(pattern, case_insensitive))
+            .transpose()?;
+
+        let mut names = vec![];
+        let mut types = vec![];
+        let mut values = vec![];
+        for item in settings.into_iter() {
+            if changed && matches!(item.level, ScopeLevel::Default) {
+                continue;
+            }
+            if pattern
+                .as_ref()
+                .is_some_and(|regex| !regex.is_match(&item.name))
+            {
+                continue;
+            }
+
+            let typename = match item.user_value {
+                UserSettingValue::UInt64(_) => "UInt64",
+                UserSettingValue::String(_) => "String",
+            };
+            names.push(item.name);
+            types.push(typename.to_string());
+            values.push(item.user_value.to_string());
+        }
+
+        let schema = TableSchemaRefExt::create(vec![
+            TableField::new("name", TableDataType::String),
+            TableField::new("type", TableDataType::String),
+            TableField::new("value", TableDataType::String),
+        ]);
+        let block = DataBlock::new_from_columns(vec![
+            StringType::from_data(names),
+            StringType::from_data(types),
+            StringType::from_data(values),
+        ]);
+        Ok((schema, block))
+    }
+
+    // ClickHouse `system.one` has a single row with `dummy = 0`.
+    fn system_one_block() -> (TableSchemaRef, DataBlock) {
+        let schema = TableSchemaRefExt::create(vec![TableField::new(
+            "dummy",
+            TableDataType::Number(NumberDataType::UInt8),
+        )]);
+        let block = DataBlock::new_from_columns(vec![UInt8Type::from_data(vec![0u8])]);
+        (schema, block)
+    }
+
+    fn uptime_block() -> (TableSchemaRef, DataBlock) {
+        let uptime = SessionManager::instance().uptime().as_secs();
+        let schema = TableSchemaRefExt::create(vec![TableField::new(
+            "uptime()",
+            TableDataType::Number(NumberDataType::UInt32),
+        )]);
+        let block = DataBlock::new_from_columns(vec![UInt32Type::from_data(vec![uptime
+            .min(u32::MAX as u64)
+            as u32])]);
+        (schema, block)
+    }
+
+    // Build block for ClickHouse `system.metrics`.
+    // Format is:
+    // | metric | value | description |
+    fn system_metrics_block(with_description: bool) -> Result<(TableSchemaRef, DataBlock)> {
+        let samples = {
+            let registry = databend_common_metrics::load_global_prometheus_registry();
+            databend_common_metrics::dump_metric_samples(&registry)?
+        };
+        // The counters are labeled, sum the samples of all the labels.
+        let sum_counter = |name: &str| {
+            samples
+                .iter()
+                .filter(|sample| sample.name == name)
+                .map(|sample| match sample.value {
+                    MetricValue::Counter(v) => v as i64,
+                    _ => 0,
+                })
+                .sum::<i64>()
+        };
+
+        let status = SessionManager::instance().get_current_session_status();
+        let metrics = [
+            (
+                "Query",
+                status.running_queries_count as i64,
+                "Number of executing queries",
+            ),
+            (
+                "QueryTotal",
+                sum_counter("query_start_total"),
+                "Number of queries started since the server started",
+            ),
+            (
+                "SelectedRows",
+                sum_counter("query_scan_rows_total"),
+                "Number of rows read from tables since the server started",
+            ),
+        ];
+
+        let mut fields = vec![
+            TableField::new("metric", TableDataType::String),
+            TableField::new("value", TableDataType::Number(NumberDataType::Int64)),
+        ];
+        let mut columns = vec![
+            StringType::from_data(metrics.iter().map(|(name, _, _)| *name).collect::<Vec<_>>()),
+            Int64Type::from_data(
+                metrics
+                    .iter()
+                    .map(|(_, value, _)| *value)
+                    .collect::<Vec<_>>(),
+            ),
+        ];
+        if with_description {
+            fields.push(TableField::new("description", TableDataType::String));
+            columns.push(StringType::from_data(
+                metrics
+                    .iter()
+                    .map(|(_, _, description)| *description)
+                    .collect::<Vec<_>>(),
+            ));
+        }
+
+        Ok((
+            TableSchemaRefExt::create(fields),
+            DataBlock::new_from_columns(columns),
+        ))
+    }
+
+    fn empty_block() -> (TableSchemaRef, DataBlock) {
+        (TableSchemaRefExt::create(vec![]), DataBlock::empty())
+    }
+}
+
+// Split `a = 1, b = 'x,y'` at the commas outside of the quoted values.
+fn split_assignments(assignments: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut quoted = false;
+    let mut start = 0;
+    for (i, c) in assignments.char_indices() {
+        match c {
+            '\'' => quoted = !quoted,
+            ',' if !quoted => {
+                parts.push(&assignments[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&assignments[start..]);
+    parts
+}
+
+// Convert a LIKE pattern to a regex, `%` matches any string and `_` matches any character.
+fn like_to_regex(pattern: &str, case_insensitive: bool) -> Result<Regex> {
+    let mut regex = String::from(if case_insensitive { "(?i)^" } else { "^" });
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            '\\' => {
+                if let Some(c) = chars.next() {
+                    regex.push_str(&regex::escape(&c.to_string()));
+                }
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+
+    Regex::new(&regex).map_err(|e| ErrorCode::BadArguments(format!("Invalid pattern: {}", e)))
 }
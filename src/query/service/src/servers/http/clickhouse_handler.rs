@@ -25,7 +25,9 @@ use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
 use databend_common_exception::ToErrorCode;
 use databend_common_expression::infer_table_schema;
+use databend_common_expression::DataBlock;
 use databend_common_expression::DataSchemaRef;
+use databend_common_expression::TableSchemaRef;
 use databend_common_formats::ClickhouseFormatType;
 use databend_common_formats::FileFormatOptionsExt;
 use databend_common_formats::FileFormatTypeExt;
@@ -34,6 +36,7 @@ use databend_common_pipeline_sources::input_formats::StreamingReadBatch;
 use databend_common_sql::plans::InsertInputSource;
 use databend_common_sql::plans::Plan;
 use databend_common_sql::Planner;
+use futures::Stream;
 use futures::StreamExt;
 use http::HeaderMap;
 use http::StatusCode;
@@ -62,16 +65,21 @@ use crate::interpreters::InterpreterFactory;
 use crate::interpreters::InterpreterPtr;
 use crate::servers::http::middleware::sanitize_request_headers;
 use crate::servers::http::v1::HttpQueryContext;
+use crate::servers::http::ClickHouseFederated;
 use crate::sessions::short_sql;
 use crate::sessions::QueryContext;
+use crate::sessions::SessionManager;
 use crate::sessions::SessionType;
 use crate::sessions::TableContext;
 
+const CLICKHOUSE_DATABASE: &str = "X-CLICKHOUSE-DATABASE";
+const CLICKHOUSE_QUERY_ID: &str = "X-ClickHouse-Query-Id";
+const CLICKHOUSE_SUMMARY: &str = "X-ClickHouse-Summary";
+
 // accept all clickhouse params, so they do not go to settings.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct StatementHandlerParams {
     query: Option<String>,
-    #[allow(unused)]
     query_id: Option<String>,
     database: Option<String>,
     default_format: Option<String>,
@@ -97,6 +105,34 @@ pub struct StatementHandlerParams {
     settings: HashMap<String, String>,
 }
 
+/// The progress of a query sent in the `X-ClickHouse-Summary` header, the numbers are
+/// strings as ClickHouse writes them.
+#[derive(Serialize)]
+struct QuerySummary {
+    read_rows: String,
+    read_bytes: String,
+    written_rows: String,
+    written_bytes: String,
+    result_rows: String,
+    result_bytes: String,
+}
+
+impl QuerySummary {
+    fn create(ctx: &Arc<QueryContext>) -> QuerySummary {
+        let scan = ctx.get_scan_progress_value();
+        let write = ctx.get_write_progress_value();
+        let result = ctx.get_result_progress_value();
+        QuerySummary {
+            read_rows: scan.rows.to_string(),
+            read_bytes: scan.bytes.to_string(),
+            written_rows: write.rows.to_string(),
+            written_bytes: write.bytes.to_string(),
+            result_rows: result.rows.to_string(),
+            result_bytes: result.bytes.to_string(),
+        }
+    }
+}
+
 impl StatementHandlerParams {
     pub fn compress(&self) -> bool {
         self.compress.unwrap_or(0u8) == 1u8
@@ -224,23 +260,50 @@ async fn execute(
     })?
 }
 
+// Serialize the result answered by the federated layer, the query is not planned.
+fn federated_response(
+    ctx: &Arc<QueryContext>,
+    schema: TableSchemaRef,
+    block: DataBlock,
+    format: ClickhouseFormatType,
+    params: &StatementHandlerParams,
+) -> Result<WithContentType<Body>> {
+    let format_typ = format.typ.clone();
+    let mut output_format = FileFormatOptionsExt::get_output_format_from_clickhouse_format(
+        format,
+        schema,
+        &ctx.get_settings(),
+    )?;
+
+    let mut data = output_format.serialize_prefix()?;
+    if block.num_rows() > 0 {
+        data.extend(output_format.serialize_block(&block)?);
+    }
+    data.extend(output_format.finalize()?);
+    if params.compress() {
+        data = compress_block(data)?;
+    }
+    Ok(Body::from(data).with_content_type(format_typ.get_content_type()))
+}
+
 #[poem::handler]
 #[async_backtrace::framed]
 pub async fn clickhouse_handler_get(
     ctx: &HttpQueryContext,
     Query(params): Query<StatementHandlerParams>,
     headers: &HeaderMap,
-) -> PoemResult<WithContentType<Body>> {
+) -> PoemResult<impl IntoResponse> {
     let root = Span::root(full_name!(), SpanContext::random());
     async {
         let session = ctx.upgrade_session(SessionType::ClickHouseHttpHandler)?;
-        if let Some(db) = &params.database {
-            session.set_current_database(db.clone());
+        if let Some(db) = get_database(&params, headers).map_err(BadRequest)? {
+            session.set_current_database(db);
         }
         let context = session
             .create_query_context()
             .await
             .map_err(InternalServerError)?;
+        set_query_id(ctx, &context, &params)?;
 
         let settings = session.get_settings();
         settings
@@ -259,6 +322,15 @@ pub async fn clickhouse_handler_get(
 
         let default_format = get_default_format(&params, headers).map_err(BadRequest)?;
         let sql = params.query();
+        if let Some(result) = ClickHouseFederated::check(&context, &sql) {
+            let (schema, block) = result.map_err(BadRequest)?;
+            let format =
+                get_format_with_default(ClickHouseFederated::get_format(&sql), default_format)?;
+            return federated_response(&context, schema, block, format, &params)
+                .map(|body| with_query_headers(&context, body))
+                .map_err(InternalServerError);
+        }
+
         let mut planner = Planner::new(context.clone());
         let (plan, extras) = planner
             .plan_sql(&sql)
@@ -272,10 +344,18 @@ pub async fn clickhouse_handler_get(
             .await
             .map_err(|err| err.display_with_sql(&sql))
             .map_err(BadRequest)?;
-        execute(context, interpreter, plan.schema(), format, params, None)
-            .await
-            .map_err(|err| err.display_with_sql(&sql))
-            .map_err(InternalServerError)
+        execute(
+            context.clone(),
+            interpreter,
+            plan.schema(),
+            format,
+            params,
+            None,
+        )
+        .await
+        .map(|body| with_query_headers(&context, body))
+        .map_err(|err| err.display_with_sql(&sql))
+        .map_err(InternalServerError)
     }
     .in_span(root)
     .await
@@ -298,13 +378,15 @@ pub async fn clickhouse_handler_post(
             params,
         );
         let session = ctx.upgrade_session(SessionType::ClickHouseHttpHandler)?;
-        if let Some(db) = &params.database {
-            session.set_current_database(db.clone());
+        if let Some(db) = get_database(&params, headers).map_err(BadRequest)? {
+            session.set_current_database(db);
         }
+        let http_ctx = ctx;
         let ctx = session
             .create_query_context()
             .await
             .map_err(InternalServerError)?;
+        set_query_id(http_ctx, &ctx, &params)?;
 
         let settings = session.get_settings();
         settings
@@ -326,7 +408,38 @@ pub async fn clickhouse_handler_post(
         if !sql.is_empty() {
             sql.push(' ');
         }
-        sql.push_str(body.into_string().await?.as_str());
+        // The data of a streaming insert is read while the insert pipeline is running, so the
+        // body is never buffered as a whole.
+        let mut body = Box::pin(body.into_bytes_stream().fuse());
+        let mut head = vec![];
+        let mut body_finished = false;
+        let mut pending = vec![];
+        if sql.is_empty() {
+            // Only the first line of the body is read before planning.
+            body_finished = read_body_line(&mut body, &mut head)
+                .await
+                .map_err(BadRequest)?;
+            if !body_finished {
+                if is_insert_statement(&head) {
+                    // The statement is in the first line, the data after it may be binary.
+                    let pos = head.iter().position(|c| *c == b'\n').unwrap() + 1;
+                    pending = head.split_off(pos);
+                } else {
+                    read_body_to_end(&mut body, &mut head)
+                        .await
+                        .map_err(BadRequest)?;
+                    body_finished = true;
+                }
+            }
+        } else if !is_insert_statement(sql.as_bytes()) {
+            read_body_to_end(&mut body, &mut head)
+                .await
+                .map_err(BadRequest)?;
+            body_finished = true;
+        }
+        // Otherwise the whole body is the data of the insert, which may be binary, e.g. the
+        // Native format, it is passed to the input pipeline as it is.
+        sql.push_str(&String::from_utf8(head).map_err(BadRequest)?);
         let n = 64;
         // other parts of the request already logged in middleware
         let len = sql.len();
@@ -337,10 +450,32 @@ pub async fn clickhouse_handler_post(
         };
         info!("receive clickhouse http post, (query + body) = {}", &msg);
 
+        if let Some(result) = ClickHouseFederated::check(&ctx, &sql) {
+            let (schema, block) = result.map_err(BadRequest)?;
+            let format =
+                get_format_with_default(ClickHouseFederated::get_format(&sql), default_format)?;
+            return federated_response(&ctx, schema, block, format, &params)
+                .map(|body| with_query_headers(&ctx, body))
+                .map_err(InternalServerError);
+        }
+
         let mut planner = Planner::new(ctx.clone());
-        let (mut plan, extras) = planner
-            .plan_sql(&sql)
-            .await
+        let mut planned = planner.plan_sql(&sql).await;
+        let is_streaming = matches!(&planned, Ok((Plan::Insert(insert), _)) if matches!(
+            insert.source,
+            InsertInputSource::StreamingWithFormat(..)
+                | InsertInputSource::StreamingWithFileFormat { .. }
+        ));
+        if (!body_finished || !pending.is_empty()) && !is_streaming {
+            // The statement does not end in the first line, plan it again with the whole body.
+            read_body_to_end(&mut body, &mut pending)
+                .await
+                .map_err(BadRequest)?;
+            sql.push_str(&String::from_utf8(std::mem::take(&mut pending)).map_err(BadRequest)?);
+            let mut planner = Planner::new(ctx.clone());
+            planned = planner.plan_sql(&sql).await;
+        }
+        let (mut plan, extras) = planned
             .map_err(|err| err.display_with_sql(&sql))
             .map_err(BadRequest)?;
         let schema = plan.schema();
@@ -381,13 +516,13 @@ pub async fn clickhouse_handler_post(
                     .get_compression_alg("")
                     .map_err(|err| err.display_with_sql(&sql))
                     .map_err(BadRequest)?;
-                let start = *start;
-                let sql_cloned = sql.clone();
+                let mut data = sql.as_bytes()[*start..].to_vec();
+                data.append(&mut pending);
                 let query_id = ctx.get_id();
                 handle = Some(ctx.spawn(query_id, async move {
                     gen_batches(
-                        sql_cloned,
-                        start,
+                        data,
+                        body,
                         input_context.read_batch_size,
                         tx,
                         compression_alg,
@@ -434,13 +569,13 @@ pub async fn clickhouse_handler_post(
                     .get_compression_alg("")
                     .map_err(|err| err.display_with_sql(&sql))
                     .map_err(BadRequest)?;
-                let start = *start;
-                let sql_cloned = sql.clone();
+                let mut data = sql.as_bytes()[*start..].to_vec();
+                data.append(&mut pending);
                 let query_id = ctx.get_id();
                 handle = Some(ctx.spawn(query_id, async move {
                     gen_batches(
-                        sql_cloned,
-                        start,
+                        data,
+                        body,
                         input_context.read_batch_size,
                         tx,
                         compression_alg,
@@ -456,8 +591,9 @@ pub async fn clickhouse_handler_post(
             .map_err(|err| err.display_with_sql(&sql))
             .map_err(BadRequest)?;
 
-        execute(ctx, interpreter, schema, format, params, handle)
+        execute(ctx.clone(), interpreter, schema, format, params, handle)
             .await
+            .map(|body| with_query_headers(&ctx, body))
             .map_err(|err| err.display_with_sql(&sql))
             .map_err(InternalServerError)
     }
@@ -531,6 +667,55 @@ fn get_default_format(
     ClickhouseFormatType::parse_clickhouse_format(name)
 }
 
+// The database of the `database` parameter, or of the `X-ClickHouse-Database` header.
+fn get_database(params: &StatementHandlerParams, headers: &HeaderMap) -> Result<Option<String>> {
+    match &params.database {
+        Some(db) => Ok(Some(db.clone())),
+        None => headers
+            .get(CLICKHOUSE_DATABASE)
+            .map(|v| {
+                v.to_str()
+                    .map(str::to_string)
+                    .map_err_to_code(ErrorCode::BadBytes, || {
+                        "value of X-CLICKHOUSE-DATABASE is not string"
+                    })
+            })
+            .transpose(),
+    }
+}
+
+// The query takes the `query_id` given by the client, so it can be correlated and killed.
+// The id of a query still running is rejected, as ClickHouse does.
+fn set_query_id(
+    http_ctx: &HttpQueryContext,
+    ctx: &Arc<QueryContext>,
+    params: &StatementHandlerParams,
+) -> PoemResult<()> {
+    let query_id = match &params.query_id {
+        Some(query_id) => query_id.clone(),
+        None => http_ctx.query_id.clone(),
+    };
+    if SessionManager::instance().is_query_running(&query_id) {
+        return Err(poem::Error::new(
+            ErrorCode::QueryWithSameIdIsAlreadyRunning(format!(
+                "Query with id = {} is already running.",
+                query_id
+            )),
+            StatusCode::CONFLICT,
+        ));
+    }
+    ctx.set_id(query_id);
+    Ok(())
+}
+
+// The ClickHouse clients read the id and the progress of the query from the headers. The
+// progress is the one when the response starts, the rest of the result is streamed after it.
+fn with_query_headers(ctx: &Arc<QueryContext>, body: WithContentType<Body>) -> impl IntoResponse {
+    let summary = serde_json::to_string(&QuerySummary::create(ctx)).unwrap_or_default();
+    body.with_header(CLICKHOUSE_QUERY_ID, ctx.get_id())
+        .with_header(CLICKHOUSE_SUMMARY, summary)
+}
+
 fn get_format_with_default(
     format: Option<String>,
     default_format: ClickhouseFormatType,
@@ -541,42 +726,111 @@ fn get_format_with_default(
     }
 }
 
-async fn gen_batches(
-    data: String,
-    start: usize,
+fn is_insert_statement(sql: &[u8]) -> bool {
+    let pos = sql
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .unwrap_or(sql.len());
+    sql[pos..]
+        .get(..6)
+        .is_some_and(|s| s.eq_ignore_ascii_case(b"insert"))
+}
+
+// Read the body until a line break, return true if the body is finished.
+async fn read_body_line<S, B>(body: &mut S, buf: &mut Vec<u8>) -> Result<bool>
+where
+    S: Stream<Item = std::io::Result<B>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    let start = buf.len();
+    while !buf[start..].contains(&b'\n') {
+        match body.next().await {
+            None => return Ok(true),
+            Some(bytes) => buf.extend_from_slice(bytes?.as_ref()),
+        }
+    }
+    Ok(false)
+}
+
+async fn read_body_to_end<S, B>(body: &mut S, buf: &mut Vec<u8>) -> Result<()>
+where
+    S: Stream<Item = std::io::Result<B>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    while let Some(bytes) = body.next().await {
+        buf.extend_from_slice(bytes?.as_ref());
+    }
+    Ok(())
+}
+
+// Send the data and then the rest of the body to the input pipeline in batches of
+// `batch_size`, the bounded channel keeps the body from being read faster than it is parsed.
+async fn gen_batches<S, B>(
+    mut data: Vec<u8>,
+    mut body: S,
     batch_size: usize,
     tx: Sender<Result<StreamingReadBatch>>,
     compression: Option<CompressAlgorithm>,
-) {
-    let buf = &data.trim_start().as_bytes()[start..];
-    let buf_size = buf.len();
+) where
+    S: Stream<Item = std::io::Result<B>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    let batch_size = batch_size.max(1);
     let mut is_start = true;
-    let mut start = 0;
+    let mut read_bytes = data.len();
     let path = "clickhouse_insert".to_string();
     debug!(
         "begin sending {} bytes, batch_size={}",
-        buf_size, batch_size
+        read_bytes, batch_size
     );
-    while start < buf_size {
-        let data = if buf_size - start >= batch_size {
-            buf[start..start + batch_size].to_vec()
-        } else {
-            buf[start..].to_vec()
+    loop {
+        let finished = match body.next().await {
+            None => true,
+            Some(Ok(bytes)) => {
+                data.extend_from_slice(bytes.as_ref());
+                read_bytes += bytes.as_ref().len();
+                false
+            }
+            Some(Err(cause)) => {
+                let err = ErrorCode::BadBytes(format!(
+                    "fail to read the body of clickhouse insert after {} bytes: {}",
+                    read_bytes, cause
+                ));
+                if let Err(e) = tx.send(Err(err)).await {
+                    warn!("clickhouse handler fail to send ReadBatch: {}", e);
+                }
+                return;
+            }
         };
 
-        debug!("sending read {} bytes", data.len());
-        if let Err(e) = tx
-            .send(Ok(StreamingReadBatch {
-                data,
-                path: path.clone(),
-                is_start,
-                compression,
-            }))
-            .await
-        {
-            warn!("clickhouse handler fail to send ReadBatch: {}", e);
+        let mut batches = vec![];
+        let mut offset = 0;
+        while data.len() - offset >= batch_size || (finished && offset < data.len()) {
+            let end = data.len().min(offset + batch_size);
+            batches.push(data[offset..end].to_vec());
+            offset = end;
+        }
+        data.drain(..offset);
+
+        for batch in batches {
+            debug!("sending read {} bytes", batch.len());
+            if let Err(e) = tx
+                .send(Ok(StreamingReadBatch {
+                    data: batch,
+                    path: path.clone(),
+                    is_start,
+                    compression,
+                }))
+                .await
+            {
+                warn!("clickhouse handler fail to send ReadBatch: {}", e);
+                return;
+            }
+            is_start = false;
+        }
+
+        if finished {
+            return;
         }
-        is_start = false;
-        start += batch_size;
     }
 }
@@ -0,0 +1,33 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// The ClickHouse settings sent by ClickHouse clients and the settings they are mapped to.
+// Format:
+// | ClickHouse setting | Databend setting |
+const CLICKHOUSE_SETTINGS: &[(&str, &str)] = &[
+    ("max_block_size", "max_block_size"),
+    ("max_threads", "max_threads"),
+    ("max_memory_usage", "max_memory_usage"),
+    ("max_result_rows", "max_result_rows"),
+    ("max_read_buffer_size", "input_read_buffer_size"),
+    ("session_timezone", "timezone"),
+];
+
+/// Returns the setting which the ClickHouse setting is mapped to.
+pub fn map_clickhouse_setting(name: &str) -> Option<&'static str> {
+    CLICKHOUSE_SETTINGS
+        .iter()
+        .find(|(clickhouse_name, _)| clickhouse_name.eq_ignore_ascii_case(name))
+        .map(|(_, name)| *name)
+}
@@ -54,6 +54,8 @@ use crate::sessions::SessionType;
 const DEDUPLICATE_LABEL: &str = "X-DATABEND-DEDUPLICATE-LABEL";
 const USER_AGENT: &str = "User-Agent";
 const QUERY_ID: &str = "X-DATABEND-QUERY-ID";
+const CLICKHOUSE_USER: &str = "X-CLICKHOUSE-USER";
+const CLICKHOUSE_KEY: &str = "X-CLICKHOUSE-KEY";
 
 pub struct HTTPSessionMiddleware {
     pub kind: HttpHandlerKind,
@@ -77,9 +79,15 @@ fn get_credential(req: &Request, kind: HttpHandlerKind) -> Result<Credential> {
         Addr::Custom(..) => Some("127.0.0.1".to_string()),
         _ => None,
     };
+    if matches!(kind, HttpHandlerKind::Clickhouse) {
+        // The ClickHouse clients authenticate by the headers first, then by the basic auth.
+        if let Some(credential) = auth_clickhouse_headers(req, client_ip.clone())? {
+            return Ok(credential);
+        }
+    }
     if std_auth_headers.is_empty() {
         if matches!(kind, HttpHandlerKind::Clickhouse) {
-            auth_clickhouse_name_password(req, client_ip)
+            auth_clickhouse_query_params(req, client_ip)
         } else {
             Err(ErrorCode::AuthenticateFailure(
                 "No authorization header detected",
@@ -123,34 +131,42 @@ fn auth_by_header(
     }
 }
 
-fn auth_clickhouse_name_password(req: &Request, client_ip: Option<String>) -> Result<Credential> {
-    let (user, key) = (
-        req.headers().get("X-CLICKHOUSE-USER"),
-        req.headers().get("X-CLICKHOUSE-KEY"),
-    );
+// The user of `X-ClickHouse-User`, with the password of `X-ClickHouse-Key` which may be
+// omitted if the user has no password. None if the request has no user header.
+fn auth_clickhouse_headers(req: &Request, client_ip: Option<String>) -> Result<Option<Credential>> {
+    let Some(name) = req.headers().get(CLICKHOUSE_USER) else {
+        return Ok(None);
+    };
+    let name = name
+        .to_str()
+        .map_err(|_| ErrorCode::AuthenticateFailure("bad X-ClickHouse-User header"))?;
+    let password = req
+        .headers()
+        .get(CLICKHOUSE_KEY)
+        .map(|key| key.as_bytes().to_vec())
+        .filter(|key| !key.is_empty());
+    Ok(Some(Credential::Password {
+        name: name.to_string(),
+        password,
+        client_ip,
+    }))
+}
+
+fn auth_clickhouse_query_params(req: &Request, client_ip: Option<String>) -> Result<Credential> {
+    let query_str = req.uri().query().unwrap_or_default();
+    let query_params = serde_urlencoded::from_str::<HashMap<String, String>>(query_str)
+        .map_err(|e| ErrorCode::BadArguments(format!("{}", e)))?;
+    let (user, key) = (query_params.get("user"), query_params.get("password"));
     if let (Some(name), Some(password)) = (user, key) {
-        let c = Credential::Password {
-            name: String::from_utf8(name.as_bytes().to_vec()).unwrap(),
+        Ok(Credential::Password {
+            name: name.clone(),
             password: Some(password.as_bytes().to_vec()),
             client_ip,
-        };
-        Ok(c)
+        })
     } else {
-        let query_str = req.uri().query().unwrap_or_default();
-        let query_params = serde_urlencoded::from_str::<HashMap<String, String>>(query_str)
-            .map_err(|e| ErrorCode::BadArguments(format!("{}", e)))?;
-        let (user, key) = (query_params.get("user"), query_params.get("password"));
-        if let (Some(name), Some(password)) = (user, key) {
-            Ok(Credential::Password {
-                name: name.clone(),
-                password: Some(password.as_bytes().to_vec()),
-                client_ip,
-            })
-        } else {
-            Err(ErrorCode::AuthenticateFailure(
-                "No header or query parameters for authorization detected",
-            ))
-        }
+        Err(ErrorCode::AuthenticateFailure(
+            "No header or query parameters for authorization detected",
+        ))
     }
 }
 
@@ -14,11 +14,13 @@
 
 mod clickhouse_federated;
 mod clickhouse_handler;
+mod clickhouse_settings;
 mod http_services;
 pub mod middleware;
 pub mod v1;
 
 pub use clickhouse_federated::ClickHouseFederated;
+pub use clickhouse_settings::map_clickhouse_setting;
 pub use http_services::HttpHandler;
 pub use http_services::HttpHandlerKind;
 
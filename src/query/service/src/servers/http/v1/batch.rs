@@ -0,0 +1,289 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::time::Duration;
+use std::time::Instant;
+
+use databend_common_ast::parser::split_sql;
+use databend_common_base::base::tokio;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use log::info;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use super::http_query_handlers::QueryResponseField;
+use super::query::ExecuteStateKind;
+use super::query::HttpQuery;
+use super::query::HttpQueryRequest;
+use super::query::PaginationConf;
+use super::query::RemoveReason;
+use crate::servers::http::v1::HttpQueryContext;
+use crate::servers::http::v1::HttpQueryManager;
+use crate::servers::http::v1::HttpSessionConf;
+use crate::servers::http::v1::JsonBlock;
+use crate::servers::http::v1::QueryError;
+use crate::servers::http::v1::QueryStats;
+use crate::sessions::short_sql;
+use crate::sessions::QueryAffect;
+use crate::sessions::SessionType;
+
+/// Executes the statements in `sql` one by one in the same session.
+///
+/// The results of a statement are returned in the response as a whole rather
+/// than paginated, so the rows of each statement are limited by the setting
+/// `http_batch_max_result_rows`. Use `/v1/query` for large result sets.
+#[derive(Deserialize, Clone)]
+pub struct HttpBatchRequest {
+    pub session: Option<HttpSessionConf>,
+    pub sql: String,
+    /// Keep executing the remaining statements after a statement fails,
+    /// the batch stops at the first failed statement by default.
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+impl Debug for HttpBatchRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpBatchRequest")
+            .field("session", &self.session)
+            .field("sql", &short_sql(self.sql.clone()))
+            .field("continue_on_error", &self.continue_on_error)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BatchStatementResult {
+    pub id: String,
+    pub sql: String,
+    pub state: ExecuteStateKind,
+    pub schema: Vec<QueryResponseField>,
+    pub data: Vec<Vec<JsonValue>>,
+    pub affect: Option<QueryAffect>,
+    pub stats: QueryStats,
+    pub error: Option<QueryError>,
+}
+
+impl BatchStatementResult {
+    fn failed(id: String, sql: String, err: &ErrorCode) -> Self {
+        BatchStatementResult {
+            id,
+            sql,
+            state: ExecuteStateKind::Failed,
+            schema: vec![],
+            data: vec![],
+            affect: None,
+            stats: QueryStats::default(),
+            error: Some(QueryError::from_error_code(err)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BatchResponse {
+    /// The query id of the batch request, also recorded in the query log of each statement.
+    pub batch_id: String,
+    pub node_id: String,
+    pub session: Option<HttpSessionConf>,
+    /// Results of the executed statements, the statements skipped after a failure are not included.
+    pub results: Vec<BatchStatementResult>,
+    /// Error of the batch itself, e.g. the sql can not be split or the batch limits are exceeded.
+    pub error: Option<QueryError>,
+}
+
+#[async_backtrace::framed]
+pub(crate) async fn execute_batch(ctx: &HttpQueryContext, req: HttpBatchRequest) -> BatchResponse {
+    let mut response = BatchResponse {
+        batch_id: ctx.query_id.clone(),
+        node_id: ctx.node_id.clone(),
+        session: req.session.clone(),
+        results: vec![],
+        error: None,
+    };
+    if let Err(err) = run_batch(ctx, req, &mut response).await {
+        info!("{}: http batch failed: {:?}", &ctx.query_id, err);
+        response.error = Some(QueryError::from_error_code(&err));
+    }
+    response
+}
+
+async fn run_batch(
+    ctx: &HttpQueryContext,
+    req: HttpBatchRequest,
+    response: &mut BatchResponse,
+) -> Result<()> {
+    let statements = split_sql(&req.sql)?;
+
+    let session = ctx
+        .upgrade_session(SessionType::HTTPQuery)
+        .map_err(|err| ErrorCode::Internal(format!("{err}")))?;
+    let settings = session.get_settings();
+    let max_statements = settings.get_http_batch_max_statements()?;
+    let timeout = Duration::from_secs(settings.get_http_batch_timeout_secs()?);
+    let max_result_rows = settings.get_http_batch_max_result_rows()?;
+    if statements.len() > max_statements {
+        return Err(ErrorCode::BadArguments(format!(
+            "too many statements in the batch: {} > http_batch_max_statements {}",
+            statements.len(),
+            max_statements
+        )));
+    }
+
+    // The statements are attached to the session by its id, like the requests
+    // of a client which keeps the server session.
+    let http_query_manager = HttpQueryManager::instance();
+    let session_id = session.get_id();
+    http_query_manager.add_session(session, timeout).await;
+
+    let deadline = Instant::now() + timeout;
+    let mut result = Ok(());
+    for sql in statements {
+        let request = HttpQueryRequest {
+            session_id: Some(session_id.clone()),
+            session: response.session.clone(),
+            sql: sql.to_string(),
+            pagination: PaginationConf::default(),
+            string_fields: true,
+            stage_attachment: None,
+        };
+        let query_id = uuid::Uuid::new_v4().to_string();
+        let stmt_ctx = ctx.for_batch_statement(query_id.clone());
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let (stmt_result, session_conf) = match tokio::time::timeout(
+            remaining,
+            execute_statement(&stmt_ctx, request, max_result_rows),
+        )
+        .await
+        {
+            Ok(r) => r,
+            Err(_) => {
+                let msg = format!(
+                    "batch timeout after {} s, set by http_batch_timeout_secs",
+                    timeout.as_secs()
+                );
+                if let Ok(query) = http_query_manager
+                    .remove_query(&query_id, RemoveReason::Canceled)
+                    .await
+                {
+                    query.kill(&msg).await;
+                }
+                result = Err(ErrorCode::AbortedQuery(msg));
+                break;
+            }
+        };
+
+        let failed = stmt_result.error.is_some();
+        response.results.push(stmt_result);
+        if session_conf.is_some() {
+            response.session = session_conf;
+        }
+        if failed && !req.continue_on_error {
+            break;
+        }
+    }
+
+    http_query_manager.kill_session(&session_id);
+    result
+}
+
+async fn execute_statement(
+    ctx: &HttpQueryContext,
+    request: HttpQueryRequest,
+    max_result_rows: usize,
+) -> (BatchStatementResult, Option<HttpSessionConf>) {
+    let http_query_manager = HttpQueryManager::instance();
+    let query_id = ctx.query_id.clone();
+    let sql = request.sql.clone();
+
+    let query = match http_query_manager
+        .try_create_query(ctx, request.clone())
+        .await
+    {
+        Ok(query) => query,
+        Err(err) => {
+            let err = err.display_with_sql(&sql);
+            ctx.set_fail();
+            return (
+                BatchStatementResult::failed(query_id, sql, &err),
+                request.session_on_failure(),
+            );
+        }
+    };
+
+    let collected = collect_result(&query, max_result_rows).await;
+    query.wait_for_stop().await;
+    let response = query.get_response_state_only().await;
+    let _ = http_query_manager
+        .remove_query(&query_id, RemoveReason::Finished)
+        .await;
+
+    let state = response.state;
+    let (data, error) = match collected {
+        Ok(data) => (data, state.error),
+        Err(err) => (JsonBlock::empty(), Some(err)),
+    };
+    if error.is_some() {
+        ctx.set_fail();
+    }
+    let result = BatchStatementResult {
+        id: query_id,
+        sql,
+        state: if error.is_some() {
+            ExecuteStateKind::Failed
+        } else {
+            state.state
+        },
+        schema: QueryResponseField::from_schema(data.schema().clone()),
+        data: data.into(),
+        affect: state.affect,
+        stats: QueryStats {
+            progresses: state.progresses,
+            running_time_ms: state.running_time_ms,
+        },
+        error: error.as_ref().map(QueryError::from_error_code),
+    };
+    (result, response.session)
+}
+
+/// Fetch all the pages of the query.
+async fn collect_result(query: &HttpQuery, max_result_rows: usize) -> Result<JsonBlock> {
+    let mut pages = vec![];
+    let mut rows = 0;
+    let mut page_no = 0;
+    loop {
+        let data = query
+            .get_response_page(page_no)
+            .await?
+            .data
+            .ok_or_else(|| ErrorCode::Internal("missing page data"))?;
+        rows += data.page.data.num_rows();
+        if rows > max_result_rows {
+            let msg = format!(
+                "result of the statement exceeds http_batch_max_result_rows {}",
+                max_result_rows
+            );
+            query.kill(&msg).await;
+            return Err(ErrorCode::BadArguments(msg));
+        }
+        pages.push(data.page.data);
+        match data.next_page_no {
+            Some(n) => page_no = n,
+            None => break,
+        }
+    }
+    Ok(JsonBlock::concat(pages))
+}
@@ -37,6 +37,8 @@ use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value as JsonValue;
 
+use super::batch::execute_batch;
+use super::batch::HttpBatchRequest;
 use super::query::ExecuteStateKind;
 use super::query::HttpQueryRequest;
 use super::query::HttpQueryResponseInternal;
@@ -100,7 +102,7 @@ pub struct QueryResponseField {
 }
 
 impl QueryResponseField {
-    fn from_schema(schema: DataSchemaRef) -> Vec<Self> {
+    pub(crate) fn from_schema(schema: DataSchemaRef) -> Vec<Self> {
         schema
             .fields()
             .iter()
@@ -399,10 +401,40 @@ pub(crate) async fn query_handler(
     .await
 }
 
+#[poem::handler]
+#[async_backtrace::framed]
+pub(crate) async fn query_batch_handler(
+    ctx: &HttpQueryContext,
+    Json(req): Json<HttpBatchRequest>,
+) -> PoemResult<impl IntoResponse> {
+    let trace_id = query_id_to_trace_id(&ctx.query_id);
+    let root = Span::root(full_name!(), SpanContext::new(trace_id, SpanId::default()))
+        .with_properties(|| ctx.to_minitrace_properties());
+    let _t = SlowRequestLogTracker::new(ctx);
+
+    async {
+        info!(
+            "http query new batch request: {:}",
+            mask_connection_info(&format!("{:?}", req))
+        );
+        let response = execute_batch(ctx, req).await;
+        info!(
+            "http batch response to batch_id={}, statements={}, error={:?}",
+            &response.batch_id,
+            response.results.len(),
+            response.error
+        );
+        Ok(Json(response))
+    }
+    .in_span(root)
+    .await
+}
+
 pub fn query_route() -> Route {
     // Note: endpoints except /v1/query may change without notice, use uris in response instead
     let rules = [
         ("/", post(query_handler)),
+        ("/batch", post(query_batch_handler)),
         ("/:id", get(query_state_handler)),
         ("/:id/page/:page_no", get(query_page_handler)),
         (
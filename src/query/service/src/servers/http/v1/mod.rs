@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod batch;
 mod http_query_handlers;
 pub mod json_block;
 mod load;
@@ -19,6 +20,9 @@ mod query;
 mod stage;
 mod suggestions;
 
+pub use batch::BatchResponse;
+pub use batch::BatchStatementResult;
+pub use batch::HttpBatchRequest;
 pub use http_query_handlers::make_final_uri;
 pub use http_query_handlers::make_page_uri;
 pub use http_query_handlers::make_state_uri;
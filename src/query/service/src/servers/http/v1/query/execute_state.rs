@@ -17,6 +17,7 @@ use std::time::SystemTime;
 
 use databend_common_base::base::tokio::sync::RwLock;
 use databend_common_base::base::ProgressValues;
+use databend_common_base::base::WatchNotify;
 use databend_common_base::runtime::CatchUnwindFuture;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
@@ -66,6 +67,9 @@ pub struct Progresses {
     pub write_progress: ProgressValues,
     pub result_progress: ProgressValues,
     pub total_scan: ProgressValues,
+    /// Number of strings in arithmetic which became NULL with `loose_arithmetic_coercion`.
+    #[serde(default)]
+    pub loose_coercion_failures: u64,
 }
 
 impl Progresses {
@@ -75,6 +79,7 @@ impl Progresses {
             write_progress: ctx.get_write_progress_value(),
             result_progress: ctx.get_result_progress_value(),
             total_scan: ctx.get_total_scan_value(),
+            loose_coercion_failures: ctx.get_loose_coercion_failures(),
         }
     }
 }
@@ -120,6 +125,8 @@ pub struct ExecuteStopped {
 pub struct Executor {
     pub query_id: String,
     pub state: ExecuteState,
+    /// Notified once the state is changed to `Stopped`.
+    pub stopped: Arc<WatchNotify>,
 }
 
 // ExecutorSessionState is used to record the session state when the query is stopped.
@@ -200,7 +207,8 @@ impl Executor {
     pub async fn start_to_stop(this: &Arc<RwLock<Executor>>, state: ExecuteState) {
         let mut guard = this.write().await;
         if let Starting(_) = &guard.state {
-            guard.state = state
+            guard.state = state;
+            guard.stopped.notify_waiters();
         }
     }
     #[async_backtrace::framed]
@@ -235,7 +243,8 @@ impl Executor {
                     query_duration_ms: s.ctx.get_query_duration_ms(),
                     warnings: s.ctx.pop_warnings(),
                     affect: Default::default(),
-                }))
+                }));
+                guard.stopped.notify_waiters();
             }
             Running(r) => {
                 // release session
@@ -259,7 +268,8 @@ impl Executor {
                     query_duration_ms: r.ctx.get_query_duration_ms(),
                     warnings: r.ctx.pop_warnings(),
                     affect: r.ctx.get_affect(),
-                }))
+                }));
+                guard.stopped.notify_waiters();
             }
             Stopped(s) => {
                 warn!(
@@ -23,6 +23,7 @@ use std::time::Instant;
 use databend_common_base::base::tokio;
 use databend_common_base::base::tokio::sync::Mutex as TokioMutex;
 use databend_common_base::base::tokio::sync::RwLock;
+use databend_common_base::base::WatchNotify;
 use databend_common_base::runtime::GlobalQueryRuntime;
 use databend_common_base::runtime::TrySpawn;
 use databend_common_catalog::table_context::StageAttachment;
@@ -83,17 +84,7 @@ pub struct HttpQueryRequest {
 impl HttpQueryRequest {
     pub(crate) fn fail_to_start_sql(&self, err: &ErrorCode) -> impl IntoResponse {
         metrics_incr_http_response_errors_count(err.name(), err.code());
-        let session = self.session.as_ref().map(|s| {
-            let txn_state = if matches!(s.txn_state, Some(TxnState::Active)) {
-                Some(TxnState::Fail)
-            } else {
-                s.txn_state.clone()
-            };
-            HttpSessionConf {
-                txn_state,
-                ..s.clone()
-            }
-        });
+        let session = self.session_on_failure();
         Json(QueryResponse {
             id: "".to_string(),
             stats: QueryStats::default(),
@@ -112,6 +103,22 @@ impl HttpQueryRequest {
             error: Some(QueryError::from_error_code(err)),
         })
     }
+
+    /// The session state replied when the query fails to start, an active
+    /// transaction is marked as failed.
+    pub(crate) fn session_on_failure(&self) -> Option<HttpSessionConf> {
+        self.session.as_ref().map(|s| {
+            let txn_state = if matches!(s.txn_state, Some(TxnState::Active)) {
+                Some(TxnState::Fail)
+            } else {
+                s.txn_state.clone()
+            };
+            HttpSessionConf {
+                txn_state,
+                ..s.clone()
+            }
+        })
+    }
 }
 
 impl Debug for HttpQueryRequest {
@@ -390,6 +397,7 @@ impl HttpQuery {
         let result_timeout_secs = settings.get_http_handler_result_timeout_secs()?;
         let deduplicate_label = &ctx.deduplicate_label;
         let user_agent = &ctx.user_agent;
+        let batch_id = &ctx.batch_id;
         let query_id = ctx.query_id.clone();
         let http_ctx = ctx;
         let ctx = session.create_query_context().await?;
@@ -404,6 +412,9 @@ impl HttpQuery {
         if let Some(ua) = user_agent {
             ctx.set_ua(ua.clone());
         }
+        if let Some(batch_id) = batch_id {
+            ctx.set_batch_id(batch_id.clone());
+        }
 
         // TODO: validate the query_id to be uuid format
         ctx.set_id(query_id.clone());
@@ -434,6 +445,7 @@ impl HttpQuery {
         let state = Arc::new(RwLock::new(Executor {
             query_id: query_id.clone(),
             state: ExecuteState::Starting(ExecuteStarting { ctx: ctx.clone() }),
+            stopped: Arc::new(WatchNotify::new()),
         }));
         let block_sender_closer = block_sender.closer();
         let state_clone = state.clone();
@@ -527,6 +539,13 @@ impl HttpQuery {
         })
     }
 
+    /// Waits until the query is stopped, after which its state is final.
+    #[async_backtrace::framed]
+    pub async fn wait_for_stop(&self) {
+        let stopped = self.state.read().await.stopped.clone();
+        stopped.notified().await;
+    }
+
     #[async_backtrace::framed]
     pub async fn get_response_state_only(&self) -> HttpQueryResponseInternal {
         let state = self.get_state().await;
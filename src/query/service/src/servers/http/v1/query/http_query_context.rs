@@ -32,6 +32,8 @@ pub struct HttpQueryContext {
     pub user_agent: Option<String>,
     pub http_method: String,
     pub uri: String,
+    /// Id of the batch request if the query is a statement of a batch.
+    pub batch_id: Option<String>,
 }
 
 impl HttpQueryContext {
@@ -52,6 +54,22 @@ impl HttpQueryContext {
             user_agent,
             http_method,
             uri,
+            batch_id: None,
+        }
+    }
+
+    /// Creates the context of a statement in the batch request, the query id of
+    /// the request is used as the batch id.
+    pub fn for_batch_statement(&self, query_id: String) -> Self {
+        HttpQueryContext {
+            session: self.session.clone(),
+            query_id,
+            node_id: self.node_id.clone(),
+            deduplicate_label: None,
+            user_agent: self.user_agent.clone(),
+            http_method: self.http_method.clone(),
+            uri: self.uri.clone(),
+            batch_id: Some(self.query_id.clone()),
         }
     }
 
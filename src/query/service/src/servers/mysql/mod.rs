@@ -16,6 +16,7 @@ mod mysql_federated;
 mod mysql_handler;
 mod mysql_interactive_worker;
 mod mysql_session;
+mod mysql_statement;
 #[allow(clippy::unused_io_amount)]
 mod reject_connection;
 mod tls;
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -38,6 +39,9 @@ use log::info;
 use minitrace::full_name;
 use minitrace::prelude::*;
 use opensrv_mysql::AsyncMysqlShim;
+use opensrv_mysql::Column;
+use opensrv_mysql::ColumnFlags;
+use opensrv_mysql::ColumnType;
 use opensrv_mysql::ErrorKind;
 use opensrv_mysql::InitWriter;
 use opensrv_mysql::ParamParser;
@@ -48,6 +52,8 @@ use rand::RngCore;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterFactory;
 use crate::interpreters::InterpreterQueryLog;
+use crate::servers::mysql::mysql_statement::decode_param;
+use crate::servers::mysql::mysql_statement::PreparedStatement;
 use crate::servers::mysql::writers::DFInitResultWriter;
 use crate::servers::mysql::writers::DFQueryResultWriter;
 use crate::servers::mysql::writers::ProgressReporter;
@@ -61,6 +67,8 @@ use crate::stream::DataBlockStream;
 
 struct InteractiveWorkerBase {
     session: Arc<Session>,
+    statements: HashMap<u32, PreparedStatement>,
+    next_statement_id: u32,
 }
 
 pub struct InteractiveWorker {
@@ -204,7 +212,7 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for InteractiveWorke
                 ));
             }
 
-            let mut writer = DFQueryResultWriter::create(writer, self.base.session.clone());
+            let mut writer = DFQueryResultWriter::create(writer, self.base.session.clone(), false);
 
             let instant = Instant::now();
             let query_result = self
@@ -282,36 +290,99 @@ impl InteractiveWorkerBase {
     #[async_backtrace::framed]
     async fn do_prepare<W: AsyncWrite + Unpin>(
         &mut self,
-        _: &str,
+        query: &str,
         writer: StatementMetaWriter<'_, W>,
     ) -> Result<()> {
-        writer
-            .error(
-                ErrorKind::ER_UNKNOWN_ERROR,
-                "Prepare is not support in Databend.".as_bytes(),
-            )
-            .await?;
+        info!("Prepare query: {}", query);
+        let max_statements = self.session.get_settings().get_max_prepared_stmt_count()?;
+        if self.statements.len() >= max_statements {
+            let message = format!(
+                "Can't create more than max_prepared_stmt_count statements (current value: {})",
+                max_statements
+            );
+            writer
+                .error(
+                    ErrorKind::ER_MAX_PREPARED_STMT_COUNT_REACHED,
+                    message.as_bytes(),
+                )
+                .await?;
+            return Ok(());
+        }
+
+        let statement = match PreparedStatement::create(query) {
+            Ok(statement) => statement,
+            Err(cause) => {
+                let cause = cause.display_with_sql(query);
+                writer
+                    .error(ErrorKind::ER_UNKNOWN_ERROR, cause.to_string().as_bytes())
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        // The parameters are bound as literals, so they are typeless to the client.
+        let param = Column {
+            table: "".to_string(),
+            column: "?".to_string(),
+            coltype: ColumnType::MYSQL_TYPE_VAR_STRING,
+            colflags: ColumnFlags::empty(),
+        };
+        let params = vec![param; statement.num_params()];
+
+        // The result columns are only known once the parameters are bound, they are sent
+        // with the result set of each execution.
+        let id = self.next_statement_id;
+        self.next_statement_id = self.next_statement_id.wrapping_add(1);
+        writer.reply(id, &params, &[]).await?;
+        self.statements.insert(id, statement);
         Ok(())
     }
 
     #[async_backtrace::framed]
-    async fn do_execute<W: AsyncWrite + Unpin>(
+    async fn do_execute<W: AsyncWrite + Send + Unpin>(
         &mut self,
-        _: u32,
-        _: ParamParser<'_>,
+        id: u32,
+        params: ParamParser<'_>,
         writer: QueryResultWriter<'_, W>,
     ) -> Result<()> {
-        writer
-            .error(
-                ErrorKind::ER_UNKNOWN_ERROR,
-                "Execute is not support in Databend.".as_bytes(),
-            )
-            .await?;
-        Ok(())
+        let Some(statement) = self.statements.get(&id) else {
+            let message = format!(
+                "Unknown prepared statement handler ({}) given to mysqld_stmt_execute",
+                id
+            );
+            writer
+                .error(ErrorKind::ER_UNKNOWN_STMT_HANDLER, message.as_bytes())
+                .await?;
+            return Ok(());
+        };
+
+        let format = self.session.get_format_settings();
+        let query = params
+            .into_iter()
+            .map(|param| decode_param(&param, format.timezone))
+            .collect::<Result<Vec<_>>>()
+            .and_then(|params| statement.bind(&params, format.timezone));
+
+        let instant = Instant::now();
+        let query_result = match query {
+            Ok(query) => self
+                .do_query(&query)
+                .await
+                .map_err(|err| err.display_with_sql(&query)),
+            Err(cause) => Err(cause),
+        };
+
+        // The result set of COM_STMT_EXECUTE is in the binary protocol.
+        let mut writer = DFQueryResultWriter::create(writer, self.session.clone(), true);
+        let write_result = writer.write(query_result, &format).await;
+        observe_mysql_process_request_duration(instant.elapsed());
+        write_result
     }
 
     #[async_backtrace::framed]
-    async fn do_close(&mut self, _: u32) {}
+    async fn do_close(&mut self, id: u32) {
+        self.statements.remove(&id);
+    }
 
     // Check the query is a federated or driver setup command.
     // Here we fake some values for the command which Databend not supported.
@@ -418,10 +489,11 @@ impl InteractiveWorkerBase {
             .in_span(Span::enter_with_local_parent(full_name!()))
         })?;
 
-        let query_result = query_result.await.map_err_to_code(
-            ErrorCode::TokioError,
-            || "Cannot join handle from context's runtime",
-        )?;
+        let query_result = query_result
+            .await
+            .map_err_to_code(ErrorCode::TokioError, || {
+                "Cannot join handle from context's runtime"
+            })?;
         let reporter = Box::new(ContextProgressReporter::new(context.clone(), instant))
             as Box<dyn ProgressReporter + Send>;
         query_result.map(|data| (data, Some(reporter)))
@@ -457,7 +529,11 @@ impl InteractiveWorker {
         }
 
         InteractiveWorker {
-            base: InteractiveWorkerBase { session },
+            base: InteractiveWorkerBase {
+                session,
+                statements: HashMap::new(),
+                next_statement_id: 1,
+            },
             salt: scramble,
             version: format!("{}-{}", MYSQL_VERSION, *DATABEND_COMMIT_VERSION),
             client_addr,
@@ -0,0 +1,193 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::TimeZone;
+use chrono_tz::Tz;
+use databend_common_ast::parser::token::TokenKind;
+use databend_common_ast::parser::token::Tokenizer;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Range;
+use databend_common_exception::Result;
+use databend_common_expression::serialize::read_decimal_with_size;
+use databend_common_expression::serialize::uniform_date;
+use databend_common_expression::types::date::date_to_string;
+use databend_common_expression::types::decimal::DecimalScalar;
+use databend_common_expression::types::decimal::DecimalSize;
+use databend_common_expression::types::decimal::MAX_DECIMAL128_PRECISION;
+use databend_common_expression::types::number::NumberScalar;
+use databend_common_expression::types::timestamp::timestamp_to_string;
+use databend_common_expression::Scalar;
+use opensrv_mysql::ColumnType;
+use opensrv_mysql::ParamValue;
+use opensrv_mysql::ValueInner;
+
+/// A statement prepared by COM_STMT_PREPARE, whose `?` placeholders are bound to the
+/// parameters of each COM_STMT_EXECUTE.
+pub struct PreparedStatement {
+    sql: String,
+    placeholders: Vec<Range>,
+}
+
+impl PreparedStatement {
+    pub fn create(sql: &str) -> Result<PreparedStatement> {
+        let mut placeholders = vec![];
+        for token in Tokenizer::new(sql) {
+            let token = token?;
+            match token.kind {
+                TokenKind::EOI => break,
+                TokenKind::Placeholder => placeholders.push(token.span),
+                _ => {}
+            }
+        }
+
+        Ok(PreparedStatement {
+            sql: sql.to_string(),
+            placeholders,
+        })
+    }
+
+    pub fn num_params(&self) -> usize {
+        self.placeholders.len()
+    }
+
+    /// The query with each placeholder replaced by the literal of its parameter, the dates
+    /// and timestamps are in the session timezone `tz`.
+    pub fn bind(&self, params: &[Scalar], tz: Tz) -> Result<String> {
+        if params.len() != self.placeholders.len() {
+            return Err(ErrorCode::BadArguments(format!(
+                "The prepared statement expects {} parameters, but got {}",
+                self.placeholders.len(),
+                params.len()
+            )));
+        }
+
+        let mut query = String::with_capacity(self.sql.len());
+        let mut last = 0;
+        for (placeholder, param) in self.placeholders.iter().zip(params) {
+            query.push_str(&self.sql[last..placeholder.start()]);
+            query.push_str(&param_to_sql_literal(param, tz)?);
+            last = placeholder.end();
+        }
+        query.push_str(&self.sql[last..]);
+        Ok(query)
+    }
+}
+
+fn param_to_sql_literal(param: &Scalar, tz: Tz) -> Result<String> {
+    match param {
+        Scalar::Date(d) => Ok(format!("DATE '{}'", date_to_string(*d, tz))),
+        Scalar::Timestamp(t) => Ok(format!("TIMESTAMP '{}'", timestamp_to_string(*t, tz))),
+        _ => param.to_sql_literal(),
+    }
+}
+
+/// Decodes a parameter of the binary protocol, the dates and times are in the session
+/// timezone `tz`. The long data sent by COM_STMT_SEND_LONG_DATA arrives as bytes.
+pub fn decode_param(param: &ParamValue, tz: Tz) -> Result<Scalar> {
+    match param.value.into_inner() {
+        ValueInner::NULL => Ok(Scalar::Null),
+        ValueInner::Int(v) => Ok(Scalar::Number(NumberScalar::Int64(v))),
+        ValueInner::UInt(v) => Ok(Scalar::Number(NumberScalar::UInt64(v))),
+        ValueInner::Double(v) => Ok(Scalar::Number(NumberScalar::Float64(v.into()))),
+        ValueInner::Bytes(bytes) => match param.coltype {
+            ColumnType::MYSQL_TYPE_DECIMAL | ColumnType::MYSQL_TYPE_NEWDECIMAL => {
+                decode_decimal(bytes)
+            }
+            _ => match std::str::from_utf8(bytes) {
+                Ok(s) => Ok(Scalar::String(s.to_string())),
+                Err(_) => Ok(Scalar::Binary(bytes.to_vec())),
+            },
+        },
+        ValueInner::Date(bytes) => {
+            let datetime = decode_datetime(bytes)?;
+            Ok(Scalar::Date(uniform_date(datetime.date())))
+        }
+        ValueInner::Datetime(bytes) => {
+            let datetime = decode_datetime(bytes)?;
+            match tz.from_local_datetime(&datetime).earliest() {
+                Some(datetime) => Ok(Scalar::Timestamp(datetime.timestamp_micros())),
+                None => Err(ErrorCode::BadArguments(format!(
+                    "The datetime parameter {} does not exist in the timezone {}",
+                    datetime, tz
+                ))),
+            }
+        }
+        // There is no TIME type, the time is bound as a string like `-12:30:00.500000`.
+        ValueInner::Time(bytes) => Ok(Scalar::String(decode_time(bytes)?)),
+    }
+}
+
+fn decode_decimal(bytes: &[u8]) -> Result<Scalar> {
+    let scale = match bytes.iter().position(|b| *b == b'.') {
+        Some(pos) => bytes.len() - pos - 1,
+        None => 0,
+    };
+    let size = DecimalSize {
+        precision: MAX_DECIMAL128_PRECISION,
+        scale: scale.min(MAX_DECIMAL128_PRECISION as usize) as u8,
+    };
+    let (n, _) = read_decimal_with_size::<i128>(bytes, size, true, true)?;
+    Ok(Scalar::Decimal(DecimalScalar::Decimal128(n, size)))
+}
+
+// The binary DATE and DATETIME are (year: u16, month, day, [hour, minute, second,
+// [microsecond: u32]]), the length is 4, 7 or 11. The zero date of length 0 is invalid.
+fn decode_datetime(bytes: &[u8]) -> Result<NaiveDateTime> {
+    let datetime = match bytes.len() {
+        4 | 7 | 11 => {
+            let year = u16::from_le_bytes([bytes[0], bytes[1]]) as i32;
+            let (hour, minute, second) = match bytes.len() {
+                4 => (0, 0, 0),
+                _ => (bytes[4] as u32, bytes[5] as u32, bytes[6] as u32),
+            };
+            let micros = match bytes.len() {
+                11 => u32::from_le_bytes([bytes[7], bytes[8], bytes[9], bytes[10]]),
+                _ => 0,
+            };
+            NaiveDate::from_ymd_opt(year, bytes[2] as u32, bytes[3] as u32)
+                .and_then(|date| date.and_hms_micro_opt(hour, minute, second, micros))
+        }
+        _ => None,
+    };
+
+    datetime
+        .ok_or_else(|| ErrorCode::BadArguments(format!("Invalid datetime parameter {:?}", bytes)))
+}
+
+// The binary TIME is (is_negative, days: u32, hour, minute, second, [microsecond: u32]),
+// the length is 0, 8 or 12.
+fn decode_time(bytes: &[u8]) -> Result<String> {
+    match bytes.len() {
+        0 => Ok("00:00:00".to_string()),
+        8 | 12 => {
+            let sign = if bytes[0] == 1 { "-" } else { "" };
+            let days = u32::from_le_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
+            let hours = days * 24 + bytes[5] as u32;
+            let time = format!("{}{:02}:{:02}:{:02}", sign, hours, bytes[6], bytes[7]);
+            match bytes.len() {
+                12 => {
+                    let micros = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+                    Ok(format!("{}.{:06}", time, micros))
+                }
+                _ => Ok(time),
+            }
+        }
+        _ => Err(ErrorCode::BadArguments(format!(
+            "Invalid time parameter {:?}",
+            bytes
+        ))),
+    }
+}
@@ -48,6 +48,7 @@ use databend_common_catalog::query_kind::QueryKind;
 use databend_common_catalog::runtime_filter_info::RuntimeFilterInfo;
 use databend_common_catalog::statistics::data_cache_statistics::DataCacheMetrics;
 use databend_common_catalog::table_args::TableArgs;
+use databend_common_catalog::table_context::FinishedStageInfo;
 use databend_common_catalog::table_context::MaterializedCtesBlocks;
 use databend_common_catalog::table_context::StageAttachment;
 use databend_common_config::GlobalConfig;
@@ -257,6 +258,25 @@ impl QueryContext {
         self.shared.pop_warnings()
     }
 
+    /// Number of strings in arithmetic which became NULL with `loose_arithmetic_coercion`.
+    pub fn get_loose_coercion_failures(&self) -> u64 {
+        self.shared.loose_coercion_failures.load(Ordering::Relaxed)
+    }
+
+    /// Takes the failures counted since the previous call, used to send the failures of a
+    /// remote fragment to the coordinator with the progress.
+    pub fn fetch_loose_coercion_failures(&self) -> u64 {
+        self.shared
+            .loose_coercion_failures
+            .swap(0, Ordering::Relaxed)
+    }
+
+    pub fn add_loose_coercion_failures(&self, failures: u64) {
+        self.shared
+            .loose_coercion_failures
+            .fetch_add(failures, Ordering::Relaxed);
+    }
+
     pub fn get_data_metrics(&self) -> StorageMetrics {
         self.shared.get_data_metrics()
     }
@@ -286,6 +306,18 @@ impl QueryContext {
         ua.clone()
     }
 
+    pub fn set_batch_id(&self, batch_id: String) {
+        *self.shared.batch_id.write() = Some(batch_id);
+    }
+
+    pub fn get_batch_id(&self) -> Option<String> {
+        self.shared.batch_id.read().clone()
+    }
+
+    pub fn get_query_lineage(&self) -> Option<serde_json::Value> {
+        self.shared.query_lineage.read().clone()
+    }
+
     pub fn get_query_duration_ms(&self) -> i64 {
         let query_start_time = convert_query_log_timestamp(self.shared.created_time);
         let finish_time = *self.shared.finish_time.read();
@@ -499,6 +531,10 @@ impl TableContext for QueryContext {
         self.shared.attach_query_str(kind, query);
     }
 
+    fn attach_query_lineage(&self, lineage: serde_json::Value) {
+        *self.shared.query_lineage.write() = Some(lineage);
+    }
+
     /// Get the session running query.
     fn get_query_str(&self) -> String {
         self.shared.get_query_str()
@@ -612,6 +648,7 @@ impl TableContext for QueryContext {
         let numeric_cast_option = self.get_settings().get_numeric_cast_option()?;
         let rounding_mode = numeric_cast_option.as_str() == "rounding";
         let disable_variant_check = self.get_settings().get_disable_variant_check()?;
+        let random_seed = self.get_settings().get_query_random_seed()?;
 
         let query_config = &GlobalConfig::instance().query;
 
@@ -629,6 +666,9 @@ impl TableContext for QueryContext {
 
             external_server_connect_timeout_secs,
             external_server_request_timeout_secs,
+
+            random_seed,
+            loose_coercion_failures: self.shared.loose_coercion_failures.clone(),
         })
     }
 
@@ -940,6 +980,10 @@ impl TableContext for QueryContext {
         queries_profile
     }
 
+    fn get_finished_stages(&self) -> Vec<FinishedStageInfo> {
+        DataExchangeManager::instance().get_finished_stages()
+    }
+
     fn set_merge_into_join(&self, join: MergeIntoJoin) {
         let mut merge_into_join = self.shared.merge_into_join.write();
         *merge_into_join = join;
@@ -15,6 +15,7 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Weak;
@@ -77,6 +78,10 @@ pub struct QueryContextShared {
     pub(in crate::sessions) result_progress: Arc<Progress>,
     pub(in crate::sessions) error: Arc<Mutex<Option<ErrorCode>>>,
     pub(in crate::sessions) warnings: Arc<Mutex<Vec<String>>>,
+    /// Number of strings in arithmetic which became NULL with `loose_arithmetic_coercion`.
+    pub(in crate::sessions) loose_coercion_failures: Arc<AtomicU64>,
+    /// The part of `loose_coercion_failures` already reported in the warnings.
+    pub(in crate::sessions) reported_loose_coercion_failures: Arc<AtomicU64>,
     pub(in crate::sessions) session: Arc<Session>,
     pub(in crate::sessions) runtime: Arc<RwLock<Option<Arc<Runtime>>>>,
     pub(in crate::sessions) init_query_id: Arc<RwLock<String>>,
@@ -111,6 +116,10 @@ pub struct QueryContextShared {
 
     // Client User-Agent
     pub(in crate::sessions) user_agent: Arc<RwLock<String>>,
+    // Id of the http query batch this query belongs to.
+    pub(in crate::sessions) batch_id: Arc<RwLock<Option<String>>>,
+    // Column lineage of the write statement, recorded in the query log.
+    pub(in crate::sessions) query_lineage: Arc<RwLock<Option<serde_json::Value>>>,
     /// Key is (cte index, used_count), value contains cte's materialized blocks
     pub(in crate::sessions) materialized_cte_tables: MaterializedCtesBlocks,
 
@@ -141,6 +150,8 @@ impl QueryContextShared {
             write_progress: Arc::new(Progress::create()),
             error: Arc::new(Mutex::new(None)),
             warnings: Arc::new(Mutex::new(vec![])),
+            loose_coercion_failures: Arc::new(AtomicU64::new(0)),
+            reported_loose_coercion_failures: Arc::new(AtomicU64::new(0)),
             runtime: Arc::new(RwLock::new(None)),
             running_query: Arc::new(RwLock::new(None)),
             running_query_kind: Arc::new(RwLock::new(None)),
@@ -161,6 +172,8 @@ impl QueryContextShared {
             auto_compact_after_write: Arc::new(AtomicBool::new(true)),
             status: Arc::new(RwLock::new("null".to_string())),
             user_agent: Arc::new(RwLock::new("null".to_string())),
+            batch_id: Arc::new(RwLock::new(None)),
+            query_lineage: Arc::new(RwLock::new(None)),
             materialized_cte_tables: Arc::new(Default::default()),
             join_spill_progress: Arc::new(Progress::create()),
             agg_spill_progress: Arc::new(Progress::create()),
@@ -189,8 +202,20 @@ impl QueryContextShared {
 
     pub fn pop_warnings(&self) -> Vec<String> {
         let mut guard = self.warnings.lock();
-        let warnings = (*guard).clone();
+        let mut warnings = (*guard).clone();
         (*guard).clear();
+
+        // The total stays in the counter for the statistics of the query.
+        let total = self.loose_coercion_failures.load(Ordering::Relaxed);
+        let reported = self
+            .reported_loose_coercion_failures
+            .swap(total, Ordering::Relaxed);
+        if total > reported {
+            let failures = total - reported;
+            warnings.push(format!(
+                "{failures} strings in arithmetic are not numbers and became NULL"
+            ));
+        }
         warnings
     }
 
@@ -32,6 +32,7 @@ use databend_storages_common_txn::TxnManagerRef;
 use log::debug;
 use parking_lot::RwLock;
 
+use crate::api::DataExchangeManager;
 use crate::clusters::ClusterDiscovery;
 use crate::servers::http::v1::HttpQueryManager;
 use crate::sessions::session_privilege_mgr::SessionPrivilegeManager;
@@ -120,6 +121,10 @@ impl Session {
 
         let http_queries_manager = HttpQueryManager::instance();
         http_queries_manager.kill_session(&self.id);
+
+        // The query contexts held by the exchanges keep the session alive, so they are
+        // released here instead of waiting for the session to be dropped.
+        DataExchangeManager::instance().on_session_closed(&self.id);
     }
 
     pub fn kill(self: &Arc<Self>) {
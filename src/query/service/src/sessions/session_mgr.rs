@@ -20,6 +20,7 @@ use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Weak;
 use std::time::Duration;
+use std::time::Instant;
 use std::time::SystemTime;
 
 use databend_common_base::base::tokio;
@@ -38,6 +39,7 @@ use futures::StreamExt;
 use log::info;
 use parking_lot::RwLock;
 
+use crate::api::DataExchangeManager;
 use crate::sessions::session::Session;
 use crate::sessions::ProcessInfo;
 use crate::sessions::SessionContext;
@@ -52,6 +54,9 @@ pub struct SessionManager {
     // When typ is MySQL, insert into this map, key is id, val is MySQL connection id.
     pub(crate) mysql_conn_map: Arc<RwLock<HashMap<Option<u32>, String>>>,
     pub(in crate::sessions) mysql_basic_conn_id: AtomicU32,
+
+    // The monotonic start time of the server, for the uptime.
+    pub(in crate::sessions) started_at: Instant,
 }
 
 impl SessionManager {
@@ -69,6 +74,7 @@ impl SessionManager {
             status: Arc::new(RwLock::new(SessionManagerStatus::default())),
             mysql_conn_map: Arc::new(RwLock::new(HashMap::with_capacity(max_sessions))),
             active_sessions: Arc::new(RwLock::new(HashMap::with_capacity(max_sessions))),
+            started_at: Instant::now(),
         })
     }
 
@@ -190,6 +196,9 @@ impl SessionManager {
             }
         }
 
+        // abort the queries prepared by the session on this node
+        DataExchangeManager::instance().on_session_closed(session_id);
+
         {
             let sessions_count = { self.active_sessions.read().len() };
 
@@ -269,6 +278,21 @@ impl SessionManager {
             .collect::<Vec<_>>()
     }
 
+    /// Returns true if a query with `query_id` is running in any of the active sessions.
+    pub fn is_query_running(&self, query_id: &str) -> bool {
+        // Drop the read lock before upgrading the sessions, see `processes_info`.
+        let active_sessions = {
+            let active_sessions_guard = self.active_sessions.read();
+            active_sessions_guard.values().cloned().collect::<Vec<_>>()
+        };
+
+        active_sessions.into_iter().any(|weak_ptr| {
+            weak_ptr
+                .upgrade()
+                .is_some_and(|session| session.get_current_query_id().as_deref() == Some(query_id))
+        })
+    }
+
     fn destroy_idle_sessions(sessions: &Arc<RwLock<HashMap<String, Weak<Session>>>>) -> bool {
         // Read lock does not support reentrant
         // https://github.com/Amanieu/parking_lot::/blob/lock_api-0.4.4/lock_api/src/rwlock.rs#L422
@@ -304,6 +328,11 @@ impl SessionManager {
         Ok(())
     }
 
+    /// The time elapsed since the server started.
+    pub fn uptime(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
     pub fn get_current_session_status(&self) -> SessionManagerStatus {
         let mut status_t = self.status.read().clone();
 
@@ -88,6 +88,8 @@ impl Spiller {
         spiller_type: SpillerType,
     ) -> Result<Self> {
         let join_spilling_partition_bits = ctx.get_settings().get_join_spilling_partition_bits()?;
+        // The partition id is stored in u8.
+        debug_assert!(join_spilling_partition_bits <= 8);
         Ok(Self {
             ctx,
             operator,
@@ -27,6 +27,7 @@ use databend_common_catalog::plan::PushDownInfo;
 use databend_common_catalog::table::TableStatistics;
 use databend_common_catalog::table_args::TableArgs;
 use databend_common_exception::Result;
+use databend_common_expression::block_origin;
 use databend_common_expression::type_check::check_number;
 use databend_common_expression::types::number::NumberScalar;
 use databend_common_expression::types::number::UInt64Type;
@@ -135,6 +136,7 @@ impl Table for NumbersTable {
         _dry_run: bool,
     ) -> Result<(PartStatistics, Partitions)> {
         let max_block_size = ctx.get_settings().get_max_block_size()?;
+        debug_assert!(max_block_size != 0);
         let mut limit = None;
 
         if let Some(extras) = &push_downs {
@@ -261,11 +263,13 @@ impl SyncSource for NumbersSource {
             remain_size => {
                 let step = std::cmp::min(remain_size, self.step);
                 let column_data = (self.begin..self.begin + step).collect::<Vec<_>>();
+                let origin = block_origin(Self::NAME.as_bytes(), self.begin);
 
                 self.begin += step;
-                Ok(Some(DataBlock::new_from_columns(vec![
-                    UInt64Type::from_data(column_data),
-                ])))
+                Ok(Some(
+                    DataBlock::new_from_columns(vec![UInt64Type::from_data(column_data)])
+                        .with_origin(origin),
+                ))
             }
         }
     }
@@ -0,0 +1,380 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use databend_common_ast::ast::Engine;
+use databend_common_catalog::catalog_kind::CATALOG_DEFAULT;
+use databend_common_exception::Result;
+use databend_common_expression::block_debug::pretty_format_blocks;
+use databend_common_expression::infer_table_schema;
+use databend_common_expression::types::BooleanType;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::DateType;
+use databend_common_expression::types::Float64Type;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::Int64Type;
+use databend_common_expression::types::StringType;
+use databend_common_expression::types::TimestampType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::BlockEntry;
+use databend_common_expression::Column;
+use databend_common_expression::ColumnBuilder;
+use databend_common_expression::DataBlock;
+use databend_common_expression::DataField;
+use databend_common_expression::DataSchemaRef;
+use databend_common_expression::DataSchemaRefExt;
+use databend_common_expression::FromData;
+use databend_common_expression::Scalar;
+use databend_common_expression::TableSchemaRef;
+use databend_common_expression::Value;
+use databend_common_meta_app::schema::CreateOption;
+use databend_common_sql::plans::CreateTablePlan;
+
+use crate::interpreters::CreateTableInterpreter;
+use crate::interpreters::Interpreter;
+use crate::sessions::TableContext;
+use crate::storages::Table;
+use crate::test_kits::TestFixture;
+
+/// Builds a [`DataBlock`] and its schema column by column.
+///
+/// ```ignore
+/// let fixture = BlockFixture::new()
+///     .col_i32_opt("a", [Some(1), Some(2), None])
+///     .col_str_opt("s", [Some("x"), None, Some("z")])
+///     .col_date("d", ["2021-01-01", "2021-01-02", "2021-01-03"]);
+/// let block = fixture.build();
+/// ```
+///
+/// The typed helpers cover the common types, [`BlockFixture::column`] and
+/// [`BlockFixture::scalars`] take a column or values of any type.
+#[derive(Clone, Default)]
+pub struct BlockFixture {
+    fields: Vec<DataField>,
+    entries: Vec<BlockEntry>,
+    num_rows: Option<usize>,
+}
+
+impl BlockFixture {
+    pub fn new() -> BlockFixture {
+        BlockFixture::default()
+    }
+
+    /// Sets the number of rows, only required if all the columns are constant.
+    pub fn num_rows(mut self, num_rows: usize) -> BlockFixture {
+        self.check_num_rows("", num_rows);
+        self
+    }
+
+    pub fn column(mut self, name: &str, column: Column) -> BlockFixture {
+        self.check_num_rows(name, column.len());
+        let data_type = column.data_type();
+        self.fields.push(DataField::new(name, data_type.clone()));
+        self.entries
+            .push(BlockEntry::new(data_type, Value::Column(column)));
+        self
+    }
+
+    /// Adds a constant column, which is kept as a scalar in the block.
+    pub fn constant(mut self, name: &str, data_type: DataType, scalar: Scalar) -> BlockFixture {
+        self.fields.push(DataField::new(name, data_type.clone()));
+        self.entries
+            .push(BlockEntry::new(data_type, Value::Scalar(scalar)));
+        self
+    }
+
+    /// Adds a column of `data_type` from its values, `Scalar::Null` is a null of a nullable column.
+    pub fn scalars(
+        self,
+        name: &str,
+        data_type: DataType,
+        values: impl IntoIterator<Item = Scalar>,
+    ) -> BlockFixture {
+        let mut builder = ColumnBuilder::with_capacity(&data_type, 0);
+        for value in values {
+            builder.push(value.as_ref());
+        }
+        self.column(name, builder.build())
+    }
+
+    pub fn col<T: FromData<D>, D>(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = D>,
+    ) -> BlockFixture {
+        self.column(name, T::from_data(values.into_iter().collect()))
+    }
+
+    /// Adds a nullable column, `None` is a null.
+    pub fn col_opt<T: FromData<D>, D>(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = Option<D>>,
+    ) -> BlockFixture {
+        self.column(name, T::from_opt_data(values.into_iter().collect()))
+    }
+
+    pub fn col_i32(self, name: &str, values: impl IntoIterator<Item = i32>) -> BlockFixture {
+        self.col::<Int32Type, _>(name, values)
+    }
+
+    pub fn col_i32_opt(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = Option<i32>>,
+    ) -> BlockFixture {
+        self.col_opt::<Int32Type, _>(name, values)
+    }
+
+    pub fn col_i64(self, name: &str, values: impl IntoIterator<Item = i64>) -> BlockFixture {
+        self.col::<Int64Type, _>(name, values)
+    }
+
+    pub fn col_i64_opt(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = Option<i64>>,
+    ) -> BlockFixture {
+        self.col_opt::<Int64Type, _>(name, values)
+    }
+
+    pub fn col_u64(self, name: &str, values: impl IntoIterator<Item = u64>) -> BlockFixture {
+        self.col::<UInt64Type, _>(name, values)
+    }
+
+    pub fn col_u64_opt(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = Option<u64>>,
+    ) -> BlockFixture {
+        self.col_opt::<UInt64Type, _>(name, values)
+    }
+
+    pub fn col_f64(self, name: &str, values: impl IntoIterator<Item = f64>) -> BlockFixture {
+        self.col::<Float64Type, _>(name, values)
+    }
+
+    pub fn col_f64_opt(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = Option<f64>>,
+    ) -> BlockFixture {
+        self.col_opt::<Float64Type, _>(name, values)
+    }
+
+    pub fn col_bool(self, name: &str, values: impl IntoIterator<Item = bool>) -> BlockFixture {
+        self.col::<BooleanType, _>(name, values)
+    }
+
+    pub fn col_bool_opt(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = Option<bool>>,
+    ) -> BlockFixture {
+        self.col_opt::<BooleanType, _>(name, values)
+    }
+
+    pub fn col_str<'a>(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = &'a str>,
+    ) -> BlockFixture {
+        self.col::<StringType, _>(name, values)
+    }
+
+    pub fn col_str_opt<'a>(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = Option<&'a str>>,
+    ) -> BlockFixture {
+        self.col_opt::<StringType, _>(name, values)
+    }
+
+    /// Adds a date column from strings like `2021-01-01`.
+    pub fn col_date<'a>(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = &'a str>,
+    ) -> BlockFixture {
+        self.col::<DateType, _>(name, values.into_iter().map(parse_date))
+    }
+
+    pub fn col_date_opt<'a>(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = Option<&'a str>>,
+    ) -> BlockFixture {
+        self.col_opt::<DateType, _>(name, values.into_iter().map(|v| v.map(parse_date)))
+    }
+
+    /// Adds a timestamp column from strings like `2021-01-01 00:00:00.000000`, in UTC.
+    pub fn col_timestamp<'a>(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = &'a str>,
+    ) -> BlockFixture {
+        self.col::<TimestampType, _>(name, values.into_iter().map(parse_timestamp))
+    }
+
+    pub fn col_timestamp_opt<'a>(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = Option<&'a str>>,
+    ) -> BlockFixture {
+        self.col_opt::<TimestampType, _>(name, values.into_iter().map(|v| v.map(parse_timestamp)))
+    }
+
+    /// Adds an array column, each of `arrays` is the items of one row.
+    pub fn col_array(
+        self,
+        name: &str,
+        item_type: DataType,
+        arrays: impl IntoIterator<Item = Column>,
+    ) -> BlockFixture {
+        self.column(name, array_column(item_type, arrays))
+    }
+
+    /// Adds a tuple column from the columns of its fields.
+    pub fn col_tuple(self, name: &str, fields: Vec<Column>) -> BlockFixture {
+        self.column(name, Column::Tuple(fields))
+    }
+
+    pub fn build(&self) -> DataBlock {
+        DataBlock::new(self.entries.clone(), self.num_rows.unwrap_or(0))
+    }
+
+    pub fn schema(&self) -> DataSchemaRef {
+        DataSchemaRefExt::create(self.fields.clone())
+    }
+
+    pub fn table_schema(&self) -> TableSchemaRef {
+        infer_table_schema(&self.schema()).unwrap()
+    }
+
+    /// Renders the block in the format of `assert_blocks_eq`, so the expected output can
+    /// be built with another fixture.
+    pub fn expected_lines(&self) -> Vec<String> {
+        expected_lines(&[self.build()])
+    }
+
+    fn check_num_rows(&mut self, name: &str, num_rows: usize) {
+        match self.num_rows {
+            None => self.num_rows = Some(num_rows),
+            Some(expected) => assert_eq!(
+                expected, num_rows,
+                "column `{name}` has {num_rows} rows, but the block has {expected} rows"
+            ),
+        }
+    }
+}
+
+/// Builds an array column, each of `arrays` is the items of one row. Nested arrays are
+/// built by passing array columns as the items.
+pub fn array_column(item_type: DataType, arrays: impl IntoIterator<Item = Column>) -> Column {
+    let data_type = DataType::Array(Box::new(item_type));
+    let mut builder = ColumnBuilder::with_capacity(&data_type, 0);
+    for array in arrays {
+        builder.push(Scalar::Array(array).as_ref());
+    }
+    builder.build()
+}
+
+/// Renders the blocks in the format of `assert_blocks_eq`.
+pub fn expected_lines(blocks: &[DataBlock]) -> Vec<String> {
+    pretty_format_blocks(blocks)
+        .unwrap()
+        .trim()
+        .lines()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+fn parse_date(value: &str) -> i32 {
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .unwrap_or_else(|e| panic!("invalid date `{value}`: {e}"));
+    date.signed_duration_since(NaiveDate::from_ymd_opt(1970, 1, 1).unwrap())
+        .num_days() as i32
+}
+
+fn parse_timestamp(value: &str) -> i64 {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+        .unwrap_or_else(|e| panic!("invalid timestamp `{value}`: {e}"))
+        .and_utc()
+        .timestamp_micros()
+}
+
+/// A memory engine table filled with the blocks of the fixtures, so that the planner
+/// and executor tests can reference it by name.
+pub struct TableFixture {
+    name: String,
+    schema: TableSchemaRef,
+    blocks: Vec<DataBlock>,
+}
+
+impl TableFixture {
+    pub fn new(name: &str, block: &BlockFixture) -> TableFixture {
+        TableFixture {
+            name: name.to_string(),
+            schema: block.table_schema(),
+            blocks: vec![block.build()],
+        }
+    }
+
+    pub fn with_block(mut self, block: &BlockFixture) -> TableFixture {
+        assert_eq!(self.schema, block.table_schema());
+        self.blocks.push(block.build());
+        self
+    }
+
+    /// Creates the table in the default database of `fixture`, which must be created first.
+    pub async fn create(&self, fixture: &TestFixture) -> Result<Arc<dyn Table>> {
+        let ctx = fixture.new_query_ctx().await?;
+        let plan = CreateTablePlan {
+            create_option: CreateOption::None,
+            tenant: fixture.default_tenant(),
+            catalog: fixture.default_catalog_name(),
+            database: fixture.default_db_name(),
+            table: self.name.clone(),
+            schema: self.schema.clone(),
+            engine: Engine::Memory,
+            engine_options: Default::default(),
+            storage_params: None,
+            read_only_attach: false,
+            part_prefix: "".to_string(),
+            options: Default::default(),
+            field_comments: vec![],
+            as_select: None,
+            cluster_key: None,
+        };
+        let interpreter = CreateTableInterpreter::try_create(ctx.clone(), plan)?;
+        let _ = interpreter.execute(ctx.clone()).await?;
+
+        let table = ctx
+            .get_catalog(CATALOG_DEFAULT)
+            .await?
+            .get_table(
+                fixture.default_tenant().as_str(),
+                fixture.default_db_name().as_str(),
+                &self.name,
+            )
+            .await?;
+        fixture
+            .append_commit_blocks(table.clone(), self.blocks.clone(), false, true)
+            .await?;
+        Ok(table)
+    }
+}
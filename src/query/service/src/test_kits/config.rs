@@ -74,6 +74,26 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn max_concurrent_stages(mut self, value: u64) -> ConfigBuilder {
+        self.conf.query.max_concurrent_stages = value;
+        self
+    }
+
+    pub fn max_queued_stages(mut self, value: u64) -> ConfigBuilder {
+        self.conf.query.max_queued_stages = value;
+        self
+    }
+
+    pub fn max_stage_queued_secs(mut self, value: u64) -> ConfigBuilder {
+        self.conf.query.max_stage_queued_secs = value;
+        self
+    }
+
+    pub fn max_finished_stages(mut self, value: u64) -> ConfigBuilder {
+        self.conf.query.max_finished_stages = value;
+        self
+    }
+
     pub fn parquet_fast_read_bytes(mut self, value: u64) -> ConfigBuilder {
         self.conf.query.parquet_fast_read_bytes = Some(value);
         self
@@ -129,6 +149,16 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn rpc_tls_query_server_root_ca_cert(mut self, value: impl Into<String>) -> ConfigBuilder {
+        self.conf.query.rpc_tls_query_server_root_ca_cert = value.into();
+        self
+    }
+
+    pub fn rpc_tls_query_service_domain_name(mut self, value: impl Into<String>) -> ConfigBuilder {
+        self.conf.query.rpc_tls_query_service_domain_name = value.into();
+        self
+    }
+
     pub fn query_flight_address(mut self, value: impl Into<String>) -> ConfigBuilder {
         self.conf.query.flight_api_address = value.into();
         self
@@ -14,6 +14,7 @@
 
 #![allow(clippy::too_many_arguments)]
 
+mod block_fixture;
 mod block_writer;
 mod check;
 mod cluster;
@@ -22,6 +23,7 @@ mod context;
 mod fixture;
 mod fuse;
 
+pub use block_fixture::*;
 pub use block_writer::BlockWriter;
 pub use check::*;
 pub use cluster::ClusterDescriptor;
@@ -0,0 +1,1017 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_channel::Receiver;
+use databend_common_arrow::arrow_format::flight::data::FlightData;
+use databend_common_base::base::tokio;
+use databend_common_catalog::query_kind::QueryKind;
+use databend_common_catalog::table_context::FinishedStageInfo;
+use databend_common_catalog::table_context::FinishedStageStatus;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::types::ValueType;
+use databend_common_expression::DataBlock;
+use databend_common_expression::DataField;
+use databend_common_expression::DataSchemaRef;
+use databend_common_expression::DataSchemaRefExt;
+use databend_common_expression::FromData;
+use databend_common_expression::FunctionID;
+use databend_common_expression::RemoteExpr;
+use databend_common_pipeline_core::processors::InputPort;
+use databend_common_pipeline_core::processors::ProcessorPtr;
+use databend_common_pipeline_core::Pipe;
+use databend_common_pipeline_core::PipeItem;
+use databend_common_pipeline_core::Pipeline;
+use databend_common_pipeline_sinks::Sink;
+use databend_common_pipeline_sinks::Sinker;
+use databend_common_settings::FlightCompression;
+use databend_common_sql::executor::physical_plans::ConstantTableScan;
+use databend_common_sql::executor::physical_plans::ExchangeSource;
+use databend_common_sql::executor::PhysicalPlan;
+use databend_common_sql::plans::Plan;
+use databend_query::api::exchange_shuffle;
+use databend_query::api::DataExchange;
+use databend_query::api::DataExchangeManager;
+use databend_query::api::DataPacket;
+use databend_query::api::DefaultExchangeInjector;
+use databend_query::api::ExchangeInjector;
+use databend_query::api::ExchangeSorting;
+use databend_query::api::FlightReceiver;
+use databend_query::api::FlightScatter;
+use databend_query::api::FragmentPlanPacket;
+use databend_query::api::FragmentStreamId;
+use databend_query::api::HashFlightScatter;
+use databend_query::api::MergeExchange;
+use databend_query::api::MergeExchangeParams;
+use databend_query::api::QueryFragmentsPlanPacket;
+use databend_query::api::RunningStreamInfo;
+use databend_query::api::ShuffleDataExchange;
+use databend_query::api::ShuffleExchangeParams;
+use databend_query::api::StreamResumeConf;
+use databend_query::interpreters::SelectInterpreter;
+use databend_query::pipelines::executor::ExecutorSettings;
+use databend_query::pipelines::executor::PipelineCompleteExecutor;
+use databend_query::pipelines::executor::PipelinePullingExecutor;
+use databend_query::pipelines::PipelineBuilder;
+use databend_query::sessions::QueryContext;
+use databend_query::sessions::SessionManager;
+use databend_query::sessions::SessionType;
+use databend_query::sessions::TableContext;
+use databend_query::sql::Planner;
+use databend_query::test_kits::*;
+use parking_lot::Mutex;
+use tonic::Status;
+
+const EXECUTOR: &str = "node1";
+
+fn schema() -> DataSchemaRef {
+    DataSchemaRefExt::create(vec![DataField::new(
+        "a",
+        DataType::Number(NumberDataType::Int32),
+    )])
+}
+
+fn constant_fragment(fragment_id: usize) -> FragmentPlanPacket {
+    let plan = PhysicalPlan::ConstantTableScan(ConstantTableScan {
+        plan_id: 0,
+        values: vec![Int32Type::from_data(vec![1, 2, 3])],
+        num_rows: 3,
+        output_schema: schema(),
+    });
+    FragmentPlanPacket::create(fragment_id, plan, None)
+}
+
+fn exchange_source_fragment(
+    fragment_id: usize,
+    source_fragment_id: usize,
+    query_id: &str,
+) -> FragmentPlanPacket {
+    let plan = PhysicalPlan::ExchangeSource(ExchangeSource {
+        plan_id: 0,
+        schema: schema(),
+        source_fragment_id,
+        query_id: query_id.to_string(),
+    });
+    FragmentPlanPacket::create(fragment_id, plan, None)
+}
+
+fn fragments_packet(
+    ctx: &Arc<QueryContext>,
+    fragments: Vec<FragmentPlanPacket>,
+) -> QueryFragmentsPlanPacket {
+    QueryFragmentsPlanPacket::create(
+        ctx.get_id(),
+        QueryKind::Query,
+        EXECUTOR.to_string(),
+        fragments,
+        HashMap::new(),
+        ctx.get_settings(),
+        EXECUTOR.to_string(),
+    )
+}
+
+fn register_query(manager: &DataExchangeManager, ctx: &Arc<QueryContext>) -> Result<()> {
+    manager.handle_statistics_exchange(ctx.get_id(), EXECUTOR.to_string())?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_prepare_fragment_before_its_source() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let ctx = fixture.new_query_ctx().await?;
+    let query_id = ctx.get_id();
+
+    let manager = DataExchangeManager::instance();
+    register_query(&manager, &ctx)?;
+
+    // Fragment 2 reads from fragment 1, which is not prepared yet.
+    let packet = fragments_packet(&ctx, vec![exchange_source_fragment(2, 1, &query_id)]);
+    manager.init_query_fragments_plan(&ctx, &packet)?;
+
+    // Preparing the source fragment also builds the deferred fragment.
+    let packet = fragments_packet(&ctx, vec![constant_fragment(1)]);
+    manager.init_query_fragments_plan(&ctx, &packet)?;
+
+    let build_res = manager.get_fragment_source(&query_id, 2, DefaultExchangeInjector::create())?;
+    assert!(build_res.main_pipeline.is_pulling_pipeline()?);
+
+    manager.on_finished_query(&query_id);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_prepare_duplicated_fragment() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let ctx = fixture.new_query_ctx().await?;
+    let query_id = ctx.get_id();
+
+    let manager = DataExchangeManager::instance();
+    register_query(&manager, &ctx)?;
+
+    let packet = fragments_packet(&ctx, vec![constant_fragment(1)]);
+    manager.init_query_fragments_plan(&ctx, &packet)?;
+
+    // Prepare the same fragment again.
+    let res = manager.init_query_fragments_plan(&ctx, &packet);
+    assert_eq!(res.unwrap_err().code(), ErrorCode::FRAGMENT_ALREADY_EXISTS);
+
+    // Duplicated fragments in one packet.
+    let packet = fragments_packet(&ctx, vec![constant_fragment(3), constant_fragment(3)]);
+    let res = manager.init_query_fragments_plan(&ctx, &packet);
+    assert_eq!(res.unwrap_err().code(), ErrorCode::FRAGMENT_ALREADY_EXISTS);
+
+    manager.on_finished_query(&query_id);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_prepare_fragment_with_bad_scatter_key() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let ctx = fixture.new_query_ctx().await?;
+    let query_id = ctx.get_id();
+
+    let manager = DataExchangeManager::instance();
+    register_query(&manager, &ctx)?;
+
+    let column = |id: usize, data_type: DataType| RemoteExpr::ColumnRef {
+        span: None,
+        id,
+        data_type,
+        display_name: "a".to_string(),
+    };
+    let shuffle_fragment = |fragment_id: usize, key: RemoteExpr| {
+        let mut fragment = constant_fragment(fragment_id);
+        fragment.data_exchange = Some(ShuffleDataExchange::create(
+            vec![EXECUTOR.to_string()],
+            vec![key],
+        ));
+        fragment
+    };
+
+    let bad_keys = vec![
+        // The fragment only outputs one column.
+        column(1, DataType::Number(NumberDataType::Int32)),
+        // The column is not a string.
+        column(0, DataType::String),
+        // The function does not exist.
+        RemoteExpr::FunctionCall {
+            span: None,
+            id: FunctionID::Builtin {
+                name: "no_such_function".to_string(),
+                id: 0,
+            },
+            generics: vec![],
+            args: vec![column(0, DataType::Number(NumberDataType::Int32))],
+            return_type: DataType::Number(NumberDataType::Int32),
+        },
+    ];
+    for (fragment_id, key) in bad_keys.into_iter().enumerate() {
+        // The preparation fails, instead of the first block sent by the fragment.
+        let packet = fragments_packet(&ctx, vec![shuffle_fragment(fragment_id, key)]);
+        let res = manager.init_query_fragments_plan(&ctx, &packet);
+        assert_eq!(res.unwrap_err().code(), ErrorCode::INTERNAL);
+    }
+
+    let key = column(0, DataType::Number(NumberDataType::Int32));
+    let packet = fragments_packet(&ctx, vec![shuffle_fragment(10, key)]);
+    manager.init_query_fragments_plan(&ctx, &packet)?;
+
+    manager.on_finished_query(&query_id);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_kill_query_fragments() -> Result<()> {
+    let _fixture = TestFixture::setup().await?;
+    let manager = DataExchangeManager::instance();
+
+    let killed_query = "killed_query".to_string();
+    let other_query = "other_query".to_string();
+    let killed_1 = manager.handle_exchange_fragment(stream_id(&killed_query, "node2", 1))?;
+    let killed_2 = manager.handle_exchange_fragment(stream_id(&killed_query, "node2", 2))?;
+    let other = manager.handle_exchange_fragment(stream_id(&other_query, "node2", 1))?;
+
+    manager.kill_query_fragments(&killed_query, ErrorCode::AbortedQuery("killed"));
+
+    for rx in [killed_1, killed_2] {
+        let status = rx.recv().await.unwrap().unwrap_err();
+        assert_eq!(ErrorCode::from(status).code(), ErrorCode::ABORTED_QUERY);
+        assert!(rx.recv().await.is_err());
+    }
+
+    // The exchanges of the other query are untouched.
+    assert!(other.try_recv().unwrap_err().is_empty());
+
+    // Killing a query which is already finished is a no-op.
+    manager.kill_query_fragments(&killed_query, ErrorCode::AbortedQuery("killed"));
+
+    manager.on_finished_query(&other_query);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fragment_stream_consumed_once() -> Result<()> {
+    let _fixture = TestFixture::setup().await?;
+    let manager = DataExchangeManager::instance();
+
+    let query_id = "consumed_once_query".to_string();
+    let _first = manager.handle_exchange_fragment(stream_id(&query_id, "node2", 1))?;
+
+    // The second consumer of the same stream is rejected.
+    let res = manager.handle_exchange_fragment(stream_id(&query_id, "node2", 1));
+    assert_eq!(res.unwrap_err().code(), ErrorCode::STREAM_ALREADY_CONSUMED);
+
+    let conf = StreamResumeConf {
+        retained_blocks: 8,
+        ..Default::default()
+    };
+    let res =
+        manager.handle_resumable_exchange_fragment(stream_id(&query_id, "node2", 1), &conf, None);
+    assert_eq!(res.unwrap_err().code(), ErrorCode::STREAM_ALREADY_CONSUMED);
+
+    // The streams of the other targets and fragments are independent.
+    manager.handle_exchange_fragment(stream_id(&query_id, "node3", 1))?;
+    manager.handle_exchange_fragment(stream_id(&query_id, "node2", 2))?;
+
+    manager.on_finished_query(&query_id);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fragment_stream_after_finished() -> Result<()> {
+    let _fixture = TestFixture::setup().await?;
+    let manager = DataExchangeManager::instance();
+
+    let query_id = "finished_stream_query".to_string();
+    let rx = manager.handle_exchange_fragment(stream_id(&query_id, "node2", 1))?;
+    manager.on_finished_query(&query_id);
+
+    // The stream is closed with the query.
+    assert!(rx.recv().await.is_err());
+
+    // Requesting the stream again fails instead of waiting forever.
+    let res = manager.handle_exchange_fragment(stream_id(&query_id, "node2", 1));
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::UNKNOWN_FRAGMENT_EXCHANGE
+    );
+
+    // The stages prepared again for the query have new fragments.
+    let rx = manager.handle_exchange_fragment(stream_id(&query_id, "node2", 2))?;
+    assert!(rx.try_recv().unwrap_err().is_empty());
+
+    manager.on_finished_query(&query_id);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_unknown_stream_lists_available_streams() -> Result<()> {
+    let _fixture = TestFixture::setup().await?;
+    let manager = DataExchangeManager::instance();
+
+    let query_id = "unknown_stream_query".to_string();
+    let conf = StreamResumeConf {
+        retained_blocks: 8,
+        ..Default::default()
+    };
+    let _node2 = manager.handle_resumable_exchange_fragment(
+        stream_id(&query_id, "node2", 1),
+        &conf,
+        None,
+    )?;
+    let _node3 = manager.handle_resumable_exchange_fragment(
+        stream_id(&query_id, "node3", 1),
+        &conf,
+        None,
+    )?;
+
+    // The error names the query and the streams which can be resumed.
+    let res = manager.handle_resumable_exchange_fragment(
+        stream_id(&query_id, "node2", 2),
+        &conf,
+        Some(0),
+    );
+    let err = res.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::UNKNOWN_FRAGMENT_EXCHANGE);
+    assert!(
+        err.message()
+            .contains("available streams: [node2/1, node3/1]"),
+        "{}",
+        err.message()
+    );
+    assert!(err.message().contains(&query_id), "{}", err.message());
+
+    manager.on_finished_query(&query_id);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_session_closed_aborts_its_queries() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let manager = DataExchangeManager::instance();
+
+    let mut queries = vec![];
+    for _ in 0..2 {
+        let session = fixture.new_session_with_type(SessionType::Dummy).await?;
+        let ctx = session.create_query_context().await?;
+        register_query(&manager, &ctx)?;
+        let packet = fragments_packet(&ctx, vec![constant_fragment(1)]);
+        manager.init_query_fragments_plan(&ctx, &packet)?;
+
+        let rx = manager.handle_exchange_fragment(stream_id(&ctx.get_id(), "node2", 2))?;
+        queries.push((session.get_id(), ctx.get_id(), rx));
+    }
+
+    let (closed_session, closed_query, closed_rx) = &queries[0];
+    SessionManager::instance().destroy_session(closed_session);
+
+    // The streams of the query are closed with an error.
+    let status = closed_rx.recv().await.unwrap().unwrap_err();
+    assert_eq!(ErrorCode::from(status).code(), ErrorCode::ABORTED_SESSION);
+    assert!(closed_rx.recv().await.is_err());
+    assert!(manager.get_query_ctx(closed_query).is_err());
+    let res = manager.handle_exchange_fragment(stream_id(closed_query, "node2", 2));
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::UNKNOWN_FRAGMENT_EXCHANGE
+    );
+
+    // The query of the other session is untouched.
+    let (_, other_query, other_rx) = &queries[1];
+    assert!(other_rx.try_recv().unwrap_err().is_empty());
+    assert!(manager.get_query_ctx(other_query).is_ok());
+
+    // Closing the session again is a no-op.
+    manager.on_session_closed(closed_session);
+
+    manager.on_finished_query(other_query);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shutdown_aborts_running_queries() -> Result<()> {
+    let _fixture = TestFixture::setup().await?;
+    let manager = DataExchangeManager::instance();
+
+    // The query is never finished, its stream waits for the data.
+    let query_id = "long_running_query".to_string();
+    let rx = manager.handle_exchange_fragment(stream_id(&query_id, "node2", 1))?;
+
+    let shutdown = {
+        let manager = manager.clone();
+        databend_common_base::runtime::spawn(async move {
+            manager.shutdown(Duration::from_millis(200)).await;
+        })
+    };
+
+    // The consumer gets an error instead of a reset connection.
+    let status = rx.recv().await.unwrap().unwrap_err();
+    assert_eq!(ErrorCode::from(status).code(), ErrorCode::ABORTED_SESSION);
+    assert!(rx.recv().await.is_err());
+    shutdown.await.unwrap();
+
+    // The new queries and streams are rejected.
+    let res = manager.handle_exchange_fragment(stream_id("new_query", "node2", 1));
+    assert_eq!(res.unwrap_err().code(), ErrorCode::SERVER_SHUTTING_DOWN);
+    let res = manager.handle_statistics_exchange("new_query".to_string(), EXECUTOR.to_string());
+    assert_eq!(res.unwrap_err().code(), ErrorCode::SERVER_SHUTTING_DOWN);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shutdown_waits_running_queries() -> Result<()> {
+    let _fixture = TestFixture::setup().await?;
+    let manager = DataExchangeManager::instance();
+
+    let query_id = "draining_query".to_string();
+    let rx = manager.handle_exchange_fragment(stream_id(&query_id, "node2", 1))?;
+
+    // The query finishes within the timeout.
+    let finish = {
+        let manager = manager.clone();
+        let query_id = query_id.clone();
+        databend_common_base::runtime::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            manager.on_finished_query(&query_id);
+        })
+    };
+    manager.shutdown(Duration::from_secs(60)).await;
+    finish.await.unwrap();
+
+    // The stream is closed by the query itself, without error.
+    assert!(rx.recv().await.is_err());
+    Ok(())
+}
+
+async fn physical_plan(ctx: &Arc<QueryContext>, sql: &str) -> Result<PhysicalPlan> {
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, _) = planner.plan_sql(sql).await?;
+    let Plan::Query {
+        s_expr,
+        metadata,
+        bind_context,
+        ..
+    } = plan
+    else {
+        unreachable!("{sql} is not a query")
+    };
+
+    SelectInterpreter::try_create(ctx.clone(), *bind_context, *s_expr, metadata, None, false)?
+        .build_physical_plan()
+        .await
+}
+
+// Run the plan of `sql` as a fragment, returns the number of result rows and scanned rows.
+async fn run_fragment(fixture: &TestFixture, sql: &str) -> Result<(usize, usize)> {
+    let ctx = fixture.new_query_ctx().await?;
+    let query_id = ctx.get_id();
+    let plan = physical_plan(&ctx, sql).await?;
+
+    let manager = DataExchangeManager::instance();
+    register_query(&manager, &ctx)?;
+    let packet = fragments_packet(&ctx, vec![FragmentPlanPacket::create(1, plan, None)]);
+    manager.init_query_fragments_plan(&ctx, &packet)?;
+    let build_res = manager.get_fragment_source(&query_id, 1, DefaultExchangeInjector::create())?;
+
+    let settings = ExecutorSettings::try_create(&ctx.get_settings(), query_id.clone())?;
+    let mut executor = PipelinePullingExecutor::from_pipelines(build_res, settings)?;
+    executor.start();
+    let mut num_rows = 0;
+    while let Some(block) = executor.pull_data()? {
+        num_rows += block.num_rows();
+    }
+
+    manager.on_finished_query(&query_id);
+    Ok((num_rows, ctx.get_scan_progress_value().rows))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fragment_result_cache() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    fixture.execute_command("CREATE TABLE t(a INT)").await?;
+    fixture
+        .execute_command("INSERT INTO t SELECT number FROM numbers(100)")
+        .await?;
+
+    let sql = "SELECT a FROM t WHERE a % 10 = 0";
+    assert_eq!(run_fragment(&fixture, sql).await?, (10, 100));
+    assert_eq!(run_fragment(&fixture, sql).await?, (10, 100));
+
+    fixture
+        .execute_command("SET enable_fragment_result_cache = 1")
+        .await?;
+    assert_eq!(run_fragment(&fixture, sql).await?, (10, 100));
+    // The identical re-run is served from the cache, nothing is scanned.
+    assert_eq!(run_fragment(&fixture, sql).await?, (10, 0));
+
+    // The new snapshot of the table changes the cache key.
+    fixture
+        .execute_command("INSERT INTO t VALUES (1000)")
+        .await?;
+    assert_eq!(run_fragment(&fixture, sql).await?, (11, 101));
+    assert_eq!(run_fragment(&fixture, sql).await?, (11, 0));
+
+    // Non-deterministic fragments are never cached.
+    let sql = "SELECT a, rand() FROM t";
+    assert_eq!(run_fragment(&fixture, sql).await?, (101, 101));
+    assert_eq!(run_fragment(&fixture, sql).await?, (101, 101));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fragment_result_cache_eviction() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    fixture.execute_command("CREATE TABLE t(a INT)").await?;
+    fixture
+        .execute_command("INSERT INTO t SELECT number FROM numbers(100)")
+        .await?;
+    fixture
+        .execute_command("SET enable_fragment_result_cache = 1")
+        .await?;
+
+    let lower = "SELECT a FROM t WHERE a < 50";
+    let upper = "SELECT a FROM t WHERE a >= 50";
+
+    // Expired results are not served.
+    fixture
+        .execute_command("SET fragment_result_cache_ttl_secs = 0")
+        .await?;
+    assert_eq!(run_fragment(&fixture, lower).await?, (50, 100));
+    assert_eq!(run_fragment(&fixture, lower).await?, (50, 100));
+    fixture
+        .execute_command("UNSET fragment_result_cache_ttl_secs")
+        .await?;
+
+    // Results larger than the cache are not cached.
+    fixture
+        .execute_command("SET fragment_result_cache_max_bytes = 1")
+        .await?;
+    assert_eq!(run_fragment(&fixture, lower).await?, (50, 100));
+    assert_eq!(run_fragment(&fixture, lower).await?, (50, 100));
+
+    // The cache holds only one of the results, the oldest one is evicted.
+    fixture
+        .execute_command("SET fragment_result_cache_max_bytes = 300")
+        .await?;
+    assert_eq!(run_fragment(&fixture, lower).await?, (50, 100));
+    assert_eq!(run_fragment(&fixture, lower).await?, (50, 0));
+    assert_eq!(run_fragment(&fixture, upper).await?, (50, 100));
+    assert_eq!(run_fragment(&fixture, upper).await?, (50, 0));
+    assert_eq!(run_fragment(&fixture, lower).await?, (50, 100));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collect_fragment_with_limit() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    fixture.execute_command("SET max_threads = 1").await?;
+    fixture.execute_command("SET max_block_size = 10").await?;
+
+    let ctx = fixture.new_query_ctx().await?;
+    let query_id = ctx.get_id();
+    let plan = physical_plan(&ctx, "SELECT number FROM numbers(1000)").await?;
+    let schema = plan.output_schema()?;
+
+    let manager = DataExchangeManager::instance();
+    let statistics = manager.handle_statistics_exchange(query_id.clone(), EXECUTOR.to_string())?;
+    let exchange = manager.handle_exchange_fragment(stream_id(&query_id, "node2", 1))?;
+
+    let data_exchange = MergeExchange::create("node2".to_string(), false, false);
+    let fragment = FragmentPlanPacket::create(1, plan, Some(data_exchange));
+    let packet = fragments_packet(&ctx, vec![fragment]);
+    manager.init_query_fragments_plan(&ctx, &packet)?;
+    manager.execute_partial_query(&query_id)?;
+
+    // Forward the fragment exchange to the receiver, as the flight client does.
+    let (tx, rx) = async_channel::bounded(1);
+    let forward_tx = tx.clone();
+    let forward = databend_common_base::runtime::spawn(async move {
+        while let Ok(data) = exchange.recv().await {
+            if tx.send(data.map_err(ErrorCode::from)).await.is_err() {
+                break;
+            }
+        }
+
+        exchange.close();
+    });
+
+    let receiver = FlightReceiver::create(rx);
+    let blocks = receiver.collect_with_limit(&schema, 15).await?;
+    let block = DataBlock::concat(&blocks)?;
+    assert_eq!(block.num_rows(), 15);
+    let expected = (0..15).collect::<Vec<u64>>();
+    let column = block.get_by_offset(0).value.as_column().unwrap();
+    assert_eq!(column, &UInt64Type::from_data(expected));
+
+    // The stream is cancelled once the limit is reached, the forwarding stops and closes the
+    // fragment exchange.
+    assert!(forward_tx.is_closed());
+    forward.await.unwrap();
+
+    // The closed stream cancels the fragment, it is finished without error.
+    while let Ok(data) = statistics.recv().await {
+        if let DataPacket::ErrorCode(cause) = DataPacket::try_from(data.unwrap())? {
+            return Err(cause);
+        }
+    }
+
+    Ok(())
+}
+
+type FlightStream = Receiver<std::result::Result<FlightData, Status>>;
+
+// Prepares a partial query sending its rows to node2, returns its data and statistics streams.
+async fn prepare_partial_query(
+    fixture: &TestFixture,
+) -> Result<(String, FlightStream, FlightStream)> {
+    let ctx = fixture.new_query_ctx().await?;
+    let query_id = ctx.get_id();
+    let plan = physical_plan(&ctx, "SELECT number FROM numbers(10)").await?;
+
+    let manager = DataExchangeManager::instance();
+    let statistics = manager.handle_statistics_exchange(query_id.clone(), EXECUTOR.to_string())?;
+    let exchange = manager.handle_exchange_fragment(stream_id(&query_id, "node2", 1))?;
+
+    let data_exchange = MergeExchange::create("node2".to_string(), false, false);
+    let fragment = FragmentPlanPacket::create(1, plan, Some(data_exchange));
+    manager.init_query_fragments_plan(&ctx, &fragments_packet(&ctx, vec![fragment]))?;
+    Ok((query_id, exchange, statistics))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_max_concurrent_stages() -> Result<()> {
+    let config = ConfigBuilder::create()
+        .max_concurrent_stages(1)
+        .max_queued_stages(1)
+        .config();
+    let fixture = TestFixture::setup_with_config(&config).await?;
+    let manager = DataExchangeManager::instance();
+
+    let (first, first_exchange, first_statistics) = prepare_partial_query(&fixture).await?;
+    let (second, second_exchange, _second_statistics) = prepare_partial_query(&fixture).await?;
+    let (third, _third_exchange, _third_statistics) = prepare_partial_query(&fixture).await?;
+
+    manager.execute_partial_query(&first)?;
+    manager.execute_partial_query(&second)?;
+    assert_eq!(manager.get_stages_status(), (1, 1));
+
+    // The queue is full.
+    let err = manager.execute_partial_query(&third).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::TOO_MANY_STAGES);
+    manager.on_finished_query(&third);
+    assert_eq!(manager.get_stages_status(), (1, 1));
+
+    // The second query is not started until the first one is finished.
+    let waiting = tokio::time::timeout(Duration::from_millis(200), second_exchange.recv()).await;
+    assert!(waiting.is_err());
+
+    while first_exchange.recv().await.is_ok() {}
+    while first_statistics.recv().await.is_ok() {}
+
+    let data = tokio::time::timeout(Duration::from_secs(10), second_exchange.recv()).await;
+    assert!(matches!(data, Ok(Ok(_))));
+    assert_eq!(manager.get_stages_status().1, 0);
+
+    manager.on_finished_query(&second);
+    assert_eq!(manager.get_stages_status(), (0, 0));
+
+    Ok(())
+}
+
+// Two nodes with one slot each, query x runs on this node and is queued on the other one,
+// query y runs on the other node and is queued on this one. Neither can finish, the queued
+// partial query of y is aborted so the whole query y fails and releases the other node.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cross_queued_stages() -> Result<()> {
+    let config = ConfigBuilder::create()
+        .max_concurrent_stages(1)
+        .max_queued_stages(1)
+        .max_stage_queued_secs(1)
+        .config();
+    let fixture = TestFixture::setup_with_config(&config).await?;
+    let manager = DataExchangeManager::instance();
+
+    // The streams of x are not consumed, as if the other node was waiting to start x.
+    let (x, x_exchange, x_statistics) = prepare_partial_query(&fixture).await?;
+    let (y, y_exchange, _y_statistics) = prepare_partial_query(&fixture).await?;
+
+    manager.execute_partial_query(&x)?;
+    manager.execute_partial_query(&y)?;
+    assert_eq!(manager.get_stages_status(), (1, 1));
+
+    let data = tokio::time::timeout(Duration::from_secs(10), y_exchange.recv()).await;
+    let Ok(Ok(Err(status))) = &data else {
+        panic!("expect the queued query to be aborted, got {:?}", data);
+    };
+    assert_eq!(ErrorCode::from(status.clone()).code(), ErrorCode::TOO_MANY_STAGES);
+    assert_eq!(manager.get_stages_status(), (1, 0));
+
+    // x still runs, it's finished once the other node starts it.
+    while x_exchange.recv().await.is_ok() {}
+    while x_statistics.recv().await.is_ok() {}
+    manager.on_finished_query(&x);
+    assert_eq!(manager.get_stages_status(), (0, 0));
+
+    Ok(())
+}
+
+// Waits for the summary of the only stage of the query, the executor records it after the
+// streams are closed.
+async fn finished_stage(manager: &DataExchangeManager, query_id: &str) -> FinishedStageInfo {
+    for _ in 0..100 {
+        let stages = manager
+            .get_finished_stages()
+            .into_iter()
+            .filter(|stage| stage.query_id == query_id)
+            .collect::<Vec<_>>();
+        if let [stage] = stages.as_slice() {
+            return stage.clone();
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    panic!("The stage of query {} is not finished", query_id);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_finished_stages() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let manager = DataExchangeManager::instance();
+
+    let mut finished = vec![];
+    for _ in 0..2 {
+        let (query_id, exchange, statistics) = prepare_partial_query(&fixture).await?;
+        manager.execute_partial_query(&query_id)?;
+        while exchange.recv().await.is_ok() {}
+        while statistics.recv().await.is_ok() {}
+        finished.push(query_id);
+    }
+
+    for query_id in &finished {
+        let stage = finished_stage(&manager, query_id).await;
+        assert_eq!(stage.stage_id, 1);
+        assert_eq!(stage.status, FinishedStageStatus::Finished);
+        assert_eq!(stage.streams.len(), 1);
+        assert_eq!(stage.streams[0].target, "node2");
+        assert_eq!(stage.streams[0].sent_rows, 10);
+        assert!(stage.streams[0].sent_bytes > 0);
+    }
+
+    // The killed stage is cancelled, the stage never started is not kept. The stream is not
+    // consumed, so the stage keeps waiting to send its blocks until it is killed.
+    fixture.execute_command("SET max_block_size = 1").await?;
+    let (killed, _exchange, _statistics) = prepare_partial_query(&fixture).await?;
+    let (queued, _exchange, _statistics) = prepare_partial_query(&fixture).await?;
+    manager.execute_partial_query(&killed)?;
+    manager.kill_query_fragments(&killed, ErrorCode::AbortedQuery("killed"));
+    manager.on_finished_query(&queued);
+
+    let stage = finished_stage(&manager, &killed).await;
+    assert_eq!(stage.status, FinishedStageStatus::Cancelled);
+    let stages = manager.get_finished_stages();
+    assert!(stages.iter().all(|stage| stage.query_id != queued));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_max_finished_stages() -> Result<()> {
+    let config = ConfigBuilder::create().max_finished_stages(2).config();
+    let fixture = TestFixture::setup_with_config(&config).await?;
+    let manager = DataExchangeManager::instance();
+
+    let mut finished = vec![];
+    for _ in 0..3 {
+        let (query_id, exchange, statistics) = prepare_partial_query(&fixture).await?;
+        manager.execute_partial_query(&query_id)?;
+        while exchange.recv().await.is_ok() {}
+        while statistics.recv().await.is_ok() {}
+        finished_stage(&manager, &query_id).await;
+        finished.push(query_id);
+    }
+
+    // The oldest stage is forgotten first.
+    let stages = manager
+        .get_finished_stages()
+        .into_iter()
+        .map(|stage| stage.query_id)
+        .collect::<Vec<_>>();
+    assert_eq!(stages, finished[1..]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_running_stages() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let manager = DataExchangeManager::instance();
+
+    // The stage is listed once prepared, and started when executed. The stream of the
+    // killed stage is not consumed, so it keeps waiting to send its blocks.
+    let (finished, exchange, statistics) = prepare_partial_query(&fixture).await?;
+    fixture.execute_command("SET max_block_size = 1").await?;
+    let (killed, _exchange, _statistics) = prepare_partial_query(&fixture).await?;
+    let stages = manager.get_running_stages();
+    let stage = stages
+        .iter()
+        .find(|stage| stage.query_id == finished)
+        .unwrap();
+    assert_eq!(stage.stage_id, 1);
+    assert!(stage.started_time.is_none());
+    assert_eq!(stage.streams, vec![RunningStreamInfo {
+        target: "node2".to_string(),
+        sent_rows: 0,
+        sent_bytes: 0,
+    }]);
+
+    manager.execute_partial_query(&killed)?;
+    let stages = manager.get_running_stages();
+    let stage = stages
+        .iter()
+        .find(|stage| stage.query_id == killed)
+        .unwrap();
+    assert!(stage.started_time.is_some());
+    assert_eq!(stage.streams.len(), 1);
+
+    manager.kill_query_fragments(&killed, ErrorCode::AbortedQuery("killed"));
+    let stages = manager.get_running_stages();
+    assert!(stages.iter().all(|stage| stage.query_id != killed));
+
+    manager.execute_partial_query(&finished)?;
+    while exchange.recv().await.is_ok() {}
+    while statistics.recv().await.is_ok() {}
+    finished_stage(&manager, &finished).await;
+    let stages = manager.get_running_stages();
+    assert!(stages.iter().all(|stage| stage.query_id != finished));
+    Ok(())
+}
+
+fn stream_id(query_id: &str, target: &str, fragment: usize) -> FragmentStreamId {
+    FragmentStreamId::create(query_id, target, fragment)
+}
+
+// Scatters the blocks without serializing them, so the blocks of the destinations can be checked.
+struct PlainExchangeInjector;
+
+impl ExchangeInjector for PlainExchangeInjector {
+    fn flight_scatter(
+        &self,
+        _: &Arc<QueryContext>,
+        _: &DataExchange,
+    ) -> Result<Arc<Box<dyn FlightScatter>>> {
+        unreachable!()
+    }
+
+    fn exchange_sorting(&self) -> Option<Arc<dyn ExchangeSorting>> {
+        None
+    }
+
+    fn apply_merge_serializer(
+        &self,
+        _: &MergeExchangeParams,
+        _: Option<FlightCompression>,
+        _: &mut Pipeline,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn apply_shuffle_serializer(
+        &self,
+        _: &ShuffleExchangeParams,
+        _: Option<FlightCompression>,
+        _: &mut Pipeline,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn apply_merge_deserializer(&self, _: &MergeExchangeParams, _: &mut Pipeline) -> Result<()> {
+        Ok(())
+    }
+
+    fn apply_shuffle_deserializer(
+        &self,
+        _: &ShuffleExchangeParams,
+        _: &mut Pipeline,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct CollectNumbers {
+    destination: usize,
+    numbers: Arc<Mutex<Vec<Vec<u64>>>>,
+}
+
+impl Sink for CollectNumbers {
+    const NAME: &'static str = "CollectNumbers";
+
+    fn consume(&mut self, block: DataBlock) -> Result<()> {
+        let column = block.get_by_offset(0).value.as_column().unwrap();
+        let column = UInt64Type::try_downcast_column(column).unwrap();
+        self.numbers.lock()[self.destination].extend(column.iter());
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shuffle_with_multiple_workers() -> Result<()> {
+    const NUM_ROWS: u64 = 100_000;
+    const WORKERS: usize = 8;
+    const DESTINATIONS: usize = 4;
+
+    let fixture = TestFixture::setup().await?;
+    let ctx = fixture.new_query_ctx().await?;
+    ctx.get_settings().set_max_threads(WORKERS as u64)?;
+    ctx.get_settings()
+        .set_setting("max_block_size".to_string(), "1000".to_string())?;
+
+    let plan = physical_plan(&ctx, &format!("SELECT number FROM numbers({NUM_ROWS})")).await?;
+    let builder = PipelineBuilder::create(
+        ctx.get_function_context()?,
+        ctx.get_settings(),
+        ctx.clone(),
+        vec![],
+    );
+    let mut build_res = builder.finalize(&plan)?;
+    assert!(build_res.sources_pipelines.is_empty());
+    build_res.set_max_threads(WORKERS);
+    let pipeline = &mut build_res.main_pipeline;
+    pipeline.try_resize(WORKERS)?;
+
+    let key = RemoteExpr::ColumnRef {
+        span: None,
+        id: 0,
+        data_type: DataType::Number(NumberDataType::UInt64),
+        display_name: "number".to_string(),
+    };
+    let scatter: Arc<Box<dyn FlightScatter>> = Arc::new(HashFlightScatter::try_create(
+        ctx.get_function_context()?,
+        vec![key],
+        DESTINATIONS,
+    )?);
+    let params = ShuffleExchangeParams {
+        query_id: ctx.get_id(),
+        executor_id: EXECUTOR.to_string(),
+        fragment_id: 1,
+        schema: plan.output_schema()?,
+        destination_ids: (0..DESTINATIONS).map(|i| format!("node{i}")).collect(),
+        shuffle_scatter: scatter.clone(),
+        exchange_injector: Arc::new(PlainExchangeInjector),
+    };
+    exchange_shuffle(&ctx, &params, pipeline)?;
+    assert_eq!(pipeline.output_len(), DESTINATIONS);
+
+    let numbers = Arc::new(Mutex::new(vec![vec![]; DESTINATIONS]));
+    let items = (0..DESTINATIONS)
+        .map(|destination| {
+            let input = InputPort::create();
+            let sink = Sinker::create(input.clone(), CollectNumbers {
+                destination,
+                numbers: numbers.clone(),
+            });
+            PipeItem::create(ProcessorPtr::create(sink), vec![input], vec![])
+        })
+        .collect::<Vec<_>>();
+    pipeline.add_pipe(Pipe::create(DESTINATIONS, 0, items));
+
+    let settings = ExecutorSettings::try_create(&ctx.get_settings(), ctx.get_id())?;
+    let executor = PipelineCompleteExecutor::try_create(build_res.main_pipeline, settings)?;
+    executor.execute()?;
+
+    // Every row is sent to exactly one destination, the one of the hash scatter.
+    let block = DataBlock::new_from_columns(vec![UInt64Type::from_data(
+        (0..NUM_ROWS).collect::<Vec<_>>(),
+    )]);
+    let expected = scatter.execute(block)?;
+    let mut numbers = numbers.lock();
+    assert_eq!(
+        numbers.iter().map(Vec::len).sum::<usize>(),
+        NUM_ROWS as usize
+    );
+    for (numbers, expected) in numbers.iter_mut().zip(expected) {
+        let column = expected.get_by_offset(0).value.as_column().unwrap();
+        let expected = UInt64Type::try_downcast_column(column).unwrap();
+        numbers.sort();
+        assert_eq!(*numbers, expected.to_vec());
+    }
+    Ok(())
+}
@@ -0,0 +1,93 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use databend_common_arrow::arrow::io::flight::default_ipc_fields;
+use databend_common_arrow::arrow::io::flight::WriteOptions;
+use databend_common_arrow::arrow::io::ipc::write::Compression;
+use databend_common_exception::Result;
+use databend_common_expression::block_debug::assert_block_value_eq;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::BlockMetaInfoDowncast;
+use databend_common_expression::DataBlock;
+use databend_common_expression::DataField;
+use databend_common_expression::DataSchemaRef;
+use databend_common_expression::DataSchemaRefExt;
+use databend_common_expression::FromData;
+use databend_query::api::serialize_block;
+use databend_query::api::DataPacket;
+use databend_query::api::ExchangeSerializeMeta;
+use databend_query::api::FragmentData;
+use databend_query::api::TransformExchangeDeserializer;
+
+fn test_schema() -> DataSchemaRef {
+    DataSchemaRefExt::create(vec![
+        DataField::new("id", DataType::Number(NumberDataType::UInt64)),
+        DataField::new("s", DataType::String),
+    ])
+}
+
+// A block of long repetitive strings.
+fn test_block() -> DataBlock {
+    let strings = (0..1000)
+        .map(|i| format!("{}-{}", "databend".repeat(32), i % 10))
+        .collect::<Vec<_>>();
+    DataBlock::new_from_columns(vec![
+        UInt64Type::from_data((0..1000).collect::<Vec<u64>>()),
+        StringType::from_data(strings.iter().map(|s| s.as_str()).collect::<Vec<_>>()),
+    ])
+}
+
+fn serialize(
+    block: &DataBlock,
+    schema: &DataSchemaRef,
+    compression: Option<Compression>,
+) -> Result<(Vec<DataPacket>, FragmentData)> {
+    let arrow_schema = ArrowSchema::from(schema.as_ref());
+    let ipc_fields = default_ipc_fields(&arrow_schema.fields);
+    let options = WriteOptions { compression };
+    let mut serialized = serialize_block(0, block.clone(), &ipc_fields, &options)?;
+
+    let meta = ExchangeSerializeMeta::downcast_from(serialized.take_meta().unwrap()).unwrap();
+    let mut packets = meta.packet;
+    match packets.pop() {
+        Some(DataPacket::FragmentData(fragment)) => Ok((packets, fragment)),
+        _ => unreachable!("the last packet is the fragment data"),
+    }
+}
+
+#[test]
+fn test_exchange_serde_compression_round_trip() -> Result<()> {
+    let schema = test_schema();
+    let block = test_block();
+    // The compression is carried by each IPC message, so one deserializer reads the data
+    // of the senders with any compression.
+    let deserializer = TransformExchangeDeserializer::new(&schema);
+
+    let mut body_sizes = vec![];
+    for compression in [None, Some(Compression::LZ4), Some(Compression::ZSTD)] {
+        let (dict, fragment) = serialize(&block, &schema, compression)?;
+        body_sizes.push(fragment.data.data_body.len());
+
+        let decoded = deserializer.recv_data(dict, fragment)?;
+        assert_block_value_eq(&decoded, &block);
+    }
+
+    assert!(body_sizes[1] < body_sizes[0], "{:?}", body_sizes);
+    assert!(body_sizes[2] < body_sizes[0], "{:?}", body_sizes);
+    Ok(())
+}
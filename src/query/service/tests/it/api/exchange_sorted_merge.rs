@@ -0,0 +1,120 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use databend_common_arrow::arrow::io::flight::default_ipc_fields;
+use databend_common_arrow::arrow::io::flight::WriteOptions;
+use databend_common_arrow::arrow_format::flight::data::FlightData;
+use databend_common_base::base::tokio;
+use databend_common_exception::Result;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::types::ValueType;
+use databend_common_expression::BlockMetaInfoDowncast;
+use databend_common_expression::DataBlock;
+use databend_common_expression::DataField;
+use databend_common_expression::DataSchemaRef;
+use databend_common_expression::DataSchemaRefExt;
+use databend_common_expression::FromData;
+use databend_query::api::serialize_block;
+use databend_query::api::ExchangeSerializeMeta;
+use databend_query::api::ExchangeSortDesc;
+use databend_query::api::SortedExchangeMerge;
+use databend_query::api::TransformSortBounds;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+
+fn test_schema() -> DataSchemaRef {
+    DataSchemaRefExt::create(vec![DataField::new(
+        "number",
+        DataType::Number(NumberDataType::UInt64),
+    )])
+}
+
+fn sort_desc() -> Vec<ExchangeSortDesc> {
+    vec![ExchangeSortDesc {
+        offset: 0,
+        asc: true,
+        nulls_first: false,
+    }]
+}
+
+// The stream of a sender of the sorted numbers, in blocks of 10 rows.
+fn sorted_stream(
+    numbers: Vec<u64>,
+    schema: &DataSchemaRef,
+) -> Result<BoxStream<'static, Result<FlightData>>> {
+    let arrow_schema = ArrowSchema::from(schema.as_ref());
+    let ipc_fields = default_ipc_fields(&arrow_schema.fields);
+    let options = WriteOptions { compression: None };
+
+    let mut flight_data = vec![];
+    for chunk in numbers.chunks(10) {
+        let block = DataBlock::new_from_columns(vec![UInt64Type::from_data(chunk.to_vec())]);
+        let block = TransformSortBounds::attach_bounds(block, &sort_desc())?;
+        let mut serialized = serialize_block(0, block, &ipc_fields, &options)?;
+        let meta = ExchangeSerializeMeta::downcast_from(serialized.take_meta().unwrap()).unwrap();
+        for packet in meta.packet {
+            flight_data.push(FlightData::try_from(packet));
+        }
+    }
+
+    Ok(futures::stream::iter(flight_data).boxed())
+}
+
+// Three senders of the numbers below 300, the sender `i` has the numbers `n % 3 == i`.
+fn create_merge(limit: Option<usize>) -> Result<SortedExchangeMerge> {
+    let schema = test_schema();
+    let streams = (0..3)
+        .map(|i| sorted_stream((0..300).filter(|n| n % 3 == i).collect(), &schema))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(SortedExchangeMerge::create(
+        &schema,
+        sort_desc(),
+        streams,
+        limit,
+    ))
+}
+
+async fn collect_numbers(merge: &mut SortedExchangeMerge) -> Result<Vec<u64>> {
+    let mut numbers = vec![];
+    while let Some(block) = merge.next_block().await? {
+        assert!(block.get_meta().is_none());
+        let column = block.get_by_offset(0).value.as_column().unwrap();
+        let column = UInt64Type::try_downcast_column(column).unwrap();
+        numbers.extend(column.iter().copied());
+    }
+    Ok(numbers)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sorted_exchange_merge() -> Result<()> {
+    let mut merge = create_merge(None)?;
+    let numbers = collect_numbers(&mut merge).await?;
+    assert_eq!(numbers, (0..300).collect::<Vec<u64>>());
+    assert_eq!(merge.decoded_blocks(), 30);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sorted_exchange_merge_with_limit() -> Result<()> {
+    // The first 25 numbers are in the first block of each sender, the other 27 blocks are
+    // received but never decoded.
+    let mut merge = create_merge(Some(25))?;
+    let numbers = collect_numbers(&mut merge).await?;
+    assert_eq!(numbers, (0..25).collect::<Vec<u64>>());
+    assert_eq!(merge.decoded_blocks(), 3);
+    Ok(())
+}
@@ -0,0 +1,536 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+use std::time::Duration;
+use std::time::Instant;
+
+use databend_common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use databend_common_arrow::arrow::io::flight::default_ipc_fields;
+use databend_common_arrow::arrow::io::flight::WriteOptions;
+use databend_common_arrow::arrow_format::flight::data::FlightData;
+use databend_common_base::base::tokio;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::BlockMetaInfoDowncast;
+use databend_common_expression::DataBlock;
+use databend_common_expression::DataField;
+use databend_common_expression::DataSchemaRef;
+use databend_common_expression::DataSchemaRefExt;
+use databend_common_expression::FromData;
+use databend_common_metrics::dump_metric_samples;
+use databend_common_metrics::load_global_prometheus_registry;
+use databend_common_metrics::transform::ExchangeStreamMetrics;
+use databend_common_metrics::MetricValue;
+use databend_query::api::query_spill_dir;
+use databend_query::api::remove_stale_spill_dirs;
+use databend_query::api::serialize_block;
+use databend_query::api::DataPacket;
+use databend_query::api::ExchangeSerializeMeta;
+use databend_query::api::FlightReceiver;
+use databend_query::api::FlightSender;
+use databend_query::api::OrderedFlightReceiver;
+use databend_query::api::PolledStream;
+use databend_query::api::StreamLagLimits;
+use databend_query::api::StreamLiveness;
+use databend_query::api::StreamSpillConf;
+use futures::StreamExt;
+
+fn packet() -> DataPacket {
+    DataPacket::SerializeProgress(vec![])
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_lag_blocks_limit() -> Result<()> {
+    let (tx, rx) = async_channel::bounded(8);
+    let sender = FlightSender::create(tx).with_lag_limits(StreamLagLimits {
+        max_blocks: 3,
+        max_duration: Duration::ZERO,
+    });
+
+    for _ in 0..3 {
+        sender.send(packet()).await?;
+    }
+
+    let error = sender.send(packet()).await.unwrap_err();
+    assert_eq!(error.code(), ErrorCode::EXCHANGE_CONSUMER_TOO_SLOW);
+
+    // The consumer catches up.
+    rx.recv().await.unwrap().unwrap();
+    sender.send(packet()).await?;
+    assert_eq!(rx.len(), 3);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_lag_duration_limit() -> Result<()> {
+    let (tx, rx) = async_channel::bounded(1);
+    let sender = FlightSender::create(tx).with_lag_limits(StreamLagLimits {
+        max_blocks: 0,
+        max_duration: Duration::from_millis(200),
+    });
+
+    // A consumer within the limit.
+    sender.send(packet()).await?;
+    let consumer = databend_common_base::runtime::spawn({
+        let rx = rx.clone();
+        async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            rx.recv().await.unwrap().unwrap();
+        }
+    });
+    sender.send(packet()).await?;
+    consumer.await.unwrap();
+
+    // The consumer never takes the block, the sending fails instead of waiting forever.
+    let error = sender.send(packet()).await.unwrap_err();
+    assert_eq!(error.code(), ErrorCode::EXCHANGE_CONSUMER_TOO_SLOW);
+    assert_eq!(rx.len(), 1);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_lag_unlimited() -> Result<()> {
+    let (tx, rx) = async_channel::bounded(1);
+    let sender = FlightSender::create(tx);
+
+    sender.send(packet()).await?;
+    let send = tokio::time::timeout(Duration::from_millis(100), sender.send(packet())).await;
+    assert!(send.is_err());
+    assert_eq!(rx.len(), 1);
+    Ok(())
+}
+
+// The values of the exchange stream metrics of the query, by the metric name.
+fn stream_metrics(query_id: &str) -> Vec<(String, MetricValue)> {
+    let registry = load_global_prometheus_registry();
+    let mut samples = dump_metric_samples(registry.inner())
+        .unwrap()
+        .into_iter()
+        .filter(|s| s.name.starts_with("transform_exchange_stream_"))
+        .filter(|s| s.labels.get("query_id").map(String::as_str) == Some(query_id))
+        .map(|s| (s.name, s.value))
+        .collect::<Vec<_>>();
+    samples.sort_by(|a, b| a.0.cmp(&b.0));
+    samples
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_stream_metrics() -> Result<()> {
+    let block = DataBlock::new_from_columns(vec![UInt64Type::from_data(vec![1u64, 2, 3, 4, 5])]);
+    let schema = DataSchemaRefExt::create(vec![DataField::new(
+        "a",
+        DataType::Number(NumberDataType::UInt64),
+    )]);
+    let arrow_schema = ArrowSchema::from(schema.as_ref());
+    let ipc_fields = default_ipc_fields(&arrow_schema.fields);
+    let mut serialized =
+        serialize_block(0, block, &ipc_fields, &WriteOptions { compression: None })?;
+    let meta = ExchangeSerializeMeta::downcast_from(serialized.take_meta().unwrap()).unwrap();
+
+    let (tx, rx) = async_channel::bounded(8);
+    let metrics = ExchangeStreamMetrics::create("test_stream_metrics", 1, "node2");
+    let sender = FlightSender::create(tx).with_metrics(metrics);
+    let mut bytes = 0;
+    for packet in meta.packet {
+        bytes += packet.bytes_size();
+        sender.send(packet).await?;
+    }
+
+    let metrics = stream_metrics("test_stream_metrics");
+    let names = metrics
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec![
+        "transform_exchange_stream_last_send_seconds",
+        "transform_exchange_stream_queued_blocks",
+        "transform_exchange_stream_sent_bytes_total",
+        "transform_exchange_stream_sent_rows_total",
+    ]);
+    assert_eq!(metrics[1].1, MetricValue::Gauge(rx.len() as f64));
+    assert_eq!(metrics[2].1, MetricValue::Untyped(bytes as f64));
+    assert_eq!(metrics[3].1, MetricValue::Untyped(5.0));
+
+    // The metrics of the stream are removed once the stream is done.
+    drop(sender);
+    assert!(stream_metrics("test_stream_metrics").is_empty());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_lag_error_through_exchange() -> Result<()> {
+    let (tx, rx) = async_channel::bounded(8);
+    let sender = FlightSender::create(tx).with_lag_limits(StreamLagLimits {
+        max_blocks: 1,
+        max_duration: Duration::ZERO,
+    });
+
+    sender.send(packet()).await?;
+    let error = sender.send(packet()).await.unwrap_err();
+
+    // The failed node reports the error to the coordinator by an error packet.
+    let flight_data = FlightData::try_from(DataPacket::ErrorCode(error.clone()))?;
+    let received = match DataPacket::try_from(flight_data)? {
+        DataPacket::ErrorCode(error) => error,
+        _ => unreachable!(),
+    };
+
+    assert_eq!(received.code(), ErrorCode::EXCHANGE_CONSUMER_TOO_SLOW);
+    assert_eq!(received.message(), error.message());
+    assert!(received.message().contains("Exchange consumer too slow"));
+    assert!(received.message().contains("max_stream_lag_blocks 1"));
+    drop(rx);
+    Ok(())
+}
+
+fn dictionary(value: u8) -> DataPacket {
+    DataPacket::Dictionary(FlightData {
+        flight_descriptor: None,
+        data_header: vec![],
+        data_body: vec![value],
+        app_metadata: vec![],
+    })
+}
+
+// Sends the packets with the sequence numbers, and returns the ordered receiver of them.
+async fn ordered_stream(
+    packets: Vec<(Option<u64>, DataPacket)>,
+    max_buffered: usize,
+) -> Result<OrderedFlightReceiver> {
+    let (tx, rx) = async_channel::bounded(packets.len());
+    let sender = FlightSender::create(tx);
+    for (sequence, packet) in packets {
+        match sequence {
+            None => sender.send(packet).await?,
+            Some(sequence) => sender.send_sequenced(sequence, packet).await?,
+        }
+    }
+    drop(sender);
+
+    let (received_tx, received_rx) = async_channel::bounded(rx.len());
+    while let Ok(data) = rx.recv().await {
+        received_tx
+            .send(data.map_err(ErrorCode::from))
+            .await
+            .unwrap();
+    }
+    Ok(FlightReceiver::create(received_rx).into_ordered(max_buffered))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ordered_receiver() -> Result<()> {
+    let sequences = [3u64, 0, 1, 7, 2, 5, 4, 9, 6, 8];
+    let mut packets = sequences
+        .iter()
+        .map(|sequence| (Some(*sequence), dictionary(*sequence as u8)))
+        .collect::<Vec<_>>();
+    // The packets without a sequence number are not reordered.
+    packets.insert(1, (None, dictionary(100)));
+
+    let mut receiver = ordered_stream(packets, 8).await?;
+    let mut received = vec![];
+    while let Some(packet) = receiver.recv().await? {
+        match packet {
+            DataPacket::Dictionary(data) => received.push(data.data_body[0]),
+            _ => unreachable!(),
+        }
+    }
+
+    assert_eq!(received, vec![100, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ordered_receiver_errors() -> Result<()> {
+    // Packet 0 never arrives, the buffer overflows.
+    let packets = (1..4).map(|i| (Some(i), dictionary(i as u8))).collect();
+    let mut receiver = ordered_stream(packets, 2).await?;
+    let error = receiver.recv().await.unwrap_err();
+    assert_eq!(error.code(), ErrorCode::INTERNAL);
+    assert!(error.message().contains("packet 0 is missing"));
+
+    // The stream is finished with a gap.
+    let packets = vec![(Some(0), dictionary(0)), (Some(2), dictionary(2))];
+    let mut receiver = ordered_stream(packets, 2).await?;
+    assert!(matches!(
+        receiver.recv().await?,
+        Some(DataPacket::Dictionary(_))
+    ));
+    let error = receiver.recv().await.unwrap_err();
+    assert_eq!(error.code(), ErrorCode::INTERNAL);
+    assert!(error.message().contains("without packet 1"));
+
+    // The same packet is sent twice.
+    let packets = vec![(Some(1), dictionary(1)), (Some(1), dictionary(1))];
+    let mut receiver = ordered_stream(packets, 2).await?;
+    let error = receiver.recv().await.unwrap_err();
+    assert!(error.message().contains("Duplicated packet 1"));
+    Ok(())
+}
+
+fn spill_files(dir: &Path) -> usize {
+    std::fs::read_dir(dir).map_or(0, |entries| entries.count())
+}
+
+// The packets of the blocks in a fragment stream.
+fn block_packets(schema: &DataSchemaRef, blocks: &[DataBlock]) -> Result<Vec<DataPacket>> {
+    let arrow_schema = ArrowSchema::from(schema.as_ref());
+    let ipc_fields = default_ipc_fields(&arrow_schema.fields);
+    let options = WriteOptions { compression: None };
+
+    let mut packets = vec![];
+    for block in blocks {
+        let mut serialized = serialize_block(0, block.clone(), &ipc_fields, &options)?;
+        let meta = ExchangeSerializeMeta::downcast_from(serialized.take_meta().unwrap()).unwrap();
+        packets.extend(meta.packet);
+    }
+    Ok(packets)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_spill_stream() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let spill_dir = dir.path().join("spill");
+    let schema = DataSchemaRefExt::create(vec![DataField::new(
+        "a",
+        DataType::Number(NumberDataType::UInt64),
+    )]);
+    let blocks = (0..20u64)
+        .map(|i| DataBlock::new_from_columns(vec![UInt64Type::from_data(vec![i, i + 100])]))
+        .collect::<Vec<_>>();
+
+    let (tx, rx) = async_channel::bounded(2);
+    let sender = FlightSender::create(tx).with_spill(StreamSpillConf {
+        max_memory_bytes: 1,
+        spill_dir: spill_dir.clone(),
+    });
+
+    // The consumer is paused, the sender spills instead of waiting for it.
+    for packet in block_packets(&schema, &blocks)? {
+        tokio::time::timeout(Duration::from_secs(5), sender.send(packet))
+            .await
+            .unwrap()?;
+    }
+    assert!(spill_files(&spill_dir) >= blocks.len() - 3);
+    sender.close();
+
+    // The consumer catches up, and receives the blocks in the send order.
+    let (received_tx, received_rx) = async_channel::bounded(1);
+    databend_common_base::runtime::spawn(async move {
+        while let Ok(data) = rx.recv().await {
+            if received_tx
+                .send(data.map_err(ErrorCode::from))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+    let received = FlightReceiver::create(received_rx)
+        .collect_with_limit(&schema, usize::MAX)
+        .await?;
+
+    assert_eq!(received.len(), blocks.len());
+    for (received, block) in received.iter().zip(blocks.iter()) {
+        assert_eq!(
+            received.get_by_offset(0).value,
+            block.get_by_offset(0).value
+        );
+    }
+    assert_eq!(spill_files(&spill_dir), 0);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_spill_stream_consumer_closed() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let (tx, rx) = async_channel::bounded(1);
+    let sender = FlightSender::create(tx).with_spill(StreamSpillConf {
+        max_memory_bytes: 1,
+        spill_dir: dir.path().to_path_buf(),
+    });
+
+    for _ in 0..10 {
+        sender.send(packet()).await?;
+    }
+    assert!(spill_files(dir.path()) > 0);
+
+    // The spilled packets are removed once the consumer is gone.
+    drop(rx);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while spill_files(dir.path()) != 0 {
+        assert!(Instant::now() < deadline);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let error = sender.send(packet()).await.unwrap_err();
+    assert_eq!(error.code(), ErrorCode::ABORTED_QUERY);
+    assert_eq!(spill_files(dir.path()), 0);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_spill_stream_io_error() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let file = dir.path().join("file");
+    std::fs::write(&file, b"")?;
+
+    // The spill directory cannot be created under a file.
+    let (tx, rx) = async_channel::bounded(2);
+    let sender = FlightSender::create(tx).with_spill(StreamSpillConf {
+        max_memory_bytes: 1,
+        spill_dir: file.join("spill"),
+    });
+
+    sender.send(packet()).await?;
+    let error = sender.send(packet()).await.unwrap_err();
+    assert_eq!(error.code(), ErrorCode::EXCHANGE_SPILL_FAILED);
+    assert!(error.message().contains("Cannot spill the exchange stream"));
+    assert_eq!(rx.len(), 1);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_spill_stream_empty_channel() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let (tx, rx) = async_channel::bounded(1);
+    let sender = FlightSender::create(tx).with_spill(StreamSpillConf {
+        max_memory_bytes: 1,
+        spill_dir: dir.path().to_path_buf(),
+    });
+
+    // The packet is bigger than the threshold, but the consumer waits on the empty channel.
+    sender.send(packet()).await?;
+    assert_eq!(rx.len(), 1);
+    assert_eq!(spill_files(dir.path()), 0);
+
+    sender.send(packet()).await?;
+    assert_eq!(spill_files(dir.path()), 1);
+
+    rx.recv().await.unwrap()?;
+    rx.recv().await.unwrap()?;
+    Ok(())
+}
+
+#[test]
+fn test_remove_stale_spill_dirs() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let query_dir = query_spill_dir(dir.path(), "query-1");
+    std::fs::create_dir_all(&query_dir)?;
+    std::fs::write(query_dir.join("packet.exchange"), b"")?;
+    std::fs::write(dir.path().join("file"), b"")?;
+
+    // Only the directories of the queries are removed.
+    remove_stale_spill_dirs(dir.path());
+    assert!(!query_dir.exists());
+    assert!(dir.path().join("file").exists());
+
+    // The query id set by the client cannot escape the root.
+    assert_eq!(
+        query_spill_dir(dir.path(), "../x/y"),
+        dir.path().join("___x_y")
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_idle_timeout_consumer_stopped() -> Result<()> {
+    let idle_timeout = Duration::from_millis(300);
+
+    // The consumer of the first stream stops polling after the first packet.
+    let (stopped_tx, stopped_rx) = async_channel::bounded(2);
+    let stopped_liveness = StreamLiveness::create();
+    let mut stopped_stream = PolledStream::create(stopped_rx, stopped_liveness.clone());
+    let stopped_sender = FlightSender::create(stopped_tx)
+        .with_liveness(stopped_liveness)
+        .with_idle_timeout(idle_timeout);
+
+    // The consumer of the second stream of the same stage keeps polling.
+    let (polled_tx, polled_rx) = async_channel::bounded(2);
+    let polled_liveness = StreamLiveness::create();
+    let polled_stream = PolledStream::create(polled_rx, polled_liveness.clone());
+    let polled_sender = FlightSender::create(polled_tx)
+        .with_liveness(polled_liveness)
+        .with_idle_timeout(idle_timeout);
+
+    let consumer = databend_common_base::runtime::spawn(async move {
+        polled_stream
+            .then(|data| async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                data
+            })
+            .collect::<Vec<_>>()
+            .await
+    });
+    let producer = databend_common_base::runtime::spawn(async move {
+        for _ in 0..20 {
+            polled_sender.send(packet()).await?;
+        }
+        polled_sender.close();
+        Ok::<_, ErrorCode>(())
+    });
+
+    stopped_sender.send(packet()).await?;
+    assert!(stopped_stream.next().await.unwrap().is_ok());
+
+    // The sender doesn't block forever on the full channel.
+    let error = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Err(error) = stopped_sender.send(packet()).await {
+                return error;
+            }
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(error.code(), ErrorCode::EXCHANGE_CONSUMER_TIMEOUT);
+
+    // The consumer receives the error instead of the remaining packets, if it comes back.
+    let status = stopped_stream.next().await.unwrap().unwrap_err();
+    assert_eq!(
+        ErrorCode::from(status).code(),
+        ErrorCode::EXCHANGE_CONSUMER_TIMEOUT
+    );
+    assert!(stopped_stream.next().await.is_none());
+
+    // The other stream completes, its consumer takes longer than the timeout in total but
+    // keeps polling.
+    producer.await.unwrap()?;
+    let received = consumer.await.unwrap();
+    assert_eq!(received.len(), 20);
+    assert!(received.iter().all(|data| data.is_ok()));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_idle_timeout_disabled() -> Result<()> {
+    let (tx, rx) = async_channel::bounded(1);
+    let liveness = StreamLiveness::create();
+    let mut stream = PolledStream::create(rx, liveness.clone());
+    let sender = FlightSender::create(tx)
+        .with_liveness(liveness)
+        .with_idle_timeout(Duration::ZERO);
+
+    sender.send(packet()).await?;
+    let blocked = tokio::time::timeout(Duration::from_millis(300), sender.send(packet())).await;
+    assert!(blocked.is_err());
+
+    assert!(stream.next().await.unwrap().is_ok());
+    Ok(())
+}
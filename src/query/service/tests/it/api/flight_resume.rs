@@ -0,0 +1,168 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_channel::Receiver;
+use databend_common_arrow::arrow_format::flight::data::FlightData;
+use databend_common_base::base::tokio;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_query::api::resumable_receiver;
+use databend_query::api::ResumableStream;
+use parking_lot::Mutex;
+use tonic::Status;
+
+fn packet(no: u8) -> FlightData {
+    FlightData {
+        data_body: vec![no],
+        ..Default::default()
+    }
+}
+
+fn packet_no(data: &FlightData) -> u8 {
+    data.data_body[0]
+}
+
+async fn collect<E>(rx: Receiver<std::result::Result<FlightData, E>>) -> Vec<u8>
+where E: std::fmt::Debug {
+    let mut packets = vec![];
+    while let Ok(data) = rx.recv().await {
+        packets.push(packet_no(&data.unwrap()));
+    }
+    packets
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resume_lost_consumer() -> Result<()> {
+    let (stream, tx, rx) = ResumableStream::create(4, Duration::from_secs(10));
+    for no in 0..3 {
+        tx.send(Ok(packet(no))).await.unwrap();
+    }
+
+    // The connection is lost after the consumer received two packets.
+    assert_eq!(packet_no(&rx.recv().await.unwrap().unwrap()), 0);
+    assert_eq!(packet_no(&rx.recv().await.unwrap().unwrap()), 1);
+    drop(rx);
+
+    let producer = databend_common_base::runtime::spawn(async move {
+        for no in 3..5 {
+            tx.send(Ok(packet(no))).await.unwrap();
+        }
+    });
+
+    // The consumer gets the remaining packets without duplicates.
+    let rx = stream.resume(2)?;
+    producer.await.unwrap();
+    assert_eq!(collect(rx).await, vec![2, 3, 4]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resume_finished_stream() -> Result<()> {
+    let (stream, tx, rx) = ResumableStream::create(4, Duration::from_secs(10));
+    for no in 0..6 {
+        tx.send(Ok(packet(no))).await.unwrap();
+    }
+    drop(tx);
+    assert_eq!(collect(rx).await, vec![0, 1, 2, 3, 4, 5]);
+
+    assert_eq!(collect(stream.resume(3)?).await, vec![3, 4, 5]);
+    assert_eq!(collect(stream.resume(6)?).await, Vec::<u8>::new());
+
+    // Only the latest 4 packets are retained.
+    let error = stream.resume(1).unwrap_err();
+    assert_eq!(error.code(), ErrorCode::UNKNOWN_FRAGMENT_EXCHANGE);
+    let error = stream.resume(7).unwrap_err();
+    assert_eq!(error.code(), ErrorCode::UNKNOWN_FRAGMENT_EXCHANGE);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resume_timeout() -> Result<()> {
+    let (_stream, tx, rx) = ResumableStream::create(4, Duration::from_millis(100));
+    drop(rx);
+
+    // The consumer does not come back, the stream is closed like a closed consumer.
+    let _ = tx.send(Ok(packet(0))).await;
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while !tx.is_closed() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    assert!(tx.send(Ok(packet(1))).await.is_err());
+    Ok(())
+}
+
+type FakeStream =
+    futures::stream::Iter<std::vec::IntoIter<std::result::Result<FlightData, Status>>>;
+
+fn fake_stream(packets: impl Iterator<Item = u8>, error: Option<Status>) -> FakeStream {
+    let mut messages = packets.map(|no| Ok(packet(no))).collect::<Vec<_>>();
+    messages.extend(error.map(Err));
+    futures::stream::iter(messages)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resumable_receiver_reconnect() -> Result<()> {
+    let resumed = Arc::new(Mutex::new(vec![]));
+    let connect = {
+        let resumed = resumed.clone();
+        move |resume_from: u64| {
+            resumed.lock().push(resume_from);
+            let error = (resume_from < 4).then(|| Status::unavailable("connection reset"));
+            async move { Ok(fake_stream(resume_from as u8..resume_from as u8 + 2, error)) }
+        }
+    };
+
+    let streaming = fake_stream(0..2, Some(Status::unavailable("connection reset")));
+    let (_notify, rx) = resumable_receiver(streaming, connect, 3);
+    assert_eq!(collect(rx).await, vec![0, 1, 2, 3, 4, 5]);
+    assert_eq!(*resumed.lock(), vec![2, 4]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resumable_receiver_errors() -> Result<()> {
+    let resumed = Arc::new(Mutex::new(vec![]));
+    let connect = {
+        let resumed = resumed.clone();
+        move |resume_from: u64| {
+            resumed.lock().push(resume_from);
+            async move { Ok(fake_stream(0..0, Some(Status::unavailable("unavailable")))) }
+        }
+    };
+
+    // The errors of the query are not retried.
+    let query_error = Status::from(ErrorCode::AbortedQuery("killed"));
+    let streaming = fake_stream(0..1, Some(query_error));
+    let (_notify, rx) = resumable_receiver(streaming, connect.clone(), 3);
+    assert_eq!(packet_no(&rx.recv().await.unwrap()?), 0);
+    let error = rx.recv().await.unwrap().unwrap_err();
+    assert_eq!(error.code(), ErrorCode::ABORTED_QUERY);
+    assert!(rx.recv().await.is_err());
+    assert!(resumed.lock().is_empty());
+
+    // Gives up after the max retries.
+    let streaming = fake_stream(0..1, Some(Status::unavailable("unavailable")));
+    let (_notify, rx) = resumable_receiver(streaming, connect, 3);
+    assert_eq!(packet_no(&rx.recv().await.unwrap()?), 0);
+    assert!(rx.recv().await.unwrap().is_err());
+    assert!(rx.recv().await.is_err());
+    assert_eq!(*resumed.lock(), vec![1, 1, 1]);
+    Ok(())
+}
@@ -0,0 +1,299 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use databend_common_exception::Result;
+use databend_common_expression::block_debug::assert_two_blocks_sorted_eq_with_name;
+use databend_common_expression::group_hash_columns;
+use databend_common_expression::types::number::NumberScalar;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::types::ValueType;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FunctionContext;
+use databend_common_expression::RemoteExpr;
+use databend_common_expression::Scalar;
+use databend_common_expression::Value;
+use databend_query::api::FlightScatter;
+use databend_query::api::HashFlightScatter;
+use databend_query::api::RangeFlightScatter;
+use databend_query::api::RoundRobinFlightScatter;
+use databend_query::test_kits::BlockFixture;
+
+const NUM_ROWS: usize = 1000;
+
+fn column_ref(id: usize, data_type: DataType) -> RemoteExpr {
+    RemoteExpr::ColumnRef {
+        span: None,
+        id,
+        data_type,
+        display_name: format!("c{id}"),
+    }
+}
+
+// c0: the row number, c1 and c2: the keys, c3: a constant.
+fn test_block() -> DataBlock {
+    let string_keys = (0..NUM_ROWS)
+        .map(|i| format!("key_{}", i % 5))
+        .collect::<Vec<_>>();
+
+    BlockFixture::new()
+        .col_u64("c0", 0..NUM_ROWS as u64)
+        .col_i64("c1", (0..NUM_ROWS as i64).map(|i| i % 7))
+        .col_str_opt(
+            "c2",
+            string_keys
+                .iter()
+                .enumerate()
+                .map(|(i, key)| (i % 3 != 0).then_some(key.as_str())),
+        )
+        .constant(
+            "c3",
+            DataType::Number(NumberDataType::Int64),
+            Scalar::Number(NumberScalar::Int64(42)),
+        )
+        .build()
+}
+
+fn row_numbers(block: &DataBlock) -> Vec<u64> {
+    let column = block.get_by_offset(0).value.as_column().unwrap();
+    UInt64Type::try_downcast_column(column).unwrap().to_vec()
+}
+
+fn check_scatter(
+    scatter: &dyn FlightScatter,
+    key_columns: &[usize],
+    scatter_size: usize,
+) -> Result<()> {
+    let block = test_block();
+    let scattered = scatter.execute(block.clone())?;
+    assert_eq!(scattered.len(), scatter_size);
+    assert_eq!(
+        scattered.iter().map(DataBlock::num_rows).sum::<usize>(),
+        NUM_ROWS
+    );
+    // The partitions have the same rows as the block, in any order.
+    assert_two_blocks_sorted_eq_with_name("scatter", &[block.clone()], &scattered);
+
+    let mut partitions = HashMap::new();
+    let mut seen_rows = vec![false; NUM_ROWS];
+    for (partition, part) in scattered.iter().enumerate() {
+        assert_eq!(part.num_columns(), block.num_columns());
+
+        // The rows keep their order and values, the constant column is not materialized.
+        let row_numbers = part.get_by_offset(0).value.as_column().unwrap();
+        let row_numbers = UInt64Type::try_downcast_column(row_numbers).unwrap();
+        let mut last_row = None;
+        for (i, row) in row_numbers.iter().enumerate() {
+            let row = *row as usize;
+            assert!(last_row < Some(row));
+            last_row = Some(row);
+            assert!(!seen_rows[row]);
+            seen_rows[row] = true;
+
+            for column in 1..block.num_columns() - 1 {
+                let expected = block.get_by_offset(column).value.index(row);
+                assert_eq!(part.get_by_offset(column).value.index(i), expected);
+            }
+
+            // The rows with the same keys are sent to the same partition.
+            let key = key_columns
+                .iter()
+                .map(|c| format!("{:?}", block.get_by_offset(*c).value.index(row)))
+                .collect::<Vec<_>>();
+            assert_eq!(*partitions.entry(key).or_insert(partition), partition);
+        }
+        assert_eq!(
+            part.get_by_offset(3).value,
+            Value::Scalar(Scalar::Number(NumberScalar::Int64(42)))
+        );
+    }
+
+    // All the keys are not sent to one partition.
+    let used_partitions = partitions.values().collect::<HashSet<_>>();
+    assert!(used_partitions.len() > 1, "{:?}", partitions);
+    Ok(())
+}
+
+#[test]
+fn test_hash_scatter_one_key() -> Result<()> {
+    let scatter = HashFlightScatter::try_create(
+        FunctionContext::default(),
+        vec![column_ref(1, DataType::Number(NumberDataType::Int64))],
+        4,
+    )?;
+    check_scatter(scatter.as_ref(), &[1], 4)
+}
+
+#[test]
+fn test_hash_scatter_multiple_keys() -> Result<()> {
+    let scatter = HashFlightScatter::try_create(
+        FunctionContext::default(),
+        vec![
+            column_ref(1, DataType::Number(NumberDataType::Int64)),
+            column_ref(2, DataType::Nullable(Box::new(DataType::String))),
+        ],
+        3,
+    )?;
+    check_scatter(scatter.as_ref(), &[1, 2], 3)
+}
+
+#[test]
+fn test_hash_scatter_equal_keys_across_blocks() -> Result<()> {
+    let scatter = HashFlightScatter::try_create(
+        FunctionContext::default(),
+        vec![column_ref(0, DataType::Number(NumberDataType::Float64))],
+        16,
+    )?;
+    let partition_of = |key: f64| -> Result<usize> {
+        let block = BlockFixture::new().col_f64("c0", [key]).build();
+        let scattered = scatter.execute(block)?;
+        Ok(scattered.iter().position(|b| b.num_rows() == 1).unwrap())
+    };
+
+    // -0.0 and +0.0 are equal keys, and so are the NaNs.
+    assert_eq!(partition_of(0.0)?, partition_of(-0.0)?);
+    assert_eq!(partition_of(f64::NAN)?, partition_of(-f64::NAN)?);
+    assert_eq!(partition_of(1.5)?, partition_of(1.5)?);
+    Ok(())
+}
+
+#[test]
+fn test_hash_scatter_keeps_slots_spread() -> Result<()> {
+    const SLOT_MASK: u64 = 0xFF;
+
+    let scatter = HashFlightScatter::try_create(
+        FunctionContext::default(),
+        vec![column_ref(0, DataType::Number(NumberDataType::UInt64))],
+        4,
+    )?;
+
+    // The aggregate hash table of a destination picks the slots from the low bits of the group
+    // hash of the keys. If the destination was picked from the same bits, the keys of a
+    // destination would only hit 1/4 of the slots, i.e. at most 64 of 256.
+    for part in scatter.execute(test_block())? {
+        let keys = part.get_by_offset(0).value.as_column().unwrap().clone();
+        let mut hashes = vec![0; part.num_rows()];
+        group_hash_columns(&[keys], &mut hashes);
+        let slots = hashes
+            .iter()
+            .map(|hash| hash & SLOT_MASK)
+            .collect::<HashSet<_>>();
+        assert!(
+            slots.len() > 100,
+            "{} rows hit {} slots",
+            part.num_rows(),
+            slots.len()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_round_robin_scatter() -> Result<()> {
+    let scatter = RoundRobinFlightScatter::try_create(3)?;
+
+    // The empty block does not move the turn.
+    let scattered = scatter.execute(test_block().slice(0..0))?;
+    assert_eq!(scattered.len(), 3);
+    assert!(scattered.iter().all(|block| block.num_rows() == 0));
+
+    // The turn goes on across the blocks.
+    for start in [0, NUM_ROWS] {
+        let scattered = scatter.execute(test_block())?;
+        assert_eq!(scattered.len(), 3);
+        for (partition, part) in scattered.iter().enumerate() {
+            let expected = (0..NUM_ROWS as u64)
+                .filter(|row| (start as u64 + row) % 3 == partition as u64)
+                .collect::<Vec<_>>();
+            assert_eq!(row_numbers(part), expected);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_range_scatter() -> Result<()> {
+    // The boundaries are compared with the keys after coercion.
+    let boundaries = vec![
+        Scalar::Number(NumberScalar::UInt8(2)),
+        Scalar::Number(NumberScalar::UInt8(5)),
+    ];
+    let key = column_ref(1, DataType::Number(NumberDataType::Int64));
+    let scatter = RangeFlightScatter::try_create(FunctionContext::default(), &key, boundaries, 3)?;
+
+    let scattered = scatter.execute(test_block())?;
+    let ranges = [0..2, 2..5, 5..7];
+    for (part, range) in scattered.iter().zip(ranges) {
+        let expected = (0..NUM_ROWS as u64)
+            .filter(|row| range.contains(&(row % 7)))
+            .collect::<Vec<_>>();
+        assert_eq!(row_numbers(part), expected);
+    }
+
+    check_scatter(&scatter, &[1], 3)?;
+
+    let scattered = scatter.execute(test_block().slice(0..0))?;
+    assert_eq!(scattered.len(), 3);
+    assert!(scattered.iter().all(|block| block.num_rows() == 0));
+    Ok(())
+}
+
+#[test]
+fn test_range_scatter_nulls() -> Result<()> {
+    // The NULL keys are sent to the first partition, and a NULL boundary is before all the keys.
+    let boundaries = vec![Scalar::Null, Scalar::String("key_2".to_string())];
+    let key = column_ref(2, DataType::Nullable(Box::new(DataType::String)));
+    let scatter = RangeFlightScatter::try_create(FunctionContext::default(), &key, boundaries, 3)?;
+
+    let scattered = scatter.execute(test_block())?;
+    let expected = [
+        (0..NUM_ROWS as u64)
+            .filter(|row| row % 3 == 0)
+            .collect::<Vec<_>>(),
+        (0..NUM_ROWS as u64)
+            .filter(|row| row % 3 != 0 && row % 5 < 2)
+            .collect::<Vec<_>>(),
+        (0..NUM_ROWS as u64)
+            .filter(|row| row % 3 != 0 && row % 5 >= 2)
+            .collect::<Vec<_>>(),
+    ];
+    for (part, expected) in scattered.iter().zip(expected) {
+        assert_eq!(row_numbers(part), expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_range_scatter_invalid_boundaries() {
+    let key = column_ref(1, DataType::Number(NumberDataType::Int64));
+    let int = |v| Scalar::Number(NumberScalar::Int64(v));
+
+    // Too few boundaries.
+    let scatter = RangeFlightScatter::try_create(FunctionContext::default(), &key, vec![int(1)], 3);
+    assert!(scatter.is_err());
+
+    // Unsorted boundaries.
+    let scatter =
+        RangeFlightScatter::try_create(FunctionContext::default(), &key, vec![int(5), int(2)], 3);
+    assert!(scatter.is_err());
+
+    // Incomparable boundaries.
+    let boundaries = vec![int(1), Scalar::String("a".to_string())];
+    let scatter = RangeFlightScatter::try_create(FunctionContext::default(), &key, boundaries, 3);
+    assert!(scatter.is_err());
+}
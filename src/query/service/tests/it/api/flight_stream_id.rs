@@ -0,0 +1,86 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_arrow::arrow_format::flight::data::Ticket;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_query::api::FragmentStreamId;
+use tonic::metadata::MetadataValue;
+use tonic::Request;
+
+#[test]
+fn test_fragment_stream_id_round_trip() -> Result<()> {
+    let id = FragmentStreamId::create("1b2c-query", "node-1", 42);
+    assert_eq!(id.to_string(), "1b2c-query/node-1/42");
+    assert_eq!(id.to_string().parse::<FragmentStreamId>()?, id);
+
+    // The separator in the components is escaped.
+    let id = FragmentStreamId::create("tenant/query%1", "node/1", 7);
+    assert_eq!(id.to_string(), "tenant%2Fquery%251/node%2F1/7");
+    assert_eq!(id.to_string().parse::<FragmentStreamId>()?, id);
+
+    let request = Request::new(id.to_ticket());
+    assert_eq!(FragmentStreamId::from_request(&request)?, id);
+    Ok(())
+}
+
+#[test]
+fn test_fragment_stream_id_invalid() -> Result<()> {
+    for invalid in [
+        "",
+        "query",
+        "query/node-1",
+        "query/node/1/2",
+        "query/node-1/abc",
+        "query//1",
+        "/node-1/1",
+        "query%2/node-1/1",
+        "query%zz/node-1/1",
+    ] {
+        let res = invalid.parse::<FragmentStreamId>();
+        assert_eq!(
+            res.unwrap_err().code(),
+            ErrorCode::BAD_ARGUMENTS,
+            "{invalid}"
+        );
+    }
+
+    let request = Request::new(Ticket {
+        ticket: b"query/node-1".to_vec(),
+    });
+    let err = FragmentStreamId::from_request(&request).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::BAD_ARGUMENTS);
+    assert!(err.message().contains("query/node-1"));
+    Ok(())
+}
+
+#[test]
+fn test_fragment_stream_id_from_legacy_metadata() -> Result<()> {
+    let mut request = Request::new(Ticket::default());
+    let metadata = request.metadata_mut();
+    metadata.insert("x-query-id", MetadataValue::from_static("query"));
+    metadata.insert("x-target", MetadataValue::from_static("node-1"));
+    metadata.insert("x-fragment-id", MetadataValue::from_static("3"));
+    assert_eq!(
+        FragmentStreamId::from_request(&request)?,
+        FragmentStreamId::create("query", "node-1", 3)
+    );
+
+    request
+        .metadata_mut()
+        .insert("x-fragment-id", MetadataValue::from_static("x"));
+    let res = FragmentStreamId::from_request(&request);
+    assert_eq!(res.unwrap_err().code(), ErrorCode::BAD_ARGUMENTS);
+    Ok(())
+}
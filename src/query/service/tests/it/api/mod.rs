@@ -12,6 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod exchange_manager;
+mod exchange_serde;
+mod exchange_sorted_merge;
+mod flight_client;
+mod flight_resume;
+mod flight_scatter;
+mod flight_stream_id;
 mod http;
 mod http_service;
 mod rpc_service;
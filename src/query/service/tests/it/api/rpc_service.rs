@@ -25,6 +25,7 @@ use databend_common_exception::Result;
 use databend_common_grpc::ConnectionFactory;
 use databend_common_grpc::GrpcConnectionError;
 use databend_common_grpc::RpcClientTlsConfig;
+use databend_query::api::FlightClient;
 use databend_query::api::RpcService;
 use databend_query::test_kits::*;
 
@@ -85,6 +86,87 @@ async fn test_tls_rpc_server_invalid_server_config() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tls_rpc_server_half_config() -> Result<()> {
+    let mut srv = RpcService {
+        config: ConfigBuilder::create()
+            .rpc_tls_server_cert(TEST_SERVER_CERT)
+            .build(),
+        abort_notify: Arc::new(Default::default()),
+    };
+    let r = srv
+        .start_with_incoming("127.0.0.1:0".parse().unwrap())
+        .await;
+    let e = r.unwrap_err();
+    assert_eq!(e.code(), ErrorCode::TLSConfigurationFailure("").code());
+    assert!(
+        e.message().contains("must be set together"),
+        "{}",
+        e.message()
+    );
+
+    let mut srv = RpcService {
+        config: ConfigBuilder::create()
+            .rpc_tls_server_key(TEST_SERVER_KEY)
+            .rpc_tls_server_cert("../tests/data/certs/none.pem")
+            .build(),
+        abort_notify: Arc::new(Default::default()),
+    };
+    let r = srv
+        .start_with_incoming("127.0.0.1:0".parse().unwrap())
+        .await;
+    let e = r.unwrap_err();
+    assert!(e.message().contains("none.pem"), "{}", e.message());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tls_flight_client() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let tls_address = listener.local_addr().unwrap();
+    drop(listener);
+    let mut tls_service = RpcService::create(
+        ConfigBuilder::create()
+            .rpc_tls_server_key(TEST_SERVER_KEY)
+            .rpc_tls_server_cert(TEST_SERVER_CERT)
+            .build(),
+    )?;
+    tls_service.start(tls_address).await?;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let plain_address = listener.local_addr().unwrap();
+    drop(listener);
+    let mut plain_service = RpcService::create(ConfigBuilder::create().build())?;
+    plain_service.start(plain_address).await?;
+
+    let tls_config = ConfigBuilder::create()
+        .rpc_tls_query_server_root_ca_cert(TEST_CA_CERT)
+        .rpc_tls_query_service_domain_name(TEST_CN_NAME)
+        .build();
+    let plain_config = ConfigBuilder::create().build();
+
+    let client = FlightClient::connect(&tls_config, &tls_address.to_string(), None).await;
+    assert!(client.is_ok());
+    let client = FlightClient::connect(&plain_config, &plain_address.to_string(), None).await;
+    assert!(client.is_ok());
+
+    // The TLS handshake with a node serving plaintext fails.
+    let r = FlightClient::connect(&tls_config, &plain_address.to_string(), None).await;
+    let e = r.err().unwrap();
+    assert_eq!(e.code(), ErrorCode::CannotConnectNode("").code());
+    assert!(
+        e.message().contains("rpc tls is enabled"),
+        "{}",
+        e.message()
+    );
+
+    // A node serving TLS rejects the plaintext requests.
+    let channel = ConnectionFactory::create_rpc_channel(tls_address, None, None).await?;
+    let mut f_client = FlightServiceClient::new(channel);
+    assert!(f_client.list_actions(Empty {}).await.is_err());
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_tls_rpc_server_invalid_client_config() -> Result<()> {
     // setup, invalid cert locations
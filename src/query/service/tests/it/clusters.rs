@@ -12,10 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+use std::time::Duration;
+
 use databend_common_base::base::tokio;
+use databend_common_config::InnerConfig;
+use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
+use databend_common_meta_types::NodeInfo;
+use databend_common_meta_types::NodeStatus;
+use databend_query::api::RpcService;
+use databend_query::clusters::Cluster;
 use databend_query::clusters::ClusterDiscovery;
 use databend_query::clusters::ClusterHelper;
+use databend_query::interpreters::InterpreterFactory;
+use databend_query::sessions::SessionManager;
+use databend_query::sessions::SessionType;
+use databend_query::sql::Planner;
 use databend_query::test_kits::*;
 use pretty_assertions::assert_eq;
 
@@ -65,6 +78,157 @@ async fn test_remove_invalid_nodes() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_drain_node() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+
+    // Two nodes sharing the cluster membership.
+    let configs = ["127.0.0.1:19091", "127.0.0.1:19092"].map(|address| {
+        ConfigBuilder::create()
+            .query_flight_address(address)
+            .build()
+    });
+    let mut rpc_services = Vec::with_capacity(configs.len());
+    for config in &configs {
+        let mut srv = RpcService::create(config.clone())?;
+        srv.start(config.query.flight_api_address.parse()?).await?;
+        rpc_services.push(srv);
+    }
+
+    let metastore = ClusterDiscovery::create_meta_client(&configs[0]).await?;
+    let node_1 = ClusterDiscovery::try_create(&configs[0], metastore.clone()).await?;
+    let node_2 = ClusterDiscovery::try_create(&configs[1], metastore).await?;
+    node_1.register_to_metastore(&configs[0]).await?;
+    node_2.register_to_metastore(&configs[1]).await?;
+    assert_eq!(node_1.discover(&configs[0]).await?.nodes.len(), 2);
+
+    // A query is still running when the drain starts.
+    let session = fixture
+        .new_session_with_type(SessionType::HTTPQuery)
+        .await?;
+    let in_flight_ctx = session.create_query_context().await?;
+
+    node_2.drain(Duration::from_secs(60)).await?;
+    assert_eq!(node_2.local_status(), NodeStatus::Draining);
+
+    // The peer stops placing new stages on the draining node at once.
+    let cluster = node_1.discover(&configs[0]).await?;
+    assert_eq!(cluster.nodes.len(), 1);
+    assert!(cluster.is_local(&cluster.nodes[0]));
+    assert_eq!(cluster.draining_nodes.len(), 1);
+    assert_eq!(cluster.draining_nodes[0].id, configs[1].query.node_id);
+    assert_eq!(cluster.draining_nodes[0].status, NodeStatus::Draining);
+    assert!(node_1.check_admission(&cluster).is_ok());
+
+    // New client queries on the draining node are refused with the healthy peers.
+    let cluster = node_2.discover(&configs[1]).await?;
+    let err = node_2.check_admission(&cluster).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::CLUSTER_NODE_DRAINING);
+    assert!(err.message().contains(&configs[0].query.node_id));
+    assert!(!err.message().contains(&configs[1].query.flight_api_address));
+
+    // A peer which started draining after the cluster was discovered is not suggested either.
+    let nodes = cluster
+        .nodes
+        .iter()
+        .map(|node| match cluster.is_local(node) {
+            true => node.clone(),
+            false => Arc::new(NodeInfo {
+                status: NodeStatus::Draining,
+                ..(**node).clone()
+            }),
+        })
+        .collect();
+    let stale_cluster = Cluster {
+        local_id: cluster.local_id.clone(),
+        nodes,
+        draining_nodes: vec![],
+    };
+    let err = node_2.check_admission(&stale_cluster).unwrap_err();
+    assert!(!err.message().contains(&configs[0].query.node_id));
+
+    // Draining a draining node is a no-op.
+    node_2.drain(Duration::from_secs(60)).await?;
+
+    // Not drained until the running query is finished.
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(node_2.local_status(), NodeStatus::Draining);
+
+    drop(in_flight_ctx);
+    SessionManager::instance().destroy_session(&session.get_id());
+    wait_for_status(
+        &node_1,
+        &configs[0],
+        &configs[1].query.node_id,
+        NodeStatus::Drained,
+    )
+    .await?;
+    assert_eq!(node_2.local_status(), NodeStatus::Drained);
+
+    // The node is reported drained at the deadline, even if queries are still running.
+    let session = fixture
+        .new_session_with_type(SessionType::HTTPQuery)
+        .await?;
+    let _in_flight_ctx = session.create_query_context().await?;
+    node_1.drain(Duration::from_millis(200)).await?;
+    wait_for_status(
+        &node_2,
+        &configs[1],
+        &configs[0].query.node_id,
+        NodeStatus::Drained,
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_system_statements() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let ctx = fixture.new_query_ctx().await?;
+
+    // The statements a draining node still accepts.
+    let cases = [
+        ("SELECT * FROM system.clusters", true),
+        ("SELECT name FROM system.tables, information_schema.columns", true),
+        ("SHOW TABLES", true),
+        ("SHOW GRANTS", true),
+        ("SYSTEM DRAIN NODE 'node1'", true),
+        ("SELECT 1", false),
+        ("SELECT * FROM numbers(10)", false),
+        ("SELECT * FROM system.one, numbers(10)", false),
+    ];
+    for (sql, expected) in cases {
+        let mut planner = Planner::new(ctx.clone());
+        let (plan, _) = planner.plan_sql(sql).await?;
+        let interpreter = InterpreterFactory::get(ctx.clone(), &plan).await?;
+        assert_eq!(interpreter.is_system_statement(), expected, "{sql}");
+    }
+
+    Ok(())
+}
+
+async fn wait_for_status(
+    discovery: &Arc<ClusterDiscovery>,
+    config: &InnerConfig,
+    node_id: &str,
+    status: NodeStatus,
+) -> Result<()> {
+    for _ in 0..100 {
+        let cluster = discovery.discover(config).await?;
+        if cluster
+            .draining_nodes
+            .iter()
+            .any(|node| node.id == node_id && node.status == status)
+        {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    panic!("node {node_id} is not {status} in time");
+}
+
 // TODO:(Winter) need kvapi::KVApi for cluster multiple nodes test
 // #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 // async fn test_multiple_cluster_discovery() -> Result<()> {
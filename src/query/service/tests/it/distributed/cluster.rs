@@ -12,6 +12,7 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::sync::Arc;
 use std::thread;
 
 use databend_common_base::base::tokio;
@@ -23,6 +24,7 @@ use databend_query::api::RpcService;
 use databend_query::test_kits::*;
 use futures_util::TryStreamExt;
 use tokio::runtime::Builder as TokioRuntimeBuilder;
+use tokio::sync::Barrier;
 
 #[test]
 fn test_simple_cluster() -> Result<()> {
@@ -112,6 +114,82 @@ fn test_simple_cluster() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_remote_error_code() -> Result<()> {
+    let configs = setup_node_configs(vec![
+        "0.0.0.0:6071", // Node 1 flight address
+        "0.0.0.0:6072", // Node 2 flight address
+        "0.0.0.0:6073", // Node 3 flight address
+    ]);
+
+    let task_count = configs.len();
+    let mut handles = Vec::with_capacity(task_count);
+
+    let cluster_desc = setup_cluster(&configs);
+    // All the nodes serve until the query on the last node is finished.
+    let started = Arc::new(Barrier::new(task_count));
+    let finished = Arc::new(Barrier::new(task_count));
+
+    for (i, conf) in configs.into_iter().enumerate() {
+        let thread_name = format!("custom-thread-error-node-{}", i + 1);
+        let is_check_node = i == task_count - 1;
+
+        let cluster_desc = cluster_desc.clone();
+        let started = started.clone();
+        let finished = finished.clone();
+
+        let handle = thread::Builder::new()
+            .name(thread_name)
+            .spawn(move || {
+                let rt = TokioRuntimeBuilder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .expect("Failed to create runtime");
+
+                rt.block_on(async move {
+                    let fixture = TestFixture::setup_with_config(&conf).await?;
+
+                    let mut srv = RpcService::create(conf.clone())?;
+                    srv.start(conf.query.flight_api_address.parse()?).await?;
+                    started.wait().await;
+
+                    // Wait for the other nodes even if the query fails.
+                    let mut result = None;
+                    if is_check_node {
+                        result = Some(
+                            async {
+                                let ctx = fixture.new_query_ctx_with_cluster(cluster_desc).await?;
+                                let sql = "select number / (number - number) from numbers(100000)";
+                                let stream = execute_query(ctx, sql).await?;
+                                stream.try_collect::<Vec<DataBlock>>().await
+                            }
+                            .await,
+                        );
+                    }
+                    finished.wait().await;
+
+                    // The error raised by the remote nodes keeps its code.
+                    if let Some(result) = result {
+                        let error = result.unwrap_err();
+                        assert_eq!(error.code(), ErrorCode::BAD_ARGUMENTS, "{}", error);
+                        assert!(error.message().contains("divided by zero"));
+                    }
+
+                    Ok::<(), ErrorCode>(())
+                })
+            })
+            .map_err(|e| ErrorCode::UnknownException(format!("Failed to spawn thread: {}", e)))?;
+
+        handles.push(handle);
+    }
+
+    for handle in handles {
+        handle.join().expect("Thread failed to complete")?;
+    }
+
+    Ok(())
+}
+
 /// Setup the configurations for the nodes in the cluster.
 fn setup_node_configs(addresses: Vec<&str>) -> Vec<InnerConfig> {
     addresses
@@ -0,0 +1,127 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_base::base::tokio;
+use databend_common_exception::Result;
+use databend_common_expression::DataBlock;
+use databend_common_expression::ScalarRef;
+use databend_query::test_kits::TestFixture;
+use futures::TryStreamExt;
+use serde_json::Value;
+
+const QUERY: &str = "SELECT number FROM numbers(1000) WHERE number % 100 = 0";
+
+async fn explain_json(fixture: &TestFixture, sql: &str) -> Result<Value> {
+    let blocks = fixture
+        .execute_query(sql)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let block = DataBlock::concat(&blocks)?;
+    assert_eq!(block.num_rows(), 1);
+
+    let value = block.get_by_offset(0).value.index(0).unwrap();
+    let ScalarRef::String(document) = value else {
+        panic!("explain result must be a string, got {value}");
+    };
+    Ok(serde_json::from_str(document).unwrap())
+}
+
+fn find_node<'a>(node: &'a Value, name: &str) -> Option<&'a Value> {
+    if node["name"] == name {
+        return Some(node);
+    }
+    node["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find_map(|child| find_node(child, name))
+}
+
+fn attribute<'a>(node: &'a Value, key: &str) -> Option<&'a str> {
+    node["attributes"].get(key).map(|v| v.as_str().unwrap())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_explain_json() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+
+    let document = explain_json(&fixture, &format!("EXPLAIN(FORMAT JSON) {QUERY}")).await?;
+    let nodes = document["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 1);
+
+    let filter = find_node(&nodes[0], "Filter").unwrap();
+    assert_eq!(
+        attribute(filter, "filters"),
+        Some("[numbers.number (#0) % 100 = 0]")
+    );
+    let scan = find_node(filter, "TableScan").unwrap();
+    assert_eq!(attribute(scan, "table"), Some("default.system.numbers"));
+    assert_eq!(attribute(scan, "estimated rows"), Some("1000.00"));
+    assert!(scan["children"].as_array().unwrap().is_empty());
+
+    // Text explain outputs are kept line by line.
+    let document = explain_json(&fixture, "EXPLAIN(FORMAT JSON) AST SELECT 1").await?;
+    for node in document["nodes"].as_array().unwrap() {
+        assert!(node["attributes"].as_object().unwrap().is_empty());
+        assert!(node["children"].as_array().unwrap().is_empty());
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_explain_analyze_verbose() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+
+    // Without VERBOSE the estimation is not compared.
+    let document = explain_json(&fixture, &format!("EXPLAIN(FORMAT JSON) ANALYZE {QUERY}")).await?;
+    let scan = find_node(&document["nodes"][0], "TableScan").unwrap();
+    assert_eq!(attribute(scan, "actual rows"), None);
+    assert_eq!(attribute(scan, "estimation ratio"), None);
+
+    let sql = format!("EXPLAIN(FORMAT JSON) ANALYZE VERBOSE {QUERY}");
+    let document = explain_json(&fixture, &sql).await?;
+    let scan = find_node(&document["nodes"][0], "TableScan").unwrap();
+    assert_eq!(attribute(scan, "estimated rows"), Some("1000.00"));
+    assert_eq!(attribute(scan, "actual rows"), Some("1000"));
+    assert_eq!(attribute(scan, "estimation ratio"), Some("1.00"));
+    assert_eq!(attribute(scan, "mis-estimated"), None);
+
+    let filter = find_node(&document["nodes"][0], "Filter").unwrap();
+    assert_eq!(attribute(filter, "actual rows"), Some("10"));
+
+    // The filter is marked once its estimation is off by more than the factor.
+    let ratio: f64 = attribute(filter, "estimation ratio")
+        .unwrap()
+        .parse()
+        .unwrap();
+    let off_by = if ratio >= 1.0 { ratio } else { 1.0 / ratio };
+    for (factor, misestimated) in [(1, off_by > 1.0), (1_000_000, false)] {
+        fixture
+            .execute_command(&format!("SET explain_misestimation_factor = {factor}"))
+            .await?;
+        let document = explain_json(&fixture, &sql).await?;
+        let filter = find_node(&document["nodes"][0], "Filter").unwrap();
+        assert_eq!(
+            attribute(filter, "mis-estimated").is_some(),
+            misestimated,
+            "factor: {factor}, ratio: {ratio}"
+        );
+        let scan = find_node(&document["nodes"][0], "TableScan").unwrap();
+        assert_eq!(attribute(scan, "mis-estimated"), None);
+    }
+
+    Ok(())
+}
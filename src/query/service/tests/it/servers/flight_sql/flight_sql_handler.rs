@@ -19,15 +19,22 @@ use std::io::Write;
 
 use arrow_array::RecordBatch;
 use arrow_cast::pretty::pretty_format_batches;
+use arrow_flight::error::FlightError;
 use arrow_flight::flight_service_server::FlightServiceServer;
 use arrow_flight::sql::client::FlightSqlServiceClient;
+use arrow_flight::FlightClient;
+use arrow_flight::FlightDescriptor;
+use arrow_flight::Ticket;
 use arrow_schema::ArrowError;
+use arrow_schema::DataType;
 use databend_common_base::base::tokio;
 use databend_common_config::InnerConfig;
 use databend_common_exception::Result;
 use databend_common_meta_app::principal::AuthInfo;
 use databend_common_meta_app::principal::PasswordHashMethod;
+use databend_query::servers::flight_sql::flight_sql_service::FlightServiceImpl;
 use databend_query::servers::flight_sql::flight_sql_service::FlightSqlServiceImpl;
+use databend_query::servers::flight_sql::flight_sql_service::SqlTicket;
 use databend_query::test_kits::ConfigBuilder;
 use databend_query::test_kits::TestFixture;
 use futures::TryStreamExt;
@@ -40,19 +47,23 @@ use tokio_stream::wrappers::UnixListenerStream;
 use tonic::transport::Channel;
 use tonic::transport::Endpoint;
 use tonic::transport::Server;
+use tonic::Code;
 use tower::service_fn;
 
 const TEST_USER: &str = "test_user";
 const TEST_PASSWORD: &str = "test_password";
 
-async fn client_with_uds(path: String) -> FlightSqlServiceClient<Channel> {
+async fn channel_with_uds(path: String) -> Channel {
     let connector = service_fn(move |_| UnixStream::connect(path.clone()));
-    let channel = Endpoint::try_from("http://example.com")
+    Endpoint::try_from("http://example.com")
         .unwrap()
         .connect_with_connector(connector)
         .await
-        .unwrap();
-    FlightSqlServiceClient::new(channel)
+        .unwrap()
+}
+
+async fn client_with_uds(path: String) -> FlightSqlServiceClient<Channel> {
+    FlightSqlServiceClient::new(channel_with_uds(path).await)
 }
 
 async fn run_query(
@@ -146,3 +157,87 @@ async fn test_query() -> Result<()> {
 
     Ok(())
 }
+
+fn sql_ticket(sql: &str, password: &str) -> Vec<u8> {
+    serde_json::to_vec(&SqlTicket {
+        sql: sql.to_string(),
+        database: None,
+        user: TEST_USER.to_string(),
+        password: password.to_string(),
+    })
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_sql_ticket() -> Result<()> {
+    let _fixture = TestFixture::setup_with_config(&prepare_config()).await?;
+
+    let file = NamedTempFile::new().unwrap();
+    let path = file.into_temp_path().to_str().unwrap().to_string();
+    let _ = fs::remove_file(path.clone());
+
+    let uds = UnixListener::bind(path.clone()).unwrap();
+    let stream = UnixListenerStream::new(uds);
+
+    let service = FlightServiceImpl::create();
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    let serve_future = Server::builder()
+        .add_service(FlightServiceServer::new(service))
+        .serve_with_incoming_shutdown(stream, async { shutdown_rx.await.unwrap() });
+
+    let request_future = async {
+        let mut client = FlightClient::new(channel_with_uds(path).await);
+        let ticket = sql_ticket("SELECT number FROM numbers(100)", TEST_PASSWORD);
+
+        // The schema is returned without executing the query.
+        let info = client
+            .get_flight_info(FlightDescriptor::new_cmd(ticket))
+            .await
+            .unwrap();
+        let schema = info.clone().try_decode_schema().unwrap();
+        assert_eq!(schema.fields().len(), 1);
+        assert_eq!(schema.field(0).name(), "number");
+        assert_eq!(schema.field(0).data_type(), &DataType::UInt64);
+
+        let ticket = info.endpoint[0].ticket.clone().unwrap();
+        let stream = client.do_get(ticket).await.unwrap();
+        let batches: Vec<RecordBatch> = stream.try_collect().await.unwrap();
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 100);
+        for batch in &batches {
+            assert_eq!(batch.schema().as_ref(), &schema);
+        }
+
+        let ticket = Ticket::new(sql_ticket("SELECT * FROM no_such_table", TEST_PASSWORD));
+        match client.do_get(ticket).await {
+            Err(FlightError::Tonic(status)) => {
+                assert_eq!(status.code(), Code::NotFound);
+                assert_eq!(
+                    status.metadata().get("x-databend-error-code").unwrap(),
+                    "1025"
+                );
+            }
+            Err(e) => panic!("unexpected error: {e:?}"),
+            Ok(_) => panic!("querying an unknown table should fail"),
+        }
+
+        let ticket = Ticket::new(sql_ticket("SELECT 1", "wrong_password"));
+        match client.do_get(ticket).await {
+            Err(FlightError::Tonic(status)) => assert_eq!(status.code(), Code::Unauthenticated),
+            Err(e) => panic!("unexpected error: {e:?}"),
+            Ok(_) => panic!("a wrong password should fail"),
+        }
+    };
+    tokio::pin!(serve_future);
+
+    tokio::select! {
+        _ = &mut serve_future => panic!("server returned first"),
+        _ = request_future => {
+            debug!("Client finished!");
+        }
+    }
+    shutdown_tx.send(()).unwrap();
+    serve_future.await.unwrap();
+    debug!("Server shutdown!");
+
+    Ok(())
+}
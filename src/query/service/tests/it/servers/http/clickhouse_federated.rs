@@ -0,0 +1,263 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_base::base::tokio;
+use databend_common_catalog::table_context::TableContext;
+use databend_common_exception::Result;
+use databend_common_expression::block_debug::assert_blocks_eq;
+use databend_common_expression::types::NumberScalar;
+use databend_common_expression::ScalarRef;
+use databend_query::servers::http::map_clickhouse_setting;
+use databend_query::servers::http::ClickHouseFederated;
+use databend_query::test_kits::TestFixture;
+
+#[test]
+fn test_map_clickhouse_setting() {
+    assert_eq!(
+        map_clickhouse_setting("max_block_size"),
+        Some("max_block_size")
+    );
+    assert_eq!(map_clickhouse_setting("MAX_THREADS"), Some("max_threads"));
+    assert_eq!(
+        map_clickhouse_setting("max_read_buffer_size"),
+        Some("input_read_buffer_size")
+    );
+    assert_eq!(map_clickhouse_setting("session_timezone"), Some("timezone"));
+    assert_eq!(map_clickhouse_setting("send_logs_level"), None);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_clickhouse_federated_set() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let ctx = fixture.new_query_ctx().await?;
+    let settings = ctx.get_shared_settings();
+
+    // Not a federated query.
+    assert!(ClickHouseFederated::check(&ctx, "select 1").is_none());
+
+    // Known ClickHouse settings change the mapped settings.
+    {
+        let result = ClickHouseFederated::check(&ctx, "SET max_block_size = 65536");
+        let (schema, block) = result.unwrap()?;
+        assert!(schema.fields().is_empty());
+        assert_eq!(block.num_rows(), 0);
+        assert_eq!(settings.get_max_block_size()?, 65536);
+
+        let result = ClickHouseFederated::check(&ctx, "set session_timezone = 'Asia/Shanghai';");
+        result.unwrap()?;
+        assert_eq!(settings.get_timezone()?, "Asia/Shanghai");
+    }
+
+    // Invalid values of known settings are errors.
+    {
+        let result = ClickHouseFederated::check(&ctx, "SET max_threads = 'abc'");
+        assert!(result.unwrap().is_err());
+    }
+
+    // Unknown ClickHouse-only settings are acknowledged with an empty result.
+    {
+        let result = ClickHouseFederated::check(&ctx, "SET send_logs_level = 'warning'");
+        let (schema, block) = result.unwrap()?;
+        assert!(schema.fields().is_empty());
+        assert_eq!(block.num_rows(), 0);
+        assert!(settings
+            .into_iter()
+            .all(|item| item.name != "send_logs_level"));
+    }
+
+    // Every setting of a list is set.
+    {
+        let result = ClickHouseFederated::check(
+            &ctx,
+            "SET max_threads = 3, send_logs_level = 'a,b', enable_cbo = 0, max_block_size = 1000",
+        );
+        result.unwrap()?;
+        assert_eq!(settings.get_max_threads()?, 3);
+        assert_eq!(settings.get_max_block_size()?, 1000);
+        assert!(!settings.get_enable_cbo()?);
+    }
+
+    // The other settings are planned as usual.
+    assert!(ClickHouseFederated::check(&ctx, "SET enable_cbo = 0").is_none());
+    assert!(ClickHouseFederated::check(&ctx, "SET enable_cbo = 0, timezone = 'UTC'").is_none());
+    assert!(ClickHouseFederated::check(&ctx, "SET max_threads = 1, enable_cbo").is_none());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_clickhouse_federated_show_settings() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let ctx = fixture.new_query_ctx().await?;
+    let settings = ctx.get_shared_settings();
+    settings.set_max_threads(4)?;
+
+    {
+        let result = ClickHouseFederated::check(&ctx, "SHOW SETTINGS LIKE 'max_threads'");
+        let (_, block) = result.unwrap()?;
+        let expect = vec![
+            "+---------------+----------+----------+",
+            "| Column 0      | Column 1 | Column 2 |",
+            "+---------------+----------+----------+",
+            "| 'max_threads' | 'UInt64' | '4'      |",
+            "+---------------+----------+----------+",
+        ];
+        assert_blocks_eq(expect, &[block]);
+    }
+
+    {
+        let result = ClickHouseFederated::check(
+            &ctx,
+            "show changed settings ilike 'MAX_%' format TabSeparated",
+        );
+        let (schema, block) = result.unwrap()?;
+        assert_eq!(schema.fields().len(), 3);
+        assert_eq!(block.num_rows(), 1);
+    }
+
+    {
+        let result = ClickHouseFederated::check(&ctx, "SHOW SETTINGS LIKE '%'");
+        let (_, block) = result.unwrap()?;
+        assert_eq!(block.num_rows(), settings.into_iter().count());
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_clickhouse_federated_system_tables() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let ctx = fixture.new_query_ctx().await?;
+
+    {
+        let result = ClickHouseFederated::check(&ctx, "SELECT * FROM system.one");
+        let (schema, block) = result.unwrap()?;
+        assert_eq!(schema.fields().len(), 1);
+        assert_eq!(schema.field(0).name(), "dummy");
+        let expect = vec![
+            "+----------+",
+            "| Column 0 |",
+            "+----------+",
+            "| 0        |",
+            "+----------+",
+        ];
+        assert_blocks_eq(expect, &[block]);
+    }
+
+    {
+        let result = ClickHouseFederated::check(&ctx, "select metric, value from system.metrics");
+        let (schema, block) = result.unwrap()?;
+        let names = schema
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["metric", "value"]);
+        assert!(block.num_rows() >= 3);
+
+        let result =
+            ClickHouseFederated::check(&ctx, "SELECT * FROM system.metrics FORMAT TabSeparated");
+        let (schema, block) = result.unwrap()?;
+        let names = schema
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["metric", "value", "description"]);
+        assert_eq!(block.num_columns(), 3);
+    }
+
+    // The uptime never goes backward.
+    {
+        let uptime = || -> Result<u32> {
+            let result = ClickHouseFederated::check(&ctx, "SELECT uptime()");
+            let (schema, block) = result.unwrap()?;
+            assert_eq!(schema.field(0).name(), "uptime()");
+            let column = block.get_by_offset(0).value.as_column().unwrap().clone();
+            match column.index(0).unwrap() {
+                ScalarRef::Number(NumberScalar::UInt32(v)) => Ok(v),
+                other => panic!("unexpected uptime: {other:?}"),
+            }
+        };
+        let first = uptime()?;
+        let second = uptime()?;
+        assert!(second >= first);
+    }
+
+    // The other queries of the system tables are planned as usual.
+    assert!(ClickHouseFederated::check(&ctx, "SELECT * FROM system.one WHERE dummy = 0").is_none());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_clickhouse_federated_comments_and_statements() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let ctx = fixture.new_query_ctx().await?;
+    let settings = ctx.get_shared_settings();
+
+    // The leading comments and whitespaces are skipped.
+    for query in [
+        // Grafana.
+        "/* grafana dashboard='Overview' panel='Uptime' user='admin' */ SELECT uptime()",
+        "-- grafana\n/* dashboard='Overview' */\n\n  SELECT uptime() FORMAT JSON;\n",
+        // DBeaver.
+        "/* ApplicationName=DBeaver 23.2.5 - Main */ SELECT uptime()",
+        "\n\t SELECT uptime() ;  ",
+    ] {
+        let result = ClickHouseFederated::check(&ctx, query);
+        let (schema, _) = result.unwrap()?;
+        assert_eq!(schema.field(0).name(), "uptime()", "{query}");
+    }
+
+    {
+        let query =
+            "/* ApplicationName=DBeaver 23.2.5 - Metadata */ select metric, value from system.metrics";
+        let (schema, _) = ClickHouseFederated::check(&ctx, query).unwrap()?;
+        assert_eq!(schema.fields().len(), 2);
+
+        let query = "/* ApplicationName=DBeaver 23.2.5 - Main */\nSET max_threads = 3";
+        ClickHouseFederated::check(&ctx, query).unwrap()?;
+        assert_eq!(settings.get_max_threads()?, 3);
+    }
+
+    // Only the first statement is considered.
+    {
+        let query = "SELECT uptime() FORMAT JSON; SELECT * FROM system.one";
+        let (schema, _) = ClickHouseFederated::check(&ctx, query).unwrap()?;
+        assert_eq!(schema.field(0).name(), "uptime()");
+        assert_eq!(
+            ClickHouseFederated::get_format(query),
+            Some("JSON".to_string())
+        );
+
+        assert!(ClickHouseFederated::check(&ctx, "SELECT 1; SELECT uptime()").is_none());
+    }
+
+    // The comments and the `;` in the string literals are kept.
+    {
+        let query = "SHOW SETTINGS LIKE 'max_threads;%'; SELECT 1";
+        let (_, block) = ClickHouseFederated::check(&ctx, query).unwrap()?;
+        assert_eq!(block.num_rows(), 0);
+
+        let query = "SHOW SETTINGS LIKE '/*max_threads*/'";
+        let (_, block) = ClickHouseFederated::check(&ctx, query).unwrap()?;
+        assert_eq!(block.num_rows(), 0);
+    }
+
+    // The comments in the middle of the statement are not skipped.
+    assert!(ClickHouseFederated::check(&ctx, "SELECT /* now */ uptime()").is_none());
+
+    Ok(())
+}
@@ -21,6 +21,8 @@ use databend_query::servers::http::middleware::HTTPSessionMiddleware;
 use databend_query::servers::http::v1::clickhouse_router;
 use databend_query::servers::http::CLICKHOUSE_VERSION;
 use databend_query::servers::HttpHandlerKind;
+use databend_query::sessions::SessionManager;
+use databend_query::sessions::SessionType;
 use databend_query::test_kits::TestFixture;
 use http::Uri;
 use poem::error::Result as PoemResult;
@@ -247,6 +249,247 @@ async fn test_insert_format_ndjson() -> PoemResult<()> {
     Ok(())
 }
 
+// The Native data, as clickhouse-client sends it, of the columns
+// `a Int32, b String, c Nullable(Float64), d Array(UInt8)` and the rows
+// `(1, 'x', 1.5, [1, 2])` and `(-2, '', NULL, [])`.
+const NATIVE_DATA: &[u8] = &[
+    // 4 columns, 2 rows
+    0x04, 0x02, //
+    // a Int32: 1, -2
+    0x01, b'a', 0x05, b'I', b'n', b't', b'3', b'2', //
+    0x01, 0x00, 0x00, 0x00, 0xfe, 0xff, 0xff, 0xff, //
+    // b String: 'x', ''
+    0x01, b'b', 0x06, b'S', b't', b'r', b'i', b'n', b'g', //
+    0x01, b'x', 0x00, //
+    // c Nullable(Float64): the null map, then 1.5 and a placeholder
+    0x01, b'c', 0x11, b'N', b'u', b'l', b'l', b'a', b'b', b'l', b'e', b'(', b'F', b'l', b'o', b'a',
+    b't', b'6', b'4', b')', //
+    0x00, 0x01, //
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf8, 0x3f, //
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+    // d Array(UInt8): the end offsets 2 and 2, then the items 1, 2
+    0x01, b'd', 0x0c, b'A', b'r', b'r', b'a', b'y', b'(', b'U', b'I', b'n', b't', b'8',
+    b')', //
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+    0x01, 0x02, //
+];
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_insert_format_native() -> PoemResult<()> {
+    let _fixture = TestFixture::setup().await.unwrap();
+
+    let server = Server::new().await;
+    let tables = [
+        ("t1", "a int, b string, c double null, d array(uint8)"),
+        // The columns are converted to the types of the table.
+        ("t2", "d array(int64), c double null, b string, a bigint"),
+    ];
+    for (table, columns) in tables {
+        let (status, body) = server
+            .post(&format!("create table {table}({columns})"), "")
+            .await;
+        assert_ok!(status, body);
+
+        let sql = format!("insert into table {table} format Native");
+        let (status, body) = server
+            .get_response(QueryBuilder::new(&sql).body(NATIVE_DATA.to_vec()).build())
+            .await;
+        assert_ok!(status, body);
+
+        let (status, body) = server
+            .get(&format!("select a, b, c, d from {table} order by a"))
+            .await;
+        assert_ok!(status, body);
+        assert_eq!(&body, "-2\t\t\\N\t[]\n1\tx\t1.5\t[1,2]\n");
+    }
+
+    // The error names the column and the row which can not be converted.
+    {
+        let (status, body) = server
+            .post(
+                "create table t3(a uint8, b string, c double null, d array(uint8))",
+                "",
+            )
+            .await;
+        assert_ok!(status, body);
+
+        let sql = "insert into table t3 format Native";
+        let (status, body) = server
+            .get_response(QueryBuilder::new(sql).body(NATIVE_DATA.to_vec()).build())
+            .await;
+        assert_ne!(status, StatusCode::OK, "{}", body);
+        assert_error!(body, "column a at row 1");
+
+        // A column of the table is missing.
+        let (status, body) = server.post("create table t4(a int, e string)", "").await;
+        assert_ok!(status, body);
+        let sql = "insert into table t4 format Native";
+        let (status, body) = server
+            .get_response(QueryBuilder::new(sql).body(NATIVE_DATA.to_vec()).build())
+            .await;
+        assert_ne!(status, StatusCode::OK, "{}", body);
+        assert_error!(body, "Column e is not found");
+    }
+
+    // The counts of a malformed body are checked before anything is allocated by them.
+    {
+        let bodies: [(&[u8], &str); 2] = [
+            // 2^60 columns
+            (
+                &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x10, 0x01],
+                "declares 1152921504606846976 columns",
+            ),
+            // 1 column a UInt8 of 2^60 rows
+            (
+                &[
+                    0x01, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x10, 0x01, b'a', 0x05,
+                    b'U', b'I', b'n', b't', b'8', 0x01,
+                ],
+                "declares 1152921504606846976 rows",
+            ),
+        ];
+        for (body, error) in bodies {
+            let sql = "insert into table t1 format Native";
+            let (status, body) = server
+                .get_response(QueryBuilder::new(sql).body(body.to_vec()).build())
+                .await;
+            assert_ne!(status, StatusCode::OK, "{}", body);
+            assert_error!(body, error);
+        }
+    }
+
+    // The body of several blocks, each block is decoded once its data is read.
+    {
+        let (status, body) = server
+            .post(
+                "create table t5(a int, b string, c double null, d array(uint8))",
+                "",
+            )
+            .await;
+        assert_ok!(status, body);
+
+        let sql = "insert into table t5 format Native";
+        let (status, body) = server
+            .get_response(QueryBuilder::new(sql).body(NATIVE_DATA.repeat(3)).build())
+            .await;
+        assert_ok!(status, body);
+
+        let (status, body) = server.get("select count(*), sum(a) from t5").await;
+        assert_ok!(status, body);
+        assert_eq!(&body, "6\t-3\n");
+
+        // An incomplete block is an error once the body ends.
+        let truncated = [NATIVE_DATA, &NATIVE_DATA[..NATIVE_DATA.len() - 1]].concat();
+        let (status, body) = server
+            .get_response(QueryBuilder::new(sql).body(truncated).build())
+            .await;
+        assert_ne!(status, StatusCode::OK, "{}", body);
+        assert_error!(body, "Unexpected end of the Native data");
+    }
+
+    // The Values of the same rows.
+    {
+        let (status, body) = server
+            .post(
+                "insert into table t1 format Values",
+                "(3, 'it''s', NULL, [4, 5]), (4, 'a\\tb', 2.5, [])",
+            )
+            .await;
+        assert_ok!(status, body);
+
+        let (status, body) = server
+            .get("select a, b, c, d from t1 where a > 2 order by a")
+            .await;
+        assert_ok!(status, body);
+        assert_eq!(&body, "3\tit's\t\\N\t[4,5]\n4\ta\\tb\t2.5\t[]\n");
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_insert_streaming_body() -> PoemResult<()> {
+    let _fixture = TestFixture::setup().await.unwrap();
+
+    let server = Server::new().await;
+    {
+        let (status, body) = server
+            .post("create table t1(a int, b string null)", "")
+            .await;
+        assert_ok!(status, body);
+    }
+
+    let rows = 10000;
+    let cases = [
+        (
+            "CSV",
+            (0..rows)
+                .map(|i| format!("{i},\"v{i}\"\n"))
+                .collect::<String>(),
+        ),
+        ("TSV", (0..rows).map(|i| format!("{i}\tv{i}\n")).collect()),
+        (
+            "JSONEachRow",
+            (0..rows)
+                .map(|i| format!("{{\"a\": {i}, \"b\": \"v{i}\"}}\n"))
+                .collect(),
+        ),
+    ];
+    for (i, (format, data)) in cases.into_iter().enumerate() {
+        // The chunks do not end at the row boundaries.
+        let chunks = data
+            .into_bytes()
+            .chunks(1000)
+            .map(|chunk| Ok::<_, std::io::Error>(chunk.to_vec()))
+            .collect::<Vec<_>>();
+        let body = Body::from_bytes_stream(futures::stream::iter(chunks));
+        let sql = format!("insert into table t1 format {format}");
+        let (status, body) = server
+            .get_response(QueryBuilder::new(&sql).body(body).build())
+            .await;
+        assert_ok!(status, body);
+
+        let (status, body) = server
+            .get("select count(*), count(distinct a), max(b) from t1")
+            .await;
+        assert_ok!(status, body);
+        assert_eq!(body, format!("{}\t{rows}\tv9999\n", rows * (i + 1)));
+    }
+
+    // The statement is in the first line of the body.
+    {
+        let data = (0..rows).map(|i| format!("{i},v{i}\n")).collect::<String>();
+        let chunks = format!("insert into table t1 format CSV\n{data}")
+            .into_bytes()
+            .chunks(1000)
+            .map(|chunk| Ok::<_, std::io::Error>(chunk.to_vec()))
+            .collect::<Vec<_>>();
+        let body = Body::from_bytes_stream(futures::stream::iter(chunks));
+        let (status, body) = server
+            .get_response(QueryBuilder::new("").body(body).build())
+            .await;
+        assert_ok!(status, body);
+
+        let (status, body) = server.get("select count(*) from t1").await;
+        assert_ok!(status, body);
+        assert_eq!(body, format!("{}\n", rows * 4));
+    }
+
+    // A malformed row fails the insert.
+    {
+        let data = format!("0,a\n{}x,b\n", "1,a\n".repeat(rows));
+        let (status, body) = server.post("insert into table t1 format CSV", &data).await;
+        assert_ne!(status, StatusCode::OK, "{}", body);
+
+        let (status, body) = server.get("select count(*) from t1").await;
+        assert_ok!(status, body);
+        assert_eq!(body, format!("{}\n", rows * 4));
+    }
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_settings() -> PoemResult<()> {
     let _fixture = TestFixture::setup().await.unwrap();
@@ -346,6 +589,115 @@ async fn test_federated() -> PoemResult<()> {
         assert_ok!(status, body);
         assert_eq!(&body, &(CLICKHOUSE_VERSION.to_string() + "\n"));
     }
+    {
+        let sql = "SET send_logs_level = 'warning'";
+        let (status, body) = server.get(sql).await;
+        assert_ok!(status, body);
+        assert_eq!(&body, "");
+    }
+    {
+        let sql = "SHOW SETTINGS LIKE 'max_block_size' FORMAT TSV";
+        let (status, body) = server.get(sql).await;
+        assert_ok!(status, body);
+        assert_eq!(&body, "max_block_size\tUInt64\t65536\n");
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_clickhouse_headers() -> PoemResult<()> {
+    let fixture = TestFixture::setup().await.unwrap();
+
+    let server = Server::new().await;
+    {
+        let (status, body) = server.get("create database db1").await;
+        assert_ok!(status, body);
+        let (status, body) = server.get("create user u1 identified by 'pass'").await;
+        assert_ok!(status, body);
+    }
+
+    // The database of the header.
+    {
+        let req = QueryBuilder::new("select database()")
+            .header("X-ClickHouse-Database", "db1")
+            .build();
+        let (status, body) = server.get_response(req).await;
+        assert_ok!(status, body);
+        assert_eq!(&body, "db1\n");
+    }
+
+    // The user of the headers is authenticated instead of the one of the basic auth.
+    {
+        let req = QueryBuilder::new("select current_user()")
+            .header("X-ClickHouse-User", "u1")
+            .header("X-ClickHouse-Key", "pass")
+            .build();
+        let (status, body) = server.get_response(req).await;
+        assert_ok!(status, body);
+        assert!(body.contains("u1"), "{}", body);
+
+        let req = QueryBuilder::new("select 1")
+            .header("X-ClickHouse-User", "u1")
+            .header("X-ClickHouse-Key", "wrong")
+            .build();
+        let (status, body) = server.get_response(req).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_error!(body, "wrong password");
+
+        let req = QueryBuilder::new("select 1")
+            .header("X-ClickHouse-User", "u1")
+            .build();
+        let (status, body) = server.get_response(req).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_error!(body, "password required");
+    }
+
+    // The id given by the client, and the progress of the query.
+    {
+        let req = QueryBuilder::new("select number from numbers(10)")
+            .query_id("clickhouse-query-1")
+            .build();
+        let response = server.endpoint.get_response(req).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let query_id = response.headers().get("X-ClickHouse-Query-Id").unwrap();
+        assert_eq!(query_id, "clickhouse-query-1");
+        let summary = response.headers().get("X-ClickHouse-Summary").unwrap();
+        let summary: serde_json::Value = serde_json::from_slice(summary.as_bytes()).unwrap();
+        assert_eq!(summary["read_rows"], "10");
+        assert_eq!(summary["read_bytes"], "80");
+        assert_eq!(summary["written_rows"], "0");
+    }
+
+    // The id of a running query is rejected.
+    {
+        let session = fixture
+            .new_session_with_type(SessionType::Dummy)
+            .await
+            .unwrap();
+        SessionManager::instance()
+            .try_upgrade_session(session.clone(), SessionType::ClickHouseHttpHandler)
+            .unwrap();
+        let ctx = session.create_query_context().await.unwrap();
+        ctx.set_id("clickhouse-query-2".to_string());
+
+        let req = QueryBuilder::new("select 1")
+            .query_id("clickhouse-query-2")
+            .build();
+        let (status, body) = server.get_response(req).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_error!(
+            body,
+            "Query with id = clickhouse-query-2 is already running"
+        );
+
+        drop(ctx);
+        let req = QueryBuilder::new("select 1")
+            .query_id("clickhouse-query-2")
+            .build();
+        let (status, body) = server.get_response(req).await;
+        assert_ok!(status, body);
+    }
 
     Ok(())
 }
@@ -355,6 +707,8 @@ struct QueryBuilder {
     body: Option<Body>,
     settings: HashMap<String, String>,
     compress: bool,
+    query_id: Option<String>,
+    headers: Vec<(String, String)>,
 }
 
 impl QueryBuilder {
@@ -364,6 +718,8 @@ impl QueryBuilder {
             body: None,
             settings: HashMap::new(),
             compress: false,
+            query_id: None,
+            headers: vec![],
         }
     }
 
@@ -382,12 +738,27 @@ impl QueryBuilder {
         Self { settings, ..self }
     }
 
+    pub fn query_id(self, query_id: &str) -> Self {
+        Self {
+            query_id: Some(query_id.to_string()),
+            ..self
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
     pub fn build(self) -> Request {
         let mut uri = url::form_urlencoded::Serializer::new(String::new());
         uri.append_pair("query", &self.sql);
         if self.compress {
             uri.append_pair("compress", "1");
         }
+        if let Some(query_id) = &self.query_id {
+            uri.append_pair("query_id", query_id);
+        }
         for (k, v) in self.settings.iter() {
             uri.append_pair(k, v);
         }
@@ -401,11 +772,14 @@ impl QueryBuilder {
         };
 
         let basic = Authorization::basic("root", "");
-        Request::builder()
+        let mut builder = Request::builder()
             .uri(uri)
             .method(method)
-            .typed_header(basic)
-            .body(body)
+            .typed_header(basic);
+        for (name, value) in &self.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        builder.body(body)
     }
 }
 
@@ -34,6 +34,7 @@ use databend_query::servers::http::v1::make_final_uri;
 use databend_query::servers::http::v1::make_page_uri;
 use databend_query::servers::http::v1::make_state_uri;
 use databend_query::servers::http::v1::query_route;
+use databend_query::servers::http::v1::BatchResponse;
 use databend_query::servers::http::v1::ExecuteStateKind;
 use databend_query::servers::http::v1::HttpSessionConf;
 use databend_query::servers::http::v1::QueryError;
@@ -744,6 +745,73 @@ async fn test_insert() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn test_insert_check_constraints() -> Result<()> {
+    let _fixture = TestFixture::setup().await?;
+
+    let sqls = vec![
+        (
+            "create table t(a int, constraint a_positive check (a > 0))",
+            None,
+        ),
+        ("insert into t(a) values (1),(2)", None),
+        ("insert into t(a) values (3),(-3)", Some(1304u16)),
+        ("update t set a = -1 where a = 1", Some(1304u16)),
+        (
+            "alter table t add constraint a_small check (a < 2)",
+            Some(1304u16),
+        ),
+    ];
+
+    for (sql, error_code) in sqls {
+        let json = serde_json::json!({"sql": sql.to_string(), "pagination": {"wait_time_secs": 3}});
+        let reply = TestHttpQueryRequest::new(json).fetch_total().await?;
+        assert_eq!(reply.last().1.error.map(|e| e.code), error_code, "{sql}");
+    }
+
+    let json = serde_json::json!({"sql": "select * from t order by a", "pagination": {"wait_time_secs": 3}});
+    let reply = TestHttpQueryRequest::new(json).fetch_total().await?;
+    assert_eq!(reply.data(), vec![vec!["1"], vec!["2"]]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_loose_arithmetic_coercion() -> Result<()> {
+    let _fixture = TestFixture::setup().await?;
+
+    let sql = "select a * 2, a * 1.5 from (values ('x'), ('4'), (''), (null)) t(a)";
+
+    let json = serde_json::json!({"sql": sql, "pagination": {"wait_time_secs": 3}});
+    let reply = TestHttpQueryRequest::new(json).fetch_total().await?;
+    assert_eq!(reply.last().1.error.map(|e| e.code), Some(1006));
+
+    // 'x' and '' become NULL in both columns, the NULL row is not counted.
+    let json = serde_json::json!({"sql": sql, "pagination": {"wait_time_secs": 3}, "session": {"settings": {"loose_arithmetic_coercion": "1"}}});
+    let reply = TestHttpQueryRequest::new(json).fetch_total().await?;
+    let result = reply.last().1;
+    assert!(result.error.is_none(), "{:?}", result.error);
+    assert_eq!(result.stats.progresses.loose_coercion_failures, 4);
+    let warnings = reply
+        .resps
+        .iter()
+        .flat_map(|(_, resp)| resp.warnings.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(warnings, vec![
+        "4 strings in arithmetic are not numbers and became NULL".to_string()
+    ]);
+
+    // The literal is constant folded while planning, it is counted only once.
+    let json = serde_json::json!({"sql": "select 'abc' + 1 from numbers(3)", "pagination": {"wait_time_secs": 3}, "session": {"settings": {"loose_arithmetic_coercion": "1"}}});
+    let reply = TestHttpQueryRequest::new(json).fetch_total().await?;
+    let result = reply.last().1;
+    assert!(result.error.is_none(), "{:?}", result.error);
+    assert_eq!(reply.data().len(), 3);
+    assert_eq!(result.stats.progresses.loose_coercion_failures, 1);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn test_query_log() -> Result<()> {
     let _fixture = TestFixture::setup().await?;
@@ -865,6 +933,142 @@ async fn test_query_log_killed() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn test_batch() -> Result<()> {
+    let _fixture = TestFixture::setup().await?;
+    let ep = create_endpoint().await?;
+
+    for (table, continue_on_error) in [("t1", false), ("t2", true)] {
+        let sql = format!(
+            "create table {table}(a int); insert into t_missing values(1); insert into {table} values(1),(2)"
+        );
+        let json = json!({"sql": sql, "continue_on_error": continue_on_error});
+        let (status, result) = post_batch_to_endpoint(&ep, &json).await?;
+        assert_eq!(status, StatusCode::OK, "{:?}", result);
+        assert!(result.error.is_none(), "{:?}", result);
+
+        let expected_len = if continue_on_error { 3 } else { 2 };
+        assert_eq!(result.results.len(), expected_len, "{:?}", result);
+        assert_eq!(result.results[0].state, ExecuteStateKind::Succeeded);
+        assert_eq!(result.results[1].state, ExecuteStateKind::Failed);
+        assert_eq!(result.results[1].sql, "insert into t_missing values(1)");
+        assert_eq!(
+            result.results[1].error.as_ref().unwrap().code,
+            ErrorCode::UnknownTable("").code(),
+            "{:?}",
+            result
+        );
+        if continue_on_error {
+            assert_eq!(result.results[2].state, ExecuteStateKind::Succeeded);
+            assert!(result.results[2].error.is_none(), "{:?}", result);
+        }
+
+        let sql = format!("select count(*) from {table}");
+        let (status, result) = post_sql_to_endpoint(&ep, &sql, 3).await?;
+        assert_eq!(status, StatusCode::OK, "{:?}", result);
+        let expected = if continue_on_error { "2" } else { "0" };
+        assert_eq!(result.data[0][0].as_str().unwrap(), expected);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_batch_split() -> Result<()> {
+    let _fixture = TestFixture::setup().await?;
+    let ep = create_endpoint().await?;
+
+    let sql = "select 'a;b' as s; -- ; not a statement\n use system ;; select name from tables where name = 'tables';";
+    let (status, result) = post_batch_to_endpoint(&ep, &json!({ "sql": sql })).await?;
+    assert_eq!(status, StatusCode::OK, "{:?}", result);
+    assert!(result.error.is_none(), "{:?}", result);
+    let statements = result
+        .results
+        .iter()
+        .map(|r| r.sql.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(statements, vec![
+        "select 'a;b' as s",
+        "use system",
+        "select name from tables where name = 'tables'",
+    ]);
+    assert_eq!(result.results[0].data, vec![vec![json!("a;b")]]);
+    // The statements share the session.
+    assert_eq!(result.results[2].data, vec![vec![json!("tables")]]);
+    assert_eq!(result.session.unwrap().database, Some("system".to_string()));
+
+    let json = json!({ "sql": "select 1; select 'unterminated" });
+    let (status, result) = post_batch_to_endpoint(&ep, &json).await?;
+    assert_eq!(status, StatusCode::OK, "{:?}", result);
+    assert!(result.error.is_some(), "{:?}", result);
+    assert!(result.results.is_empty(), "{:?}", result);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_batch_query_log() -> Result<()> {
+    let _fixture = TestFixture::setup().await?;
+    let ep = create_endpoint().await?;
+
+    let json = json!({ "sql": "select 1; select 2" });
+    let (status, result) = post_batch_to_endpoint(&ep, &json).await?;
+    assert_eq!(status, StatusCode::OK, "{:?}", result);
+    assert!(result.error.is_none(), "{:?}", result);
+    let mut query_ids = result
+        .results
+        .iter()
+        .map(|r| r.id.clone())
+        .collect::<Vec<_>>();
+    query_ids.sort();
+    assert_eq!(query_ids.len(), 2);
+    assert_ne!(query_ids[0], query_ids[1]);
+
+    let extra = json!({ "batch_id": result.batch_id }).to_string();
+    let sql = format!(
+        "select query_id from system.query_log where log_type = 2 and extra = '{extra}' order by query_id"
+    );
+    let (status, result) = post_sql_to_endpoint(&ep, &sql, 3).await?;
+    assert_eq!(status, StatusCode::OK, "{:?}", result);
+    let logged = result
+        .data
+        .iter()
+        .map(|row| row[0].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(logged, query_ids);
+
+    Ok(())
+}
+
+async fn post_batch_to_endpoint(
+    ep: &EndpointType,
+    json: &serde_json::Value,
+) -> Result<(StatusCode, BatchResponse)> {
+    let body = serde_json::to_vec(&json)?;
+    let basic = headers::Authorization::basic("root", "");
+    let req = Request::builder()
+        .uri("/v1/query/batch".parse().unwrap())
+        .method(Method::POST)
+        .header(header::CONTENT_TYPE, "application/json")
+        .typed_header(basic)
+        .body(body);
+
+    let response = ep
+        .call(req)
+        .await
+        .map_err(|e| ErrorCode::Internal(e.to_string()))?;
+    let status = response.status();
+    let body = response.into_body().into_string().await.unwrap();
+    let result = serde_json::from_str::<BatchResponse>(&body);
+    assert!(
+        result.is_ok(),
+        "body ='{}', result='{:?}'",
+        &body,
+        result.err()
+    );
+    Ok((status, result?))
+}
+
 async fn check_response(response: Response) -> Result<(StatusCode, QueryResponse)> {
     let status = response.status();
     let body = response.into_body().into_string().await.unwrap();
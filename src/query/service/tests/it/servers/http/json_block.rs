@@ -12,23 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use databend_common_arrow::arrow::bitmap::Bitmap;
 use databend_common_exception::Result;
-use databend_common_expression::types::nullable::NullableColumn;
-use databend_common_expression::types::number::Float64Type;
-use databend_common_expression::types::number::Int32Type;
-use databend_common_expression::types::BooleanType;
-use databend_common_expression::types::DataType;
-use databend_common_expression::types::DateType;
-use databend_common_expression::types::NumberDataType;
-use databend_common_expression::types::StringType;
-use databend_common_expression::Column;
 use databend_common_expression::DataBlock;
-use databend_common_expression::DataField;
 use databend_common_expression::DataSchemaRefExt;
-use databend_common_expression::FromData;
 use databend_common_io::prelude::FormatSettings;
 use databend_query::servers::http::v1::json_block::JsonBlock;
+use databend_query::test_kits::BlockFixture;
 use pretty_assertions::assert_eq;
 use serde::Serialize;
 use serde_json::to_value;
@@ -40,47 +29,27 @@ where T: Serialize {
 }
 
 fn test_data_block(is_nullable: bool) -> Result<()> {
-    let schema = match is_nullable {
-        false => DataSchemaRefExt::create(vec![
-            DataField::new("c1", DataType::Number(NumberDataType::Int32)),
-            DataField::new("c2", DataType::String),
-            DataField::new("c3", DataType::Boolean),
-            DataField::new("c4", DataType::Number(NumberDataType::Float64)),
-            DataField::new("c5", DataType::Date),
-        ]),
-        true => DataSchemaRefExt::create(vec![
-            DataField::new_nullable("c1", DataType::Number(NumberDataType::Int32)),
-            DataField::new_nullable("c2", DataType::String),
-            DataField::new_nullable("c3", DataType::Boolean),
-            DataField::new_nullable("c4", DataType::Number(NumberDataType::Float64)),
-            DataField::new_nullable("c5", DataType::Date),
-        ]),
+    let fixture = match is_nullable {
+        false => BlockFixture::new()
+            .col_i32("c1", [1, 2, 3])
+            .col_str("c2", ["a", "b", "c"])
+            .col_bool("c3", [true, true, false])
+            .col_f64("c4", [1.1, 2.2, 3.3])
+            .col_date("c5", ["1970-01-02", "1970-01-03", "1970-01-04"]),
+        true => BlockFixture::new()
+            .col_i32_opt("c1", [Some(1), Some(2), Some(3)])
+            .col_str_opt("c2", [Some("a"), Some("b"), Some("c")])
+            .col_bool_opt("c3", [Some(true), Some(true), Some(false)])
+            .col_f64_opt("c4", [Some(1.1), Some(2.2), Some(3.3)])
+            .col_date_opt("c5", [
+                Some("1970-01-02"),
+                Some("1970-01-03"),
+                Some("1970-01-04"),
+            ]),
     };
 
-    let mut columns = vec![
-        Int32Type::from_data(vec![1, 2, 3]),
-        StringType::from_data(vec!["a", "b", "c"]),
-        BooleanType::from_data(vec![true, true, false]),
-        Float64Type::from_data(vec![1.1, 2.2, 3.3]),
-        DateType::from_data(vec![1_i32, 2_i32, 3_i32]),
-    ];
-
-    if is_nullable {
-        columns = columns
-            .iter()
-            .map(|c| {
-                Column::Nullable(Box::new(NullableColumn {
-                    column: c.clone(),
-                    validity: Bitmap::new_constant(true, c.len()),
-                }))
-            })
-            .collect();
-    }
-
-    let block = DataBlock::new_from_columns(columns);
-
     let format = FormatSettings::default();
-    let json_block = JsonBlock::new(schema, &block, &format)?;
+    let json_block = JsonBlock::new(fixture.schema(), &fixture.build(), &format)?;
     let expect = vec![
         vec![val("1"), val("a"), val("1"), val("1.1"), val("1970-01-02")],
         vec![val("2"), val("b"), val("1"), val("2.2"), val("1970-01-03")],
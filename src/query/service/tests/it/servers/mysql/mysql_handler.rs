@@ -28,11 +28,13 @@ use databend_query::servers::MySQLHandler;
 use databend_query::servers::MySQLTlsConfig;
 use databend_query::test_kits::ConfigBuilder;
 use databend_query::test_kits::TestFixture;
+use mysql_async::consts::ColumnType;
 use mysql_async::prelude::FromRow;
 use mysql_async::prelude::Queryable;
 use mysql_async::FromRowError;
 use mysql_async::Row;
 use mysql_async::SslOpts;
+use mysql_async::Value;
 use tokio::sync::Barrier;
 
 use crate::tests::tls_constants::*;
@@ -54,6 +56,154 @@ async fn test_generic_code_with_on_query() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn test_date_and_timestamp_columns() -> Result<()> {
+    let _fixture = TestFixture::setup().await?;
+
+    let tcp_keepalive_timeout_secs = 120;
+    let mut handler = MySQLHandler::create(tcp_keepalive_timeout_secs, MySQLTlsConfig::default())?;
+
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port(), false).await?;
+
+    let sql = "SELECT '1969-07-20'::DATE, '2021-04-01 12:30:00.5'::TIMESTAMP, \
+        NULL::DATE, '1900-01-01 00:00:00'::TIMESTAMP";
+    let mut result = connection
+        .query_iter(sql)
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Query failed")?;
+
+    let column_types = result
+        .columns()
+        .unwrap()
+        .iter()
+        .map(|column| column.column_type())
+        .collect::<Vec<_>>();
+    assert_eq!(column_types, vec![
+        ColumnType::MYSQL_TYPE_DATE,
+        ColumnType::MYSQL_TYPE_DATETIME,
+        ColumnType::MYSQL_TYPE_DATE,
+        ColumnType::MYSQL_TYPE_DATETIME,
+    ]);
+
+    let rows: Vec<Row> = result
+        .collect()
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Read rows failed")?;
+    assert_eq!(rows.len(), 1);
+    let values = (0..4).map(|i| rows[0][i].clone()).collect::<Vec<_>>();
+    assert_eq!(values, vec![
+        Value::Bytes(b"1969-07-20".to_vec()),
+        Value::Bytes(b"2021-04-01 12:30:00.500000".to_vec()),
+        Value::NULL,
+        Value::Bytes(b"1900-01-01 00:00:00.000000".to_vec()),
+    ]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_prepared_statement_with_params() -> Result<()> {
+    let _fixture = TestFixture::setup().await?;
+
+    let tcp_keepalive_timeout_secs = 120;
+    let mut handler = MySQLHandler::create(tcp_keepalive_timeout_secs, MySQLTlsConfig::default())?;
+
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port(), false).await?;
+
+    let statement = connection
+        .prep("SELECT ? + 1, ?, ?, ?")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Prepare failed")?;
+    assert_eq!(statement.num_params(), 4);
+
+    let rows: Vec<Row> = connection
+        .exec(
+            &statement,
+            (
+                41i64,
+                "data'bend",
+                None::<i64>,
+                Value::Date(2021, 4, 1, 12, 30, 0, 500000),
+            ),
+        )
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute failed")?;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<i64, _>(0), Some(42));
+    assert_eq!(rows[0].get::<String, _>(1), Some("data'bend".to_string()));
+    assert_eq!(rows[0].get::<Option<i64>, _>(2), Some(None));
+    assert_eq!(
+        rows[0].get::<Value, _>(3),
+        Some(Value::Date(2021, 4, 1, 12, 30, 0, 500000))
+    );
+
+    // The statement is bound again to the parameters of each execution.
+    let rows: Vec<Row> = connection
+        .exec(
+            &statement,
+            (-2i64, "", 7i64, Value::Date(1969, 7, 20, 0, 0, 0, 0)),
+        )
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute failed")?;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<i64, _>(0), Some(-1));
+    assert_eq!(rows[0].get::<String, _>(1), Some("".to_string()));
+    assert_eq!(rows[0].get::<Option<i64>, _>(2), Some(Some(7)));
+    assert_eq!(
+        rows[0].get::<Value, _>(3),
+        Some(Value::Date(1969, 7, 20, 0, 0, 0, 0))
+    );
+
+    connection
+        .close(statement)
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Close failed")?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_prepared_statement_count_limit() -> Result<()> {
+    let _fixture = TestFixture::setup().await?;
+
+    let tcp_keepalive_timeout_secs = 120;
+    let mut handler = MySQLHandler::create(tcp_keepalive_timeout_secs, MySQLTlsConfig::default())?;
+
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port(), false).await?;
+    connection
+        .query_drop("SET max_prepared_stmt_count = 1")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Set failed")?;
+
+    let statement = connection
+        .prep("SELECT ?")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Prepare failed")?;
+    match connection.prep("SELECT ? + 1").await {
+        Err(mysql_async::Error::Server(error)) => assert_eq!(error.code, 1461),
+        other => panic!("expect ER_MAX_PREPARED_STMT_COUNT_REACHED, got {:?}", other),
+    }
+
+    // A closed statement no longer counts.
+    connection
+        .close(statement)
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Close failed")?;
+    let rows: Vec<Row> = connection
+        .exec("SELECT ? + 1", (1.5f64,))
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute failed")?;
+    assert_eq!(rows[0].get::<f64, _>(0), Some(2.5));
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn test_connect_with_tls() -> Result<()> {
     let _fixture = TestFixture::setup().await?;
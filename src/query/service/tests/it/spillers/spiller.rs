@@ -16,17 +16,16 @@ use databend_common_base::base::tokio;
 use databend_common_catalog::table_context::TableContext;
 use databend_common_exception::Result;
 use databend_common_expression::types::DataType;
-use databend_common_expression::types::Int32Type;
 use databend_common_expression::types::NumberDataType;
 use databend_common_expression::types::NumberScalar;
 use databend_common_expression::DataBlock;
-use databend_common_expression::FromData;
 use databend_common_expression::ScalarRef;
 use databend_common_pipeline_core::query_spill_prefix;
 use databend_common_storage::DataOperator;
 use databend_query::spillers::Spiller;
 use databend_query::spillers::SpillerConfig;
 use databend_query::spillers::SpillerType;
+use databend_query::test_kits::BlockFixture;
 use databend_query::test_kits::TestFixture;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
@@ -41,10 +40,10 @@ async fn test_spill_with_partition() -> Result<()> {
     let mut spiller = Spiller::create(ctx, operator, spiller_config, SpillerType::HashJoinBuild)?;
 
     // Generate data block: two columns, type is i32, 100 rows
-    let data = DataBlock::new_from_columns(vec![
-        Int32Type::from_data((0..100).collect::<Vec<_>>()),
-        Int32Type::from_data((1..101).collect::<Vec<_>>()),
-    ]);
+    let data = BlockFixture::new()
+        .col_i32("c0", 0..100)
+        .col_i32("c1", 1..101)
+        .build();
 
     let res = spiller.spill_with_partition(0_u8, data).await;
 
@@ -33,6 +33,7 @@ use databend_common_catalog::query_kind::QueryKind;
 use databend_common_catalog::runtime_filter_info::RuntimeFilterInfo;
 use databend_common_catalog::statistics::data_cache_statistics::DataCacheMetrics;
 use databend_common_catalog::table::Table;
+use databend_common_catalog::table_context::FinishedStageInfo;
 use databend_common_catalog::table_context::MaterializedCtesBlocks;
 use databend_common_catalog::table_context::ProcessInfo;
 use databend_common_catalog::table_context::StageAttachment;
@@ -506,6 +507,10 @@ impl TableContext for CtxDelegation {
         todo!()
     }
 
+    fn attach_query_lineage(&self, _lineage: serde_json::Value) {
+        todo!()
+    }
+
     fn get_fragment_id(&self) -> usize {
         todo!()
     }
@@ -744,6 +749,9 @@ impl TableContext for CtxDelegation {
     fn get_queries_profile(&self) -> HashMap<String, Vec<Arc<Profile>>> {
         todo!()
     }
+    fn get_finished_stages(&self) -> Vec<FinishedStageInfo> {
+        todo!()
+    }
     fn add_merge_status(&self, _merge_status: MergeStatus) {
         todo!()
     }
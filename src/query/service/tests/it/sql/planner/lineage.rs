@@ -0,0 +1,83 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_base::base::tokio;
+use databend_common_exception::Result;
+use databend_common_sql::Planner;
+use databend_query::test_kits::TestFixture;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_query_log_lineage() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    fixture.create_default_database().await?;
+    let db = fixture.default_db_name();
+    fixture
+        .execute_command(&format!("create table {db}.t1(a int, b int, c int)"))
+        .await?;
+    fixture
+        .execute_command(&format!("create table {db}.t2(s int, m int)"))
+        .await?;
+
+    let insert = format!("insert into {db}.t2 select a + b, max(c) from {db}.t1 group by a + b");
+
+    // The lineage is attached only if it's enabled.
+    {
+        let ctx = fixture.new_query_ctx().await?;
+        let mut planner = Planner::new(ctx.clone());
+        planner.plan_sql(&insert).await?;
+        assert!(ctx.get_query_lineage().is_none());
+    }
+
+    {
+        let ctx = fixture.new_query_ctx().await?;
+        ctx.get_settings()
+            .set_setting("enable_query_log_lineage".to_string(), "1".to_string())?;
+        let mut planner = Planner::new(ctx.clone());
+        planner.plan_sql(&insert).await?;
+
+        let t1 = format!("{db}.t1");
+        let t2 = format!("{db}.t2");
+        let expect = json!([
+            {
+                "target_table": t2,
+                "column": "s",
+                "sources": [
+                    { "table": t1, "column": "a", "kind": "transform" },
+                    { "table": t1, "column": "b", "kind": "transform" },
+                ],
+            },
+            {
+                "target_table": t2,
+                "column": "m",
+                "sources": [
+                    { "table": t1, "column": "c", "kind": "aggregation" },
+                ],
+            },
+        ]);
+        assert_eq!(ctx.get_query_lineage(), Some(expect));
+    }
+
+    // Queries don't write columns, no lineage is attached.
+    {
+        let ctx = fixture.new_query_ctx().await?;
+        ctx.get_settings()
+            .set_setting("enable_query_log_lineage".to_string(), "1".to_string())?;
+        let mut planner = Planner::new(ctx.clone());
+        planner.plan_sql(&format!("select a from {db}.t1")).await?;
+        assert!(ctx.get_query_lineage().is_none());
+    }
+
+    Ok(())
+}
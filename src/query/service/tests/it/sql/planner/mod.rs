@@ -13,5 +13,7 @@
 // limitations under the License.
 
 mod builders;
+mod lineage;
 mod optimizer;
+mod query_fingerprint;
 mod semantic;
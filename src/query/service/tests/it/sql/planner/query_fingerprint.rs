@@ -0,0 +1,60 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_sql::normalize_query;
+use databend_common_sql::query_fingerprint;
+
+#[test]
+fn test_normalize_query() {
+    let cases = [
+        (
+            "select a from t where b = 1 and c = 'x'",
+            "SELECT a FROM t WHERE b = ? AND c = ?",
+        ),
+        (
+            "SELECT  A FROM T -- comment\n WHERE b > 1.5e3",
+            "SELECT a FROM t WHERE b > ?",
+        ),
+        (
+            "select * from t where a in (1, 2, 3)",
+            "SELECT * FROM t WHERE a IN ( ? )",
+        ),
+        (
+            r#"select "A", `b` from t limit 10"#,
+            r#"SELECT "A" , `b` FROM t LIMIT ?"#,
+        ),
+        (
+            "insert into t values (1, 'a'), (2, 'b')",
+            "INSERT INTO t VALUES",
+        ),
+        ("insert into t select 1", "INSERT INTO t SELECT ?"),
+    ];
+    for (query, expect) in cases {
+        assert_eq!(normalize_query(query), expect, "query: {query}");
+    }
+}
+
+#[test]
+fn test_query_fingerprint() {
+    let fingerprint = query_fingerprint("SELECT number FROM numbers(10) WHERE number > 5");
+    assert_eq!(fingerprint.len(), 64);
+    assert_eq!(
+        fingerprint,
+        query_fingerprint("select number from numbers(100) where number > 50")
+    );
+    assert_ne!(
+        fingerprint,
+        query_fingerprint("SELECT number FROM numbers(10) WHERE number < 5")
+    );
+}
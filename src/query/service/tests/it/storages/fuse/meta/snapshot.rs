@@ -15,11 +15,19 @@
 use std::collections::HashMap;
 use std::ops::Add;
 
+use databend_common_exception::ErrorCode;
 use databend_common_expression::TableSchema;
 use databend_storages_common_table_meta::meta::testing::StatisticsV0;
 use databend_storages_common_table_meta::meta::testing::TableSnapshotV1;
 use databend_storages_common_table_meta::meta::testing::TableSnapshotV2;
+use databend_storages_common_table_meta::meta::SegmentInfo;
+use databend_storages_common_table_meta::meta::SnapshotVersion;
 use databend_storages_common_table_meta::meta::TableSnapshot;
+use databend_storages_common_table_meta::meta::TableSnapshotStatistics;
+use databend_storages_common_table_meta::meta::Versioned;
+use databend_storages_common_table_meta::meta::SEGMENT_FORMAT_VERSIONS;
+use databend_storages_common_table_meta::meta::SNAPSHOT_FORMAT_VERSIONS;
+use databend_storages_common_table_meta::meta::TABLE_SNAPSHOT_STATISTICS_FORMAT_VERSIONS;
 use uuid::Uuid;
 
 fn default_snapshot() -> TableSnapshot {
@@ -107,3 +115,23 @@ fn test_snapshot_v1_to_v4() {
     assert_eq!(v4.snapshot_id, v1.snapshot_id);
     assert_eq!(v4.timestamp, v1.timestamp);
 }
+
+#[test]
+fn test_snapshot_version_newer_than_supported() {
+    assert!(SnapshotVersion::try_from(TableSnapshot::VERSION).is_ok());
+
+    let res = SnapshotVersion::try_from(TableSnapshot::VERSION + 1);
+    assert_eq!(res.unwrap_err().code(), ErrorCode::STORAGE_UNSUPPORTED);
+}
+
+#[test]
+fn test_format_versions_latest() {
+    // The latest supported version is the one written.
+    assert_eq!(SEGMENT_FORMAT_VERSIONS.latest(), SegmentInfo::VERSION);
+    assert_eq!(SNAPSHOT_FORMAT_VERSIONS.latest(), TableSnapshot::VERSION);
+    assert_eq!(
+        TABLE_SNAPSHOT_STATISTICS_FORMAT_VERSIONS.latest(),
+        TableSnapshotStatistics::VERSION
+    );
+    assert_eq!(SNAPSHOT_FORMAT_VERSIONS.supported_list(), "0, 1, 2, 3, 4");
+}
@@ -32,6 +32,7 @@ use databend_common_catalog::query_kind::QueryKind;
 use databend_common_catalog::runtime_filter_info::RuntimeFilterInfo;
 use databend_common_catalog::statistics::data_cache_statistics::DataCacheMetrics;
 use databend_common_catalog::table::Table;
+use databend_common_catalog::table_context::FinishedStageInfo;
 use databend_common_catalog::table_context::MaterializedCtesBlocks;
 use databend_common_catalog::table_context::ProcessInfo;
 use databend_common_catalog::table_context::StageAttachment;
@@ -481,6 +482,10 @@ impl TableContext for CtxDelegation {
         todo!()
     }
 
+    fn attach_query_lineage(&self, _lineage: serde_json::Value) {
+        todo!()
+    }
+
     fn get_fragment_id(&self) -> usize {
         todo!()
     }
@@ -694,6 +699,10 @@ impl TableContext for CtxDelegation {
         todo!()
     }
 
+    fn get_finished_stages(&self) -> Vec<FinishedStageInfo> {
+        todo!()
+    }
+
     fn add_merge_status(&self, _merge_status: MergeStatus) {
         todo!()
     }
@@ -32,6 +32,8 @@ use databend_common_meta_app::storage::StorageFsConfig;
 use databend_common_meta_app::storage::StorageParams;
 use databend_common_meta_app::storage::StorageS3Config;
 use databend_common_sql::executor::table_read_plan::ToReadDataSourcePlan;
+use databend_common_sql::query_fingerprint;
+use databend_common_sql::Planner;
 use databend_common_storages_system::BuildOptionsTable;
 use databend_common_storages_system::CachesTable;
 use databend_common_storages_system::CatalogsTable;
@@ -42,12 +44,16 @@ use databend_common_storages_system::ContributorsTable;
 use databend_common_storages_system::CreditsTable;
 use databend_common_storages_system::DatabasesTable;
 use databend_common_storages_system::EnginesTable;
+use databend_common_storages_system::FormatVersionsTable;
 use databend_common_storages_system::FunctionsTable;
 use databend_common_storages_system::MetricsTable;
+use databend_common_storages_system::QueryProfileRecord;
+use databend_common_storages_system::QueryProfileStore;
 use databend_common_storages_system::RolesTable;
 use databend_common_storages_system::TracingTable;
 use databend_common_storages_system::UsersTable;
 use databend_common_users::UserApiProvider;
+use databend_query::interpreters::InterpreterFactory;
 use databend_query::sessions::QueryContext;
 use databend_query::sessions::TableContext;
 use databend_query::stream::ReadDataBlockStream;
@@ -275,6 +281,19 @@ async fn test_engines_table() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_format_versions_table() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let ctx = fixture.new_query_ctx().await?;
+
+    let table = FormatVersionsTable::create(1);
+    let mut mint = Mint::new("tests/it/storages/testdata");
+    let file = &mut mint.new_goldenfile("format_versions_table.txt").unwrap();
+    run_table_tests(file, ctx, table).await?;
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_functions_table() -> Result<()> {
     let fixture = TestFixture::setup().await?;
@@ -445,3 +464,117 @@ async fn test_caches_table() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_query_profile_store() {
+    let record = |fingerprint: &str, duration_ms: u64, is_error: bool| QueryProfileRecord {
+        fingerprint: fingerprint.to_string(),
+        query_text: format!("{fingerprint} {duration_ms}"),
+        duration_ms,
+        scan_rows: 10,
+        scan_bytes: 100,
+        is_error,
+        event_time: duration_ms as i64,
+    };
+
+    let store = QueryProfileStore::create(2);
+    for duration_ms in 1..=100 {
+        store.record(record("a", duration_ms, duration_ms % 10 == 0));
+    }
+    let entries = store.entries();
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.example_query, "a 100");
+    assert_eq!(entry.executions, 100);
+    assert_eq!(entry.errors, 10);
+    assert_eq!(entry.total_duration_ms, 5050);
+    assert_eq!(entry.mean_duration_ms(), 50.5);
+    assert!((93..=97).contains(&entry.p95_duration_ms()));
+    assert_eq!(entry.total_scan_rows, 1000);
+    assert_eq!(entry.total_scan_bytes, 10000);
+    assert_eq!(entry.last_seen, 100);
+
+    // The least recently seen fingerprint is evicted.
+    store.record(record("b", 1, false));
+    store.record(record("a", 1, false));
+    store.record(record("c", 1, false));
+    let mut fingerprints = store
+        .entries()
+        .into_iter()
+        .map(|entry| entry.fingerprint)
+        .collect::<Vec<_>>();
+    fingerprints.sort();
+    assert_eq!(fingerprints, vec!["a", "c"]);
+    assert_eq!(store.evictions(), 1);
+
+    store.reset();
+    assert!(store.entries().is_empty());
+}
+
+async fn execute_with_query_str(fixture: &TestFixture, query: &str) -> Result<()> {
+    let ctx = fixture.new_query_ctx().await?;
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, extras) = planner.plan_sql(query).await?;
+    ctx.attach_query_str(plan.kind(), extras.statement.to_mask_sql());
+    let interpreter = InterpreterFactory::get(ctx.clone(), &plan).await?;
+    interpreter
+        .execute(ctx)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_query_profile_table() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let store = QueryProfileStore::instance();
+    store.reset();
+
+    // The queries only differ in the literals are aggregated under one fingerprint.
+    for i in 0..20 {
+        let query = format!("SELECT number FROM numbers({}) WHERE number > {i}", 100 + i);
+        execute_with_query_str(&fixture, &query).await?;
+    }
+    execute_with_query_str(&fixture, "SELECT count(*) FROM numbers(10)").await?;
+
+    let fingerprint = query_fingerprint("select number from numbers(1) where number > 0");
+    let entries = store.entries();
+    let entry = entries
+        .iter()
+        .find(|entry| entry.fingerprint == fingerprint)
+        .unwrap();
+    assert_eq!(entry.executions, 20);
+    assert_eq!(entry.errors, 0);
+    assert!(entry.example_query.contains("numbers(119)"));
+    assert!(entry.p95_duration_ms() <= entry.total_duration_ms);
+
+    // Distinct shapes get distinct rows.
+    let count_fingerprint = query_fingerprint("SELECT count(*) FROM numbers(10)");
+    assert_ne!(fingerprint, count_fingerprint);
+    let entry = entries
+        .iter()
+        .find(|entry| entry.fingerprint == count_fingerprint)
+        .unwrap();
+    assert_eq!(entry.executions, 1);
+
+    // The statistics are exposed in system.query_profile.
+    let stream = fixture
+        .execute_query(&format!(
+            "SELECT executions FROM system.query_profile WHERE fingerprint = '{fingerprint}'"
+        ))
+        .await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    assert_eq!(
+        blocks.iter().map(|block| block.num_rows()).sum::<usize>(),
+        1
+    );
+
+    execute_with_query_str(&fixture, "SYSTEM RESET QUERY PROFILE").await?;
+    assert!(store
+        .entries()
+        .iter()
+        .all(|entry| entry.fingerprint != fingerprint));
+
+    Ok(())
+}
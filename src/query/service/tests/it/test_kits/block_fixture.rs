@@ -0,0 +1,215 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_base::base::tokio;
+use databend_common_exception::Result;
+use databend_common_expression::block_debug::assert_blocks_eq;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::NumberScalar;
+use databend_common_expression::types::StringType;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_expression::Scalar;
+use databend_common_expression::Value;
+use databend_query::test_kits::array_column;
+use databend_query::test_kits::BlockFixture;
+use databend_query::test_kits::TableFixture;
+use databend_query::test_kits::TestFixture;
+use futures_util::TryStreamExt;
+
+fn int32() -> DataType {
+    DataType::Number(NumberDataType::Int32)
+}
+
+fn row(block: &DataBlock, index: usize) -> Vec<String> {
+    block
+        .columns()
+        .iter()
+        .map(|entry| entry.value.index(index).unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_block_fixture_types() {
+    let fixture = BlockFixture::new()
+        .col_i32("i32", [1, -2])
+        .col_i64_opt("i64", [None, Some(-4)])
+        .col_u64("u64", [5, 6])
+        .col_f64_opt("f64", [Some(1.5), None])
+        .col_bool("bool", [true, false])
+        .col_str_opt("str", [Some("x"), None])
+        .col_date("date", ["2021-01-01", "1969-12-31"])
+        .col_timestamp_opt("ts", [Some("2021-01-01 01:02:03.000004"), None])
+        .col_timestamp("ts2", ["1970-01-01 00:00:01", "1970-01-01 00:00:00"]);
+
+    let data_types = fixture
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.data_type().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(data_types, vec![
+        "Int32",
+        "Int64 NULL",
+        "UInt64",
+        "Float64 NULL",
+        "Boolean",
+        "String NULL",
+        "Date",
+        "Timestamp NULL",
+        "Timestamp",
+    ]);
+
+    let block = fixture.build();
+    assert_eq!(block.num_rows(), 2);
+    assert_eq!(row(&block, 0), vec![
+        "1",
+        "NULL",
+        "5",
+        "1.5",
+        "true",
+        "'x'",
+        "'2021-01-01'",
+        "'2021-01-01 01:02:03.000004'",
+        "'1970-01-01 00:00:01.000000'",
+    ]);
+    assert_eq!(row(&block, 1), vec![
+        "-2",
+        "-4",
+        "6",
+        "NULL",
+        "false",
+        "NULL",
+        "'1969-12-31'",
+        "NULL",
+        "'1970-01-01 00:00:00.000000'",
+    ]);
+}
+
+#[test]
+fn test_block_fixture_nested() {
+    let nested = array_column(int32(), [
+        Int32Type::from_data(vec![1]),
+        Int32Type::from_data(vec![2, 3]),
+    ]);
+    let fixture = BlockFixture::new()
+        .col_array("array", int32(), [
+            Int32Type::from_data(vec![1, 2]),
+            Int32Type::from_data(vec![]),
+        ])
+        .col_array("nested", DataType::Array(Box::new(int32())), [
+            nested,
+            array_column(int32(), []),
+        ])
+        .col_tuple("tuple", vec![
+            Int32Type::from_data(vec![1, 2]),
+            StringType::from_data(vec!["a", "b"]),
+        ])
+        .scalars(
+            "scalars",
+            DataType::Array(Box::new(int32())).wrap_nullable(),
+            [Scalar::Null, Scalar::Array(Int32Type::from_data(vec![7]))],
+        );
+
+    let data_types = fixture
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.data_type().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(data_types, vec![
+        "Array(Int32)",
+        "Array(Array(Int32))",
+        "Tuple(Int32, String)",
+        "Array(Int32) NULL",
+    ]);
+
+    let block = fixture.build();
+    assert_eq!(row(&block, 0), vec![
+        "[1, 2]",
+        "[[1], [2, 3]]",
+        "(1, 'a')",
+        "NULL"
+    ]);
+    assert_eq!(row(&block, 1), vec!["[]", "[]", "(2, 'b')", "[7]"]);
+}
+
+#[test]
+fn test_block_fixture_constant() {
+    let fixture = BlockFixture::new().num_rows(2).constant(
+        "c",
+        int32(),
+        Scalar::Number(NumberScalar::Int32(7)),
+    );
+    let block = fixture.build();
+    assert_eq!(block.num_rows(), 2);
+    assert_eq!(
+        block.get_by_offset(0).value,
+        Value::Scalar(Scalar::Number(NumberScalar::Int32(7)))
+    );
+
+    assert_eq!(fixture.expected_lines(), vec![
+        "+----------+",
+        "| Column 0 |",
+        "+----------+",
+        "| 7        |",
+        "| 7        |",
+        "+----------+",
+    ]);
+}
+
+#[test]
+#[should_panic(expected = "column `b` has 1 rows, but the block has 2 rows")]
+fn test_block_fixture_rows_mismatch() {
+    BlockFixture::new().col_i32("a", [1, 2]).col_i32("b", [1]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_fixture() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    fixture.create_default_database().await?;
+
+    TableFixture::new(
+        "t",
+        &BlockFixture::new()
+            .col_i32_opt("a", [Some(3), None])
+            .col_str("s", ["c", "n"]),
+    )
+    .with_block(
+        &BlockFixture::new()
+            .col_i32_opt("a", [Some(1)])
+            .col_str("s", ["a"]),
+    )
+    .create(&fixture)
+    .await?;
+
+    let query = format!(
+        "SELECT a, s FROM {}.t ORDER BY s",
+        fixture.default_db_name()
+    );
+    let blocks = fixture
+        .execute_query(&query)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let expected = BlockFixture::new()
+        .col_i32_opt("a", [Some(1), Some(3), None])
+        .col_str("s", ["a", "c", "n"])
+        .expected_lines();
+    assert_blocks_eq(expected.iter().map(|s| s.as_str()).collect(), &blocks);
+    Ok(())
+}
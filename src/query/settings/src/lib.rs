@@ -16,6 +16,7 @@ mod settings;
 mod settings_default;
 mod settings_getter_setter;
 mod settings_global;
+mod settings_validation;
 
 pub use settings::ChangeValue;
 pub use settings::ScopeLevel;
@@ -175,12 +175,54 @@ impl DefaultSettings {
                     value: UserSettingValue::UInt64(512 * 1024),
                     desc: "Sets the maximum byte size of data pages that can be read from storage in a single I/O operation.",
                     mode: SettingMode::Both,
-                    range: None,
+                    range: Some(SettingRange::Numeric(1..=u64::MAX)),
                 }),
                 ("flight_client_timeout", DefaultSettingValue {
                     value: UserSettingValue::UInt64(60),
                     desc: "Sets the maximum time in seconds that a flight client request can be processed.",
                     mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(1..=u64::MAX)),
+                }),
+                ("flight_stream_retained_blocks", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Sets the number of the latest blocks retained by a node for each fragment stream, so that the stream can be resumed when the connection is lost, 0 disables resuming.",
+                    mode: SettingMode::Both,
+                    range: None,
+                }),
+                ("flight_stream_max_retries", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(3),
+                    desc: "Sets the maximum number of times a resumable fragment stream is reconnected.",
+                    mode: SettingMode::Both,
+                    range: None,
+                }),
+                ("drain_node_timeout", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(600),
+                    desc: "Sets the maximum time in seconds that SYSTEM DRAIN NODE waits for the running queries before reporting the node drained.",
+                    mode: SettingMode::Both,
+                    range: None,
+                }),
+                ("max_stream_lag_blocks", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Sets the maximum number of blocks sent to another node but not yet consumed by it before the query fails, 0 means unlimited.",
+                    mode: SettingMode::Both,
+                    range: None,
+                }),
+                ("max_stream_lag_seconds", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Sets the maximum time in seconds that a block sent to another node may wait to be consumed before the query fails, 0 means unlimited.",
+                    mode: SettingMode::Both,
+                    range: None,
+                }),
+                ("stream_idle_timeout_secs", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Sets the maximum time in seconds that another node may stop polling a stream whose blocks are not consumed before the stream is cancelled, 0 means unlimited.",
+                    mode: SettingMode::Both,
+                    range: None,
+                }),
+                ("exchange_spilling_bytes_threshold", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Sets the maximum amount of memory in bytes that the blocks sent to another node but not yet consumed can use before spilling them to the local disk, 0 disables spilling.",
+                    mode: SettingMode::Both,
                     range: None,
                 }),
                 ("http_handler_result_timeout_secs", DefaultSettingValue {
@@ -193,17 +235,41 @@ impl DefaultSettings {
                     mode: SettingMode::Both,
                     range: None,
                 }),
+                ("http_batch_max_statements", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(100),
+                    desc: "Sets the maximum number of statements in a http query batch.",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(1..=u64::MAX)),
+                }),
+                ("max_prepared_stmt_count", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(16382),
+                    desc: "Sets the maximum number of prepared statements of a MySQL session.",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=u64::MAX)),
+                }),
+                ("http_batch_timeout_secs", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(600),
+                    desc: "Sets the maximum time in seconds to execute a http query batch.",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(1..=u64::MAX)),
+                }),
+                ("http_batch_max_result_rows", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(10000),
+                    desc: "Sets the maximum number of result rows of each statement in a http query batch.",
+                    mode: SettingMode::Both,
+                    range: None,
+                }),
                 ("storage_read_buffer_size", DefaultSettingValue {
                     value: UserSettingValue::UInt64(1024 * 1024),
                     desc: "Sets the byte size of the buffer used for reading data into memory.",
                     mode: SettingMode::Both,
-                    range: None,
+                    range: Some(SettingRange::Numeric(1..=u64::MAX)),
                 }),
                 ("input_read_buffer_size", DefaultSettingValue {
                     value: UserSettingValue::UInt64(4 * 1024 * 1024),
                     desc: "Sets the memory size in bytes allocated to the buffer used by the buffered reader to read data from storage.",
                     mode: SettingMode::Both,
-                    range: None,
+                    range: Some(SettingRange::Numeric(1..=u64::MAX)),
                 }),
                 ("enable_new_copy_for_text_formats", DefaultSettingValue {
                     value: UserSettingValue::UInt64(1),
@@ -269,7 +335,7 @@ impl DefaultSettings {
                     value: UserSettingValue::UInt64(0),
                     desc: "Sets the maximum memory ratio in bytes that hash join can use before spilling data to storage during query execution, 0 is unlimited",
                     mode: SettingMode::Both,
-                    range: None,
+                    range: Some(SettingRange::Numeric(0..=100)),
                 }),
                 ("join_spilling_bytes_threshold_per_proc", DefaultSettingValue {
                     value: UserSettingValue::UInt64(0),
@@ -281,7 +347,7 @@ impl DefaultSettings {
                     value: UserSettingValue::UInt64(4),
                     desc: "Set the number of partitions for join spilling. Default value is 4, it means 2^4 partitions.",
                     mode: SettingMode::Both,
-                    range: None,
+                    range: Some(SettingRange::Numeric(0..=8)),
                 }),
                 ("enable_bloom_runtime_filter", DefaultSettingValue {
                     value: UserSettingValue::UInt64(1),
@@ -319,6 +385,12 @@ impl DefaultSettings {
                     mode: SettingMode::Both,
                     range: None,
                 }),
+                ("broadcast_join_hint_max_rows", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(10_000_000),
+                    desc: "Sets the maximum estimated rows of a table broadcast by the BROADCAST_JOIN hint, the hint is ignored for larger tables.",
+                    mode: SettingMode::Both,
+                    range: None,
+                }),
                 ("storage_fetch_part_num", DefaultSettingValue {
                     value: UserSettingValue::UInt64(2),
                     desc: "Sets the number of partitions that are fetched in parallel from storage during query execution.",
@@ -355,6 +427,18 @@ impl DefaultSettings {
                     mode: SettingMode::Both,
                     range: Some(SettingRange::Numeric(0..=1)),
                 }),
+                ("explain_misestimation_factor", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(10),
+                    desc: "Sets the factor by which the actual rows of a plan node must differ from the estimated rows to be marked as mis-estimated by EXPLAIN ANALYZE VERBOSE.",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(1..=u64::MAX)),
+                }),
+                ("enable_query_log_lineage", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Enables recording the column lineage of INSERT SELECT and CREATE TABLE AS SELECT statements in the query log.",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=1)),
+                }),
                 ("enable_query_result_cache", DefaultSettingValue {
                     value: UserSettingValue::UInt64(0),
                     desc: "Enables caching query results to improve performance for identical queries.",
@@ -380,6 +464,30 @@ impl DefaultSettings {
                     mode: SettingMode::Both,
                     range: None,
                 }),
+                ("enable_fragment_result_cache", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Enables caching the results of deterministic query fragments on each node to reuse them in later queries.",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=1)),
+                }),
+                ("fragment_result_cache_max_bytes", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(256 * 1024 * 1024),
+                    desc: "Sets the maximum byte size of the cached fragment results on each node, the oldest results are evicted first.",
+                    mode: SettingMode::Both,
+                    range: None,
+                }),
+                ("fragment_result_cache_ttl_secs", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(3600), // seconds
+                    desc: "Sets the time-to-live (TTL) in seconds for cached fragment results.",
+                    mode: SettingMode::Both,
+                    range: None,
+                }),
+                ("query_random_seed", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Sets the seed of rand() to make the results reproducible, 0 to use a random seed per call.",
+                    mode: SettingMode::Both,
+                    range: None,
+                }),
                 ("enable_hive_parquet_predict_pushdown", DefaultSettingValue {
                     value: UserSettingValue::UInt64(1),
                     desc: "Enable hive parquet predict pushdown  by setting this variable to 1, default value: 1",
@@ -390,7 +498,7 @@ impl DefaultSettings {
                     value: UserSettingValue::UInt64(16384),
                     desc: "the max number of rows each read from parquet to databend processor",
                     mode: SettingMode::Both,
-                    range: None,
+                    range: Some(SettingRange::Numeric(1..=u64::MAX)),
                 }),
                 ("aggregate_spilling_bytes_threshold_per_proc", DefaultSettingValue {
                     value: UserSettingValue::UInt64(0),
@@ -539,6 +647,12 @@ impl DefaultSettings {
                     mode: SettingMode::Both,
                     range: Some(SettingRange::Numeric(0..=1)),
                 }),
+                ("fuse_late_materialization_threshold", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(10),
+                    desc: "Sets the maximum percentage of rows passing the pushed-down filter for a fuse parquet block to be filtered before its other columns are materialized, 0 disables it.",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=100)),
+                }),
                 ("enable_replace_into_partitioning", DefaultSettingValue {
                     value: UserSettingValue::UInt64(1),
                     desc: "Enables partitioning for replace-into statement (if table has cluster keys).",
@@ -672,6 +786,12 @@ impl DefaultSettings {
                     mode: SettingMode::Both,
                     range: Some(SettingRange::Numeric(0..=1)),
                 }),
+                ("loose_arithmetic_coercion", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Coerce the strings in arithmetic to Float64 as MySQL does, the strings which are not numbers become NULL",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=1)),
+                }),
                 ("cost_factor_hash_table_per_row", DefaultSettingValue {
                     value: UserSettingValue::UInt64(COST_FACTOR_HASH_TABLE_PER_ROW),
                     desc: "Cost factor of building hash table for a data row",
@@ -214,6 +214,34 @@ impl Settings {
         self.try_get_u64("flight_client_timeout")
     }
 
+    pub fn get_flight_stream_retained_blocks(&self) -> Result<usize> {
+        Ok(self.try_get_u64("flight_stream_retained_blocks")? as usize)
+    }
+
+    pub fn get_flight_stream_max_retries(&self) -> Result<usize> {
+        Ok(self.try_get_u64("flight_stream_max_retries")? as usize)
+    }
+
+    pub fn get_drain_node_timeout(&self) -> Result<u64> {
+        self.try_get_u64("drain_node_timeout")
+    }
+
+    pub fn get_max_stream_lag_blocks(&self) -> Result<usize> {
+        Ok(self.try_get_u64("max_stream_lag_blocks")? as usize)
+    }
+
+    pub fn get_max_stream_lag_seconds(&self) -> Result<u64> {
+        self.try_get_u64("max_stream_lag_seconds")
+    }
+
+    pub fn get_stream_idle_timeout_secs(&self) -> Result<u64> {
+        self.try_get_u64("stream_idle_timeout_secs")
+    }
+
+    pub fn get_exchange_spilling_bytes_threshold(&self) -> Result<usize> {
+        Ok(self.try_get_u64("exchange_spilling_bytes_threshold")? as usize)
+    }
+
     // Get storage read buffer size.
     pub fn get_storage_read_buffer_size(&self) -> Result<u64> {
         self.try_get_u64("storage_read_buffer_size")
@@ -293,6 +321,10 @@ impl Settings {
         Ok(self.try_get_u64("enforce_broadcast_join")? != 0)
     }
 
+    pub fn get_broadcast_join_hint_max_rows(&self) -> Result<u64> {
+        self.try_get_u64("broadcast_join_hint_max_rows")
+    }
+
     pub fn get_sql_dialect(&self) -> Result<Dialect> {
         match self.try_get_string("sql_dialect")?.to_lowercase().as_str() {
             "hive" => Ok(Dialect::Hive),
@@ -329,6 +361,14 @@ impl Settings {
         Ok(self.try_get_u64("hide_options_in_show_create_table")? != 0)
     }
 
+    pub fn get_explain_misestimation_factor(&self) -> Result<u64> {
+        self.try_get_u64("explain_misestimation_factor")
+    }
+
+    pub fn get_enable_query_log_lineage(&self) -> Result<bool> {
+        Ok(self.try_get_u64("enable_query_log_lineage")? != 0)
+    }
+
     pub fn get_enable_query_result_cache(&self) -> Result<bool> {
         Ok(self.try_get_u64("enable_query_result_cache")? != 0)
     }
@@ -341,6 +381,22 @@ impl Settings {
         self.try_get_u64("http_handler_result_timeout_secs")
     }
 
+    pub fn get_http_batch_max_statements(&self) -> Result<usize> {
+        Ok(self.try_get_u64("http_batch_max_statements")? as usize)
+    }
+
+    pub fn get_max_prepared_stmt_count(&self) -> Result<usize> {
+        Ok(self.try_get_u64("max_prepared_stmt_count")? as usize)
+    }
+
+    pub fn get_http_batch_timeout_secs(&self) -> Result<u64> {
+        self.try_get_u64("http_batch_timeout_secs")
+    }
+
+    pub fn get_http_batch_max_result_rows(&self) -> Result<usize> {
+        Ok(self.try_get_u64("http_batch_max_result_rows")? as usize)
+    }
+
     pub fn get_query_result_cache_ttl_secs(&self) -> Result<u64> {
         self.try_get_u64("query_result_cache_ttl_secs")
     }
@@ -349,6 +405,25 @@ impl Settings {
         Ok(self.try_get_u64("query_result_cache_allow_inconsistent")? != 0)
     }
 
+    pub fn get_enable_fragment_result_cache(&self) -> Result<bool> {
+        Ok(self.try_get_u64("enable_fragment_result_cache")? != 0)
+    }
+
+    pub fn get_fragment_result_cache_max_bytes(&self) -> Result<usize> {
+        Ok(self.try_get_u64("fragment_result_cache_max_bytes")? as usize)
+    }
+
+    pub fn get_fragment_result_cache_ttl_secs(&self) -> Result<u64> {
+        self.try_get_u64("fragment_result_cache_ttl_secs")
+    }
+
+    pub fn get_query_random_seed(&self) -> Result<Option<u64>> {
+        match self.try_get_u64("query_random_seed")? {
+            0 => Ok(None),
+            seed => Ok(Some(seed)),
+        }
+    }
+
     pub fn get_aggregate_spilling_bytes_threshold_per_proc(&self) -> Result<usize> {
         Ok(self.try_get_u64("aggregate_spilling_bytes_threshold_per_proc")? as usize)
     }
@@ -490,6 +565,10 @@ impl Settings {
         self.try_set_u64("fuse_read_use_parquet2", u64::from(val))
     }
 
+    pub fn get_fuse_late_materialization_threshold(&self) -> Result<u64> {
+        self.try_get_u64("fuse_late_materialization_threshold")
+    }
+
     pub fn get_enable_replace_into_partitioning(&self) -> Result<bool> {
         Ok(self.try_get_u64("enable_replace_into_partitioning")? != 0)
     }
@@ -599,6 +678,10 @@ impl Settings {
         self.try_set_u64("disable_variant_check", u64::from(val))
     }
 
+    pub fn get_loose_arithmetic_coercion(&self) -> Result<bool> {
+        Ok(self.try_get_u64("loose_arithmetic_coercion")? != 0)
+    }
+
     pub fn get_cost_factor_hash_table_per_row(&self) -> Result<u64> {
         self.try_get_u64("cost_factor_hash_table_per_row")
     }
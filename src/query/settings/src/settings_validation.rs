@@ -0,0 +1,65 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_meta_app::principal::UserSettingValue;
+
+use crate::settings::Settings;
+
+impl Settings {
+    /// Validates the effective settings before a query starts.
+    ///
+    /// The ranges are checked again because the values from the config file or the metasrv
+    /// are not checked when they are loaded, then the interactions between the settings
+    /// are checked, the error names both settings and the violated relation.
+    pub fn check_interactions(&self) -> Result<()> {
+        for item in self.into_iter() {
+            if let (Some(range), UserSettingValue::UInt64(value)) = (&item.range, &item.user_value)
+            {
+                range.is_within_numeric_range(*value).map_err(|err| {
+                    ErrorCode::WrongValueForVariable(format!("{}: {}", item.name, err.message()))
+                })?;
+            }
+        }
+
+        let max_memory_usage = self.get_max_memory_usage()?;
+        if max_memory_usage != 0 {
+            let spilling_thresholds = [
+                (
+                    "join_spilling_bytes_threshold_per_proc",
+                    self.get_join_spilling_bytes_threshold_per_proc()?,
+                ),
+                (
+                    "aggregate_spilling_bytes_threshold_per_proc",
+                    self.get_aggregate_spilling_bytes_threshold_per_proc()?,
+                ),
+                (
+                    "sort_spilling_bytes_threshold_per_proc",
+                    self.get_sort_spilling_bytes_threshold_per_proc()?,
+                ),
+            ];
+            for (name, threshold) in spilling_thresholds {
+                if threshold as u64 > max_memory_usage {
+                    return Err(ErrorCode::WrongValueForVariable(format!(
+                        "{} ({}) must not be greater than max_memory_usage ({})",
+                        name, threshold, max_memory_usage
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
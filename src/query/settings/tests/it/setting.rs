@@ -14,7 +14,11 @@
 
 use databend_common_config::GlobalConfig;
 use databend_common_config::InnerConfig;
+use databend_common_meta_app::principal::UserSettingValue;
 use databend_common_settings::Settings;
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_set_settings() {
@@ -197,3 +201,142 @@ async fn test_set_data_retention_time_in_days_from_config() {
         assert_eq!(expect, format!("{}", result.unwrap_err()));
     }
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_set_pathological_settings() {
+    let settings = Settings::create("test".to_string());
+
+    let cases = [
+        (
+            "max_block_size",
+            "0",
+            "Value 0 is not within the range [1, 18446744073709551615]",
+        ),
+        (
+            "max_threads",
+            "10000000",
+            "Value 10000000 is not within the range [1, 1024]",
+        ),
+        (
+            "join_spilling_partition_bits",
+            "64",
+            "Value 64 is not within the range [0, 8]",
+        ),
+        (
+            "join_spilling_memory_ratio",
+            "101",
+            "Value 101 is not within the range [0, 100]",
+        ),
+        (
+            "flight_client_timeout",
+            "0",
+            "Value 0 is not within the range [1, 18446744073709551615]",
+        ),
+        (
+            "input_read_buffer_size",
+            "0",
+            "Value 0 is not within the range [1, 18446744073709551615]",
+        ),
+    ];
+
+    for (name, value, message) in cases {
+        let result = settings.set_setting(name.to_string(), value.to_string());
+        let expect = format!("WrongValueForVariable. Code: 2803, Text = {}.", message);
+        assert_eq!(expect, format!("{}", result.unwrap_err()), "{}", name);
+    }
+
+    // The rejected values are not applied.
+    settings.check_interactions().unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_check_settings_interactions() {
+    let settings = Settings::create("test".to_string());
+    settings.check_interactions().unwrap();
+
+    // Each setting is valid, but the spilling threshold is larger than the memory limit.
+    settings
+        .set_setting("max_memory_usage".to_string(), "1024".to_string())
+        .unwrap();
+    settings
+        .set_setting(
+            "join_spilling_bytes_threshold_per_proc".to_string(),
+            "2048".to_string(),
+        )
+        .unwrap();
+    let result = settings.check_interactions();
+    let expect = "WrongValueForVariable. Code: 2803, Text = join_spilling_bytes_threshold_per_proc (2048) must not be greater than max_memory_usage (1024).";
+    assert_eq!(expect, format!("{}", result.unwrap_err()));
+
+    // 0 means unlimited memory.
+    settings
+        .set_setting("max_memory_usage".to_string(), "0".to_string())
+        .unwrap();
+    settings.check_interactions().unwrap();
+
+    settings
+        .set_setting("max_memory_usage".to_string(), "1024".to_string())
+        .unwrap();
+    settings
+        .set_setting(
+            "join_spilling_bytes_threshold_per_proc".to_string(),
+            "1024".to_string(),
+        )
+        .unwrap();
+    settings
+        .set_setting(
+            "sort_spilling_bytes_threshold_per_proc".to_string(),
+            "4096".to_string(),
+        )
+        .unwrap();
+    let result = settings.check_interactions();
+    let expect = "WrongValueForVariable. Code: 2803, Text = sort_spilling_bytes_threshold_per_proc (4096) must not be greater than max_memory_usage (1024).";
+    assert_eq!(expect, format!("{}", result.unwrap_err()));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_check_settings_from_config() {
+    // Init.
+    let thread_name = std::thread::current().name().unwrap().to_string();
+    databend_common_base::base::GlobalInstance::init_testing(&thread_name);
+
+    // The values from the config file are not checked when they are loaded.
+    {
+        let mut conf = InnerConfig::default();
+        conf.query
+            .settings
+            .insert("max_block_size".to_string(), UserSettingValue::UInt64(0));
+        GlobalConfig::init(&conf).unwrap();
+    }
+
+    let settings = Settings::create("test".to_string());
+    let result = settings.check_interactions();
+    let expect = "WrongValueForVariable. Code: 2803, Text = max_block_size: Value 0 is not within the range [1, 18446744073709551615].";
+    assert_eq!(expect, format!("{}", result.unwrap_err()));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_check_random_settings() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let names = Settings::create("test".to_string())
+        .into_iter()
+        .filter(|item| matches!(item.default_value, UserSettingValue::UInt64(_)))
+        .map(|item| item.name)
+        .collect::<Vec<_>>();
+
+    for _ in 0..100 {
+        let settings = Settings::create("test".to_string());
+        for name in &names {
+            let value = match rng.gen_range(0..5) {
+                0 => 0,
+                1 => 1,
+                2 => u64::MAX,
+                3 => rng.gen_range(0..=1024),
+                _ => rng.gen(),
+            };
+            // The values out of range are rejected, the others must not break the validation.
+            let _ = settings.set_setting(name.clone(), value.to_string());
+        }
+        let _ = settings.check_interactions();
+    }
+}
@@ -81,7 +81,8 @@ impl BlockOperator {
             }
 
             BlockOperator::Project { projection } => {
-                let mut result = DataBlock::new(vec![], input.num_rows());
+                let mut result =
+                    DataBlock::new(vec![], input.num_rows()).with_optional_origin(input.origin());
                 for index in projection {
                     result.add_column(input.get_by_offset(*index).clone());
                 }
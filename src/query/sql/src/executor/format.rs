@@ -16,6 +16,7 @@ use std::collections::HashMap;
 
 use databend_common_ast::ast::FormatTreeNode;
 use databend_common_base::runtime::profile::get_statistics_desc;
+use databend_common_base::runtime::profile::ProfileStatisticsName;
 use databend_common_catalog::plan::PartStatistics;
 use databend_common_exception::Result;
 use databend_common_expression::DataSchemaRef;
@@ -66,6 +67,28 @@ impl PhysicalPlan {
         profs: HashMap<u32, PlanProfile>,
     ) -> Result<FormatTreeNode<String>> {
         let metadata = metadata.read().clone();
+        let profs = FormatProfiles {
+            profs: &profs,
+            misestimation_factor: None,
+        };
+        to_format_tree(self, &metadata, &profs)
+    }
+
+    /// Format the plan with the profiles collected by `EXPLAIN ANALYZE VERBOSE`.
+    ///
+    /// The actual output rows of each node are shown beside the estimated rows, nodes whose
+    /// actual rows differ from the estimation by more than `misestimation_factor` are marked.
+    pub fn format_with_estimation(
+        &self,
+        metadata: MetadataRef,
+        profs: HashMap<u32, PlanProfile>,
+        misestimation_factor: f64,
+    ) -> Result<FormatTreeNode<String>> {
+        let metadata = metadata.read().clone();
+        let profs = FormatProfiles {
+            profs: &profs,
+            misestimation_factor: Some(misestimation_factor),
+        };
         to_format_tree(self, &metadata, &profs)
     }
 
@@ -179,7 +202,7 @@ impl PhysicalPlan {
 fn to_format_tree(
     plan: &PhysicalPlan,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     match plan {
         PhysicalPlan::TableScan(plan) => table_scan_to_format_tree(plan, metadata, profs),
@@ -238,13 +261,21 @@ fn to_format_tree(
     }
 }
 
+/// Profiles of the plan nodes to be attached to the format tree.
+struct FormatProfiles<'a> {
+    profs: &'a HashMap<u32, PlanProfile>,
+    /// Compare the actual rows with the estimated rows if set.
+    misestimation_factor: Option<f64>,
+}
+
 /// Helper function to add profile info to the format tree.
 fn append_profile_info(
     children: &mut Vec<FormatTreeNode<String>>,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
     plan_id: u32,
+    stat_info: Option<&PlanStatsInfo>,
 ) {
-    if let Some(prof) = profs.get(&plan_id) {
+    if let Some(prof) = profs.profs.get(&plan_id) {
         for (_, desc) in get_statistics_desc().iter() {
             if prof.statistics[desc.index] != 0 {
                 children.push(FormatTreeNode::new(format!(
@@ -254,7 +285,40 @@ fn append_profile_info(
                 )));
             }
         }
+
+        if let (Some(factor), Some(info)) = (profs.misestimation_factor, stat_info) {
+            let actual_rows = prof.statistics[ProfileStatisticsName::OutputRows as usize];
+            children.extend(estimation_to_format_tree(info, actual_rows, factor));
+        }
+    }
+}
+
+fn estimation_to_format_tree(
+    info: &PlanStatsInfo,
+    actual_rows: usize,
+    misestimation_factor: f64,
+) -> Vec<FormatTreeNode<String>> {
+    let ratio = estimation_ratio(info.estimated_rows, actual_rows);
+    let mut items = vec![
+        FormatTreeNode::new(format!("actual rows: {actual_rows}")),
+        FormatTreeNode::new(format!("estimation ratio: {ratio:.2}")),
+    ];
+    if is_misestimated(ratio, misestimation_factor) {
+        items.push(FormatTreeNode::new("mis-estimated: true".to_string()));
     }
+    items
+}
+
+/// The ratio of the actual rows to the estimated rows. Both sides are counted as
+/// at least one row, so an empty input doesn't make the ratio infinite.
+fn estimation_ratio(estimated_rows: f64, actual_rows: usize) -> f64 {
+    (actual_rows as f64).max(1.0) / estimated_rows.max(1.0)
+}
+
+/// An estimation is off if the actual rows are more than `factor` times the
+/// estimated rows, or less than 1/`factor` of them.
+fn is_misestimated(ratio: f64, factor: f64) -> bool {
+    ratio > factor || ratio * factor < 1.0
 }
 
 fn copy_into_table(plan: &CopyIntoTable) -> Result<FormatTreeNode<String>> {
@@ -267,7 +331,7 @@ fn copy_into_table(plan: &CopyIntoTable) -> Result<FormatTreeNode<String>> {
 fn table_scan_to_format_tree(
     plan: &TableScan,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     if plan.table_index == DUMMY_TABLE_INDEX {
         return Ok(FormatTreeNode::new("DummyTableScan".to_string()));
@@ -367,7 +431,7 @@ fn table_scan_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, plan.stat_info.as_ref());
 
     Ok(FormatTreeNode::with_children(
         "TableScan".to_string(),
@@ -411,7 +475,7 @@ fn constant_table_scan_to_format_tree(
 fn filter_to_format_tree(
     plan: &Filter,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let filter = plan
         .predicates
@@ -431,7 +495,7 @@ fn filter_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, plan.stat_info.as_ref());
 
     children.push(to_format_tree(&plan.input, metadata, profs)?);
 
@@ -444,7 +508,7 @@ fn filter_to_format_tree(
 fn project_to_format_tree(
     plan: &Project,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let mut children = vec![FormatTreeNode::new(format!(
         "output columns: [{}]",
@@ -456,7 +520,7 @@ fn project_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, plan.stat_info.as_ref());
 
     children.push(to_format_tree(&plan.input, metadata, profs)?);
 
@@ -469,7 +533,7 @@ fn project_to_format_tree(
 fn eval_scalar_to_format_tree(
     plan: &EvalScalar,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     if plan.exprs.is_empty() {
         return to_format_tree(&plan.input, metadata, profs);
@@ -493,7 +557,7 @@ fn eval_scalar_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, plan.stat_info.as_ref());
 
     children.push(to_format_tree(&plan.input, metadata, profs)?);
 
@@ -518,7 +582,7 @@ pub fn pretty_display_agg_desc(desc: &AggregateFunctionDesc, metadata: &Metadata
 fn aggregate_expand_to_format_tree(
     plan: &AggregateExpand,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let sets = plan
         .grouping_sets
@@ -547,7 +611,7 @@ fn aggregate_expand_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, plan.stat_info.as_ref());
 
     children.push(to_format_tree(&plan.input, metadata, profs)?);
 
@@ -560,7 +624,7 @@ fn aggregate_expand_to_format_tree(
 fn aggregate_partial_to_format_tree(
     plan: &AggregatePartial,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let group_by = plan
         .group_by
@@ -588,7 +652,7 @@ fn aggregate_partial_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, plan.stat_info.as_ref());
 
     children.push(to_format_tree(&plan.input, metadata, profs)?);
 
@@ -601,7 +665,7 @@ fn aggregate_partial_to_format_tree(
 fn aggregate_final_to_format_tree(
     plan: &AggregateFinal,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let group_by = plan
         .group_by
@@ -639,7 +703,7 @@ fn aggregate_final_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, plan.stat_info.as_ref());
 
     children.push(to_format_tree(&plan.input, metadata, profs)?);
 
@@ -652,7 +716,7 @@ fn aggregate_final_to_format_tree(
 fn window_to_format_tree(
     plan: &Window,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let partition_by = plan
         .partition_by
@@ -693,7 +757,7 @@ fn window_to_format_tree(
         children.push(FormatTreeNode::new(format!("limit: [{limit}]")))
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, None);
 
     children.push(to_format_tree(&plan.input, metadata, profs)?);
 
@@ -706,7 +770,7 @@ fn window_to_format_tree(
 fn sort_to_format_tree(
     plan: &Sort,
     metadata: &Metadata,
-    prof_span_set: &HashMap<u32, PlanProfile>,
+    prof_span_set: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let sort_keys = plan
         .order_by
@@ -739,7 +803,12 @@ fn sort_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, prof_span_set, plan.plan_id);
+    append_profile_info(
+        &mut children,
+        prof_span_set,
+        plan.plan_id,
+        plan.stat_info.as_ref(),
+    );
 
     children.push(to_format_tree(&plan.input, metadata, prof_span_set)?);
 
@@ -749,7 +818,7 @@ fn sort_to_format_tree(
 fn limit_to_format_tree(
     plan: &Limit,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let mut children = vec![
         FormatTreeNode::new(format!(
@@ -769,7 +838,7 @@ fn limit_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, plan.stat_info.as_ref());
 
     children.push(to_format_tree(&plan.input, metadata, profs)?);
 
@@ -779,7 +848,7 @@ fn limit_to_format_tree(
 fn row_fetch_to_format_tree(
     plan: &RowFetch,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let table_schema = plan.source.source_info.schema();
     let projected_schema = plan.cols_to_fetch.project_schema(&table_schema);
@@ -801,7 +870,7 @@ fn row_fetch_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, plan.stat_info.as_ref());
 
     children.push(to_format_tree(&plan.input, metadata, profs)?);
 
@@ -814,7 +883,7 @@ fn row_fetch_to_format_tree(
 fn range_join_to_format_tree(
     plan: &RangeJoin,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let range_join_conditions = plan
         .conditions
@@ -860,7 +929,7 @@ fn range_join_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, plan.stat_info.as_ref());
 
     children.push(left_child);
     children.push(right_child);
@@ -877,7 +946,7 @@ fn range_join_to_format_tree(
 fn hash_join_to_format_tree(
     plan: &HashJoin,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let build_keys = plan
         .build_keys
@@ -920,7 +989,7 @@ fn hash_join_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, plan.stat_info.as_ref());
 
     children.push(build_child);
     children.push(probe_child);
@@ -934,7 +1003,7 @@ fn hash_join_to_format_tree(
 fn exchange_to_format_tree(
     plan: &Exchange,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     Ok(FormatTreeNode::with_children("Exchange".to_string(), vec![
         FormatTreeNode::new(format!(
@@ -961,7 +1030,7 @@ fn exchange_to_format_tree(
 fn union_all_to_format_tree(
     plan: &UnionAll,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let mut children = vec![FormatTreeNode::new(format!(
         "output columns: [{}]",
@@ -973,7 +1042,7 @@ fn union_all_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, plan.stat_info.as_ref());
 
     children.extend(vec![
         to_format_tree(&plan.left, metadata, profs)?,
@@ -1039,7 +1108,7 @@ fn exchange_source_to_format_tree(
 fn exchange_sink_to_format_tree(
     plan: &ExchangeSink,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let mut children = vec![FormatTreeNode::new(format!(
         "output columns: [{}]",
@@ -1062,7 +1131,7 @@ fn exchange_sink_to_format_tree(
 fn distributed_insert_to_format_tree(
     plan: &DistributedInsertSelect,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let children = vec![to_format_tree(&plan.input, metadata, profs)?];
 
@@ -1075,7 +1144,7 @@ fn distributed_insert_to_format_tree(
 fn recluster_sink_to_format_tree(
     plan: &ReclusterSink,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let children = vec![to_format_tree(&plan.input, metadata, profs)?];
     Ok(FormatTreeNode::with_children(
@@ -1087,7 +1156,7 @@ fn recluster_sink_to_format_tree(
 fn commit_sink_to_format_tree(
     plan: &CommitSink,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let children = vec![to_format_tree(&plan.input, metadata, profs)?];
     Ok(FormatTreeNode::with_children(
@@ -1099,7 +1168,7 @@ fn commit_sink_to_format_tree(
 fn project_set_to_format_tree(
     plan: &ProjectSet,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let mut children = vec![FormatTreeNode::new(format!(
         "output columns: [{}]",
@@ -1111,7 +1180,7 @@ fn project_set_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, plan.stat_info.as_ref());
 
     children.extend(vec![FormatTreeNode::new(format!(
         "set returning functions: {}",
@@ -1133,7 +1202,7 @@ fn project_set_to_format_tree(
 fn udf_to_format_tree(
     plan: &Udf,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let mut children = vec![FormatTreeNode::new(format!(
         "output columns: [{}]",
@@ -1145,7 +1214,7 @@ fn udf_to_format_tree(
         children.extend(items);
     }
 
-    append_profile_info(&mut children, profs, plan.plan_id);
+    append_profile_info(&mut children, profs, plan.plan_id, plan.stat_info.as_ref());
 
     children.extend(vec![FormatTreeNode::new(format!(
         "udf functions: {}",
@@ -1167,7 +1236,7 @@ fn udf_to_format_tree(
 fn materialized_cte_to_format_tree(
     plan: &MaterializedCte,
     metadata: &Metadata,
-    profs: &HashMap<u32, PlanProfile>,
+    profs: &FormatProfiles,
 ) -> Result<FormatTreeNode<String>> {
     let children = vec![
         FormatTreeNode::new(format!(
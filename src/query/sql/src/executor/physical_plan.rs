@@ -17,6 +17,7 @@ use std::collections::HashMap;
 use databend_common_catalog::plan::DataSourcePlan;
 use databend_common_exception::Result;
 use databend_common_expression::DataSchemaRef;
+use databend_common_expression::RemoteExpr;
 use databend_common_functions::BUILTIN_FUNCTIONS;
 use enum_as_inner::EnumAsInner;
 use itertools::Itertools;
@@ -542,6 +543,69 @@ impl PhysicalPlan {
             )
     }
 
+    /// Whether the plan produces the same result every time it runs over the same data.
+    ///
+    /// It's false if any expression of the plan is non-deterministic, or the plan reads
+    /// from sources other than table scans and constant scans, e.g. other fragments.
+    pub fn is_deterministic(&self) -> bool {
+        let deterministic = |expr: &RemoteExpr| {
+            expr.as_expr(&BUILTIN_FUNCTIONS)
+                .is_deterministic(&BUILTIN_FUNCTIONS)
+        };
+
+        let is_deterministic = match self {
+            PhysicalPlan::TableScan(scan) => scan
+                .source
+                .push_downs
+                .as_ref()
+                .map_or(true, |push_downs| push_downs.is_deterministic),
+            PhysicalPlan::Filter(plan) => plan.predicates.iter().all(deterministic),
+            PhysicalPlan::EvalScalar(plan) => {
+                plan.exprs.iter().all(|(expr, _)| deterministic(expr))
+            }
+            PhysicalPlan::ProjectSet(plan) => {
+                plan.srf_exprs.iter().all(|(expr, _)| deterministic(expr))
+            }
+            PhysicalPlan::HashJoin(plan) => plan
+                .build_keys
+                .iter()
+                .chain(plan.probe_keys.iter())
+                .chain(plan.non_equi_conditions.iter())
+                .all(deterministic),
+            PhysicalPlan::RangeJoin(plan) => plan
+                .conditions
+                .iter()
+                .flat_map(|condition| [&condition.left_expr, &condition.right_expr])
+                .chain(plan.other_conditions.iter())
+                .all(deterministic),
+            PhysicalPlan::ConstantTableScan(_)
+            | PhysicalPlan::Project(_)
+            | PhysicalPlan::AggregateExpand(_)
+            | PhysicalPlan::AggregatePartial(_)
+            | PhysicalPlan::AggregateFinal(_)
+            | PhysicalPlan::Window(_)
+            | PhysicalPlan::Sort(_)
+            | PhysicalPlan::Limit(_)
+            | PhysicalPlan::RowFetch(_)
+            | PhysicalPlan::UnionAll(_)
+            | PhysicalPlan::ExchangeSink(_) => true,
+            _ => false,
+        };
+
+        is_deterministic && self.children().all(|child| child.is_deterministic())
+    }
+
+    /// Ids of the fragments this plan reads from through `ExchangeSource`.
+    pub fn source_fragment_ids(&self) -> Vec<usize> {
+        match self {
+            PhysicalPlan::ExchangeSource(plan) => vec![plan.source_fragment_id],
+            _ => self
+                .children()
+                .flat_map(|child| child.source_fragment_ids())
+                .collect(),
+        }
+    }
+
     pub fn get_desc(&self) -> Result<String> {
         Ok(match self {
             PhysicalPlan::TableScan(v) => format!(
@@ -51,6 +51,8 @@ impl PhysicalPlanBuilder {
         mut required: ColumnSet,
     ) -> Result<PhysicalPlan> {
         // 1. Prune unused Columns.
+        // The input only reads the columns required by the parent, plus the columns used by
+        // the shuffle keys, which are evaluated by the scatter of the sender.
         if let crate::plans::Exchange::Hash(exprs) = exchange {
             for expr in exprs {
                 required.extend(expr.used_columns());
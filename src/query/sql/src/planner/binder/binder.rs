@@ -66,6 +66,7 @@ use crate::ColumnBinding;
 use crate::IndexType;
 use crate::MetadataRef;
 use crate::NameResolutionContext;
+use crate::QueryHint;
 use crate::ScalarExpr;
 use crate::TypeChecker;
 use crate::Visibility;
@@ -178,6 +179,25 @@ impl<'a> Binder {
         self.ctx.get_settings().set_batch_settings(&hint_settings)
     }
 
+    /// Resolves the optimizer hints, they are applied by the optimizer. The unknown
+    /// hints are ignored with a warning of the query.
+    pub(crate) fn bind_optimizer_hints(&mut self, hints: &Hint) {
+        for hint in &hints.optimizer_hints {
+            let args = hint.args.as_ref().map(|args| {
+                args.iter()
+                    .map(|arg| normalize_identifier(arg, &self.name_resolution_ctx).name)
+                    .collect()
+            });
+            match QueryHint::try_create(&hint.name.name, args) {
+                Ok(hint) => self.metadata.write().add_hint(hint),
+                Err(e) => {
+                    warn!("{}", e.message());
+                    self.ctx.push_warning(e.message());
+                }
+            }
+        }
+    }
+
     #[async_recursion::async_recursion]
     #[async_backtrace::framed]
     pub(crate) async fn bind_statement(
@@ -220,9 +240,8 @@ impl<'a> Binder {
                 self.bind_explain(bind_context, kind, options, query).await?
             }
 
-            Statement::ExplainAnalyze { query } => {
-                let plan = self.bind_statement(bind_context, query).await?;
-                Plan::ExplainAnalyze { plan: Box::new(plan) }
+            Statement::ExplainAnalyze { options, query } => {
+                self.bind_explain_analyze(bind_context, options, query).await?
             }
 
             Statement::ShowFunctions { show_options } => {
@@ -376,6 +395,7 @@ impl<'a> Binder {
                     if let Some(e) = self.opt_hints_set_var(bind_context, hints).await.err() {
                         warn!("In INSERT resolve optimize hints {:?} failed, err: {:?}", hints, e);
                     }
+                    self.bind_optimizer_hints(hints);
                 }
                 self.bind_insert(bind_context, stmt).await?
             }
@@ -495,6 +515,7 @@ impl<'a> Binder {
                 self.bind_kill_stmt(bind_context, kill_target, object_id.as_str())
                     .await?
             }
+            Statement::System(stmt) => self.bind_system(stmt)?,
 
             // share statements
             Statement::CreateShareEndpoint(stmt) => {
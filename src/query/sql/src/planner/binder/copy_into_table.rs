@@ -396,6 +396,7 @@ impl<'a> Binder {
                         lit: Literal::UInt64(1),
                     },
                 }],
+                optimizer_hints: vec![],
             };
             if let Some(e) = self
                 .opt_hints_set_var(&mut output_context, &hints)
@@ -22,6 +22,7 @@ use databend_common_ast::ast::AlterTableAction;
 use databend_common_ast::ast::AlterTableStmt;
 use databend_common_ast::ast::AnalyzeTableStmt;
 use databend_common_ast::ast::AttachTableStmt;
+use databend_common_ast::ast::CheckConstraintDefinition;
 use databend_common_ast::ast::ColumnDefinition;
 use databend_common_ast::ast::ColumnExpr;
 use databend_common_ast::ast::CompactTarget;
@@ -75,6 +76,8 @@ use databend_common_storages_iceberg::IcebergTable;
 use databend_common_storages_view::view_table::QUERY;
 use databend_common_storages_view::view_table::VIEW_ENGINE;
 use databend_storages_common_table_meta::table::is_reserved_opt_key;
+use databend_storages_common_table_meta::table::CheckConstraint;
+use databend_storages_common_table_meta::table::OPT_KEY_CHECK_CONSTRAINTS;
 use databend_storages_common_table_meta::table::OPT_KEY_DATABASE_ID;
 use databend_storages_common_table_meta::table::OPT_KEY_ENGINE_META;
 use databend_storages_common_table_meta::table::OPT_KEY_STORAGE_FORMAT;
@@ -99,12 +102,14 @@ use crate::planner::semantic::resolve_type_name;
 use crate::planner::semantic::IdentifierNormalizer;
 use crate::plans::AddColumnOption;
 use crate::plans::AddTableColumnPlan;
+use crate::plans::AddTableConstraintPlan;
 use crate::plans::AlterTableClusterKeyPlan;
 use crate::plans::AnalyzeTablePlan;
 use crate::plans::CreateTablePlan;
 use crate::plans::DescribeTablePlan;
 use crate::plans::DropTableClusterKeyPlan;
 use crate::plans::DropTableColumnPlan;
+use crate::plans::DropTableConstraintPlan;
 use crate::plans::DropTablePlan;
 use crate::plans::ExistsTablePlan;
 use crate::plans::ModifyColumnAction as ModifyColumnActionInPlan;
@@ -472,7 +477,7 @@ impl Binder {
         }
 
         // todo(geometry): remove this when geometry stable.
-        if let Some(CreateTableSource::Columns(cols)) = &source {
+        if let Some(CreateTableSource::Columns(cols, _)) = &source {
             if cols
                 .iter()
                 .any(|col| matches!(col.data_type, TypeName::Geometry))
@@ -633,6 +638,18 @@ impl Binder {
             }
         }
 
+        if let Some(CreateTableSource::Columns(_, constraints)) = &source {
+            if !constraints.is_empty() {
+                let constraints = self
+                    .analyze_check_constraints(constraints, schema.clone())
+                    .await?;
+                options.insert(
+                    OPT_KEY_CHECK_CONSTRAINTS.to_owned(),
+                    CheckConstraint::encode(&constraints)?,
+                );
+            }
+        }
+
         let cluster_key = {
             let keys = self
                 .analyze_cluster_keys(cluster_by, schema.clone())
@@ -928,6 +945,31 @@ impl Binder {
                     column: column.to_string(),
                 })))
             }
+            AlterTableAction::AddConstraint { constraint } => {
+                let schema = self
+                    .ctx
+                    .get_table(&catalog, &database, &table)
+                    .await?
+                    .schema();
+                let constraint = self
+                    .analyze_check_constraints(std::slice::from_ref(constraint), schema)
+                    .await?
+                    .remove(0);
+                Ok(Plan::AddTableConstraint(Box::new(AddTableConstraintPlan {
+                    catalog,
+                    database,
+                    table,
+                    constraint,
+                })))
+            }
+            AlterTableAction::DropConstraint { name } => Ok(Plan::DropTableConstraint(Box::new(
+                DropTableConstraintPlan {
+                    catalog,
+                    database,
+                    table,
+                    name: normalize_identifier(name, &self.name_resolution_ctx).name,
+                },
+            ))),
             AlterTableAction::AlterTableClusterKey { cluster_by } => {
                 let schema = self
                     .ctx
@@ -1394,7 +1436,7 @@ impl Binder {
         source: &CreateTableSource,
     ) -> Result<(TableSchemaRef, Vec<String>)> {
         match source {
-            CreateTableSource::Columns(columns) => {
+            CreateTableSource::Columns(columns, _) => {
                 self.analyze_create_table_schema_by_columns(columns).await
             }
             CreateTableSource::Like {
@@ -1528,6 +1570,87 @@ impl Binder {
         Ok(cluster_keys)
     }
 
+    #[async_backtrace::framed]
+    async fn analyze_check_constraints(
+        &mut self,
+        constraints: &[CheckConstraintDefinition],
+        schema: TableSchemaRef,
+    ) -> Result<Vec<CheckConstraint>> {
+        // Build a temporary BindContext to resolve the expr
+        let mut bind_context = BindContext::new();
+        for (index, field) in schema.fields().iter().enumerate() {
+            let column = ColumnBindingBuilder::new(
+                field.name().clone(),
+                index,
+                Box::new(DataType::from(field.data_type())),
+                Visibility::Visible,
+            )
+            .build();
+
+            bind_context.add_column_binding(column);
+        }
+        let mut scalar_binder = ScalarBinder::new(
+            &mut bind_context,
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+            self.m_cte_bound_ctx.clone(),
+            self.ctes_map.clone(),
+        );
+        // check constraints cannot be a udf expression.
+        scalar_binder.forbid_udf();
+
+        let mut names = HashSet::with_capacity(constraints.len());
+        let mut check_constraints = Vec::with_capacity(constraints.len());
+        for constraint in constraints.iter() {
+            let name = normalize_identifier(&constraint.name, &self.name_resolution_ctx).name;
+            if !names.insert(name.clone()) {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Duplicated constraint name: {name}"
+                )));
+            }
+
+            let (scalar, _) = scalar_binder.bind(&constraint.expr).await?;
+            if !scalar.evaluable() {
+                return Err(ErrorCode::BadArguments(format!(
+                    "CHECK constraint `{name}` expression `{:#}` is invalid",
+                    constraint.expr
+                )));
+            }
+
+            let expr = scalar.as_expr()?;
+            if !expr.is_deterministic(&BUILTIN_FUNCTIONS) {
+                return Err(ErrorCode::BadArguments(format!(
+                    "CHECK constraint `{name}` expression `{:#}` is not deterministic",
+                    constraint.expr
+                )));
+            }
+
+            if expr.data_type().remove_nullable() != DataType::Boolean {
+                return Err(ErrorCode::BadArguments(format!(
+                    "CHECK constraint `{name}` expression `{:#}` must be a boolean expression, but got {}",
+                    constraint.expr,
+                    expr.data_type()
+                )));
+            }
+
+            let mut check_expr = constraint.expr.clone();
+            walk_expr_mut(
+                &mut IdentifierNormalizer {
+                    ctx: &self.name_resolution_ctx,
+                },
+                &mut check_expr,
+            );
+            check_constraints.push(CheckConstraint {
+                name,
+                expr: format!("{:#}", &check_expr),
+            });
+        }
+
+        Ok(check_constraints)
+    }
+
     fn valid_cluster_key_type(data_type: &DataType) -> bool {
         let inner_type = data_type.remove_nullable();
         matches!(
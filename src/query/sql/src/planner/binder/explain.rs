@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use databend_common_ast::ast::ExplainFormat;
 use databend_common_ast::ast::ExplainKind;
 use databend_common_ast::ast::ExplainOption;
 use databend_common_ast::ast::Statement;
@@ -28,12 +29,14 @@ pub struct ExplainConfig {
     pub verbose: bool,
     pub logical: bool,
     pub optimized: bool,
+    pub format: ExplainFormat,
 }
 
 struct ExplainConfigBuilder {
     verbose: bool,
     logical: bool,
     optimized: bool,
+    format: ExplainFormat,
 }
 
 impl ExplainConfigBuilder {
@@ -42,6 +45,7 @@ impl ExplainConfigBuilder {
             verbose: false,
             logical: false,
             optimized: false,
+            format: ExplainFormat::Text,
         }
     }
 
@@ -55,6 +59,7 @@ impl ExplainConfigBuilder {
                 }
                 self.optimized = *v;
             }
+            ExplainOption::Format(format) => self.format = *format,
         }
 
         self
@@ -65,6 +70,7 @@ impl ExplainConfigBuilder {
             verbose: self.verbose,
             logical: self.logical,
             optimized: self.optimized,
+            format: self.format,
         }
     }
 }
@@ -102,9 +108,11 @@ impl Binder {
         let plan = match kind {
             ExplainKind::Ast(formatted_stmt) => Plan::ExplainAst {
                 formatted_string: formatted_stmt.clone(),
+                config,
             },
             ExplainKind::Syntax(formatted_sql) => Plan::ExplainSyntax {
                 formatted_sql: formatted_sql.clone(),
+                config,
             },
             _ => Plan::Explain {
                 kind: kind.clone(),
@@ -115,6 +123,26 @@ impl Binder {
 
         Ok(plan)
     }
+
+    pub async fn bind_explain_analyze(
+        &mut self,
+        bind_context: &mut BindContext,
+        options: &[ExplainOption],
+        inner: &Statement,
+    ) -> Result<Plan> {
+        let mut builder = ExplainConfigBuilder::new();
+        for option in options {
+            builder = builder.add_option(option);
+        }
+
+        let config = builder.build();
+        validate_explain_config(&ExplainKind::AnalyzePlan, &config)?;
+
+        Ok(Plan::ExplainAnalyze {
+            config,
+            plan: Box::new(self.bind_statement(bind_context, inner).await?),
+        })
+    }
 }
 
 fn validate_explain_config(kind: &ExplainKind, config: &ExplainConfig) -> Result<()> {
@@ -140,11 +168,12 @@ fn validate_explain_config(kind: &ExplainKind, config: &ExplainConfig) -> Result
 
     if !matches!(
         kind,
-        ExplainKind::Plan | ExplainKind::Raw | ExplainKind::Optimized
+        ExplainKind::Plan | ExplainKind::Raw | ExplainKind::Optimized | ExplainKind::AnalyzePlan
     ) && config.verbose
     {
         return Err(ErrorCode::SyntaxException(
-            "VERBOSE option is only supported for EXPLAIN SELECT statement".to_string(),
+            "VERBOSE option is only supported for EXPLAIN SELECT and EXPLAIN ANALYZE statement"
+                .to_string(),
         ));
     }
 
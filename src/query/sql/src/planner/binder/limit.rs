@@ -18,6 +18,10 @@ use databend_common_ast::ast::Expr;
 use databend_common_ast::ast::Literal;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
+use databend_common_expression::types::decimal::DecimalScalar;
+use databend_common_expression::types::decimal::DecimalSize;
+use databend_common_expression::types::NumberScalar;
+use databend_common_expression::Scalar;
 
 use crate::binder::Binder;
 use crate::optimizer::SExpr;
@@ -29,20 +33,13 @@ impl Binder {
         offset: &Option<Expr>,
     ) -> Result<(Option<usize>, usize)> {
         let limit_cnt = match limit {
-            Some(limit) => Some(
-                Self::bind_limit_argument(limit)
-                    .ok_or_else(|| ErrorCode::SemanticError("Invalid LIMIT expression"))?
-                    as usize,
-            ),
+            Some(limit) => Some(Self::bind_limit_argument(limit, "LIMIT")?),
             None => None,
         };
 
-        let offset_cnt = if let Some(offset) = offset {
-            Self::bind_limit_argument(offset)
-                .ok_or_else(|| ErrorCode::SemanticError("Invalid OFFSET expression"))?
-                as usize
-        } else {
-            0
+        let offset_cnt = match offset {
+            Some(offset) => Self::bind_limit_argument(offset, "OFFSET")?,
+            None => 0,
         };
 
         Ok((limit_cnt, offset_cnt))
@@ -57,16 +54,35 @@ impl Binder {
         SExpr::create_unary(Arc::new(limit_plan.into()), Arc::new(child))
     }
 
-    /// So far, we only support integer literal as limit argument.
-    /// So we will try to extract the integer value from the AST directly.
+    /// So far, we only support numeric literal as limit argument.
+    /// So we will try to extract the integer value from the AST directly,
+    /// the decimals without fractional part like `10.0` are also accepted.
     /// In the future it's possible to treat the argument as an expression.
-    fn bind_limit_argument(expr: &Expr) -> Option<u64> {
-        match expr {
+    fn bind_limit_argument(expr: &Expr, name: &str) -> Result<usize> {
+        let value = match expr {
             Expr::Literal {
                 lit: Literal::UInt64(value),
                 ..
-            } => Some(*value),
-            _ => None,
-        }
+            } => Scalar::Number(NumberScalar::UInt64(*value)),
+            Expr::Literal {
+                lit:
+                    Literal::Decimal256 {
+                        value,
+                        precision,
+                        scale,
+                    },
+                ..
+            } => Scalar::Decimal(DecimalScalar::Decimal256(*value, DecimalSize {
+                precision: *precision,
+                scale: *scale,
+            })),
+            _ => {
+                return Err(ErrorCode::SemanticError(format!(
+                    "Invalid {name} expression"
+                )));
+            }
+        };
+
+        Ok(value.try_get_u64()? as usize)
     }
 }
@@ -14,8 +14,11 @@
 
 use std::collections::HashSet;
 
+use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
+use databend_common_expression::type_check::common_super_type;
 use databend_common_expression::types::DataType;
+use databend_common_functions::BUILTIN_FUNCTIONS;
 
 use crate::optimizer::RelationalProperty;
 use crate::plans::walk_expr;
@@ -227,6 +230,34 @@ pub fn prune_by_children(scalar: &ScalarExpr, columns: &HashSet<ScalarExpr>) ->
     visitor.can_prune
 }
 
+/// Check a value of `data_type` can be coerced into the column `column_name`.
+///
+/// A bare `NULL` has no type of its own and takes the type of the target column. The type is
+/// inferred by `common_super_type` like in the CASE, coalesce and IN resolution and the VALUES
+/// columns, so it is only accepted when the inferred type is the column type, that is when the
+/// column is nullable.
+pub fn check_null_coercion(
+    data_type: &DataType,
+    target_type: &DataType,
+    column_name: &str,
+) -> Result<()> {
+    if data_type != &DataType::Null {
+        return Ok(());
+    }
+
+    let inferred_type = common_super_type(
+        DataType::Null,
+        target_type.clone(),
+        &BUILTIN_FUNCTIONS.default_cast_rules,
+    );
+    if inferred_type.as_ref() != Some(target_type) {
+        return Err(ErrorCode::BadArguments(format!(
+            "unable to insert `NULL` into the non-nullable column `{column_name}` of type `{target_type}`"
+        )));
+    }
+    Ok(())
+}
+
 /// Wrap a cast expression with given target type
 pub fn wrap_cast(scalar: &ScalarExpr, target_type: &DataType) -> ScalarExpr {
     ScalarExpr::CastExpr(CastExpr {
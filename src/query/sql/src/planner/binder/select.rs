@@ -108,6 +108,7 @@ impl Binder {
                     hints, e
                 );
             }
+            self.bind_optimizer_hints(hints);
         }
         let (mut s_expr, mut from_context) = if stmt.from.is_empty() {
             let select_list = &stmt.select_list;
@@ -30,6 +30,7 @@ use databend_common_expression::Value;
 use databend_common_pipeline_transforms::processors::Transform;
 use indexmap::IndexMap;
 
+use crate::binder::check_null_coercion;
 use crate::binder::wrap_cast;
 use crate::evaluator::BlockOperator;
 use crate::evaluator::CompoundBlockOperator;
@@ -77,7 +78,9 @@ impl BindContext {
             }
 
             let (mut scalar, data_type) = scalar_binder.bind(expr).await?;
-            let target_type = schema.field(i).data_type();
+            let field = schema.field(i);
+            let target_type = field.data_type();
+            check_null_coercion(&data_type, target_type, field.name())?;
             if data_type != *target_type {
                 scalar = wrap_cast(&scalar, target_type);
             }
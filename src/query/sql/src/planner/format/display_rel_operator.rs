@@ -62,7 +62,10 @@ pub fn format_scalar(scalar: &ScalarExpr) -> String {
                 )
             }
         }
-        ScalarExpr::ConstantExpr(constant) => constant.value.to_string(),
+        ScalarExpr::ConstantExpr(constant) => constant
+            .value
+            .to_sql_literal()
+            .unwrap_or_else(|_| constant.value.to_string()),
         ScalarExpr::WindowFunction(win) => win.display_name.clone(),
         ScalarExpr::AggregateFunction(agg) => agg.display_name.clone(),
         ScalarExpr::LambdaFunction(lambda) => {
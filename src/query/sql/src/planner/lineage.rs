@@ -0,0 +1,430 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use databend_common_exception::Result;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::DataBlock;
+use databend_common_expression::DataField;
+use databend_common_expression::DataSchemaRef;
+use databend_common_expression::DataSchemaRefExt;
+use databend_common_expression::FromData;
+use serde::Serialize;
+
+use crate::optimizer::SExpr;
+use crate::plans::walk_window;
+use crate::plans::AggregateFunction;
+use crate::plans::BoundColumnRef;
+use crate::plans::Plan;
+use crate::plans::RelOperator;
+use crate::plans::ScalarExpr;
+use crate::plans::SubqueryExpr;
+use crate::plans::SubqueryType;
+use crate::plans::Visitor;
+use crate::plans::WindowFunc;
+use crate::IndexType;
+use crate::InsertInputSource;
+use crate::Metadata;
+
+/// How a source column contributes to an output column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineageKind {
+    /// The value is copied from the source column.
+    Direct,
+    /// The value is computed from the source column by functions, casts, CASE, etc.
+    Transform,
+    /// The value is aggregated from the source column, by aggregate or window functions.
+    Aggregation,
+    /// The source column is a join key, which decides the rows of the output.
+    JoinKey,
+    /// The source column is used in a filter, which decides the rows of the output.
+    Filter,
+}
+
+impl Display for LineageKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LineageKind::Direct => write!(f, "direct"),
+            LineageKind::Transform => write!(f, "transform"),
+            LineageKind::Aggregation => write!(f, "aggregation"),
+            LineageKind::JoinKey => write!(f, "join_key"),
+            LineageKind::Filter => write!(f, "filter"),
+        }
+    }
+}
+
+/// A source table column contributing to an output column.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct LineageSource {
+    /// `database.table` of the source column.
+    pub table: String,
+    pub column: String,
+    pub kind: LineageKind,
+}
+
+/// The lineage of an output column of a query, or of a column written by a statement.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ColumnLineage {
+    /// `database.table` written by the statement, None for queries.
+    pub target_table: Option<String>,
+    pub column: String,
+    pub sources: Vec<LineageSource>,
+}
+
+/// Extracts the column lineage of a bound plan, returns None if the plan
+/// is not a query, an `INSERT ... SELECT` or a `CREATE TABLE ... AS SELECT`.
+pub fn extract_lineage(plan: &Plan) -> Result<Option<Vec<ColumnLineage>>> {
+    match plan {
+        Plan::Query { .. } => query_lineage(plan, None, None),
+        Plan::Insert(insert) => match &insert.source {
+            InsertInputSource::SelectPlan(select_plan) => {
+                let target_table = format!("{}.{}", insert.database, insert.table);
+                let columns = insert
+                    .schema
+                    .fields()
+                    .iter()
+                    .map(|field| field.name().clone())
+                    .collect();
+                query_lineage(select_plan, Some(target_table), Some(columns))
+            }
+            _ => Ok(None),
+        },
+        Plan::CreateTable(create_table) => match &create_table.as_select {
+            Some(select_plan) => {
+                let target_table = format!("{}.{}", create_table.database, create_table.table);
+                let columns = create_table
+                    .schema
+                    .fields()
+                    .iter()
+                    .map(|field| field.name().clone())
+                    .collect();
+                query_lineage(select_plan, Some(target_table), Some(columns))
+            }
+            None => Ok(None),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// The schema of the lineage result set, one row per (column, source) pair.
+pub fn lineage_schema() -> DataSchemaRef {
+    let nullable_string = DataType::Nullable(Box::new(DataType::String));
+    DataSchemaRefExt::create(vec![
+        DataField::new("target_table", nullable_string.clone()),
+        DataField::new("target_column", DataType::String),
+        DataField::new("source_table", nullable_string.clone()),
+        DataField::new("source_column", nullable_string.clone()),
+        DataField::new("kind", nullable_string),
+    ])
+}
+
+/// Builds the lineage result set, the columns without sources, such as constants,
+/// have a single row with NULL sources.
+pub fn lineage_block(lineage: &[ColumnLineage]) -> DataBlock {
+    let mut target_tables = vec![];
+    let mut target_columns = vec![];
+    let mut source_tables = vec![];
+    let mut source_columns = vec![];
+    let mut kinds = vec![];
+    for column in lineage {
+        if column.sources.is_empty() {
+            target_tables.push(column.target_table.clone());
+            target_columns.push(column.column.clone());
+            source_tables.push(None);
+            source_columns.push(None);
+            kinds.push(None);
+        }
+        for source in &column.sources {
+            target_tables.push(column.target_table.clone());
+            target_columns.push(column.column.clone());
+            source_tables.push(Some(source.table.clone()));
+            source_columns.push(Some(source.column.clone()));
+            kinds.push(Some(source.kind.to_string()));
+        }
+    }
+
+    DataBlock::new_from_columns(vec![
+        StringType::from_opt_data(target_tables),
+        StringType::from_data(target_columns),
+        StringType::from_opt_data(source_tables),
+        StringType::from_opt_data(source_columns),
+        StringType::from_opt_data(kinds),
+    ])
+}
+
+fn query_lineage(
+    plan: &Plan,
+    target_table: Option<String>,
+    target_columns: Option<Vec<String>>,
+) -> Result<Option<Vec<ColumnLineage>>> {
+    let Plan::Query {
+        s_expr,
+        metadata,
+        bind_context,
+        ..
+    } = plan
+    else {
+        return Ok(None);
+    };
+
+    let metadata = metadata.read();
+    let mut extractor = LineageExtractor::new(&metadata);
+    extractor.visit(s_expr)?;
+
+    let names = match target_columns {
+        Some(names) => names,
+        None => bind_context
+            .columns
+            .iter()
+            .map(|column| column.column_name.clone())
+            .collect(),
+    };
+    let lineage = names
+        .into_iter()
+        .zip(bind_context.columns.iter())
+        .map(|(name, column)| ColumnLineage {
+            target_table: target_table.clone(),
+            column: name,
+            sources: extractor.sources(column.index),
+        })
+        .collect();
+    Ok(Some(lineage))
+}
+
+/// Walks the operator tree bottom-up, and tracks the base table columns
+/// each derived column is computed from.
+struct LineageExtractor<'a> {
+    metadata: &'a Metadata,
+    // The value lineage of the columns computed by the operators.
+    derived: HashMap<IndexType, BTreeMap<IndexType, LineageKind>>,
+    // The columns deciding the rows of the output, i.e. the join keys and the filters,
+    // every output column depends on them.
+    dependencies: BTreeSet<(IndexType, LineageKind)>,
+}
+
+impl<'a> LineageExtractor<'a> {
+    fn new(metadata: &'a Metadata) -> Self {
+        LineageExtractor {
+            metadata,
+            derived: HashMap::new(),
+            dependencies: BTreeSet::new(),
+        }
+    }
+
+    fn visit(&mut self, s_expr: &SExpr) -> Result<()> {
+        for child in s_expr.children() {
+            self.visit(child)?;
+        }
+
+        match s_expr.plan() {
+            RelOperator::Scan(scan) => {
+                for predicate in scan.push_down_predicates.iter().flatten() {
+                    self.depend(predicate, LineageKind::Filter)?;
+                }
+            }
+            RelOperator::EvalScalar(eval_scalar) => {
+                for item in &eval_scalar.items {
+                    self.derive(item.index, &item.scalar)?;
+                }
+            }
+            RelOperator::Aggregate(aggregate) => {
+                for item in aggregate
+                    .group_items
+                    .iter()
+                    .chain(aggregate.aggregate_functions.iter())
+                {
+                    self.derive(item.index, &item.scalar)?;
+                }
+            }
+            RelOperator::Window(window) => {
+                let items = window
+                    .arguments
+                    .iter()
+                    .chain(window.partition_by.iter())
+                    .chain(window.order_by.iter().map(|order| &order.order_by_item));
+                let mut sources = BTreeMap::new();
+                for item in items {
+                    merge_sources(&mut sources, self.lineage(item.index), LineageKind::Direct);
+                    let scalar_sources = self.scalar_lineage(&item.scalar)?;
+                    merge_sources(&mut sources, scalar_sources, LineageKind::Direct);
+                }
+                let mut window_sources = BTreeMap::new();
+                merge_sources(&mut window_sources, sources, LineageKind::Aggregation);
+                self.derived.insert(window.index, window_sources);
+            }
+            RelOperator::ProjectSet(project_set) => {
+                for srf in &project_set.srfs {
+                    self.derive(srf.index, &srf.scalar)?;
+                }
+            }
+            RelOperator::Udf(udf) => {
+                for item in &udf.items {
+                    self.derive(item.index, &item.scalar)?;
+                }
+            }
+            RelOperator::UnionAll(union_all) => {
+                for (left, right) in &union_all.pairs {
+                    let mut sources = self.lineage(*left);
+                    merge_sources(&mut sources, self.lineage(*right), LineageKind::Direct);
+                    self.derived.insert(*left, sources);
+                }
+            }
+            RelOperator::Filter(filter) => {
+                for predicate in &filter.predicates {
+                    self.depend(predicate, LineageKind::Filter)?;
+                }
+            }
+            RelOperator::Join(join) => {
+                for condition in join
+                    .left_conditions
+                    .iter()
+                    .chain(join.right_conditions.iter())
+                {
+                    self.depend(condition, LineageKind::JoinKey)?;
+                }
+                for condition in &join.non_equi_conditions {
+                    self.depend(condition, LineageKind::Filter)?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn derive(&mut self, index: IndexType, scalar: &ScalarExpr) -> Result<()> {
+        let sources = self.scalar_lineage(scalar)?;
+        self.derived.insert(index, sources);
+        Ok(())
+    }
+
+    fn depend(&mut self, scalar: &ScalarExpr, kind: LineageKind) -> Result<()> {
+        let sources = self.scalar_lineage(scalar)?;
+        self.dependencies
+            .extend(sources.into_keys().map(|index| (index, kind)));
+        Ok(())
+    }
+
+    // A column reference copies the lineage of the column, any other expression transforms it.
+    fn scalar_lineage(&mut self, scalar: &ScalarExpr) -> Result<BTreeMap<IndexType, LineageKind>> {
+        let kind = match scalar {
+            ScalarExpr::BoundColumnRef(_) => LineageKind::Direct,
+            _ => LineageKind::Transform,
+        };
+        let mut visitor = ScalarLineageVisitor {
+            extractor: self,
+            kind,
+            sources: BTreeMap::new(),
+        };
+        visitor.visit(scalar)?;
+        Ok(visitor.sources)
+    }
+
+    // The value lineage of a column, the base table columns are their own sources.
+    fn lineage(&self, index: IndexType) -> BTreeMap<IndexType, LineageKind> {
+        if let Some(sources) = self.derived.get(&index) {
+            return sources.clone();
+        }
+        match self.metadata.column(index).table_index() {
+            Some(_) => BTreeMap::from([(index, LineageKind::Direct)]),
+            None => BTreeMap::new(),
+        }
+    }
+
+    fn sources(&self, index: IndexType) -> Vec<LineageSource> {
+        let sources: BTreeSet<LineageSource> = self
+            .lineage(index)
+            .into_iter()
+            .chain(self.dependencies.iter().cloned())
+            .filter_map(|(index, kind)| {
+                let column = self.metadata.column(index);
+                let table = self.metadata.table(column.table_index()?);
+                Some(LineageSource {
+                    table: format!("{}.{}", table.database(), table.name()),
+                    column: column.name(),
+                    kind,
+                })
+            })
+            .collect();
+        sources.into_iter().collect()
+    }
+}
+
+struct ScalarLineageVisitor<'a, 'b> {
+    extractor: &'b mut LineageExtractor<'a>,
+    // The least kind of the sources of the expression being visited.
+    kind: LineageKind,
+    sources: BTreeMap<IndexType, LineageKind>,
+}
+
+impl<'s> Visitor<'s> for ScalarLineageVisitor<'_, '_> {
+    fn visit_bound_column_ref(&mut self, col: &'s BoundColumnRef) -> Result<()> {
+        let sources = self.extractor.lineage(col.column.index);
+        merge_sources(&mut self.sources, sources, self.kind);
+        Ok(())
+    }
+
+    fn visit_aggregate_function(&mut self, aggregate: &'s AggregateFunction) -> Result<()> {
+        let kind = std::mem::replace(&mut self.kind, LineageKind::Aggregation);
+        for arg in &aggregate.args {
+            self.visit(arg)?;
+        }
+        self.kind = kind;
+        Ok(())
+    }
+
+    fn visit_window_function(&mut self, window: &'s WindowFunc) -> Result<()> {
+        let kind = std::mem::replace(&mut self.kind, LineageKind::Aggregation);
+        walk_window(self, window)?;
+        self.kind = kind;
+        Ok(())
+    }
+
+    fn visit_subquery(&mut self, subquery: &'s SubqueryExpr) -> Result<()> {
+        self.extractor.visit(&subquery.subquery)?;
+        if matches!(
+            subquery.typ,
+            SubqueryType::Scalar | SubqueryType::Any | SubqueryType::All
+        ) {
+            let sources = self.extractor.lineage(subquery.output_column.index);
+            merge_sources(&mut self.sources, sources, self.kind);
+        }
+        if let Some(child_expr) = subquery.child_expr.as_ref() {
+            self.visit(child_expr)?;
+        }
+        Ok(())
+    }
+}
+
+// Merges the sources lifted to at least `kind`, keeps the strongest kind of each source.
+fn merge_sources(
+    target: &mut BTreeMap<IndexType, LineageKind>,
+    sources: BTreeMap<IndexType, LineageKind>,
+    kind: LineageKind,
+) {
+    for (index, source_kind) in sources {
+        let source_kind = source_kind.max(kind);
+        target
+            .entry(index)
+            .and_modify(|existing| *existing = (*existing).max(source_kind))
+            .or_insert(source_kind);
+    }
+}
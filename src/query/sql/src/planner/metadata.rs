@@ -31,6 +31,8 @@ use databend_common_expression::TableField;
 use parking_lot::RwLock;
 
 use crate::optimizer::SExpr;
+use crate::HintState;
+use crate::QueryHint;
 
 /// Planner use [`usize`] as it's index type.
 ///
@@ -62,6 +64,10 @@ pub struct Metadata {
     table_row_id_index: HashMap<IndexType, IndexType>,
     agg_indexes: HashMap<String, Vec<(u64, String, SExpr)>>,
     max_column_position: usize, // for CSV
+    /// Optimizer hints of the query and whether they took effect.
+    hints: Vec<(QueryHint, HintState)>,
+    /// Number of the seeded `rand()` calls bound so far.
+    random_call_sites: u64,
 }
 
 impl Metadata {
@@ -395,6 +401,37 @@ impl Metadata {
     pub fn get_max_column_position(&self) -> usize {
         self.max_column_position
     }
+
+    pub fn add_hint(&mut self, hint: QueryHint) {
+        if self.hints.iter().all(|(h, _)| h != &hint) {
+            self.hints.push((hint, HintState::Pending));
+        }
+    }
+
+    pub fn hints(&self) -> &[(QueryHint, HintState)] {
+        &self.hints
+    }
+
+    pub fn set_hint_state(&mut self, index: usize, state: HintState) {
+        self.hints[index].1 = state;
+    }
+
+    /// Assigns an id to a call of the seeded `rand()`, the calls of a query draw different values.
+    pub fn next_random_call_site(&mut self) -> u64 {
+        self.random_call_sites += 1;
+        self.random_call_sites
+    }
+
+    /// Find the table referenced by a hint, by its alias or name.
+    pub fn table_index_by_hint_name(&self, name: &str) -> Option<IndexType> {
+        self.tables
+            .iter()
+            .find(|table| match table.alias_name() {
+                Some(alias) => alias == name,
+                None => table.name() == name,
+            })
+            .map(|table| table.index())
+    }
 }
 
 #[derive(Clone)]
@@ -14,9 +14,12 @@
 
 mod bloom_index;
 mod format;
+mod lineage;
 mod metadata;
 #[allow(clippy::module_inception)]
 mod planner;
+mod query_fingerprint;
+mod query_hint;
 mod semantic;
 
 pub mod binder;
@@ -38,6 +41,7 @@ pub use binder::Visibility;
 pub use bloom_index::BloomIndexColumns;
 pub use expression_parser::*;
 pub use format::format_scalar;
+pub use lineage::*;
 pub use metadata::*;
 pub use planner::PlanExtras;
 pub use planner::Planner;
@@ -46,5 +50,7 @@ pub use plans::ScalarExpr;
 pub use plans::DELETE_NAME;
 pub use plans::INSERT_NAME;
 pub use plans::UPDATE_NAME;
+pub use query_fingerprint::*;
+pub use query_hint::*;
 pub use semantic::*;
 pub use stream_column::*;
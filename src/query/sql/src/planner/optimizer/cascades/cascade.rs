@@ -27,6 +27,7 @@ use crate::optimizer::cascades::tasks::Task;
 use crate::optimizer::cost::CostModel;
 use crate::optimizer::cost::DefaultCostModel;
 use crate::optimizer::format::display_memo;
+use crate::optimizer::hint::JoinDistributionHint;
 use crate::optimizer::memo::Memo;
 use crate::optimizer::rule::TransformResult;
 use crate::optimizer::Distribution;
@@ -45,6 +46,7 @@ pub struct CascadesOptimizer {
     pub(crate) explore_rule_set: RuleSet,
     pub(crate) metadata: MetadataRef,
     pub(crate) enforce_distribution: bool,
+    pub(crate) join_hints: Vec<JoinDistributionHint>,
 }
 
 impl CascadesOptimizer {
@@ -77,9 +79,16 @@ impl CascadesOptimizer {
             explore_rule_set,
             metadata,
             enforce_distribution,
+            join_hints: vec![],
         })
     }
 
+    /// Prefer the join distributions required by `BROADCAST_JOIN` and `SHUFFLE_JOIN` hints.
+    pub(crate) fn with_join_distribution_hints(mut self, hints: Vec<JoinDistributionHint>) -> Self {
+        self.join_hints = hints;
+        self
+    }
+
     fn init(&mut self, expression: SExpr) -> Result<()> {
         self.memo.init(expression)?;
 
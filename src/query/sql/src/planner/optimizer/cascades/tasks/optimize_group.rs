@@ -25,6 +25,7 @@ use crate::optimizer::cascades::scheduler::Scheduler;
 use crate::optimizer::cascades::tasks::ExploreGroupTask;
 use crate::optimizer::cascades::tasks::SharedCounter;
 use crate::optimizer::cascades::CascadesOptimizer;
+use crate::optimizer::hint::filter_join_distributions;
 use crate::optimizer::RelExpr;
 use crate::optimizer::RequiredProperty;
 use crate::plans::Operator;
@@ -175,7 +176,15 @@ impl OptimizeGroupTask {
 
             let rel_expr = RelExpr::with_m_expr(m_expr, &optimizer.memo);
             let children_required_props = if optimizer.enforce_distribution {
-                rel_expr.compute_required_prop_children(self.ctx.clone(), &self.required_prop)?
+                let children_required_props = rel_expr
+                    .compute_required_prop_children(self.ctx.clone(), &self.required_prop)?;
+                filter_join_distributions(
+                    &self.ctx,
+                    &optimizer.join_hints,
+                    &m_expr.plan,
+                    &rel_expr,
+                    children_required_props,
+                )?
             } else {
                 vec![vec![RequiredProperty::default(); m_expr.plan.arity()]]
             };
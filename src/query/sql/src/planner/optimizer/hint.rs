@@ -0,0 +1,300 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use databend_common_catalog::table_context::TableContext;
+use databend_common_exception::Result;
+
+use crate::optimizer::Distribution;
+use crate::optimizer::RelExpr;
+use crate::optimizer::RequiredProperty;
+use crate::optimizer::RuleID;
+use crate::optimizer::SExpr;
+use crate::optimizer::DEFAULT_REWRITE_RULES;
+use crate::plans::Exchange;
+use crate::plans::Join;
+use crate::plans::JoinType;
+use crate::plans::RelOperator;
+use crate::ColumnSet;
+use crate::HintState;
+use crate::Metadata;
+use crate::MetadataRef;
+use crate::QueryHint;
+
+/// A `BROADCAST_JOIN` or `SHUFFLE_JOIN` hint, with the columns of the hinted table.
+#[derive(Clone, Debug)]
+pub struct JoinDistributionHint {
+    hint_index: usize,
+    table: String,
+    broadcast: bool,
+    columns: ColumnSet,
+}
+
+/// Returns the rewrite rules, without the rules disabled by `NO_PUSHDOWN`.
+pub fn rewrite_rules_with_hints(metadata: &MetadataRef) -> Vec<RuleID> {
+    let no_pushdown = set_hints_state(metadata, |_, hint| match hint {
+        QueryHint::NoPushdown => Some(HintState::Applied),
+        _ => None,
+    });
+
+    DEFAULT_REWRITE_RULES
+        .iter()
+        .filter(|rule| {
+            !no_pushdown || !matches!(rule, RuleID::PushDownFilterScan | RuleID::PushDownPrewhere)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Checks the `LEADING` hints, returns true if the join order must be kept.
+///
+/// The join order is kept as written, so the hint is only applied if the tables
+/// are in the order of the FROM clause.
+pub fn apply_leading_hints(metadata: &MetadataRef) -> bool {
+    let mut keep_join_order = false;
+    set_hints_state(metadata, |metadata, hint| match hint {
+        QueryHint::Leading(tables) => {
+            let state = leading_hint_state(metadata, tables);
+            keep_join_order |= state == HintState::Applied;
+            Some(state)
+        }
+        _ => None,
+    });
+    keep_join_order
+}
+
+fn leading_hint_state(metadata: &Metadata, tables: &[String]) -> HintState {
+    let mut last_index = None;
+    for table in tables {
+        match metadata.table_index_by_hint_name(table) {
+            None => return HintState::Ignored(format!("table {} is not found", table)),
+            Some(index) if last_index.is_some_and(|last| index <= last) => {
+                return HintState::Ignored(
+                    "only the order of the tables in the FROM clause can be kept".to_string(),
+                );
+            }
+            Some(index) => last_index = Some(index),
+        }
+    }
+    HintState::Applied
+}
+
+/// Marks the hints which the optimizer doesn't support as ignored.
+pub fn ignore_unsupported_hints(metadata: &MetadataRef) {
+    set_hints_state(metadata, |_, hint| match hint {
+        QueryHint::NoCse => Some(HintState::Ignored(
+            "common subexpressions are eliminated when building the pipeline".to_string(),
+        )),
+        _ => None,
+    });
+}
+
+/// Resolves the `BROADCAST_JOIN` and `SHUFFLE_JOIN` hints, they only take effect
+/// in distributed queries.
+pub fn join_distribution_hints(
+    metadata: &MetadataRef,
+    distributed: bool,
+) -> Vec<JoinDistributionHint> {
+    let mut metadata = metadata.write();
+    let mut join_hints = vec![];
+    for hint_index in 0..metadata.hints().len() {
+        let (table, broadcast) = match &metadata.hints()[hint_index].0 {
+            QueryHint::BroadcastJoin(table) => (table.clone(), true),
+            QueryHint::ShuffleJoin(table) => (table.clone(), false),
+            _ => continue,
+        };
+
+        let state = match metadata.table_index_by_hint_name(&table) {
+            _ if !distributed => HintState::Ignored("the query is not distributed".to_string()),
+            None => HintState::Ignored(format!("table {} is not found", table)),
+            Some(table_index) => {
+                let columns = metadata
+                    .columns_by_table_index(table_index)
+                    .iter()
+                    .map(|column| column.index())
+                    .collect();
+                join_hints.push(JoinDistributionHint {
+                    hint_index,
+                    table,
+                    broadcast,
+                    columns,
+                });
+                continue;
+            }
+        };
+        metadata.set_hint_state(hint_index, state);
+    }
+    join_hints
+}
+
+/// Keeps the distributions of the join children required by the hint on the
+/// build side, all the distributions are kept if none of them is hinted.
+pub fn filter_join_distributions(
+    ctx: &Arc<dyn TableContext>,
+    hints: &[JoinDistributionHint],
+    plan: &RelOperator,
+    rel_expr: &RelExpr,
+    children_required: Vec<Vec<RequiredProperty>>,
+) -> Result<Vec<Vec<RequiredProperty>>> {
+    if hints.is_empty() || !matches!(plan, RelOperator::Join(_)) {
+        return Ok(children_required);
+    }
+
+    let build_columns = rel_expr
+        .derive_relational_prop_child(1)?
+        .used_columns
+        .clone();
+    let Some(hint) = hints
+        .iter()
+        .find(|hint| !hint.columns.is_disjoint(&build_columns))
+    else {
+        return Ok(children_required);
+    };
+
+    if hint.broadcast {
+        let max_rows = ctx.get_settings().get_broadcast_join_hint_max_rows()?;
+        if rel_expr.derive_cardinality_child(1)?.cardinality > max_rows as f64 {
+            return Ok(children_required);
+        }
+    }
+
+    let hinted = children_required
+        .iter()
+        .filter(|required| match &required[1].distribution {
+            Distribution::Broadcast => hint.broadcast,
+            Distribution::Hash(_) => !hint.broadcast,
+            _ => false,
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    match hinted.is_empty() {
+        true => Ok(children_required),
+        false => Ok(hinted),
+    }
+}
+
+/// Checks whether the join distribution hints took effect in the optimized plan.
+pub fn check_join_distribution_hints(
+    ctx: &Arc<dyn TableContext>,
+    metadata: &MetadataRef,
+    hints: &[JoinDistributionHint],
+    s_expr: &SExpr,
+) -> Result<()> {
+    let mut states = vec![None; hints.len()];
+    collect_join_hint_states(ctx, hints, s_expr, &mut states)?;
+
+    let mut metadata = metadata.write();
+    for (hint, state) in hints.iter().zip(states) {
+        let state = state.unwrap_or_else(|| {
+            HintState::Ignored(format!(
+                "table {} is not on the build side of a join",
+                hint.table
+            ))
+        });
+        metadata.set_hint_state(hint.hint_index, state);
+    }
+    Ok(())
+}
+
+fn collect_join_hint_states(
+    ctx: &Arc<dyn TableContext>,
+    hints: &[JoinDistributionHint],
+    s_expr: &SExpr,
+    states: &mut [Option<HintState>],
+) -> Result<()> {
+    if let RelOperator::Join(join) = s_expr.plan() {
+        let build = s_expr.child(1)?;
+        let build_columns = RelExpr::with_s_expr(build)
+            .derive_relational_prop()?
+            .used_columns
+            .clone();
+        for (hint, state) in hints.iter().zip(states.iter_mut()) {
+            // The hint takes effect if it's applied to any join.
+            if *state == Some(HintState::Applied) || hint.columns.is_disjoint(&build_columns) {
+                continue;
+            }
+            *state = Some(join_hint_state(ctx, hint, join, build)?);
+        }
+    }
+
+    for child in s_expr.children() {
+        collect_join_hint_states(ctx, hints, child, states)?;
+    }
+    Ok(())
+}
+
+fn join_hint_state(
+    ctx: &Arc<dyn TableContext>,
+    hint: &JoinDistributionHint,
+    join: &Join,
+    build: &SExpr,
+) -> Result<HintState> {
+    let reason = match (build.plan(), hint.broadcast) {
+        (RelOperator::Exchange(Exchange::Broadcast), true)
+        | (RelOperator::Exchange(Exchange::Hash(_)), false) => return Ok(HintState::Applied),
+        (RelOperator::Exchange(_), true)
+            if matches!(
+                join.join_type,
+                JoinType::Right
+                    | JoinType::Full
+                    | JoinType::RightAnti
+                    | JoinType::RightSemi
+                    | JoinType::LeftMark
+                    | JoinType::RightSingle
+            ) =>
+        {
+            format!(
+                "the build side of {} join can't be broadcast",
+                join.join_type
+            )
+        }
+        (RelOperator::Exchange(_), true) => {
+            let max_rows = ctx.get_settings().get_broadcast_join_hint_max_rows()?;
+            let rows = RelExpr::with_s_expr(build)
+                .derive_cardinality()?
+                .cardinality;
+            if rows > max_rows as f64 {
+                format!(
+                    "the estimated rows {:.0} of table {} exceed broadcast_join_hint_max_rows {}",
+                    rows, hint.table, max_rows
+                )
+            } else {
+                "the broadcast join is not applicable".to_string()
+            }
+        }
+        (RelOperator::Exchange(_), false) if join.right_conditions.is_empty() => {
+            "the join has no equi conditions to shuffle by".to_string()
+        }
+        _ => "the join is not distributed".to_string(),
+    };
+    Ok(HintState::Ignored(reason))
+}
+
+/// Sets the state of the hints for which `f` returns a state, returns true if any.
+fn set_hints_state(
+    metadata: &MetadataRef,
+    mut f: impl FnMut(&Metadata, &QueryHint) -> Option<HintState>,
+) -> bool {
+    let mut metadata = metadata.write();
+    let mut found = false;
+    for index in 0..metadata.hints().len() {
+        if let Some(state) = f(&metadata, &metadata.hints()[index].0) {
+            metadata.set_hint_state(index, state);
+            found = true;
+        }
+    }
+    found
+}
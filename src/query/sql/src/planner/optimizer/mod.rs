@@ -20,6 +20,7 @@ mod extract;
 mod filter;
 mod format;
 mod group;
+mod hint;
 mod hyper_dp;
 mod m_expr;
 mod memo;
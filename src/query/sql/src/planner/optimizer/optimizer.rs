@@ -34,13 +34,17 @@ use crate::optimizer::distributed::optimize_distributed_query;
 use crate::optimizer::distributed::SortAndLimitPushDownOptimizer;
 use crate::optimizer::filter::DeduplicateJoinConditionOptimizer;
 use crate::optimizer::filter::PullUpFilterOptimizer;
+use crate::optimizer::hint::apply_leading_hints;
+use crate::optimizer::hint::check_join_distribution_hints;
+use crate::optimizer::hint::ignore_unsupported_hints;
+use crate::optimizer::hint::join_distribution_hints;
+use crate::optimizer::hint::rewrite_rules_with_hints;
 use crate::optimizer::hyper_dp::DPhpy;
 use crate::optimizer::rule::TransformResult;
 use crate::optimizer::util::contains_local_table_scan;
 use crate::optimizer::RuleFactory;
 use crate::optimizer::RuleID;
 use crate::optimizer::SExpr;
-use crate::optimizer::DEFAULT_REWRITE_RULES;
 use crate::plans::CopyIntoLocationPlan;
 use crate::plans::Exchange;
 use crate::plans::Join;
@@ -95,12 +99,12 @@ impl OptimizerContext {
 /// until no more rules can be applied.
 pub struct RecursiveOptimizer<'a> {
     ctx: &'a OptimizerContext,
-    rules: &'static [RuleID],
+    rules: &'a [RuleID],
     after_join_reorder: bool,
 }
 
 impl<'a> RecursiveOptimizer<'a> {
-    pub fn new(rules: &'static [RuleID], ctx: &'a OptimizerContext) -> Self {
+    pub fn new(rules: &'a [RuleID], ctx: &'a OptimizerContext) -> Self {
         Self {
             ctx,
             rules,
@@ -177,7 +181,8 @@ pub fn optimize(opt_ctx: OptimizerContext, plan: Plan) -> Result<Plan> {
             ignore_result,
         }),
         Plan::Explain { kind, config, plan } => match kind {
-            ExplainKind::Ast(_) | ExplainKind::Syntax(_) => {
+            // The lineage is extracted from the bound plan.
+            ExplainKind::Ast(_) | ExplainKind::Syntax(_) | ExplainKind::Lineage => {
                 Ok(Plan::Explain { config, kind, plan })
             }
             ExplainKind::Memo(_) => {
@@ -207,7 +212,8 @@ pub fn optimize(opt_ctx: OptimizerContext, plan: Plan) -> Result<Plan> {
                 }
             }
         },
-        Plan::ExplainAnalyze { plan } => Ok(Plan::ExplainAnalyze {
+        Plan::ExplainAnalyze { config, plan } => Ok(Plan::ExplainAnalyze {
+            config,
             plan: Box::new(optimize(opt_ctx, *plan)?),
         }),
         Plan::CopyIntoLocation(CopyIntoLocationPlan { stage, path, from }) => {
@@ -252,12 +258,19 @@ pub fn optimize_query(opt_ctx: OptimizerContext, mut s_expr: SExpr) -> Result<SE
     // Pull up and infer filter.
     s_expr = PullUpFilterOptimizer::new(opt_ctx.metadata.clone()).run(&s_expr)?;
 
-    // Run default rewrite rules
-    s_expr = RecursiveOptimizer::new(&DEFAULT_REWRITE_RULES, &opt_ctx).run(&s_expr)?;
+    // Run default rewrite rules, without the rules disabled by hints.
+    let rewrite_rules = rewrite_rules_with_hints(&opt_ctx.metadata);
+    s_expr = RecursiveOptimizer::new(&rewrite_rules, &opt_ctx).run(&s_expr)?;
+
+    // A `LEADING` hint keeps the join order as written.
+    let enable_join_reorder =
+        opt_ctx.enable_join_reorder && !apply_leading_hints(&opt_ctx.metadata);
+    let join_hints = join_distribution_hints(&opt_ctx.metadata, enable_distributed_query);
+    ignore_unsupported_hints(&opt_ctx.metadata);
 
     // Cost based optimization
     let mut dphyp_optimized = false;
-    if opt_ctx.enable_dphyp && opt_ctx.enable_join_reorder {
+    if opt_ctx.enable_dphyp && enable_join_reorder {
         let (dp_res, optimized) =
             DPhpy::new(opt_ctx.table_ctx.clone(), opt_ctx.metadata.clone()).optimize(&s_expr)?;
         if optimized {
@@ -279,14 +292,22 @@ pub fn optimize_query(opt_ctx: OptimizerContext, mut s_expr: SExpr) -> Result<SE
     let mut cascades = CascadesOptimizer::new(
         opt_ctx.table_ctx.clone(),
         opt_ctx.metadata.clone(),
-        dphyp_optimized,
+        dphyp_optimized || !enable_join_reorder,
         enable_distributed_query,
-    )?;
+    )?
+    .with_join_distribution_hints(join_hints.clone());
 
     // Cascades optimizer may fail due to timeout, fallback to heuristic optimizer in this case.
     s_expr = match cascades.optimize(s_expr.clone()) {
         Ok(mut s_expr) => {
-            let rules = if opt_ctx.enable_join_reorder {
+            check_join_distribution_hints(
+                &opt_ctx.table_ctx,
+                &opt_ctx.metadata,
+                &join_hints,
+                &s_expr,
+            )?;
+
+            let rules = if enable_join_reorder {
                 [RuleID::EliminateEvalScalar, RuleID::CommuteJoin].as_slice()
             } else {
                 [RuleID::EliminateEvalScalar].as_slice()
@@ -315,6 +336,12 @@ pub fn optimize_query(opt_ctx: OptimizerContext, mut s_expr: SExpr) -> Result<SE
             if enable_distributed_query {
                 s_expr = optimize_distributed_query(opt_ctx.table_ctx.clone(), &s_expr)?;
             }
+            check_join_distribution_hints(
+                &opt_ctx.table_ctx,
+                &opt_ctx.metadata,
+                &join_hints,
+                &s_expr,
+            )?;
 
             s_expr
         }
@@ -337,12 +364,19 @@ fn get_optimized_memo(opt_ctx: OptimizerContext, mut s_expr: SExpr) -> Result<Me
         )?;
     }
 
-    // Run default rewrite rules
-    s_expr = RecursiveOptimizer::new(&DEFAULT_REWRITE_RULES, &opt_ctx).run(&s_expr)?;
+    // Run default rewrite rules, without the rules disabled by hints.
+    let rewrite_rules = rewrite_rules_with_hints(&opt_ctx.metadata);
+    s_expr = RecursiveOptimizer::new(&rewrite_rules, &opt_ctx).run(&s_expr)?;
+
+    // A `LEADING` hint keeps the join order as written.
+    let enable_join_reorder =
+        opt_ctx.enable_join_reorder && !apply_leading_hints(&opt_ctx.metadata);
+    let join_hints = join_distribution_hints(&opt_ctx.metadata, enable_distributed_query);
+    ignore_unsupported_hints(&opt_ctx.metadata);
 
     // Cost based optimization
     let mut dphyp_optimized = false;
-    if opt_ctx.enable_dphyp && opt_ctx.enable_join_reorder {
+    if opt_ctx.enable_dphyp && enable_join_reorder {
         let (dp_res, optimized) =
             DPhpy::new(opt_ctx.table_ctx.clone(), opt_ctx.metadata.clone()).optimize(&s_expr)?;
         if optimized {
@@ -353,9 +387,10 @@ fn get_optimized_memo(opt_ctx: OptimizerContext, mut s_expr: SExpr) -> Result<Me
     let mut cascades = CascadesOptimizer::new(
         opt_ctx.table_ctx.clone(),
         opt_ctx.metadata.clone(),
-        dphyp_optimized,
+        dphyp_optimized || !enable_join_reorder,
         enable_distributed_query,
-    )?;
+    )?
+    .with_join_distribution_hints(join_hints);
     cascades.optimize(s_expr)?;
 
     Ok(cascades.memo)
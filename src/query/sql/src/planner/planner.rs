@@ -31,6 +31,7 @@ use parking_lot::RwLock;
 
 use super::semantic::AggregateRewriter;
 use super::semantic::DistinctToGroupBy;
+use crate::extract_lineage;
 use crate::optimizer::optimize;
 use crate::optimizer::OptimizerContext;
 use crate::plans::Insert;
@@ -112,6 +113,16 @@ impl Planner {
                 );
                 let plan = binder.bind(&stmt).await?;
 
+                // Attach the column lineage of the write statements to the query log
+                if matches!(plan, Plan::Insert(_) | Plan::CreateTable(_))
+                    && settings.get_enable_query_log_lineage()?
+                {
+                    if let Some(lineage) = extract_lineage(&plan)? {
+                        self.ctx
+                            .attach_query_lineage(serde_json::to_value(lineage)?);
+                    }
+                }
+
                 // Step 4: Optimize the SExpr with optimizers, and generate optimized physical SExpr
                 let opt_ctx = OptimizerContext::new(self.ctx.clone(), metadata.clone())
                     .with_enable_distributed_optimization(!self.ctx.get_cluster().is_empty())
@@ -31,6 +31,7 @@ use databend_common_meta_app::schema::CreateOption;
 use databend_common_meta_app::schema::TableNameIdent;
 use databend_common_meta_app::schema::UndropTableReq;
 use databend_common_meta_app::storage::StorageParams;
+use databend_storages_common_table_meta::table::CheckConstraint;
 
 use crate::plans::Plan;
 
@@ -295,6 +296,36 @@ impl DropTableColumnPlan {
     }
 }
 
+// Table add check constraint
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddTableConstraintPlan {
+    pub catalog: String,
+    pub database: String,
+    pub table: String,
+    pub constraint: CheckConstraint,
+}
+
+impl AddTableConstraintPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
+
+// Table drop check constraint
+#[derive(Clone, Debug, PartialEq)]
+pub struct DropTableConstraintPlan {
+    pub catalog: String,
+    pub database: String,
+    pub table: String,
+    pub name: String,
+}
+
+impl DropTableConstraintPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
+
 // ModifyColumnAction after name resolved, used in ModifyTableColumnPlan
 #[derive(Debug, Clone, PartialEq)]
 pub enum ModifyColumnAction {
@@ -46,6 +46,7 @@ mod scan;
 mod setting;
 pub mod share;
 mod sort;
+mod system;
 mod union_all;
 mod update;
 mod window;
@@ -89,6 +90,7 @@ pub use scan::*;
 pub use setting::*;
 pub use share::*;
 pub use sort::*;
+pub use system::SystemPlan;
 pub use udf::*;
 pub use union_all::UnionAll;
 pub use update::*;
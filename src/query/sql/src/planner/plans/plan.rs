@@ -26,9 +26,11 @@ use databend_common_expression::DataSchemaRefExt;
 
 use super::SetSecondaryRolesPlan;
 use crate::binder::ExplainConfig;
+use crate::lineage_schema;
 use crate::optimizer::SExpr;
 use crate::plans::copy_into_location::CopyIntoLocationPlan;
 use crate::plans::AddTableColumnPlan;
+use crate::plans::AddTableConstraintPlan;
 use crate::plans::AlterNetworkPolicyPlan;
 use crate::plans::AlterPasswordPolicyPlan;
 use crate::plans::AlterShareTenantsPlan;
@@ -83,6 +85,7 @@ use crate::plans::DropStagePlan;
 use crate::plans::DropStreamPlan;
 use crate::plans::DropTableClusterKeyPlan;
 use crate::plans::DropTableColumnPlan;
+use crate::plans::DropTableConstraintPlan;
 use crate::plans::DropTablePlan;
 use crate::plans::DropTaskPlan;
 use crate::plans::DropUDFPlan;
@@ -128,6 +131,7 @@ use crate::plans::ShowRolesPlan;
 use crate::plans::ShowShareEndpointPlan;
 use crate::plans::ShowSharesPlan;
 use crate::plans::ShowTasksPlan;
+use crate::plans::SystemPlan;
 use crate::plans::TruncateTablePlan;
 use crate::plans::UnSettingPlan;
 use crate::plans::UndropDatabasePlan;
@@ -160,11 +164,14 @@ pub enum Plan {
     },
     ExplainAst {
         formatted_string: String,
+        config: ExplainConfig,
     },
     ExplainSyntax {
         formatted_sql: String,
+        config: ExplainConfig,
     },
     ExplainAnalyze {
+        config: ExplainConfig,
         plan: Box<Plan>,
     },
 
@@ -197,6 +204,8 @@ pub enum Plan {
     RenameTableColumn(Box<RenameTableColumnPlan>),
     AddTableColumn(Box<AddTableColumnPlan>),
     DropTableColumn(Box<DropTableColumnPlan>),
+    AddTableConstraint(Box<AddTableConstraintPlan>),
+    DropTableConstraint(Box<DropTableConstraintPlan>),
     ModifyTableColumn(Box<ModifyTableColumnPlan>),
     AlterTableClusterKey(Box<AlterTableClusterKeyPlan>),
     DropTableClusterKey(Box<DropTableClusterKeyPlan>),
@@ -282,6 +291,7 @@ pub enum Plan {
     SetVariable(Box<SettingPlan>),
     UnSetVariable(Box<UnSettingPlan>),
     Kill(Box<KillPlan>),
+    System(Box<SystemPlan>),
 
     // Share
     CreateShareEndpoint(Box<CreateShareEndpointPlan>),
@@ -400,6 +410,10 @@ impl Plan {
                 bind_context,
                 ..
             } => bind_context.output_schema(),
+            Plan::Explain {
+                kind: ExplainKind::Lineage,
+                ..
+            } => lineage_schema(),
             Plan::Explain { .. }
             | Plan::ExplainAst { .. }
             | Plan::ExplainSyntax { .. }
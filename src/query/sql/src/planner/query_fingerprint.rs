@@ -0,0 +1,89 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_ast::parser::token::TokenKind;
+use databend_common_ast::parser::token::Tokenizer;
+use sha2::Digest;
+use sha2::Sha256;
+
+const PLACEHOLDER: &str = "?";
+
+/// Normalizes the query text so that the queries which only differ in literals, whitespaces,
+/// comments and keyword case have the same text.
+///
+/// - The literals are replaced with `?`, and a list of literals like `IN (1, 2, 3)` is
+///   collapsed to a single `?`.
+/// - The keywords are upper-cased and the unquoted identifiers are lower-cased.
+/// - The values of `INSERT ... VALUES` are dropped.
+/// - The text after an unrecognized token is kept as it is.
+pub fn normalize_query(sql: &str) -> String {
+    let mut tokens: Vec<String> = vec![];
+    let mut is_insert = false;
+    for token in Tokenizer::new(sql) {
+        let token = match token {
+            Ok(token) => token,
+            Err(err) => {
+                let start = err.span().map(|span| span.start()).unwrap_or(0);
+                let rest = sql[start..].trim();
+                if !rest.is_empty() {
+                    tokens.push(rest.to_string());
+                }
+                break;
+            }
+        };
+
+        match token.kind {
+            TokenKind::EOI => break,
+            TokenKind::INSERT | TokenKind::REPLACE if tokens.is_empty() => is_insert = true,
+            TokenKind::VALUES if is_insert => {
+                tokens.push(token.text().to_uppercase());
+                break;
+            }
+            _ => {}
+        }
+
+        if is_literal(token.kind, token.text()) {
+            // Collapse `?, ?` into `?`.
+            if tokens.len() >= 2
+                && tokens[tokens.len() - 1] == ","
+                && tokens[tokens.len() - 2] == PLACEHOLDER
+            {
+                tokens.pop();
+                continue;
+            }
+            tokens.push(PLACEHOLDER.to_string());
+        } else if token.kind == TokenKind::Ident {
+            tokens.push(token.text().to_lowercase());
+        } else if token.kind.is_keyword() {
+            tokens.push(token.text().to_uppercase());
+        } else {
+            tokens.push(token.text().to_string());
+        }
+    }
+
+    tokens.join(" ")
+}
+
+/// The fingerprint of the query shape, it's the sha256 of the normalized query text.
+pub fn query_fingerprint(sql: &str) -> String {
+    format!("{:x}", Sha256::digest(normalize_query(sql)))
+}
+
+// The quoted identifiers are also tokenized as `QuotedString`.
+fn is_literal(kind: TokenKind, text: &str) -> bool {
+    match kind {
+        TokenKind::QuotedString => text.starts_with('\''),
+        _ => kind.is_literal(),
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+
+/// The known optimizer hints, i.e. the hints in `/*+ ... */` other than `SET_VAR`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QueryHint {
+    /// Broadcast the table when it's on the build side of a join.
+    BroadcastJoin(String),
+    /// Hash shuffle both sides of the join when the table is on the build side.
+    ShuffleJoin(String),
+    /// Disable the common subexpression elimination.
+    NoCse,
+    /// Keep the filters out of the table scans.
+    NoPushdown,
+    /// Join the tables in the given order.
+    Leading(Vec<String>),
+}
+
+impl QueryHint {
+    /// Resolves a hint by the registry of the known hints.
+    pub fn try_create(name: &str, args: Option<Vec<String>>) -> Result<QueryHint> {
+        let upper_name = name.to_uppercase();
+        match (upper_name.as_str(), args) {
+            ("BROADCAST_JOIN", Some(mut args)) if args.len() == 1 => {
+                Ok(QueryHint::BroadcastJoin(args.remove(0)))
+            }
+            ("SHUFFLE_JOIN", Some(mut args)) if args.len() == 1 => {
+                Ok(QueryHint::ShuffleJoin(args.remove(0)))
+            }
+            ("NO_CSE", None) => Ok(QueryHint::NoCse),
+            ("NO_PUSHDOWN", None) => Ok(QueryHint::NoPushdown),
+            ("LEADING", Some(args)) if args.len() >= 2 => Ok(QueryHint::Leading(args)),
+            ("BROADCAST_JOIN" | "SHUFFLE_JOIN", _) => Err(ErrorCode::SemanticError(format!(
+                "Hint {} requires exactly one table, ignored",
+                upper_name
+            ))),
+            ("NO_CSE" | "NO_PUSHDOWN", _) => Err(ErrorCode::SemanticError(format!(
+                "Hint {} does not take arguments, ignored",
+                upper_name
+            ))),
+            ("LEADING", _) => Err(ErrorCode::SemanticError(
+                "Hint LEADING requires at least two tables, ignored",
+            )),
+            _ => Err(ErrorCode::SemanticError(format!(
+                "Unknown hint {}, ignored",
+                name
+            ))),
+        }
+    }
+}
+
+impl Display for QueryHint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryHint::BroadcastJoin(table) => write!(f, "BROADCAST_JOIN({})", table),
+            QueryHint::ShuffleJoin(table) => write!(f, "SHUFFLE_JOIN({})", table),
+            QueryHint::NoCse => write!(f, "NO_CSE"),
+            QueryHint::NoPushdown => write!(f, "NO_PUSHDOWN"),
+            QueryHint::Leading(tables) => write!(f, "LEADING({})", tables.join(" ")),
+        }
+    }
+}
+
+/// Whether a hint took effect, shown by `EXPLAIN`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HintState {
+    /// The statement is not optimized yet.
+    Pending,
+    Applied,
+    Ignored(String),
+}
+
+impl Display for HintState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HintState::Pending => write!(f, "pending"),
+            HintState::Applied => write!(f, "applied"),
+            HintState::Ignored(reason) => write!(f, "ignored, {}", reason),
+        }
+    }
+}
@@ -324,52 +324,13 @@ impl<'a> TypeChecker<'a> {
                 right,
                 not,
             } => {
-                let left_null_expr = Box::new(Expr::IsNull {
-                    span: *span,
-                    expr: left.clone(),
-                    not: false,
-                });
-                let right_null_expr = Box::new(Expr::IsNull {
-                    span: *span,
-                    expr: right.clone(),
-                    not: false,
-                });
-                let op = if *not {
-                    BinaryOperator::Eq
+                let name = if *not {
+                    "is_not_distinct_from"
                 } else {
-                    BinaryOperator::NotEq
+                    "is_distinct_from"
                 };
-                let (scalar, _) = *self
-                    .resolve_function(*span, "if", vec![], &[
-                        &Expr::BinaryOp {
-                            span: *span,
-                            op: BinaryOperator::And,
-                            left: left_null_expr.clone(),
-                            right: right_null_expr.clone(),
-                        },
-                        &Expr::Literal {
-                            span: *span,
-                            lit: Literal::Boolean(*not),
-                        },
-                        &Expr::BinaryOp {
-                            span: *span,
-                            op: BinaryOperator::Or,
-                            left: left_null_expr.clone(),
-                            right: right_null_expr.clone(),
-                        },
-                        &Expr::Literal {
-                            span: *span,
-                            lit: Literal::Boolean(!*not),
-                        },
-                        &Expr::BinaryOp {
-                            span: *span,
-                            op,
-                            left: left.clone(),
-                            right: right.clone(),
-                        },
-                    ])
-                    .await?;
-                self.resolve_scalar_function_call(*span, "assume_not_null", vec![], vec![scalar])?
+                self.resolve_function(*span, name, vec![], &[left.as_ref(), right.as_ref()])
+                    .await?
             }
 
             Expr::InList {
@@ -394,10 +355,19 @@ impl<'a> TypeChecker<'a> {
                 }
 
                 let get_max_inlist_to_or = self.ctx.get_settings().get_max_inlist_to_or()? as usize;
-                if list.len() > get_max_inlist_to_or && list.iter().all(satisfy_contain_func) {
+                // The NULLs in the list can't be looked up, they only turn a miss into NULL.
+                let (nulls, values): (Vec<_>, Vec<_>) = list.iter().partition(|e| {
+                    matches!(e, Expr::Literal {
+                        lit: Literal::Null,
+                        ..
+                    })
+                });
+                if values.len() > get_max_inlist_to_or
+                    && values.iter().all(|e| satisfy_contain_func(e))
+                {
                     let array_expr = Expr::Array {
                         span: *span,
-                        exprs: list.clone(),
+                        exprs: values.into_iter().cloned().collect(),
                     };
                     // Deduplicate the array.
                     let array_expr = Expr::FunctionCall {
@@ -411,28 +381,42 @@ impl<'a> TypeChecker<'a> {
                             distinct: false,
                         },
                     };
-                    let args = vec![&array_expr, expr.as_ref()];
+                    let mut result = Expr::FunctionCall {
+                        span: *span,
+                        func: ASTFunctionCall {
+                            distinct: false,
+                            name: Identifier {
+                                name: "contains".to_string(),
+                                quote: None,
+                                span: *span,
+                            },
+                            args: vec![array_expr, expr.as_ref().clone()],
+                            params: vec![],
+                            window: None,
+                            lambda: None,
+                        },
+                    };
+                    // `x IN (1, 2, NULL)` is TRUE if `x` is 1 or 2, otherwise it's NULL,
+                    // which is `contains([1, 2], x) OR NULL` in the three-valued logic.
+                    if !nulls.is_empty() {
+                        result = Expr::BinaryOp {
+                            span: *span,
+                            op: BinaryOperator::Or,
+                            left: Box::new(result),
+                            right: Box::new(Expr::Literal {
+                                span: *span,
+                                lit: Literal::Null,
+                            }),
+                        };
+                    }
                     if *not {
-                        self.resolve_unary_op(*span, &UnaryOperator::Not, &Expr::FunctionCall {
+                        result = Expr::UnaryOp {
                             span: *span,
-                            func: ASTFunctionCall {
-                                distinct: false,
-                                name: Identifier {
-                                    name: "contains".to_string(),
-                                    quote: None,
-                                    span: *span,
-                                },
-                                args: args.iter().copied().cloned().collect(),
-                                params: vec![],
-                                window: None,
-                                lambda: None,
-                            },
-                        })
-                        .await?
-                    } else {
-                        self.resolve_function(*span, "contains", vec![], &args)
-                            .await?
+                            op: UnaryOperator::Not,
+                            expr: Box::new(result),
+                        };
                     }
+                    self.resolve(&result).await?
                 } else {
                     let mut result = list
                         .iter()
@@ -2010,6 +1994,18 @@ impl<'a> TypeChecker<'a> {
             )));
         }
 
+        // The seeded `rand()` takes the id of its call site as parameter, so that two calls
+        // in one query don't return the same values.
+        if func_name == "rand"
+            && args.is_empty()
+            && params.is_empty()
+            && self.ctx.get_settings().get_query_random_seed()?.is_some()
+        {
+            let call_site = self.metadata.write().next_random_call_site();
+            let params = vec![Scalar::Number(NumberScalar::UInt64(call_site))];
+            return self.resolve_scalar_function_call(span, func_name, params, args);
+        }
+
         self.resolve_scalar_function_call(span, func_name, params, args)
     }
 
@@ -2024,7 +2020,7 @@ impl<'a> TypeChecker<'a> {
         let arguments = args.iter().map(|v| v.as_raw_expr()).collect::<Vec<_>>();
 
         // inject the params
-        if ["round", "truncate"].contains(&func_name)
+        if ["round", "truncate", "ceil", "ceiling", "floor"].contains(&func_name)
             && !args.is_empty()
             && params.is_empty()
             && args[0].data_type()?.remove_nullable().is_decimal()
@@ -2111,9 +2107,13 @@ impl<'a> TypeChecker<'a> {
         right: &Expr,
     ) -> Result<Box<(ScalarExpr, DataType)>> {
         match op {
-            BinaryOperator::NotLike | BinaryOperator::NotRegexp | BinaryOperator::NotRLike => {
+            BinaryOperator::NotLike
+            | BinaryOperator::NotILike
+            | BinaryOperator::NotRegexp
+            | BinaryOperator::NotRLike => {
                 let positive_op = match op {
                     BinaryOperator::NotLike => BinaryOperator::Like,
+                    BinaryOperator::NotILike => BinaryOperator::ILike,
                     BinaryOperator::NotRegexp => BinaryOperator::Regexp,
                     BinaryOperator::NotRLike => BinaryOperator::RLike,
                     _ => unreachable!(),
@@ -2153,6 +2153,24 @@ impl<'a> TypeChecker<'a> {
                 self.resolve_function(span, name.as_str(), vec![], &[left, right])
                     .await
             }
+            BinaryOperator::Plus
+            | BinaryOperator::Minus
+            | BinaryOperator::Multiply
+            | BinaryOperator::Divide
+            | BinaryOperator::Div
+            | BinaryOperator::IntDiv
+            | BinaryOperator::Modulo
+                if self.ctx.get_settings().get_loose_arithmetic_coercion()? =>
+            {
+                let box (left, left_type) = self.resolve(left).await?;
+                let box (right, right_type) = self.resolve(right).await?;
+                let left = self.loose_coerce_arithmetic_arg(span, left, &left_type, &right_type)?;
+                let right =
+                    self.loose_coerce_arithmetic_arg(span, right, &right_type, &left_type)?;
+                self.resolve_scalar_function_call(span, &op.to_func_name(), vec![], vec![
+                    left, right,
+                ])
+            }
             other => {
                 let name = other.to_func_name();
                 self.resolve_function(span, name.as_str(), vec![], &[left, right])
@@ -2161,6 +2179,32 @@ impl<'a> TypeChecker<'a> {
         }
     }
 
+    /// Coerces a string operand of the arithmetic with a number or another string to Float64 as
+    /// MySQL does, with the `loose_arithmetic_coercion` setting. The strings which are not numbers
+    /// become NULL, and are counted in the warnings and the statistics of the query.
+    fn loose_coerce_arithmetic_arg(
+        &self,
+        span: Span,
+        arg: ScalarExpr,
+        data_type: &DataType,
+        other_type: &DataType,
+    ) -> Result<ScalarExpr> {
+        let other_type = other_type.remove_nullable();
+        if data_type.remove_nullable() != DataType::String
+            || !(other_type.is_numeric()
+                || matches!(
+                    other_type,
+                    DataType::Decimal(_) | DataType::String | DataType::Null
+                ))
+        {
+            return Ok(arg);
+        }
+
+        let box (arg, _) =
+            self.resolve_scalar_function_call(span, "loose_to_float64", vec![], vec![arg])?;
+        Ok(arg)
+    }
+
     /// Resolve unary expressions.
     #[async_recursion::async_recursion]
     #[async_backtrace::framed]
@@ -39,10 +39,15 @@ pub use statistics::*;
 pub(crate) use testing::*;
 pub(crate) use utils::*;
 pub use versions::testify_version;
+pub use versions::FormatVersions;
 pub use versions::SegmentInfoVersion;
 pub use versions::SnapshotVersion;
 pub use versions::TableSnapshotStatisticsVersion;
 pub use versions::Versioned;
+pub use versions::FORMAT_VERSIONS;
+pub use versions::SEGMENT_FORMAT_VERSIONS;
+pub use versions::SNAPSHOT_FORMAT_VERSIONS;
+pub use versions::TABLE_SNAPSHOT_STATISTICS_FORMAT_VERSIONS;
 
 // - export legacy versioned table meta types for testing purposes
 //   currently, only used by crate `test_kits`
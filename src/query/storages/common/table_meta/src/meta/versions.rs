@@ -132,6 +132,72 @@ where T: Versioned<V> {
     t
 }
 
+/// The versions of a kind of persisted meta that this binary reads, in ascending order.
+///
+/// Older versions are upgraded on read, the latest one is the version written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FormatVersions {
+    pub kind: &'static str,
+    pub supported: &'static [u64],
+}
+
+impl FormatVersions {
+    pub fn latest(&self) -> u64 {
+        self.supported[self.supported.len() - 1]
+    }
+
+    pub fn supported_list(&self) -> String {
+        self.supported
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+pub const SEGMENT_FORMAT_VERSIONS: FormatVersions = FormatVersions {
+    kind: "segment",
+    supported: &[0, 1, 2, 3, 4],
+};
+
+pub const SNAPSHOT_FORMAT_VERSIONS: FormatVersions = FormatVersions {
+    kind: "snapshot",
+    supported: &[0, 1, 2, 3, 4],
+};
+
+pub const TABLE_SNAPSHOT_STATISTICS_FORMAT_VERSIONS: FormatVersions = FormatVersions {
+    kind: "table snapshot statistics",
+    supported: &[0, 2],
+};
+
+/// All kinds of persisted meta, reported by `system.format_versions`.
+pub const FORMAT_VERSIONS: [FormatVersions; 3] = [
+    SEGMENT_FORMAT_VERSIONS,
+    SNAPSHOT_FORMAT_VERSIONS,
+    TABLE_SNAPSHOT_STATISTICS_FORMAT_VERSIONS,
+];
+
+/// Error for a version of persisted meta that this binary can not read.
+///
+/// Meta of an older version is always upgraded on read, so a version above the latest one can
+/// only have been written by a newer databend-query. Reading it is refused rather than risking
+/// a misinterpretation of the format after a downgrade.
+fn unsupported_version(versions: &FormatVersions, value: u64) -> ErrorCode {
+    let kind = versions.kind;
+    let latest = versions.latest();
+    if value > latest {
+        ErrorCode::StorageUnsupported(format!(
+            "{kind} version {value} is newer than the latest version {latest} supported by this databend-query, \
+            it was written by a newer release and downgrading is not supported"
+        ))
+    } else {
+        ErrorCode::Internal(format!(
+            "unknown {kind} version {value}, versions supported: {}",
+            versions.supported_list()
+        ))
+    }
+}
+
 mod converters {
 
     use super::*;
@@ -149,9 +215,7 @@ mod converters {
                 2 => Ok(SegmentInfoVersion::V2(testify_version::<_, 2>(PhantomData))),
                 3 => Ok(SegmentInfoVersion::V3(testify_version::<_, 3>(PhantomData))),
                 4 => Ok(SegmentInfoVersion::V4(testify_version::<_, 4>(PhantomData))),
-                _ => Err(ErrorCode::Internal(format!(
-                    "unknown segment version {value}, versions supported: 0, 1, 2, 3, 4"
-                ))),
+                _ => Err(unsupported_version(&SEGMENT_FORMAT_VERSIONS, value)),
             }
         }
     }
@@ -165,9 +229,7 @@ mod converters {
                 2 => Ok(SnapshotVersion::V2(testify_version::<_, 2>(PhantomData))),
                 3 => Ok(SnapshotVersion::V3(testify_version::<_, 3>(PhantomData))),
                 4 => Ok(SnapshotVersion::V4(testify_version::<_, 4>(PhantomData))),
-                _ => Err(ErrorCode::Internal(format!(
-                    "unknown snapshot segment version {value}, versions supported: 0, 1, 2, 3, 4"
-                ))),
+                _ => Err(unsupported_version(&SNAPSHOT_FORMAT_VERSIONS, value)),
             }
         }
     }
@@ -182,9 +244,10 @@ mod converters {
                 2 => Ok(TableSnapshotStatisticsVersion::V2(testify_version::<_, 2>(
                     PhantomData,
                 ))),
-                _ => Err(ErrorCode::Internal(format!(
-                    "unknown table snapshot statistics version {value}, versions supported: 0"
-                ))),
+                _ => Err(unsupported_version(
+                    &TABLE_SNAPSHOT_STATISTICS_FORMAT_VERSIONS,
+                    value,
+                )),
             }
         }
     }
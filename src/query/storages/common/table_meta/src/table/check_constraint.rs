@@ -0,0 +1,46 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use databend_common_exception::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::table::OPT_KEY_CHECK_CONSTRAINTS;
+
+/// A named `CHECK (<expr>)` constraint of a table.
+///
+/// The expression is kept as normalized SQL text, and is re-bound against
+/// the table schema whenever data is ingested.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CheckConstraint {
+    pub name: String,
+    pub expr: String,
+}
+
+impl CheckConstraint {
+    /// Encode the constraints into the value of `OPT_KEY_CHECK_CONSTRAINTS`.
+    pub fn encode(constraints: &[CheckConstraint]) -> Result<String> {
+        Ok(serde_json::to_string(constraints)?)
+    }
+
+    /// Decode the constraints from the table options, in declaration order.
+    pub fn decode_from_options(options: &BTreeMap<String, String>) -> Result<Vec<CheckConstraint>> {
+        match options.get(OPT_KEY_CHECK_CONSTRAINTS) {
+            Some(value) => Ok(serde_json::from_str(value)?),
+            None => Ok(vec![]),
+        }
+    }
+}
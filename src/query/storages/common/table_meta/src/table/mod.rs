@@ -12,11 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod check_constraint;
 mod stream_keys;
 mod table_compression;
 mod table_keys;
 mod table_prefix;
 
+pub use check_constraint::CheckConstraint;
 pub use stream_keys::*;
 pub use table_compression::TableCompression;
 pub use table_keys::*;
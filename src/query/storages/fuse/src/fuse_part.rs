@@ -26,6 +26,7 @@ use databend_common_catalog::plan::PartInfo;
 use databend_common_catalog::plan::PartInfoPtr;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
+use databend_common_expression::block_origin;
 use databend_common_expression::ColumnId;
 use databend_common_expression::Scalar;
 use databend_storages_common_pruner::BlockMetaIndex;
@@ -114,6 +115,17 @@ impl FusePartInfo {
             .map(|meta| meta.page_size)
             .unwrap_or(self.nums_rows)
     }
+
+    /// The origin of the rows read from `offset` of the block, see `DataBlock::with_origin`.
+    pub fn block_origin(&self, offset: usize) -> u64 {
+        block_origin(self.location.as_bytes(), offset as u64)
+    }
+
+    /// The offset of the first row of the part in the block.
+    pub fn first_row(&self) -> usize {
+        self.range()
+            .map_or(0, |range| self.page_size() * range.start)
+    }
 }
 
 /// Fuse table lazy partition information.
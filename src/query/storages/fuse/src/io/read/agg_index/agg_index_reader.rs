@@ -18,7 +18,6 @@ use databend_common_catalog::plan::AggIndexInfo;
 use databend_common_catalog::plan::AggIndexMeta;
 use databend_common_catalog::table_context::TableContext;
 use databend_common_exception::Result;
-use databend_common_expression::types::BooleanType;
 use databend_common_expression::types::DataType;
 use databend_common_expression::BlockEntry;
 use databend_common_expression::DataBlock;
@@ -102,11 +101,8 @@ impl AggIndexReader {
 
         // 1. Filter the block if there is a filter.
         let block = if let Some(filter) = self.filter.as_ref() {
-            let filter = evaluator
-                .run(filter)?
-                .try_downcast::<BooleanType>()
-                .unwrap();
-            block.filter_boolean_value(&filter)?
+            let filter = evaluator.run(filter)?;
+            block.filter_block(&filter)?
         } else {
             block
         };
@@ -608,9 +608,7 @@ impl NativeDeserializeDataTransform {
             let part = self.parts.front().unwrap();
             let part = FusePartInfo::from_part(part)?;
 
-            if let Some(range) = part.range() {
-                self.read_state.offset = part.page_size() * range.start;
-            }
+            self.read_state.offset = part.first_row();
 
             for (index, column_node) in self.block_reader.project_column_nodes.iter().enumerate() {
                 let readers = chunks.remove(&index).unwrap_or_default();
@@ -958,6 +956,7 @@ impl NativeDeserializeDataTransform {
             let mut block = self.build_default_block(fuse_part)?;
             // for merge into target build
             block = add_row_prefix_meta(self.need_reserve_block_info, fuse_part, block)?;
+            block = block.with_origin(fuse_part.block_origin(fuse_part.first_row()));
             self.add_output_block(block);
             self.finish_partition();
             return Ok(());
@@ -1009,6 +1008,7 @@ impl NativeDeserializeDataTransform {
 
         // for merge into target build
         block = add_row_prefix_meta(self.need_reserve_block_info, fuse_part, block)?;
+        block = block.with_origin(fuse_part.block_origin(self.read_state.offset));
 
         self.read_state.offset += origin_num_rows;
 
@@ -1104,6 +1104,8 @@ impl Processor for NativeDeserializeDataTransform {
                     }
                     data_block =
                         add_row_prefix_meta(self.need_reserve_block_info, fuse_part, data_block)?;
+                    data_block =
+                        data_block.with_origin(fuse_part.block_origin(fuse_part.first_row()));
 
                     self.finish_partition();
                     self.add_output_block(data_block);
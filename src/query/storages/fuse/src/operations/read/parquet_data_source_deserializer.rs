@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::ops::BitAnd;
 use std::sync::Arc;
 use std::time::Instant;
@@ -26,15 +28,24 @@ use databend_common_base::runtime::profile::ProfileStatisticsName;
 use databend_common_catalog::plan::gen_mutation_stream_meta;
 use databend_common_catalog::plan::DataSourcePlan;
 use databend_common_catalog::plan::PartInfoPtr;
+use databend_common_catalog::plan::Projection;
 use databend_common_catalog::table_context::TableContext;
 use databend_common_exception::Result;
+use databend_common_expression::types::BooleanType;
 use databend_common_expression::types::DataType;
+use databend_common_expression::BlockEntry;
 use databend_common_expression::BlockMetaInfoDowncast;
+use databend_common_expression::ColumnId;
 use databend_common_expression::DataBlock;
 use databend_common_expression::DataField;
 use databend_common_expression::DataSchema;
+use databend_common_expression::Evaluator;
+use databend_common_expression::Expr;
 use databend_common_expression::FieldIndex;
+use databend_common_expression::FunctionContext;
 use databend_common_expression::Scalar;
+use databend_common_expression::Value;
+use databend_common_functions::BUILTIN_FUNCTIONS;
 use databend_common_metrics::storage::*;
 use databend_common_pipeline_core::processors::Event;
 use databend_common_pipeline_core::processors::InputPort;
@@ -51,11 +62,207 @@ use super::util::need_reserve_block_info;
 use crate::fuse_part::FusePartInfo;
 use crate::io::AggIndexReader;
 use crate::io::BlockReader;
+use crate::io::MergeIOReadResult;
 use crate::io::UncompressedBuffer;
 use crate::io::VirtualColumnReader;
 use crate::operations::read::data_source_with_meta::DataSourceWithMeta;
 use crate::operations::read::runtime_filter_prunner::update_bitmap_with_bloom_filter;
 
+/// Late materialization of a parquet block.
+///
+/// The columns referenced by the pushed-down filter are deserialized first. The other
+/// projected columns are only deserialized if some rows pass the filter, and the block is
+/// filtered in the scan if the selected rows are no more than `threshold` percent.
+/// The filter is still evaluated by the `Filter` above the scan, so it is only used to
+/// skip work here.
+struct LateMaterialization {
+    filter_reader: Arc<BlockReader>,
+    remain_reader: Arc<BlockReader>,
+    filter_column_ids: HashSet<ColumnId>,
+    /// Positions of the columns of `filter_reader` in the block of the source reader.
+    filter_positions: Vec<usize>,
+    /// Positions of the columns of `remain_reader` in the block of the source reader.
+    remain_positions: Vec<usize>,
+    filter: Expr,
+    func_ctx: FunctionContext,
+    threshold: u64,
+}
+
+/// The block of a part deserialized with late materialization.
+enum LateMaterialized {
+    /// The block, with the rows selected by the filter if it is filtered in the scan.
+    Block(DataBlock, Option<Bitmap>),
+    /// No rows pass the filter, only the filter columns of `bytes` are deserialized.
+    Filtered { bytes: usize },
+}
+
+impl LateMaterialization {
+    fn try_create(
+        ctx: Arc<dyn TableContext>,
+        block_reader: &BlockReader,
+        plan: &DataSourcePlan,
+        has_virtual_reader: bool,
+    ) -> Result<Option<Self>> {
+        let threshold = ctx
+            .get_settings()
+            .get_fuse_late_materialization_threshold()?;
+        if threshold == 0 || has_virtual_reader || block_reader.update_stream_columns() {
+            return Ok(None);
+        }
+        let Some(push_downs) = plan.push_downs.as_ref() else {
+            return Ok(None);
+        };
+        let Some(filters) = push_downs.filters.as_ref() else {
+            return Ok(None);
+        };
+        if !push_downs.is_deterministic
+            || push_downs.prewhere.is_some()
+            || push_downs.agg_index.is_some()
+            || push_downs.change_type.is_some()
+        {
+            return Ok(None);
+        }
+        let Projection::Columns(indices) = &block_reader.projection else {
+            return Ok(None);
+        };
+
+        let filter = filters.filter.as_expr(&BUILTIN_FUNCTIONS);
+        if filter.data_type() != &DataType::Boolean {
+            return Ok(None);
+        }
+
+        let schema = block_reader.schema();
+        let mut filter_positions = Vec::new();
+        for name in filter.column_refs().keys() {
+            match schema.index_of(name) {
+                Ok(position) => filter_positions.push(position),
+                Err(_) => return Ok(None),
+            }
+        }
+        filter_positions.sort();
+        // Nothing to save if the filter reads most of the columns.
+        if filter_positions.is_empty() || filter_positions.len() * 2 > indices.len() {
+            return Ok(None);
+        }
+        let remain_positions = (0..indices.len())
+            .filter(|i| !filter_positions.contains(i))
+            .collect::<Vec<_>>();
+
+        let sub_reader = |positions: &[usize]| {
+            BlockReader::create(
+                ctx.clone(),
+                block_reader.operator.clone(),
+                block_reader.original_schema.clone(),
+                Projection::Columns(positions.iter().map(|i| indices[*i]).collect()),
+                false,
+                false,
+                block_reader.put_cache,
+            )
+        };
+        let filter_reader = sub_reader(&filter_positions)?;
+        let remain_reader = sub_reader(&remain_positions)?;
+
+        let filter_schema = filter_reader.schema();
+        let filter = filter.project_column_ref(|name| filter_schema.index_of(name).unwrap());
+        let filter_column_ids = filter_reader
+            .project_indices
+            .values()
+            .map(|(column_id, _, _)| *column_id)
+            .collect();
+
+        Ok(Some(LateMaterialization {
+            filter_reader,
+            remain_reader,
+            filter_column_ids,
+            filter_positions,
+            remain_positions,
+            filter,
+            func_ctx: ctx.get_function_context()?,
+            threshold,
+        }))
+    }
+
+    /// Deserialize the block of `part`, together with the rows selected by the filter.
+    ///
+    /// The other columns are not deserialized if no rows pass the filter.
+    fn deserialize(
+        &self,
+        part: &FusePartInfo,
+        data: &MergeIOReadResult,
+        uncompressed_buffer: &Arc<UncompressedBuffer>,
+    ) -> Result<LateMaterialized> {
+        let (filter_chunks, remain_chunks): (HashMap<_, _>, HashMap<_, _>) = data
+            .columns_chunks()?
+            .into_iter()
+            .partition(|(column_id, _)| self.filter_column_ids.contains(column_id));
+
+        let filter_block = self.filter_reader.deserialize_parquet_chunks_with_buffer(
+            &part.location,
+            part.nums_rows,
+            &part.compression,
+            &part.columns_meta,
+            filter_chunks,
+            Some(uncompressed_buffer.clone()),
+        )?;
+        Profile::record_usize_profile(
+            ProfileStatisticsName::ScanFilterDecodedBytes,
+            filter_block.memory_size(),
+        );
+
+        let num_rows = filter_block.num_rows();
+        let filtered = LateMaterialized::Filtered {
+            bytes: filter_block.memory_size(),
+        };
+        let evaluator = Evaluator::new(&filter_block, &self.func_ctx, &BUILTIN_FUNCTIONS);
+        let predicate = evaluator
+            .run(&self.filter)?
+            .try_downcast::<BooleanType>()
+            .unwrap();
+        let selection = match predicate {
+            Value::Scalar(false) => return Ok(filtered),
+            Value::Scalar(true) => None,
+            Value::Column(bitmap) => {
+                let selected = num_rows - bitmap.unset_bits();
+                if selected == 0 {
+                    return Ok(filtered);
+                }
+                (selected as u64 * 100 <= num_rows as u64 * self.threshold).then_some(bitmap)
+            }
+        };
+
+        let remain_block = self.remain_reader.deserialize_parquet_chunks_with_buffer(
+            &part.location,
+            part.nums_rows,
+            &part.compression,
+            &part.columns_meta,
+            remain_chunks,
+            Some(uncompressed_buffer.clone()),
+        )?;
+        Profile::record_usize_profile(
+            ProfileStatisticsName::ScanRemainDecodedBytes,
+            remain_block.memory_size(),
+        );
+
+        let mut columns = vec![None; self.filter_positions.len() + self.remain_positions.len()];
+        for (position, entry) in self
+            .filter_positions
+            .iter()
+            .zip(filter_block.columns())
+            .chain(self.remain_positions.iter().zip(remain_block.columns()))
+        {
+            columns[*position] = Some(entry.clone());
+        }
+        let columns = columns
+            .into_iter()
+            .map(Option::unwrap)
+            .collect::<Vec<BlockEntry>>();
+        Ok(LateMaterialized::Block(
+            DataBlock::new(columns, num_rows),
+            selection,
+        ))
+    }
+}
+
 pub struct DeserializeDataTransform {
     ctx: Arc<dyn TableContext>,
     table_index: IndexType,
@@ -78,6 +285,7 @@ pub struct DeserializeDataTransform {
     cached_runtime_filter: Option<Vec<(FieldIndex, BinaryFuse16)>>,
     // for merge_into target build.
     need_reserve_block_info: bool,
+    late_materialization: Option<LateMaterialization>,
 }
 
 unsafe impl Send for DeserializeDataTransform {}
@@ -112,6 +320,12 @@ impl DeserializeDataTransform {
         output_schema.remove_internal_fields();
         let output_schema: DataSchema = (&output_schema).into();
         let (need_reserve_block_info, _) = need_reserve_block_info(ctx.clone(), plan.table_index);
+        let late_materialization = LateMaterialization::try_create(
+            ctx.clone(),
+            &block_reader,
+            plan,
+            virtual_reader.is_some(),
+        )?;
         Ok(ProcessorPtr::create(Box::new(DeserializeDataTransform {
             ctx,
             table_index: plan.table_index,
@@ -130,6 +344,7 @@ impl DeserializeDataTransform {
             base_block_ids: plan.base_block_ids.clone(),
             cached_runtime_filter: None,
             need_reserve_block_info,
+            late_materialization,
         })))
     }
 
@@ -262,27 +477,56 @@ impl Processor for DeserializeDataTransform {
                 }
                 ParquetDataSource::Normal((data, virtual_data)) => {
                     let start = Instant::now();
-                    let columns_chunks = data.columns_chunks()?;
                     let part = FusePartInfo::from_part(&part)?;
 
-                    let mut data_block = self.block_reader.deserialize_parquet_chunks_with_buffer(
-                        &part.location,
-                        part.nums_rows,
-                        &part.compression,
-                        &part.columns_meta,
-                        columns_chunks,
-                        Some(self.uncompressed_buffer.clone()),
-                    )?;
+                    let (mut data_block, mut filter) = match &self.late_materialization {
+                        Some(late_materialization) => match late_materialization.deserialize(
+                            part,
+                            &data,
+                            &self.uncompressed_buffer,
+                        )? {
+                            LateMaterialized::Block(data_block, filter) => (data_block, filter),
+                            LateMaterialized::Filtered { bytes } => {
+                                // The rows of the block are read, though none of them is output.
+                                self.scan_progress.incr(&ProgressValues {
+                                    rows: part.nums_rows,
+                                    bytes,
+                                });
+                                Profile::record_usize_profile(
+                                    ProfileStatisticsName::ScanBytes,
+                                    bytes,
+                                );
+                                return Ok(());
+                            }
+                        },
+                        None => {
+                            let data_block =
+                                self.block_reader.deserialize_parquet_chunks_with_buffer(
+                                    &part.location,
+                                    part.nums_rows,
+                                    &part.compression,
+                                    &part.columns_meta,
+                                    data.columns_chunks()?,
+                                    Some(self.uncompressed_buffer.clone()),
+                                )?;
+                            (data_block, None)
+                        }
+                    };
+                    data_block = data_block.with_origin(part.block_origin(part.first_row()));
 
                     let origin_num_rows = data_block.num_rows();
 
-                    let mut filter = None;
                     if self.ctx.has_bloom_runtime_filters(self.table_index) {
                         if let Some(bitmap) = self.runtime_filter(data_block.clone())? {
-                            data_block = data_block.filter_with_bitmap(&bitmap)?;
-                            filter = Some(bitmap);
+                            filter = Some(match filter {
+                                Some(selection) => (&selection).bitand(&bitmap),
+                                None => bitmap,
+                            });
                         }
                     }
+                    if let Some(bitmap) = &filter {
+                        data_block = data_block.filter_with_bitmap(bitmap)?;
+                    }
 
                     // Add optional virtual columns
                     if let Some(virtual_reader) = self.virtual_reader.as_ref() {
@@ -64,14 +64,12 @@ pub fn reduce_block_statistics<T: Borrow<StatisticsOfColumns>>(
 
             let min = min_stats
                 .into_iter()
-                .filter(|s| !s.is_null())
-                .min_by(|x, y| x.cmp(y))
+                .reduce(Scalar::min_coerced)
                 .unwrap_or(Scalar::Null);
 
             let max = max_stats
                 .into_iter()
-                .filter(|s| !s.is_null())
-                .max_by(|x, y| x.cmp(y))
+                .reduce(Scalar::max_coerced)
                 .unwrap_or(Scalar::Null);
 
             acc.insert(
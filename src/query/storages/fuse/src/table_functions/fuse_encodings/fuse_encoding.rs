@@ -24,7 +24,6 @@ use databend_common_catalog::table::Table;
 use databend_common_exception::Result;
 use databend_common_expression::types::nullable::NullableColumnBuilder;
 use databend_common_expression::types::string::StringColumnBuilder;
-use databend_common_expression::types::BooleanType;
 use databend_common_expression::types::DataType;
 use databend_common_expression::types::NumberDataType;
 use databend_common_expression::types::StringType;
@@ -142,15 +141,12 @@ impl<'a> FuseEncoding<'a> {
         let result = if let Some(filter) = self.filters.as_ref().map(|f| &f.filter) {
             let func_ctx = FunctionContext::default();
             let evaluator = Evaluator::new(&data_block, &func_ctx, &BUILTIN_FUNCTIONS);
-            let filter = evaluator
-                .run(&as_expr(
-                    filter,
-                    &BUILTIN_FUNCTIONS,
-                    &FuseEncoding::schema(),
-                ))?
-                .try_downcast::<BooleanType>()
-                .unwrap();
-            data_block.filter_boolean_value(&filter)?
+            let filter = evaluator.run(&as_expr(
+                filter,
+                &BUILTIN_FUNCTIONS,
+                &FuseEncoding::schema(),
+            ))?;
+            data_block.filter_block(&filter)?
         } else {
             data_block
         };
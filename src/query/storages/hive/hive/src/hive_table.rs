@@ -181,6 +181,7 @@ impl HiveTable {
     ) -> Result<()> {
         let push_downs = &plan.push_downs;
         let chunk_size = ctx.get_settings().get_hive_parquet_chunk_size()? as usize;
+        debug_assert!(chunk_size != 0);
 
         let parts_len = plan.parts.len();
         let max_threads = ctx.get_settings().get_max_threads()? as usize;
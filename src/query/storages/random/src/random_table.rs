@@ -99,6 +99,7 @@ impl Table for RandomTable {
     ) -> Result<(PartStatistics, Partitions)> {
         let settings = ctx.get_settings();
         let block_size = settings.get_max_block_size()? as usize;
+        debug_assert!(block_size != 0);
         // If extras.push_downs is None or extras.push_down.limit is None,
         // set limit to `max_block_size`.
         let (schema, total_rows) = match push_downs {
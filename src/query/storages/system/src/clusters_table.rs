@@ -46,7 +46,13 @@ impl SyncSystemTable for ClustersTable {
     }
 
     fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
-        let cluster_nodes = ctx.get_cluster().nodes.clone();
+        let cluster = ctx.get_cluster();
+        let cluster_nodes = cluster
+            .nodes
+            .iter()
+            .chain(cluster.draining_nodes.iter())
+            .cloned()
+            .collect::<Vec<_>>();
 
         let mut names = ColumnBuilder::with_capacity(&DataType::String, cluster_nodes.len());
         let mut clusters = ColumnBuilder::with_capacity(&DataType::String, cluster_nodes.len());
@@ -56,6 +62,7 @@ impl SyncSystemTable for ClustersTable {
             cluster_nodes.len(),
         );
         let mut versions = ColumnBuilder::with_capacity(&DataType::String, cluster_nodes.len());
+        let mut statuses = ColumnBuilder::with_capacity(&DataType::String, cluster_nodes.len());
 
         let cluster_id = GlobalConfig::instance().query.cluster_id.clone();
         for cluster_node in &cluster_nodes {
@@ -66,6 +73,7 @@ impl SyncSystemTable for ClustersTable {
             addresses.push(Scalar::String(ip).as_ref());
             addresses_port.push(Scalar::Number(NumberScalar::UInt16(port)).as_ref());
             versions.push(Scalar::String(cluster_node.binary_version.clone()).as_ref());
+            statuses.push(Scalar::String(cluster_node.status.to_string()).as_ref());
         }
 
         Ok(DataBlock::new_from_columns(vec![
@@ -74,6 +82,7 @@ impl SyncSystemTable for ClustersTable {
             addresses.build(),
             addresses_port.build(),
             versions.build(),
+            statuses.build(),
         ]))
     }
 }
@@ -86,6 +95,7 @@ impl ClustersTable {
             TableField::new("host", TableDataType::String),
             TableField::new("port", TableDataType::Number(NumberDataType::UInt16)),
             TableField::new("version", TableDataType::String),
+            TableField::new("status", TableDataType::String),
         ]);
 
         let table_info = TableInfo {
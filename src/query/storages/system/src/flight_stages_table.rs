@@ -0,0 +1,135 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use databend_common_catalog::table::Table;
+use databend_common_catalog::table_context::TableContext;
+use databend_common_exception::Result;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::types::TimestampType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::types::VariantType;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_expression::TableDataType;
+use databend_common_expression::TableField;
+use databend_common_expression::TableSchemaRefExt;
+use databend_common_meta_app::schema::TableIdent;
+use databend_common_meta_app::schema::TableInfo;
+use databend_common_meta_app::schema::TableMeta;
+use serde_json::json;
+
+use crate::SyncOneBlockSystemTable;
+use crate::SyncSystemTable;
+
+/// The summaries of the partial queries of the distributed queries finished on each node,
+/// with the rows and bytes they sent to the other nodes.
+pub struct FlightStagesTable {
+    table_info: TableInfo,
+}
+
+impl SyncSystemTable for FlightStagesTable {
+    const NAME: &'static str = "system.flight_stages";
+
+    const IS_LOCAL: bool = false;
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let finished_stages = ctx.get_finished_stages();
+
+        let local_id = ctx.get_cluster().local_id.clone();
+        let size = finished_stages.len();
+
+        let mut node: Vec<String> = Vec::with_capacity(size);
+        let mut query_id: Vec<String> = Vec::with_capacity(size);
+        let mut stage_id: Vec<u64> = Vec::with_capacity(size);
+        let mut status: Vec<String> = Vec::with_capacity(size);
+        let mut duration_ms: Vec<u64> = Vec::with_capacity(size);
+        let mut finished_time: Vec<i64> = Vec::with_capacity(size);
+        let mut streams: Vec<Vec<u8>> = Vec::with_capacity(size);
+
+        for stage in finished_stages {
+            node.push(local_id.clone());
+            query_id.push(stage.query_id);
+            stage_id.push(stage.stage_id as u64);
+            status.push(stage.status.to_string());
+            duration_ms.push(stage.duration.as_millis() as u64);
+            finished_time.push(
+                stage
+                    .finished_time
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_micros() as i64,
+            );
+
+            let stage_streams = stage
+                .streams
+                .iter()
+                .map(|stream| {
+                    json!({
+                        "target": stream.target,
+                        "sent_rows": stream.sent_rows,
+                        "sent_bytes": stream.sent_bytes,
+                    })
+                })
+                .collect::<Vec<_>>();
+            let stage_streams: jsonb::Value = (&json!(stage_streams)).into();
+            streams.push(stage_streams.to_vec());
+        }
+
+        Ok(DataBlock::new_from_columns(vec![
+            StringType::from_data(node),
+            StringType::from_data(query_id),
+            UInt64Type::from_data(stage_id),
+            StringType::from_data(status),
+            UInt64Type::from_data(duration_ms),
+            TimestampType::from_data(finished_time),
+            VariantType::from_data(streams),
+        ]))
+    }
+}
+
+impl FlightStagesTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = TableSchemaRefExt::create(vec![
+            TableField::new("node", TableDataType::String),
+            TableField::new("query_id", TableDataType::String),
+            TableField::new("stage_id", TableDataType::Number(NumberDataType::UInt64)),
+            TableField::new("status", TableDataType::String),
+            TableField::new("duration_ms", TableDataType::Number(NumberDataType::UInt64)),
+            TableField::new("finished_time", TableDataType::Timestamp),
+            TableField::new("streams", TableDataType::Variant),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'flight_stages'".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            name: "flight_stages".to_string(),
+            meta: TableMeta {
+                schema,
+                engine: "SystemFlightStages".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        SyncOneBlockSystemTable::create(Self { table_info })
+    }
+}
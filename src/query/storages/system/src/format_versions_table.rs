@@ -0,0 +1,94 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use databend_common_catalog::table::Table;
+use databend_common_catalog::table_context::TableContext;
+use databend_common_exception::Result;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::utils::FromData;
+use databend_common_expression::DataBlock;
+use databend_common_expression::TableDataType;
+use databend_common_expression::TableField;
+use databend_common_expression::TableSchemaRefExt;
+use databend_common_meta_app::schema::TableIdent;
+use databend_common_meta_app::schema::TableInfo;
+use databend_common_meta_app::schema::TableMeta;
+use databend_storages_common_table_meta::meta::FORMAT_VERSIONS;
+
+use crate::SyncOneBlockSystemTable;
+use crate::SyncSystemTable;
+
+/// The versions of the persisted table meta this binary reads and writes.
+///
+/// The version of a table on disk is shown by the `format_version` of `fuse_snapshot`.
+pub struct FormatVersionsTable {
+    table_info: TableInfo,
+}
+
+impl SyncSystemTable for FormatVersionsTable {
+    const NAME: &'static str = "system.format_versions";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn get_full_data(&self, _: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let kinds = FORMAT_VERSIONS.iter().map(|v| v.kind).collect::<Vec<_>>();
+        let supported = FORMAT_VERSIONS
+            .iter()
+            .map(|v| v.supported_list())
+            .collect::<Vec<_>>();
+        let latest = FORMAT_VERSIONS
+            .iter()
+            .map(|v| v.latest())
+            .collect::<Vec<_>>();
+
+        Ok(DataBlock::new_from_columns(vec![
+            StringType::from_data(kinds),
+            StringType::from_data(supported),
+            UInt64Type::from_data(latest),
+        ]))
+    }
+}
+
+impl FormatVersionsTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = TableSchemaRefExt::create(vec![
+            TableField::new("kind", TableDataType::String),
+            TableField::new("supported_versions", TableDataType::String),
+            TableField::new(
+                "latest_version",
+                TableDataType::Number(NumberDataType::UInt64),
+            ),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'format_versions'".to_string(),
+            name: "format_versions".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemFormatVersions".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        SyncOneBlockSystemTable::create(FormatVersionsTable { table_info })
+    }
+}
@@ -34,6 +34,8 @@ mod contributors_table;
 mod credits_table;
 mod databases_table;
 mod engines_table;
+mod flight_stages_table;
+mod format_versions_table;
 mod functions_table;
 mod indexes_table;
 mod locks_table;
@@ -47,6 +49,7 @@ mod processes_table;
 mod processor_profile_table;
 mod query_cache_table;
 mod query_log_table;
+mod query_profile_table;
 mod roles_table;
 mod settings_table;
 mod stages_table;
@@ -79,6 +82,8 @@ pub use contributors_table::ContributorsTable;
 pub use credits_table::CreditsTable;
 pub use databases_table::DatabasesTable;
 pub use engines_table::EnginesTable;
+pub use flight_stages_table::FlightStagesTable;
+pub use format_versions_table::FormatVersionsTable;
 pub use functions_table::FunctionsTable;
 pub use indexes_table::IndexesTable;
 pub use locks_table::LocksTable;
@@ -97,6 +102,10 @@ pub use query_log_table::LogType;
 pub use query_log_table::QueryLogElement;
 pub use query_log_table::QueryLogQueue;
 pub use query_log_table::QueryLogTable;
+pub use query_profile_table::QueryProfileEntry;
+pub use query_profile_table::QueryProfileRecord;
+pub use query_profile_table::QueryProfileStore;
+pub use query_profile_table::QueryProfileTable;
 pub use roles_table::RolesTable;
 pub use settings_table::SettingsTable;
 pub use stages_table::StagesTable;
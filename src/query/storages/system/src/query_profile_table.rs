@@ -0,0 +1,323 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use databend_common_base::base::GlobalInstance;
+use databend_common_cache::Cache;
+use databend_common_cache::LruCache;
+use databend_common_catalog::table::Table;
+use databend_common_catalog::table_context::TableContext;
+use databend_common_exception::Result;
+use databend_common_expression::types::Float64Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::types::TimestampType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::utils::FromData;
+use databend_common_expression::DataBlock;
+use databend_common_expression::TableDataType;
+use databend_common_expression::TableField;
+use databend_common_expression::TableSchemaRefExt;
+use databend_common_meta_app::schema::TableIdent;
+use databend_common_meta_app::schema::TableInfo;
+use databend_common_meta_app::schema::TableMeta;
+use databend_common_metrics::interpreter::QUERY_PROFILE_EVICTIONS;
+use parking_lot::Mutex;
+
+use crate::SyncOneBlockSystemTable;
+use crate::SyncSystemTable;
+
+/// The statistics of a finished query.
+#[derive(Clone, Debug)]
+pub struct QueryProfileRecord {
+    pub fingerprint: String,
+    pub query_text: String,
+    pub duration_ms: u64,
+    pub scan_rows: u64,
+    pub scan_bytes: u64,
+    pub is_error: bool,
+    /// The finish time in microseconds.
+    pub event_time: i64,
+}
+
+/// The aggregated statistics of the queries with the same fingerprint.
+#[derive(Clone, Debug)]
+pub struct QueryProfileEntry {
+    pub fingerprint: String,
+    /// The text of the most recent query.
+    pub example_query: String,
+    pub executions: u64,
+    pub errors: u64,
+    pub total_duration_ms: u64,
+    pub total_scan_rows: u64,
+    pub total_scan_bytes: u64,
+    pub last_seen: i64,
+    durations: DurationSketch,
+}
+
+impl QueryProfileEntry {
+    fn new(fingerprint: String) -> Self {
+        QueryProfileEntry {
+            fingerprint,
+            example_query: String::new(),
+            executions: 0,
+            errors: 0,
+            total_duration_ms: 0,
+            total_scan_rows: 0,
+            total_scan_bytes: 0,
+            last_seen: 0,
+            durations: DurationSketch::default(),
+        }
+    }
+
+    fn add(&mut self, record: QueryProfileRecord) {
+        self.example_query = record.query_text;
+        self.executions += 1;
+        self.errors += record.is_error as u64;
+        self.total_duration_ms += record.duration_ms;
+        self.total_scan_rows += record.scan_rows;
+        self.total_scan_bytes += record.scan_bytes;
+        self.last_seen = self.last_seen.max(record.event_time);
+        self.durations.add(record.duration_ms);
+    }
+
+    pub fn mean_duration_ms(&self) -> f64 {
+        if self.executions == 0 {
+            return 0.0;
+        }
+        self.total_duration_ms as f64 / self.executions as f64
+    }
+
+    pub fn p95_duration_ms(&self) -> u64 {
+        self.durations.quantile(0.95)
+    }
+}
+
+const SKETCH_GAMMA: f64 = 1.02;
+
+/// A small quantile sketch of the durations.
+///
+/// The durations are counted in buckets whose bounds grow by `SKETCH_GAMMA`, so the relative
+/// error of the quantiles is about 1%, and a sketch has about a thousand buckets at most.
+#[derive(Clone, Debug, Default)]
+struct DurationSketch {
+    count: u64,
+    // Bucket 0 is for zero, bucket `i` is for the values in `(GAMMA^(i-2), GAMMA^(i-1)]`.
+    buckets: BTreeMap<u32, u64>,
+}
+
+impl DurationSketch {
+    fn add(&mut self, value: u64) {
+        let bucket = if value == 0 {
+            0
+        } else {
+            ((value as f64).ln() / SKETCH_GAMMA.ln()).ceil() as u32 + 1
+        };
+        *self.buckets.entry(bucket).or_default() += 1;
+        self.count += 1;
+    }
+
+    fn quantile(&self, q: f64) -> u64 {
+        let rank = ((q * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bucket, count) in self.buckets.iter() {
+            seen += count;
+            if seen >= rank {
+                if *bucket == 0 {
+                    return 0;
+                }
+                let upper = SKETCH_GAMMA.powi(*bucket as i32 - 1);
+                return (2.0 * upper / (SKETCH_GAMMA + 1.0)).round() as u64;
+            }
+        }
+        0
+    }
+}
+
+struct QueryProfileState {
+    entries: LruCache<String, QueryProfileEntry>,
+    evictions: u64,
+}
+
+/// The statistics of the queries aggregated by the fingerprint on this node,
+/// the least recently seen fingerprint is evicted if there are too many fingerprints.
+pub struct QueryProfileStore {
+    max_entries: u64,
+    state: Mutex<QueryProfileState>,
+}
+
+impl QueryProfileStore {
+    pub fn init(max_entries: usize) -> Result<()> {
+        GlobalInstance::set(Self::create(max_entries));
+        Ok(())
+    }
+
+    pub fn instance() -> Arc<QueryProfileStore> {
+        GlobalInstance::get()
+    }
+
+    pub fn create(max_entries: usize) -> Arc<QueryProfileStore> {
+        let max_entries = max_entries.max(1) as u64;
+        Arc::new(QueryProfileStore {
+            max_entries,
+            state: Mutex::new(QueryProfileState {
+                entries: LruCache::new(max_entries),
+                evictions: 0,
+            }),
+        })
+    }
+
+    pub fn record(&self, record: QueryProfileRecord) {
+        let mut state = self.state.lock();
+        let mut entry = match state.entries.pop(&record.fingerprint) {
+            Some(entry) => entry,
+            None => {
+                if state.entries.len() as u64 >= self.max_entries {
+                    state.entries.pop_by_policy();
+                    state.evictions += 1;
+                    QUERY_PROFILE_EVICTIONS.inc();
+                }
+                QueryProfileEntry::new(record.fingerprint.clone())
+            }
+        };
+        entry.add(record);
+        state.entries.put(entry.fingerprint.clone(), entry);
+    }
+
+    pub fn entries(&self) -> Vec<QueryProfileEntry> {
+        let state = self.state.lock();
+        state
+            .entries
+            .iter()
+            .map(|(_, entry)| entry.clone())
+            .collect()
+    }
+
+    /// The number of the fingerprints evicted since the node started.
+    pub fn evictions(&self) -> u64 {
+        self.state.lock().evictions
+    }
+
+    pub fn reset(&self) {
+        self.state.lock().entries.clear();
+    }
+}
+
+pub struct QueryProfileTable {
+    table_info: TableInfo,
+}
+
+impl SyncSystemTable for QueryProfileTable {
+    const NAME: &'static str = "system.query_profile";
+
+    // Allow distributed query.
+    const IS_LOCAL: bool = false;
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let entries = QueryProfileStore::instance().entries();
+
+        let local_node = ctx.get_cluster().local_id.clone();
+        let mut nodes = Vec::with_capacity(entries.len());
+        let mut fingerprints = Vec::with_capacity(entries.len());
+        let mut example_queries = Vec::with_capacity(entries.len());
+        let mut executions = Vec::with_capacity(entries.len());
+        let mut errors = Vec::with_capacity(entries.len());
+        let mut total_durations = Vec::with_capacity(entries.len());
+        let mut mean_durations = Vec::with_capacity(entries.len());
+        let mut p95_durations = Vec::with_capacity(entries.len());
+        let mut total_scan_rows = Vec::with_capacity(entries.len());
+        let mut total_scan_bytes = Vec::with_capacity(entries.len());
+        let mut last_seen = Vec::with_capacity(entries.len());
+        for entry in entries {
+            nodes.push(local_node.clone());
+            mean_durations.push(entry.mean_duration_ms());
+            p95_durations.push(entry.p95_duration_ms());
+            fingerprints.push(entry.fingerprint);
+            example_queries.push(entry.example_query);
+            executions.push(entry.executions);
+            errors.push(entry.errors);
+            total_durations.push(entry.total_duration_ms);
+            total_scan_rows.push(entry.total_scan_rows);
+            total_scan_bytes.push(entry.total_scan_bytes);
+            last_seen.push(entry.last_seen);
+        }
+
+        Ok(DataBlock::new_from_columns(vec![
+            StringType::from_data(nodes),
+            StringType::from_data(fingerprints),
+            StringType::from_data(example_queries),
+            UInt64Type::from_data(executions),
+            UInt64Type::from_data(errors),
+            UInt64Type::from_data(total_durations),
+            Float64Type::from_data(mean_durations),
+            UInt64Type::from_data(p95_durations),
+            UInt64Type::from_data(total_scan_rows),
+            UInt64Type::from_data(total_scan_bytes),
+            TimestampType::from_data(last_seen),
+        ]))
+    }
+}
+
+impl QueryProfileTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = TableSchemaRefExt::create(vec![
+            TableField::new("node", TableDataType::String),
+            TableField::new("fingerprint", TableDataType::String),
+            TableField::new("example_query", TableDataType::String),
+            TableField::new("executions", TableDataType::Number(NumberDataType::UInt64)),
+            TableField::new("errors", TableDataType::Number(NumberDataType::UInt64)),
+            TableField::new(
+                "total_duration_ms",
+                TableDataType::Number(NumberDataType::UInt64),
+            ),
+            TableField::new(
+                "mean_duration_ms",
+                TableDataType::Number(NumberDataType::Float64),
+            ),
+            TableField::new(
+                "p95_duration_ms",
+                TableDataType::Number(NumberDataType::UInt64),
+            ),
+            TableField::new(
+                "total_scan_rows",
+                TableDataType::Number(NumberDataType::UInt64),
+            ),
+            TableField::new(
+                "total_scan_bytes",
+                TableDataType::Number(NumberDataType::UInt64),
+            ),
+            TableField::new("last_seen", TableDataType::Timestamp),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'query_profile'".to_string(),
+            name: "query_profile".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemQueryProfile".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        SyncOneBlockSystemTable::create(Self { table_info })
+    }
+}
@@ -279,7 +279,10 @@ impl<'a, R: Rng + 'a> SqlGenerator<'a, R> {
                 };
                 hints_list.push(hint);
             }
-            Some(Hint { hints_list })
+            Some(Hint {
+                hints_list,
+                optimizer_hints: vec![],
+            })
         } else {
             None
         }